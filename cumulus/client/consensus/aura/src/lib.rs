@@ -165,6 +165,7 @@ where
 				force_authoring,
 				backoff_authoring_blocks,
 				keystore,
+				slashing_protection: None,
 				telemetry,
 				block_proposal_slot_portion,
 				max_block_proposal_slot_portion,
@@ -64,7 +64,7 @@ use frame_support::{
 	traits::{
 		fungible, fungible::HoldConsideration, fungibles, tokens::imbalance::ResolveAssetTo,
 		AsEnsureOriginWithArg, ConstBool, ConstU128, ConstU32, ConstU64, ConstU8,
-		ConstantStoragePrice, EitherOfDiverse, Equals, InstanceFilter, TransformOrigin,
+		ConstantStoragePrice, Contains, EitherOfDiverse, Equals, InstanceFilter, TransformOrigin,
 	},
 	weights::{ConstantMultiplier, Weight},
 	BoundedVec, PalletId,
@@ -705,6 +705,22 @@ impl InstanceFilter<RuntimeCall> for ProxyType {
 	}
 }
 
+/// The calls which move no funds of the account they are dispatched for, and which a proxy may
+/// dispatch regardless of its spending allowance.
+pub struct SpendsNothing;
+impl Contains<RuntimeCall> for SpendsNothing {
+	fn contains(call: &RuntimeCall) -> bool {
+		matches!(
+			call,
+			RuntimeCall::System(
+				frame_system::Call::remark { .. } | frame_system::Call::remark_with_event { .. }
+			) | RuntimeCall::Session(..) |
+				RuntimeCall::Multisig(..) |
+				RuntimeCall::Proxy(..)
+		)
+	}
+}
+
 impl pallet_proxy::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type RuntimeCall = RuntimeCall;
@@ -719,6 +735,7 @@ impl pallet_proxy::Config for Runtime {
 	type AnnouncementDepositBase = AnnouncementDepositBase;
 	type AnnouncementDepositFactor = AnnouncementDepositFactor;
 	type BlockNumberProvider = frame_system::Pallet<Runtime>;
+	type CallSpending = polkadot_runtime_common::impls::TransferSpending<Runtime, SpendsNothing>;
 }
 
 parameter_types! {
@@ -951,6 +968,7 @@ impl pallet_nft_fractionalization::Config for Runtime {
 parameter_types! {
 	pub NftsPalletFeatures: PalletFeatures = PalletFeatures::all_enabled();
 	pub const NftsMaxDeadlineDuration: BlockNumber = 12 * 30 * DAYS;
+	pub const NftsMaxRoyalty: Permill = Permill::from_percent(25);
 	// re-use the Uniques deposits
 	pub const NftsCollectionDeposit: Balance = UniquesCollectionDeposit::get();
 	pub const NftsItemDeposit: Balance = UniquesItemDeposit::get();
@@ -980,6 +998,8 @@ impl pallet_nfts::Config for Runtime {
 	type MaxTips = ConstU32<10>;
 	type MaxDeadlineDuration = NftsMaxDeadlineDuration;
 	type MaxAttributesPerCall = ConstU32<10>;
+	type MaxRoyaltyRecipients = ConstU32<5>;
+	type MaxRoyalty = NftsMaxRoyalty;
 	type Features = NftsPalletFeatures;
 	type OffchainSignature = Signature;
 	type OffchainPublic = <Signature as Verify>::Signer;
@@ -1152,6 +1172,8 @@ pub type UncheckedExtrinsic =
 pub type Migrations = (
 	InitStorageVersions,
 	// unreleased
+	pallet_nfts::migration::v2::MigrateToV2<Runtime>,
+	// unreleased
 	cumulus_pallet_xcmp_queue::migration::v4::MigrationToV4<Runtime>,
 	cumulus_pallet_xcmp_queue::migration::v5::MigrateV4ToV5<Runtime>,
 	pallet_collator_selection::migration::v2::MigrationToV2<Runtime>,
@@ -1208,8 +1230,9 @@ impl frame_support::traits::OnRuntimeUpgrade for InitStorageVersions {
 			writes.saturating_inc();
 		}
 
+		// The collections are migrated to v2 by `pallet_nfts::migration::v2::MigrateToV2`.
 		if Nfts::on_chain_storage_version() == StorageVersion::new(0) {
-			Nfts::in_code_storage_version().put::<Nfts>();
+			StorageVersion::new(1).put::<Nfts>();
 			writes.saturating_inc();
 		}
 
@@ -193,4 +193,8 @@ impl<T: frame_system::Config> pallet_asset_conversion::WeightInfo for WeightInfo
 			.saturating_add(T::DbWeight::get().reads(6))
 			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(n.into())))
 	}
+	/// Not benchmarked for this runtime yet, falls back to the weight of the pallet.
+	fn stable_swap_curve() -> Weight {
+		<pallet_asset_conversion::weights::SubstrateWeight<T> as pallet_asset_conversion::WeightInfo>::stable_swap_curve()
+	}
 }
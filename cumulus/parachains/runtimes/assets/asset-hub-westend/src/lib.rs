@@ -51,7 +51,7 @@ use frame_support::{
 		fungibles,
 		tokens::{imbalance::ResolveAssetTo, nonfungibles_v2::Inspect},
 		AsEnsureOriginWithArg, ConstBool, ConstU128, ConstU32, ConstU64, ConstU8,
-		ConstantStoragePrice, Equals, InstanceFilter, Nothing, TransformOrigin,
+		ConstantStoragePrice, Contains, Equals, InstanceFilter, Nothing, TransformOrigin,
 	},
 	weights::{ConstantMultiplier, Weight},
 	BoundedVec, PalletId,
@@ -758,6 +758,22 @@ impl InstanceFilter<RuntimeCall> for ProxyType {
 	}
 }
 
+/// The calls which move no funds of the account they are dispatched for, and which a proxy may
+/// dispatch regardless of its spending allowance.
+pub struct SpendsNothing;
+impl Contains<RuntimeCall> for SpendsNothing {
+	fn contains(call: &RuntimeCall) -> bool {
+		matches!(
+			call,
+			RuntimeCall::System(
+				frame_system::Call::remark { .. } | frame_system::Call::remark_with_event { .. }
+			) | RuntimeCall::Session(..) |
+				RuntimeCall::Multisig(..) |
+				RuntimeCall::Proxy(..)
+		)
+	}
+}
+
 impl pallet_proxy::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type RuntimeCall = RuntimeCall;
@@ -772,6 +788,7 @@ impl pallet_proxy::Config for Runtime {
 	type AnnouncementDepositBase = AnnouncementDepositBase;
 	type AnnouncementDepositFactor = AnnouncementDepositFactor;
 	type BlockNumberProvider = frame_system::Pallet<Runtime>;
+	type CallSpending = polkadot_runtime_common::impls::TransferSpending<Runtime, SpendsNothing>;
 }
 
 parameter_types! {
@@ -999,6 +1016,7 @@ impl pallet_nft_fractionalization::Config for Runtime {
 parameter_types! {
 	pub NftsPalletFeatures: PalletFeatures = PalletFeatures::all_enabled();
 	pub const NftsMaxDeadlineDuration: BlockNumber = 12 * 30 * DAYS;
+	pub const NftsMaxRoyalty: Permill = Permill::from_percent(25);
 	// re-use the Uniques deposits
 	pub const NftsCollectionDeposit: Balance = UniquesCollectionDeposit::get();
 	pub const NftsItemDeposit: Balance = UniquesItemDeposit::get();
@@ -1028,6 +1046,8 @@ impl pallet_nfts::Config for Runtime {
 	type MaxTips = ConstU32<10>;
 	type MaxDeadlineDuration = NftsMaxDeadlineDuration;
 	type MaxAttributesPerCall = ConstU32<10>;
+	type MaxRoyaltyRecipients = ConstU32<5>;
+	type MaxRoyalty = NftsMaxRoyalty;
 	type Features = NftsPalletFeatures;
 	type OffchainSignature = Signature;
 	type OffchainPublic = <Signature as Verify>::Signer;
@@ -1254,6 +1274,8 @@ pub type Migrations = (
 	// unreleased
 	InitStorageVersions,
 	// unreleased
+	pallet_nfts::migration::v2::MigrateToV2<Runtime>,
+	// unreleased
 	DeleteUndecodableStorage,
 	// unreleased
 	cumulus_pallet_xcmp_queue::migration::v4::MigrationToV4<Runtime>,
@@ -1596,6 +1618,7 @@ impl_runtime_apis! {
 		}
 	}
 
+	#[api_version(2)]
 	impl pallet_nfts_runtime_api::NftsApi<Block, AccountId, u32, u32> for Runtime {
 		fn owner(collection: u32, item: u32) -> Option<AccountId> {
 			<Nfts as Inspect<AccountId>>::owner(&collection, &item)
@@ -1638,6 +1661,14 @@ impl_runtime_apis! {
 		fn collection_attribute(collection: u32, key: Vec<u8>) -> Option<Vec<u8>> {
 			<Nfts as Inspect<AccountId>>::collection_attribute(&collection, &key)
 		}
+
+		fn royalty(collection: u32, item: u32) -> Vec<(AccountId, sp_runtime::Permill)> {
+			Nfts::royalty(&collection, &item)
+				.map(|recipients| {
+					recipients.into_iter().map(|r| (r.account, r.percentage)).collect()
+				})
+				.unwrap_or_default()
+		}
 	}
 
 	impl pallet_asset_conversion::AssetConversionApi<
@@ -74,8 +74,8 @@ use frame_support::{
 	genesis_builder_helper::{build_state, get_preset},
 	parameter_types,
 	traits::{
-		fungible::HoldConsideration, ConstBool, ConstU32, ConstU64, ConstU8, EitherOfDiverse,
-		InstanceFilter, LinearStoragePrice, TransformOrigin,
+		fungible::HoldConsideration, ConstBool, ConstU32, ConstU64, ConstU8, Contains,
+		EitherOfDiverse, InstanceFilter, LinearStoragePrice, TransformOrigin,
 	},
 	weights::{ConstantMultiplier, Weight},
 	PalletId,
@@ -375,6 +375,22 @@ impl InstanceFilter<RuntimeCall> for ProxyType {
 	}
 }
 
+/// The calls which move no funds of the account they are dispatched for, and which a proxy may
+/// dispatch regardless of its spending allowance.
+pub struct SpendsNothing;
+impl Contains<RuntimeCall> for SpendsNothing {
+	fn contains(call: &RuntimeCall) -> bool {
+		matches!(
+			call,
+			RuntimeCall::System(
+				frame_system::Call::remark { .. } | frame_system::Call::remark_with_event { .. }
+			) | RuntimeCall::Session(..) |
+				RuntimeCall::Multisig(..) |
+				RuntimeCall::Proxy(..)
+		)
+	}
+}
+
 impl pallet_proxy::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type RuntimeCall = RuntimeCall;
@@ -389,6 +405,7 @@ impl pallet_proxy::Config for Runtime {
 	type AnnouncementDepositBase = AnnouncementDepositBase;
 	type AnnouncementDepositFactor = AnnouncementDepositFactor;
 	type BlockNumberProvider = frame_system::Pallet<Runtime>;
+	type CallSpending = polkadot_runtime_common::impls::TransferSpending<Runtime, SpendsNothing>;
 }
 
 parameter_types! {
@@ -631,6 +648,8 @@ impl pallet_scheduler::Config for Runtime {
 	type OriginPrivilegeCmp = EqualOrGreatestRootCmp;
 	type Preimages = Preimage;
 	type BlockNumberProvider = frame_system::Pallet<Runtime>;
+	type Time = Timestamp;
+	type MaxRecurring = ConstU32<50>;
 }
 
 parameter_types! {
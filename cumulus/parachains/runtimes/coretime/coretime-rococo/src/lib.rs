@@ -46,7 +46,8 @@ use frame_support::{
 	genesis_builder_helper::{build_state, get_preset},
 	parameter_types,
 	traits::{
-		ConstBool, ConstU32, ConstU64, ConstU8, EitherOfDiverse, InstanceFilter, TransformOrigin,
+		ConstBool, ConstU32, ConstU64, ConstU8, Contains, EitherOfDiverse, InstanceFilter,
+		TransformOrigin,
 	},
 	weights::{ConstantMultiplier, Weight},
 	PalletId,
@@ -580,6 +581,22 @@ parameter_types! {
 	pub const MaxPending: u16 = 32;
 }
 
+/// The calls which move no funds of the account they are dispatched for, and which a proxy may
+/// dispatch regardless of its spending allowance.
+pub struct SpendsNothing;
+impl Contains<RuntimeCall> for SpendsNothing {
+	fn contains(call: &RuntimeCall) -> bool {
+		matches!(
+			call,
+			RuntimeCall::System(
+				frame_system::Call::remark { .. } | frame_system::Call::remark_with_event { .. }
+			) | RuntimeCall::Session(..) |
+				RuntimeCall::Multisig(..) |
+				RuntimeCall::Proxy(..)
+		)
+	}
+}
+
 impl pallet_proxy::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type RuntimeCall = RuntimeCall;
@@ -594,6 +611,7 @@ impl pallet_proxy::Config for Runtime {
 	type AnnouncementDepositBase = AnnouncementDepositBase;
 	type AnnouncementDepositFactor = AnnouncementDepositFactor;
 	type BlockNumberProvider = frame_system::Pallet<Runtime>;
+	type CallSpending = polkadot_runtime_common::impls::TransferSpending<Runtime, SpendsNothing>;
 }
 
 impl pallet_utility::Config for Runtime {
@@ -324,6 +324,11 @@ pub(crate) trait NodeSpec: BaseNodeSpec {
 						network_provider: Arc::new(network.clone()),
 						is_validator: parachain_config.role.is_authority(),
 						enable_http_requests: true,
+						http_request_policy: parachain_config
+							.offchain_worker
+							.http_request_policy
+							.clone(),
+						prometheus_registry: prometheus_registry.clone(),
 						custom_extensions: move |_| vec![],
 					})?;
 				task_manager.spawn_handle().spawn(
@@ -133,6 +133,8 @@ impl<NodeSpec: NodeSpecT> ManualSealNode<NodeSpec> {
 					network_provider: Arc::new(network.clone()),
 					is_validator: config.role.is_authority(),
 					enable_http_requests: true,
+					http_request_policy: config.offchain_worker.http_request_policy.clone(),
+					prometheus_registry: config.prometheus_registry().cloned(),
 					custom_extensions: move |_| vec![],
 				})?;
 			task_manager.spawn_handle().spawn(
@@ -242,6 +244,7 @@ impl<NodeSpec: NodeSpecT> ManualSealNode<NodeSpec> {
 					))
 				}
 			},
+			revert_backend: Some(backend.clone()),
 		};
 		let authorship_future = sc_consensus_manual_seal::run_manual_seal(params);
 		task_manager.spawn_essential_handle().spawn_blocking(
@@ -904,7 +904,11 @@ pub fn node_config(
 		},
 		prometheus_config: None,
 		telemetry_endpoints: None,
-		offchain_worker: OffchainWorkerConfig { enabled: true, indexing_enabled: false },
+		offchain_worker: OffchainWorkerConfig {
+			enabled: true,
+			indexing_enabled: false,
+			http_request_policy: Default::default(),
+		},
 		force_authoring: false,
 		disable_grandpa: false,
 		dev_key_seed: Some(key_seed),
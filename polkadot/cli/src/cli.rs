@@ -166,6 +166,15 @@ pub struct RunCmd {
 	/// networks.
 	#[arg(long)]
 	pub keep_finalized_for: Option<u32>,
+
+	/// Path to a JSON file listing the rules restricting the GRANDPA votes.
+	///
+	/// The rules replace the default ones, and are applied in order. The `at_most_ahead_of` rule
+	/// supports the `beefy` and `parachain_approvals` reference blocks, i.e. the best BEEFY block
+	/// and the highest block approved by parachain approval checking. Rules based on `beefy` must
+	/// allow at least 8 blocks, the minimum distance between two BEEFY votes.
+	#[arg(long, value_name = "PATH")]
+	pub grandpa_voting_rules: Option<PathBuf>,
 }
 
 #[allow(missing_docs)]
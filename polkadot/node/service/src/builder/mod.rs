@@ -59,6 +59,9 @@ use sp_consensus_beefy::ecdsa_crypto;
 use sp_runtime::traits::Block as BlockT;
 use std::{collections::HashMap, sync::Arc, time::Duration};
 
+/// Minimum number of blocks between two BEEFY votes.
+const BEEFY_MIN_BLOCK_DELTA: u32 = 8;
+
 /// Polkadot node service initialization parameters.
 pub struct NewFullParams<OverseerGenerator: OverseerGen> {
 	pub is_parachain_node: IsParachainNode,
@@ -92,6 +95,9 @@ pub struct NewFullParams<OverseerGenerator: OverseerGen> {
 	pub hwbench: Option<sc_sysinfo::HwBench>,
 	/// Enable approval voting processing in parallel.
 	pub enable_approval_voting_parallel: bool,
+	/// An optional path to a JSON file listing the rules restricting the GRANDPA votes, replacing
+	/// the default ones.
+	pub grandpa_voting_rules: Option<std::path::PathBuf>,
 }
 
 /// Completely built polkadot node service.
@@ -203,6 +209,7 @@ where
 					prepare_workers_hard_max_num,
 					keep_finalized_for,
 					enable_approval_voting_parallel,
+					grandpa_voting_rules,
 				},
 			overseer_connector,
 			partial_components:
@@ -246,7 +253,7 @@ where
 
 			Some(backoff)
 		};
-		let shared_voter_state = rpc_setup;
+		let (shared_voter_state, beefy_best_block_stream) = rpc_setup;
 		let auth_disc_publish_non_global_ips = config.network.allow_non_globals_in_dht;
 		let auth_disc_public_addresses = config.network.public_addresses.clone();
 
@@ -431,7 +438,9 @@ where
 			};
 
 			Some(ExtendedOverseerGenArgs {
-				keystore: keystore_container.local_keystore(),
+				keystore: keystore_container
+					.local_keystore()
+					.ok_or(Error::RemoteSignerUnsupported)?,
 				parachains_db,
 				candidate_validation_config,
 				availability_config,
@@ -478,6 +487,8 @@ where
 					network_provider: Arc::new(network.clone()),
 					is_validator: role.is_authority(),
 					enable_http_requests: false,
+					http_request_policy: config.offchain_worker.http_request_policy.clone(),
+					prometheus_registry: prometheus_registry.clone(),
 					custom_extensions: move |_| vec![],
 				})?
 				.run(client.clone(), task_manager.spawn_handle())
@@ -674,6 +685,7 @@ where
 			let slot_duration = babe_link.config().slot_duration();
 			let babe_config = sc_consensus_babe::BabeParams {
 				keystore: keystore_container.keystore(),
+				slashing_protection: Some(keystore_container.slashing_protection()),
 				client: client.clone(),
 				select_chain,
 				block_import,
@@ -719,6 +731,8 @@ where
 		// need a keystore, regardless of which protocol we use below.
 		let keystore_opt =
 			if role.is_authority() { Some(keystore_container.keystore()) } else { None };
+		let slashing_protection =
+			if role.is_authority() { Some(keystore_container.slashing_protection()) } else { None };
 
 		// beefy is enabled if its notification service exists
 		if let Some(notification_service) = beefy_notification_service {
@@ -739,8 +753,9 @@ where
 				payload_provider,
 				runtime: client.clone(),
 				key_store: keystore_opt.clone(),
+				slashing_protection: slashing_protection.clone(),
 				network_params,
-				min_block_delta: 8,
+				min_block_delta: BEEFY_MIN_BLOCK_DELTA,
 				prometheus_registry: prometheus_registry.clone(),
 				links: beefy_links,
 				on_demand_justifications_handler: beefy_on_demand_justifications_handler,
@@ -786,6 +801,7 @@ where
 			name: Some(name),
 			observer_enabled: false,
 			keystore: keystore_opt,
+			slashing_protection,
 			local_role: role,
 			telemetry: telemetry.as_ref().map(|x| x.handle()),
 			protocol_name: grandpa_protocol_name,
@@ -800,7 +816,48 @@ where
 			// provide better guarantees of block and vote data availability than
 			// the observer.
 
-			let mut voting_rules_builder = sc_consensus_grandpa::VotingRulesBuilder::default();
+			let mut voting_rules_builder = match grandpa_voting_rules {
+				Some(path) => {
+					let rules = sc_consensus_grandpa::VotingRuleConfig::load(&path)?;
+					let beefy_best_block = sc_consensus_grandpa::SharedReferenceBlock::new();
+					let mut beefy_best_blocks = beefy_best_block_stream.subscribe(100_000);
+					let tracked_best_block = beefy_best_block.clone();
+					let tracker_client = client.clone();
+					task_manager.spawn_handle().spawn(
+						"beefy-best-block-tracker",
+						None,
+						async move {
+							use futures::StreamExt;
+							use sp_blockchain::HeaderBackend;
+
+							while let Some(hash) = beefy_best_blocks.next().await {
+								if let Ok(Some(number)) = tracker_client.number(hash) {
+									tracked_best_block.note(number);
+								}
+							}
+						},
+					);
+
+					let mut context = sc_consensus_grandpa::VotingRuleContext::default()
+						.with_reference_block("beefy", Arc::new(beefy_best_block))
+						.with_min_blocks_ahead("beefy", BEEFY_MIN_BLOCK_DELTA)
+						.with_session_boundaries(sc_consensus_babe::is_epoch_change::<Block>);
+					if let Some(overseer_handle) = overseer_handle.clone() {
+						context = context.with_reference_block(
+							"parachain_approvals",
+							Arc::new(grandpa_support::ApprovedAncestor::new(
+								overseer_handle,
+								enable_approval_voting_parallel,
+							)),
+						);
+					}
+
+					sc_consensus_grandpa::VotingRulesBuilder::new()
+						.add_from_config(&rules, &context)?
+				},
+				None => sc_consensus_grandpa::VotingRulesBuilder::default(),
+			}
+			.telemetry(telemetry.as_ref().map(|x| x.handle()));
 
 			#[cfg(not(feature = "malus"))]
 			let _malus_finality_delay = None;
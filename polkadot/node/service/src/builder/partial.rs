@@ -69,7 +69,10 @@ pub(crate) type PolkadotPartialComponents<ChainSelection> = sc_service::PartialC
 			sc_consensus_babe::BabeLink<Block>,
 			sc_consensus_beefy::BeefyVoterLinks<Block, ecdsa_crypto::AuthorityId>,
 		),
-		sc_consensus_grandpa::SharedVoterState,
+		(
+			sc_consensus_grandpa::SharedVoterState,
+			sc_consensus_beefy::communication::notification::BeefyBestBlockStream<Block>,
+		),
 		sp_consensus_babe::SlotDuration,
 		Option<Telemetry>,
 	),
@@ -221,7 +224,8 @@ where
 	);
 
 	let import_setup = (block_import, grandpa_link, babe_link, beefy_voter_links);
-	let rpc_setup = shared_voter_state.clone();
+	let rpc_setup =
+		(shared_voter_state.clone(), beefy_rpc_links.from_voter_best_beefy_stream.clone());
 
 	let rpc_extensions_builder = {
 		let client = client.clone();
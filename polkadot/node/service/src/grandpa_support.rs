@@ -57,6 +57,72 @@ where
 	}
 }
 
+/// The highest block approved by parachain approval checking, used by the GRANDPA
+/// voting rules as the `parachain_approvals` reference block.
+#[cfg(feature = "full-node")]
+pub(crate) struct ApprovedAncestor {
+	overseer: polkadot_overseer::Handle,
+	approval_voting_parallel_enabled: bool,
+}
+
+#[cfg(feature = "full-node")]
+impl ApprovedAncestor {
+	pub(crate) fn new(
+		overseer: polkadot_overseer::Handle,
+		approval_voting_parallel_enabled: bool,
+	) -> Self {
+		ApprovedAncestor { overseer, approval_voting_parallel_enabled }
+	}
+}
+
+#[cfg(feature = "full-node")]
+impl sc_consensus_grandpa::ReferenceBlock<Block> for ApprovedAncestor {
+	fn reference_block(
+		&self,
+		base: &polkadot_primitives::Header,
+		current_target: &polkadot_primitives::Header,
+	) -> sc_consensus_grandpa::ReferenceBlockResult<Block> {
+		use futures::channel::oneshot;
+		use polkadot_node_subsystem::messages::{
+			ApprovalVotingMessage, ApprovalVotingParallelMessage, HighestApprovedAncestorBlock,
+		};
+
+		let mut overseer = self.overseer.clone();
+		let approval_voting_parallel_enabled = self.approval_voting_parallel_enabled;
+		let target_hash = current_target.hash();
+		let base_number = base.number;
+
+		Box::pin(async move {
+			let (tx, rx) = oneshot::channel();
+			if approval_voting_parallel_enabled {
+				overseer
+					.send_msg(
+						ApprovalVotingParallelMessage::ApprovedAncestor(
+							target_hash,
+							base_number,
+							tx,
+						),
+						std::any::type_name::<Self>(),
+					)
+					.await;
+			} else {
+				overseer
+					.send_msg(
+						ApprovalVotingMessage::ApprovedAncestor(target_hash, base_number, tx),
+						std::any::type_name::<Self>(),
+					)
+					.await;
+			}
+
+			match rx.await.ok()? {
+				Some(HighestApprovedAncestorBlock { number, .. }) => Some(number),
+				// nothing past the base is approved yet
+				None => Some(base_number),
+			}
+		})
+	}
+}
+
 /// GRANDPA hard forks due to borked migration of session keys after a runtime
 /// upgrade (at #1491596), the signaled authority set changes were invalid
 /// (blank keys) and were impossible to finalize. The authorities for these
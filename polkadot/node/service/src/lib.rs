@@ -209,6 +209,10 @@ pub enum Error {
 	#[error(transparent)]
 	Availability(#[from] AvailabilityError),
 
+	#[cfg(feature = "full-node")]
+	#[error(transparent)]
+	VotingRules(#[from] sc_consensus_grandpa::VotingRuleConfigError),
+
 	#[error("Authorities require the real overseer implementation")]
 	AuthoritiesRequireRealOverseer,
 
@@ -220,6 +224,10 @@ pub enum Error {
 	#[error("Expected at least one of polkadot, kusama, westend or rococo runtime feature")]
 	NoRuntime,
 
+	#[cfg(feature = "full-node")]
+	#[error("The parachain subsystems need direct access to the keys and can't be used with a remote signer")]
+	RemoteSignerUnsupported,
+
 	#[cfg(feature = "full-node")]
 	#[error("Worker binaries not executable, prepare binary: {prep_worker_path:?}, execute binary: {exec_worker_path:?}")]
 	InvalidWorkerBinaries { prep_worker_path: PathBuf, exec_worker_path: PathBuf },
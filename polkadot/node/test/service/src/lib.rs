@@ -106,6 +106,7 @@ pub fn new_full<OverseerGenerator: OverseerGen>(
 					prepare_workers_hard_max_num: None,
 					prepare_workers_soft_max_num: None,
 					enable_approval_voting_parallel: false,
+					grandpa_voting_rules: None,
 					keep_finalized_for: None,
 				},
 			),
@@ -129,6 +130,7 @@ pub fn new_full<OverseerGenerator: OverseerGen>(
 					prepare_workers_hard_max_num: None,
 					prepare_workers_soft_max_num: None,
 					enable_approval_voting_parallel: false,
+					grandpa_voting_rules: None,
 					keep_finalized_for: None,
 				},
 			),
@@ -110,6 +110,7 @@ fn main() -> Result<()> {
 						prepare_workers_hard_max_num: None,
 						prepare_workers_soft_max_num: None,
 						enable_approval_voting_parallel: false,
+						grandpa_voting_rules: None,
 						keep_finalized_for: None,
 					},
 				)
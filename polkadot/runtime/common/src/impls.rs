@@ -20,11 +20,14 @@ use codec::{Decode, DecodeWithMemTracking, Encode, MaxEncodedLen};
 use frame_support::traits::{
 	fungible::{Balanced, Credit},
 	tokens::imbalance::ResolveTo,
-	Contains, ContainsPair, Imbalance, OnUnbalanced,
+	Contains, ContainsPair, Imbalance, IsSubType, IsType, OnUnbalanced,
 };
 use pallet_treasury::TreasuryAccountId;
 use polkadot_primitives::Balance;
-use sp_runtime::{traits::TryConvert, Perquintill, RuntimeDebug};
+use sp_runtime::{
+	traits::{Bounded, Saturating, TryConvert, Zero},
+	Perquintill, RuntimeDebug,
+};
 use xcm::VersionedLocation;
 
 /// Logic for the author to get a portion of fees.
@@ -239,6 +242,57 @@ where
 	}
 }
 
+/// Measures the native currency moved out of the proxied account by a call dispatched through a
+/// proxy, which is counted against the spending allowance of the proxy.
+///
+/// The transfers of `pallet_balances` are valued at the amount they move and the calls nested in
+/// `pallet_utility` batches are measured recursively. Calls matched by `SpendsNothing` are known
+/// to move no funds of the proxied account. Any other call is valued at `Balance::max_value()`
+/// and exceeds every allowance, which covers the calls moving funds in other ways, e.g. the
+/// transfers of other assets which can't be valued in the native currency.
+pub struct TransferSpending<R, SpendsNothing>(core::marker::PhantomData<(R, SpendsNothing)>);
+impl<R, SpendsNothing>
+	pallet_proxy::CallSpending<R::AccountId, <R as frame_system::Config>::RuntimeCall, R::Balance>
+	for TransferSpending<R, SpendsNothing>
+where
+	R: pallet_balances::Config + pallet_utility::Config,
+	<R as frame_system::Config>::RuntimeCall:
+		IsSubType<pallet_balances::Call<R>> + IsSubType<pallet_utility::Call<R>>,
+	SpendsNothing: Contains<<R as frame_system::Config>::RuntimeCall>,
+{
+	fn spending(who: &R::AccountId, call: &<R as frame_system::Config>::RuntimeCall) -> R::Balance {
+		use pallet_balances::Call as BalancesCall;
+		use pallet_utility::Call as UtilityCall;
+
+		if SpendsNothing::contains(call) {
+			return Zero::zero()
+		}
+		let nested = |call: &<R as pallet_utility::Config>::RuntimeCall| {
+			Self::spending(who, call.into_ref())
+		};
+		if let Some(call) = IsSubType::<BalancesCall<R>>::is_sub_type(call) {
+			return match call {
+				BalancesCall::transfer_allow_death { value, .. } |
+				BalancesCall::transfer_keep_alive { value, .. } |
+				BalancesCall::burn { value, .. } => *value,
+				BalancesCall::transfer_all { .. } =>
+					pallet_balances::Pallet::<R>::free_balance(who),
+				_ => Bounded::max_value(),
+			}
+		}
+		match IsSubType::<UtilityCall<R>>::is_sub_type(call) {
+			Some(
+				UtilityCall::batch { calls } |
+				UtilityCall::batch_all { calls } |
+				UtilityCall::force_batch { calls },
+			) => calls.iter().map(nested).fold(Zero::zero(), R::Balance::saturating_add),
+			Some(UtilityCall::if_else { main, fallback }) => nested(main).max(nested(fallback)),
+			Some(UtilityCall::with_weight { call, .. }) => nested(call),
+			_ => Bounded::max_value(),
+		}
+	}
+}
+
 #[cfg(feature = "runtime-benchmarks")]
 pub mod benchmarks {
 	use super::VersionedLocatableAsset;
@@ -323,6 +377,7 @@ mod tests {
 			Authorship: pallet_authorship,
 			Balances: pallet_balances,
 			Treasury: pallet_treasury,
+			Utility: pallet_utility,
 		}
 	);
 
@@ -400,6 +455,13 @@ mod tests {
 		type BenchmarkHelper = ();
 	}
 
+	impl pallet_utility::Config for Test {
+		type RuntimeEvent = RuntimeEvent;
+		type RuntimeCall = RuntimeCall;
+		type PalletsOrigin = OriginCaller;
+		type WeightInfo = ();
+	}
+
 	pub struct OneAuthor;
 	impl FindAuthor<AccountId> for OneAuthor {
 		fn find_author<'a, I>(_: I) -> Option<AccountId>
@@ -487,6 +549,51 @@ mod tests {
 		});
 	}
 
+	pub struct SpendsNothing;
+	impl Contains<RuntimeCall> for SpendsNothing {
+		fn contains(call: &RuntimeCall) -> bool {
+			matches!(call, RuntimeCall::System(frame_system::Call::remark { .. }))
+		}
+	}
+
+	#[test]
+	fn transfer_spending_denies_unknown_calls() {
+		use pallet_proxy::CallSpending;
+		type Spending = TransferSpending<Test, SpendsNothing>;
+
+		new_test_ext().execute_with(|| {
+			let transfer = |value| {
+				RuntimeCall::Balances(pallet_balances::Call::transfer_keep_alive {
+					dest: TEST_ACCOUNT,
+					value,
+				})
+			};
+			let remark = RuntimeCall::System(frame_system::Call::remark { remark: vec![] });
+			let batch = |calls| RuntimeCall::Utility(pallet_utility::Call::batch_all { calls });
+
+			assert_eq!(Spending::spending(&TEST_ACCOUNT, &transfer(5)), 5);
+			assert_eq!(Spending::spending(&TEST_ACCOUNT, &remark), 0);
+			assert_eq!(
+				Spending::spending(&TEST_ACCOUNT, &batch(vec![transfer(5), remark, transfer(2)])),
+				7
+			);
+
+			// Calls which aren't known to move nothing exceed any allowance.
+			let unknown =
+				RuntimeCall::System(frame_system::Call::remark_with_event { remark: vec![] });
+			assert_eq!(Spending::spending(&TEST_ACCOUNT, &unknown), u64::MAX);
+			let derivative = RuntimeCall::Utility(pallet_utility::Call::as_derivative {
+				index: 0,
+				call: Box::new(transfer(5)),
+			});
+			assert_eq!(Spending::spending(&TEST_ACCOUNT, &derivative), u64::MAX);
+			assert_eq!(
+				Spending::spending(&TEST_ACCOUNT, &batch(vec![transfer(5), derivative])),
+				u64::MAX
+			);
+		});
+	}
+
 	#[test]
 	fn compute_inflation_should_give_sensible_results() {
 		assert_eq!(
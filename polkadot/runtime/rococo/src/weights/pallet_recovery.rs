@@ -201,4 +201,16 @@ impl<T: frame_system::Config> pallet_recovery::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads(2))
 			.saturating_add(T::DbWeight::get().writes(2))
 	}
+	/// Not benchmarked for this runtime yet, falls back to the weight of the pallet.
+	fn update_recovery(n: u32, ) -> Weight {
+		<pallet_recovery::weights::SubstrateWeight<T> as pallet_recovery::WeightInfo>::update_recovery(n)
+	}
+	/// Not benchmarked for this runtime yet, falls back to the weight of the pallet.
+	fn cancel_recovery_update(n: u32, ) -> Weight {
+		<pallet_recovery::weights::SubstrateWeight<T> as pallet_recovery::WeightInfo>::cancel_recovery_update(n)
+	}
+	/// Not benchmarked for this runtime yet, falls back to the weight of the pallet.
+	fn apply_recovery_update(n: u32, ) -> Weight {
+		<pallet_recovery::weights::SubstrateWeight<T> as pallet_recovery::WeightInfo>::apply_recovery_update(n)
+	}
 }
@@ -178,4 +178,40 @@ impl<T: frame_system::Config> pallet_multisig::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads(1))
 			.saturating_add(T::DbWeight::get().writes(1))
 	}
+	/// Not benchmarked for this runtime yet, falls back to the weight of the pallet.
+	fn create_stored(s: u32, ) -> Weight {
+		<pallet_multisig::weights::SubstrateWeight<T> as pallet_multisig::WeightInfo>::create_stored(s)
+	}
+	/// Not benchmarked for this runtime yet, falls back to the weight of the pallet.
+	fn as_stored_multi_create(s: u32, z: u32, ) -> Weight {
+		<pallet_multisig::weights::SubstrateWeight<T> as pallet_multisig::WeightInfo>::as_stored_multi_create(s, z)
+	}
+	/// Not benchmarked for this runtime yet, falls back to the weight of the pallet.
+	fn as_stored_multi_approve(s: u32, z: u32, ) -> Weight {
+		<pallet_multisig::weights::SubstrateWeight<T> as pallet_multisig::WeightInfo>::as_stored_multi_approve(s, z)
+	}
+	/// Not benchmarked for this runtime yet, falls back to the weight of the pallet.
+	fn as_stored_multi_complete(s: u32, z: u32, ) -> Weight {
+		<pallet_multisig::weights::SubstrateWeight<T> as pallet_multisig::WeightInfo>::as_stored_multi_complete(s, z)
+	}
+	/// Not benchmarked for this runtime yet, falls back to the weight of the pallet.
+	fn approve_as_stored_multi_create(s: u32, ) -> Weight {
+		<pallet_multisig::weights::SubstrateWeight<T> as pallet_multisig::WeightInfo>::approve_as_stored_multi_create(s)
+	}
+	/// Not benchmarked for this runtime yet, falls back to the weight of the pallet.
+	fn approve_as_stored_multi_approve(s: u32, ) -> Weight {
+		<pallet_multisig::weights::SubstrateWeight<T> as pallet_multisig::WeightInfo>::approve_as_stored_multi_approve(s)
+	}
+	/// Not benchmarked for this runtime yet, falls back to the weight of the pallet.
+	fn cancel_as_stored_multi(s: u32, ) -> Weight {
+		<pallet_multisig::weights::SubstrateWeight<T> as pallet_multisig::WeightInfo>::cancel_as_stored_multi(s)
+	}
+	/// Not benchmarked for this runtime yet, falls back to the weight of the pallet.
+	fn set_members(s: u32, ) -> Weight {
+		<pallet_multisig::weights::SubstrateWeight<T> as pallet_multisig::WeightInfo>::set_members(s)
+	}
+	/// Not benchmarked for this runtime yet, falls back to the weight of the pallet.
+	fn destroy_stored() -> Weight {
+		<pallet_multisig::weights::SubstrateWeight<T> as pallet_multisig::WeightInfo>::destroy_stored()
+	}
 }
@@ -240,4 +240,12 @@ impl<T: frame_system::Config> pallet_proxy::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads(3))
 			.saturating_add(T::DbWeight::get().writes(3))
 	}
+	/// Not benchmarked for this runtime yet, falls back to the weight of the pallet.
+	fn add_limited_proxy(p: u32, ) -> Weight {
+		<pallet_proxy::weights::SubstrateWeight<T> as pallet_proxy::WeightInfo>::add_limited_proxy(p)
+	}
+	/// Not benchmarked for this runtime yet, falls back to the weight of the pallet.
+	fn remove_expired_proxy(p: u32, ) -> Weight {
+		<pallet_proxy::weights::SubstrateWeight<T> as pallet_proxy::WeightInfo>::remove_expired_proxy(p)
+	}
 }
@@ -99,7 +99,11 @@ fn new_node(tokio_handle: Handle) -> node_cli::service::NewFullBase {
 		},
 		prometheus_config: None,
 		telemetry_endpoints: None,
-		offchain_worker: OffchainWorkerConfig { enabled: true, indexing_enabled: false },
+		offchain_worker: OffchainWorkerConfig {
+			enabled: true,
+			indexing_enabled: false,
+			http_request_policy: Default::default(),
+		},
 		force_authoring: false,
 		disable_grandpa: false,
 		dev_key_seed: Some(Sr25519Keyring::Alice.to_seed()),
@@ -115,6 +119,7 @@ fn new_node(tokio_handle: Handle) -> node_cli::service::NewFullBase {
 		config,
 		None,
 		false,
+		None,
 		|_, _| (),
 	)
 	.expect("creating a full node doesn't fail")
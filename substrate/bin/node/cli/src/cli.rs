@@ -43,6 +43,14 @@ pub struct Cli {
 	#[arg(long)]
 	pub no_hardware_benchmarks: bool,
 
+	/// Path to a JSON file listing the rules restricting the GRANDPA votes.
+	///
+	/// The rules replace the default ones, and are applied in order. The `at_most_ahead_of` rule
+	/// supports the `beefy` reference block, i.e. the best BEEFY block. Rules based on `beefy`
+	/// must allow at least 8 blocks, the minimum distance between two BEEFY votes.
+	#[arg(long, value_name = "PATH")]
+	pub grandpa_voting_rules: Option<std::path::PathBuf>,
+
 	#[allow(missing_docs)]
 	#[clap(flatten)]
 	pub storage_monitor: sc_storage_monitor::StorageMonitorParams,
@@ -106,6 +114,9 @@ pub enum Subcommand {
 	/// Revert the chain to a previous state.
 	Revert(sc_cli::RevertCmd),
 
+	/// Profile the runtime execution of a block.
+	ProfileBlock(sc_cli::ProfileBlockCmd),
+
 	/// Db meta columns information.
 	ChainInfo(sc_cli::ChainInfoCmd),
 }
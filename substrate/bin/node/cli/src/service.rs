@@ -32,7 +32,7 @@ use frame_system_rpc_runtime_api::AccountNonceApi;
 use futures::prelude::*;
 use kitchensink_runtime::RuntimeApi;
 use node_primitives::Block;
-use sc_client_api::{Backend, BlockBackend};
+use sc_client_api::{Backend, BlockBackend, HeaderBackend};
 use sc_consensus_babe::{self, SlotProportion};
 use sc_network::{
 	event::Event, service::traits::NetworkService, NetworkBackend, NetworkEventStream,
@@ -86,6 +86,9 @@ pub type TransactionPool = sc_transaction_pool::TransactionPoolHandle<Block, Ful
 /// imported and generated.
 const GRANDPA_JUSTIFICATION_PERIOD: u32 = 512;
 
+/// Minimum number of blocks between two BEEFY votes.
+const BEEFY_MIN_BLOCK_DELTA: u32 = 8;
+
 /// Fetch the nonce of the given `account` from the chain state.
 ///
 /// Note: Should only be used for tests.
@@ -195,7 +198,11 @@ pub fn new_partial(
 				sc_consensus_babe::BabeLink<Block>,
 				beefy::BeefyVoterLinks<Block, beefy_primitives::ecdsa_crypto::AuthorityId>,
 			),
-			grandpa::SharedVoterState,
+			(
+				grandpa::SharedVoterState,
+				beefy::communication::notification::BeefyBestBlockStream<Block>,
+				sc_authority_discovery::ServiceReceiver,
+			),
 			Option<Telemetry>,
 			Arc<StatementStore>,
 			Option<sc_mixnet::ApiBackend>,
@@ -296,7 +303,13 @@ pub fn new_partial(
 		&config.data_path,
 		Default::default(),
 		client.clone(),
-		keystore_container.local_keystore(),
+		keystore_container.local_keystore().ok_or_else(|| {
+			ServiceError::Other(
+				"The statement store needs direct access to the keys to decrypt statements and \
+				 can't be used with a remote signer"
+					.into(),
+			)
+		})?,
 		config.prometheus_registry(),
 		&task_manager.spawn_handle(),
 	)
@@ -304,6 +317,9 @@ pub fn new_partial(
 
 	let (mixnet_api, mixnet_api_backend) = mixnet_config.map(sc_mixnet::Api::new).unzip();
 
+	let (authority_discovery_service, authority_discovery_service_receiver) =
+		sc_authority_discovery::new_service();
+
 	let (rpc_extensions_builder, rpc_setup) = {
 		let (_, grandpa_link, _, _) = &import_setup;
 
@@ -311,6 +327,7 @@ pub fn new_partial(
 		let shared_authority_set = grandpa_link.shared_authority_set().clone();
 		let shared_voter_state = grandpa::SharedVoterState::empty();
 		let shared_voter_state2 = shared_voter_state.clone();
+		let beefy_best_block_stream = beefy_rpc_links.from_voter_best_beefy_stream.clone();
 
 		let finality_proof_provider = grandpa::FinalityProofProvider::new_for_service(
 			backend.clone(),
@@ -355,12 +372,16 @@ pub fn new_partial(
 					statement_store: rpc_statement_store.clone(),
 					backend: rpc_backend.clone(),
 					mixnet_api: mixnet_api.as_ref().cloned(),
+					authority_discovery: authority_discovery_service.clone(),
 				};
 
 				node_rpc::create_full(deps).map_err(Into::into)
 			};
 
-		(rpc_extensions_builder, shared_voter_state2)
+		(
+			rpc_extensions_builder,
+			(shared_voter_state2, beefy_best_block_stream, authority_discovery_service_receiver),
+		)
 	};
 
 	Ok(sc_service::PartialComponents {
@@ -403,6 +424,7 @@ pub fn new_full_base<N: NetworkBackend<Block, <Block as BlockT>::Hash>>(
 	config: Configuration,
 	mixnet_config: Option<sc_mixnet::Config>,
 	disable_hardware_benchmarks: bool,
+	grandpa_voting_rules: Option<Vec<grandpa::VotingRuleConfig>>,
 	with_startup_data: impl FnOnce(
 		&sc_consensus_babe::BabeBlockImport<
 			Block,
@@ -421,7 +443,9 @@ pub fn new_full_base<N: NetworkBackend<Block, <Block as BlockT>::Hash>>(
 	let enable_grandpa = !config.disable_grandpa;
 	let prometheus_registry = config.prometheus_registry().cloned();
 	let enable_offchain_worker = config.offchain_worker.enabled;
+	let offchain_http_request_policy = config.offchain_worker.http_request_policy.clone();
 
+	let hwbench_history_dir = config.base_path.path().to_path_buf();
 	let hwbench = (!disable_hardware_benchmarks)
 		.then(|| {
 			config.database.path().map(|database_path| {
@@ -446,7 +470,8 @@ pub fn new_full_base<N: NetworkBackend<Block, <Block as BlockT>::Hash>>(
 	let metrics = N::register_notification_metrics(
 		config.prometheus_config.as_ref().map(|cfg| &cfg.registry),
 	);
-	let shared_voter_state = rpc_setup;
+	let (shared_voter_state, beefy_best_block_stream, authority_discovery_service_receiver) =
+		rpc_setup;
 	let auth_disc_publish_non_global_ips = config.network.allow_non_globals_in_dht;
 	let auth_disc_public_addresses = config.network.public_addresses.clone();
 
@@ -574,6 +599,30 @@ pub fn new_full_base<N: NetworkBackend<Block, <Block as BlockT>::Hash>>(
 			_ => {},
 		}
 
+		match sc_sysinfo::HwBenchHistory::open(&hwbench_history_dir) {
+			Ok(mut history) => {
+				match history.check_degradation(&SUBSTRATE_REFERENCE_HARDWARE, &hwbench, false) {
+					Err(err) if role.is_authority() => {
+						log::warn!(
+							"⚠️  The hardware no longer meets the minimal requirements it usually met on the previous runs. {}",
+							err
+						);
+					},
+					_ => {},
+				}
+				if let Err(err) = history.push(hwbench.clone()) {
+					log::warn!("Failed to record the hardware benchmark results: {}", err);
+				}
+			},
+			Err(err) => log::warn!("Failed to load the hardware benchmark history: {}", err),
+		}
+
+		if let Some(registry) = prometheus_registry.as_ref() {
+			if let Err(err) = sc_sysinfo::register_hwbench_metrics(registry, &hwbench) {
+				log::warn!("Failed to register the hardware benchmark metrics: {}", err);
+			}
+		}
+
 		if let Some(ref mut telemetry) = telemetry {
 			let telemetry_handle = telemetry.handle();
 			task_manager.spawn_handle().spawn(
@@ -601,6 +650,7 @@ pub fn new_full_base<N: NetworkBackend<Block, <Block as BlockT>::Hash>>(
 		let slot_duration = babe_link.config().slot_duration();
 		let babe_config = sc_consensus_babe::BabeParams {
 			keystore: keystore_container.keystore(),
+			slashing_protection: Some(keystore_container.slashing_protection()),
 			client: client.clone(),
 			select_chain,
 			env: proposer,
@@ -654,19 +704,19 @@ pub fn new_full_base<N: NetworkBackend<Block, <Block as BlockT>::Hash>>(
 					_ => None,
 				}
 			});
-		let (authority_discovery_worker, _service) =
-			sc_authority_discovery::new_worker_and_service_with_config(
-				sc_authority_discovery::WorkerConfig {
-					publish_non_global_ips: auth_disc_publish_non_global_ips,
-					public_addresses: auth_disc_public_addresses,
-					..Default::default()
-				},
-				client.clone(),
-				Arc::new(network.clone()),
-				Box::pin(dht_event_stream),
-				authority_discovery_role,
-				prometheus_registry.clone(),
-			);
+		let authority_discovery_worker = sc_authority_discovery::new_worker_for_service(
+			sc_authority_discovery::WorkerConfig {
+				publish_non_global_ips: auth_disc_publish_non_global_ips,
+				public_addresses: auth_disc_public_addresses,
+				..Default::default()
+			},
+			authority_discovery_service_receiver,
+			client.clone(),
+			Arc::new(network.clone()),
+			Box::pin(dht_event_stream),
+			authority_discovery_role,
+			prometheus_registry.clone(),
+		);
 
 		task_manager.spawn_handle().spawn(
 			"authority-discovery-worker",
@@ -678,6 +728,8 @@ pub fn new_full_base<N: NetworkBackend<Block, <Block as BlockT>::Hash>>(
 	// if the node isn't actively participating in consensus then it doesn't
 	// need a keystore, regardless of which protocol we use below.
 	let keystore = if role.is_authority() { Some(keystore_container.keystore()) } else { None };
+	let slashing_protection =
+		if role.is_authority() { Some(keystore_container.slashing_protection()) } else { None };
 
 	// beefy is enabled if its notification service exists
 	let network_params = beefy::BeefyNetworkParams {
@@ -694,8 +746,9 @@ pub fn new_full_base<N: NetworkBackend<Block, <Block as BlockT>::Hash>>(
 		payload_provider: sp_consensus_beefy::mmr::MmrRootProvider::new(client.clone()),
 		runtime: client.clone(),
 		key_store: keystore.clone(),
+		slashing_protection: slashing_protection.clone(),
 		network_params,
-		min_block_delta: 8,
+		min_block_delta: BEEFY_MIN_BLOCK_DELTA,
 		prometheus_registry: prometheus_registry.clone(),
 		links: beefy_links,
 		on_demand_justifications_handler: beefy_on_demand_justifications_handler,
@@ -728,12 +781,38 @@ pub fn new_full_base<N: NetworkBackend<Block, <Block as BlockT>::Hash>>(
 		name: Some(name),
 		observer_enabled: false,
 		keystore,
+		slashing_protection,
 		local_role: role,
 		telemetry: telemetry.as_ref().map(|x| x.handle()),
 		protocol_name: grandpa_protocol_name,
 	};
 
 	if enable_grandpa {
+		let voting_rules_builder = match grandpa_voting_rules {
+			Some(rules) => {
+				let beefy_best_block = grandpa::SharedReferenceBlock::new();
+				let mut beefy_best_blocks = beefy_best_block_stream.subscribe(100_000);
+				let tracked_best_block = beefy_best_block.clone();
+				let tracker_client = client.clone();
+				task_manager.spawn_handle().spawn("beefy-best-block-tracker", None, async move {
+					while let Some(hash) = beefy_best_blocks.next().await {
+						if let Ok(Some(number)) = tracker_client.number(hash) {
+							tracked_best_block.note(number);
+						}
+					}
+				});
+
+				let context = grandpa::VotingRuleContext::default()
+					.with_reference_block("beefy", Arc::new(beefy_best_block))
+					.with_min_blocks_ahead("beefy", BEEFY_MIN_BLOCK_DELTA)
+					.with_session_boundaries(sc_consensus_babe::is_epoch_change::<Block>);
+				grandpa::VotingRulesBuilder::new()
+					.add_from_config(&rules, &context)
+					.map_err(|e| ServiceError::Other(e.to_string()))?
+			},
+			None => grandpa::VotingRulesBuilder::default(),
+		};
+
 		// start the full GRANDPA voter
 		// NOTE: non-authorities could run the GRANDPA observer protocol, but at
 		// this point the full voter should provide better guarantees of block
@@ -747,7 +826,9 @@ pub fn new_full_base<N: NetworkBackend<Block, <Block as BlockT>::Hash>>(
 			sync: Arc::new(sync_service.clone()),
 			notification_service: grandpa_notification_service,
 			telemetry: telemetry.as_ref().map(|x| x.handle()),
-			voting_rule: grandpa::VotingRulesBuilder::default().build(),
+			voting_rule: voting_rules_builder
+				.telemetry(telemetry.as_ref().map(|x| x.handle()))
+				.build(),
 			prometheus_registry: prometheus_registry.clone(),
 			shared_voter_state,
 			offchain_tx_pool_factory: OffchainTransactionPoolFactory::new(transaction_pool.clone()),
@@ -794,6 +875,8 @@ pub fn new_full_base<N: NetworkBackend<Block, <Block as BlockT>::Hash>>(
 				network_provider: Arc::new(network.clone()),
 				is_validator: role.is_authority(),
 				enable_http_requests: true,
+				http_request_policy: offchain_http_request_policy,
+				prometheus_registry: prometheus_registry.clone(),
 				custom_extensions: move |_| {
 					vec![Box::new(statement_store.clone().as_statement_store_ext()) as Box<_>]
 				},
@@ -818,6 +901,12 @@ pub fn new_full_base<N: NetworkBackend<Block, <Block as BlockT>::Hash>>(
 /// Builds a new service for a full client.
 pub fn new_full(config: Configuration, cli: Cli) -> Result<TaskManager, ServiceError> {
 	let mixnet_config = cli.mixnet_params.config(config.role.is_authority());
+	let grandpa_voting_rules = cli
+		.grandpa_voting_rules
+		.as_deref()
+		.map(grandpa::VotingRuleConfig::load)
+		.transpose()
+		.map_err(|e| ServiceError::Other(e.to_string()))?;
 	let database_path = config.database.path().map(Path::to_path_buf);
 
 	let task_manager = match config.network.network_backend.unwrap_or_default() {
@@ -826,6 +915,7 @@ pub fn new_full(config: Configuration, cli: Cli) -> Result<TaskManager, ServiceE
 				config,
 				mixnet_config,
 				cli.no_hardware_benchmarks,
+				grandpa_voting_rules,
 				|_, _| (),
 			)
 			.map(|NewFullBase { task_manager, .. }| task_manager)?;
@@ -836,6 +926,7 @@ pub fn new_full(config: Configuration, cli: Cli) -> Result<TaskManager, ServiceE
 				config,
 				mixnet_config,
 				cli.no_hardware_benchmarks,
+				grandpa_voting_rules,
 				|_, _| (),
 			)
 			.map(|NewFullBase { task_manager, .. }| task_manager)?;
@@ -923,6 +1014,7 @@ mod tests {
 						config,
 						None,
 						false,
+						None,
 						|block_import: &sc_consensus_babe::BabeBlockImport<Block, _, _>,
 						 babe_link: &sc_consensus_babe::BabeLink<Block>| {
 							setup_handles = Some((block_import.clone(), babe_link.clone()));
@@ -1127,6 +1219,7 @@ mod tests {
 						config,
 						None,
 						false,
+						None,
 						|_, _| (),
 					)?;
 				Ok(sc_service_test::TestNetComponents::new(
@@ -108,6 +108,8 @@ pub struct FullDeps<C, P, SC, B, AuthorityId: AuthorityIdBound> {
 	pub backend: Arc<B>,
 	/// Mixnet API.
 	pub mixnet_api: Option<sc_mixnet::Api>,
+	/// Authority discovery service.
+	pub authority_discovery: sc_authority_discovery::Service,
 }
 
 /// Instantiate all Full RPC extensions.
@@ -123,6 +125,7 @@ pub fn create_full<C, P, SC, B, AuthorityId>(
 		statement_store,
 		backend,
 		mixnet_api,
+		authority_discovery,
 	}: FullDeps<C, P, SC, B, AuthorityId>,
 ) -> Result<RpcModule<()>, Box<dyn std::error::Error + Send + Sync>>
 where
@@ -148,6 +151,7 @@ where
 {
 	use mmr_rpc::{Mmr, MmrApiServer};
 	use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApiServer};
+	use sc_authority_discovery_rpc::{AuthorityDiscovery, AuthorityDiscoveryApiServer};
 	use sc_consensus_babe_rpc::{Babe, BabeApiServer};
 	use sc_consensus_beefy_rpc::{Beefy, BeefyApiServer};
 	use sc_consensus_grandpa_rpc::{Grandpa, GrandpaApiServer};
@@ -214,6 +218,8 @@ where
 		io.merge(mixnet)?;
 	}
 
+	io.merge(AuthorityDiscovery::new(authority_discovery).into_rpc())?;
+
 	io.merge(
 		Beefy::<Block, AuthorityId>::new(
 			beefy.beefy_finality_proof_stream,
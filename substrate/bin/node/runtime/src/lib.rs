@@ -474,6 +474,22 @@ impl InstanceFilter<RuntimeCall> for ProxyType {
 	}
 }
 
+/// The calls which move no funds of the account they are dispatched for, and which a proxy may
+/// dispatch regardless of its spending allowance.
+pub struct SpendsNothing;
+impl Contains<RuntimeCall> for SpendsNothing {
+	fn contains(call: &RuntimeCall) -> bool {
+		matches!(
+			call,
+			RuntimeCall::System(
+				frame_system::Call::remark { .. } | frame_system::Call::remark_with_event { .. }
+			) | RuntimeCall::Session(..) |
+				RuntimeCall::Multisig(..) |
+				RuntimeCall::Proxy(..)
+		)
+	}
+}
+
 impl pallet_proxy::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type RuntimeCall = RuntimeCall;
@@ -488,6 +504,7 @@ impl pallet_proxy::Config for Runtime {
 	type AnnouncementDepositBase = AnnouncementDepositBase;
 	type AnnouncementDepositFactor = AnnouncementDepositFactor;
 	type BlockNumberProvider = frame_system::Pallet<Runtime>;
+	type CallSpending = polkadot_runtime_common::impls::TransferSpending<Runtime, SpendsNothing>;
 }
 
 parameter_types! {
@@ -510,6 +527,8 @@ impl pallet_scheduler::Config for Runtime {
 	type OriginPrivilegeCmp = EqualPrivilegeOnly;
 	type Preimages = Preimage;
 	type BlockNumberProvider = frame_system::Pallet<Runtime>;
+	type Time = Timestamp;
+	type MaxRecurring = ConstU32<50>;
 }
 
 impl pallet_glutton::Config for Runtime {
@@ -2029,6 +2048,7 @@ parameter_types! {
 	pub const ItemAttributesApprovalsLimit: u32 = 20;
 	pub const MaxTips: u32 = 10;
 	pub const MaxDeadlineDuration: BlockNumber = 12 * 30 * DAYS;
+	pub const MaxRoyalty: Permill = Permill::from_percent(25);
 }
 
 impl pallet_uniques::Config for Runtime {
@@ -2139,6 +2159,8 @@ impl pallet_nfts::Config for Runtime {
 	type MaxTips = MaxTips;
 	type MaxDeadlineDuration = MaxDeadlineDuration;
 	type MaxAttributesPerCall = MaxAttributesPerCall;
+	type MaxRoyaltyRecipients = ConstU32<5>;
+	type MaxRoyalty = MaxRoyalty;
 	type Features = Features;
 	type OffchainSignature = Signature;
 	type OffchainPublic = <Signature as traits::Verify>::Signer;
@@ -3609,6 +3631,13 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_scheduler::runtime_api::SchedulerApi<Block, Moment> for Runtime {
+		fn upcoming_executions(max: u32) -> Vec<(pallet_scheduler::TaskName, Moment)> {
+			Scheduler::upcoming_executions(max)
+		}
+	}
+
+	#[api_version(2)]
 	impl pallet_nfts_runtime_api::NftsApi<Block, AccountId, u32, u32> for Runtime {
 		fn owner(collection: u32, item: u32) -> Option<AccountId> {
 			<Nfts as Inspect<AccountId>>::owner(&collection, &item)
@@ -3651,6 +3680,14 @@ impl_runtime_apis! {
 		fn collection_attribute(collection: u32, key: Vec<u8>) -> Option<Vec<u8>> {
 			<Nfts as Inspect<AccountId>>::collection_attribute(&collection, &key)
 		}
+
+		fn royalty(collection: u32, item: u32) -> Vec<(AccountId, sp_runtime::Permill)> {
+			Nfts::royalty(&collection, &item)
+				.map(|recipients| {
+					recipients.into_iter().map(|r| (r.account, r.percentage)).collect()
+				})
+				.unwrap_or_default()
+		}
 	}
 
 	#[api_version(5)]
@@ -3805,6 +3842,14 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_recovery::runtime_api::RecoveryApi<Block, AccountId, BlockNumber, Balance> for Runtime {
+		fn active_recoveries(
+			lost: AccountId,
+		) -> Vec<(AccountId, pallet_recovery::ActiveRecovery<BlockNumber, Balance, Vec<AccountId>>)> {
+			Recovery::active_recoveries(&lost)
+		}
+	}
+
 	impl pallet_asset_rewards::AssetRewards<Block, Balance> for Runtime {
 		fn pool_creation_cost() -> Balance {
 			StakePoolCreationDeposit::get()
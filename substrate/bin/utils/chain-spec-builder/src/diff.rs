@@ -0,0 +1,169 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Semantic differences between chain specs.
+
+use crate::storage_keys::StorageKeyNames;
+use serde_json::{Map, Value};
+use std::fmt;
+
+/// Values longer than this are abbreviated when displayed.
+const MAX_DISPLAYED_LEN: usize = 66;
+
+/// A difference between two JSON documents.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Difference {
+	/// The value at `path` only exists in the second document.
+	Added { path: String, value: Value },
+	/// The value at `path` only exists in the first document.
+	Removed { path: String, value: Value },
+	/// The value at `path` differs between the documents.
+	Changed { path: String, left: Value, right: Value },
+}
+
+impl fmt::Display for Difference {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Added { path, value } => write!(f, "+ {path}: {}", abbreviate(value)),
+			Self::Removed { path, value } => write!(f, "- {path}: {}", abbreviate(value)),
+			Self::Changed { path, left, right } =>
+				write!(f, "~ {path}: {} -> {}", abbreviate(left), abbreviate(right)),
+		}
+	}
+}
+
+fn abbreviate(value: &Value) -> String {
+	let value = value.to_string();
+	match value.char_indices().nth(MAX_DISPLAYED_LEN) {
+		Some((end, _)) => format!("{}… ({} chars)", &value[..end], value.len()),
+		None => value,
+	}
+}
+
+/// Returns the differences between the `left` and `right` JSON documents.
+///
+/// Objects are compared key by key and arrays element by element, so that only the values which
+/// actually differ are reported.
+pub fn diff(left: &Value, right: &Value) -> Vec<Difference> {
+	let mut differences = Vec::new();
+	diff_at(String::new(), left, right, &mut differences);
+	differences
+}
+
+fn diff_at(path: String, left: &Value, right: &Value, differences: &mut Vec<Difference>) {
+	let join = |key: &str| if path.is_empty() { key.to_string() } else { format!("{path}.{key}") };
+
+	match (left, right) {
+		(Value::Object(left), Value::Object(right)) => {
+			for (key, left_value) in left {
+				match right.get(key) {
+					Some(right_value) => diff_at(join(key), left_value, right_value, differences),
+					None => differences
+						.push(Difference::Removed { path: join(key), value: left_value.clone() }),
+				}
+			}
+			for (key, right_value) in right.iter().filter(|(key, _)| !left.contains_key(*key)) {
+				differences.push(Difference::Added { path: join(key), value: right_value.clone() });
+			}
+		},
+		(Value::Array(left), Value::Array(right)) =>
+			for index in 0..left.len().max(right.len()) {
+				let path = format!("{path}[{index}]");
+				match (left.get(index), right.get(index)) {
+					(Some(left), Some(right)) => diff_at(path, left, right, differences),
+					(Some(value), None) =>
+						differences.push(Difference::Removed { path, value: value.clone() }),
+					(None, Some(value)) =>
+						differences.push(Difference::Added { path, value: value.clone() }),
+					(None, None) => unreachable!("index is lower than one of the lengths; qed"),
+				}
+			},
+		(left, right) if left != right =>
+			differences.push(Difference::Changed { path, left: left.clone(), right: right.clone() }),
+		_ => {},
+	}
+}
+
+/// Replaces the hex encoded keys of the raw genesis storage of `chain_spec` with their `names`.
+///
+/// Does nothing if `chain_spec` is not a raw chain spec.
+pub fn name_raw_storage_keys(chain_spec: &mut Value, names: &StorageKeyNames) {
+	let Some(raw) = chain_spec.pointer_mut("/genesis/raw").and_then(Value::as_object_mut) else {
+		return
+	};
+
+	if let Some(Value::Object(top)) = raw.get_mut("top") {
+		name_keys(top, names);
+	}
+	if let Some(Value::Object(children)) = raw.get_mut("childrenDefault") {
+		for child in children.values_mut().filter_map(Value::as_object_mut) {
+			name_keys(child, names);
+		}
+	}
+}
+
+fn name_keys(storage: &mut Map<String, Value>, names: &StorageKeyNames) {
+	*storage = std::mem::take(storage)
+		.into_iter()
+		.map(|(key, value)| match array_bytes::hex2bytes(&key) {
+			Ok(key) => (names.name(&key), value),
+			Err(_) => (key, value),
+		})
+		.collect();
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use serde_json::json;
+
+	#[test]
+	fn diff_reports_only_differing_values() {
+		let left = json!({
+			"name": "Local",
+			"bootNodes": ["a", "b"],
+			"properties": {"tokenSymbol": "UNIT", "tokenDecimals": 12},
+		});
+		let right = json!({
+			"name": "Local",
+			"bootNodes": ["a"],
+			"properties": {"tokenSymbol": "TEST", "ss58Format": 42},
+		});
+
+		assert_eq!(
+			diff(&left, &right),
+			vec![
+				Difference::Removed { path: "bootNodes[1]".into(), value: json!("b") },
+				Difference::Removed { path: "properties.tokenDecimals".into(), value: json!(12) },
+				Difference::Changed {
+					path: "properties.tokenSymbol".into(),
+					left: json!("UNIT"),
+					right: json!("TEST"),
+				},
+				Difference::Added { path: "properties.ss58Format".into(), value: json!(42) },
+			]
+		);
+		assert!(diff(&left, &left).is_empty());
+	}
+
+	#[test]
+	fn long_values_are_abbreviated() {
+		let difference = Difference::Added { path: "code".into(), value: json!("0".repeat(100)) };
+		assert_eq!(difference.to_string(), format!("+ code: \"{}… (102 chars)", "0".repeat(65)));
+	}
+}
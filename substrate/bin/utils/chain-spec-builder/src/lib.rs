@@ -19,10 +19,14 @@
 #[cfg(feature = "generate-readme")]
 docify::compile_markdown!("README.docify.md", "README.md");
 
+mod diff;
+mod storage_keys;
+mod validate;
+
 use clap::{Parser, Subcommand};
 use sc_chain_spec::{
-	json_patch, set_code_substitute_in_json_chain_spec, update_code_in_json_chain_spec, ChainType,
-	GenericChainSpec, GenesisConfigBuilderRuntimeCaller,
+	code_in_json_chain_spec, json_patch, set_code_substitute_in_json_chain_spec,
+	update_code_in_json_chain_spec, ChainType, GenericChainSpec, GenesisConfigBuilderRuntimeCaller,
 };
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -53,6 +57,8 @@ pub enum ChainSpecBuilderCmd {
 	ListPresets(ListPresetsCmd),
 	DisplayPreset(DisplayPresetCmd),
 	AddCodeSubstitute(AddCodeSubstituteCmd),
+	Diff(DiffCmd),
+	Validate(ValidateCmd),
 }
 
 /// Create a new chain spec by interacting with the provided runtime wasm blob.
@@ -212,6 +218,37 @@ pub struct VerifyCmd {
 	pub input_chain_spec: PathBuf,
 }
 
+/// Shows the semantic differences between two chain specs.
+///
+/// Only the values which differ are shown. Keys of the raw genesis storage are named after the
+/// storage items of the runtime metadata, e.g. `System::Account(0x..)`, using the code of the chain
+/// specs.
+#[derive(Parser, Debug, Clone)]
+pub struct DiffCmd {
+	/// The first chain spec.
+	pub left_chain_spec: PathBuf,
+	/// The second chain spec.
+	pub right_chain_spec: PathBuf,
+	/// Compare the raw genesis storage, converting plain chain specs to raw first.
+	#[arg(long)]
+	pub raw: bool,
+}
+
+/// Validates the provided input chain spec against its runtime.
+///
+/// For plain chain specs, builds the genesis state and reports the genesis config fields unknown
+/// to the runtime's `GenesisBuilder`. For raw chain specs, reports the storage keys which don't
+/// belong to any storage item of the runtime metadata.
+#[derive(Parser, Debug, Clone)]
+pub struct ValidateCmd {
+	/// Chain spec to be validated.
+	pub input_chain_spec: PathBuf,
+	/// The path to the runtime wasm blob to validate against, instead of the code of the chain
+	/// spec.
+	#[arg(long, short, alias = "runtime-wasm-path")]
+	pub runtime: Option<PathBuf>,
+}
+
 #[derive(Deserialize, Serialize, Clone)]
 pub struct ParachainExtension {
 	/// The relay chain of the Parachain.
@@ -297,6 +334,61 @@ impl ChainSpecBuilder {
 				serde_json::from_str::<serde_json::Value>(&chain_spec.as_json(true)?)
 					.map_err(|e| format!("Conversion to json failed: {e}"))?;
 			},
+			ChainSpecBuilderCmd::Diff(DiffCmd { left_chain_spec, right_chain_spec, raw }) => {
+				let load = |path: &PathBuf| {
+					if *raw {
+						let chain_spec = ChainSpec::from_json_file(path.clone())?;
+						serde_json::from_str::<Value>(&chain_spec.as_json(true)?)
+							.map_err(|e| format!("Conversion to json failed: {e}"))
+					} else {
+						extract_chain_spec_json(path.as_path())
+					}
+				};
+				let mut left = load(left_chain_spec)?;
+				let mut right = load(right_chain_spec)?;
+
+				let mut names = storage_keys::StorageKeyNames::default();
+				for chain_spec in [&left, &right] {
+					match code_in_json_chain_spec(chain_spec)
+						.ok_or_else(|| "no code in the chain spec".to_string())
+						.and_then(|code| storage_keys::StorageKeyNames::from_runtime(&code))
+					{
+						Ok(other) => names.extend(other),
+						Err(e) => eprintln!("Storage keys can't be named: {e}"),
+					}
+				}
+				diff::name_raw_storage_keys(&mut left, &names);
+				diff::name_raw_storage_keys(&mut right, &names);
+
+				let differences = diff::diff(&left, &right);
+				if differences.is_empty() {
+					println!("The chain specs are identical");
+				}
+				for difference in differences {
+					println!("{difference}");
+				}
+			},
+			ChainSpecBuilderCmd::Validate(ValidateCmd { input_chain_spec, runtime }) => {
+				let chain_spec = extract_chain_spec_json(input_chain_spec.as_path())?;
+				let code = match runtime {
+					Some(runtime) => fs::read(runtime.as_path())
+						.map_err(|e| format!("wasm blob shall be readable {e}"))?,
+					None => code_in_json_chain_spec(&chain_spec)
+						.ok_or("The chain spec contains no code, use `--runtime`")?,
+				};
+
+				let problems = validate::validate(&chain_spec, &code)?;
+				if !problems.is_empty() {
+					for problem in &problems {
+						println!("{problem}");
+					}
+					return Err(format!(
+						"Chain spec validation failed: {} problem(s)",
+						problems.len()
+					))
+				}
+				println!("Chain spec validation: OK");
+			},
 			ChainSpecBuilderCmd::ListPresets(ListPresetsCmd { runtime }) => {
 				let code = fs::read(runtime.as_path())
 					.map_err(|e| format!("wasm blob shall be readable {e}"))?;
@@ -0,0 +1,167 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Naming of raw storage keys using the metadata of a runtime.
+
+use codec::Decode;
+use frame_metadata::{RuntimeMetadata, RuntimeMetadataPrefixed};
+use sc_chain_spec::GenesisConfigBuilderRuntimeCaller;
+use sp_crypto_hashing::twox_128;
+use std::collections::HashMap;
+
+/// Suffix of the key storing the storage version of a pallet.
+const STORAGE_VERSION_KEY: &[u8] = b":__STORAGE_VERSION__:";
+
+/// Names of the storage items of a runtime, by hashed key prefix.
+#[derive(Debug, Default)]
+pub struct StorageKeyNames {
+	/// Storage prefix of the pallets, by `twox128(prefix)`.
+	pallets: HashMap<[u8; 16], String>,
+	/// `Pallet::Item` names, by `twox128(prefix) ++ twox128(item)`.
+	items: HashMap<[u8; 32], String>,
+}
+
+impl StorageKeyNames {
+	/// Collects the names of the storage items from the metadata of the `code` runtime.
+	pub fn from_runtime(code: &[u8]) -> Result<Self, String> {
+		let metadata = <GenesisConfigBuilderRuntimeCaller>::new(code).metadata()?;
+		let metadata = RuntimeMetadataPrefixed::decode(&mut &metadata[..])
+			.map_err(|e| format!("scale codec error: {e}"))?;
+
+		let pallets: Vec<(String, Vec<String>)> = match metadata.1 {
+			RuntimeMetadata::V14(metadata) => metadata
+				.pallets
+				.into_iter()
+				.map(|pallet| match pallet.storage {
+					Some(storage) =>
+						(storage.prefix, storage.entries.into_iter().map(|e| e.name).collect()),
+					None => (pallet.name, Vec::new()),
+				})
+				.collect(),
+			RuntimeMetadata::V15(metadata) => metadata
+				.pallets
+				.into_iter()
+				.map(|pallet| match pallet.storage {
+					Some(storage) =>
+						(storage.prefix, storage.entries.into_iter().map(|e| e.name).collect()),
+					None => (pallet.name, Vec::new()),
+				})
+				.collect(),
+			_ => return Err("Unsupported metadata version".into()),
+		};
+
+		let mut names = Self::default();
+		for (prefix, items) in pallets {
+			let pallet_hash = twox_128(prefix.as_bytes());
+			let version_key = item_key(&pallet_hash, STORAGE_VERSION_KEY);
+			names
+				.items
+				.insert(version_key, format!("{prefix}::{}", as_str(STORAGE_VERSION_KEY)));
+			for item in items {
+				names
+					.items
+					.insert(item_key(&pallet_hash, item.as_bytes()), format!("{prefix}::{item}"));
+			}
+			names.pallets.insert(pallet_hash, prefix);
+		}
+
+		Ok(names)
+	}
+
+	/// Adds the names of `other`, which take precedence over the names of `self`.
+	pub fn extend(&mut self, other: Self) {
+		self.pallets.extend(other.pallets);
+		self.items.extend(other.items);
+	}
+
+	/// Human readable name of the storage `key`.
+	///
+	/// The keys of storage items are named `Pallet::Item`, followed by the rest of the key if
+	/// any. Well-known keys such as `:code` are named as is, other keys are hex encoded.
+	pub fn name(&self, key: &[u8]) -> String {
+		if let Some(key) = well_known(key) {
+			return key.into()
+		}
+
+		let (name, rest) = if let Some(name) = key.get(..32).and_then(|p| self.items.get(p)) {
+			(name.clone(), &key[32..])
+		} else if let Some(name) = key.get(..16).and_then(|p| self.pallets.get(p)) {
+			(format!("{name}::?"), &key[16..])
+		} else {
+			return array_bytes::bytes2hex("0x", key)
+		};
+
+		if rest.is_empty() {
+			name
+		} else {
+			format!("{name}({})", array_bytes::bytes2hex("0x", rest))
+		}
+	}
+
+	/// Whether `key` belongs to a storage item of the runtime or is a well-known key.
+	pub fn is_known(&self, key: &[u8]) -> bool {
+		well_known(key).is_some() || key.get(..32).map_or(false, |p| self.items.contains_key(p))
+	}
+}
+
+fn item_key(pallet_hash: &[u8; 16], item: &[u8]) -> [u8; 32] {
+	let mut key = [0; 32];
+	key[..16].copy_from_slice(pallet_hash);
+	key[16..].copy_from_slice(&twox_128(item));
+	key
+}
+
+fn as_str(key: &[u8]) -> &str {
+	std::str::from_utf8(key).expect("well-known keys are valid UTF-8; qed")
+}
+
+/// The key as a string if it is a well-known key, e.g. `:code`.
+fn well_known(key: &[u8]) -> Option<&str> {
+	key.starts_with(b":").then(|| std::str::from_utf8(key).ok()).flatten()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn storage_keys_are_named() {
+		let names =
+			StorageKeyNames::from_runtime(substrate_test_runtime::wasm_binary_unwrap()).unwrap();
+
+		let total_issuance = item_key(&twox_128(b"Balances"), b"TotalIssuance");
+		assert_eq!(names.name(&total_issuance), "Balances::TotalIssuance");
+		assert!(names.is_known(&total_issuance));
+
+		let mut account = item_key(&twox_128(b"System"), b"Account").to_vec();
+		account.extend([1, 2]);
+		assert_eq!(names.name(&account), "System::Account(0x0102)");
+
+		let version = item_key(&twox_128(b"Balances"), STORAGE_VERSION_KEY);
+		assert_eq!(names.name(&version), "Balances:::__STORAGE_VERSION__:");
+
+		let mut unknown_item = twox_128(b"System").to_vec();
+		unknown_item.extend([0; 16]);
+		assert!(names.name(&unknown_item).starts_with("System::?(0x"));
+		assert!(!names.is_known(&unknown_item));
+
+		assert_eq!(names.name(b":code"), ":code");
+		assert!(names.is_known(b":code"));
+		assert_eq!(names.name(&[1, 2, 3]), "0x010203");
+	}
+}
@@ -0,0 +1,143 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Validation of chain specs against their runtime.
+
+use crate::storage_keys::StorageKeyNames;
+use sc_chain_spec::GenesisConfigBuilderRuntimeCaller;
+use serde_json::Value;
+
+/// Returns the problems found in the genesis of the `chain_spec` JSON, using the `code` runtime.
+///
+/// For plain chain specs, the fields of the genesis config unknown to the runtime are reported,
+/// as well as the error returned when building the genesis state. For raw chain specs, the keys
+/// which don't belong to any storage item of the runtime are reported.
+pub fn validate(chain_spec: &Value, code: &[u8]) -> Result<Vec<String>, String> {
+	if let Some(runtime_genesis) = chain_spec.pointer("/genesis/runtimeGenesis") {
+		let caller: GenesisConfigBuilderRuntimeCaller =
+			GenesisConfigBuilderRuntimeCaller::new(code);
+		let default_config = caller
+			.get_default_config()
+			.map_err(|e| format!("getting default config from runtime should work: {e}"))?;
+
+		let (config, build_result) = if let Some(patch) = runtime_genesis.get("patch") {
+			(patch, caller.get_storage_for_patch(patch.clone()))
+		} else if let Some(config) = runtime_genesis.get("config") {
+			(config, caller.get_storage_for_config(config.clone()))
+		} else {
+			return Err("The runtime genesis contains neither a config nor a patch".into())
+		};
+
+		let mut problems = unknown_fields(&default_config, config)
+			.into_iter()
+			.map(|path| format!("Unknown genesis config field: {path}"))
+			.collect::<Vec<_>>();
+		if let Err(e) = build_result {
+			problems.push(format!("Building the genesis state failed: {e}"));
+		}
+		Ok(problems)
+	} else if let Some(top) = chain_spec.pointer("/genesis/raw/top").and_then(Value::as_object) {
+		let names = StorageKeyNames::from_runtime(code)?;
+
+		Ok(top
+			.keys()
+			.filter(|key| !array_bytes::hex2bytes(key).map_or(false, |key| names.is_known(&key)))
+			.map(|key| format!("Unknown storage key: {key}"))
+			.collect())
+	} else {
+		Err("The chain spec contains neither a runtime genesis nor a raw genesis".into())
+	}
+}
+
+/// Returns the paths of the fields of the genesis `config` which are not part of the
+/// `default_config` of the runtime.
+///
+/// The content of fields whose default value is `null` or an empty object is not checked, as such
+/// fields are typically optional values or maps.
+pub fn unknown_fields(default_config: &Value, config: &Value) -> Vec<String> {
+	let mut unknown = Vec::new();
+	unknown_fields_at("", default_config, config, &mut unknown);
+	unknown
+}
+
+fn unknown_fields_at(
+	path: &str,
+	default_config: &Value,
+	config: &Value,
+	unknown: &mut Vec<String>,
+) {
+	let (Some(default_config), Some(config)) = (default_config.as_object(), config.as_object())
+	else {
+		return
+	};
+	if default_config.is_empty() {
+		return
+	}
+
+	for (key, value) in config {
+		let path = if path.is_empty() { key.clone() } else { format!("{path}.{key}") };
+		match default_config.get(key) {
+			Some(default_value) => unknown_fields_at(&path, default_value, value, unknown),
+			None => unknown.push(path),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use serde_json::json;
+
+	#[test]
+	fn unknown_fields_are_reported() {
+		let default_config = json!({
+			"balances": {"balances": [], "devAccounts": null},
+			"babe": {"authorities": [], "epochConfig": {"c": [1, 4]}},
+			"system": {},
+		});
+		let config = json!({
+			"balances": {"balances": [["5Gr", 1]], "devAccounts": [1, 2]},
+			"babe": {"epochConfig": {"c": [1, 2], "allowedSlots": "PrimarySlots"}},
+			"system": {"anything": 1},
+			"sudo": {"key": "5Gr"},
+		});
+
+		assert_eq!(
+			unknown_fields(&default_config, &config),
+			vec!["babe.epochConfig.allowedSlots".to_string(), "sudo".to_string()]
+		);
+	}
+
+	#[test]
+	fn validate_works_with_plain_and_raw_chain_specs() {
+		let code = substrate_test_runtime::wasm_binary_unwrap();
+
+		let plain = json!({"genesis": {"runtimeGenesis": {"patch": {
+			"balances": {"balances": [], "unknownField": 1},
+		}}}});
+		let problems = validate(&plain, code).unwrap();
+		assert_eq!(problems[0], "Unknown genesis config field: balances.unknownField");
+		assert!(problems[1].starts_with("Building the genesis state failed"));
+
+		let raw = json!({"genesis": {"raw": {"top": {
+			"0x3a636f6465": "0x00",
+			"0x0102": "0x00",
+		}}}});
+		assert_eq!(validate(&raw, code).unwrap(), vec!["Unknown storage key: 0x0102".to_string()]);
+	}
+}
@@ -0,0 +1,91 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Reference remote signer, serving the keys of a local keystore to a node.
+
+use clap::Parser;
+use sc_keystore::{
+	remote::{serve, RemoteSignerAddress, RemoteSignerSecret},
+	LocalKeystore,
+};
+use sp_core::crypto::SecretString;
+use std::{fs, path::PathBuf, sync::Arc};
+
+/// Serve the keys of a keystore to a node started with `--keystore-remote`.
+#[derive(Debug, Parser)]
+#[command(author, version, about)]
+struct RemoteSigner {
+	/// Path of the keystore holding the keys.
+	#[arg(long, value_name = "PATH")]
+	keystore_path: PathBuf,
+
+	/// File that contains the password used by the keystore.
+	#[arg(long, value_name = "PATH")]
+	password_filename: Option<PathBuf>,
+
+	/// File that contains the password the key files of the keystore are encrypted with.
+	#[arg(long, value_name = "PATH")]
+	encryption_password_filename: Option<PathBuf>,
+
+	/// Address to listen on, either `unix:<PATH>` or `tcp:<HOST>:<PORT>`.
+	#[arg(long, value_name = "ADDRESS")]
+	listen: RemoteSignerAddress,
+
+	/// File that contains the hex encoded secret shared with the node.
+	#[arg(long, value_name = "PATH")]
+	secret_file: PathBuf,
+
+	/// Generate a new secret and write it to `--secret-file` if this file doesn't exist yet.
+	#[arg(long)]
+	generate_secret: bool,
+}
+
+fn main() -> Result<(), String> {
+	sp_tracing::try_init_simple();
+
+	let cli = RemoteSigner::parse();
+
+	if cli.generate_secret && !cli.secret_file.exists() {
+		RemoteSignerSecret::generate()
+			.write_to_file(&cli.secret_file)
+			.map_err(|e| format!("Failed to write {}: {e}", cli.secret_file.display()))?;
+		log::info!("Generated a new secret in {}", cli.secret_file.display());
+	}
+	let secret = RemoteSignerSecret::from_file(&cli.secret_file)
+		.map_err(|e| format!("Failed to read {}: {e}", cli.secret_file.display()))?;
+
+	let password = cli
+		.password_filename
+		.map(|file| fs::read_to_string(&file).map(SecretString::new))
+		.transpose()
+		.map_err(|e| format!("Failed to read the keystore password: {e}"))?;
+	let encryption_password = cli
+		.encryption_password_filename
+		.map(|file| {
+			fs::read_to_string(&file)
+				.map(|p| SecretString::new(p.trim_end_matches(['\n', '\r']).to_owned()))
+		})
+		.transpose()
+		.map_err(|e| format!("Failed to read the keystore encryption password: {e}"))?;
+	let keystore =
+		LocalKeystore::open_with_encryption(cli.keystore_path, password, encryption_password)
+			.map_err(|e| format!("Failed to open the keystore: {e}"))?;
+
+	serve(&cli.listen, secret, Arc::new(keystore))
+		.map_err(|e| format!("Failed to listen on {}: {e}", cli.listen))
+}
@@ -0,0 +1,225 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! RPC api for authority discovery.
+
+use jsonrpsee::{
+	core::async_trait,
+	proc_macros::rpc,
+	types::{ErrorObject, ErrorObjectOwned},
+	Extensions,
+};
+use serde::{Deserialize, Serialize};
+
+use sc_authority_discovery::{PublicationStatus, Service};
+use sc_rpc_api::{check_if_safe, UnsafeRpcError};
+use sp_authority_discovery::AuthorityId;
+
+const AUTHORITY_DISCOVERY_ERROR: i32 = 9100;
+
+/// Provides rpc methods for inspecting the authority discovery records.
+#[rpc(client, server)]
+pub trait AuthorityDiscoveryApi {
+	/// Returns, for each authority of the current and next session, the addresses discovered for
+	/// it and the creation time of its record, along with the outcome of the latest publication
+	/// of the records of the local node.
+	#[method(name = "authorityDiscovery_records", with_extensions)]
+	async fn records(&self) -> Result<Records, Error>;
+
+	/// Publishes the records of the local node right away, instead of waiting for the next
+	/// scheduled publication.
+	#[method(name = "authorityDiscovery_republish", with_extensions)]
+	async fn republish(&self) -> Result<(), Error>;
+}
+
+/// Provides RPC methods for inspecting the authority discovery records.
+pub struct AuthorityDiscovery {
+	/// Service to interact with the authority discovery worker.
+	service: Service,
+}
+
+impl AuthorityDiscovery {
+	/// Creates a new instance of the authority discovery Rpc handler.
+	pub fn new(service: Service) -> Self {
+		Self { service }
+	}
+}
+
+#[async_trait]
+impl AuthorityDiscoveryApiServer for AuthorityDiscovery {
+	async fn records(&self, ext: &Extensions) -> Result<Records, Error> {
+		check_if_safe(ext)?;
+
+		let records = self.service.clone().records().await.ok_or(Error::WorkerNotRunning)?;
+
+		Ok(Records {
+			authorities: records
+				.authorities
+				.into_iter()
+				.map(|authority| DiscoveredAuthority {
+					authority_id: authority.authority_id,
+					addresses: authority.addresses.iter().map(ToString::to_string).collect(),
+					record_creation_time: authority.record_creation_time.map(nanos_to_millis),
+				})
+				.collect(),
+			published: records
+				.published
+				.into_iter()
+				.map(|record| PublishedRecord {
+					authority_id: record.authority_id,
+					creation_time: nanos_to_millis(record.creation_time),
+					status: record.status.into(),
+				})
+				.collect(),
+		})
+	}
+
+	async fn republish(&self, ext: &Extensions) -> Result<(), Error> {
+		check_if_safe(ext)?;
+
+		self.service.clone().republish().await.map_err(|e| match e {
+			sc_authority_discovery::Error::WorkerNotRunning => Error::WorkerNotRunning,
+			e => Error::Republish(e),
+		})
+	}
+}
+
+fn nanos_to_millis(nanos: u128) -> u64 {
+	(nanos / 1_000_000).try_into().unwrap_or(u64::MAX)
+}
+
+/// Records known by the authority discovery worker.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Records {
+	/// Authorities of the current and next session.
+	pub authorities: Vec<DiscoveredAuthority>,
+	/// Records published by the local node at its latest publication.
+	pub published: Vec<PublishedRecord>,
+}
+
+/// An authority of the current or next session.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiscoveredAuthority {
+	/// Id of the authority.
+	pub authority_id: AuthorityId,
+	/// Addresses discovered for the authority.
+	pub addresses: Vec<String>,
+	/// Creation time of the latest record received for the authority, in milliseconds since the
+	/// UNIX epoch. `None` if no record was received yet.
+	pub record_creation_time: Option<u64>,
+}
+
+/// A record published by the local node.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PublishedRecord {
+	/// Authority id the record was published for.
+	pub authority_id: AuthorityId,
+	/// Creation time of the record, in milliseconds since the UNIX epoch.
+	pub creation_time: u64,
+	/// Outcome of the publication.
+	pub status: Status,
+}
+
+/// Outcome of the publication of a record.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Status {
+	/// The record is being put on the DHT.
+	InProgress,
+	/// The record was put on the DHT.
+	Published,
+	/// Putting the record on the DHT failed.
+	Failed,
+}
+
+impl From<PublicationStatus> for Status {
+	fn from(status: PublicationStatus) -> Self {
+		match status {
+			PublicationStatus::InProgress => Self::InProgress,
+			PublicationStatus::Published => Self::Published,
+			PublicationStatus::Failed => Self::Failed,
+		}
+	}
+}
+
+/// Top-level error type for the RPC handler.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+	/// The authority discovery worker is not running.
+	#[error("The authority discovery worker is not running")]
+	WorkerNotRunning,
+	/// Failed to publish the records of the local node.
+	#[error("Failed to publish the records: {0}")]
+	Republish(sc_authority_discovery::Error),
+	/// Call to an unsafe RPC was denied.
+	#[error(transparent)]
+	UnsafeRpcCalled(#[from] UnsafeRpcError),
+}
+
+impl From<Error> for ErrorObjectOwned {
+	fn from(error: Error) -> Self {
+		match error {
+			Error::WorkerNotRunning =>
+				ErrorObject::owned(AUTHORITY_DISCOVERY_ERROR + 1, error.to_string(), None::<()>),
+			Error::Republish(_) =>
+				ErrorObject::owned(AUTHORITY_DISCOVERY_ERROR + 2, error.to_string(), None::<()>),
+			Error::UnsafeRpcCalled(e) => e.into(),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sc_rpc_api::DenyUnsafe;
+
+	fn test_authority_discovery_rpc_module() -> AuthorityDiscovery {
+		// The receiver is dropped right away, as if the worker was not running.
+		let (service, _) = sc_authority_discovery::new_service();
+		AuthorityDiscovery::new(service)
+	}
+
+	#[tokio::test]
+	async fn records_fails_without_worker() {
+		let mut api = test_authority_discovery_rpc_module().into_rpc();
+		api.extensions_mut().insert(DenyUnsafe::No);
+
+		let request =
+			r#"{"jsonrpc":"2.0","id":1,"method":"authorityDiscovery_records","params":[]}"#;
+		let (response, _) = api.raw_json_request(request, 1).await.unwrap();
+		let expected = r#"{"jsonrpc":"2.0","id":1,"error":{"code":9101,"message":"The authority discovery worker is not running"}}"#;
+
+		assert_eq!(response, expected);
+	}
+
+	#[tokio::test]
+	async fn republish_is_unsafe() {
+		let mut api = test_authority_discovery_rpc_module().into_rpc();
+		api.extensions_mut().insert(DenyUnsafe::Yes);
+
+		let request =
+			r#"{"jsonrpc":"2.0","method":"authorityDiscovery_republish","params":[],"id":1}"#;
+		let (response, _) = api.raw_json_request(request, 1).await.unwrap();
+		let expected = r#"{"jsonrpc":"2.0","id":1,"error":{"code":-32601,"message":"RPC call is unsafe to be called externally"}}"#;
+
+		assert_eq!(response, expected);
+	}
+}
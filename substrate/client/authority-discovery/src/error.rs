@@ -81,4 +81,7 @@ pub enum Error {
 
 	#[error("Unknown authority.")]
 	UnknownAuthority,
+
+	#[error("The authority discovery worker is not running.")]
+	WorkerNotRunning,
 }
@@ -29,7 +29,7 @@
 
 pub use crate::{
 	error::Error,
-	service::Service,
+	service::{DiscoveredAuthority, PublicationStatus, PublishedRecord, Records, Service},
 	worker::{AuthorityDiscovery, NetworkProvider, Role, Worker},
 };
 
@@ -155,19 +155,70 @@ where
 	Client: AuthorityDiscovery<Block> + 'static,
 	DhtEventStream: Stream<Item = DhtEvent> + Unpin,
 {
-	let (to_worker, from_service) = mpsc::channel(0);
+	let (service, service_receiver) = new_service();
 
-	let worker =
-		Worker::new(from_service, client, network, dht_event_rx, role, prometheus_registry, config);
-	let service = Service::new(to_worker);
+	let worker = new_worker_for_service(
+		config,
+		service_receiver,
+		client,
+		network,
+		dht_event_rx,
+		role,
+		prometheus_registry,
+	);
 
 	(worker, service)
 }
 
+/// Receiving side of a [`Service`] created with [`new_service`].
+pub struct ServiceReceiver(mpsc::Receiver<ServicetoWorkerMsg>);
+
+/// Create a [`Service`] ahead of its [`Worker`], e.g. to hand it to the RPC layer before the
+/// network is started.
+///
+/// The [`Worker`] serving it is created with [`new_worker_for_service`]. Requests made through the
+/// [`Service`] fail if the [`ServiceReceiver`] is dropped instead.
+pub fn new_service() -> (Service, ServiceReceiver) {
+	let (to_worker, from_service) = mpsc::channel(0);
+
+	(Service::new(to_worker), ServiceReceiver(from_service))
+}
+
+/// Same as [`new_worker_and_service_with_config`], but for a [`Service`] created beforehand with
+/// [`new_service`].
+pub fn new_worker_for_service<Client, Block, DhtEventStream>(
+	config: WorkerConfig,
+	service_receiver: ServiceReceiver,
+	client: Arc<Client>,
+	network: Arc<dyn NetworkProvider>,
+	dht_event_rx: DhtEventStream,
+	role: Role,
+	prometheus_registry: Option<prometheus_endpoint::Registry>,
+) -> Worker<Client, Block, DhtEventStream>
+where
+	Block: BlockT + Unpin + 'static,
+	Client: AuthorityDiscovery<Block> + 'static,
+	DhtEventStream: Stream<Item = DhtEvent> + Unpin,
+{
+	Worker::new(
+		service_receiver.0,
+		client,
+		network,
+		dht_event_rx,
+		role,
+		prometheus_registry,
+		config,
+	)
+}
+
 /// Message send from the [`Service`] to the [`Worker`].
 pub(crate) enum ServicetoWorkerMsg {
 	/// See [`Service::get_addresses_by_authority_id`].
 	GetAddressesByAuthorityId(AuthorityId, oneshot::Sender<Option<HashSet<Multiaddr>>>),
 	/// See [`Service::get_authority_ids_by_peer_id`].
 	GetAuthorityIdsByPeerId(PeerId, oneshot::Sender<Option<HashSet<AuthorityId>>>),
+	/// See [`Service::records`].
+	GetRecords(oneshot::Sender<Records>),
+	/// See [`Service::republish`].
+	Republish(oneshot::Sender<error::Result<()>>),
 }
@@ -18,7 +18,7 @@
 
 use std::{collections::HashSet, fmt::Debug};
 
-use crate::ServicetoWorkerMsg;
+use crate::{error::Error, ServicetoWorkerMsg};
 
 use futures::{
 	channel::{mpsc, oneshot},
@@ -29,6 +29,51 @@ use sc_network::Multiaddr;
 use sc_network_types::PeerId;
 use sp_authority_discovery::AuthorityId;
 
+/// An authority of the current or next session, as known by a [`crate::Worker`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiscoveredAuthority {
+	/// Id of the authority.
+	pub authority_id: AuthorityId,
+	/// Addresses discovered for the authority, empty if none was discovered yet.
+	pub addresses: Vec<Multiaddr>,
+	/// Creation time of the latest record received for the authority, in nanoseconds since the
+	/// UNIX epoch.
+	///
+	/// `None` if no record was received yet, `Some(0)` if the record has no creation time.
+	pub record_creation_time: Option<u128>,
+}
+
+/// Outcome of putting a record of the local node on the DHT.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PublicationStatus {
+	/// The record is being put on the DHT.
+	InProgress,
+	/// The record was put on the DHT.
+	Published,
+	/// Putting the record on the DHT failed.
+	Failed,
+}
+
+/// A record published by the local node for one of its authority ids.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PublishedRecord {
+	/// Authority id the record was published for.
+	pub authority_id: AuthorityId,
+	/// Creation time of the record, in nanoseconds since the UNIX epoch.
+	pub creation_time: u128,
+	/// Outcome of the publication.
+	pub status: PublicationStatus,
+}
+
+/// Records known by a [`crate::Worker`], see [`Service::records`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Records {
+	/// Authorities of the current and next session.
+	pub authorities: Vec<DiscoveredAuthority>,
+	/// Records published by the local node at its latest publication.
+	pub published: Vec<PublishedRecord>,
+}
+
 /// Service to interact with the [`crate::Worker`].
 #[derive(Clone)]
 pub struct Service {
@@ -92,4 +137,29 @@ impl Service {
 
 		rx.await.ok().flatten()
 	}
+
+	/// Get the authorities of the current and next session along with what was discovered about
+	/// them, and the records published by the local node.
+	///
+	/// Returns `None` if connection to the [`crate::Worker`] failed.
+	pub async fn records(&mut self) -> Option<Records> {
+		let (tx, rx) = oneshot::channel();
+
+		self.to_worker.send(ServicetoWorkerMsg::GetRecords(tx)).await.ok()?;
+
+		rx.await.ok()
+	}
+
+	/// Publish the addresses of the local node on the DHT right away, instead of waiting for the
+	/// next scheduled publication.
+	pub async fn republish(&mut self) -> Result<(), Error> {
+		let (tx, rx) = oneshot::channel();
+
+		self.to_worker
+			.send(ServicetoWorkerMsg::Republish(tx))
+			.await
+			.map_err(|_| Error::WorkerNotRunning)?;
+
+		rx.await.map_err(|_| Error::WorkerNotRunning)?
+	}
 }
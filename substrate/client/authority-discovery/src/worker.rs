@@ -19,7 +19,8 @@
 use crate::{
 	error::{Error, Result},
 	interval::ExpIncInterval,
-	ServicetoWorkerMsg, WorkerConfig,
+	DiscoveredAuthority, PublicationStatus, PublishedRecord, Records, ServicetoWorkerMsg,
+	WorkerConfig,
 };
 
 use std::{
@@ -137,9 +138,9 @@ pub struct Worker<Client, Block: BlockT, DhtEventStream> {
 	/// List of keys onto which addresses have been published at the latest publication.
 	/// Used to check whether they have changed.
 	latest_published_keys: HashSet<AuthorityId>,
-	/// List of the kademlia keys that have been published at the latest publication.
+	/// Records published at the latest publication, by kademlia key.
 	/// Used to associate DHT events with our published records.
-	latest_published_kad_keys: HashSet<KademliaKey>,
+	latest_published_records: HashMap<KademliaKey, PublishedRecord>,
 
 	/// Same value as in the configuration.
 	publish_non_global_ips: bool,
@@ -294,7 +295,7 @@ where
 			authorities_queried_at: None,
 			publish_if_changed_interval,
 			latest_published_keys: HashSet::new(),
-			latest_published_kad_keys: HashSet::new(),
+			latest_published_records: HashMap::new(),
 			publish_non_global_ips: config.publish_non_global_ips,
 			public_addresses,
 			strict_record_validation: config.strict_record_validation,
@@ -329,7 +330,7 @@ where
 				},
 				// Handle messages from [`Service`]. Ignore if sender side is closed.
 				msg = self.from_service.select_next_some() => {
-					self.process_message_from_service(msg);
+					self.process_message_from_service(msg).await;
 				},
 				// Publish own addresses.
 				only_if_changed = future::select(
@@ -356,7 +357,7 @@ where
 		}
 	}
 
-	fn process_message_from_service(&self, msg: ServicetoWorkerMsg) {
+	async fn process_message_from_service(&mut self, msg: ServicetoWorkerMsg) {
 		match msg {
 			ServicetoWorkerMsg::GetAddressesByAuthorityId(authority, sender) => {
 				let _ = sender.send(
@@ -367,9 +368,42 @@ where
 				let _ = sender
 					.send(self.addr_cache.get_authority_ids_by_peer_id(&peer_id).map(Clone::clone));
 			},
+			ServicetoWorkerMsg::GetRecords(sender) => {
+				let _ = sender.send(self.records());
+			},
+			ServicetoWorkerMsg::Republish(sender) => {
+				let _ = sender.send(self.publish_ext_addresses(false).await);
+			},
 		}
 	}
 
+	/// Records known about the current and next authorities, and records published by the local
+	/// node.
+	fn records(&self) -> Records {
+		let mut authorities = self
+			.known_authorities
+			.iter()
+			.map(|(key, authority_id)| DiscoveredAuthority {
+				authority_id: authority_id.clone(),
+				addresses: self
+					.addr_cache
+					.get_addresses_by_authority_id(authority_id)
+					.map(|addresses| addresses.iter().cloned().collect())
+					.unwrap_or_default(),
+				record_creation_time: self
+					.last_known_records
+					.get(key)
+					.map(|record| record.creation_time),
+			})
+			.collect::<Vec<_>>();
+		authorities.sort_by(|a, b| a.authority_id.cmp(&b.authority_id));
+
+		let mut published = self.latest_published_records.values().cloned().collect::<Vec<_>>();
+		published.sort_by(|a, b| a.authority_id.cmp(&b.authority_id));
+
+		Records { authorities, published }
+	}
+
 	fn addresses_to_publish(&mut self) -> impl Iterator<Item = Multiaddr> {
 		let local_peer_id = self.network.local_peer_id();
 		let publish_non_global_ips = self.publish_non_global_ips;
@@ -515,7 +549,9 @@ where
 				.set(addresses.len().try_into().unwrap_or(std::u64::MAX));
 		}
 
-		let serialized_record = serialize_authority_record(addresses, Some(build_creation_time()))?;
+		let creation_time = build_creation_time();
+		let published_at = u128::decode(&mut &creation_time.timestamp[..]).unwrap_or_default();
+		let serialized_record = serialize_authority_record(addresses, Some(creation_time))?;
 		let peer_signature = sign_record_with_peer_id(&serialized_record, &self.network)?;
 
 		let keys_vec = keys.iter().cloned().collect::<Vec<_>>();
@@ -524,10 +560,22 @@ where
 			serialized_record,
 			Some(peer_signature),
 			key_store.as_ref(),
-			keys_vec,
+			keys_vec.clone(),
 		)?;
 
-		self.latest_published_kad_keys = kv_pairs.iter().map(|(k, _)| k.clone()).collect();
+		self.latest_published_records = kv_pairs
+			.iter()
+			.map(|(k, _)| k.clone())
+			.zip(keys_vec)
+			.map(|(key, authority_id)| {
+				let record = PublishedRecord {
+					authority_id,
+					creation_time: published_at,
+					status: PublicationStatus::InProgress,
+				};
+				(key, record)
+			})
+			.collect();
 
 		for (key, value) in kv_pairs.into_iter() {
 			self.network.put_value(key, value);
@@ -638,9 +686,10 @@ where
 				}
 			},
 			DhtEvent::ValuePut(hash) => {
-				if !self.latest_published_kad_keys.contains(&hash) {
+				let Some(record) = self.latest_published_records.get_mut(&hash) else {
 					return;
-				}
+				};
+				record.status = PublicationStatus::Published;
 
 				// Fast forward the exponentially increasing interval to the configured maximum. In
 				// case this was the first successful address publishing there is no need for a
@@ -654,9 +703,13 @@ where
 				debug!(target: LOG_TARGET, "Successfully put hash '{:?}' on Dht.", hash)
 			},
 			DhtEvent::ValuePutFailed(hash) => {
-				if !self.latest_published_kad_keys.contains(&hash) {
+				let Some(record) = self.latest_published_records.get_mut(&hash) else {
 					// Not a value we have published or received multiple times.
 					return;
+				};
+				// Another put of the same record may already have succeeded.
+				if record.status == PublicationStatus::InProgress {
+					record.status = PublicationStatus::Failed;
 				}
 
 				if let Some(metrics) = &self.metrics {
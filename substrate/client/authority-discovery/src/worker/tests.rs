@@ -445,6 +445,70 @@ fn publish_discover_cycle() {
 	pool.run();
 }
 
+#[test]
+fn records_report_discovered_authorities_and_publication_status() {
+	let network: Arc<TestNetwork> = Arc::new(Default::default());
+	let key_store = MemoryKeystore::new();
+	let local_public =
+		key_store.sr25519_generate_new(key_types::AUTHORITY_DISCOVERY, None).unwrap();
+	let remote_public: AuthorityId = MemoryKeystore::new()
+		.sr25519_generate_new(key_types::AUTHORITY_DISCOVERY, None)
+		.unwrap()
+		.into();
+	let test_api =
+		Arc::new(TestApi { authorities: vec![local_public.into(), remote_public.clone()] });
+
+	let (_dht_event_tx, dht_event_rx) = channel(1);
+	let (_to_worker, from_service) = mpsc::channel(0);
+	let mut worker = Worker::new(
+		from_service,
+		test_api,
+		network.clone(),
+		Box::pin(dht_event_rx),
+		Role::PublishAndDiscover(key_store.into()),
+		None,
+		Default::default(),
+	);
+
+	block_on(async {
+		worker.refill_pending_lookups_queue().await.unwrap();
+
+		// The remote authority is known, but nothing was discovered about it yet.
+		let (sender, records) = futures::channel::oneshot::channel();
+		worker
+			.process_message_from_service(ServicetoWorkerMsg::GetRecords(sender))
+			.await;
+		let records = records.await.unwrap();
+		assert_eq!(
+			records.authorities,
+			vec![DiscoveredAuthority {
+				authority_id: remote_public.clone(),
+				addresses: vec![],
+				record_creation_time: None,
+			}]
+		);
+		assert!(records.published.is_empty());
+
+		let remote_addr: Multiaddr = "/ip6/2001:db8:0:0:0:0:0:2/tcp/30333".parse().unwrap();
+		let remote_addr = remote_addr.with(Protocol::P2p(PeerId::random().into()));
+		worker.inject_addresses(remote_public.clone(), vec![remote_addr.clone()]);
+		assert_eq!(worker.records().authorities[0].addresses, vec![remote_addr]);
+
+		// Force a publication of the local record.
+		let (sender, result) = futures::channel::oneshot::channel();
+		worker.process_message_from_service(ServicetoWorkerMsg::Republish(sender)).await;
+		result.await.unwrap().unwrap();
+		let published = worker.records().published;
+		assert_eq!(published.len(), 1);
+		assert_eq!(published[0].authority_id, AuthorityId::from(local_public));
+		assert_eq!(published[0].status, PublicationStatus::InProgress);
+
+		let (key, _) = network.put_value_call.lock().unwrap().pop().unwrap();
+		worker.handle_dht_event(DhtEvent::ValuePut(key)).await;
+		assert_eq!(worker.records().published[0].status, PublicationStatus::Published);
+	});
+}
+
 /// Don't terminate when sender side of service channel is dropped. Terminate when network event
 /// stream terminates.
 #[test]
@@ -82,6 +82,8 @@ pub struct ProposerFactory<A, C, PR> {
 	telemetry: Option<TelemetryHandle>,
 	/// When estimating the block size, should the proof be included?
 	include_proof_in_block_size_estimation: bool,
+	/// The maximum number of transactions to include in a block, unlimited if `None`.
+	max_transactions: Option<usize>,
 	/// phantom member to pin the `ProofRecording` type.
 	_phantom: PhantomData<PR>,
 }
@@ -97,6 +99,7 @@ impl<A, C, PR> Clone for ProposerFactory<A, C, PR> {
 			soft_deadline_percent: self.soft_deadline_percent,
 			telemetry: self.telemetry.clone(),
 			include_proof_in_block_size_estimation: self.include_proof_in_block_size_estimation,
+			max_transactions: self.max_transactions,
 			_phantom: self._phantom,
 		}
 	}
@@ -123,6 +126,7 @@ impl<A, C> ProposerFactory<A, C, DisableProofRecording> {
 			telemetry,
 			client,
 			include_proof_in_block_size_estimation: false,
+			max_transactions: None,
 			_phantom: PhantomData,
 		}
 	}
@@ -151,6 +155,7 @@ impl<A, C> ProposerFactory<A, C, EnableProofRecording> {
 			soft_deadline_percent: DEFAULT_SOFT_DEADLINE_PERCENT,
 			telemetry,
 			include_proof_in_block_size_estimation: true,
+			max_transactions: None,
 			_phantom: PhantomData,
 		}
 	}
@@ -188,6 +193,13 @@ impl<A, C, PR> ProposerFactory<A, C, PR> {
 	pub fn set_soft_deadline(&mut self, percent: Percent) {
 		self.soft_deadline_percent = percent;
 	}
+
+	/// Set the maximum number of transactions to include in a block.
+	///
+	/// By default the number of transactions is only limited by the block size and weight.
+	pub fn set_max_transactions(&mut self, max_transactions: Option<usize>) {
+		self.max_transactions = max_transactions;
+	}
 }
 
 impl<Block, C, A, PR> ProposerFactory<A, C, PR>
@@ -223,6 +235,7 @@ where
 			telemetry: self.telemetry.clone(),
 			_phantom: PhantomData,
 			include_proof_in_block_size_estimation: self.include_proof_in_block_size_estimation,
+			max_transactions: self.max_transactions,
 		};
 
 		proposer
@@ -259,6 +272,7 @@ pub struct Proposer<Block: BlockT, C, A: TransactionPool, PR> {
 	include_proof_in_block_size_estimation: bool,
 	soft_deadline_percent: Percent,
 	telemetry: Option<TelemetryHandle>,
+	max_transactions: Option<usize>,
 	_phantom: PhantomData<PR>,
 }
 
@@ -423,8 +437,17 @@ where
 
 		debug!(target: LOG_TARGET, "Attempting to push transactions from the pool at {:?}.", self.parent_hash);
 		let mut transaction_pushed = false;
+		let mut transactions_pushed = 0;
 
 		let end_reason = loop {
+			if self.max_transactions.map_or(false, |max| transactions_pushed >= max) {
+				debug!(
+					target: LOG_TARGET,
+					"Reached the maximum number of transactions, proceeding with proposing."
+				);
+				break EndProposingReason::HitTransactionLimit
+			}
+
 			let pending_tx = if let Some(pending_tx) = pending_iterator.next() {
 				pending_tx
 			} else {
@@ -483,6 +506,7 @@ where
 			match sc_block_builder::BlockBuilder::push(block_builder, pending_tx_data) {
 				Ok(()) => {
 					transaction_pushed = true;
+					transactions_pushed += 1;
 					trace!(target: LOG_TARGET, "[{:?}] Pushed to the block.", pending_tx_hash);
 				},
 				Err(ApplyExtrinsicFailed(Validity(e))) if e.exhausted_resources() => {
@@ -782,6 +782,22 @@ pub fn update_code_in_json_chain_spec(chain_spec: &mut json::Value, code: &[u8])
 	}
 }
 
+/// This function returns the code of given chain spec.
+///
+/// Function supports reading the runtime code from provided JSON chain spec blob. `Genesis::Raw`
+/// and `Genesis::RuntimeGenesis` formats are supported.
+///
+/// Returns `None` if the chain spec contains no valid code.
+pub fn code_in_json_chain_spec(chain_spec: &json::Value) -> Option<Vec<u8>> {
+	let genesis = chain_spec.get("genesis")?;
+	let code = genesis
+		.get("runtimeGenesis")
+		.and_then(|runtime_genesis| runtime_genesis.get("code"))
+		.or_else(|| genesis.get("raw")?.get("top")?.get("0x3a636f6465"))?;
+
+	sp_core::bytes::from_hex(code.as_str()?).ok()
+}
+
 /// This function sets a codeSubstitute in the chain spec.
 pub fn set_code_substitute_in_json_chain_spec(
 	chain_spec: &mut json::Value,
@@ -1187,6 +1203,24 @@ mod tests {
 		));
 	}
 
+	#[test]
+	fn code_in_json_chain_spec_works() {
+		let chain_spec = ChainSpec::<()>::builder(&[1, 2, 3], Default::default())
+			.with_name("TestName")
+			.with_id("test_id")
+			.with_chain_type(ChainType::Local)
+			.with_genesis_config_patch(json!({}))
+			.build();
+		let mut chain_spec_json = from_str::<Value>(&chain_spec.as_json(false).unwrap()).unwrap();
+		assert_eq!(code_in_json_chain_spec(&chain_spec_json), Some(vec![1, 2, 3]));
+
+		chain_spec_json["genesis"] = json!({"raw": {"top": {"0x3a636f6465": "0x040506"}}});
+		assert_eq!(code_in_json_chain_spec(&chain_spec_json), Some(vec![4, 5, 6]));
+
+		chain_spec_json["genesis"] = json!({"raw": {"top": {}}});
+		assert_eq!(code_in_json_chain_spec(&chain_spec_json), None);
+	}
+
 	#[test]
 	fn update_code_works_with_runtime_genesis_patch() {
 		let chain_spec = ChainSpec::<()>::builder(
@@ -160,6 +160,18 @@ where
 		self.get_storage_for_patch(self.get_named_preset(name)?)
 	}
 
+	/// Returns the SCALE encoded metadata of the `runtime`.
+	///
+	/// Calls `Metadata_metadata` provided by the `runtime`.
+	pub fn metadata(&self) -> core::result::Result<Vec<u8>, String> {
+		let mut t = BasicExternalities::new_empty();
+		let call_result = self
+			.call(&mut t, "Metadata_metadata", &[])
+			.map_err(|e| format!("wasm call error {e}"))?;
+
+		Vec::<u8>::decode(&mut &call_result[..]).map_err(|e| format!("scale codec error: {e}"))
+	}
+
 	pub fn preset_names(&self) -> core::result::Result<Vec<PresetId>, String> {
 		let mut t = BasicExternalities::new_empty();
 		let call_result = self
@@ -190,6 +202,16 @@ mod tests {
 		assert_eq!(presets, vec![PresetId::from("foobar"), PresetId::from("staging"),]);
 	}
 
+	#[test]
+	fn metadata_works() {
+		let metadata =
+			<GenesisConfigBuilderRuntimeCaller>::new(substrate_test_runtime::wasm_binary_unwrap())
+				.metadata()
+				.unwrap();
+		// The metadata starts with the `meta` magic number.
+		assert_eq!(&metadata[..4], b"meta");
+	}
+
 	#[test]
 	fn get_default_config_works() {
 		let config =
@@ -54,6 +54,9 @@ pub enum WasmExecutionMethod {
 	Interpreted,
 	/// Uses a compiled runtime.
 	Compiled,
+	/// Uses the experimental PolkaVM executor. Only PolkaVM runtime blobs are accepted.
+	#[clap(name = "polkavm")]
+	PolkaVM,
 }
 
 impl std::fmt::Display for WasmExecutionMethod {
@@ -61,6 +64,7 @@ impl std::fmt::Display for WasmExecutionMethod {
 		match self {
 			Self::Interpreted => write!(f, "Interpreted"),
 			Self::Compiled => write!(f, "Compiled"),
+			Self::PolkaVM => write!(f, "PolkaVM"),
 		}
 	}
 }
@@ -77,6 +81,13 @@ pub fn execution_method_from_cli(
 		);
 	}
 
+	if let WasmExecutionMethod::PolkaVM = execution_method {
+		log::warn!(
+			"`polkavm` is experimental and must not be used in production; only PolkaVM runtimes can be executed."
+		);
+		return sc_service::config::WasmExecutionMethod::PolkaVM
+	}
+
 	sc_service::config::WasmExecutionMethod::Compiled {
 		instantiation_strategy: match instantiation_strategy {
 			WasmtimeInstantiationStrategy::PoolingCopyOnWrite =>
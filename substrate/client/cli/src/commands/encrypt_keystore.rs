@@ -0,0 +1,158 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Implementation of the `encrypt-keystore` subcommand
+
+use crate::{Error, KeystoreParams, SharedParams, SubstrateCli};
+use clap::Parser;
+use sc_keystore::LocalKeystore;
+use sc_service::config::{BasePath, KeystoreConfig};
+
+/// The `encrypt-keystore` command
+#[derive(Debug, Clone, Parser)]
+#[command(
+	name = "encrypt-keystore",
+	about = "Encrypt the key files of the keystore of a node at rest."
+)]
+pub struct EncryptKeystoreCmd {
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub keystore_params: KeystoreParams,
+}
+
+impl EncryptKeystoreCmd {
+	/// Run the command
+	pub fn run<C: SubstrateCli>(&self, cli: &C) -> Result<(), Error> {
+		let base_path = self
+			.shared_params
+			.base_path()?
+			.unwrap_or_else(|| BasePath::from_project("", "", &C::executable_name()));
+		let chain_id = self.shared_params.chain_id(self.shared_params.is_dev());
+		let chain_spec = cli.load_spec(&chain_id)?;
+		let config_dir = base_path.config_dir(chain_spec.id());
+
+		let (path, encryption_password) = match self.keystore_params.keystore_config(&config_dir)? {
+			KeystoreConfig::Path { path, encryption_password: Some(password), .. } =>
+				(path, password),
+			KeystoreConfig::Path { encryption_password: None, .. } =>
+				return Err(Error::Input(
+					"An encryption password is required, pass it with \
+						 `--keystore-encryption-password-filename` or the \
+						 `KEYSTORE_ENCRYPTION_PASSWORD` environment variable"
+						.into(),
+				)),
+			KeystoreConfig::Remote { .. } | KeystoreConfig::InMemory =>
+				return Err(Error::Input("Only a local keystore can be encrypted".into())),
+		};
+
+		let encrypted = LocalKeystore::encrypt(&path, &encryption_password)?;
+		println!("Encrypted {} key file(s) in {}", encrypted, path.display());
+
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sc_service::{ChainSpec, ChainType, GenericChainSpec, NoExtension};
+	use sp_core::{crypto::SecretString, sr25519, ByteArray, Pair};
+	use sp_keystore::Keystore;
+	use tempfile::TempDir;
+
+	struct Cli;
+
+	impl SubstrateCli for Cli {
+		fn impl_name() -> String {
+			"test".into()
+		}
+
+		fn impl_version() -> String {
+			"2.0".into()
+		}
+
+		fn description() -> String {
+			"test".into()
+		}
+
+		fn support_url() -> String {
+			"test.test".into()
+		}
+
+		fn copyright_start_year() -> i32 {
+			2021
+		}
+
+		fn author() -> String {
+			"test".into()
+		}
+
+		fn load_spec(&self, _: &str) -> std::result::Result<Box<dyn ChainSpec>, String> {
+			let builder =
+				GenericChainSpec::<NoExtension, ()>::builder(Default::default(), NoExtension::None);
+			Ok(Box::new(
+				builder
+					.with_name("test")
+					.with_id("test_id")
+					.with_chain_type(ChainType::Development)
+					.with_genesis_config_patch(Default::default())
+					.build(),
+			))
+		}
+	}
+
+	#[test]
+	fn encrypt_keystore() {
+		let path = TempDir::new().unwrap();
+		let path_str = format!("{}", path.path().display());
+		let keystore_path = path.path().join("chains").join("test_id").join("keystore");
+
+		let pair = sr25519::Pair::from_string("//Alice", None).unwrap();
+		LocalKeystore::open(&keystore_path, None)
+			.unwrap()
+			.insert(sp_core::crypto::key_types::AURA, "//Alice", pair.public().as_slice())
+			.unwrap();
+
+		let password_file = path.path().join("password");
+		std::fs::write(&password_file, "secret\n").unwrap();
+
+		let cmd = EncryptKeystoreCmd::parse_from(&[
+			"encrypt-keystore",
+			"-d",
+			&path_str,
+			"--keystore-encryption-password-filename",
+			&format!("{}", password_file.display()),
+		]);
+		cmd.run(&Cli).unwrap();
+
+		assert!(LocalKeystore::open(&keystore_path, None).is_err());
+		let keystore = LocalKeystore::open_with_encryption(
+			&keystore_path,
+			None,
+			Some(SecretString::new("secret".into())),
+		)
+		.unwrap();
+		assert!(
+			keystore.has_keys(&[(pair.public().to_raw_vec(), sp_core::crypto::key_types::AURA)])
+		);
+	}
+}
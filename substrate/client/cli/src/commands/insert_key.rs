@@ -67,12 +67,19 @@ impl InsertKeyCmd {
 		let config_dir = base_path.config_dir(chain_spec.id());
 
 		let (keystore, public) = match self.keystore_params.keystore_config(&config_dir)? {
-			KeystoreConfig::Path { path, password } => {
+			KeystoreConfig::Path { path, password, encryption_password } => {
 				let public = with_crypto_scheme!(self.scheme, to_vec(&suri, password.clone()))?;
-				let keystore: KeystorePtr = LocalKeystore::open(path, password)?.into();
+				let keystore: KeystorePtr =
+					LocalKeystore::open_with_encryption(path, password, encryption_password)?
+						.into();
 				(keystore, public)
 			},
-			_ => unreachable!("keystore_config always returns path and password; qed"),
+			KeystoreConfig::Remote { .. } =>
+				return Err(Error::Input(
+					"Keys of a remote signer have to be inserted into the remote signer".into(),
+				)),
+			KeystoreConfig::InMemory =>
+				unreachable!("keystore_config never returns an in-memory keystore; qed"),
 		};
 
 		let key_type =
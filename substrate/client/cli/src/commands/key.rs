@@ -19,8 +19,13 @@
 //! Key related CLI utilities
 
 use super::{
-	generate::GenerateCmd, generate_node_key::GenerateNodeKeyCmd, insert_key::InsertKeyCmd,
-	inspect_key::InspectKeyCmd, inspect_node_key::InspectNodeKeyCmd,
+	encrypt_keystore::EncryptKeystoreCmd,
+	generate::GenerateCmd,
+	generate_node_key::GenerateNodeKeyCmd,
+	insert_key::InsertKeyCmd,
+	inspect_key::InspectKeyCmd,
+	inspect_node_key::InspectNodeKeyCmd,
+	slashing_protection::{ExportSlashingProtectionCmd, ImportSlashingProtectionCmd},
 };
 use crate::{Error, SubstrateCli};
 
@@ -42,6 +47,15 @@ pub enum KeySubcommand {
 
 	/// Insert a key to the keystore of a node.
 	Insert(InsertKeyCmd),
+
+	/// Encrypt the key files of the keystore of a node at rest.
+	EncryptKeystore(EncryptKeystoreCmd),
+
+	/// Export the slashing protection history of the keystore of a node.
+	ExportSlashingProtection(ExportSlashingProtectionCmd),
+
+	/// Import a slashing protection history into the keystore of a node.
+	ImportSlashingProtection(ImportSlashingProtectionCmd),
 }
 
 impl KeySubcommand {
@@ -56,6 +70,9 @@ impl KeySubcommand {
 			KeySubcommand::Inspect(cmd) => cmd.run(),
 			KeySubcommand::Insert(cmd) => cmd.run(cli),
 			KeySubcommand::InspectNodeKey(cmd) => cmd.run(),
+			KeySubcommand::EncryptKeystore(cmd) => cmd.run(cli),
+			KeySubcommand::ExportSlashingProtection(cmd) => cmd.run(cli),
+			KeySubcommand::ImportSlashingProtection(cmd) => cmd.run(cli),
 		}
 	}
 }
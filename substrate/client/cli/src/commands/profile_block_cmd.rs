@@ -0,0 +1,181 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+	error,
+	params::{BlockNumberOrHash, DatabaseParams, PruningParams, SharedParams},
+	CliConfiguration,
+};
+use clap::Parser;
+use codec::Encode;
+use log::info;
+use sc_client_api::{
+	backend::TrieCacheContext, Backend as ClientBackend, BlockBackend, HeaderBackend,
+};
+use sc_executor::{
+	sp_wasm_interface::HostFunctions, HeapAllocStrategy, Profiler, WasmExecutionMethod,
+	WasmExecutor, DEFAULT_HEAP_ALLOC_STRATEGY,
+};
+use sp_core::traits::{CallContext, ReadRuntimeVersionExt};
+use sp_runtime::{
+	generic::DigestItem,
+	traits::{Block as BlockT, Header as HeaderT},
+};
+use sp_state_machine::{backend::BackendRuntimeCode, OverlayedChanges, StateMachine};
+use std::{
+	fmt::Debug, fs::File, io::BufWriter, path::PathBuf, str::FromStr, sync::Arc, time::Duration,
+};
+
+/// The `profile-block` command used to profile the execution of a block.
+///
+/// Re-executes the given block on top of its parent state with a profiling wasm executor and
+/// writes the sampled wasm stacks in the folded format understood by `flamegraph.pl` and
+/// `inferno-flamegraph`.
+#[derive(Debug, Clone, Parser)]
+pub struct ProfileBlockCmd {
+	/// Block hash or number.
+	#[arg(value_name = "HASH or NUMBER")]
+	pub input: BlockNumberOrHash,
+
+	/// Output file for the folded stacks.
+	#[arg(long, short, value_name = "PATH", default_value = "block-profile.folded")]
+	pub output: PathBuf,
+
+	/// Interval in microseconds between two samples of the wasm stack.
+	#[arg(long, value_name = "MICROSECONDS", default_value_t = 100)]
+	pub sample_interval: u64,
+
+	/// The default number of 64KB pages to ever allocate for Wasm execution.
+	/// Don't alter this unless you know what you're doing.
+	#[arg(long, value_name = "COUNT")]
+	pub default_heap_pages: Option<u32>,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub pruning_params: PruningParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub database_params: DatabaseParams,
+}
+
+impl ProfileBlockCmd {
+	/// Run the profile-block command
+	pub fn run<B, BA, C, H>(&self, client: Arc<C>, backend: Arc<BA>) -> error::Result<()>
+	where
+		B: BlockT,
+		BA: ClientBackend<B>,
+		C: BlockBackend<B> + HeaderBackend<B>,
+		H: HostFunctions,
+		<B::Hash as FromStr>::Err: Debug,
+		<<B::Header as HeaderT>::Number as FromStr>::Err: Debug,
+	{
+		if self.sample_interval == 0 {
+			return Err("The sample interval must be greater than zero".into())
+		}
+
+		let hash = client.expect_block_hash_from_id(&self.input.parse::<B>()?)?;
+		let block = client.block(hash)?.ok_or_else(|| format!("Block {} not found", hash))?.block;
+
+		// The seal is removed by the consensus engine before importing, so the runtime
+		// never sees it.
+		let (mut header, extrinsics) = block.deconstruct();
+		header.digest_mut().logs.retain(|item| !matches!(item, DigestItem::Seal(_, _)));
+		let parent_hash = *header.parent_hash();
+		let block = B::new(header, extrinsics);
+
+		let heap_pages = self
+			.default_heap_pages
+			.map_or(DEFAULT_HEAP_ALLOC_STRATEGY, |p| HeapAllocStrategy::Static { extra_pages: p });
+		let profiler = Arc::new(Profiler::new(Duration::from_micros(self.sample_interval)));
+		let executor = WasmExecutor::<H>::builder()
+			.with_execution_method(WasmExecutionMethod::default())
+			.with_onchain_heap_alloc_strategy(heap_pages)
+			.with_offchain_heap_alloc_strategy(heap_pages)
+			.with_profiler(profiler.clone())
+			.build();
+
+		let state = backend.state_at(parent_hash, TrieCacheContext::Untrusted)?;
+		let runtime_code = BackendRuntimeCode::new(&state);
+		let runtime_code = runtime_code.runtime_code().map_err(|e| e.to_string())?;
+		let mut overlay = OverlayedChanges::default();
+		let mut extensions = sp_externalities::Extensions::default();
+		extensions.register(ReadRuntimeVersionExt::new(executor.clone()));
+
+		StateMachine::new(
+			&state,
+			&mut overlay,
+			&executor,
+			"Core_execute_block",
+			&block.encode(),
+			&mut extensions,
+			&runtime_code,
+			CallContext::Onchain,
+		)
+		.set_parent_hash(parent_hash)
+		.execute()
+		.map_err(|e| format!("Failed to execute block {}: {}", hash, e))?;
+
+		let profiles = profiler.take_profiles();
+		let mut output = BufWriter::new(File::create(&self.output)?);
+		for profile in &profiles {
+			profile.write_folded(&mut output)?;
+		}
+
+		for profile in profiles {
+			info!(
+				"{} took {} µs, {} µs of which in host functions",
+				profile.method,
+				profile.total_time.as_micros(),
+				profile.host_time().as_micros(),
+			);
+
+			let mut host_functions = profile.host_functions.into_iter().collect::<Vec<_>>();
+			host_functions.sort_by(|a, b| b.1.total_time.cmp(&a.1.total_time));
+			for (name, stats) in host_functions {
+				info!(
+					"  {: <50} {: >8} calls {: >10} µs",
+					name,
+					stats.calls,
+					stats.total_time.as_micros(),
+				);
+			}
+		}
+		info!("Folded stacks written to {}", self.output.display());
+
+		Ok(())
+	}
+}
+
+impl CliConfiguration for ProfileBlockCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn pruning_params(&self) -> Option<&PruningParams> {
+		Some(&self.pruning_params)
+	}
+
+	fn database_params(&self) -> Option<&DatabaseParams> {
+		Some(&self.database_params)
+	}
+}
@@ -0,0 +1,213 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Implementation of the `export-slashing-protection` and `import-slashing-protection`
+//! subcommands
+
+use crate::{Error, KeystoreParams, SharedParams, SubstrateCli};
+use clap::Parser;
+use sc_keystore::{
+	slashing_protection::{Interchange, SLASHING_PROTECTION_FILE},
+	SlashingProtection,
+};
+use sc_service::config::{BasePath, KeystoreConfig};
+use std::{fs, io, path::PathBuf};
+
+/// The `export-slashing-protection` command
+#[derive(Debug, Clone, Parser)]
+#[command(
+	name = "export-slashing-protection",
+	about = "Export the slashing protection history of the keystore of a node."
+)]
+pub struct ExportSlashingProtectionCmd {
+	/// File to write the history to.
+	///
+	/// The history is written to stdout if not given.
+	#[arg(long, value_name = "PATH")]
+	pub output: Option<PathBuf>,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub keystore_params: KeystoreParams,
+}
+
+impl ExportSlashingProtectionCmd {
+	/// Run the command
+	pub fn run<C: SubstrateCli>(&self, cli: &C) -> Result<(), Error> {
+		let store = open_store(cli, &self.shared_params, &self.keystore_params)?;
+		let interchange = store.export();
+
+		match &self.output {
+			Some(path) => serde_json::to_writer_pretty(fs::File::create(path)?, &interchange),
+			None => serde_json::to_writer_pretty(io::stdout(), &interchange),
+		}
+		.map_err(|e| Error::Input(format!("Failed to write the history: {e}")))?;
+
+		Ok(())
+	}
+}
+
+/// The `import-slashing-protection` command
+#[derive(Debug, Clone, Parser)]
+#[command(
+	name = "import-slashing-protection",
+	about = "Import a slashing protection history into the keystore of a node."
+)]
+pub struct ImportSlashingProtectionCmd {
+	/// File containing the history, as written by `export-slashing-protection`.
+	#[arg(value_name = "PATH")]
+	pub input: PathBuf,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub keystore_params: KeystoreParams,
+}
+
+impl ImportSlashingProtectionCmd {
+	/// Run the command
+	pub fn run<C: SubstrateCli>(&self, cli: &C) -> Result<(), Error> {
+		let interchange: Interchange = serde_json::from_reader(fs::File::open(&self.input)?)
+			.map_err(|e| Error::Input(format!("Failed to read the history: {e}")))?;
+
+		let store = open_store(cli, &self.shared_params, &self.keystore_params)?;
+		let imported = store.import(interchange)?;
+		println!("Imported {} slashing protection record(s)", imported);
+
+		Ok(())
+	}
+}
+
+fn open_store<C: SubstrateCli>(
+	cli: &C,
+	shared_params: &SharedParams,
+	keystore_params: &KeystoreParams,
+) -> Result<SlashingProtection, Error> {
+	let base_path = shared_params
+		.base_path()?
+		.unwrap_or_else(|| BasePath::from_project("", "", &C::executable_name()));
+	let chain_id = shared_params.chain_id(shared_params.is_dev());
+	let chain_spec = cli.load_spec(&chain_id)?;
+	let config_dir = base_path.config_dir(chain_spec.id());
+
+	match keystore_params.keystore_config(&config_dir)? {
+		KeystoreConfig::Path { path, .. } | KeystoreConfig::Remote { path, .. } => {
+			fs::create_dir_all(&path)?;
+			Ok(SlashingProtection::open(path.join(SLASHING_PROTECTION_FILE))?)
+		},
+		KeystoreConfig::InMemory => Err(Error::Input(
+			"The slashing protection history is not persisted for an in-memory keystore".into(),
+		)),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sc_keystore::slashing_protection::{SigningDomain, SigningPosition};
+	use sc_service::{ChainSpec, ChainType, GenericChainSpec, NoExtension};
+	use tempfile::TempDir;
+
+	struct Cli;
+
+	impl SubstrateCli for Cli {
+		fn impl_name() -> String {
+			"test".into()
+		}
+
+		fn impl_version() -> String {
+			"2.0".into()
+		}
+
+		fn description() -> String {
+			"test".into()
+		}
+
+		fn support_url() -> String {
+			"test.test".into()
+		}
+
+		fn copyright_start_year() -> i32 {
+			2021
+		}
+
+		fn author() -> String {
+			"test".into()
+		}
+
+		fn load_spec(&self, _: &str) -> std::result::Result<Box<dyn ChainSpec>, String> {
+			let builder =
+				GenericChainSpec::<NoExtension, ()>::builder(Default::default(), NoExtension::None);
+			Ok(Box::new(
+				builder
+					.with_name("test")
+					.with_id("test_id")
+					.with_chain_type(ChainType::Development)
+					.with_genesis_config_patch(Default::default())
+					.build(),
+			))
+		}
+	}
+
+	#[test]
+	fn export_and_import_slashing_protection() {
+		let old_node = TempDir::new().unwrap();
+		let new_node = TempDir::new().unwrap();
+		let history = old_node.path().join("history.json");
+		let public = [7u8; 32];
+
+		let old_keystore = old_node.path().join("chains").join("test_id").join("keystore");
+		fs::create_dir_all(&old_keystore).unwrap();
+		SlashingProtection::open(old_keystore.join(SLASHING_PROTECTION_FILE))
+			.unwrap()
+			.check_and_record(SigningDomain::Babe, &public, SigningPosition::slot(10), b"block")
+			.unwrap();
+
+		ExportSlashingProtectionCmd::parse_from(&[
+			"export-slashing-protection",
+			"-d",
+			&old_node.path().display().to_string(),
+			"--output",
+			&history.display().to_string(),
+		])
+		.run(&Cli)
+		.unwrap();
+
+		ImportSlashingProtectionCmd::parse_from(&[
+			"import-slashing-protection",
+			&history.display().to_string(),
+			"-d",
+			&new_node.path().display().to_string(),
+		])
+		.run(&Cli)
+		.unwrap();
+
+		let new_keystore = new_node.path().join("chains").join("test_id").join("keystore");
+		let store = SlashingProtection::open(new_keystore.join(SLASHING_PROTECTION_FILE)).unwrap();
+		assert!(store
+			.check_and_record(SigningDomain::Babe, &public, SigningPosition::slot(10), b"other")
+			.is_err());
+	}
+}
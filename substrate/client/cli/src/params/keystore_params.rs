@@ -18,7 +18,7 @@
 
 use crate::{error, error::Result};
 use clap::Args;
-use sc_service::config::KeystoreConfig;
+use sc_service::config::{KeystoreConfig, RemoteSignerAddress, RemoteSignerSecret};
 use sp_core::crypto::SecretString;
 use std::{
 	fs,
@@ -32,9 +32,28 @@ const DEFAULT_KEYSTORE_CONFIG_PATH: &str = "keystore";
 #[derive(Debug, Clone, Args)]
 pub struct KeystoreParams {
 	/// Specify custom keystore path.
+	///
+	/// With `--keystore-remote`, only the slashing protection history is stored at this path.
 	#[arg(long, value_name = "PATH")]
 	pub keystore_path: Option<PathBuf>,
 
+	/// Use a remote signer holding the keys instead of a local keystore.
+	///
+	/// The address is either `unix:<PATH>` for a unix socket or `tcp:<HOST>:<PORT>`. The node
+	/// and the remote signer authenticate each other with the secret given by
+	/// `--keystore-remote-secret-file`.
+	#[arg(
+		long,
+		value_name = "ADDRESS",
+		requires = "keystore_remote_secret_file",
+		conflicts_with_all = &["password_interactive", "password", "password_filename"]
+	)]
+	pub keystore_remote: Option<RemoteSignerAddress>,
+
+	/// File that contains the hex encoded secret shared with the remote signer.
+	#[arg(long, value_name = "PATH", requires = "keystore_remote")]
+	pub keystore_remote_secret_file: Option<PathBuf>,
+
 	/// Use interactive shell for entering the password used by the keystore.
 	#[arg(long, conflicts_with_all = &["password", "password_filename"])]
 	pub password_interactive: bool,
@@ -56,6 +75,30 @@ pub struct KeystoreParams {
 		conflicts_with_all = &["password_interactive", "password"]
 	)]
 	pub password_filename: Option<PathBuf>,
+
+	/// Password used to encrypt the key files of the keystore at rest.
+	///
+	/// A new keystore is created encrypted, an existing one has to be encrypted first with
+	/// `key encrypt-keystore`. Prefer passing it through the environment variable or
+	/// `--keystore-encryption-password-filename`.
+	#[arg(
+		long,
+		value_parser = secret_string_from_str,
+		env = "KEYSTORE_ENCRYPTION_PASSWORD",
+		hide_env_values = true,
+		conflicts_with_all = &["keystore_encryption_password_filename", "keystore_remote"]
+	)]
+	pub keystore_encryption_password: Option<SecretString>,
+
+	/// File that contains the password used to encrypt the key files of the keystore at rest.
+	///
+	/// A trailing newline is ignored.
+	#[arg(
+		long,
+		value_name = "PATH",
+		conflicts_with_all = &["keystore_encryption_password", "keystore_remote"]
+	)]
+	pub keystore_encryption_password_filename: Option<PathBuf>,
 }
 
 /// Parse a secret string, returning a displayable error.
@@ -66,6 +109,18 @@ pub fn secret_string_from_str(s: &str) -> std::result::Result<SecretString, Stri
 impl KeystoreParams {
 	/// Get the keystore configuration for the parameters
 	pub fn keystore_config(&self, config_dir: &Path) -> Result<KeystoreConfig> {
+		let path = self
+			.keystore_path
+			.clone()
+			.unwrap_or_else(|| config_dir.join(DEFAULT_KEYSTORE_CONFIG_PATH));
+
+		if let (Some(address), Some(secret_file)) =
+			(&self.keystore_remote, &self.keystore_remote_secret_file)
+		{
+			let secret = RemoteSignerSecret::from_file(secret_file)?;
+			return Ok(KeystoreConfig::Remote { address: address.clone(), secret, path })
+		}
+
 		let password = if self.password_interactive {
 			Some(SecretString::new(input_keystore_password()?))
 		} else if let Some(ref file) = self.password_filename {
@@ -75,12 +130,15 @@ impl KeystoreParams {
 			self.password.clone()
 		};
 
-		let path = self
-			.keystore_path
-			.clone()
-			.unwrap_or_else(|| config_dir.join(DEFAULT_KEYSTORE_CONFIG_PATH));
+		let encryption_password = if let Some(ref file) = self.keystore_encryption_password_filename
+		{
+			let password = fs::read_to_string(file).map_err(|e| format!("{}", e))?;
+			Some(SecretString::new(password.trim_end_matches(['\n', '\r']).to_owned()))
+		} else {
+			self.keystore_encryption_password.clone()
+		};
 
-		Ok(KeystoreConfig::Path { path, password })
+		Ok(KeystoreConfig::Path { path, password, encryption_password })
 	}
 
 	/// helper method to fetch password from `KeyParams` or read from stdin
@@ -25,7 +25,8 @@
 
 use clap::{ArgAction, Args};
 use sc_network::config::Role;
-use sc_service::config::OffchainWorkerConfig;
+use sc_service::config::{OffchainHttpRequestPolicy, OffchainWorkerConfig, UrlPattern};
+use std::num::NonZeroU32;
 
 use crate::{error, OffchainWorkerEnabled};
 
@@ -47,6 +48,25 @@ pub struct OffchainWorkerParams {
 	/// Allows the runtime to write directly to offchain workers DB during block import.
 	#[arg(long = "enable-offchain-indexing", value_name = "ENABLE_OFFCHAIN_INDEXING", default_value_t = false, action = ArgAction::Set)]
 	pub indexing_enabled: bool,
+
+	/// Only allow offchain workers to send HTTP requests to the given hosts or URLs.
+	///
+	/// Accepts host names (`example.com`), subdomain wildcards (`*.example.com`) and URL
+	/// prefixes (`https://example.com/api/`). Can be passed multiple times. If not given, all
+	/// hosts are allowed.
+	#[arg(long = "offchain-http-allow", value_name = "PATTERN", num_args = 1.., value_delimiter = ',')]
+	pub http_allow_list: Vec<UrlPattern>,
+
+	/// Maximum number of HTTP requests all offchain workers together can start per second.
+	///
+	/// Requests exceeding this rate are denied.
+	#[arg(long = "offchain-http-max-requests-per-second", value_name = "COUNT")]
+	pub http_max_requests_per_second: Option<NonZeroU32>,
+
+	/// Maximum number of HTTP body bytes all offchain workers together can send and receive per
+	/// second.
+	#[arg(long = "offchain-http-max-bandwidth", value_name = "BYTES")]
+	pub http_max_bandwidth: Option<u64>,
 }
 
 impl OffchainWorkerParams {
@@ -60,6 +80,11 @@ impl OffchainWorkerParams {
 		};
 
 		let indexing_enabled = self.indexing_enabled;
-		Ok(OffchainWorkerConfig { enabled, indexing_enabled })
+		let http_request_policy = OffchainHttpRequestPolicy {
+			allow_list: (!self.http_allow_list.is_empty()).then(|| self.http_allow_list.clone()),
+			max_requests_per_second: self.http_max_requests_per_second,
+			max_bandwidth: self.http_max_bandwidth,
+		};
+		Ok(OffchainWorkerConfig { enabled, indexing_enabled, http_request_policy })
 	}
 }
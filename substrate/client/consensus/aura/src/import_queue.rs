@@ -30,7 +30,9 @@ use sc_consensus::{
 	block_import::{BlockImport, BlockImportParams, ForkChoiceStrategy},
 	import_queue::{BasicQueue, BoxJustificationImport, DefaultImportQueue, Verifier},
 };
-use sc_consensus_slots::{check_equivocation, CheckedHeader, InherentDataProviderExt};
+use sc_consensus_slots::{
+	check_equivocation, check_sub_slot_equivocation, CheckedHeader, InherentDataProviderExt,
+};
 use sc_telemetry::{telemetry, TelemetryHandle, CONSENSUS_DEBUG, CONSENSUS_TRACE};
 use sp_api::{ApiExt, ProvideRuntimeApi};
 use sp_block_builder::BlockBuilder as BlockBuilderApi;
@@ -50,6 +52,9 @@ use std::{fmt::Debug, marker::PhantomData, sync::Arc};
 /// will be returned. If it's successful, returns the pre-header and the digest item
 /// containing the seal.
 ///
+/// If the runtime bounds the number of blocks per slot, the header is rejected when its parent
+/// already used all of them and equivocations are checked per sub-slot.
+///
 /// This digest item will always return `Some` when used with `as_aura_seal`.
 fn check_header<C, B: BlockT, P: Pair>(
 	client: &C,
@@ -57,25 +62,52 @@ fn check_header<C, B: BlockT, P: Pair>(
 	header: B::Header,
 	hash: B::Hash,
 	authorities: &[AuthorityId<P>],
+	blocks_per_slot: Option<u32>,
 	check_for_equivocation: CheckForEquivocation,
 ) -> Result<CheckedHeader<B::Header, (Slot, DigestItem)>, Error<B>>
 where
 	P::Public: Codec,
 	P::Signature: Codec,
-	C: sc_client_api::backend::AuxStore,
+	C: sc_client_api::backend::AuxStore + HeaderBackend<B>,
 {
 	let check_result =
 		crate::standalone::check_header_slot_and_seal::<B, P>(slot_now, header, authorities);
 
 	match check_result {
 		Ok((header, slot, seal)) => {
+			let sub_slot = match blocks_per_slot {
+				Some(blocks_per_slot) => {
+					let index = client
+						.expect_header(*header.parent_hash())
+						.and_then(|parent| {
+							crate::standalone::sub_slot_index::<P, B, _>(
+								client,
+								&parent,
+								slot,
+								blocks_per_slot,
+							)
+						})
+						.map_err(Error::Client)?;
+					if index >= blocks_per_slot {
+						return Err(Error::TooManyBlocksInSlot(hash, blocks_per_slot))
+					}
+					Some(index).filter(|_| blocks_per_slot > 1)
+				},
+				None => None,
+			};
+
 			let expected_author = crate::standalone::slot_author::<P>(slot, &authorities);
 			let should_equiv_check = check_for_equivocation.check_for_equivocation();
 			if let (true, Some(expected)) = (should_equiv_check, expected_author) {
-				if let Some(equivocation_proof) =
-					check_equivocation(client, slot_now, slot, &header, expected)
-						.map_err(Error::Client)?
-				{
+				let equivocation_proof = match sub_slot {
+					// Authors sign one header per sub-slot in that case.
+					Some(sub_slot) => check_sub_slot_equivocation(
+						client, slot_now, slot, sub_slot, &header, expected,
+					),
+					None => check_equivocation(client, slot_now, slot, &header, expected),
+				}
+				.map_err(Error::Client)?;
+				if let Some(equivocation_proof) = equivocation_proof {
 					info!(
 						target: LOG_TARGET,
 						"Slot author is equivocating at slot {} with headers {:?} and {:?}",
@@ -165,7 +197,7 @@ where
 #[async_trait::async_trait]
 impl<B: BlockT, C, P, CIDP> Verifier<B> for AuraVerifier<C, P, CIDP, NumberFor<B>>
 where
-	C: ProvideRuntimeApi<B> + Send + Sync + sc_client_api::backend::AuxStore,
+	C: ProvideRuntimeApi<B> + HeaderBackend<B> + Send + Sync + sc_client_api::backend::AuxStore,
 	C::Api: BlockBuilderApi<B> + AuraApi<B, AuthorityId<P>> + ApiExt<B>,
 	P: Pair,
 	P::Public: Codec + Debug,
@@ -199,6 +231,12 @@ where
 		)
 		.map_err(|e| format!("Could not fetch authorities at {:?}: {}", parent_hash, e))?;
 
+		let blocks_per_slot = crate::standalone::blocks_per_slot_at::<AuthorityId<P>, B, C>(
+			self.client.as_ref(),
+			parent_hash,
+		)
+		.map_err(|e| format!("Could not fetch blocks per slot at {:?}: {}", parent_hash, e))?;
+
 		let create_inherent_data_providers = self
 			.create_inherent_data_providers
 			.create_inherent_data_providers(parent_hash, ())
@@ -221,6 +259,7 @@ where
 			block.header,
 			hash,
 			&authorities[..],
+			blocks_per_slot,
 			self.check_for_equivocation,
 		)
 		.map_err(|e| e.to_string())?;
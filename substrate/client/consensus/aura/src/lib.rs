@@ -24,6 +24,8 @@
 //!
 //! The author is allowed to issue one block but not more during that slot,
 //! and it will be built upon the longest valid chain that has been seen.
+//! Runtimes may allow the author to issue several blocks within its slot instead,
+//! see [`AuraApi::blocks_per_slot`] and [`SubSlotWorker`].
 //!
 //! Blocks from future steps will be either deferred or rejected depending on how
 //! far in the future they are.
@@ -38,9 +40,10 @@ use futures::prelude::*;
 use sc_client_api::{backend::AuxStore, BlockOf};
 use sc_consensus::{BlockImport, BlockImportParams, ForkChoiceStrategy, StateAction};
 use sc_consensus_slots::{
-	BackoffAuthoringBlocksStrategy, InherentDataProviderExt, SimpleSlotWorkerToSlotWorker,
-	SlotInfo, StorageChanges,
+	BackoffAuthoringBlocksStrategy, InherentDataProviderExt, SimpleSlotWorker, SlotInfo,
+	SlotResult, SlotWorker, StorageChanges,
 };
+use sc_keystore::slashing_protection::{SigningDomain, SigningPosition, SlashingProtectionPtr};
 use sc_telemetry::TelemetryHandle;
 use sp_api::{Core, ProvideRuntimeApi};
 use sp_application_crypto::AppPublic;
@@ -48,9 +51,10 @@ use sp_blockchain::HeaderBackend;
 use sp_consensus::{BlockOrigin, Environment, Error as ConsensusError, Proposer, SelectChain};
 use sp_consensus_slots::Slot;
 use sp_core::crypto::Pair;
-use sp_inherents::CreateInherentDataProviders;
+use sp_inherents::{CreateInherentDataProviders, InherentData, InherentIdentifier};
 use sp_keystore::KeystorePtr;
 use sp_runtime::traits::{Block as BlockT, Header, Member, NumberFor};
+use sp_timestamp::{Timestamp, TimestampInherentData};
 
 mod import_queue;
 pub mod standalone;
@@ -133,6 +137,10 @@ pub struct StartAuraParams<C, SC, I, PF, SO, L, CIDP, BS, N> {
 	pub backoff_authoring_blocks: Option<BS>,
 	/// The keystore used by the node.
 	pub keystore: KeystorePtr,
+	/// Slashing protection store consulted before sealing a block.
+	///
+	/// Authoring a second block for the same slot is refused if set.
+	pub slashing_protection: Option<SlashingProtectionPtr>,
 	/// The proportion of the slot dedicated to proposing.
 	///
 	/// The block proposing will be limited to this proportion of the slot from the starting of the
@@ -164,6 +172,7 @@ pub fn start_aura<P, B, C, SC, I, PF, SO, L, CIDP, BS, Error>(
 		force_authoring,
 		backoff_authoring_blocks,
 		keystore,
+		slashing_protection,
 		block_proposal_slot_portion,
 		max_block_proposal_slot_portion,
 		telemetry,
@@ -189,10 +198,11 @@ where
 	Error: std::error::Error + Send + From<ConsensusError> + 'static,
 {
 	let worker = build_aura_worker::<P, _, _, _, _, _, _, _, _>(BuildAuraWorkerParams {
-		client,
+		client: client.clone(),
 		block_import,
 		proposer_factory,
 		keystore,
+		slashing_protection,
 		sync_oracle: sync_oracle.clone(),
 		justification_sync_link,
 		force_authoring,
@@ -206,7 +216,7 @@ where
 	Ok(sc_consensus_slots::start_slot_worker(
 		slot_duration,
 		select_chain,
-		SimpleSlotWorkerToSlotWorker(worker),
+		SubSlotWorker::<_, _, P>::new(client, worker),
 		sync_oracle,
 		create_inherent_data_providers,
 	))
@@ -230,6 +240,10 @@ pub struct BuildAuraWorkerParams<C, I, PF, SO, L, BS, N> {
 	pub backoff_authoring_blocks: Option<BS>,
 	/// The keystore used by the node.
 	pub keystore: KeystorePtr,
+	/// Slashing protection store consulted before sealing a block.
+	///
+	/// Authoring a second block for the same slot is refused if set.
+	pub slashing_protection: Option<SlashingProtectionPtr>,
 	/// The proportion of the slot dedicated to proposing.
 	///
 	/// The block proposing will be limited to this proportion of the slot from the starting of the
@@ -259,6 +273,7 @@ pub fn build_aura_worker<P, B, C, PF, I, SO, L, BS, Error>(
 		justification_sync_link,
 		backoff_authoring_blocks,
 		keystore,
+		slashing_protection,
 		block_proposal_slot_portion,
 		max_block_proposal_slot_portion,
 		telemetry,
@@ -294,6 +309,7 @@ where
 		block_import,
 		env: proposer_factory,
 		keystore,
+		slashing_protection,
 		sync_oracle,
 		justification_sync_link,
 		force_authoring,
@@ -311,6 +327,7 @@ struct AuraWorker<C, E, I, P, SO, L, BS, N> {
 	block_import: I,
 	env: E,
 	keystore: KeystorePtr,
+	slashing_protection: Option<SlashingProtectionPtr>,
 	sync_oracle: SO,
 	justification_sync_link: L,
 	force_authoring: bool,
@@ -392,6 +409,31 @@ where
 		public: Self::Claim,
 		_authorities: Self::AuxData,
 	) -> Result<sc_consensus::BlockImportParams<B>, ConsensusError> {
+		if let Some(slashing_protection) = &self.slashing_protection {
+			let slot = find_pre_digest::<B, P::Signature>(&header)
+				.map_err(|e| ConsensusError::CannotSign(e.to_string()))?;
+			let index = self
+				.client
+				.expect_header(*header.parent_hash())
+				.and_then(|parent| {
+					crate::standalone::sub_slot_index::<P, B, _>(
+						self.client.as_ref(),
+						&parent,
+						slot,
+						u32::MAX,
+					)
+				})
+				.map_err(|e| ConsensusError::ChainLookup(e.to_string()))?;
+			slashing_protection
+				.check_and_record(
+					SigningDomain::Aura,
+					public.as_slice(),
+					SigningPosition::sub_slot(*slot, index),
+					header_hash.as_ref(),
+				)
+				.map_err(|e| ConsensusError::CannotSign(e.to_string()))?;
+		}
+
 		let signature_digest_item =
 			crate::standalone::seal::<_, P>(header_hash, &public, &self.keystore)?;
 
@@ -457,6 +499,130 @@ where
 	}
 }
 
+/// A slot worker authoring up to [`AuraApi::blocks_per_slot`] blocks in each claimed slot.
+///
+/// The slot is split into sub-slots of equal duration and the wrapped worker is invoked once per
+/// sub-slot, on top of the best block. The timestamp of every block is set to the start of its
+/// sub-slot. A single block is authored per slot if the runtime doesn't implement
+/// [`AuraApi::blocks_per_slot`].
+pub struct SubSlotWorker<C, W, P> {
+	client: Arc<C>,
+	worker: W,
+	_phantom: PhantomData<fn() -> P>,
+}
+
+impl<C, W, P> SubSlotWorker<C, W, P> {
+	/// Create a new [`SubSlotWorker`] wrapping `worker`, e.g. built with [`build_aura_worker`].
+	pub fn new(client: Arc<C>, worker: W) -> Self {
+		Self { client, worker, _phantom: PhantomData }
+	}
+}
+
+#[async_trait::async_trait]
+impl<B, C, W, P> SlotWorker<B, <W::Proposer as Proposer<B>>::Proof> for SubSlotWorker<C, W, P>
+where
+	B: BlockT,
+	C: ProvideRuntimeApi<B> + HeaderBackend<B> + Send + Sync,
+	C::Api: AuraApi<B, AuthorityId<P>>,
+	W: SimpleSlotWorker<B> + Send + Sync,
+	P: Pair,
+	P::Public: Codec,
+{
+	async fn on_slot(
+		&mut self,
+		slot_info: SlotInfo<B>,
+	) -> Option<SlotResult<B, <W::Proposer as Proposer<B>>::Proof>> {
+		let blocks_per_slot = crate::standalone::blocks_per_slot_at::<AuthorityId<P>, B, C>(
+			self.client.as_ref(),
+			slot_info.chain_head.hash(),
+		)
+		.unwrap_or_else(|e| {
+			log::warn!(target: LOG_TARGET, "Unable to fetch the number of blocks per slot: {e}");
+			None
+		})
+		.unwrap_or(1);
+
+		let slot_duration = slot_info.duration.as_millis() as u64;
+		let sub_slot_duration = slot_duration / blocks_per_slot as u64;
+		if blocks_per_slot == 1 || sub_slot_duration == 0 {
+			return self.worker.on_slot(slot_info).await
+		}
+
+		let SlotInfo { slot, create_inherent_data, mut chain_head, block_size_limit, .. } =
+			slot_info;
+		let create_inherent_data: Arc<dyn sp_inherents::InherentDataProvider> =
+			create_inherent_data.into();
+		let slot_start = *slot * slot_duration;
+
+		let mut result = None;
+		for index in 0..blocks_per_slot as u64 {
+			let sub_slot_start = Timestamp::new(slot_start + index * sub_slot_duration);
+			let sub_slot_end = sub_slot_start + sub_slot_duration;
+
+			let now = Timestamp::current();
+			if now >= sub_slot_end {
+				// Authoring the previous block took longer than its sub-slot.
+				continue
+			}
+			if let Some(wait) = sub_slot_start.checked_sub(now) {
+				futures_timer::Delay::new(wait.as_duration()).await;
+			}
+
+			let sub_slot_info = SlotInfo {
+				slot,
+				ends_at: std::time::Instant::now() +
+					sub_slot_end
+						.checked_sub(Timestamp::current())
+						.map_or_else(Default::default, Timestamp::as_duration),
+				create_inherent_data: Box::new(SubSlotInherentDataProvider {
+					inner: create_inherent_data.clone(),
+					timestamp: sub_slot_start,
+				}),
+				duration: std::time::Duration::from_millis(sub_slot_duration),
+				chain_head: chain_head.clone(),
+				block_size_limit,
+			};
+			let Some(slot_result) = self.worker.on_slot(sub_slot_info).await else { break };
+			result = Some(slot_result);
+
+			match self.client.header(self.client.info().best_hash) {
+				Ok(Some(header)) => chain_head = header,
+				_ => break,
+			}
+		}
+
+		result
+	}
+}
+
+/// Inherent data provider setting the timestamp of a block to the start of its sub-slot.
+struct SubSlotInherentDataProvider {
+	inner: Arc<dyn sp_inherents::InherentDataProvider>,
+	timestamp: Timestamp,
+}
+
+#[async_trait::async_trait]
+impl sp_inherents::InherentDataProvider for SubSlotInherentDataProvider {
+	async fn provide_inherent_data(
+		&self,
+		inherent_data: &mut InherentData,
+	) -> Result<(), sp_inherents::Error> {
+		self.inner.provide_inherent_data(inherent_data).await?;
+		if inherent_data.timestamp_inherent_data()?.is_some() {
+			inherent_data.replace_data(sp_timestamp::INHERENT_IDENTIFIER, &self.timestamp);
+		}
+		Ok(())
+	}
+
+	async fn try_handle_error(
+		&self,
+		identifier: &InherentIdentifier,
+		error: &[u8],
+	) -> Option<Result<(), sp_inherents::Error>> {
+		self.inner.try_handle_error(identifier, error).await
+	}
+}
+
 /// Aura Errors
 #[derive(Debug, thiserror::Error)]
 pub enum Error<B: BlockT> {
@@ -478,6 +644,9 @@ pub enum Error<B: BlockT> {
 	/// Bad signature
 	#[error("Bad signature on {0:?}")]
 	BadSignature(B::Hash),
+	/// More blocks than allowed in the slot
+	#[error("Block {0:?} exceeds the {1} blocks allowed in its slot")]
+	TooManyBlocksInSlot(B::Hash, u32),
 	/// Client Error
 	#[error(transparent)]
 	Client(sp_blockchain::Error),
@@ -547,16 +716,17 @@ where
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use codec::Encode;
 	use parking_lot::Mutex;
 	use sc_block_builder::BlockBuilderBuilder;
 	use sc_client_api::BlockchainEvents;
 	use sc_consensus::BoxJustificationImport;
-	use sc_consensus_slots::{BackoffAuthoringOnFinalizedHeadLagging, SimpleSlotWorker};
-	use sc_keystore::LocalKeystore;
+	use sc_consensus_slots::BackoffAuthoringOnFinalizedHeadLagging;
+	use sc_keystore::{LocalKeystore, SlashingProtection};
 	use sc_network_test::{Block as TestBlock, *};
 	use sp_application_crypto::{key_types::AURA, AppCrypto};
 	use sp_consensus::{DisableProofRecording, NoNetwork as DummyOracle, Proposal};
-	use sp_consensus_aura::sr25519::AuthorityPair;
+	use sp_consensus_aura::sr25519::{AuthorityPair, AuthoritySignature};
 	use sp_inherents::InherentData;
 	use sp_keyring::sr25519::Keyring;
 	use sp_keystore::Keystore;
@@ -570,8 +740,8 @@ mod tests {
 		time::{Duration, Instant},
 	};
 	use substrate_test_runtime_client::{
-		runtime::{Header, H256},
-		TestClient,
+		runtime::{Header, AURA_BLOCKS_PER_SLOT, H256},
+		DefaultTestClientBuilderExt, TestClient, TestClientBuilder, TestClientBuilderExt,
 	};
 
 	const SLOT_DURATION_MS: u64 = 1000;
@@ -754,6 +924,7 @@ mod tests {
 						BackoffAuthoringOnFinalizedHeadLagging::default(),
 					),
 					keystore,
+					slashing_protection: None,
 					block_proposal_slot_portion: SlotProportion::new(0.5),
 					max_block_proposal_slot_portion: None,
 					telemetry: None,
@@ -802,6 +973,7 @@ mod tests {
 			block_import: client,
 			env: environ,
 			keystore: keystore.into(),
+			slashing_protection: None,
 			sync_oracle: DummyOracle,
 			justification_sync_link: (),
 			force_authoring: false,
@@ -852,6 +1024,7 @@ mod tests {
 			block_import: client.clone(),
 			env: environ,
 			keystore: keystore.into(),
+			slashing_protection: None,
 			sync_oracle: DummyOracle,
 			justification_sync_link: (),
 			force_authoring: false,
@@ -880,4 +1053,172 @@ mod tests {
 		// The returned block should be imported and we should be able to get its header by now.
 		assert!(client.header(res.block.hash()).unwrap().is_some());
 	}
+
+	#[tokio::test]
+	async fn on_slot_refuses_block_conflicting_with_slashing_protection() {
+		let net = AuraTestNet::new(4);
+
+		let keystore_path = tempfile::tempdir().expect("Creates keystore path");
+		let keystore = LocalKeystore::open(keystore_path.path(), None).expect("Creates keystore.");
+		let public = keystore
+			.sr25519_generate_new(AuthorityPair::ID, Some(&Keyring::Alice.to_seed()))
+			.expect("Key should be created");
+
+		// A block was already authored for a later slot, e.g. before the node was restored from
+		// a backup.
+		let slashing_protection = Arc::new(SlashingProtection::in_memory());
+		slashing_protection
+			.check_and_record(SigningDomain::Aura, public.as_ref(), SigningPosition::slot(4), &[])
+			.unwrap();
+
+		let net = Arc::new(Mutex::new(net));
+
+		let mut net = net.lock();
+		let peer = net.peer(3);
+		let client = peer.client().as_client();
+		let environ = DummyFactory(client.clone());
+
+		let mut worker = AuraWorker {
+			client: client.clone(),
+			block_import: client.clone(),
+			env: environ,
+			keystore: keystore.into(),
+			slashing_protection: Some(slashing_protection),
+			sync_oracle: DummyOracle,
+			justification_sync_link: (),
+			force_authoring: false,
+			backoff_authoring_blocks: Option::<()>::None,
+			telemetry: None,
+			block_proposal_slot_portion: SlotProportion::new(0.5),
+			max_block_proposal_slot_portion: None,
+			compatibility_mode: Default::default(),
+			_phantom: PhantomData::<fn() -> AuthorityPair>,
+		};
+
+		let head = client.expect_header(client.info().genesis_hash).unwrap();
+
+		let res = worker
+			.on_slot(SlotInfo {
+				slot: 0.into(),
+				ends_at: Instant::now() + Duration::from_secs(100),
+				create_inherent_data: Box::new(()),
+				duration: Duration::from_millis(1000),
+				chain_head: head,
+				block_size_limit: None,
+			})
+			.await;
+
+		assert!(res.is_none());
+		assert_eq!(client.info().best_number, 0);
+	}
+
+	#[tokio::test]
+	async fn on_slot_authors_sub_slot_blocks_with_slashing_protection() {
+		let net = AuraTestNet::new(4);
+
+		let keystore_path = tempfile::tempdir().expect("Creates keystore path");
+		let keystore = LocalKeystore::open(keystore_path.path(), None).expect("Creates keystore.");
+		keystore
+			.sr25519_generate_new(AuthorityPair::ID, Some(&Keyring::Alice.to_seed()))
+			.expect("Key should be created");
+
+		let net = Arc::new(Mutex::new(net));
+
+		let mut net = net.lock();
+		let peer = net.peer(3);
+		let client = peer.client().as_client();
+		let environ = DummyFactory(client.clone());
+
+		let mut worker = AuraWorker {
+			client: client.clone(),
+			block_import: client.clone(),
+			env: environ,
+			keystore: keystore.into(),
+			slashing_protection: Some(Arc::new(SlashingProtection::in_memory())),
+			sync_oracle: DummyOracle,
+			justification_sync_link: (),
+			force_authoring: false,
+			backoff_authoring_blocks: Option::<()>::None,
+			telemetry: None,
+			block_proposal_slot_portion: SlotProportion::new(0.5),
+			max_block_proposal_slot_portion: None,
+			compatibility_mode: Default::default(),
+			_phantom: PhantomData::<fn() -> AuthorityPair>,
+		};
+
+		// Both blocks are authored at slot 0, the second one on top of the first one.
+		for expected_number in 1..=2 {
+			let head = client.expect_header(client.info().best_hash).unwrap();
+			let res = worker
+				.on_slot(SlotInfo {
+					slot: 0.into(),
+					ends_at: Instant::now() + Duration::from_secs(100),
+					create_inherent_data: Box::new(()),
+					duration: Duration::from_millis(500),
+					chain_head: head,
+					block_size_limit: None,
+				})
+				.await;
+
+			assert!(res.is_some());
+			assert_eq!(client.info().best_number, expected_number);
+		}
+	}
+
+	#[tokio::test]
+	async fn sub_slot_worker_authors_blocks_per_slot() {
+		let client = Arc::new(
+			TestClientBuilder::new()
+				.add_extra_storage(AURA_BLOCKS_PER_SLOT, 2u32.encode())
+				.build(),
+		);
+
+		let keystore_path = tempfile::tempdir().expect("Creates keystore path");
+		let keystore = LocalKeystore::open(keystore_path.path(), None).expect("Creates keystore.");
+		for keyring in [Keyring::Alice, Keyring::Bob, Keyring::Charlie] {
+			keystore
+				.sr25519_generate_new(AuthorityPair::ID, Some(&keyring.to_seed()))
+				.expect("Key should be created");
+		}
+
+		let worker = AuraWorker {
+			client: client.clone(),
+			block_import: client.clone(),
+			env: DummyFactory(client.clone()),
+			keystore: keystore.into(),
+			slashing_protection: Some(Arc::new(SlashingProtection::in_memory())),
+			sync_oracle: DummyOracle,
+			justification_sync_link: (),
+			force_authoring: false,
+			backoff_authoring_blocks: Option::<()>::None,
+			telemetry: None,
+			block_proposal_slot_portion: SlotProportion::new(0.5),
+			max_block_proposal_slot_portion: None,
+			compatibility_mode: Default::default(),
+			_phantom: PhantomData::<fn() -> AuthorityPair>,
+		};
+		let mut worker = SubSlotWorker::<_, _, AuthorityPair>::new(client.clone(), worker);
+
+		// Start at the next slot, so that both sub-slots are ahead of us.
+		let duration = Duration::from_millis(400);
+		let slot = Slot::from(*Timestamp::current() / duration.as_millis() as u64 + 1);
+		let res = worker
+			.on_slot(SlotInfo {
+				slot,
+				ends_at: Instant::now() + Duration::from_secs(100),
+				create_inherent_data: Box::new(()),
+				duration,
+				chain_head: client.expect_header(client.info().best_hash).unwrap(),
+				block_size_limit: None,
+			})
+			.await;
+
+		assert!(res.is_some());
+		assert_eq!(client.info().best_number, 2);
+
+		let best = client.expect_header(client.info().best_hash).unwrap();
+		let parent = client.expect_header(*best.parent_hash()).unwrap();
+		assert_eq!(find_pre_digest::<TestBlock, AuthoritySignature>(&best).unwrap(), slot);
+		assert_eq!(find_pre_digest::<TestBlock, AuthoritySignature>(&parent).unwrap(), slot);
+	}
 }
@@ -25,9 +25,9 @@ use log::trace;
 use codec::Codec;
 
 use sc_client_api::UsageProvider;
-use sp_api::{Core, ProvideRuntimeApi};
+use sp_api::{ApiExt, Core, ProvideRuntimeApi};
 use sp_application_crypto::{AppCrypto, AppPublic};
-use sp_blockchain::Result as CResult;
+use sp_blockchain::{HeaderBackend, Result as CResult};
 use sp_consensus::Error as ConsensusError;
 use sp_consensus_slots::Slot;
 use sp_core::crypto::{ByteArray, Pair};
@@ -65,6 +65,56 @@ where
 	client.runtime_api().slot_duration(block_hash).map_err(|err| err.into())
 }
 
+/// Get the maximum number of blocks an author may build within its slot by reading from a runtime
+/// API at a given block's state.
+///
+/// Returns `None` if the runtime doesn't bound the number of blocks per slot, i.e. if it only
+/// implements the first version of [`AuraApi`].
+pub fn blocks_per_slot_at<A, B, C>(client: &C, block_hash: B::Hash) -> CResult<Option<u32>>
+where
+	A: Codec,
+	B: BlockT,
+	C: ProvideRuntimeApi<B>,
+	C::Api: AuraApi<B, A>,
+{
+	let runtime_api = client.runtime_api();
+
+	if runtime_api.has_api_with::<dyn AuraApi<B, A>, _>(block_hash, |v| v >= 2)? {
+		Ok(Some(runtime_api.blocks_per_slot(block_hash)?.max(1)))
+	} else {
+		Ok(None)
+	}
+}
+
+/// Get the index within `slot` of a block authored at `slot` on top of `parent`.
+///
+/// This is the number of consecutive ancestors, starting with `parent`, which were authored at
+/// `slot`. Counting stops at `limit`.
+pub fn sub_slot_index<P, B, C>(
+	client: &C,
+	parent: &B::Header,
+	slot: Slot,
+	limit: u32,
+) -> CResult<u32>
+where
+	P: Pair,
+	P::Signature: Codec,
+	B: BlockT,
+	C: HeaderBackend<B>,
+{
+	let mut index = 0;
+	let mut header = parent.clone();
+	while index < limit &&
+		!header.number().is_zero() &&
+		find_pre_digest::<B, P::Signature>(&header).ok() == Some(slot)
+	{
+		index += 1;
+		header = client.expect_header(*header.parent_hash())?;
+	}
+
+	Ok(index)
+}
+
 /// Get the slot author for given block along with authorities.
 pub fn slot_author<P: Pair>(slot: Slot, authorities: &[AuthorityId<P>]) -> Option<&AuthorityId<P>> {
 	if authorities.is_empty() {
@@ -323,7 +373,32 @@ where
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use codec::Encode;
+	use sc_block_builder::BlockBuilderBuilder;
+	use sp_consensus::BlockOrigin;
+	use sp_consensus_aura::sr25519::AuthorityPair;
 	use sp_keyring::sr25519::Keyring;
+	use sp_runtime::Digest;
+	use substrate_test_runtime_client::{
+		runtime::{Header, AURA_BLOCKS_PER_SLOT},
+		ClientBlockImportExt, DefaultTestClientBuilderExt, TestClient, TestClientBuilder,
+		TestClientBuilderExt,
+	};
+
+	fn import_block_at_slot(client: &mut TestClient, slot: u64) -> Header {
+		let block = BlockBuilderBuilder::new(&*client)
+			.on_parent_block(client.chain_info().best_hash)
+			.fetch_parent_block_number(&*client)
+			.unwrap()
+			.with_inherent_digests(Digest { logs: vec![pre_digest::<AuthorityPair>(slot.into())] })
+			.build()
+			.unwrap()
+			.build()
+			.unwrap()
+			.block;
+		futures::executor::block_on(client.import(BlockOrigin::Own, block.clone())).unwrap();
+		block.header
+	}
 
 	#[test]
 	fn authorities_call_works() {
@@ -354,4 +429,49 @@ mod tests {
 			]
 		);
 	}
+
+	#[test]
+	fn blocks_per_slot_call_works() {
+		let client = substrate_test_runtime_client::new();
+
+		assert_eq!(
+			blocks_per_slot_at::<AuthorityId<AuthorityPair>, _, _>(
+				&client,
+				client.chain_info().best_hash
+			)
+			.unwrap(),
+			Some(1)
+		);
+
+		let client = TestClientBuilder::new()
+			.add_extra_storage(AURA_BLOCKS_PER_SLOT, 2u32.encode())
+			.build();
+
+		assert_eq!(
+			blocks_per_slot_at::<AuthorityId<AuthorityPair>, _, _>(
+				&client,
+				client.chain_info().best_hash
+			)
+			.unwrap(),
+			Some(2)
+		);
+	}
+
+	#[test]
+	fn sub_slot_index_counts_blocks_of_the_slot() {
+		let mut client = substrate_test_runtime_client::new();
+
+		let first = import_block_at_slot(&mut client, 1);
+		assert_eq!(sub_slot_index::<AuthorityPair, _, _>(&client, &first, 1.into(), 4).unwrap(), 1);
+		assert_eq!(sub_slot_index::<AuthorityPair, _, _>(&client, &first, 2.into(), 4).unwrap(), 0);
+
+		let second = import_block_at_slot(&mut client, 2);
+		let third = import_block_at_slot(&mut client, 2);
+		assert_eq!(
+			sub_slot_index::<AuthorityPair, _, _>(&client, &second, 2.into(), 4).unwrap(),
+			1
+		);
+		assert_eq!(sub_slot_index::<AuthorityPair, _, _>(&client, &third, 2.into(), 4).unwrap(), 2);
+		assert_eq!(sub_slot_index::<AuthorityPair, _, _>(&client, &third, 2.into(), 1).unwrap(), 1);
+	}
 }
@@ -106,6 +106,7 @@ use sc_consensus_slots::{
 	check_equivocation, BackoffAuthoringBlocksStrategy, CheckedHeader, InherentDataProviderExt,
 	SlotInfo, StorageChanges,
 };
+use sc_keystore::slashing_protection::{SigningDomain, SigningPosition, SlashingProtectionPtr};
 use sc_telemetry::{telemetry, TelemetryHandle, CONSENSUS_DEBUG, CONSENSUS_TRACE};
 use sc_transaction_pool_api::OffchainTransactionPoolFactory;
 use sp_api::{ApiExt, ProvideRuntimeApi};
@@ -412,6 +413,11 @@ pub struct BabeParams<B: BlockT, C, SC, E, I, SO, L, CIDP, BS> {
 	/// The keystore that manages the keys of the node.
 	pub keystore: KeystorePtr,
 
+	/// Slashing protection store consulted before sealing a block.
+	///
+	/// Authoring a second block for the same slot is refused if set.
+	pub slashing_protection: Option<SlashingProtectionPtr>,
+
 	/// The client to use
 	pub client: Arc<C>,
 
@@ -463,6 +469,7 @@ pub struct BabeParams<B: BlockT, C, SC, E, I, SO, L, CIDP, BS> {
 pub fn start_babe<B, C, SC, E, I, SO, CIDP, BS, L, Error>(
 	BabeParams {
 		keystore,
+		slashing_protection,
 		client,
 		select_chain,
 		env,
@@ -509,6 +516,7 @@ where
 		force_authoring,
 		backoff_authoring_blocks,
 		keystore,
+		slashing_protection,
 		epoch_changes: babe_link.epoch_changes.clone(),
 		slot_notification_sinks: slot_notification_sinks.clone(),
 		config: babe_link.config.clone(),
@@ -706,6 +714,7 @@ struct BabeSlotWorker<B: BlockT, C, E, I, SO, L, BS> {
 	force_authoring: bool,
 	backoff_authoring_blocks: Option<BS>,
 	keystore: KeystorePtr,
+	slashing_protection: Option<SlashingProtectionPtr>,
 	epoch_changes: SharedEpochChanges<B, Epoch>,
 	slot_notification_sinks: SlotNotificationSinks<B>,
 	config: BabeConfiguration,
@@ -818,9 +827,20 @@ where
 		header_hash: &B::Hash,
 		body: Vec<B::Extrinsic>,
 		storage_changes: StorageChanges<B>,
-		(_, public): Self::Claim,
+		(pre_digest, public): Self::Claim,
 		epoch_descriptor: Self::AuxData,
 	) -> Result<BlockImportParams<B>, ConsensusError> {
+		if let Some(slashing_protection) = &self.slashing_protection {
+			slashing_protection
+				.check_and_record(
+					SigningDomain::Babe,
+					public.as_ref(),
+					SigningPosition::slot(*pre_digest.slot()),
+					header_hash.as_ref(),
+				)
+				.map_err(|e| ConsensusError::CannotSign(e.to_string()))?;
+		}
+
 		let signature = self
 			.keystore
 			.sr25519_sign(<AuthorityId as AppCrypto>::ID, public.as_ref(), header_hash.as_ref())
@@ -920,6 +940,11 @@ pub fn find_pre_digest<B: BlockT>(header: &B::Header) -> Result<PreDigest, Error
 	pre_digest.ok_or_else(|| babe_err(Error::NoPreRuntimeDigest))
 }
 
+/// Returns `true` if the given header starts a new epoch, i.e. announces the next one.
+pub fn is_epoch_change<B: BlockT>(header: &B::Header) -> bool {
+	matches!(find_next_epoch_digest::<B>(header), Ok(Some(_)))
+}
+
 /// Extract the BABE epoch change digest from the given header, if it exists.
 fn find_next_epoch_digest<B: BlockT>(
 	header: &B::Header,
@@ -49,6 +49,9 @@ const REBROADCAST_AFTER: Duration = Duration::from_secs(5);
 pub(super) enum Action<H> {
 	// repropagate under given topic, to the given peers, applying cost/benefit to originator.
 	Keep(H, ReputationChange),
+	// process locally under given topic without repropagating, applying cost/benefit to
+	// originator.
+	ProcessAndDiscard(H, ReputationChange),
 	// discard, applying cost/benefit to originator.
 	Discard(ReputationChange),
 	// ignore, no cost/benefit applied to originator.
@@ -282,17 +285,25 @@ where
 		self.known_peers.lock().note_vote_for(*sender, round);
 
 		// Verify general usefulness of the message.
-		// We are going to discard old votes right away (without verification).
-		{
+		// We are going to discard votes of other validator sets right away (without
+		// verification). Votes of the current set for rounds which aren't live are still
+		// processed locally, without being propagated, so that the fisherman can report fork and
+		// future block voting.
+		let not_live = {
 			let filter = self.gossip_filter.read();
 
-			match filter.consider_vote(round, set_id) {
-				Consider::RejectPast => return Action::Discard(cost::OUTDATED_MESSAGE),
-				Consider::RejectFuture => return Action::Discard(cost::FUTURE_MESSAGE),
+			let not_live = match filter.consider_vote(round, set_id) {
+				Consider::RejectPast => Some(cost::OUTDATED_MESSAGE),
+				Consider::RejectFuture => Some(cost::FUTURE_MESSAGE),
 				// When we can't evaluate, it's our fault (e.g. filter not initialized yet), we
 				// discard the vote without punishing or rewarding the sending peer.
 				Consider::CannotEvaluate => return Action::DiscardNoReport,
-				Consider::Accept => {},
+				Consider::Accept => None,
+			};
+			if let Some(cost) = not_live {
+				if filter.validator_set().map(|set| set.id()) != Some(set_id) {
+					return Action::Discard(cost);
+				}
 			}
 
 			// ensure authority is part of the set.
@@ -304,10 +315,15 @@ where
 				debug!(target: LOG_TARGET, "Message from voter not in validator set: {}", vote.id);
 				return Action::Discard(cost::UNKNOWN_VOTER);
 			}
-		}
+
+			not_live
+		};
 
 		if BeefyKeystore::verify(&vote.id, &vote.signature, &vote.commitment.encode()) {
-			Action::Keep(self.votes_topic, benefit::VOTE_MESSAGE)
+			match not_live {
+				Some(cost) => Action::ProcessAndDiscard(self.votes_topic, cost),
+				None => Action::Keep(self.votes_topic, benefit::VOTE_MESSAGE),
+			}
 		} else {
 			debug!(
 				target: LOG_TARGET,
@@ -408,6 +424,10 @@ where
 				context.broadcast_message(topic, data.to_vec(), false);
 				ValidationResult::ProcessAndKeep(topic)
 			},
+			Action::ProcessAndDiscard(topic, cb) => {
+				self.report(*sender, cb);
+				ValidationResult::ProcessAndDiscard(topic)
+			},
 			Action::Discard(cb) => {
 				self.report(*sender, cb);
 				ValidationResult::Discard
@@ -595,7 +615,7 @@ pub(crate) mod tests {
 		}
 	}
 
-	struct TestContext;
+	pub(crate) struct TestContext;
 	impl<B: sp_runtime::traits::Block> ValidatorContext<B> for TestContext {
 		fn broadcast_topic(&mut self, _topic: B::Hash, _force: bool) {
 			unimplemented!()
@@ -715,26 +735,36 @@ pub(crate) mod tests {
 		expected_report.cost_benefit = cost::UNKNOWN_VOTER;
 		assert_eq!(report_stream.try_next().unwrap().unwrap(), expected_report);
 
-		// reject if the round is not GRANDPA finalized
+		// don't propagate if the round is not GRANDPA finalized, but process it for the fisherman
 		gv.update_filter(GossipFilterCfg { start: 1, end: 2, validator_set: &validator_set });
 		let number = vote.commitment.block_number;
 		let set_id = vote.commitment.validator_set_id;
 		assert_eq!(gv.gossip_filter.read().consider_vote(number, set_id), Consider::RejectFuture);
 		let res = gv.validate(&mut context, &sender, &encoded);
-		assert!(matches!(res, ValidationResult::Discard));
+		assert!(matches!(res, ValidationResult::ProcessAndDiscard(_)));
 		expected_report.cost_benefit = cost::FUTURE_MESSAGE;
 		assert_eq!(report_stream.try_next().unwrap().unwrap(), expected_report);
 
-		// reject if the round is not live anymore
+		// same if the round is not live anymore
 		gv.update_filter(GossipFilterCfg { start: 7, end: 10, validator_set: &validator_set });
 		let number = vote.commitment.block_number;
 		let set_id = vote.commitment.validator_set_id;
 		assert_eq!(gv.gossip_filter.read().consider_vote(number, set_id), Consider::RejectPast);
 		let res = gv.validate(&mut context, &sender, &encoded);
-		assert!(matches!(res, ValidationResult::Discard));
+		assert!(matches!(res, ValidationResult::ProcessAndDiscard(_)));
 		expected_report.cost_benefit = cost::OUTDATED_MESSAGE;
 		assert_eq!(report_stream.try_next().unwrap().unwrap(), expected_report);
 
+		// reject votes of other validator sets
+		let mut other_set_vote = vote.clone();
+		other_set_vote.commitment.validator_set_id = 1;
+		let other_set_vote =
+			GossipMessage::<Block, ecdsa_crypto::AuthorityId>::Vote(other_set_vote).encode();
+		let res = gv.validate(&mut context, &sender, &other_set_vote);
+		assert!(matches!(res, ValidationResult::Discard));
+		expected_report.cost_benefit = cost::FUTURE_MESSAGE;
+		assert_eq!(report_stream.try_next().unwrap().unwrap(), expected_report);
+
 		// now verify proofs validation
 
 		// reject old proof
@@ -16,38 +16,68 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{error::Error, keystore::BeefyKeystore, round::Rounds, LOG_TARGET};
+use crate::{
+	error::Error, justification::BeefyVersionedFinalityProof, keystore::BeefyKeystore, metric_inc,
+	metrics::VoterMetrics, round::Rounds, LOG_TARGET,
+};
 use log::{debug, error, warn};
+use parking_lot::Mutex;
 use sc_client_api::Backend;
 use sp_api::ProvideRuntimeApi;
 use sp_application_crypto::RuntimeAppPublic;
 use sp_blockchain::HeaderBackend;
 use sp_consensus_beefy::{
-	check_double_voting_proof, AuthorityIdBound, BeefyApi, BeefySignatureHasher, DoubleVotingProof,
-	OpaqueKeyOwnershipProof, ValidatorSetId,
+	check_commitment_signature, check_double_voting_proof, AuthorityIdBound, BeefyApi,
+	BeefySignatureHasher, Commitment, DoubleVotingProof, ForkVotingProof, FutureBlockVotingProof,
+	OpaqueKeyOwnershipProof, Payload, PayloadProvider, ValidatorSetId, VersionedFinalityProof,
+	VoteMessage,
 };
 use sp_runtime::{
 	generic::BlockId,
 	traits::{Block, NumberFor},
 };
-use std::{marker::PhantomData, sync::Arc};
+use std::{collections::BTreeMap, sync::Arc};
+
+/// Number of canonical payloads kept in memory, so that the expected payload isn't computed again
+/// for every vote of a round.
+const CANONICAL_PAYLOADS_CACHE_SIZE: usize = 64;
 
 /// Helper struct containing the key ownership proof for a validator.
 pub struct ProvedValidator {
 	pub key_owner_proof: OpaqueKeyOwnershipProof,
 }
 
+/// Kind of misbehavior detected for a commitment, apart from double voting.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Misbehavior {
+	/// The commitment is for a block that isn't part of the finalized chain.
+	ForkVoting,
+	/// The commitment is for a block that doesn't exist yet.
+	FutureBlockVoting,
+}
+
 /// Helper used to check and report equivocations.
-pub struct Fisherman<B, BE, RuntimeApi, AuthorityId: AuthorityIdBound> {
+///
+/// Besides double voting, which is detected by the voting rounds, the fisherman checks the
+/// commitments of the votes and justifications it is given against the local view of the chain,
+/// and reports votes for blocks that are not part of the finalized chain (fork voting) as well as
+/// votes for blocks that don't exist yet (future block voting).
+pub struct Fisherman<B: Block, BE, P, RuntimeApi, AuthorityId: AuthorityIdBound> {
 	backend: Arc<BE>,
 	runtime: Arc<RuntimeApi>,
 	key_store: Arc<BeefyKeystore<AuthorityId>>,
-
-	_phantom: PhantomData<B>,
+	payload_provider: P,
+	canonical_payloads: Mutex<BTreeMap<NumberFor<B>, Payload>>,
+	metrics: Option<VoterMetrics>,
 }
 
-impl<B: Block, BE: Backend<B>, RuntimeApi: ProvideRuntimeApi<B>, AuthorityId>
-	Fisherman<B, BE, RuntimeApi, AuthorityId>
+impl<
+		B: Block,
+		BE: Backend<B>,
+		P: PayloadProvider<B>,
+		RuntimeApi: ProvideRuntimeApi<B>,
+		AuthorityId,
+	> Fisherman<B, BE, P, RuntimeApi, AuthorityId>
 where
 	RuntimeApi::Api: BeefyApi<B, AuthorityId>,
 	AuthorityId: AuthorityIdBound,
@@ -56,8 +86,17 @@ where
 		backend: Arc<BE>,
 		runtime: Arc<RuntimeApi>,
 		keystore: Arc<BeefyKeystore<AuthorityId>>,
+		payload_provider: P,
+		metrics: Option<VoterMetrics>,
 	) -> Self {
-		Self { backend, runtime, key_store: keystore, _phantom: Default::default() }
+		Self {
+			backend,
+			runtime,
+			key_store: keystore,
+			payload_provider,
+			canonical_payloads: Mutex::new(BTreeMap::new()),
+			metrics,
+		}
 	}
 
 	fn prove_offenders<'a>(
@@ -152,14 +191,259 @@ where
 		// submit equivocation report at **best** block
 		let best_block_hash = self.backend.blockchain().info().best_hash;
 		for ProvedValidator { key_owner_proof, .. } in key_owner_proofs {
-			self.runtime
+			if self
+				.runtime
 				.runtime_api()
 				.submit_report_double_voting_unsigned_extrinsic(
 					best_block_hash,
 					proof.clone(),
 					key_owner_proof,
 				)
-				.map_err(Error::RuntimeApi)?;
+				.map_err(Error::RuntimeApi)?
+				.is_some()
+			{
+				metric_inc!(self.metrics, beefy_double_voting_reports);
+			}
+		}
+
+		Ok(())
+	}
+
+	/// Check the commitment of `vote` against the local view of the chain, and report the vote
+	/// to the BEEFY runtime module if it is for a block that is not part of the finalized chain,
+	/// or for a block that doesn't exist yet.
+	///
+	/// Expects the signature of `vote` to have been checked already.
+	pub fn check_vote(
+		&self,
+		vote: VoteMessage<NumberFor<B>, AuthorityId, <AuthorityId as RuntimeAppPublic>::Signature>,
+	) -> Result<(), Error> {
+		match self.check_commitment(&vote.commitment)? {
+			Some(Misbehavior::ForkVoting) => self.report_fork_voting(vote),
+			Some(Misbehavior::FutureBlockVoting) =>
+				self.report_future_block_voting(FutureBlockVotingProof { vote }),
+			None => Ok(()),
+		}
+	}
+
+	/// Check the commitment of `proof` against the local view of the chain, and report all its
+	/// signatories to the BEEFY runtime module if it is for a block that is not part of the
+	/// finalized chain, or for a block that doesn't exist yet.
+	///
+	/// Expects `proof` to be valid.
+	pub fn check_proof(
+		&self,
+		proof: BeefyVersionedFinalityProof<B, AuthorityId>,
+	) -> Result<(), Error> {
+		let VersionedFinalityProof::V1(signed_commitment) = proof;
+		let Some(misbehavior) = self.check_commitment(&signed_commitment.commitment)? else {
+			return Ok(())
+		};
+
+		// The validator set isn't part of the proof, get the one it was checked against.
+		let at = match misbehavior {
+			Misbehavior::ForkVoting =>
+				self.canonical_hash(signed_commitment.commitment.block_number)?,
+			Misbehavior::FutureBlockVoting => self.backend.blockchain().info().best_hash,
+		};
+		let validator_set = match self.runtime.runtime_api().validator_set(at) {
+			Ok(Some(set)) if set.id() == signed_commitment.commitment.validator_set_id => set,
+			_ => {
+				debug!(
+					target: LOG_TARGET,
+					"🥩 Unknown validator set {}, skipping report for equivocation {:?}",
+					signed_commitment.commitment.validator_set_id,
+					signed_commitment.commitment,
+				);
+				return Ok(())
+			},
+		};
+
+		for (id, signature) in validator_set.validators().iter().zip(signed_commitment.signatures) {
+			let Some(signature) = signature else { continue };
+			let vote = VoteMessage {
+				commitment: signed_commitment.commitment.clone(),
+				id: id.clone(),
+				signature,
+			};
+			match misbehavior {
+				Misbehavior::ForkVoting => self.report_fork_voting(vote)?,
+				Misbehavior::FutureBlockVoting =>
+					self.report_future_block_voting(FutureBlockVotingProof { vote })?,
+			}
+		}
+
+		Ok(())
+	}
+
+	/// Check `commitment` against the local view of the chain.
+	///
+	/// Commitments for blocks that are not finalized yet are only checked to not be in the future,
+	/// since the block they are for might still become canonical.
+	fn check_commitment(
+		&self,
+		commitment: &Commitment<NumberFor<B>>,
+	) -> Result<Option<Misbehavior>, Error> {
+		let info = self.backend.blockchain().info();
+		let number = commitment.block_number;
+
+		if number > info.best_number {
+			return Ok(Some(Misbehavior::FutureBlockVoting))
+		}
+		if number > info.finalized_number {
+			return Ok(None)
+		}
+
+		match self.canonical_payload(number)? {
+			Some(payload) if payload != commitment.payload => Ok(Some(Misbehavior::ForkVoting)),
+			_ => Ok(None),
+		}
+	}
+
+	/// Return the hash of the finalized block `number`.
+	fn canonical_hash(&self, number: NumberFor<B>) -> Result<B::Hash, Error> {
+		self.backend
+			.blockchain()
+			.expect_block_hash_from_id(&BlockId::Number(number))
+			.map_err(|err| {
+				Error::Backend(format!(
+					"Couldn't get hash for block #{:?} (error: {:?}). \
+					Skipping check for equivocation",
+					number, err
+				))
+			})
+	}
+
+	/// Return the payload of the finalized block `number`, if any.
+	fn canonical_payload(&self, number: NumberFor<B>) -> Result<Option<Payload>, Error> {
+		if let Some(payload) = self.canonical_payloads.lock().get(&number) {
+			return Ok(Some(payload.clone()))
+		}
+
+		let header = self.backend.blockchain().expect_header(self.canonical_hash(number)?)?;
+		let Some(payload) = self.payload_provider.payload(&header) else { return Ok(None) };
+
+		let mut canonical_payloads = self.canonical_payloads.lock();
+		canonical_payloads.insert(number, payload.clone());
+		if canonical_payloads.len() > CANONICAL_PAYLOADS_CACHE_SIZE {
+			canonical_payloads.pop_first();
+		}
+		Ok(Some(payload))
+	}
+
+	/// Returns `true` if `vote` should be reported: its signature is valid and it isn't ours.
+	fn should_report(
+		&self,
+		vote: &VoteMessage<NumberFor<B>, AuthorityId, <AuthorityId as RuntimeAppPublic>::Signature>,
+	) -> bool {
+		if !check_commitment_signature::<_, _, BeefySignatureHasher>(
+			&vote.commitment,
+			&vote.id,
+			&vote.signature,
+		) {
+			debug!(target: LOG_TARGET, "🥩 Skipping report for bad equivocation {:?}", vote);
+			return false
+		}
+
+		if self.key_store.authority_id(std::slice::from_ref(&vote.id)).is_some() {
+			warn!(target: LOG_TARGET, "🥩 Skipping report for own equivocation");
+			return false
+		}
+
+		true
+	}
+
+	/// Report a vote for a block that is not part of the finalized chain to the BEEFY runtime
+	/// module. The ancestry proof shows the voted block number is part of the chain at the best
+	/// finalized block, so that the runtime can check the vote doesn't match it.
+	fn report_fork_voting(
+		&self,
+		vote: VoteMessage<NumberFor<B>, AuthorityId, <AuthorityId as RuntimeAppPublic>::Signature>,
+	) -> Result<(), Error> {
+		if !self.should_report(&vote) {
+			return Ok(())
+		}
+
+		let info = self.backend.blockchain().info();
+		let header = self.backend.blockchain().expect_header(info.finalized_hash)?;
+		let runtime_api = self.runtime.runtime_api();
+		let ancestry_proof = match runtime_api
+			.generate_ancestry_proof(
+				info.finalized_hash,
+				vote.commitment.block_number,
+				Some(info.finalized_number),
+			)
+			.map_err(Error::RuntimeApi)?
+		{
+			Some(ancestry_proof) => ancestry_proof,
+			None => {
+				debug!(
+					target: LOG_TARGET,
+					"🥩 Couldn't generate ancestry proof for block #{:?}, \
+					skipping report for fork voting {:?}",
+					vote.commitment.block_number,
+					vote,
+				);
+				return Ok(())
+			},
+		};
+
+		let key_owner_proofs = self.prove_offenders(
+			BlockId::Number(vote.commitment.block_number),
+			std::iter::once(&vote.id),
+			vote.commitment.validator_set_id,
+		)?;
+
+		// submit equivocation report at **best** block
+		let proof = ForkVotingProof { vote, ancestry_proof, header };
+		for ProvedValidator { key_owner_proof, .. } in key_owner_proofs {
+			if runtime_api
+				.submit_report_fork_voting_unsigned_extrinsic(
+					info.best_hash,
+					proof.clone(),
+					key_owner_proof,
+				)
+				.map_err(Error::RuntimeApi)?
+				.is_some()
+			{
+				metric_inc!(self.metrics, beefy_fork_voting_reports);
+			}
+		}
+
+		Ok(())
+	}
+
+	/// Report a vote for a block that doesn't exist yet to the BEEFY runtime module.
+	fn report_future_block_voting(
+		&self,
+		proof: FutureBlockVotingProof<NumberFor<B>, AuthorityId>,
+	) -> Result<(), Error> {
+		if !self.should_report(&proof.vote) {
+			return Ok(())
+		}
+
+		// The voted block doesn't exist, prove the offender at the best block instead.
+		let best_block_hash = self.backend.blockchain().info().best_hash;
+		let key_owner_proofs = self.prove_offenders(
+			BlockId::Hash(best_block_hash),
+			std::iter::once(&proof.vote.id),
+			proof.vote.commitment.validator_set_id,
+		)?;
+
+		for ProvedValidator { key_owner_proof, .. } in key_owner_proofs {
+			if self
+				.runtime
+				.runtime_api()
+				.submit_report_future_block_voting_unsigned_extrinsic(
+					best_block_hash,
+					proof.clone(),
+					key_owner_proof,
+				)
+				.map_err(Error::RuntimeApi)?
+				.is_some()
+			{
+				metric_inc!(self.metrics, beefy_future_block_voting_reports);
+			}
 		}
 
 		Ok(())
@@ -37,6 +37,7 @@ use parking_lot::Mutex;
 use prometheus_endpoint::Registry;
 use sc_client_api::{Backend, BlockBackend, BlockchainEvents, FinalityNotification, Finalizer};
 use sc_consensus::BlockImport;
+use sc_keystore::SlashingProtectionPtr;
 use sc_network::{NetworkRequest, NotificationService, ProtocolName};
 use sc_network_gossip::{GossipEngine, Network as GossipNetwork, Syncing as GossipSyncing};
 use sc_utils::mpsc::{tracing_unbounded, TracingUnboundedReceiver};
@@ -221,6 +222,10 @@ pub struct BeefyParams<B: Block, BE, C, N, P, R, S, AuthorityId: AuthorityIdBoun
 	pub runtime: Arc<R>,
 	/// Local key store
 	pub key_store: Option<KeystorePtr>,
+	/// Slashing protection store consulted before signing votes.
+	///
+	/// Votes conflicting with the ones already signed are not cast if set.
+	pub slashing_protection: Option<SlashingProtectionPtr>,
 	/// BEEFY voter network params
 	pub network_params: BeefyNetworkParams<B, N, S>,
 	/// Minimal delta between blocks, BEEFY should vote for
@@ -307,7 +312,7 @@ where
 	}
 
 	/// Takes rest of missing pieces as params and builds the `BeefyWorker`.
-	pub fn build<P, S, N>(
+	pub fn build<P: PayloadProvider<B> + Clone, S, N>(
 		self,
 		payload_provider: P,
 		sync: Arc<S>,
@@ -315,15 +320,23 @@ where
 		links: BeefyVoterLinks<B, AuthorityId>,
 		pending_justifications: BTreeMap<NumberFor<B>, BeefyVersionedFinalityProof<B, AuthorityId>>,
 		is_authority: bool,
+		slashing_protection: Option<SlashingProtectionPtr>,
 	) -> BeefyWorker<B, BE, P, R, S, N, AuthorityId> {
 		let key_store = Arc::new(self.key_store);
 		BeefyWorker {
 			backend: self.backend.clone(),
 			runtime: self.runtime.clone(),
 			key_store: key_store.clone(),
-			payload_provider,
+			slashing_protection,
+			payload_provider: payload_provider.clone(),
 			sync,
-			fisherman: Arc::new(Fisherman::new(self.backend, self.runtime, key_store)),
+			fisherman: Arc::new(Fisherman::new(
+				self.backend,
+				self.runtime,
+				key_store,
+				payload_provider,
+				self.metrics.clone(),
+			)),
 			metrics: self.metrics,
 			persisted_state: self.persisted_state,
 			comms,
@@ -536,6 +549,7 @@ pub async fn start_beefy_gadget<B, BE, C, N, P, R, S, AuthorityId>(
 		payload_provider,
 		runtime,
 		key_store,
+		slashing_protection,
 		network_params,
 		min_block_delta,
 		prometheus_registry,
@@ -629,6 +643,7 @@ pub async fn start_beefy_gadget<B, BE, C, N, P, R, S, AuthorityId>(
 			links.clone(),
 			BTreeMap::new(),
 			is_authority,
+			slashing_protection.clone(),
 		);
 
 		futures::select! {
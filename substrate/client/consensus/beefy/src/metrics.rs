@@ -49,6 +49,12 @@ pub struct VoterMetrics {
 	pub beefy_good_votes_processed: Counter<U64>,
 	/// Number of equivocation votes received
 	pub beefy_equivocation_votes: Counter<U64>,
+	/// Number of double voting equivocation reports submitted
+	pub beefy_double_voting_reports: Counter<U64>,
+	/// Number of fork voting equivocation reports submitted
+	pub beefy_fork_voting_reports: Counter<U64>,
+	/// Number of future block voting equivocation reports submitted
+	pub beefy_future_block_voting_reports: Counter<U64>,
 	/// Number of invalid votes received
 	pub beefy_invalid_votes: Counter<U64>,
 	/// Number of valid but stale votes received
@@ -118,6 +124,27 @@ impl PrometheusRegister for VoterMetrics {
 				)?,
 				registry,
 			)?,
+			beefy_double_voting_reports: register(
+				Counter::new(
+					"substrate_beefy_double_voting_reports",
+					"Number of double voting equivocation reports submitted",
+				)?,
+				registry,
+			)?,
+			beefy_fork_voting_reports: register(
+				Counter::new(
+					"substrate_beefy_fork_voting_reports",
+					"Number of fork voting equivocation reports submitted",
+				)?,
+				registry,
+			)?,
+			beefy_future_block_voting_reports: register(
+				Counter::new(
+					"substrate_beefy_future_block_voting_reports",
+					"Number of future block voting equivocation reports submitted",
+				)?,
+				registry,
+			)?,
 			beefy_invalid_votes: register(
 				Counter::new("substrate_beefy_invalid_votes", "Number of invalid votes received")?,
 				registry,
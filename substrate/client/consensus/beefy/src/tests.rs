@@ -64,9 +64,9 @@ use sp_consensus_beefy::{
 	known_payloads,
 	mmr::{find_mmr_root_digest, MmrRootProvider},
 	test_utils::Keyring as BeefyKeyring,
-	BeefyApi, Commitment, ConsensusLog, DoubleVotingProof, MmrRootHash, OpaqueKeyOwnershipProof,
-	Payload, SignedCommitment, ValidatorSet, ValidatorSetId, VersionedFinalityProof, VoteMessage,
-	BEEFY_ENGINE_ID,
+	BeefyApi, Commitment, ConsensusLog, DoubleVotingProof, ForkVotingProof, FutureBlockVotingProof,
+	MmrRootHash, OpaqueKeyOwnershipProof, Payload, SignedCommitment, ValidatorSet, ValidatorSetId,
+	VersionedFinalityProof, VoteMessage, BEEFY_ENGINE_ID,
 };
 use sp_core::H256;
 use sp_keystore::{testing::MemoryKeystore, Keystore, KeystorePtr};
@@ -74,10 +74,10 @@ use sp_mmr_primitives::{Error as MmrError, MmrApi};
 use sp_runtime::{
 	codec::{Decode, Encode},
 	traits::{Header as HeaderT, NumberFor},
-	BuildStorage, DigestItem, EncodedJustification, Justifications, Storage,
+	BuildStorage, DigestItem, EncodedJustification, Justifications, OpaqueValue, Storage,
 };
 use std::{marker::PhantomData, sync::Arc, task::Poll};
-use substrate_test_runtime_client::{BlockBuilderExt, ClientExt};
+use substrate_test_runtime_client::{runtime::Header, BlockBuilderExt, ClientExt};
 use tokio::time::Duration;
 
 const GENESIS_HASH: H256 = H256::zero();
@@ -266,6 +266,10 @@ pub(crate) struct TestApi {
 	pub mmr_root_hash: MmrRootHash,
 	pub reported_equivocations:
 		Option<Arc<Mutex<Vec<DoubleVotingProof<NumberFor<Block>, AuthorityId, Signature>>>>>,
+	pub reported_fork_votings:
+		Option<Arc<Mutex<Vec<ForkVotingProof<Header, AuthorityId, OpaqueValue>>>>>,
+	pub reported_future_block_votings:
+		Option<Arc<Mutex<Vec<FutureBlockVotingProof<NumberFor<Block>, AuthorityId>>>>>,
 }
 
 impl TestApi {
@@ -279,6 +283,8 @@ impl TestApi {
 			validator_set: Some(validator_set.clone()),
 			mmr_root_hash,
 			reported_equivocations: None,
+			reported_fork_votings: None,
+			reported_future_block_votings: None,
 		}
 	}
 
@@ -288,11 +294,15 @@ impl TestApi {
 			validator_set: Some(validator_set.clone()),
 			mmr_root_hash: GOOD_MMR_ROOT,
 			reported_equivocations: None,
+			reported_fork_votings: None,
+			reported_future_block_votings: None,
 		}
 	}
 
 	pub fn allow_equivocations(&mut self) {
 		self.reported_equivocations = Some(Arc::new(Mutex::new(vec![])));
+		self.reported_fork_votings = Some(Arc::new(Mutex::new(vec![])));
+		self.reported_future_block_votings = Some(Arc::new(Mutex::new(vec![])));
 	}
 }
 
@@ -330,10 +340,37 @@ sp_api::mock_impl_runtime_apis! {
 			}
 		}
 
+		fn submit_report_fork_voting_unsigned_extrinsic(
+			proof: ForkVotingProof<Header, AuthorityId, OpaqueValue>,
+			_dummy: OpaqueKeyOwnershipProof,
+		) -> Option<()> {
+			// Peers voting on bad payloads are expected in some tests, only record the reports
+			// if asked to.
+			if let Some(equivocations_buf) = self.inner.reported_fork_votings.as_ref() {
+				equivocations_buf.lock().push(proof);
+			}
+			None
+		}
+
+		fn submit_report_future_block_voting_unsigned_extrinsic(
+			proof: FutureBlockVotingProof<NumberFor<Block>, AuthorityId>,
+			_dummy: OpaqueKeyOwnershipProof,
+		) -> Option<()> {
+			if let Some(equivocations_buf) = self.inner.reported_future_block_votings.as_ref() {
+				equivocations_buf.lock().push(proof);
+			}
+			None
+		}
+
 		fn generate_key_ownership_proof(
 			_dummy1: ValidatorSetId,
 			_dummy2: AuthorityId,
 		) -> Option<OpaqueKeyOwnershipProof> { Some(OpaqueKeyOwnershipProof::new(vec![])) }
+
+		fn generate_ancestry_proof(
+			_dummy1: NumberFor<Block>,
+			_dummy2: Option<NumberFor<Block>>,
+		) -> Option<OpaqueValue> { Some(OpaqueValue::new(vec![])) }
 	}
 
 	impl MmrApi<Block, MmrRootHash, NumberFor<Block>> for RuntimeApi {
@@ -454,6 +491,7 @@ where
 			payload_provider,
 			runtime: api.clone(),
 			key_store: Some(keystore),
+			slashing_protection: None,
 			network_params,
 			links: beefy_voter_links.unwrap(),
 			min_block_delta,
@@ -37,6 +37,7 @@ use codec::{Codec, Decode, DecodeAll, Encode};
 use futures::{stream::Fuse, FutureExt, StreamExt};
 use log::{debug, error, info, trace, warn};
 use sc_client_api::{Backend, HeaderBackend};
+use sc_keystore::slashing_protection::{SigningDomain, SigningPosition, SlashingProtectionPtr};
 use sc_utils::notification::NotificationReceiver;
 use sp_api::ProvideRuntimeApi;
 use sp_arithmetic::traits::{AtLeast32Bit, Saturating};
@@ -385,9 +386,10 @@ pub(crate) struct BeefyWorker<B: Block, BE, P, RuntimeApi, S, N, AuthorityId: Au
 	pub backend: Arc<BE>,
 	pub runtime: Arc<RuntimeApi>,
 	pub key_store: Arc<BeefyKeystore<AuthorityId>>,
+	pub slashing_protection: Option<SlashingProtectionPtr>,
 	pub payload_provider: P,
 	pub sync: Arc<S>,
-	pub fisherman: Arc<Fisherman<B, BE, RuntimeApi, AuthorityId>>,
+	pub fisherman: Arc<Fisherman<B, BE, P, RuntimeApi, AuthorityId>>,
 
 	// communication (created once, but returned and reused if worker is restarted/reinitialized)
 	pub comms: BeefyComms<B, N, AuthorityId>,
@@ -515,6 +517,8 @@ where
 	}
 
 	/// Based on [VoterOracle] this vote is either processed here or discarded.
+	///
+	/// The vote is also checked for fork and future block voting by the [Fisherman].
 	fn triage_incoming_vote(
 		&mut self,
 		vote: VoteMessage<NumberFor<B>, AuthorityId, <AuthorityId as RuntimeAppPublic>::Signature>,
@@ -522,6 +526,10 @@ where
 	where
 		<AuthorityId as RuntimeAppPublic>::Signature: Encode + Decode,
 	{
+		if let Err(err) = self.fisherman.check_vote(vote.clone()) {
+			debug!(target: LOG_TARGET, "🥩 Failed to check vote for equivocation: {}", err);
+		}
+
 		let block_num = vote.commitment.block_number;
 		match self.voting_oracle().triage_round(block_num)? {
 			RoundAction::Process =>
@@ -536,18 +544,25 @@ where
 					);
 				},
 			RoundAction::Drop => metric_inc!(self.metrics, beefy_stale_votes),
-			RoundAction::Enqueue => error!(target: LOG_TARGET, "🥩 unexpected vote: {:?}.", vote),
+			// The gossip validator hands over votes for future rounds for the fisherman only.
+			RoundAction::Enqueue =>
+				debug!(target: LOG_TARGET, "🥩 Ignoring vote for future round: {:?}.", vote),
 		};
 		Ok(())
 	}
 
 	/// Based on [VoterOracle] this justification is either processed here or enqueued for later.
 	///
-	/// Expects `justification` to be valid.
+	/// Expects `justification` to be valid. Its signatories are also checked for fork and future
+	/// block voting by the [Fisherman].
 	fn triage_incoming_justif(
 		&mut self,
 		justification: BeefyVersionedFinalityProof<B, AuthorityId>,
 	) -> Result<(), Error> {
+		if let Err(err) = self.fisherman.check_proof(justification.clone()) {
+			debug!(target: LOG_TARGET, "🥩 Failed to check justification for equivocation: {}", err);
+		}
+
 		let signed_commitment = match justification {
 			VersionedFinalityProof::V1(ref sc) => sc,
 		};
@@ -781,6 +796,18 @@ where
 		let commitment = Commitment { payload, block_number: target_number, validator_set_id };
 		let encoded_commitment = commitment.encode();
 
+		if let Some(slashing_protection) = &self.slashing_protection {
+			if let Err(err) = slashing_protection.check_and_record(
+				SigningDomain::Beefy,
+				&authority_id.to_raw_vec(),
+				SigningPosition::round(validator_set_id, target_number.saturated_into()),
+				&encoded_commitment,
+			) {
+				warn!(target: LOG_TARGET, "🥩 Not voting for {:?}: {}", target_hash, err);
+				return Ok(());
+			}
+		}
+
 		let signature = match self.key_store.sign(&authority_id, &encoded_commitment) {
 			Ok(sig) => sig,
 			Err(err) => {
@@ -1044,7 +1071,7 @@ pub(crate) mod tests {
 		ecdsa_crypto, known_payloads,
 		known_payloads::MMR_ROOT_ID,
 		mmr::MmrRootProvider,
-		test_utils::{generate_double_voting_proof, Keyring},
+		test_utils::{generate_double_voting_proof, signed_vote, Keyring},
 		ConsensusLog, Payload, SignedCommitment,
 	};
 	use sp_runtime::traits::{Header as HeaderT, One};
@@ -1152,10 +1179,17 @@ pub(crate) mod tests {
 			backend: backend.clone(),
 			runtime: api.clone(),
 			key_store: key_store.clone(),
+			slashing_protection: None,
 			metrics,
 			payload_provider,
 			sync: Arc::new(sync),
-			fisherman: Arc::new(Fisherman::new(backend, api, key_store)),
+			fisherman: Arc::new(Fisherman::new(
+				backend,
+				api,
+				key_store,
+				payload_provider.clone(),
+				None,
+			)),
 			links,
 			comms,
 			pending_justifications: BTreeMap::new(),
@@ -1582,6 +1616,8 @@ pub(crate) mod tests {
 			worker.backend.clone(),
 			worker.runtime.clone(),
 			worker.key_store.clone(),
+			MmrRootProvider::new(api_alice.clone()),
+			None,
 		));
 
 		// let there be a block with num = 1:
@@ -1632,4 +1668,200 @@ pub(crate) mod tests {
 		// verify nothing reported to runtime
 		assert!(api_alice.reported_equivocations.as_ref().unwrap().lock().is_empty());
 	}
+
+	#[tokio::test]
+	async fn should_report_fork_and_future_block_votes() {
+		let set_id = 0;
+		let keys = [Keyring::Alice];
+		let validator_set = ValidatorSet::new(make_beefy_ids(&keys), set_id).unwrap();
+		let mut api_alice = TestApi::with_validator_set(&validator_set);
+		api_alice.allow_equivocations();
+		let api_alice = Arc::new(api_alice);
+
+		let mut net = BeefyTestNet::new(1);
+		let mut worker = create_beefy_worker(net.peer(0), &keys[0], 1, validator_set.clone());
+		worker.runtime = api_alice.clone();
+		worker.fisherman = Arc::new(Fisherman::new(
+			worker.backend.clone(),
+			worker.runtime.clone(),
+			worker.key_store.clone(),
+			MmrRootProvider::new(api_alice.clone()),
+			None,
+		));
+
+		// block #1 is finalized, block #2 is not
+		let _ = net.peer(0).push_blocks(1, false);
+
+		let good_payload =
+			Payload::from_single_entry(MMR_ROOT_ID, api_alice.mmr_root_hash.encode());
+		let bad_payload = Payload::from_single_entry(MMR_ROOT_ID, vec![42]);
+
+		// votes on the canonical payload are not reported
+		worker
+			.fisherman
+			.check_vote(signed_vote(1, good_payload.clone(), set_id, &Keyring::Bob))
+			.unwrap();
+		// votes on unfinalized blocks can't be checked yet
+		worker
+			.fisherman
+			.check_vote(signed_vote(2, bad_payload.clone(), set_id, &Keyring::Bob))
+			.unwrap();
+		assert!(api_alice.reported_fork_votings.as_ref().unwrap().lock().is_empty());
+		assert!(api_alice.reported_future_block_votings.as_ref().unwrap().lock().is_empty());
+
+		// Bob votes on a fork of finalized block #1
+		let fork_vote = signed_vote(1, bad_payload.clone(), set_id, &Keyring::Bob);
+		worker.fisherman.check_vote(fork_vote.clone()).unwrap();
+		{
+			let reported = api_alice.reported_fork_votings.as_ref().unwrap().lock();
+			assert_eq!(reported.len(), 1);
+			assert_eq!(reported[0].vote, fork_vote);
+			assert_eq!(
+				reported[0].header.hash(),
+				worker.backend.blockchain().info().finalized_hash
+			);
+		}
+
+		// Bob votes on block #3, which doesn't exist yet
+		let future_vote = signed_vote(3, good_payload.clone(), set_id, &Keyring::Bob);
+		worker.fisherman.check_vote(future_vote.clone()).unwrap();
+		{
+			let reported = api_alice.reported_future_block_votings.as_ref().unwrap().lock();
+			assert_eq!(reported.len(), 1);
+			assert_eq!(reported[0].vote, future_vote);
+		}
+
+		// signatories of a justification for a fork are reported too
+		let commitment =
+			Commitment { payload: bad_payload.clone(), block_number: 1, validator_set_id: set_id };
+		let signature = Keyring::Alice.sign(&commitment.encode());
+		let proof = VersionedFinalityProof::V1(SignedCommitment {
+			commitment,
+			signatures: vec![Some(signature)],
+		});
+		// ..unless they are our own
+		worker.fisherman.check_proof(proof).unwrap();
+		assert_eq!(api_alice.reported_fork_votings.as_ref().unwrap().lock().len(), 1);
+
+		// bad signatures are never reported
+		let mut bad_vote = signed_vote(3, good_payload, set_id, &Keyring::Bob);
+		bad_vote.id = Keyring::Charlie.public();
+		worker.fisherman.check_vote(bad_vote).unwrap();
+		assert_eq!(api_alice.reported_future_block_votings.as_ref().unwrap().lock().len(), 1);
+	}
+
+	#[tokio::test]
+	async fn should_report_equivocations_received_through_gossip() {
+		use crate::communication::gossip::tests::TestContext;
+		use sc_network_gossip::{ValidationResult, Validator};
+		use sc_network_types::PeerId;
+
+		let set_id = 0;
+		let keys = [Keyring::Alice, Keyring::Bob];
+		let validator_set = ValidatorSet::new(make_beefy_ids(&keys), set_id).unwrap();
+		let mut api_alice = TestApi::with_validator_set(&validator_set);
+		api_alice.allow_equivocations();
+		let api_alice = Arc::new(api_alice);
+
+		let mut net = BeefyTestNet::new(1);
+		let mut worker = create_beefy_worker(net.peer(0), &keys[0], 1, validator_set.clone());
+		worker.runtime = api_alice.clone();
+		worker.fisherman = Arc::new(Fisherman::new(
+			worker.backend.clone(),
+			worker.runtime.clone(),
+			worker.key_store.clone(),
+			MmrRootProvider::new(api_alice.clone()),
+			None,
+		));
+
+		// block #1 is finalized, block #2 is not, and only round #2 is live
+		let _ = net.peer(0).push_blocks(1, false);
+		worker.comms.gossip_validator.update_filter(GossipFilterCfg {
+			start: 2,
+			end: 2,
+			validator_set: &validator_set,
+		});
+
+		// hand the vote to the gossip validator, and to the worker if it is to be processed
+		let sender = PeerId::random();
+		let gossip = |worker: &mut BeefyWorker<_, _, _, _, _, _, _>, vote| {
+			let encoded = GossipMessage::<Block, ecdsa_crypto::AuthorityId>::Vote(vote).encode();
+			let result =
+				worker.comms.gossip_validator.validate(&mut TestContext, &sender, &encoded);
+			assert!(matches!(result, ValidationResult::ProcessAndDiscard(_)));
+			let vote =
+				GossipMessage::<Block, ecdsa_crypto::AuthorityId>::decode_all(&mut &encoded[..])
+					.unwrap()
+					.unwrap_vote()
+					.unwrap();
+			worker.triage_incoming_vote(vote).unwrap();
+		};
+
+		let good_payload =
+			Payload::from_single_entry(MMR_ROOT_ID, api_alice.mmr_root_hash.encode());
+		let bad_payload = Payload::from_single_entry(MMR_ROOT_ID, vec![42]);
+
+		// Bob votes on a fork of finalized block #1, which isn't live anymore
+		let fork_vote = signed_vote(1, bad_payload, set_id, &Keyring::Bob);
+		gossip(&mut worker, fork_vote.clone());
+		{
+			let reported = api_alice.reported_fork_votings.as_ref().unwrap().lock();
+			assert_eq!(reported.len(), 1);
+			assert_eq!(reported[0].vote, fork_vote);
+		}
+
+		// Bob votes on block #3, which doesn't exist yet
+		let future_vote = signed_vote(3, good_payload, set_id, &Keyring::Bob);
+		gossip(&mut worker, future_vote.clone());
+		{
+			let reported = api_alice.reported_future_block_votings.as_ref().unwrap().lock();
+			assert_eq!(reported.len(), 1);
+			assert_eq!(reported[0].vote, future_vote);
+		}
+	}
+
+	#[tokio::test]
+	async fn should_not_vote_against_slashing_protection() {
+		use sc_keystore::slashing_protection::SlashingProtection;
+
+		let keys = [Keyring::Alice, Keyring::Bob];
+		let validator_set = ValidatorSet::new(make_beefy_ids(&keys), 0).unwrap();
+		let mut net = BeefyTestNet::new(1);
+		let mut worker = create_beefy_worker(net.peer(0), &keys[0], 1, validator_set.clone());
+
+		// a commitment on another payload for block #1 has already been signed
+		let slashing_protection = Arc::new(SlashingProtection::in_memory());
+		let other_commitment = Commitment {
+			payload: Payload::from_single_entry(MMR_ROOT_ID, vec![42]),
+			block_number: 1,
+			validator_set_id: validator_set.id(),
+		};
+		slashing_protection
+			.check_and_record(
+				SigningDomain::Beefy,
+				&Keyring::<ecdsa_crypto::AuthorityId>::Alice.public().to_raw_vec(),
+				SigningPosition::round(validator_set.id(), 1),
+				&other_commitment.encode(),
+			)
+			.unwrap();
+		worker.slashing_protection = Some(slashing_protection);
+
+		// the vote is refused
+		worker.do_vote(1).unwrap();
+		assert_eq!(worker.persisted_state.best_voted, 0);
+
+		// while it is cast with a clean history, which then records it
+		let slashing_protection = Arc::new(SlashingProtection::in_memory());
+		worker.slashing_protection = Some(slashing_protection.clone());
+		worker.do_vote(1).unwrap();
+		assert_eq!(worker.persisted_state.best_voted, 1);
+		assert!(slashing_protection
+			.check_and_record(
+				SigningDomain::Beefy,
+				&Keyring::<ecdsa_crypto::AuthorityId>::Alice.public().to_raw_vec(),
+				SigningPosition::round(validator_set.id(), 1),
+				&other_commitment.encode(),
+			)
+			.is_err());
+	}
 }
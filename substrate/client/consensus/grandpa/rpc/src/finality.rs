@@ -32,6 +32,14 @@ pub trait RpcFinalityProofProvider<Block: BlockT> {
 		&self,
 		block: NumberFor<Block>,
 	) -> Result<Option<EncodedFinalityProof>, sc_consensus_grandpa::FinalityProofError>;
+
+	/// Prove finality for the blocks in the given range by returning Justifications for blocks
+	/// covering it.
+	fn rpc_prove_finality_range(
+		&self,
+		from: NumberFor<Block>,
+		to: NumberFor<Block>,
+	) -> Result<Vec<EncodedFinalityProof>, sc_consensus_grandpa::FinalityProofError>;
 }
 
 impl<B, Block> RpcFinalityProofProvider<Block> for FinalityProofProvider<B, Block>
@@ -46,4 +54,13 @@ where
 	) -> Result<Option<EncodedFinalityProof>, sc_consensus_grandpa::FinalityProofError> {
 		self.prove_finality(block).map(|x| x.map(|y| EncodedFinalityProof(y.into())))
 	}
+
+	fn rpc_prove_finality_range(
+		&self,
+		from: NumberFor<Block>,
+		to: NumberFor<Block>,
+	) -> Result<Vec<EncodedFinalityProof>, sc_consensus_grandpa::FinalityProofError> {
+		self.prove_finality_range(from, to)
+			.map(|proofs| proofs.into_iter().map(|y| EncodedFinalityProof(y.into())).collect())
+	}
 }
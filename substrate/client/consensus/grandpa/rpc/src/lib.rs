@@ -26,6 +26,7 @@ use std::sync::Arc;
 use jsonrpsee::{
 	core::{async_trait, server::PendingSubscriptionSink},
 	proc_macros::rpc,
+	Extensions,
 };
 
 mod error;
@@ -42,6 +43,7 @@ use sc_rpc::{
 	utils::{BoundedVecDeque, PendingSubscription},
 	SubscriptionTaskExecutor,
 };
+use sc_rpc_api::check_if_safe;
 use sp_runtime::traits::{Block as BlockT, NumberFor};
 
 /// Provides RPC methods for interacting with GRANDPA.
@@ -65,6 +67,22 @@ pub trait GrandpaApi<Notification, Hash, Number> {
 	/// in the set and all the intermediary headers to link them together.
 	#[method(name = "grandpa_proveFinality")]
 	async fn prove_finality(&self, block: Number) -> Result<Option<EncodedFinalityProof>, Error>;
+
+	/// Prove finality for the blocks in the range `[from, to]` by returning Justifications and the
+	/// intermediary headers linking them together, so that all the headers after `from` up to at
+	/// least `to` are returned once.
+	///
+	/// At most `MAX_FINALITY_PROOFS_PER_RANGE` proofs are returned, in which case the caller
+	/// should continue from the block justified by the last one.
+	///
+	/// This method is unsafe, as proving finality of a range of blocks may read many blocks
+	/// from the database.
+	#[method(name = "grandpa_proveFinalityRange", with_extensions)]
+	async fn prove_finality_range(
+		&self,
+		from: Number,
+		to: Number,
+	) -> Result<Vec<EncodedFinalityProof>, Error>;
 }
 
 /// Provides RPC methods for interacting with GRANDPA.
@@ -126,6 +144,20 @@ where
 			error::Error::ProveFinalityFailed(e)
 		})
 	}
+
+	async fn prove_finality_range(
+		&self,
+		ext: &Extensions,
+		from: NumberFor<Block>,
+		to: NumberFor<Block>,
+	) -> Result<Vec<EncodedFinalityProof>, Error> {
+		check_if_safe(ext)?;
+
+		self.finality_proof_provider.rpc_prove_finality_range(from, to).map_err(|e| {
+			warn!("Error proving finality of range: {}", e);
+			error::Error::ProveFinalityFailed(e)
+		})
+	}
 }
 
 #[cfg(test)]
@@ -140,6 +172,7 @@ mod tests {
 		report, AuthorityId, FinalityProof, GrandpaJustification, GrandpaJustificationSender,
 	};
 	use sc_rpc::testing::test_executor;
+	use sc_rpc_api::DenyUnsafe;
 	use sp_blockchain::HeaderBackend;
 	use sp_core::crypto::ByteArray;
 	use sp_keyring::Ed25519Keyring;
@@ -203,6 +236,18 @@ mod tests {
 					.into(),
 			)))
 		}
+
+		fn rpc_prove_finality_range(
+			&self,
+			_from: NumberFor<Block>,
+			_to: NumberFor<Block>,
+		) -> Result<Vec<EncodedFinalityProof>, sc_consensus_grandpa::FinalityProofError> {
+			Ok(self
+				.finality_proof
+				.iter()
+				.map(|proof| EncodedFinalityProof(proof.encode().into()))
+				.collect())
+		}
 	}
 
 	impl ReportVoterState for TestVoterState {
@@ -412,4 +457,36 @@ mod tests {
 		let finality_proof_rpc: FinalityProof<Header> = Decode::decode(&mut &bytes[..]).unwrap();
 		assert_eq!(finality_proof_rpc, finality_proof);
 	}
+
+	#[tokio::test]
+	async fn prove_finality_range_with_test_finality_proof_provider() {
+		let finality_proof = FinalityProof {
+			block: header(42).hash(),
+			justification: create_justification().encode(),
+			unknown_headers: vec![header(41), header(42)],
+		};
+		let (mut rpc, _) =
+			setup_io_handler_with_finality_proofs(TestVoterState, Some(finality_proof.clone()));
+
+		// the method is unsafe
+		rpc.extensions_mut().insert(DenyUnsafe::Yes);
+		let (response, _) = rpc
+			.raw_json_request(
+				r#"{"jsonrpc":"2.0","method":"grandpa_proveFinalityRange","params":[40,42],"id":0}"#,
+				1,
+			)
+			.await
+			.unwrap();
+		assert_eq!(
+			response,
+			r#"{"jsonrpc":"2.0","id":0,"error":{"code":-32601,"message":"RPC call is unsafe to be called externally"}}"#,
+		);
+
+		rpc.extensions_mut().insert(DenyUnsafe::No);
+		let proofs: Vec<sp_core::Bytes> =
+			rpc.call("grandpa_proveFinalityRange", [40, 42]).await.unwrap();
+		let proofs: Vec<FinalityProof<Header>> =
+			proofs.iter().map(|bytes| Decode::decode(&mut &bytes[..]).unwrap()).collect();
+		assert_eq!(proofs, vec![finality_proof]);
+	}
 }
@@ -30,7 +30,7 @@
 //! under certain conditions that are used to un-stick the protocol.
 
 use futures::{channel::mpsc, prelude::*};
-use log::{debug, trace};
+use log::{debug, trace, warn};
 use parking_lot::Mutex;
 use prometheus_endpoint::Registry;
 use std::{
@@ -46,6 +46,7 @@ use finality_grandpa::{
 	voter_set::VoterSet,
 	Message::{Precommit, Prevote, PrimaryPropose},
 };
+use sc_keystore::slashing_protection::{SigningDomain, SigningPosition, SlashingProtectionPtr};
 use sc_network::{NetworkBlock, NetworkSyncForkRequest, NotificationService, ReputationChange};
 use sc_network_gossip::{GossipEngine, Network as GossipNetwork};
 use sc_telemetry::{telemetry, TelemetryHandle, CONSENSUS_DEBUG, CONSENSUS_INFO};
@@ -334,6 +335,7 @@ impl<B: BlockT, N: Network<B>, S: Syncing<B>> NetworkBridge<B, N, S> {
 	pub(crate) fn round_communication(
 		&self,
 		keystore: Option<LocalIdKeystore>,
+		slashing_protection: Option<SlashingProtectionPtr>,
 		round: Round,
 		set_id: SetId,
 		voters: Arc<VoterSet<AuthorityId>>,
@@ -421,6 +423,7 @@ impl<B: BlockT, N: Network<B>, S: Syncing<B>> NetworkBridge<B, N, S> {
 		let (tx, out_rx) = mpsc::channel(0);
 		let outgoing = OutgoingMessages::<B> {
 			keystore,
+			slashing_protection,
 			round: round.0,
 			set_id: set_id.0,
 			network: self.gossip_engine.clone(),
@@ -712,6 +715,7 @@ pub(crate) struct OutgoingMessages<Block: BlockT> {
 	round: RoundNumber,
 	set_id: SetIdNumber,
 	keystore: Option<LocalIdKeystore>,
+	slashing_protection: Option<SlashingProtectionPtr>,
 	sender: mpsc::Sender<SignedMessage<Block::Header>>,
 	network: Arc<Mutex<GossipEngine<Block>>>,
 	has_voted: HasVoted<Block::Header>,
@@ -757,6 +761,27 @@ impl<Block: BlockT> Sink<Message<Block::Header>> for OutgoingMessages<Block> {
 		// when locals exist, sign messages on import
 		if let Some(ref keystore) = self.keystore {
 			let target_hash = *(msg.target().0);
+
+			if let Some(ref slashing_protection) = self.slashing_protection {
+				let domain = match msg {
+					finality_grandpa::Message::PrimaryPropose(_) =>
+						SigningDomain::GrandpaPrimaryPropose,
+					finality_grandpa::Message::Prevote(_) => SigningDomain::GrandpaPrevote,
+					finality_grandpa::Message::Precommit(_) => SigningDomain::GrandpaPrecommit,
+				};
+				let payload =
+					sp_consensus_grandpa::localized_payload(self.round, self.set_id, &msg);
+				if let Err(e) = slashing_protection.check_and_record(
+					domain,
+					keystore.local_id().as_ref(),
+					SigningPosition::round(self.set_id, self.round),
+					&payload,
+				) {
+					warn!(target: LOG_TARGET, "Not voting in round {}: {}", self.round, e);
+					return Ok(())
+				}
+			}
+
 			let signed = sp_consensus_grandpa::sign_message(
 				keystore.keystore(),
 				msg,
@@ -299,6 +299,7 @@ fn config() -> crate::Config {
 		gossip_duration: std::time::Duration::from_millis(10),
 		justification_generation_period: 256,
 		keystore: None,
+		slashing_protection: None,
 		name: None,
 		local_role: Role::Authority,
 		observer_enabled: true,
@@ -705,6 +706,64 @@ fn peer_with_higher_view_leads_to_catch_up_request() {
 	futures::executor::block_on(test);
 }
 
+#[test]
+fn outgoing_votes_conflicting_with_slashing_protection_are_not_sent() {
+	use crate::environment::HasVoted;
+	use sc_keystore::slashing_protection::{SigningDomain, SigningPosition, SlashingProtection};
+	use sp_consensus_grandpa::{AuthorityId, GRANDPA};
+	use sp_keystore::{testing::MemoryKeystore, Keystore, KeystorePtr};
+
+	let keystore = MemoryKeystore::new();
+	let local_id: AuthorityId = keystore
+		.ed25519_generate_new(GRANDPA, Some(&Ed25519Keyring::Alice.to_seed()))
+		.unwrap()
+		.into();
+	let voters = Arc::new(VoterSet::new(make_ids(&[Ed25519Keyring::Alice])).unwrap());
+
+	// a prevote for another block has already been signed in round 1 of set 0.
+	let slashing_protection = Arc::new(SlashingProtection::in_memory());
+	let previous_prevote = finality_grandpa::Message::Prevote(finality_grandpa::Prevote {
+		target_hash: [2; 32].into(),
+		target_number: 2,
+	});
+	slashing_protection
+		.check_and_record(
+			SigningDomain::GrandpaPrevote,
+			local_id.as_ref(),
+			SigningPosition::round(0, 1),
+			&sp_consensus_grandpa::localized_payload(1, 0, &previous_prevote),
+		)
+		.unwrap();
+
+	let (tester, _net) = make_test_network();
+	let tester = futures::executor::block_on(tester);
+	let (mut incoming, mut outgoing) = tester.net_handle.round_communication(
+		Some((local_id.clone(), Arc::new(keystore) as KeystorePtr).into()),
+		Some(slashing_protection),
+		Round(1),
+		SetId(0),
+		voters,
+		HasVoted::No,
+	);
+
+	let prevote = finality_grandpa::Prevote { target_hash: [1; 32].into(), target_number: 1 };
+	let precommit =
+		finality_grandpa::Precommit { target_hash: prevote.target_hash, target_number: 1 };
+
+	futures::executor::block_on(async {
+		// the conflicting prevote is refused, the precommit is signed and sent.
+		outgoing.send(finality_grandpa::Message::Prevote(prevote)).await.unwrap();
+		outgoing
+			.send(finality_grandpa::Message::Precommit(precommit.clone()))
+			.await
+			.unwrap();
+
+		let signed = incoming.next().await.unwrap();
+		assert_eq!(signed.id, local_id);
+		assert_eq!(signed.message, finality_grandpa::Message::Precommit(precommit));
+	});
+}
+
 fn local_chain_spec() -> Box<dyn sc_chain_spec::ChainSpec> {
 	let chain_spec =
 		sc_chain_spec::GenericChainSpec::<sc_chain_spec::NoExtension, ()>::from_json_bytes(
@@ -35,6 +35,11 @@
 //! Finality proof provider can choose how to provide finality proof on its own. The incomplete
 //! finality proof (that finalizes some block C that is ancestor of the B and descendant
 //! of the U) could be returned.
+//!
+//! The justification used is the one of the first block F >= B for which a justification is
+//! stored (i.e. either a block enacting an authority set change, or a block for which a
+//! justification was persisted every `justification_period` blocks), so that the headers
+//! sub-chain linking B to F is kept short.
 
 use log::{trace, warn};
 use std::sync::Arc;
@@ -57,6 +62,16 @@ use crate::{
 
 const MAX_UNKNOWN_HEADERS: usize = 100_000;
 
+/// Maximum number of finality proofs returned when proving the finality of a range of blocks.
+pub const MAX_FINALITY_PROOFS_PER_RANGE: usize = 32;
+
+/// Maximum number of blocks looked at for a stored justification when proving finality, in
+/// total for all the proofs of a range.
+///
+/// Once reached, the justification of the last block of the authority set (or the best
+/// justification for the latest authority set) is used, which is always known.
+const MAX_JUSTIFICATION_LOOKUPS: usize = 4_096;
+
 /// Finality proof provider for serving network requests.
 #[derive(Clone)]
 pub struct FinalityProofProvider<BE, Block: BlockT> {
@@ -130,6 +145,47 @@ where
 
 		prove_finality(&*self.backend, authority_set_changes, block, collect_unknown_headers)
 	}
+
+	/// Prove finality for the blocks in the range `[from, to]`, returning encoded finality
+	/// proofs.
+	///
+	/// See [`Self::prove_finality_proof_range`].
+	pub fn prove_finality_range(
+		&self,
+		from: NumberFor<Block>,
+		to: NumberFor<Block>,
+	) -> Result<Vec<Vec<u8>>, FinalityProofError> {
+		Ok(self
+			.prove_finality_proof_range(from, to)?
+			.into_iter()
+			.map(|proof| proof.encode())
+			.collect())
+	}
+
+	/// Prove finality for the blocks in the range `[from, to]`.
+	///
+	/// The first proof proves the finality of `from`, and each of the following ones proves the
+	/// finality of the blocks following the block justified by the previous one, so that all the
+	/// headers after `from` up to at least `to` are returned once. At most
+	/// [`MAX_FINALITY_PROOFS_PER_RANGE`] proofs are returned, in which case callers should
+	/// continue from the block justified by the last one.
+	pub fn prove_finality_proof_range(
+		&self,
+		from: NumberFor<Block>,
+		to: NumberFor<Block>,
+	) -> Result<Vec<FinalityProof<Block::Header>>, FinalityProofError> {
+		let authority_set_changes = if let Some(changes) = self
+			.shared_authority_set
+			.as_ref()
+			.map(SharedAuthoritySet::authority_set_changes)
+		{
+			changes
+		} else {
+			return Ok(Vec::new())
+		};
+
+		prove_finality_range(&*self.backend, authority_set_changes, from, to)
+	}
 }
 
 /// Finality for block B is proved by providing:
@@ -151,6 +207,9 @@ pub enum FinalityProofError {
 	/// The requested block has not yet been finalized.
 	#[error("Block not yet finalized")]
 	BlockNotYetFinalized,
+	/// The requested range of blocks is empty.
+	#[error("Invalid block range")]
+	InvalidRange,
 	/// The requested block is not covered by authority set changes. Likely this means the block is
 	/// in the latest authority set, and the subscription API is more appropriate.
 	#[error("Block not covered by authority set changes")]
@@ -160,9 +219,9 @@ pub enum FinalityProofError {
 	Client(#[from] sp_blockchain::Error),
 }
 
-/// Prove finality for the given block number by returning a justification for the last block of
-/// the authority set of which the given block is part of, or a justification for the latest
-/// finalized block if the given block is part of the current authority set.
+/// Prove finality for the given block number by returning the first justification stored for a
+/// block of the authority set of which the given block is part of, or a justification for the
+/// latest finalized block if none is stored for a block of the current authority set.
 ///
 /// If `collect_unknown_headers` is true, the finality proof will include all headers from the
 /// requested block until the block the justification refers to.
@@ -172,6 +231,86 @@ fn prove_finality<Block, B>(
 	block: NumberFor<Block>,
 	collect_unknown_headers: bool,
 ) -> Result<Option<FinalityProof<Block::Header>>, FinalityProofError>
+where
+	Block: BlockT,
+	B: Backend<Block>,
+{
+	let mut lookups = MAX_JUSTIFICATION_LOOKUPS;
+	Ok(prove_finality_from(
+		backend,
+		&authority_set_changes,
+		block,
+		block,
+		collect_unknown_headers,
+		&mut lookups,
+	)?
+	.map(|(proof, _)| proof))
+}
+
+/// Prove finality for the blocks in the range `[from, to]`, see
+/// [`FinalityProofProvider::prove_finality_proof_range`].
+fn prove_finality_range<Block, B>(
+	backend: &B,
+	authority_set_changes: AuthoritySetChanges<NumberFor<Block>>,
+	from: NumberFor<Block>,
+	to: NumberFor<Block>,
+) -> Result<Vec<FinalityProof<Block::Header>>, FinalityProofError>
+where
+	Block: BlockT,
+	B: Backend<Block>,
+{
+	if from > to {
+		return Err(FinalityProofError::InvalidRange)
+	}
+
+	if backend.blockchain().info().finalized_number < to {
+		return Err(FinalityProofError::BlockNotYetFinalized)
+	}
+
+	let mut proofs = Vec::new();
+	let mut block = from;
+	let mut justified_from = from;
+	let mut lookups = MAX_JUSTIFICATION_LOOKUPS;
+	let mut headers = 0;
+	while proofs.len() < MAX_FINALITY_PROOFS_PER_RANGE {
+		let Some((proof, just_block)) = prove_finality_from(
+			backend,
+			&authority_set_changes,
+			block,
+			justified_from,
+			true,
+			&mut lookups,
+		)?
+		else {
+			break
+		};
+
+		headers += proof.unknown_headers.len();
+		proofs.push(proof);
+		if just_block >= to || headers >= MAX_UNKNOWN_HEADERS {
+			break
+		}
+
+		block = just_block;
+		justified_from = just_block + One::one();
+	}
+
+	Ok(proofs)
+}
+
+/// Prove finality for the given block number, using the first justification stored for a block
+/// not lower than `justified_from`, and return it alongside the number of the justified block.
+///
+/// At most `lookups` blocks are looked at for a stored justification, and `lookups` is decreased
+/// by the number of blocks looked at.
+fn prove_finality_from<Block, B>(
+	backend: &B,
+	authority_set_changes: &AuthoritySetChanges<NumberFor<Block>>,
+	block: NumberFor<Block>,
+	justified_from: NumberFor<Block>,
+	collect_unknown_headers: bool,
+	lookups: &mut usize,
+) -> Result<Option<(FinalityProof<Block::Header>, NumberFor<Block>)>, FinalityProofError>
 where
 	Block: BlockT,
 	B: Backend<Block>,
@@ -179,19 +318,24 @@ where
 	// Early-return if we are sure that there are no blocks finalized that cover the requested
 	// block.
 	let finalized_number = backend.blockchain().info().finalized_number;
-	if finalized_number < block {
+	if finalized_number < justified_from {
 		let err = format!(
 			"Requested finality proof for descendant of #{} while we only have finalized #{}.",
-			block, finalized_number,
+			justified_from, finalized_number,
 		);
 		trace!(target: LOG_TARGET, "{}", &err);
 		return Err(FinalityProofError::BlockNotYetFinalized)
 	}
 
-	let (justification, just_block) = match authority_set_changes.get_set_id(block) {
+	let (justification, just_block) = match authority_set_changes.get_set_id(justified_from) {
 		AuthoritySetChangeId::Latest => {
-			if let Some(justification) = best_justification(backend)?
+			if let Some(justification) =
+				first_stored_justification(backend, justified_from, finalized_number, lookups)?
+			{
+				justification
+			} else if let Some(justification) = best_justification(backend)?
 				.map(|j: GrandpaJustification<Block>| (j.encode(), j.target().0))
+				.filter(|(_, just_block)| *just_block >= justified_from)
 			{
 				justification
 			} else {
@@ -204,32 +348,38 @@ where
 			}
 		},
 		AuthoritySetChangeId::Set(_, last_block_for_set) => {
-			let last_block_for_set_id = backend
-				.blockchain()
-				.expect_block_hash_from_id(&BlockId::Number(last_block_for_set))?;
-			let justification = if let Some(grandpa_justification) = backend
-				.blockchain()
-				.justifications(last_block_for_set_id)?
-				.and_then(|justifications| justifications.into_justification(GRANDPA_ENGINE_ID))
+			if let Some(justification) =
+				first_stored_justification(backend, justified_from, last_block_for_set, lookups)?
 			{
-				grandpa_justification
+				justification
 			} else {
-				trace!(
-					target: LOG_TARGET,
-					"No justification found when making finality proof for {}. \
-					Returning empty proof.",
-					block,
-				);
-				return Ok(None)
-			};
-			(justification, last_block_for_set)
+				let last_block_for_set_id = backend
+					.blockchain()
+					.expect_block_hash_from_id(&BlockId::Number(last_block_for_set))?;
+				let justification = if let Some(grandpa_justification) = backend
+					.blockchain()
+					.justifications(last_block_for_set_id)?
+					.and_then(|justifications| justifications.into_justification(GRANDPA_ENGINE_ID))
+				{
+					grandpa_justification
+				} else {
+					trace!(
+						target: LOG_TARGET,
+						"No justification found when making finality proof for {}. \
+						Returning empty proof.",
+						block,
+					);
+					return Ok(None)
+				};
+				(justification, last_block_for_set)
+			}
 		},
 		AuthoritySetChangeId::Unknown => {
 			warn!(
 				target: LOG_TARGET,
 				"AuthoritySetChanges does not cover the requested block #{} due to missing data. \
 				 You need to resync to populate AuthoritySetChanges properly.",
-				block,
+				justified_from,
 			);
 			return Err(FinalityProofError::BlockNotInAuthoritySetChanges)
 		},
@@ -237,7 +387,7 @@ where
 
 	let mut headers = Vec::new();
 	if collect_unknown_headers {
-		// Collect all headers from the requested block until the last block of the set
+		// Collect all headers from the requested block until the justified block
 		let mut current = block + One::one();
 		loop {
 			if current > just_block || headers.len() >= MAX_UNKNOWN_HEADERS {
@@ -249,11 +399,45 @@ where
 		}
 	};
 
-	Ok(Some(FinalityProof {
+	let proof = FinalityProof {
 		block: backend.blockchain().expect_block_hash_from_id(&BlockId::Number(just_block))?,
 		justification,
 		unknown_headers: headers,
-	}))
+	};
+	Ok(Some((proof, just_block)))
+}
+
+/// Return the first GRANDPA justification stored for a block in the range `[from, to]`,
+/// alongside the number of that block.
+///
+/// At most `lookups` blocks are looked at, and `lookups` is decreased by the number of blocks
+/// looked at.
+fn first_stored_justification<Block, B>(
+	backend: &B,
+	from: NumberFor<Block>,
+	to: NumberFor<Block>,
+	lookups: &mut usize,
+) -> Result<Option<(Vec<u8>, NumberFor<Block>)>, FinalityProofError>
+where
+	Block: BlockT,
+	B: Backend<Block>,
+{
+	let mut current = from;
+	while current <= to && *lookups > 0 {
+		let hash = backend.blockchain().expect_block_hash_from_id(&BlockId::Number(current))?;
+		if let Some(justification) = backend
+			.blockchain()
+			.justifications(hash)?
+			.and_then(|justifications| justifications.into_justification(GRANDPA_ENGINE_ID))
+		{
+			return Ok(Some((justification, current)))
+		}
+
+		current += One::one();
+		*lookups -= 1;
+	}
+
+	Ok(None)
 }
 
 #[cfg(test)]
@@ -595,4 +779,145 @@ mod tests {
 			}
 		);
 	}
+
+	fn finalize_with_justification(
+		client: &TestClient,
+		block: &Block,
+		round: u64,
+	) -> GrandpaJustification<Block> {
+		let commit = create_commit(block.clone(), round, 1, &[Ed25519Keyring::Alice]);
+		let justification = GrandpaJustification::from_commit(client, round, commit).unwrap();
+		client.finalize_block(block.hash(), Some((ID, justification.encode()))).unwrap();
+		justification
+	}
+
+	#[test]
+	fn finality_proof_uses_first_stored_justification() {
+		let (client, backend, blocks) = test_blockchain(8, &[4]);
+		let grandpa_just6 = finalize_with_justification(&client, &blocks[5], 6);
+		finalize_with_justification(&client, &blocks[7], 8);
+
+		// The justification stored for block 6 is used instead of the latest one.
+		let mut authority_set_changes = AuthoritySetChanges::empty();
+		authority_set_changes.append(0, 2);
+
+		let proof_of_5: FinalityProof =
+			prove_finality(&*backend, authority_set_changes, 5, true).unwrap().unwrap();
+
+		assert_eq!(
+			proof_of_5,
+			FinalityProof {
+				block: blocks[5].hash(),
+				justification: grandpa_just6.encode(),
+				unknown_headers: vec![blocks[5].header().clone()],
+			}
+		);
+	}
+
+	#[test]
+	fn finality_proof_range_works() {
+		let (client, backend, blocks) = test_blockchain(8, &[4]);
+		let grandpa_just6 = finalize_with_justification(&client, &blocks[5], 6);
+		let grandpa_just8 = finalize_with_justification(&client, &blocks[7], 8);
+
+		let mut authority_set_changes = AuthoritySetChanges::empty();
+		authority_set_changes.append(0, 2);
+
+		assert!(matches!(
+			prove_finality_range(&*backend, authority_set_changes.clone(), 5, 3),
+			Err(FinalityProofError::InvalidRange)
+		));
+		assert!(matches!(
+			prove_finality_range(&*backend, authority_set_changes.clone(), 3, 9),
+			Err(FinalityProofError::BlockNotYetFinalized)
+		));
+
+		let proofs = prove_finality_range(&*backend, authority_set_changes, 3, 8).unwrap();
+
+		assert_eq!(
+			proofs,
+			vec![
+				FinalityProof {
+					block: blocks[5].hash(),
+					justification: grandpa_just6.encode(),
+					unknown_headers: blocks[3..6].iter().map(|b| b.header().clone()).collect(),
+				},
+				FinalityProof {
+					block: blocks[7].hash(),
+					justification: grandpa_just8.encode(),
+					unknown_headers: blocks[6..8].iter().map(|b| b.header().clone()).collect(),
+				},
+			]
+		);
+	}
+
+	#[test]
+	fn finality_proof_caps_justification_lookups() {
+		let (client, backend, blocks) = test_blockchain(8, &[4]);
+		finalize_with_justification(&client, &blocks[5], 6);
+		let grandpa_just8 = finalize_with_justification(&client, &blocks[7], 8);
+
+		let mut authority_set_changes = AuthoritySetChanges::empty();
+		authority_set_changes.append(0, 8);
+
+		// the justification stored for block 6 isn't reached, the one of the last block of the
+		// set is used instead
+		let mut lookups = 2;
+		let (proof, just_block) =
+			prove_finality_from(&*backend, &authority_set_changes, 3, 3, true, &mut lookups)
+				.unwrap()
+				.unwrap();
+
+		assert_eq!(lookups, 0);
+		assert_eq!(just_block, 8);
+		assert_eq!(
+			proof,
+			FinalityProof {
+				block: blocks[7].hash(),
+				justification: grandpa_just8.encode(),
+				unknown_headers: blocks[3..8].iter().map(|b| b.header().clone()).collect(),
+			}
+		);
+	}
+
+	#[test]
+	fn finality_proof_provider_proves_range() {
+		use crate::authorities::AuthoritySet;
+		use fork_tree::ForkTree;
+
+		let (client, backend, blocks) = test_blockchain(8, &[4]);
+		let grandpa_just6 = finalize_with_justification(&client, &blocks[5], 6);
+		let grandpa_just8 = finalize_with_justification(&client, &blocks[7], 8);
+
+		let mut authority_set_changes = AuthoritySetChanges::empty();
+		authority_set_changes.append(0, 2);
+		let authority_set = AuthoritySet::<H256, u64>::new(
+			vec![(Ed25519Keyring::Alice.public().into(), 1)],
+			1,
+			ForkTree::new(),
+			Vec::new(),
+			authority_set_changes,
+		)
+		.unwrap();
+		let provider = FinalityProofProvider::<_, Block>::new(backend, Some(authority_set.into()));
+
+		let proofs = provider.prove_finality_range(3, 8).unwrap();
+		let proofs = proofs
+			.iter()
+			.map(|proof| {
+				check_finality_proof::<Block>(
+					1,
+					vec![(Ed25519Keyring::Alice.public().into(), 1)],
+					proof.clone(),
+				)
+				.unwrap()
+			})
+			.collect::<Vec<_>>();
+
+		assert_eq!(proofs.len(), 2);
+		assert_eq!(proofs[0].justification, grandpa_just6.encode());
+		assert_eq!(proofs[0].unknown_headers.len(), 3);
+		assert_eq!(proofs[1].justification, grandpa_just8.encode());
+		assert_eq!(proofs[1].unknown_headers.last(), Some(blocks[7].header()));
+	}
 }
@@ -67,6 +67,7 @@ use sc_client_api::{
 	BlockchainEvents, CallExecutor, ExecutorProvider, Finalizer, LockImportRun, StorageProvider,
 };
 use sc_consensus::BlockImport;
+use sc_keystore::SlashingProtectionPtr;
 use sc_network::{types::ProtocolName, NetworkBackend, NotificationService};
 use sc_telemetry::{telemetry, TelemetryHandle, CONSENSUS_DEBUG, CONSENSUS_INFO};
 use sc_transaction_pool_api::OffchainTransactionPoolFactory;
@@ -130,14 +131,17 @@ pub use authorities::{AuthoritySet, AuthoritySetChanges, SharedAuthoritySet};
 pub use aux_schema::best_justification;
 pub use communication::grandpa_protocol_name::standard_name as protocol_standard_name;
 pub use finality_grandpa::voter::report;
-pub use finality_proof::{FinalityProof, FinalityProofError, FinalityProofProvider};
+pub use finality_proof::{
+	FinalityProof, FinalityProofError, FinalityProofProvider, MAX_FINALITY_PROOFS_PER_RANGE,
+};
 pub use import::{find_forced_change, find_scheduled_change, GrandpaBlockImport};
 pub use justification::GrandpaJustification;
 pub use notification::{GrandpaJustificationSender, GrandpaJustificationStream};
 pub use observer::run_grandpa_observer;
 pub use voting_rule::{
-	BeforeBestBlockBy, ThreeQuartersOfTheUnfinalizedChain, VotingRule, VotingRuleResult,
-	VotingRulesBuilder,
+	AtMostAheadOf, BeforeBestBlockBy, PauseAtSessionBoundary, ReferenceBlock, ReferenceBlockResult,
+	SharedReferenceBlock, ThreeQuartersOfTheUnfinalizedChain, VotingRule, VotingRuleConfig,
+	VotingRuleConfigError, VotingRuleContext, VotingRuleResult, VotingRulesBuilder,
 };
 
 use aux_schema::PersistentData;
@@ -229,6 +233,10 @@ pub struct Config {
 	pub name: Option<String>,
 	/// The keystore that manages the keys of this node.
 	pub keystore: Option<KeystorePtr>,
+	/// Slashing protection store consulted before signing votes.
+	///
+	/// Votes conflicting with the ones already signed are not cast if set.
+	pub slashing_protection: Option<SlashingProtectionPtr>,
 	/// TelemetryHandle instance.
 	pub telemetry: Option<TelemetryHandle>,
 	/// Chain specific GRANDPA protocol name. See [`crate::protocol_standard_name`].
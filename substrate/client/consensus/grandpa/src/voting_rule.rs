@@ -21,14 +21,29 @@
 //! This exposes the `VotingRule` trait used to implement arbitrary voting
 //! restrictions that are taken into account by the GRANDPA environment when
 //! selecting a finality target to vote on.
+//!
+//! Rules can either be added programmatically through the [`VotingRulesBuilder`],
+//! or described by a list of [`VotingRuleConfig`] loaded from the node
+//! configuration. Rules which depend on other subsystems (e.g. BEEFY or
+//! parachain approval checking) get the block they track through a
+//! [`ReferenceBlock`] registered in a [`VotingRuleContext`].
 
-use std::{future::Future, pin::Pin, sync::Arc};
+use std::{
+	borrow::Cow, collections::HashMap, fs, future::Future, io, path::Path, pin::Pin, sync::Arc,
+};
 
 use dyn_clone::DynClone;
+use log::debug;
+use parking_lot::RwLock;
+use serde::{Deserialize, Serialize};
 
 use sc_client_api::blockchain::HeaderBackend;
+use sc_telemetry::{telemetry, TelemetryHandle, CONSENSUS_DEBUG};
+use sp_arithmetic::traits::Saturating;
 use sp_runtime::traits::{Block as BlockT, Header, NumberFor, One, Zero};
 
+use crate::LOG_TARGET;
+
 /// A future returned by a `VotingRule` to restrict a given vote, if any restriction is necessary.
 pub type VotingRuleResult<Block> =
 	Pin<Box<dyn Future<Output = Option<(<Block as BlockT>::Hash, NumberFor<Block>)>> + Send>>;
@@ -58,6 +73,11 @@ where
 		best_target: &Block::Header,
 		current_target: &Block::Header,
 	) -> VotingRuleResult<Block>;
+
+	/// Name of the rule, reported when it restricts a vote.
+	fn name(&self) -> Cow<'static, str> {
+		std::any::type_name::<Self>().into()
+	}
 }
 
 impl<Block, B> VotingRule<Block, B> for ()
@@ -99,8 +119,6 @@ where
 		best_target: &Block::Header,
 		current_target: &Block::Header,
 	) -> VotingRuleResult<Block> {
-		use sp_arithmetic::traits::Saturating;
-
 		if current_target.number().is_zero() {
 			return Box::pin(async { None })
 		}
@@ -124,6 +142,10 @@ where
 		// find the block at the given target height
 		Box::pin(std::future::ready(find_target(&*backend, target_number, &current_target)))
 	}
+
+	fn name(&self) -> Cow<'static, str> {
+		"before_best_block_by".into()
+	}
 }
 
 /// A custom voting rule that limits votes towards 3/4 of the unfinalized chain,
@@ -164,6 +186,305 @@ where
 		// find the block at the given target height
 		Box::pin(std::future::ready(find_target(&*backend, target_number, current_target)))
 	}
+
+	fn name(&self) -> Cow<'static, str> {
+		"three_quarters_of_the_unfinalized_chain".into()
+	}
+}
+
+/// A future returned by a [`ReferenceBlock`], resolving to the number of the reference block.
+pub type ReferenceBlockResult<Block> =
+	Pin<Box<dyn Future<Output = Option<NumberFor<Block>>> + Send>>;
+
+/// A block tracked by another subsystem that votes shouldn't get too far ahead of,
+/// e.g. the best BEEFY block or the last block approved by parachain approval checking.
+pub trait ReferenceBlock<Block: BlockT>: Send + Sync {
+	/// Return the number of the reference block on the chain from `base` to
+	/// `current_target`, or `None` if it isn't known (in which case votes aren't restricted).
+	fn reference_block(
+		&self,
+		base: &Block::Header,
+		current_target: &Block::Header,
+	) -> ReferenceBlockResult<Block>;
+}
+
+/// A [`ReferenceBlock`] updated by the subsystem tracking it.
+pub struct SharedReferenceBlock<N>(Arc<RwLock<Option<N>>>);
+
+impl<N> SharedReferenceBlock<N> {
+	/// Create a new, not yet known, reference block.
+	pub fn new() -> Self {
+		SharedReferenceBlock(Arc::new(RwLock::new(None)))
+	}
+}
+
+impl<N: Copy> SharedReferenceBlock<N> {
+	/// Note the number of the current reference block.
+	pub fn note(&self, number: N) {
+		*self.0.write() = Some(number);
+	}
+
+	/// Return the number of the current reference block, if known.
+	pub fn get(&self) -> Option<N> {
+		*self.0.read()
+	}
+}
+
+impl<N> Clone for SharedReferenceBlock<N> {
+	fn clone(&self) -> Self {
+		SharedReferenceBlock(self.0.clone())
+	}
+}
+
+impl<N> Default for SharedReferenceBlock<N> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<Block: BlockT> ReferenceBlock<Block> for SharedReferenceBlock<NumberFor<Block>> {
+	fn reference_block(
+		&self,
+		_base: &Block::Header,
+		_current_target: &Block::Header,
+	) -> ReferenceBlockResult<Block> {
+		Box::pin(std::future::ready(self.get()))
+	}
+}
+
+/// A custom voting rule that never votes more than N blocks past a reference
+/// block tracked by another subsystem, e.g. to keep finality from getting too far
+/// ahead of parachain approval checking. Votes are never restricted below the base.
+pub struct AtMostAheadOf<Block: BlockT> {
+	source: Cow<'static, str>,
+	reference: Arc<dyn ReferenceBlock<Block>>,
+	blocks: NumberFor<Block>,
+}
+
+impl<Block: BlockT> AtMostAheadOf<Block> {
+	/// Create a new rule voting at most `blocks` past the `reference` block, which is
+	/// reported as `source` when the rule restricts a vote.
+	pub fn new(
+		source: impl Into<Cow<'static, str>>,
+		reference: Arc<dyn ReferenceBlock<Block>>,
+		blocks: NumberFor<Block>,
+	) -> Self {
+		AtMostAheadOf { source: source.into(), reference, blocks }
+	}
+}
+
+impl<Block: BlockT> Clone for AtMostAheadOf<Block> {
+	fn clone(&self) -> Self {
+		AtMostAheadOf {
+			source: self.source.clone(),
+			reference: self.reference.clone(),
+			blocks: self.blocks,
+		}
+	}
+}
+
+impl<Block, B> VotingRule<Block, B> for AtMostAheadOf<Block>
+where
+	Block: BlockT,
+	B: HeaderBackend<Block> + 'static,
+{
+	fn restrict_vote(
+		&self,
+		backend: Arc<B>,
+		base: &Block::Header,
+		_best_target: &Block::Header,
+		current_target: &Block::Header,
+	) -> VotingRuleResult<Block> {
+		let reference = self.reference.reference_block(base, current_target);
+		let blocks = self.blocks;
+		let base_number = *base.number();
+		let current_target = current_target.clone();
+
+		Box::pin(async move {
+			let target_number = std::cmp::max(reference.await?.saturating_add(blocks), base_number);
+
+			// our current target is already lower than this rule would restrict
+			if target_number >= *current_target.number() {
+				return None
+			}
+
+			find_target(&*backend, target_number, &current_target)
+		})
+	}
+
+	fn name(&self) -> Cow<'static, str> {
+		format!("at_most_ahead_of({})", self.source).into()
+	}
+}
+
+/// A custom voting rule that pauses votes at session boundaries: a vote never goes
+/// past a block starting a new session until the best block is at least N blocks
+/// past it.
+pub struct PauseAtSessionBoundary<Block: BlockT> {
+	is_boundary: Arc<dyn Fn(&Block::Header) -> bool + Send + Sync>,
+	blocks: NumberFor<Block>,
+}
+
+impl<Block: BlockT> PauseAtSessionBoundary<Block> {
+	/// Create a new rule pausing for `blocks` after the blocks for which `is_boundary`
+	/// returns `true`.
+	pub fn new(
+		is_boundary: Arc<dyn Fn(&Block::Header) -> bool + Send + Sync>,
+		blocks: NumberFor<Block>,
+	) -> Self {
+		PauseAtSessionBoundary { is_boundary, blocks }
+	}
+}
+
+impl<Block: BlockT> Clone for PauseAtSessionBoundary<Block> {
+	fn clone(&self) -> Self {
+		PauseAtSessionBoundary { is_boundary: self.is_boundary.clone(), blocks: self.blocks }
+	}
+}
+
+impl<Block, B> VotingRule<Block, B> for PauseAtSessionBoundary<Block>
+where
+	Block: BlockT,
+	B: HeaderBackend<Block>,
+{
+	fn restrict_vote(
+		&self,
+		backend: Arc<B>,
+		base: &Block::Header,
+		best_target: &Block::Header,
+		current_target: &Block::Header,
+	) -> VotingRuleResult<Block> {
+		// walk backwards to the base, keeping the lowest boundary we are still paused at
+		let mut restricted = None;
+		let mut header = current_target.clone();
+
+		while header.number() > base.number() {
+			if header.number().saturating_add(self.blocks) > *best_target.number() &&
+				(self.is_boundary)(&header)
+			{
+				restricted = Some((header.hash(), *header.number()));
+			}
+
+			header = match backend.header(*header.parent_hash()) {
+				Ok(Some(header)) => header,
+				_ => return Box::pin(async { None }),
+			};
+		}
+
+		Box::pin(std::future::ready(restricted))
+	}
+
+	fn name(&self) -> Cow<'static, str> {
+		"pause_at_session_boundary".into()
+	}
+}
+
+/// Description of a voting rule, as loaded from the node configuration.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "rule", rename_all = "snake_case")]
+pub enum VotingRuleConfig {
+	/// See [`BeforeBestBlockBy`].
+	BeforeBestBlockBy {
+		/// Number of blocks to stay behind the best block.
+		blocks: u32,
+	},
+	/// See [`ThreeQuartersOfTheUnfinalizedChain`].
+	ThreeQuartersOfTheUnfinalizedChain,
+	/// See [`AtMostAheadOf`].
+	AtMostAheadOf {
+		/// Name of the reference block, as registered in the [`VotingRuleContext`].
+		source: String,
+		/// Maximum number of blocks to vote past the reference block.
+		blocks: u32,
+	},
+	/// See [`PauseAtSessionBoundary`].
+	PauseAtSessionBoundary {
+		/// Number of blocks to pause for after a session boundary.
+		blocks: u32,
+	},
+}
+
+impl VotingRuleConfig {
+	/// Load a list of voting rules from the JSON file at `path`.
+	pub fn load(path: &Path) -> Result<Vec<Self>, VotingRuleConfigError> {
+		Ok(serde_json::from_slice(&fs::read(path)?)?)
+	}
+}
+
+/// Errors building voting rules from a [`VotingRuleConfig`].
+#[derive(Debug, thiserror::Error)]
+pub enum VotingRuleConfigError {
+	/// The configuration couldn't be read.
+	#[error("failed to read the voting rules: {0}")]
+	Io(#[from] io::Error),
+	/// The configuration is invalid.
+	#[error("invalid voting rules: {0}")]
+	Json(#[from] serde_json::Error),
+	/// A rule refers to a reference block the node doesn't track.
+	#[error("unknown reference block `{0}`")]
+	UnknownReferenceBlock(String),
+	/// A rule doesn't allow voting far enough past a reference block for it to progress.
+	#[error("voting at most {blocks} blocks ahead of `{reference}` stalls finality, need {min}")]
+	TooFewBlocksAhead {
+		/// Name of the reference block.
+		reference: String,
+		/// Number of blocks allowed by the rule.
+		blocks: u32,
+		/// Minimum number of blocks needed by the reference block.
+		min: u32,
+	},
+	/// The node can't tell session boundaries apart.
+	#[error("session boundaries are not known by this node")]
+	UnknownSessionBoundaries,
+}
+
+/// What the node provides to the voting rules built from a [`VotingRuleConfig`].
+pub struct VotingRuleContext<Block: BlockT> {
+	reference_blocks: HashMap<String, Arc<dyn ReferenceBlock<Block>>>,
+	min_blocks_ahead: HashMap<String, u32>,
+	is_session_boundary: Option<Arc<dyn Fn(&Block::Header) -> bool + Send + Sync>>,
+}
+
+impl<Block: BlockT> Default for VotingRuleContext<Block> {
+	fn default() -> Self {
+		VotingRuleContext {
+			reference_blocks: HashMap::new(),
+			min_blocks_ahead: HashMap::new(),
+			is_session_boundary: None,
+		}
+	}
+}
+
+impl<Block: BlockT> VotingRuleContext<Block> {
+	/// Register a reference block under `name`.
+	pub fn with_reference_block(
+		mut self,
+		name: impl Into<String>,
+		reference: Arc<dyn ReferenceBlock<Block>>,
+	) -> Self {
+		self.reference_blocks.insert(name.into(), reference);
+		self
+	}
+
+	/// Require the rules voting at most N blocks ahead of the reference block registered
+	/// under `name` to allow at least `blocks`.
+	///
+	/// This is needed for reference blocks which only progress once enough blocks past them
+	/// are finalized, e.g. BEEFY only votes every `min_block_delta` blocks: with fewer blocks
+	/// allowed, neither GRANDPA nor BEEFY would make progress anymore.
+	pub fn with_min_blocks_ahead(mut self, name: impl Into<String>, blocks: u32) -> Self {
+		self.min_blocks_ahead.insert(name.into(), blocks);
+		self
+	}
+
+	/// Register the function telling whether a block starts a new session.
+	pub fn with_session_boundaries(
+		mut self,
+		is_session_boundary: impl Fn(&Block::Header) -> bool + Send + Sync + 'static,
+	) -> Self {
+		self.is_session_boundary = Some(Arc::new(is_session_boundary));
+		self
+	}
 }
 
 // walk backwards until we find the target block
@@ -202,11 +523,12 @@ where
 
 struct VotingRules<Block, B> {
 	rules: Arc<Vec<Box<dyn VotingRule<Block, B>>>>,
+	telemetry: Option<TelemetryHandle>,
 }
 
 impl<B, Block> Clone for VotingRules<B, Block> {
 	fn clone(&self) -> Self {
-		VotingRules { rules: self.rules.clone() }
+		VotingRules { rules: self.rules.clone(), telemetry: self.telemetry.clone() }
 	}
 }
 
@@ -223,6 +545,7 @@ where
 		current_target: &Block::Header,
 	) -> VotingRuleResult<Block> {
 		let rules = self.rules.clone();
+		let telemetry = self.telemetry.clone();
 		let base = base.clone();
 		let best_target = best_target.clone();
 		let current_target = current_target.clone();
@@ -242,6 +565,23 @@ where
 					.and_then(|(hash, _)| backend.header(hash).ok())
 					.and_then(std::convert::identity)
 				{
+					debug!(
+						target: LOG_TARGET,
+						"Vote target restricted from #{} to #{} by voting rule {}",
+						restricted_target.number(),
+						header.number(),
+						rule.name(),
+					);
+					telemetry!(
+						telemetry;
+						CONSENSUS_DEBUG;
+						"afg.vote_target_restricted";
+						"rule" => rule.name(),
+						"from_number" => ?restricted_target.number(),
+						"to_number" => ?header.number(),
+						"to_hash" => ?header.hash(),
+					);
+
 					restricted_target = header;
 				}
 			}
@@ -261,6 +601,7 @@ where
 /// progressively restrict the vote.
 pub struct VotingRulesBuilder<Block, B> {
 	rules: Vec<Box<dyn VotingRule<Block, B>>>,
+	telemetry: Option<TelemetryHandle>,
 }
 
 impl<Block, B> Default for VotingRulesBuilder<Block, B>
@@ -282,7 +623,7 @@ where
 {
 	/// Return a new voting rule builder using the given backend.
 	pub fn new() -> Self {
-		VotingRulesBuilder { rules: Vec::new() }
+		VotingRulesBuilder { rules: Vec::new(), telemetry: None }
 	}
 
 	/// Add a new voting rule to the builder.
@@ -303,10 +644,61 @@ where
 		self
 	}
 
+	/// Add the voting rules described by the given configuration, using `context`
+	/// for the rules which depend on other subsystems.
+	pub fn add_from_config(
+		mut self,
+		config: &[VotingRuleConfig],
+		context: &VotingRuleContext<Block>,
+	) -> Result<Self, VotingRuleConfigError> {
+		for rule in config {
+			self = match rule {
+				VotingRuleConfig::BeforeBestBlockBy { blocks } =>
+					self.add(BeforeBestBlockBy::<NumberFor<Block>>((*blocks).into())),
+				VotingRuleConfig::ThreeQuartersOfTheUnfinalizedChain =>
+					self.add(ThreeQuartersOfTheUnfinalizedChain),
+				VotingRuleConfig::AtMostAheadOf { source, blocks } => {
+					let reference = context.reference_blocks.get(source).ok_or_else(|| {
+						VotingRuleConfigError::UnknownReferenceBlock(source.clone())
+					})?;
+					if let Some(&min) = context.min_blocks_ahead.get(source) {
+						if *blocks < min {
+							return Err(VotingRuleConfigError::TooFewBlocksAhead {
+								reference: source.clone(),
+								blocks: *blocks,
+								min,
+							})
+						}
+					}
+					self.add(AtMostAheadOf::new(
+						source.clone(),
+						reference.clone(),
+						(*blocks).into(),
+					))
+				},
+				VotingRuleConfig::PauseAtSessionBoundary { blocks } => {
+					let is_boundary = context
+						.is_session_boundary
+						.clone()
+						.ok_or(VotingRuleConfigError::UnknownSessionBoundaries)?;
+					self.add(PauseAtSessionBoundary::new(is_boundary, (*blocks).into()))
+				},
+			};
+		}
+
+		Ok(self)
+	}
+
+	/// Report the votes restricted by the voting rules to the given telemetry.
+	pub fn telemetry(mut self, telemetry: Option<TelemetryHandle>) -> Self {
+		self.telemetry = telemetry;
+		self
+	}
+
 	/// Return a new `VotingRule` that applies all of the previously added
 	/// voting rules in-order.
 	pub fn build(self) -> impl VotingRule<Block, B> + Clone {
-		VotingRules { rules: Arc::new(self.rules) }
+		VotingRules { rules: Arc::new(self.rules), telemetry: self.telemetry }
 	}
 }
 
@@ -325,6 +717,10 @@ where
 	) -> VotingRuleResult<Block> {
 		(**self).restrict_vote(backend, base, best_target, current_target)
 	}
+
+	fn name(&self) -> Cow<'static, str> {
+		(**self).name()
+	}
 }
 
 #[cfg(test)]
@@ -451,4 +847,158 @@ mod tests {
 			assert_eq!(number, expected, "best = {}, lag = 2, base = {}", best_number, i);
 		}
 	}
+
+	fn import_blocks(client: &Client<Backend>, n: usize) -> Vec<<Block as BlockT>::Hash> {
+		let mut hashes = Vec::with_capacity(n);
+		for _ in 0..n {
+			let block = BlockBuilderBuilder::new(client)
+				.on_parent_block(client.chain_info().best_hash)
+				.with_parent_block_number(client.chain_info().best_number)
+				.build()
+				.unwrap()
+				.build()
+				.unwrap()
+				.block;
+			hashes.push(block.hash());
+
+			futures::executor::block_on(client.import(BlockOrigin::Own, block)).unwrap();
+		}
+		hashes
+	}
+
+	#[test]
+	fn at_most_ahead_of_restricts_to_reference_block() {
+		let client = Arc::new(TestClientBuilder::new().build());
+		let hashes = import_blocks(&client, 20);
+
+		let reference = SharedReferenceBlock::<u64>::new();
+		let rule = AtMostAheadOf::<Block>::new("test", Arc::new(reference.clone()), 3);
+
+		let genesis = client.header(client.info().genesis_hash).unwrap().unwrap();
+		let best = client.header(client.info().best_hash).unwrap().unwrap();
+		let restrict = |base: &Header| {
+			futures::executor::block_on(rule.restrict_vote(client.clone(), base, &best, &best))
+		};
+
+		// the reference block isn't known yet, so votes aren't restricted
+		assert_eq!(restrict(&genesis), None);
+
+		reference.note(10);
+		assert_eq!(restrict(&genesis), Some((hashes[12], 13)));
+
+		// votes are never restricted below the base
+		let block15 = client.header(hashes[14]).unwrap().unwrap();
+		assert_eq!(restrict(&block15), Some((hashes[14], 15)));
+
+		reference.note(18);
+		assert_eq!(restrict(&genesis), None);
+	}
+
+	#[test]
+	fn pause_at_session_boundary_waits_for_enough_blocks() {
+		let client = Arc::new(TestClientBuilder::new().build());
+		let hashes = import_blocks(&client, 20);
+
+		// blocks #5 and #15 start a new session
+		let rule = PauseAtSessionBoundary::<Block>::new(
+			Arc::new(|header: &Header| *header.number() % 10 == 5),
+			8,
+		);
+
+		let genesis = client.header(client.info().genesis_hash).unwrap().unwrap();
+		let block10 = client.header(hashes[9]).unwrap().unwrap();
+		let best = client.header(client.info().best_hash).unwrap().unwrap();
+		let restrict = |best: &Header| {
+			futures::executor::block_on(rule.restrict_vote(client.clone(), &genesis, best, best))
+		};
+
+		// only 5 blocks were built on top of #5
+		assert_eq!(restrict(&block10), Some((hashes[4], 5)));
+
+		// #5 is old enough but only 5 blocks were built on top of #15
+		assert_eq!(restrict(&best), Some((hashes[14], 15)));
+	}
+
+	#[test]
+	fn voting_rules_from_config() {
+		let config: Vec<VotingRuleConfig> = serde_json::from_str(
+			r#"[
+				{ "rule": "before_best_block_by", "blocks": 2 },
+				{ "rule": "at_most_ahead_of", "source": "beefy", "blocks": 4 }
+			]"#,
+		)
+		.unwrap();
+
+		let client = Arc::new(TestClientBuilder::new().build());
+		let hashes = import_blocks(&client, 20);
+
+		let context = VotingRuleContext::<Block>::default();
+		assert!(matches!(
+			VotingRulesBuilder::<Block, Client<Backend>>::new().add_from_config(&config, &context),
+			Err(VotingRuleConfigError::UnknownReferenceBlock(source)) if source == "beefy"
+		));
+
+		let beefy = SharedReferenceBlock::<u64>::new();
+		beefy.note(10);
+		let context = context.with_reference_block("beefy", Arc::new(beefy));
+		let rule = VotingRulesBuilder::new().add_from_config(&config, &context).unwrap().build();
+
+		let genesis = client.header(client.info().genesis_hash).unwrap().unwrap();
+		let best = client.header(client.info().best_hash).unwrap().unwrap();
+		let (hash, number) =
+			futures::executor::block_on(rule.restrict_vote(client.clone(), &genesis, &best, &best))
+				.unwrap();
+
+		assert_eq!((hash, number), (hashes[13], 14));
+	}
+
+	#[test]
+	fn finality_progresses_with_beefy_reference_block() {
+		const MIN_BLOCK_DELTA: u32 = 8;
+
+		let client = Arc::new(TestClientBuilder::new().build());
+		let hashes = import_blocks(&client, 40);
+		let best = client.header(client.info().best_hash).unwrap().unwrap();
+
+		let beefy = SharedReferenceBlock::<u64>::new();
+		let context = VotingRuleContext::<Block>::default()
+			.with_reference_block("beefy", Arc::new(beefy.clone()))
+			.with_min_blocks_ahead("beefy", MIN_BLOCK_DELTA);
+		let config =
+			|blocks| vec![VotingRuleConfig::AtMostAheadOf { source: "beefy".into(), blocks }];
+
+		// BEEFY only votes on blocks at least `MIN_BLOCK_DELTA` past its best block, so
+		// GRANDPA would never finalize them with fewer blocks allowed
+		assert!(matches!(
+			VotingRulesBuilder::<Block, Client<Backend>>::new()
+				.add_from_config(&config(MIN_BLOCK_DELTA - 1), &context),
+			Err(VotingRuleConfigError::TooFewBlocksAhead { blocks: 7, min: 8, .. })
+		));
+
+		let rule = VotingRulesBuilder::new()
+			.add_from_config(&config(MIN_BLOCK_DELTA), &context)
+			.unwrap()
+			.build();
+
+		// alternate GRANDPA and BEEFY rounds until the best block is finalized
+		beefy.note(0);
+		let mut finalized = client.header(client.info().genesis_hash).unwrap().unwrap();
+		for _ in 0..10 {
+			let target = futures::executor::block_on(rule.restrict_vote(
+				client.clone(),
+				&finalized,
+				&best,
+				&best,
+			))
+			.unwrap_or((best.hash(), *best.number()));
+			finalized = client.header(target.0).unwrap().unwrap();
+
+			let best_beefy = beefy.get().unwrap();
+			if *finalized.number() >= best_beefy + MIN_BLOCK_DELTA as u64 {
+				beefy.note(best_beefy + MIN_BLOCK_DELTA as u64);
+			}
+		}
+
+		assert_eq!(finalized.hash(), hashes[39]);
+	}
 }
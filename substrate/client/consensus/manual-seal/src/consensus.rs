@@ -44,4 +44,16 @@ pub trait ConsensusDataProvider<B: BlockT>: Send + Sync {
 		inherents: &InherentData,
 		proof: Self::Proof,
 	) -> Result<(), Error>;
+
+	/// Update any slot related inherent data after the timestamp inherent was overridden with
+	/// `timestamp` (see [`SealOverrides`](crate::SealOverrides)).
+	///
+	/// Does nothing by default.
+	fn override_timestamp(
+		&self,
+		_inherents: &mut InherentData,
+		_timestamp: sp_timestamp::Timestamp,
+	) -> Result<(), Error> {
+		Ok(())
+	}
 }
@@ -315,4 +315,15 @@ where
 
 		Ok(())
 	}
+
+	fn override_timestamp(
+		&self,
+		inherents: &mut InherentData,
+		timestamp: sp_timestamp::Timestamp,
+	) -> Result<(), Error> {
+		// the slot is derived from the timestamp, jumping ahead in time also moves the epoch.
+		let slot = Slot::from_timestamp(timestamp, self.config.slot_duration());
+		inherents.babe_replace_inherent_data(slot);
+		Ok(())
+	}
 }
@@ -39,6 +39,7 @@ use std::{marker::PhantomData, sync::Arc, time::Duration};
 
 mod error;
 mod finalize_block;
+mod revert_block;
 mod seal_block;
 
 pub mod consensus;
@@ -48,11 +49,12 @@ pub use self::{
 	consensus::ConsensusDataProvider,
 	error::Error,
 	finalize_block::{finalize_block, FinalizeBlockParams},
+	revert_block::{revert_block, RevertBackend, RevertBlockParams},
 	rpc::{CreatedBlock, EngineCommand},
-	seal_block::{seal_block, SealBlockParams, MAX_PROPOSAL_DURATION},
+	seal_block::{seal_block, SealBlockParams, SealOverrides, MAX_PROPOSAL_DURATION},
 };
 use sc_transaction_pool_api::TransactionPool;
-use sp_api::ProvideRuntimeApi;
+use sp_api::{CallApiAt, ProvideRuntimeApi};
 
 const LOG_TARGET: &str = "manual-seal";
 
@@ -112,6 +114,10 @@ pub struct ManualSealParams<B: BlockT, BI, E, C: ProvideRuntimeApi<B>, TP, SC, C
 
 	/// Something that can create the inherent data providers.
 	pub create_inherent_data_providers: CIDP,
+
+	/// Backend used to revert blocks on [`EngineCommand::RevertTo`], usually the client
+	/// backend. Reverting is not supported if `None`.
+	pub revert_backend: Option<Arc<dyn RevertBackend<B>>>,
 }
 
 /// Params required to start the instant sealing authorship task.
@@ -150,6 +156,25 @@ pub struct DelayedFinalizeParams<C, S> {
 	pub delay_sec: u64,
 }
 
+/// Params for the [`hybrid_seal_commands_stream`] command source.
+pub struct HybridSealParams<TP> {
+	/// Shared reference to the transaction pool.
+	pub pool: Arc<TP>,
+
+	/// Number of ready transactions in the pool that triggers sealing a block.
+	///
+	/// This should also be the maximum number of transactions per block, set on the proposer
+	/// with `ProposerFactory::set_max_transactions`, so that a block holds at most one batch.
+	pub batch_size: usize,
+
+	/// Maximum time to wait for a batch before sealing whatever is in the pool,
+	/// an empty block if there is nothing.
+	pub idle_interval: Duration,
+
+	/// Instantly finalize the sealed blocks?
+	pub finalize: bool,
+}
+
 /// Creates the background authorship task for the manually seal engine.
 pub async fn run_manual_seal<B, BI, CB, E, C, TP, SC, CS, CIDP, P>(
 	ManualSealParams {
@@ -161,11 +186,12 @@ pub async fn run_manual_seal<B, BI, CB, E, C, TP, SC, CS, CIDP, P>(
 		select_chain,
 		consensus_data_provider,
 		create_inherent_data_providers,
+		revert_backend,
 	}: ManualSealParams<B, BI, E, C, TP, SC, CS, CIDP, P>,
 ) where
 	B: BlockT + 'static,
 	BI: BlockImport<B, Error = sp_consensus::Error> + Send + Sync + 'static,
-	C: HeaderBackend<B> + Finalizer<B, CB> + ProvideRuntimeApi<B> + 'static,
+	C: HeaderBackend<B> + Finalizer<B, CB> + ProvideRuntimeApi<B> + CallApiAt<B> + 'static,
 	CB: ClientBackend<B> + 'static,
 	E: Environment<B> + 'static,
 	E::Proposer: Proposer<B, Proof = P>,
@@ -175,6 +201,8 @@ pub async fn run_manual_seal<B, BI, CB, E, C, TP, SC, CS, CIDP, P>(
 	CIDP: CreateInherentDataProviders<B, ()>,
 	P: codec::Encode + Send + Sync + 'static,
 {
+	let mut overrides = SealOverrides::default();
+
 	while let Some(command) = commands_stream.next().await {
 		match command {
 			EngineCommand::SealNewBlock { create_empty, finalize, parent_hash, sender } => {
@@ -190,6 +218,7 @@ pub async fn run_manual_seal<B, BI, CB, E, C, TP, SC, CS, CIDP, P>(
 					pool: pool.clone(),
 					client: client.clone(),
 					create_inherent_data_providers: &create_inherent_data_providers,
+					overrides: &mut overrides,
 				})
 				.await;
 			},
@@ -204,6 +233,22 @@ pub async fn run_manual_seal<B, BI, CB, E, C, TP, SC, CS, CIDP, P>(
 				})
 				.await
 			},
+			EngineCommand::SetNextTimestamp { timestamp, mut sender } => {
+				overrides.timestamp = Some(timestamp.into());
+				rpc::send_result(&mut sender, Ok(()))
+			},
+			EngineCommand::SetStorage { changes, mut sender } => {
+				overrides.storage.extend(changes);
+				rpc::send_result(&mut sender, Ok(()))
+			},
+			EngineCommand::RevertTo { hash, sender } =>
+				revert_block(RevertBlockParams {
+					hash,
+					sender,
+					client: client.clone(),
+					revert_backend: revert_backend.as_deref(),
+				})
+				.await,
 		}
 	}
 }
@@ -224,7 +269,7 @@ pub async fn run_instant_seal<B, BI, CB, E, C, TP, SC, CIDP, P>(
 ) where
 	B: BlockT + 'static,
 	BI: BlockImport<B, Error = sp_consensus::Error> + Send + Sync + 'static,
-	C: HeaderBackend<B> + Finalizer<B, CB> + ProvideRuntimeApi<B> + 'static,
+	C: HeaderBackend<B> + Finalizer<B, CB> + ProvideRuntimeApi<B> + CallApiAt<B> + 'static,
 	CB: ClientBackend<B> + 'static,
 	E: Environment<B> + 'static,
 	E::Proposer: Proposer<B, Proof = P>,
@@ -251,6 +296,7 @@ pub async fn run_instant_seal<B, BI, CB, E, C, TP, SC, CIDP, P>(
 		select_chain,
 		consensus_data_provider,
 		create_inherent_data_providers,
+		revert_backend: None,
 	})
 	.await
 }
@@ -274,7 +320,7 @@ pub async fn run_instant_seal_and_finalize<B, BI, CB, E, C, TP, SC, CIDP, P>(
 ) where
 	B: BlockT + 'static,
 	BI: BlockImport<B, Error = sp_consensus::Error> + Send + Sync + 'static,
-	C: HeaderBackend<B> + Finalizer<B, CB> + ProvideRuntimeApi<B> + 'static,
+	C: HeaderBackend<B> + Finalizer<B, CB> + ProvideRuntimeApi<B> + CallApiAt<B> + 'static,
 	CB: ClientBackend<B> + 'static,
 	E: Environment<B> + 'static,
 	E::Proposer: Proposer<B, Proof = P>,
@@ -301,10 +347,57 @@ pub async fn run_instant_seal_and_finalize<B, BI, CB, E, C, TP, SC, CIDP, P>(
 		select_chain,
 		consensus_data_provider,
 		create_inherent_data_providers,
+		revert_backend: None,
 	})
 	.await
 }
 
+/// Creates a stream of [`EngineCommand`]s to be passed as
+/// [`ManualSealParams::commands_stream`], combining instant and interval sealing.
+///
+/// A block is sealed as soon as the pool holds `batch_size` ready transactions, or once
+/// `idle_interval` has passed since the last sealed block, in which case an empty block is
+/// created if the pool has no transactions. Combined with `finalize` this gives a dev chain
+/// that reacts instantly to transactions, keeps producing blocks while idle and does not
+/// need any finality gadget.
+///
+/// The stream can be merged with the one fed by the manual seal RPC to seal blocks on demand.
+pub fn hybrid_seal_commands_stream<TP>(
+	HybridSealParams { pool, batch_size, idle_interval, finalize }: HybridSealParams<TP>,
+) -> impl Stream<Item = EngineCommand<<TP::Block as BlockT>::Hash>> + Unpin
+where
+	TP: TransactionPool + 'static,
+{
+	let batch_size = batch_size.max(1);
+	let imports = pool.import_notification_stream();
+	let idle = Delay::new(idle_interval);
+
+	Box::pin(stream::unfold(
+		(pool, imports, idle),
+		move |(pool, mut imports, mut idle)| async move {
+			let create_empty = loop {
+				match future::select(imports.next(), &mut idle).await {
+					future::Either::Left((Some(_), _)) =>
+						if pool.status().ready >= batch_size {
+							break false
+						},
+					future::Either::Left((None, _)) => return None,
+					future::Either::Right(_) => break true,
+				}
+			};
+			idle.reset(idle_interval);
+
+			let command = EngineCommand::SealNewBlock {
+				create_empty,
+				finalize,
+				parent_hash: None,
+				sender: None,
+			};
+			Some((command, (pool, imports, idle)))
+		},
+	))
+}
+
 /// Creates a future for delayed finalization of manual sealed blocks.
 ///
 /// The future needs to be spawned in the background alongside the
@@ -347,9 +440,11 @@ pub async fn run_delayed_finalize<B, CB, C, S>(
 mod tests {
 	use super::*;
 	use sc_basic_authorship::ProposerFactory;
+	use sc_client_api::{BlockBackend, StorageProvider};
 	use sc_consensus::ImportedAux;
 	use sc_transaction_pool::{BasicPool, FullChainApi, Options, RevalidationType};
 	use sc_transaction_pool_api::{MaintainedTransactionPool, TransactionPool, TransactionSource};
+	use sp_core::storage::{StorageData, StorageKey};
 	use sp_inherents::InherentData;
 	use sp_runtime::generic::{Digest, DigestItem};
 	use substrate_test_runtime_client::{
@@ -440,6 +535,7 @@ mod tests {
 			select_chain,
 			create_inherent_data_providers: |_, _| async { Ok(()) },
 			consensus_data_provider: None,
+			revert_backend: None,
 		}));
 
 		// submit a transaction to pool.
@@ -518,6 +614,7 @@ mod tests {
 			select_chain,
 			create_inherent_data_providers: |_, _| async { Ok(()) },
 			consensus_data_provider: None,
+			revert_backend: None,
 		}));
 
 		let delay_sec = 5;
@@ -594,6 +691,7 @@ mod tests {
 			select_chain,
 			consensus_data_provider: None,
 			create_inherent_data_providers: |_, _| async { Ok(()) },
+			revert_backend: None,
 		}));
 
 		// submit a transaction to pool.
@@ -678,6 +776,7 @@ mod tests {
 			select_chain,
 			consensus_data_provider: None,
 			create_inherent_data_providers: |_, _| async { Ok(()) },
+			revert_backend: None,
 		}));
 
 		// submit a transaction to pool.
@@ -783,6 +882,7 @@ mod tests {
 			// use a provider that pushes some post digest data
 			consensus_data_provider: Some(Box::new(TestDigestProvider { _client: client.clone() })),
 			create_inherent_data_providers: |_, _| async { Ok(()) },
+			revert_backend: None,
 		}));
 
 		let (tx, rx) = futures::channel::oneshot::channel();
@@ -800,4 +900,316 @@ mod tests {
 		let header = client.header(created_block.hash).unwrap().unwrap();
 		assert_eq!(header.number, 1);
 	}
+
+	#[tokio::test]
+	async fn manual_seal_set_storage_and_revert() {
+		let builder = TestClientBuilder::new();
+		let backend = builder.backend();
+		let (client, select_chain) = builder.build_with_longest_chain();
+		let client = Arc::new(client);
+		let spawner = sp_core::testing::TaskExecutor::new();
+		let genesis_hash = client.info().genesis_hash;
+		let pool = Arc::new(BasicPool::with_revalidation_type(
+			Options::default(),
+			true.into(),
+			api(),
+			None,
+			RevalidationType::Full,
+			spawner.clone(),
+			0,
+			genesis_hash,
+			genesis_hash,
+		));
+		let env = ProposerFactory::new(spawner.clone(), client.clone(), pool.clone(), None, None);
+
+		let (mut sink, commands_stream) = futures::channel::mpsc::channel(1024);
+
+		// spawn the background authorship task
+		tokio::spawn(run_manual_seal(ManualSealParams {
+			block_import: client.clone(),
+			env,
+			client: client.clone(),
+			pool: pool.clone(),
+			commands_stream,
+			select_chain,
+			consensus_data_provider: None,
+			create_inherent_data_providers: |_, _| async { Ok(()) },
+			revert_backend: Some(backend),
+		}));
+
+		let key = b"manual_seal_key".to_vec();
+		let (tx, rx) = futures::channel::oneshot::channel();
+		sink.send(EngineCommand::SetStorage {
+			changes: vec![(key.clone(), Some(b"value".to_vec()))],
+			sender: Some(tx),
+		})
+		.await
+		.unwrap();
+		rx.await.unwrap().unwrap();
+
+		let seal_new_block = || {
+			let mut sink = sink.clone();
+			async move {
+				let (tx, rx) = futures::channel::oneshot::channel();
+				sink.send(EngineCommand::SealNewBlock {
+					parent_hash: None,
+					sender: Some(tx),
+					create_empty: true,
+					finalize: false,
+				})
+				.await
+				.unwrap();
+				rx.await.unwrap().unwrap()
+			}
+		};
+
+		// the override is applied to the state of the next block only.
+		let first = seal_new_block().await;
+		let value = client.storage(first.hash, &StorageKey(key.clone())).unwrap();
+		assert_eq!(value, Some(StorageData(b"value".to_vec())));
+		assert_eq!(client.storage(genesis_hash, &StorageKey(key.clone())).unwrap(), None);
+
+		let second = seal_new_block().await;
+		assert_eq!(client.info().best_hash, second.hash);
+		assert_eq!(client.info().best_number, 2);
+
+		let (tx, rx) = futures::channel::oneshot::channel();
+		sink.send(EngineCommand::RevertTo { hash: first.hash, sender: Some(tx) })
+			.await
+			.unwrap();
+		rx.await.unwrap().unwrap();
+
+		assert_eq!(client.info().best_hash, first.hash);
+		assert_eq!(client.info().best_number, 1);
+	}
+
+	/// Records the timestamp of the inherent data of every sealed block.
+	struct TimestampRecorder(Arc<std::sync::Mutex<Vec<u64>>>);
+	impl<B: BlockT> ConsensusDataProvider<B> for TimestampRecorder {
+		type Proof = ();
+
+		fn create_digest(
+			&self,
+			_parent: &B::Header,
+			inherents: &InherentData,
+		) -> Result<Digest, Error> {
+			let timestamp = inherents
+				.get_data::<sp_timestamp::Timestamp>(&sp_timestamp::INHERENT_IDENTIFIER)?
+				.expect("timestamp inherent is provided; qed");
+			self.0.lock().unwrap().push(timestamp.as_millis());
+			Ok(Digest { logs: vec![] })
+		}
+
+		fn append_block_import(
+			&self,
+			_parent: &B::Header,
+			_params: &mut BlockImportParams<B>,
+			_inherents: &InherentData,
+			_proof: Self::Proof,
+		) -> Result<(), Error> {
+			Ok(())
+		}
+	}
+
+	#[tokio::test]
+	async fn manual_seal_next_timestamp_moves_following_blocks() {
+		let builder = TestClientBuilder::new();
+		let (client, select_chain) = builder.build_with_longest_chain();
+		let client = Arc::new(client);
+		let spawner = sp_core::testing::TaskExecutor::new();
+		let genesis_hash = client.info().genesis_hash;
+		let pool = Arc::new(BasicPool::with_revalidation_type(
+			Options::default(),
+			true.into(),
+			api(),
+			None,
+			RevalidationType::Full,
+			spawner.clone(),
+			0,
+			genesis_hash,
+			genesis_hash,
+		));
+		let env = ProposerFactory::new(spawner.clone(), client.clone(), pool.clone(), None, None);
+		let timestamps = Arc::new(std::sync::Mutex::new(Vec::new()));
+		// the provided time advances by a second per block.
+		let clock = Arc::new(std::sync::atomic::AtomicU64::new(0));
+
+		let (mut sink, commands_stream) = futures::channel::mpsc::channel(1024);
+		tokio::spawn(run_manual_seal(ManualSealParams {
+			block_import: client.clone(),
+			env,
+			client: client.clone(),
+			pool: pool.clone(),
+			commands_stream,
+			select_chain,
+			consensus_data_provider: Some(Box::new(TimestampRecorder(timestamps.clone()))),
+			create_inherent_data_providers: move |_, _| {
+				let now = clock.fetch_add(1_000, std::sync::atomic::Ordering::SeqCst) + 1_000;
+				async move {
+					Ok(sp_timestamp::InherentDataProvider::new(sp_timestamp::Timestamp::new(now)))
+				}
+			},
+			revert_backend: None,
+		}));
+
+		let mut control = sink.clone();
+		let seal_new_block = || {
+			let mut sink = sink.clone();
+			async move {
+				let (tx, rx) = futures::channel::oneshot::channel();
+				sink.send(EngineCommand::SealNewBlock {
+					parent_hash: None,
+					sender: Some(tx),
+					create_empty: true,
+					finalize: false,
+				})
+				.await
+				.unwrap();
+				rx.await.unwrap().unwrap()
+			}
+		};
+
+		seal_new_block().await;
+		let (tx, rx) = futures::channel::oneshot::channel();
+		control
+			.send(EngineCommand::SetNextTimestamp { timestamp: 1_000_000, sender: Some(tx) })
+			.await
+			.unwrap();
+		rx.await.unwrap().unwrap();
+		for _ in 0..3 {
+			seal_new_block().await;
+		}
+
+		// the blocks after the jump stay ahead of it instead of going back to the provided time.
+		assert_eq!(*timestamps.lock().unwrap(), vec![1_000, 1_000_000, 1_001_000, 1_002_000]);
+		assert_eq!(client.info().best_number, 4);
+	}
+
+	#[tokio::test]
+	async fn hybrid_seal_batches_and_idle_blocks() {
+		let builder = TestClientBuilder::new();
+		let (client, select_chain) = builder.build_with_longest_chain();
+		let client = Arc::new(client);
+		let spawner = sp_core::testing::TaskExecutor::new();
+		let genesis_hash = client.info().genesis_hash;
+		let pool_api = Arc::new(FullChainApi::new(client.clone(), None, &spawner.clone()));
+		let pool = Arc::new(BasicPool::with_revalidation_type(
+			Options::default(),
+			true.into(),
+			pool_api,
+			None,
+			RevalidationType::Full,
+			spawner.clone(),
+			0,
+			genesis_hash,
+			genesis_hash,
+		));
+		let env = ProposerFactory::new(spawner.clone(), client.clone(), pool.clone(), None, None);
+		let commands_stream = hybrid_seal_commands_stream(HybridSealParams {
+			pool: pool.clone(),
+			batch_size: 2,
+			idle_interval: Duration::from_secs(2),
+			finalize: true,
+		});
+		let mut import_stream = client.import_notification_stream();
+
+		// spawn the background authorship task
+		tokio::spawn(run_manual_seal(ManualSealParams {
+			block_import: client.clone(),
+			env,
+			client: client.clone(),
+			pool: pool.clone(),
+			commands_stream,
+			select_chain,
+			consensus_data_provider: None,
+			create_inherent_data_providers: |_, _| async { Ok(()) },
+			revert_backend: None,
+		}));
+
+		// a full batch is sealed right away.
+		assert!(pool.submit_one(genesis_hash, SOURCE, uxt(Alice, 0)).await.is_ok());
+		assert!(pool.submit_one(genesis_hash, SOURCE, uxt(Bob, 0)).await.is_ok());
+		let imported = import_stream.select_next_some().await;
+		assert_eq!(imported.header.number, 1);
+		let body = client.body(imported.hash).unwrap().unwrap();
+		assert_eq!(body.len(), 2);
+		assert_eq!(client.info().finalized_hash, imported.hash);
+		pool.maintain(sc_transaction_pool_api::ChainEvent::NewBestBlock {
+			hash: imported.hash,
+			tree_route: None,
+		})
+		.await;
+
+		// without transactions, an empty block is sealed after the idle interval.
+		let imported = import_stream.select_next_some().await;
+		assert_eq!(imported.header.number, 2);
+		assert!(client.body(imported.hash).unwrap().unwrap().is_empty());
+		assert_eq!(client.info().finalized_hash, imported.hash);
+	}
+
+	#[tokio::test]
+	async fn hybrid_seal_blocks_hold_at_most_batch_size_transactions() {
+		let builder = TestClientBuilder::new();
+		let (client, select_chain) = builder.build_with_longest_chain();
+		let client = Arc::new(client);
+		let spawner = sp_core::testing::TaskExecutor::new();
+		let genesis_hash = client.info().genesis_hash;
+		let pool_api = Arc::new(FullChainApi::new(client.clone(), None, &spawner.clone()));
+		let pool = Arc::new(BasicPool::with_revalidation_type(
+			Options::default(),
+			true.into(),
+			pool_api,
+			None,
+			RevalidationType::Full,
+			spawner.clone(),
+			0,
+			genesis_hash,
+			genesis_hash,
+		));
+		let mut env =
+			ProposerFactory::new(spawner.clone(), client.clone(), pool.clone(), None, None);
+		env.set_max_transactions(Some(2));
+		let commands_stream = hybrid_seal_commands_stream(HybridSealParams {
+			pool: pool.clone(),
+			batch_size: 2,
+			idle_interval: Duration::from_secs(2),
+			finalize: true,
+		});
+		let mut import_stream = client.import_notification_stream();
+
+		// submit more transactions than fit in a batch before the authorship task starts.
+		assert!(pool.submit_one(genesis_hash, SOURCE, uxt(Alice, 0)).await.is_ok());
+		assert!(pool.submit_one(genesis_hash, SOURCE, uxt(Bob, 0)).await.is_ok());
+		assert!(pool.submit_one(genesis_hash, SOURCE, uxt(Charlie, 0)).await.is_ok());
+		assert_eq!(pool.status().ready, 3);
+
+		// spawn the background authorship task
+		tokio::spawn(run_manual_seal(ManualSealParams {
+			block_import: client.clone(),
+			env,
+			client: client.clone(),
+			pool: pool.clone(),
+			commands_stream,
+			select_chain,
+			consensus_data_provider: None,
+			create_inherent_data_providers: |_, _| async { Ok(()) },
+			revert_backend: None,
+		}));
+
+		// the first block only takes a batch, even though more transactions are ready.
+		let imported = import_stream.select_next_some().await;
+		assert_eq!(imported.header.number, 1);
+		assert_eq!(client.body(imported.hash).unwrap().unwrap().len(), 2);
+		pool.maintain(sc_transaction_pool_api::ChainEvent::NewBestBlock {
+			hash: imported.hash,
+			tree_route: None,
+		})
+		.await;
+		assert_eq!(pool.status().ready, 1);
+
+		// the remaining transaction is sealed after the idle interval.
+		let imported = import_stream.select_next_some().await;
+		assert_eq!(imported.header.number, 2);
+		assert_eq!(client.body(imported.hash).unwrap().unwrap().len(), 1);
+	}
 }
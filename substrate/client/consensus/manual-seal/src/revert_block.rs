@@ -0,0 +1,96 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+//! Block revert utilities
+
+use crate::{rpc, Error};
+use sc_client_api::backend::Backend as ClientBackend;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::{Block as BlockT, NumberFor};
+use std::sync::Arc;
+
+/// Something that can revert blocks from the best chain.
+///
+/// Implemented for every client [`Backend`](ClientBackend), which is what should usually be
+/// passed to [`ManualSealParams::revert_backend`](crate::ManualSealParams::revert_backend).
+pub trait RevertBackend<B: BlockT>: Send + Sync {
+	/// Revert `n` blocks from the best chain, including finalized ones.
+	///
+	/// Returns the number of blocks that were reverted.
+	fn revert_blocks(&self, n: NumberFor<B>) -> Result<NumberFor<B>, Error>;
+}
+
+impl<B, CB> RevertBackend<B> for CB
+where
+	B: BlockT,
+	CB: ClientBackend<B>,
+{
+	fn revert_blocks(&self, n: NumberFor<B>) -> Result<NumberFor<B>, Error> {
+		let (reverted, _) = self.revert(n, true)?;
+		Ok(reverted)
+	}
+}
+
+/// params for reverting the chain.
+pub struct RevertBlockParams<'a, B: BlockT, C> {
+	/// hash of the block that becomes the new best block
+	pub hash: <B as BlockT>::Hash,
+	/// sender to report errors/success to the rpc.
+	pub sender: rpc::Sender<()>,
+	/// header backend
+	pub client: Arc<C>,
+	/// backend to revert the blocks from, reverting is not supported if `None`.
+	pub revert_backend: Option<&'a dyn RevertBackend<B>>,
+}
+
+/// reverts the best chain to the block given in params.
+///
+/// All blocks on top of the target block are removed, even if they have been finalized.
+/// Data kept outside of the client backend (e.g. BABE epoch changes) is not reverted.
+pub async fn revert_block<B, C>(params: RevertBlockParams<'_, B, C>)
+where
+	B: BlockT,
+	C: HeaderBackend<B>,
+{
+	let RevertBlockParams { hash, mut sender, client, revert_backend } = params;
+
+	let result = (|| {
+		let revert_backend = revert_backend.ok_or_else(|| {
+			Error::StringError("Reverting blocks is not supported by this node".into())
+		})?;
+
+		let number =
+			client.number(hash)?.ok_or_else(|| Error::BlockNotFound(format!("{}", hash)))?;
+		if client.hash(number)? != Some(hash) {
+			return Err(Error::StringError(format!("Block {} is not part of the best chain", hash)))
+		}
+
+		let best_number = client.info().best_number;
+		revert_backend.revert_blocks(best_number - number)
+	})();
+
+	match result {
+		Err(e) => {
+			log::warn!("Failed to revert to block {}: {}", hash, e);
+			rpc::send_result(&mut sender, Err(e))
+		},
+		Ok(reverted) => {
+			log::info!("⏪ Reverted {} blocks, new best block: {}", reverted, hash);
+			rpc::send_result(&mut sender, Ok(()))
+		},
+	}
+}
@@ -26,6 +26,7 @@ use futures::{
 use jsonrpsee::{core::async_trait, proc_macros::rpc};
 use sc_consensus::ImportedAux;
 use serde::{Deserialize, Serialize};
+use sp_core::Bytes;
 use sp_runtime::EncodedJustification;
 
 /// Sender passed to the authorship task to report errors or successes.
@@ -59,6 +60,30 @@ pub enum EngineCommand<Hash> {
 		/// finalization justification
 		justification: Option<EncodedJustification>,
 	},
+	/// Tells the engine to use the given timestamp (in milliseconds since the unix epoch) for the
+	/// next sealed block, instead of the one created by the inherent data providers. Later blocks
+	/// keep the same offset from the provided timestamps.
+	SetNextTimestamp {
+		/// timestamp of the next block
+		timestamp: u64,
+		/// sender to report errors/success to the rpc.
+		sender: Sender<()>,
+	},
+	/// Tells the engine to set (or clear, if the value is `None`) the given storage keys
+	/// on top of the state changes of the next sealed block.
+	SetStorage {
+		/// storage keys and their new values
+		changes: Vec<(Vec<u8>, Option<Vec<u8>>)>,
+		/// sender to report errors/success to the rpc.
+		sender: Sender<()>,
+	},
+	/// Tells the engine to revert the best chain back to the block with the supplied hash.
+	RevertTo {
+		/// hash of the block that becomes the new best block
+		hash: Hash,
+		/// sender to report errors/success to the rpc.
+		sender: Sender<()>,
+	},
 }
 
 /// RPC trait that provides methods for interacting with the manual-seal authorship task over rpc.
@@ -80,6 +105,21 @@ pub trait ManualSealApi<Hash> {
 		hash: Hash,
 		justification: Option<EncodedJustification>,
 	) -> Result<bool, Error>;
+
+	/// Instructs the manual-seal authorship task to use the given timestamp (in milliseconds)
+	/// for the next block it seals. Later blocks keep the same offset from the wall clock, so the
+	/// chain doesn't go back in time.
+	#[method(name = "engine_setNextTimestamp")]
+	async fn set_next_timestamp(&self, timestamp: u64) -> Result<bool, Error>;
+
+	/// Instructs the manual-seal authorship task to set (or clear, for `null` values) the given
+	/// storage keys when sealing the next block
+	#[method(name = "engine_setStorage")]
+	async fn set_storage(&self, changes: Vec<(Bytes, Option<Bytes>)>) -> Result<bool, Error>;
+
+	/// Instructs the manual-seal authorship task to revert the chain to the given block
+	#[method(name = "engine_revertTo")]
+	async fn revert_to(&self, hash: Hash) -> Result<bool, Error>;
 }
 
 /// A struct that implements the [`ManualSealApiServer`].
@@ -143,6 +183,34 @@ impl<Hash: Send + 'static> ManualSealApiServer<Hash> for ManualSeal<Hash> {
 		sink.send(command).await?;
 		receiver.await.map(|_| true).map_err(Into::into)
 	}
+
+	async fn set_next_timestamp(&self, timestamp: u64) -> Result<bool, Error> {
+		let mut sink = self.import_block_channel.clone();
+		let (sender, receiver) = oneshot::channel();
+		let command = EngineCommand::SetNextTimestamp { timestamp, sender: Some(sender) };
+		sink.send(command).await?;
+		receiver.await?.map(|_| true)
+	}
+
+	async fn set_storage(&self, changes: Vec<(Bytes, Option<Bytes>)>) -> Result<bool, Error> {
+		let mut sink = self.import_block_channel.clone();
+		let (sender, receiver) = oneshot::channel();
+		let changes = changes
+			.into_iter()
+			.map(|(key, value)| (key.0, value.map(|value| value.0)))
+			.collect();
+		let command = EngineCommand::SetStorage { changes, sender: Some(sender) };
+		sink.send(command).await?;
+		receiver.await?.map(|_| true)
+	}
+
+	async fn revert_to(&self, hash: Hash) -> Result<bool, Error> {
+		let mut sink = self.import_block_channel.clone();
+		let (sender, receiver) = oneshot::channel();
+		let command = EngineCommand::RevertTo { hash, sender: Some(sender) };
+		sink.send(command).await?;
+		receiver.await?.map(|_| true)
+	}
 }
 
 /// report any errors or successes encountered by the authorship task back
@@ -22,16 +22,34 @@ use crate::{rpc, ConsensusDataProvider, CreatedBlock, Error};
 use futures::prelude::*;
 use sc_consensus::{BlockImport, BlockImportParams, ForkChoiceStrategy, ImportResult, StateAction};
 use sc_transaction_pool_api::TransactionPool;
-use sp_api::ProvideRuntimeApi;
+use sp_api::{CallApiAt, ProvideRuntimeApi, StorageChanges};
 use sp_blockchain::HeaderBackend;
 use sp_consensus::{self, BlockOrigin, Environment, Proposer, SelectChain};
+use sp_core::storage::ChildInfo;
 use sp_inherents::{CreateInherentDataProviders, InherentDataProvider};
-use sp_runtime::traits::{Block as BlockT, Header as HeaderT};
-use std::{sync::Arc, time::Duration};
+use sp_runtime::traits::{Block as BlockT, HashingFor, Header as HeaderT};
+use sp_state_machine::OverlayedChanges;
+use std::{collections::BTreeMap, sync::Arc, time::Duration};
 
 /// max duration for creating a proposal in secs
 pub const MAX_PROPOSAL_DURATION: u64 = 10;
 
+/// Overrides for the next sealed block, set via the `engine_setNextTimestamp` and
+/// `engine_setStorage` rpc methods.
+///
+/// They are cleared once a block has been sealed successfully, except for the timestamp offset
+/// which keeps later blocks ahead of the provided timestamp after a jump in time.
+#[derive(Debug, Default, Clone)]
+pub struct SealOverrides {
+	/// timestamp to put into the timestamp inherent instead of the provided one.
+	pub timestamp: Option<sp_timestamp::Timestamp>,
+	/// milliseconds added to the provided timestamp of every block, set by the last sealed
+	/// `timestamp` override.
+	pub timestamp_offset: u64,
+	/// storage keys to set, or to clear if the value is `None`, after the block was built.
+	pub storage: BTreeMap<Vec<u8>, Option<Vec<u8>>>,
+}
+
 /// params for sealing a new block
 pub struct SealBlockParams<'a, B: BlockT, BI, SC, C: ProvideRuntimeApi<B>, E, TP, CIDP, P> {
 	/// if true, empty blocks(without extrinsics) will be created.
@@ -57,6 +75,8 @@ pub struct SealBlockParams<'a, B: BlockT, BI, SC, C: ProvideRuntimeApi<B>, E, TP
 	pub block_import: &'a mut BI,
 	/// Something that can create the inherent data providers.
 	pub create_inherent_data_providers: &'a CIDP,
+	/// overrides to apply to this block, cleared if the block was sealed.
+	pub overrides: &'a mut SealOverrides,
 }
 
 /// seals a new block with the given params
@@ -72,12 +92,13 @@ pub async fn seal_block<B, BI, SC, C, E, TP, CIDP, P>(
 		env,
 		create_inherent_data_providers,
 		consensus_data_provider: digest_provider,
+		overrides,
 		mut sender,
 	}: SealBlockParams<'_, B, BI, SC, C, E, TP, CIDP, P>,
 ) where
 	B: BlockT,
 	BI: BlockImport<B, Error = sp_consensus::Error> + Send + Sync + 'static,
-	C: HeaderBackend<B> + ProvideRuntimeApi<B>,
+	C: HeaderBackend<B> + ProvideRuntimeApi<B> + CallApiAt<B>,
 	E: Environment<B>,
 	E::Proposer: Proposer<B, Proof = P>,
 	TP: TransactionPool<Block = B>,
@@ -85,6 +106,7 @@ pub async fn seal_block<B, BI, SC, C, E, TP, CIDP, P>(
 	CIDP: CreateInherentDataProviders<B, ()>,
 	P: codec::Encode + Send + Sync + 'static,
 {
+	let mut timestamp_offset = overrides.timestamp_offset;
 	let future = async {
 		if pool.status().ready == 0 && !create_empty {
 			return Err(Error::EmptyTransactionPool)
@@ -104,7 +126,30 @@ pub async fn seal_block<B, BI, SC, C, E, TP, CIDP, P>(
 			.await
 			.map_err(|e| Error::Other(e))?;
 
-		let inherent_data = inherent_data_providers.create_inherent_data().await?;
+		let mut inherent_data = inherent_data_providers.create_inherent_data().await?;
+
+		let provided = inherent_data
+			.get_data::<sp_timestamp::Timestamp>(&sp_timestamp::INHERENT_IDENTIFIER)?;
+		let timestamp = match (overrides.timestamp, provided) {
+			(Some(timestamp), provided) => {
+				// keep the following blocks at the same distance from the provided time, else
+				// they would go back in time and be rejected by the runtime.
+				timestamp_offset = provided.map_or(0, |provided| {
+					timestamp.as_millis().saturating_sub(provided.as_millis())
+				});
+				Some(timestamp)
+			},
+			(None, Some(provided)) if timestamp_offset > 0 => Some(sp_timestamp::Timestamp::new(
+				provided.as_millis().saturating_add(timestamp_offset),
+			)),
+			_ => None,
+		};
+		if let Some(timestamp) = timestamp {
+			inherent_data.replace_data(sp_timestamp::INHERENT_IDENTIFIER, &timestamp);
+			if let Some(digest_provider) = digest_provider {
+				digest_provider.override_timestamp(&mut inherent_data, timestamp)?;
+			}
+		}
 
 		let proposer = env.init(&parent).map_err(|err| Error::StringError(err.to_string())).await?;
 		let inherents_len = inherent_data.len();
@@ -129,16 +174,22 @@ pub async fn seal_block<B, BI, SC, C, E, TP, CIDP, P>(
 			return Err(Error::EmptyTransactionPool)
 		}
 
-		let (header, body) = proposal.block.deconstruct();
+		let (mut header, body) = proposal.block.deconstruct();
+		let mut storage_changes = proposal.storage_changes;
+		if !overrides.storage.is_empty() {
+			storage_changes =
+				override_storage(&*client, parent.hash(), storage_changes, &overrides.storage)?;
+			header.set_state_root(storage_changes.transaction_storage_root);
+		}
+
 		let proof = proposal.proof;
 		let proof_size = proof.encoded_size();
 		let mut params = BlockImportParams::new(BlockOrigin::Own, header.clone());
 		params.body = Some(body);
 		params.finalized = finalize;
 		params.fork_choice = Some(ForkChoiceStrategy::LongestChain);
-		params.state_action = StateAction::ApplyChanges(sc_consensus::StorageChanges::Changes(
-			proposal.storage_changes,
-		));
+		params.state_action =
+			StateAction::ApplyChanges(sc_consensus::StorageChanges::Changes(storage_changes));
 
 		if let Some(digest_provider) = digest_provider {
 			digest_provider.append_block_import(&parent, &mut params, &inherent_data, proof)?;
@@ -159,5 +210,58 @@ pub async fn seal_block<B, BI, SC, C, E, TP, CIDP, P>(
 		}
 	};
 
-	rpc::send_result(&mut sender, future.await)
+	let result = future.await;
+	if result.is_ok() {
+		*overrides = SealOverrides { timestamp_offset, ..Default::default() };
+	}
+
+	rpc::send_result(&mut sender, result)
+}
+
+/// Applies the `overrides` on top of the storage changes of a block built on `parent_hash`,
+/// recalculating the storage root.
+fn override_storage<B, C>(
+	client: &C,
+	parent_hash: B::Hash,
+	changes: StorageChanges<B>,
+	overrides: &BTreeMap<Vec<u8>, Option<Vec<u8>>>,
+) -> Result<StorageChanges<B>, Error>
+where
+	B: BlockT,
+	C: CallApiAt<B>,
+{
+	let state = client.state_at(parent_hash).map_err(sp_blockchain::Error::from)?;
+	let state_version = client
+		.runtime_version_at(parent_hash)
+		.map_err(sp_blockchain::Error::from)?
+		.state_version();
+
+	let StorageChanges {
+		main_storage_changes,
+		child_storage_changes,
+		offchain_storage_changes,
+		transaction_index_changes,
+		..
+	} = changes;
+
+	let mut overlay = OverlayedChanges::<HashingFor<B>>::default();
+	for (key, value) in main_storage_changes {
+		overlay.set_storage(key, value);
+	}
+	for (storage_key, child_changes) in child_storage_changes {
+		let child_info = ChildInfo::new_default(&storage_key);
+		for (key, value) in child_changes {
+			overlay.set_child_storage(&child_info, key, value);
+		}
+	}
+	for (key, value) in overrides {
+		overlay.set_storage(key.clone(), value.clone());
+	}
+
+	let mut changes = overlay.drain_storage_changes(&state, state_version)?;
+	// neither of them end up in the storage root, keep them as produced by the block.
+	changes.offchain_storage_changes = offchain_storage_changes;
+	changes.transaction_index_changes = transaction_index_changes;
+
+	Ok(changes)
 }
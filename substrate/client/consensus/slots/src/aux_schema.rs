@@ -25,6 +25,7 @@ use sp_consensus_slots::{EquivocationProof, Slot};
 use sp_runtime::traits::Header;
 
 const SLOT_HEADER_MAP_KEY: &[u8] = b"slot_header_map";
+const SLOT_SUB_SLOT_HEADER_MAP_KEY: &[u8] = b"slot_sub_slot_header_map";
 const SLOT_HEADER_START: &[u8] = b"slot_header_start";
 
 /// We keep at least this number of slots in database.
@@ -61,6 +62,69 @@ where
 	H: Header,
 	C: AuxStore,
 	P: Clone + Encode + Decode + PartialEq,
+{
+	check_and_record_header(
+		backend,
+		slot_now,
+		slot,
+		(header, signer),
+		SLOT_HEADER_MAP_KEY,
+		(header.clone(), signer.clone()),
+		|(prev_header, prev_signer): &(H, P)| (prev_signer == signer).then_some(prev_header),
+	)
+}
+
+/// Checks if the header is an equivocation at the given sub-slot of `slot` and returns the proof
+/// in that case.
+///
+/// This is meant for authors allowed to build several blocks within their slot, each one in its
+/// own sub-slot: only two different headers signed for the same sub-slot are an equivocation.
+///
+/// Note: it detects equivocations only when slot_now - slot <= MAX_SLOT_CAPACITY.
+pub fn check_sub_slot_equivocation<C, H, P>(
+	backend: &C,
+	slot_now: Slot,
+	slot: Slot,
+	sub_slot: u32,
+	header: &H,
+	signer: &P,
+) -> ClientResult<Option<EquivocationProof<H, P>>>
+where
+	H: Header,
+	C: AuxStore,
+	P: Clone + Encode + Decode + PartialEq,
+{
+	check_and_record_header(
+		backend,
+		slot_now,
+		slot,
+		(header, signer),
+		SLOT_SUB_SLOT_HEADER_MAP_KEY,
+		(sub_slot, header.clone(), signer.clone()),
+		|(prev_sub_slot, prev_header, prev_signer): &(u32, H, P)| {
+			(*prev_sub_slot == sub_slot && prev_signer == signer).then_some(prev_header)
+		},
+	)
+}
+
+/// Looks for a header of `signer` among the entries saved for `slot` under `map_key` and
+/// records `entry` for `header` if there is none.
+///
+/// `previous` returns the header of an entry if it was signed by `signer` at the same position.
+fn check_and_record_header<C, H, P, E>(
+	backend: &C,
+	slot_now: Slot,
+	slot: Slot,
+	(header, signer): (&H, &P),
+	map_key: &[u8],
+	entry: E,
+	previous: impl Fn(&E) -> Option<&H>,
+) -> ClientResult<Option<EquivocationProof<H, P>>>
+where
+	H: Header,
+	C: AuxStore,
+	P: Clone,
+	E: Encode + Decode,
 {
 	// We don't check equivocations for old headers out of our capacity.
 	if slot_now.saturating_sub(*slot) > MAX_SLOT_CAPACITY {
@@ -68,12 +132,12 @@ where
 	}
 
 	// Key for this slot.
-	let mut curr_slot_key = SLOT_HEADER_MAP_KEY.to_vec();
+	let mut curr_slot_key = map_key.to_vec();
 	slot.using_encoded(|s| curr_slot_key.extend(s));
 
 	// Get headers of this slot.
 	let mut headers_with_sig =
-		load_decode::<_, Vec<(H, P)>>(backend, &curr_slot_key[..])?.unwrap_or_else(Vec::new);
+		load_decode::<_, Vec<E>>(backend, &curr_slot_key[..])?.unwrap_or_else(Vec::new);
 
 	// Get first slot saved.
 	let slot_header_start = SLOT_HEADER_START.to_vec();
@@ -84,24 +148,22 @@ where
 		return Ok(None)
 	}
 
-	for (prev_header, prev_signer) in headers_with_sig.iter() {
-		// A proof of equivocation consists of two headers:
-		// 1) signed by the same voter,
-		if prev_signer == signer {
-			// 2) with different hash
-			return if header.hash() != prev_header.hash() {
-				Ok(Some(EquivocationProof {
-					slot,
-					offender: signer.clone(),
-					first_header: prev_header.clone(),
-					second_header: header.clone(),
-				}))
-			} else {
-				// We don't need to continue in case of duplicated header,
-				// since it's already saved and a possible equivocation
-				// would have been detected before.
-				Ok(None)
-			}
+	// A proof of equivocation consists of two headers:
+	// 1) signed by the same voter,
+	if let Some(prev_header) = headers_with_sig.iter().find_map(&previous) {
+		// 2) with different hash
+		return if header.hash() != prev_header.hash() {
+			Ok(Some(EquivocationProof {
+				slot,
+				offender: signer.clone(),
+				first_header: prev_header.clone(),
+				second_header: header.clone(),
+			}))
+		} else {
+			// We don't need to continue in case of duplicated header,
+			// since it's already saved and a possible equivocation
+			// would have been detected before.
+			Ok(None)
 		}
 	}
 
@@ -109,17 +171,19 @@ where
 	let mut new_first_saved_slot = first_saved_slot;
 
 	if *slot_now - *first_saved_slot >= PRUNING_BOUND {
-		let prefix = SLOT_HEADER_MAP_KEY.to_vec();
 		new_first_saved_slot = slot_now.saturating_sub(MAX_SLOT_CAPACITY);
 
-		for s in u64::from(first_saved_slot)..new_first_saved_slot.into() {
-			let mut p = prefix.clone();
-			s.using_encoded(|s| p.extend(s));
-			keys_to_delete.push(p);
+		// Both maps share the pruning bound.
+		for prefix in [SLOT_HEADER_MAP_KEY, SLOT_SUB_SLOT_HEADER_MAP_KEY] {
+			for s in u64::from(first_saved_slot)..new_first_saved_slot.into() {
+				let mut p = prefix.to_vec();
+				s.using_encoded(|s| p.extend(s));
+				keys_to_delete.push(p);
+			}
 		}
 	}
 
-	headers_with_sig.push((header.clone(), signer.clone()));
+	headers_with_sig.push(entry);
 
 	backend.insert_aux(
 		&[
@@ -138,7 +202,9 @@ mod test {
 	use sp_runtime::testing::{Digest as DigestTest, Header as HeaderTest};
 	use substrate_test_runtime_client;
 
-	use super::{check_equivocation, MAX_SLOT_CAPACITY, PRUNING_BOUND};
+	use super::{
+		check_equivocation, check_sub_slot_equivocation, MAX_SLOT_CAPACITY, PRUNING_BOUND,
+	};
 
 	fn create_header(number: u64) -> HeaderTest {
 		// so that different headers for the same number get different hashes
@@ -220,4 +286,36 @@ mod test {
 		.unwrap()
 		.is_none(),);
 	}
+
+	#[test]
+	fn check_sub_slot_equivocation_works() {
+		let client = substrate_test_runtime_client::new();
+		let (pair, _seed) = sr25519::Pair::generate();
+		let public = pair.public();
+
+		let header1 = create_header(1); // @ slot 2, sub-slot 0
+		let header2 = create_header(2); // @ slot 2, sub-slot 1
+		let header3 = create_header(2); // @ slot 2, sub-slot 1
+
+		assert!(check_sub_slot_equivocation(&client, 2.into(), 2.into(), 0, &header1, &public)
+			.unwrap()
+			.is_none());
+
+		// Another header in the next sub-slot is ok.
+		assert!(check_sub_slot_equivocation(&client, 2.into(), 2.into(), 1, &header2, &public)
+			.unwrap()
+			.is_none());
+
+		// It's ok to sign same headers.
+		assert!(check_sub_slot_equivocation(&client, 3.into(), 2.into(), 1, &header2, &public)
+			.unwrap()
+			.is_none());
+
+		// But not two different headers at the same sub-slot.
+		let proof = check_sub_slot_equivocation(&client, 3.into(), 2.into(), 1, &header3, &public)
+			.unwrap()
+			.unwrap();
+		assert_eq!(proof.first_header, header2);
+		assert_eq!(proof.second_header, header3);
+	}
 }
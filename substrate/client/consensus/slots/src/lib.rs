@@ -28,7 +28,9 @@
 mod aux_schema;
 mod slots;
 
-pub use aux_schema::{check_equivocation, MAX_SLOT_CAPACITY, PRUNING_BOUND};
+pub use aux_schema::{
+	check_equivocation, check_sub_slot_equivocation, MAX_SLOT_CAPACITY, PRUNING_BOUND,
+};
 use slots::Slots;
 pub use slots::{time_until_next_slot, SlotInfo};
 
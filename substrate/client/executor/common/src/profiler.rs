@@ -0,0 +1,222 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+//! Profiling of runtime calls.
+//!
+//! An executor created with a [`Profiler`] records a [`CallProfile`] for every runtime call it
+//! performs. A profile consists of the time spent in each host function and of periodic samples
+//! of the wasm call stack, which can be written out in the "folded stacks" format understood by
+//! flamegraph tools (e.g. `inferno-flamegraph` or `flamegraph.pl`).
+
+use std::{
+	collections::BTreeMap,
+	io,
+	sync::Mutex,
+	time::{Duration, Instant},
+};
+
+/// The prefix of host function frames in the folded stacks.
+const HOST_FRAME_PREFIX: &str = "host:";
+
+/// Collects the [`CallProfile`]s of all runtime calls made by an executor.
+#[derive(Debug)]
+pub struct Profiler {
+	sample_interval: Duration,
+	profiles: Mutex<Vec<CallProfile>>,
+}
+
+impl Profiler {
+	/// Creates a new profiler sampling the wasm call stack every `sample_interval`.
+	pub fn new(sample_interval: Duration) -> Self {
+		Self { sample_interval, profiles: Mutex::new(Vec::new()) }
+	}
+
+	/// The interval at which the wasm call stack is sampled.
+	pub fn sample_interval(&self) -> Duration {
+		self.sample_interval
+	}
+
+	/// Stores the profile of a finished runtime call.
+	pub fn add_profile(&self, profile: CallProfile) {
+		self.profiles.lock().unwrap_or_else(|e| e.into_inner()).push(profile);
+	}
+
+	/// Takes all the profiles recorded so far, in the order the calls were made.
+	pub fn take_profiles(&self) -> Vec<CallProfile> {
+		std::mem::take(&mut *self.profiles.lock().unwrap_or_else(|e| e.into_inner()))
+	}
+}
+
+/// Statistics of the calls into a single host function.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct HostFunctionStats {
+	/// How many times the host function was called.
+	pub calls: u64,
+	/// The total time spent in the host function.
+	pub total_time: Duration,
+}
+
+/// The profile of a single runtime call.
+#[derive(Debug, Clone)]
+pub struct CallProfile {
+	/// The runtime method that was called, e.g. `Core_execute_block`.
+	pub method: String,
+	/// The total duration of the call.
+	pub total_time: Duration,
+	/// Statistics of every host function called by the runtime, by host function name.
+	pub host_functions: BTreeMap<String, HostFunctionStats>,
+	/// The time attributed to each observed call stack, outermost frame first.
+	///
+	/// Stacks ending in a host function frame account for the time spent in that host function,
+	/// the other ones are estimated from periodic samples of the wasm call stack.
+	pub stacks: BTreeMap<Vec<String>, Duration>,
+	started: Instant,
+	last_sample: Instant,
+	host_time_since_sample: Duration,
+}
+
+impl CallProfile {
+	/// Starts profiling a call into the given runtime `method`.
+	pub fn start(method: &str) -> Self {
+		let now = Instant::now();
+		Self {
+			method: method.into(),
+			total_time: Duration::ZERO,
+			host_functions: BTreeMap::new(),
+			stacks: BTreeMap::new(),
+			started: now,
+			last_sample: now,
+			host_time_since_sample: Duration::ZERO,
+		}
+	}
+
+	/// Records a sample of the wasm call stack, `stack` being ordered from the outermost frame.
+	///
+	/// The time since the previous sample, minus the time spent in host functions, is attributed
+	/// to the stack.
+	pub fn record_sample(&mut self, stack: Vec<String>) {
+		let now = Instant::now();
+		let elapsed = now
+			.saturating_duration_since(self.last_sample)
+			.saturating_sub(std::mem::take(&mut self.host_time_since_sample));
+		self.last_sample = now;
+
+		*self.stacks.entry(stack).or_default() += elapsed;
+	}
+
+	/// Records a call into the host function `name` that took `elapsed`, made from the wasm call
+	/// `stack` ordered from the outermost frame.
+	pub fn record_host_call(&mut self, name: &str, mut stack: Vec<String>, elapsed: Duration) {
+		let stats = self.host_functions.entry(name.into()).or_default();
+		stats.calls += 1;
+		stats.total_time += elapsed;
+		self.host_time_since_sample += elapsed;
+
+		stack.push(format!("{HOST_FRAME_PREFIX}{name}"));
+		*self.stacks.entry(stack).or_default() += elapsed;
+	}
+
+	/// Marks the call as finished.
+	pub fn finish(mut self) -> Self {
+		self.total_time = self.started.elapsed();
+		self
+	}
+
+	/// The total time spent in host functions.
+	pub fn host_time(&self) -> Duration {
+		self.host_functions.values().map(|stats| stats.total_time).sum()
+	}
+
+	/// Writes the call stacks in the folded stacks format, one `frame;frame;... weight` line per
+	/// stack, rooted at the runtime method and weighted in microseconds.
+	pub fn write_folded(&self, out: &mut impl io::Write) -> io::Result<()> {
+		for (stack, time) in &self.stacks {
+			let micros = time.as_micros();
+			if micros == 0 {
+				continue
+			}
+
+			write!(out, "{}", self.method)?;
+			for frame in stack {
+				// `;` separates the frames and the last space separates the weight.
+				write!(out, ";{}", frame.replace([';', ' '], "_"))?;
+			}
+			writeln!(out, " {}", micros)?;
+		}
+
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn stack(frames: &[&str]) -> Vec<String> {
+		frames.iter().map(|frame| frame.to_string()).collect()
+	}
+
+	#[test]
+	fn host_calls_are_aggregated() {
+		let mut profile = CallProfile::start("Core_execute_block");
+		profile.record_host_call(
+			"ext_storage_get_version_1",
+			stack(&["a"]),
+			Duration::from_millis(2),
+		);
+		profile.record_host_call(
+			"ext_storage_get_version_1",
+			stack(&["b"]),
+			Duration::from_millis(3),
+		);
+		profile.record_host_call(
+			"ext_hashing_blake2_256_version_1",
+			stack(&["a"]),
+			Duration::from_millis(1),
+		);
+
+		assert_eq!(
+			profile.host_functions["ext_storage_get_version_1"],
+			HostFunctionStats { calls: 2, total_time: Duration::from_millis(5) },
+		);
+		assert_eq!(profile.host_time(), Duration::from_millis(6));
+		assert_eq!(
+			profile.stacks[&stack(&["a", "host:ext_storage_get_version_1"])],
+			Duration::from_millis(2)
+		);
+	}
+
+	#[test]
+	fn write_folded_works() {
+		let mut profile = CallProfile::start("Core_execute_block");
+		profile.record_host_call(
+			"ext_storage_get_version_1",
+			stack(&["main", "a b"]),
+			Duration::from_micros(20),
+		);
+		profile.stacks.insert(stack(&["main"]), Duration::from_micros(100));
+		profile.stacks.insert(stack(&["main", "empty"]), Duration::ZERO);
+
+		let mut out = Vec::new();
+		profile.write_folded(&mut out).unwrap();
+
+		assert_eq!(
+			String::from_utf8(out).unwrap(),
+			"Core_execute_block;main 100\nCore_execute_block;main;a_b;host:ext_storage_get_version_1 20\n",
+		);
+	}
+}
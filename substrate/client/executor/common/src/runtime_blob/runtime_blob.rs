@@ -38,10 +38,20 @@ impl RuntimeBlob {
 	///
 	/// See [`sp_maybe_compressed_blob`] for details about decompression.
 	pub fn uncompress_if_needed(wasm_code: &[u8]) -> Result<Self, WasmError> {
+		Self::uncompress_if_needed_with_polkavm(wasm_code, crate::is_polkavm_enabled())
+	}
+
+	/// Same as [`Self::uncompress_if_needed`], but PolkaVM programs are accepted only if
+	/// `polkavm_enabled` is `true`, regardless of the `SUBSTRATE_ENABLE_POLKAVM` environment
+	/// variable.
+	pub fn uncompress_if_needed_with_polkavm(
+		wasm_code: &[u8],
+		polkavm_enabled: bool,
+	) -> Result<Self, WasmError> {
 		use sp_maybe_compressed_blob::CODE_BLOB_BOMB_LIMIT;
 		let wasm_code = sp_maybe_compressed_blob::decompress(wasm_code, CODE_BLOB_BOMB_LIMIT)
 			.map_err(|e| WasmError::Other(format!("Decompression error: {:?}", e)))?;
-		Self::new(&wasm_code)
+		Self::new_with_polkavm(&wasm_code, polkavm_enabled)
 	}
 
 	/// Create `RuntimeBlob` from the given WASM or PolkaVM program blob.
@@ -51,13 +61,19 @@ impl RuntimeBlob {
 	/// Will only accept a PolkaVM program if the `SUBSTRATE_ENABLE_POLKAVM` environment
 	/// variable is set to `1`.
 	pub fn new(raw_blob: &[u8]) -> Result<Self, WasmError> {
+		Self::new_with_polkavm(raw_blob, crate::is_polkavm_enabled())
+	}
+
+	/// Same as [`Self::new`], but PolkaVM programs are accepted only if `polkavm_enabled` is
+	/// `true`, regardless of the `SUBSTRATE_ENABLE_POLKAVM` environment variable.
+	pub fn new_with_polkavm(raw_blob: &[u8], polkavm_enabled: bool) -> Result<Self, WasmError> {
 		if raw_blob.starts_with(b"PVM\0") {
-			if crate::is_polkavm_enabled() {
+			if polkavm_enabled {
 				let raw = ArcBytes::from(raw_blob);
 				let blob = polkavm::ProgramBlob::parse(raw.clone())?;
 				return Ok(Self(BlobKind::PolkaVM((blob, raw))));
 			} else {
-				return Err(WasmError::Other("expected a WASM runtime blob, found a PolkaVM runtime blob; set the 'SUBSTRATE_ENABLE_POLKAVM' environment variable to enable the experimental PolkaVM-based executor".to_string()));
+				return Err(WasmError::Other("expected a WASM runtime blob, found a PolkaVM runtime blob; set the 'SUBSTRATE_ENABLE_POLKAVM' environment variable or use the 'polkavm' execution method to enable the experimental PolkaVM-based executor".to_string()));
 			}
 		}
 
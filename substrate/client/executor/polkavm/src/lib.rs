@@ -18,18 +18,25 @@
 
 use polkavm::{CallError, Caller, Reg};
 use sc_executor_common::{
-	error::{Error, WasmError},
+	error::{Error, MessageWithBacktrace, WasmError},
 	wasm_runtime::{AllocationStats, WasmInstance, WasmModule},
 };
 use sp_wasm_interface::{
 	Function, FunctionContext, HostFunctions, Pointer, Value, ValueType, WordSize,
 };
 
+/// The state shared with the host functions during a runtime call.
+#[derive(Default)]
+struct HostState {
+	/// The panic message registered by the runtime before it aborted, if any.
+	panic_message: Option<String>,
+}
+
 #[repr(transparent)]
-pub struct InstancePre(polkavm::InstancePre<(), String>);
+pub struct InstancePre(polkavm::InstancePre<HostState, String>);
 
 #[repr(transparent)]
-pub struct Instance(polkavm::Instance<(), String>);
+pub struct Instance(polkavm::Instance<HostState, String>);
 
 impl WasmModule for InstancePre {
 	fn new_instance(&self) -> Result<Box<dyn WasmInstance>, Error> {
@@ -91,11 +98,23 @@ impl WasmInstance for Instance {
 			return (Err(format!("call into the runtime method '{name}': failed to write the input payload into guest memory: {err}").into()), None);
 		}
 
-		match self.0.call_typed(&mut (), pc, (data_pointer, raw_data_length)) {
+		let mut host_state = HostState::default();
+		match self.0.call_typed(&mut host_state, pc, (data_pointer, raw_data_length)) {
 			Ok(()) => {},
+			Err(_) if host_state.panic_message.is_some() =>
+				return (
+					Err(Error::AbortedDueToPanic(MessageWithBacktrace {
+						message: host_state.panic_message.take().expect("checked above; qed"),
+						backtrace: None,
+					})),
+					None,
+				),
 			Err(CallError::Trap) =>
 				return (
-					Err(format!("call into the runtime method '{name}' failed: trap").into()),
+					Err(Error::AbortedDueToTrap(MessageWithBacktrace {
+						message: format!("call into the runtime method '{name}' failed: trap"),
+						backtrace: None,
+					})),
 					None,
 				),
 			Err(CallError::Error(err)) =>
@@ -105,7 +124,10 @@ impl WasmInstance for Instance {
 				),
 			Err(CallError::User(err)) =>
 				return (
-					Err(format!("call into the runtime method '{name}' failed: {err}").into()),
+					Err(Error::AbortedDueToTrap(MessageWithBacktrace {
+						message: err,
+						backtrace: None,
+					})),
 					None,
 				),
 			Err(CallError::NotEnoughGas) => unreachable!("gas metering is never enabled"),
@@ -113,10 +135,16 @@ impl WasmInstance for Instance {
 
 		let result_pointer = self.0.reg(Reg::A0);
 		let result_length = self.0.reg(Reg::A1);
-		let output = match self.0.read_memory(result_pointer as u32, result_length as u32) {
+		let (Ok(result_pointer), Ok(result_length)) =
+			(u32::try_from(result_pointer), u32::try_from(result_length))
+		else {
+			return (Err(Error::OutputExceedsBounds), None)
+		};
+		let output = match self.0.read_memory(result_pointer, result_length) {
 			Ok(output) => output,
 			Err(error) => {
-				return (Err(format!("call into the runtime method '{name}' failed: failed to read the return payload: {error}").into()), None)
+				log::debug!("call into the runtime method '{name}' failed: failed to read the return payload: {error}");
+				return (Err(Error::OutputExceedsBounds), None)
 			},
 		};
 
@@ -124,7 +152,7 @@ impl WasmInstance for Instance {
 	}
 }
 
-struct Context<'r, 'a>(&'r mut polkavm::Caller<'a, ()>);
+struct Context<'r, 'a>(&'r mut polkavm::Caller<'a, HostState>);
 
 impl<'r, 'a> FunctionContext for Context<'r, 'a> {
 	fn read_memory_into(
@@ -167,12 +195,15 @@ impl<'r, 'a> FunctionContext for Context<'r, 'a> {
 		unimplemented!("'deallocate_memory' is never used when running under PolkaVM");
 	}
 
-	fn register_panic_error_message(&mut self, _message: &str) {
-		unimplemented!("'register_panic_error_message' is never used when running under PolkaVM");
+	fn register_panic_error_message(&mut self, message: &str) {
+		self.0.user_data.panic_message = Some(message.to_owned());
 	}
 }
 
-fn call_host_function(caller: &mut Caller<()>, function: &dyn Function) -> Result<(), String> {
+fn call_host_function(
+	caller: &mut Caller<HostState>,
+	function: &dyn Function,
+) -> Result<(), String> {
 	let mut args = [Value::I64(0); Reg::ARG_REGS.len()];
 	let mut nth_reg = 0;
 	for (nth_arg, kind) in function.signature().args.iter().enumerate() {
@@ -259,7 +290,15 @@ fn call_host_function(caller: &mut Caller<()>, function: &dyn Function) -> Resul
 	Ok(())
 }
 
-pub fn create_runtime<H>(blob: &polkavm::ProgramBlob) -> Result<Box<dyn WasmModule>, WasmError>
+/// Create a new PolkaVM runtime out of the given program `blob`.
+///
+/// The runtime has access to the host functions `H`, the same ones which are available to the
+/// wasmtime backend. If `allow_missing_func_imports` is `true`, imports which can't be resolved
+/// are replaced with stubs which abort the execution once called.
+pub fn create_runtime<H>(
+	blob: &polkavm::ProgramBlob,
+	allow_missing_func_imports: bool,
+) -> Result<Box<dyn WasmModule>, WasmError>
 where
 	H: HostFunctions,
 {
@@ -283,10 +322,27 @@ where
 
 	let mut linker = polkavm::Linker::new();
 
+	let mut defined = std::collections::HashSet::new();
 	for function in H::host_functions() {
-		linker.define_untyped(function.name(), |mut caller: Caller<()>| {
+		linker.define_untyped(function.name(), |mut caller: Caller<HostState>| {
 			call_host_function(&mut caller, function)
 		})?;
+		defined.insert(function.name().as_bytes());
+	}
+
+	if allow_missing_func_imports {
+		for symbol in blob.imports().iter().flatten() {
+			let name = symbol.as_bytes();
+			if !defined.insert(name) {
+				continue
+			}
+
+			let message =
+				format!("call to a missing function env:{}", String::from_utf8_lossy(name));
+			linker.define_untyped(name, move |_caller: Caller<HostState>| {
+				Err::<(), _>(message.clone())
+			})?;
+		}
 	}
 	let instance_pre = linker.instantiate_pre(&module)?;
 	Ok(Box::new(InstancePre(instance_pre)))
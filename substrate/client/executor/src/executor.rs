@@ -31,6 +31,7 @@ use std::{
 
 use codec::Encode;
 use sc_executor_common::{
+	profiler::Profiler,
 	runtime_blob::RuntimeBlob,
 	wasm_runtime::{
 		AllocationStats, HeapAllocStrategy, WasmInstance, WasmModule, DEFAULT_HEAP_ALLOC_STRATEGY,
@@ -93,6 +94,7 @@ pub struct WasmExecutorBuilder<H = sp_io::SubstrateHostFunctions> {
 	cache_path: Option<PathBuf>,
 	allow_missing_host_functions: bool,
 	runtime_cache_size: u8,
+	profiler: Option<Arc<Profiler>>,
 }
 
 impl<H> WasmExecutorBuilder<H> {
@@ -110,6 +112,7 @@ impl<H> WasmExecutorBuilder<H> {
 			runtime_cache_size: 4,
 			allow_missing_host_functions: false,
 			cache_path: None,
+			profiler: None,
 		}
 	}
 
@@ -193,6 +196,18 @@ impl<H> WasmExecutorBuilder<H> {
 		self
 	}
 
+	/// Create the wasm executor with the given `profiler`.
+	///
+	/// Every runtime call made by the executor records a profile into the `profiler`, see
+	/// [`Profiler`] for more details. Profiling slows down the execution of the runtime, so it
+	/// should only be used for diagnostics.
+	///
+	/// By default profiling is disabled.
+	pub fn with_profiler(mut self, profiler: Arc<Profiler>) -> Self {
+		self.profiler = Some(profiler);
+		self
+	}
+
 	/// Build the configured [`WasmExecutor`].
 	pub fn build(self) -> WasmExecutor<H> {
 		WasmExecutor {
@@ -204,13 +219,17 @@ impl<H> WasmExecutorBuilder<H> {
 				self.onchain_heap_alloc_strategy,
 			),
 			ignore_onchain_heap_pages: self.ignore_onchain_heap_pages,
-			cache: Arc::new(RuntimeCache::new(
-				self.max_runtime_instances,
-				self.cache_path.clone(),
-				self.runtime_cache_size,
-			)),
+			cache: Arc::new(
+				RuntimeCache::new(
+					self.max_runtime_instances,
+					self.cache_path.clone(),
+					self.runtime_cache_size,
+				)
+				.with_profiler(self.profiler.clone()),
+			),
 			cache_path: self.cache_path,
 			allow_missing_host_functions: self.allow_missing_host_functions,
+			profiler: self.profiler,
 			phantom: PhantomData,
 		}
 	}
@@ -234,6 +253,8 @@ pub struct WasmExecutor<H = sp_io::SubstrateHostFunctions> {
 	cache_path: Option<PathBuf>,
 	/// Ignore missing function imports.
 	allow_missing_host_functions: bool,
+	/// Profiler recording the runtime calls, if enabled.
+	profiler: Option<Arc<Profiler>>,
 	phantom: PhantomData<H>,
 }
 
@@ -247,6 +268,7 @@ impl<H> Clone for WasmExecutor<H> {
 			cache: self.cache.clone(),
 			cache_path: self.cache_path.clone(),
 			allow_missing_host_functions: self.allow_missing_host_functions,
+			profiler: self.profiler.clone(),
 			phantom: self.phantom,
 		}
 	}
@@ -301,6 +323,7 @@ impl<H> WasmExecutor<H> {
 			)),
 			cache_path,
 			allow_missing_host_functions: false,
+			profiler: None,
 			phantom: PhantomData,
 		}
 	}
@@ -424,12 +447,13 @@ where
 		call_data: &[u8],
 		allocation_stats_out: &mut Option<AllocationStats>,
 	) -> std::result::Result<Vec<u8>, Error> {
-		let module = crate::wasm_runtime::create_wasm_runtime_with_code::<H>(
+		let module = crate::wasm_runtime::create_wasm_runtime_with_code_and_profiler::<H>(
 			self.method,
 			self.default_onchain_heap_alloc_strategy,
 			runtime_blob,
 			allow_missing_host_functions,
 			self.cache_path.as_deref(),
+			self.profiler.clone(),
 		)
 		.map_err(|e| format!("Failed to create module: {}", e))?;
 
@@ -459,7 +483,9 @@ where
 		wasm_code: &[u8],
 		ext: &mut dyn Externalities,
 	) -> std::result::Result<Vec<u8>, String> {
-		let runtime_blob = RuntimeBlob::uncompress_if_needed(wasm_code)
+		let runtime_blob = self
+			.method
+			.uncompress_runtime_blob(wasm_code)
 			.map_err(|e| format!("Failed to create runtime blob: {:?}", e))?;
 
 		if let Some(version) = crate::wasm_runtime::read_embedded_version(&runtime_blob)
@@ -20,6 +20,7 @@ use assert_matches::assert_matches;
 use codec::{Decode, Encode};
 use sc_executor_common::{
 	error::Error,
+	profiler::Profiler,
 	runtime_blob::RuntimeBlob,
 	wasm_runtime::{HeapAllocStrategy, WasmModule},
 };
@@ -44,9 +45,29 @@ pub type TestExternalities = CoreTestExternalities<BlakeTwo256>;
 type HostFunctions = sp_io::SubstrateHostFunctions;
 
 /// Simple macro that runs a given method as test with the available wasm execution methods.
+///
+/// The PolkaVM variant requires the test runtime to be built for PolkaVM, i.e. with
+/// `RUNTIME_TARGET=riscv`, and is ignored by default. Run it with `cargo test -- --ignored` on
+/// such a build. Use `@compiled` for tests which rely on WASM specific behavior.
 #[macro_export]
 macro_rules! test_wasm_execution {
 	($method_name:ident) => {
+		$crate::test_wasm_execution!(@compiled $method_name);
+
+		paste::item! {
+			#[test]
+			#[ignore = "requires the test runtime to be built with `RUNTIME_TARGET=riscv`"]
+			fn [<$method_name _polkavm>]() {
+				let _ = sp_tracing::try_init_simple();
+				assert!(
+					runtime_test_is_polkavm(),
+					"the test runtime was not built for PolkaVM, set `RUNTIME_TARGET=riscv`",
+				);
+				$method_name(WasmExecutionMethod::PolkaVM);
+			}
+		}
+	};
+	(@compiled $method_name:ident) => {
 		paste::item! {
 			#[test]
 			fn [<$method_name _compiled_recreate_instance_cow>]() {
@@ -83,6 +104,12 @@ macro_rules! test_wasm_execution {
 	};
 }
 
+/// Returns `true` if the test runtime was built for PolkaVM.
+fn runtime_test_is_polkavm() -> bool {
+	RuntimeBlob::uncompress_if_needed_with_polkavm(wasm_binary_unwrap(), true)
+		.map_or(false, |blob| blob.as_polkavm_blob().is_some())
+}
+
 fn call_in_wasm<E: Externalities>(
 	function: &str,
 	call_data: &[u8],
@@ -94,7 +121,7 @@ fn call_in_wasm<E: Externalities>(
 		.build();
 
 	executor.uncached_call(
-		RuntimeBlob::uncompress_if_needed(wasm_binary_unwrap()).unwrap(),
+		execution_method.uncompress_runtime_blob(wasm_binary_unwrap()).unwrap(),
 		ext,
 		true,
 		function,
@@ -119,7 +146,7 @@ fn call_not_existing_function(wasm_method: WasmExecutionMethod) {
 	match call_in_wasm("test_calling_missing_external", &[], wasm_method, &mut ext).unwrap_err() {
 		Error::AbortedDueToTrap(error) => {
 			let expected = match wasm_method {
-				WasmExecutionMethod::Compiled { .. } =>
+				WasmExecutionMethod::Compiled { .. } | WasmExecutionMethod::PolkaVM =>
 					"call to a missing function env:missing_external",
 			};
 			assert_eq!(error.message, expected);
@@ -138,7 +165,7 @@ fn call_yet_another_not_existing_function(wasm_method: WasmExecutionMethod) {
 	{
 		Error::AbortedDueToTrap(error) => {
 			let expected = match wasm_method {
-				WasmExecutionMethod::Compiled { .. } =>
+				WasmExecutionMethod::Compiled { .. } | WasmExecutionMethod::PolkaVM =>
 					"call to a missing function env:yet_another_missing_external",
 			};
 			assert_eq!(error.message, expected);
@@ -407,7 +434,7 @@ fn offchain_http_should_work(wasm_method: WasmExecutionMethod) {
 	);
 }
 
-test_wasm_execution!(should_trap_when_heap_exhausted);
+test_wasm_execution!(@compiled should_trap_when_heap_exhausted);
 fn should_trap_when_heap_exhausted(wasm_method: WasmExecutionMethod) {
 	let mut ext = TestExternalities::default();
 
@@ -419,7 +446,7 @@ fn should_trap_when_heap_exhausted(wasm_method: WasmExecutionMethod) {
 
 	let err = executor
 		.uncached_call(
-			RuntimeBlob::uncompress_if_needed(wasm_binary_unwrap()).unwrap(),
+			wasm_method.uncompress_runtime_blob(wasm_binary_unwrap()).unwrap(),
 			&mut ext.ext(),
 			true,
 			"test_allocate_vec",
@@ -444,7 +471,8 @@ fn mk_test_runtime(
 	wasm_method: WasmExecutionMethod,
 	pages: HeapAllocStrategy,
 ) -> Box<dyn WasmModule> {
-	let blob = RuntimeBlob::uncompress_if_needed(wasm_binary_unwrap())
+	let blob = wasm_method
+		.uncompress_runtime_blob(wasm_binary_unwrap())
 		.expect("failed to create a runtime blob out of test runtime");
 
 	crate::wasm_runtime::create_wasm_runtime_with_code::<HostFunctions>(
@@ -493,7 +521,7 @@ fn returns_mutable_static_bss(wasm_method: WasmExecutionMethod) {
 // returned to its initial value and thus the stack space is going to be leaked.
 //
 // See https://github.com/paritytech/substrate/issues/2967 for details
-test_wasm_execution!(restoration_of_globals);
+test_wasm_execution!(@compiled restoration_of_globals);
 fn restoration_of_globals(wasm_method: WasmExecutionMethod) {
 	// Allocate 32 pages (of 65536 bytes) which gives the runtime 2048KB of heap to operate on
 	// (plus some additional space unused from the initial pages requested by the wasm runtime
@@ -534,7 +562,7 @@ fn parallel_execution(wasm_method: WasmExecutionMethod) {
 				assert_eq!(
 					executor
 						.uncached_call(
-							RuntimeBlob::uncompress_if_needed(wasm_binary_unwrap()).unwrap(),
+							wasm_method.uncompress_runtime_blob(wasm_binary_unwrap()).unwrap(),
 							&mut ext,
 							true,
 							"test_twox_128",
@@ -604,7 +632,7 @@ fn wasm_tracing_should_work(wasm_method: WasmExecutionMethod) {
 	assert_eq!(len, 2);
 }
 
-test_wasm_execution!(allocate_two_gigabyte);
+test_wasm_execution!(@compiled allocate_two_gigabyte);
 fn allocate_two_gigabyte(wasm_method: WasmExecutionMethod) {
 	let runtime = mk_test_runtime(wasm_method, HeapAllocStrategy::Dynamic { maximum_pages: None });
 
@@ -613,7 +641,7 @@ fn allocate_two_gigabyte(wasm_method: WasmExecutionMethod) {
 	assert_eq!(10 * 1024 * 1024 * 205, u32::decode(&mut &res[..]).unwrap());
 }
 
-test_wasm_execution!(memory_is_cleared_between_invocations);
+test_wasm_execution!(@compiled memory_is_cleared_between_invocations);
 fn memory_is_cleared_between_invocations(wasm_method: WasmExecutionMethod) {
 	// This is based on the code generated by compiling a runtime *without*
 	// the `-C link-arg=--import-memory` using the following code and then
@@ -730,6 +758,8 @@ fn unreachable_intrinsic(wasm_method: WasmExecutionMethod) {
 			let expected = match wasm_method {
 				WasmExecutionMethod::Compiled { .. } =>
 					"wasm trap: wasm `unreachable` instruction executed",
+				WasmExecutionMethod::PolkaVM =>
+					"call into the runtime method 'test_unreachable_intrinsic' failed: trap",
 			};
 			assert_eq!(error.message, expected);
 		},
@@ -748,7 +778,7 @@ fn return_value(wasm_method: WasmExecutionMethod) {
 	);
 }
 
-test_wasm_execution!(return_huge_len);
+test_wasm_execution!(@compiled return_huge_len);
 fn return_huge_len(wasm_method: WasmExecutionMethod) {
 	let mut ext = TestExternalities::default();
 	let mut ext = ext.ext();
@@ -761,7 +791,7 @@ fn return_huge_len(wasm_method: WasmExecutionMethod) {
 	}
 }
 
-test_wasm_execution!(return_max_memory_offset);
+test_wasm_execution!(@compiled return_max_memory_offset);
 fn return_max_memory_offset(wasm_method: WasmExecutionMethod) {
 	let mut ext = TestExternalities::default();
 	let mut ext = ext.ext();
@@ -772,7 +802,7 @@ fn return_max_memory_offset(wasm_method: WasmExecutionMethod) {
 	);
 }
 
-test_wasm_execution!(return_max_memory_offset_plus_one);
+test_wasm_execution!(@compiled return_max_memory_offset_plus_one);
 fn return_max_memory_offset_plus_one(wasm_method: WasmExecutionMethod) {
 	let mut ext = TestExternalities::default();
 	let mut ext = ext.ext();
@@ -787,7 +817,7 @@ fn return_max_memory_offset_plus_one(wasm_method: WasmExecutionMethod) {
 	}
 }
 
-test_wasm_execution!(return_overflow);
+test_wasm_execution!(@compiled return_overflow);
 fn return_overflow(wasm_method: WasmExecutionMethod) {
 	let mut ext = TestExternalities::default();
 	let mut ext = ext.ext();
@@ -799,3 +829,46 @@ fn return_overflow(wasm_method: WasmExecutionMethod) {
 		error => panic!("unexpected error: {:?}", error),
 	}
 }
+
+test_wasm_execution!(@compiled profiler_records_host_calls_and_samples);
+fn profiler_records_host_calls_and_samples(wasm_method: WasmExecutionMethod) {
+	let profiler = Arc::new(Profiler::new(std::time::Duration::from_micros(100)));
+	let runtime = crate::wasm_runtime::create_wasm_runtime_with_code_and_profiler::<HostFunctions>(
+		wasm_method,
+		HeapAllocStrategy::Static { extra_pages: 1024 },
+		RuntimeBlob::uncompress_if_needed(wasm_binary_unwrap()).unwrap(),
+		true,
+		None,
+		Some(profiler.clone()),
+	)
+	.unwrap();
+	let mut instance = runtime.new_instance().unwrap();
+
+	// Decoding the input copies it inside of the runtime, which keeps the wasm code busy for
+	// long enough to be sampled.
+	let input = vec![1u8; 4 * 1024 * 1024].encode();
+	let host_frame = "host:ext_hashing_blake2_256_version_1".to_string();
+	let mut sampled = false;
+	for _ in 0..50 {
+		let output = instance.call_export("test_blake2_256", &input).unwrap();
+		assert_eq!(output, blake2_256(&vec![1u8; 4 * 1024 * 1024]).to_vec().encode());
+
+		let profiles = profiler.take_profiles();
+		assert_eq!(profiles.len(), 1);
+		let profile = &profiles[0];
+		assert_eq!(profile.method, "test_blake2_256");
+		assert_eq!(profile.host_functions["ext_hashing_blake2_256_version_1"].calls, 1);
+		assert!(profile.stacks.keys().any(|stack| stack.last() == Some(&host_frame)));
+		assert!(profile.host_time() <= profile.total_time);
+
+		if profile
+			.stacks
+			.keys()
+			.any(|stack| !stack.is_empty() && !stack.last().unwrap().starts_with("host:"))
+		{
+			sampled = true;
+			break
+		}
+	}
+	assert!(sampled, "the wasm call stack was never sampled");
+}
@@ -50,6 +50,7 @@ pub use wasm_runtime::{read_embedded_version, WasmExecutionMethod};
 
 pub use sc_executor_common::{
 	error,
+	profiler::{CallProfile, HostFunctionStats, Profiler},
 	wasm_runtime::{HeapAllocStrategy, DEFAULT_HEAP_ALLOC_PAGES, DEFAULT_HEAP_ALLOC_STRATEGY},
 };
 pub use sc_executor_wasmtime::InstantiationStrategy as WasmtimeInstantiationStrategy;
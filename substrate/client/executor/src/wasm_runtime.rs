@@ -26,6 +26,7 @@ use crate::error::{Error, WasmError};
 use codec::Decode;
 use parking_lot::Mutex;
 use sc_executor_common::{
+	profiler::Profiler,
 	runtime_blob::RuntimeBlob,
 	wasm_runtime::{HeapAllocStrategy, WasmInstance, WasmModule},
 };
@@ -48,6 +49,24 @@ pub enum WasmExecutionMethod {
 		/// The instantiation strategy to use.
 		instantiation_strategy: sc_executor_wasmtime::InstantiationStrategy,
 	},
+	/// Uses the PolkaVM executor.
+	///
+	/// Only PolkaVM runtime blobs can be executed with this method. This is experimental and
+	/// must not be used in production.
+	PolkaVM,
+}
+
+impl WasmExecutionMethod {
+	/// Decompress the given runtime `code` into a [`RuntimeBlob`] executable by this method.
+	///
+	/// PolkaVM runtime blobs are always accepted by [`Self::PolkaVM`], while [`Self::Compiled`]
+	/// only accepts them if the `SUBSTRATE_ENABLE_POLKAVM` environment variable is set to `1`.
+	pub(crate) fn uncompress_runtime_blob(&self, code: &[u8]) -> Result<RuntimeBlob, WasmError> {
+		match self {
+			Self::Compiled { .. } => RuntimeBlob::uncompress_if_needed(code),
+			Self::PolkaVM => RuntimeBlob::uncompress_if_needed_with_polkavm(code, true),
+		}
+	}
 }
 
 impl Default for WasmExecutionMethod {
@@ -167,6 +186,7 @@ pub struct RuntimeCache {
 	/// The size of the instances cache for each runtime.
 	max_runtime_instances: usize,
 	cache_path: Option<PathBuf>,
+	profiler: Option<Arc<Profiler>>,
 }
 
 impl RuntimeCache {
@@ -186,7 +206,18 @@ impl RuntimeCache {
 		runtime_cache_size: u8,
 	) -> RuntimeCache {
 		let cap = ByLength::new(runtime_cache_size.max(1) as u32);
-		RuntimeCache { runtimes: Mutex::new(LruMap::new(cap)), max_runtime_instances, cache_path }
+		RuntimeCache {
+			runtimes: Mutex::new(LruMap::new(cap)),
+			max_runtime_instances,
+			cache_path,
+			profiler: None,
+		}
+	}
+
+	/// Record a profile of every call into the runtimes created by this cache.
+	pub fn with_profiler(mut self, profiler: Option<Arc<Profiler>>) -> Self {
+		self.profiler = profiler;
+		self
 	}
 
 	/// Prepares a WASM module instance and executes given function for it.
@@ -255,6 +286,7 @@ impl RuntimeCache {
 				allow_missing_func_imports,
 				self.max_runtime_instances,
 				self.cache_path.as_deref(),
+				self.profiler.clone(),
 			);
 
 			match result {
@@ -294,34 +326,63 @@ pub fn create_wasm_runtime_with_code<H>(
 	allow_missing_func_imports: bool,
 	cache_path: Option<&Path>,
 ) -> Result<Box<dyn WasmModule>, WasmError>
+where
+	H: HostFunctions,
+{
+	create_wasm_runtime_with_code_and_profiler::<H>(
+		wasm_method,
+		heap_alloc_strategy,
+		blob,
+		allow_missing_func_imports,
+		cache_path,
+		None,
+	)
+}
+
+/// The same as [`create_wasm_runtime_with_code`] but records a profile of every call into the
+/// runtime into `profiler`, if given.
+pub(crate) fn create_wasm_runtime_with_code_and_profiler<H>(
+	wasm_method: WasmExecutionMethod,
+	heap_alloc_strategy: HeapAllocStrategy,
+	blob: RuntimeBlob,
+	allow_missing_func_imports: bool,
+	cache_path: Option<&Path>,
+	profiler: Option<Arc<Profiler>>,
+) -> Result<Box<dyn WasmModule>, WasmError>
 where
 	H: HostFunctions,
 {
 	if let Some(blob) = blob.as_polkavm_blob() {
-		return sc_executor_polkavm::create_runtime::<H>(blob);
+		return sc_executor_polkavm::create_runtime::<H>(blob, allow_missing_func_imports);
 	}
 
 	match wasm_method {
-		WasmExecutionMethod::Compiled { instantiation_strategy } =>
-			sc_executor_wasmtime::create_runtime::<H>(
-				blob,
-				sc_executor_wasmtime::Config {
-					allow_missing_func_imports,
-					cache_path: cache_path.map(ToOwned::to_owned),
-					semantics: sc_executor_wasmtime::Semantics {
-						heap_alloc_strategy,
-						instantiation_strategy,
-						deterministic_stack_limit: None,
-						canonicalize_nans: false,
-						parallel_compilation: true,
-						wasm_multi_value: false,
-						wasm_bulk_memory: false,
-						wasm_reference_types: false,
-						wasm_simd: false,
-					},
+		WasmExecutionMethod::Compiled { instantiation_strategy } => {
+			let config = sc_executor_wasmtime::Config {
+				allow_missing_func_imports,
+				cache_path: cache_path.map(ToOwned::to_owned),
+				semantics: sc_executor_wasmtime::Semantics {
+					heap_alloc_strategy,
+					instantiation_strategy,
+					deterministic_stack_limit: None,
+					canonicalize_nans: false,
+					parallel_compilation: true,
+					wasm_multi_value: false,
+					wasm_bulk_memory: false,
+					wasm_reference_types: false,
+					wasm_simd: false,
 				},
-			)
-			.map(|runtime| -> Box<dyn WasmModule> { Box::new(runtime) }),
+			};
+			match profiler {
+				Some(profiler) =>
+					sc_executor_wasmtime::create_runtime_with_profiler::<H>(blob, config, profiler),
+				None => sc_executor_wasmtime::create_runtime::<H>(blob, config),
+			}
+			.map(|runtime| -> Box<dyn WasmModule> { Box::new(runtime) })
+		},
+		WasmExecutionMethod::PolkaVM => Err(WasmError::Other(
+			"expected a PolkaVM runtime blob, found a WASM runtime blob".into(),
+		)),
 	}
 }
 
@@ -389,25 +450,27 @@ fn create_versioned_wasm_runtime<H>(
 	allow_missing_func_imports: bool,
 	max_instances: usize,
 	cache_path: Option<&Path>,
+	profiler: Option<Arc<Profiler>>,
 ) -> Result<VersionedRuntime, WasmError>
 where
 	H: HostFunctions,
 {
 	// The incoming code may be actually compressed. We decompress it here and then work with
 	// the uncompressed code from now on.
-	let blob = sc_executor_common::runtime_blob::RuntimeBlob::uncompress_if_needed(code)?;
+	let blob = wasm_method.uncompress_runtime_blob(code)?;
 
 	// Use the runtime blob to scan if there is any metadata embedded into the wasm binary
 	// pertaining to runtime version. We do it before consuming the runtime blob for creating the
 	// runtime.
 	let mut version = read_embedded_version(&blob)?;
 
-	let runtime = create_wasm_runtime_with_code::<H>(
+	let runtime = create_wasm_runtime_with_code_and_profiler::<H>(
 		wasm_method,
 		heap_alloc_strategy,
 		blob,
 		allow_missing_func_imports,
 		cache_path,
+		profiler,
 	)?;
 
 	// If the runtime blob doesn't embed the runtime version then use the legacy version query
@@ -22,6 +22,7 @@
 use wasmtime::Caller;
 
 use sc_allocator::{AllocationStats, FreeingBumpHeapAllocator};
+use sc_executor_common::profiler::CallProfile;
 use sp_wasm_interface::{Pointer, WordSize};
 
 use crate::{instance_wrapper::MemoryWrapper, runtime::StoreData, util};
@@ -37,12 +38,14 @@ pub struct HostState {
 	/// once.
 	allocator: Option<FreeingBumpHeapAllocator>,
 	panic_message: Option<String>,
+	/// The profile of the ongoing call, only set if profiling is enabled.
+	pub(crate) profile: Option<CallProfile>,
 }
 
 impl HostState {
 	/// Constructs a new `HostState`.
 	pub fn new(allocator: FreeingBumpHeapAllocator) -> Self {
-		HostState { allocator: Some(allocator), panic_message: None }
+		HostState { allocator: Some(allocator), panic_message: None, profile: None }
 	}
 
 	/// Takes the error message out of the host state, leaving a `None` in its place.
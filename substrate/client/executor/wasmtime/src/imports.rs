@@ -19,7 +19,7 @@
 use crate::{host::HostContext, runtime::StoreData};
 use sc_executor_common::error::WasmError;
 use sp_wasm_interface::{FunctionContext, HostFunctions};
-use std::collections::HashMap;
+use std::{collections::HashMap, time::Instant};
 use wasmtime::{ExternType, FuncType, ImportType, Linker, Module};
 
 /// Goes over all imports of a module and prepares the given linker for instantiation of the module.
@@ -104,6 +104,38 @@ impl<'a, 'b> sp_wasm_interface::HostFunctionRegistry for Registry<'a, 'b> {
 		callback(&mut HostContext { caller })
 	}
 
+	fn with_named_function_context<R>(
+		caller: wasmtime::Caller<Self::State>,
+		fn_name: &'static str,
+		callback: impl FnOnce(&mut dyn FunctionContext) -> R,
+	) -> R {
+		let profiling = caller
+			.data()
+			.host_state
+			.as_ref()
+			.map_or(false, |host_state| host_state.profile.is_some());
+		if !profiling {
+			return Self::with_function_context(caller, callback)
+		}
+
+		let stack = crate::profiling::wasm_stack(&caller);
+		let mut context = HostContext { caller };
+		let started = Instant::now();
+		let result = callback(&mut context);
+		let elapsed = started.elapsed();
+
+		if let Some(profile) = context
+			.caller
+			.data_mut()
+			.host_state_mut()
+			.and_then(|host_state| host_state.profile.as_mut())
+		{
+			profile.record_host_call(fn_name, stack, elapsed);
+		}
+
+		result
+	}
+
 	fn register_static<Params, Results>(
 		&mut self,
 		fn_name: &str,
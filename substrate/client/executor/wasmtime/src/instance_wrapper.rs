@@ -126,9 +126,13 @@ impl InstanceWrapper {
 		engine: &Engine,
 		instance_pre: &InstancePre<StoreData>,
 		instance_counter: Arc<InstanceCounter>,
+		profiling: bool,
 	) -> Result<Self> {
 		let _release_instance_handle = instance_counter.acquire_instance();
 		let mut store = Store::new(engine, Default::default());
+		if profiling {
+			crate::profiling::enable_sampling(&mut store);
+		}
 		let instance = instance_pre.instantiate(&mut store).map_err(|error| {
 			WasmError::Other(format!(
 				"failed to instantiate a new WASM module instance: {:#}",
@@ -30,6 +30,7 @@
 mod host;
 mod imports;
 mod instance_wrapper;
+mod profiling;
 mod runtime;
 mod util;
 
@@ -38,8 +39,8 @@ mod tests;
 
 pub use runtime::{
 	create_runtime, create_runtime_from_artifact, create_runtime_from_artifact_bytes,
-	prepare_runtime_artifact, Config, DeterministicStackLimit, InstantiationStrategy, Semantics,
-	WasmtimeRuntime,
+	create_runtime_with_profiler, prepare_runtime_artifact, Config, DeterministicStackLimit,
+	InstantiationStrategy, Semantics, WasmtimeRuntime,
 };
 pub use sc_executor_common::{
 	runtime_blob::RuntimeBlob,
@@ -0,0 +1,104 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+//! Sampling of the wasm call stack for [`Profiler`](sc_executor_common::profiler::Profiler).
+//!
+//! Sampling relies on wasmtime's epoch interruption: a background thread bumps the engine's
+//! epoch every sample interval and the deadline callback installed on the store records the
+//! current wasm call stack into the profile of the ongoing call.
+
+use std::{
+	sync::{
+		atomic::{AtomicBool, Ordering},
+		Arc,
+	},
+	thread::JoinHandle,
+	time::Duration,
+};
+
+use wasmtime::{AsContext, Engine, WasmBacktrace};
+
+use crate::runtime::Store;
+
+/// Returns the current wasm call stack, outermost frame first.
+pub(crate) fn wasm_stack(ctx: impl AsContext) -> Vec<String> {
+	let backtrace = WasmBacktrace::capture(ctx);
+	backtrace
+		.frames()
+		.iter()
+		.rev()
+		.map(|frame| match frame.func_name() {
+			Some(name) => name.to_owned(),
+			None => format!("wasm-function[{}]", frame.func_index()),
+		})
+		.collect()
+}
+
+/// Installs the epoch deadline callback that samples the wasm call stack.
+pub(crate) fn enable_sampling(store: &mut Store) {
+	store.epoch_deadline_callback(|mut ctx| {
+		let stack = wasm_stack(&ctx);
+		if let Some(profile) = ctx
+			.data_mut()
+			.host_state_mut()
+			.and_then(|host_state| host_state.profile.as_mut())
+		{
+			profile.record_sample(stack);
+		}
+
+		// Sample again on the next tick.
+		Ok(1)
+	});
+	store.set_epoch_deadline(1);
+}
+
+/// Bumps the epoch of an engine at a regular interval, until dropped.
+pub(crate) struct EpochTicker {
+	stop: Arc<AtomicBool>,
+	handle: Option<JoinHandle<()>>,
+}
+
+impl EpochTicker {
+	/// Starts incrementing the epoch of `engine` every `interval`.
+	pub(crate) fn start(engine: Engine, interval: Duration) -> Self {
+		let stop = Arc::new(AtomicBool::new(false));
+		let handle = std::thread::Builder::new()
+			.name("wasmtime-profiler".into())
+			.spawn({
+				let stop = stop.clone();
+				move || {
+					while !stop.load(Ordering::Relaxed) {
+						std::thread::sleep(interval);
+						engine.increment_epoch();
+					}
+				}
+			})
+			.map_err(|error| log::warn!("Failed to start the profiler sampling thread: {}", error))
+			.ok();
+
+		Self { stop, handle }
+	}
+}
+
+impl Drop for EpochTicker {
+	fn drop(&mut self) {
+		self.stop.store(true, Ordering::Relaxed);
+		if let Some(handle) = self.handle.take() {
+			let _ = handle.join();
+		}
+	}
+}
@@ -21,6 +21,7 @@
 use crate::{
 	host::HostState,
 	instance_wrapper::{EntryPoint, InstanceWrapper, MemoryWrapper},
+	profiling::EpochTicker,
 	util::{self, replace_strategy_if_broken},
 };
 
@@ -28,6 +29,7 @@ use parking_lot::Mutex;
 use sc_allocator::{AllocationStats, FreeingBumpHeapAllocator};
 use sc_executor_common::{
 	error::{Error, Result, WasmError},
+	profiler::{CallProfile, Profiler},
 	runtime_blob::RuntimeBlob,
 	util::checked_range,
 	wasm_runtime::{HeapAllocStrategy, WasmInstance, WasmModule},
@@ -75,11 +77,17 @@ struct InstanceCreator {
 	engine: Engine,
 	instance_pre: Arc<wasmtime::InstancePre<StoreData>>,
 	instance_counter: Arc<InstanceCounter>,
+	profiler: Option<Arc<Profiler>>,
 }
 
 impl InstanceCreator {
 	fn instantiate(&mut self) -> Result<InstanceWrapper> {
-		InstanceWrapper::new(&self.engine, &self.instance_pre, self.instance_counter.clone())
+		InstanceWrapper::new(
+			&self.engine,
+			&self.instance_pre,
+			self.instance_counter.clone(),
+			self.profiler.is_some(),
+		)
 	}
 }
 
@@ -137,6 +145,7 @@ pub struct WasmtimeRuntime {
 	instance_pre: Arc<wasmtime::InstancePre<StoreData>>,
 	instantiation_strategy: InternalInstantiationStrategy,
 	instance_counter: Arc<InstanceCounter>,
+	profiler: Option<Arc<Profiler>>,
 }
 
 impl WasmModule for WasmtimeRuntime {
@@ -146,6 +155,7 @@ impl WasmModule for WasmtimeRuntime {
 				engine: self.engine.clone(),
 				instance_pre: self.instance_pre.clone(),
 				instance_counter: self.instance_counter.clone(),
+				profiler: self.profiler.clone(),
 			}),
 		};
 
@@ -173,7 +183,33 @@ impl WasmtimeInstance {
 				let entrypoint = instance_wrapper.resolve_entrypoint(method)?;
 				let allocator = FreeingBumpHeapAllocator::new(heap_base);
 
-				perform_call(data, &mut instance_wrapper, entrypoint, allocator, allocation_stats)
+				let Some(profiler) = instance_creator.profiler.clone() else {
+					return perform_call(
+						data,
+						&mut instance_wrapper,
+						entrypoint,
+						allocator,
+						allocation_stats,
+						&mut None,
+					)
+				};
+
+				let _ticker =
+					EpochTicker::start(instance_creator.engine.clone(), profiler.sample_interval());
+				let mut profile = Some(CallProfile::start(method));
+				let result = perform_call(
+					data,
+					&mut instance_wrapper,
+					entrypoint,
+					allocator,
+					allocation_stats,
+					&mut profile,
+				);
+				if let Some(profile) = profile {
+					profiler.add_profile(profile.finish());
+				}
+
+				result
 			},
 		}
 	}
@@ -506,7 +542,23 @@ where
 	H: HostFunctions,
 {
 	// SAFETY: this is safe because it doesn't use `CodeSupplyMode::Precompiled`.
-	unsafe { do_create_runtime::<H>(CodeSupplyMode::Fresh(blob), config) }
+	unsafe { do_create_runtime::<H>(CodeSupplyMode::Fresh(blob), config, None) }
+}
+
+/// The same as [`create_runtime`] but records a profile of every runtime call into `profiler`.
+///
+/// This samples the wasm call stack using epoch interruption, which makes the compiled code
+/// slower. It should not be used on nodes following the chain.
+pub fn create_runtime_with_profiler<H>(
+	blob: RuntimeBlob,
+	config: Config,
+	profiler: Arc<Profiler>,
+) -> std::result::Result<WasmtimeRuntime, WasmError>
+where
+	H: HostFunctions,
+{
+	// SAFETY: this is safe because it doesn't use `CodeSupplyMode::Precompiled`.
+	unsafe { do_create_runtime::<H>(CodeSupplyMode::Fresh(blob), config, Some(profiler)) }
 }
 
 /// The same as [`create_runtime`] but takes a path to a precompiled artifact,
@@ -532,7 +584,7 @@ pub unsafe fn create_runtime_from_artifact<H>(
 where
 	H: HostFunctions,
 {
-	do_create_runtime::<H>(CodeSupplyMode::Precompiled(compiled_artifact_path), config)
+	do_create_runtime::<H>(CodeSupplyMode::Precompiled(compiled_artifact_path), config, None)
 }
 
 /// The same as [`create_runtime`] but takes the bytes of a precompiled artifact,
@@ -557,7 +609,7 @@ pub unsafe fn create_runtime_from_artifact_bytes<H>(
 where
 	H: HostFunctions,
 {
-	do_create_runtime::<H>(CodeSupplyMode::PrecompiledBytes(compiled_artifact_bytes), config)
+	do_create_runtime::<H>(CodeSupplyMode::PrecompiledBytes(compiled_artifact_bytes), config, None)
 }
 
 /// # Safety
@@ -567,6 +619,7 @@ where
 unsafe fn do_create_runtime<H>(
 	code_supply_mode: CodeSupplyMode<'_>,
 	mut config: Config,
+	profiler: Option<Arc<Profiler>>,
 ) -> std::result::Result<WasmtimeRuntime, WasmError>
 where
 	H: HostFunctions,
@@ -574,6 +627,9 @@ where
 	replace_strategy_if_broken(&mut config.semantics.instantiation_strategy);
 
 	let mut wasmtime_config = common_config(&config.semantics)?;
+	if profiler.is_some() {
+		wasmtime_config.epoch_interruption(true);
+	}
 	if let Some(ref cache_path) = config.cache_path {
 		if let Err(reason) = setup_wasmtime_caching(cache_path, &mut wasmtime_config) {
 			log::warn!(
@@ -636,6 +692,7 @@ where
 		instance_pre: Arc::new(instance_pre),
 		instantiation_strategy,
 		instance_counter: Default::default(),
+		profiler,
 	})
 }
 
@@ -682,10 +739,12 @@ fn perform_call(
 	entrypoint: EntryPoint,
 	mut allocator: FreeingBumpHeapAllocator,
 	allocation_stats: &mut Option<AllocationStats>,
+	profile: &mut Option<CallProfile>,
 ) -> Result<Vec<u8>> {
 	let (data_ptr, data_len) = inject_input_data(instance_wrapper, &mut allocator, data)?;
 
-	let host_state = HostState::new(allocator);
+	let mut host_state = HostState::new(allocator);
+	host_state.profile = profile.take();
 
 	// Set the host state before calling into wasm.
 	instance_wrapper.store_mut().data_mut().host_state = Some(host_state);
@@ -695,10 +754,11 @@ fn perform_call(
 		.map(unpack_ptr_and_len);
 
 	// Reset the host state
-	let host_state = instance_wrapper.store_mut().data_mut().host_state.take().expect(
+	let mut host_state = instance_wrapper.store_mut().data_mut().host_state.take().expect(
 		"the host state is always set before calling into WASM so it can't be None here; qed",
 	);
 	*allocation_stats = Some(host_state.allocation_stats());
+	*profile = host_state.profile.take();
 
 	let (output_ptr, output_len) = ret?;
 	let output = extract_output_data(instance_wrapper, output_ptr, output_len)?;
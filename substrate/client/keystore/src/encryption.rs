@@ -0,0 +1,209 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+//
+//! Encryption at rest of the key files of a [`LocalKeystore`](crate::LocalKeystore).
+//!
+//! An encrypted keystore contains an [`HEADER_FILE`] storing the parameters used to derive the
+//! encryption key from the encryption password with scrypt. The key is derived once when the
+//! keystore is opened, and every key file is then encrypted on its own with AES-256-GCM, using a
+//! random nonce and the name of the key file as associated data, so that key files can't be
+//! swapped.
+
+use aes_gcm::{
+	aead::{Aead, Payload},
+	Aes256Gcm, KeyInit, Nonce,
+};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sp_core::crypto::{ExposeSecret, SecretString};
+use std::{
+	fs::{self, File},
+	io::{self, Write},
+	path::Path,
+};
+use zeroize::Zeroizing;
+
+use crate::{Error, Result};
+
+/// Name of the file storing the encryption parameters of an encrypted keystore.
+pub(crate) const HEADER_FILE: &str = "encryption.json";
+
+/// Version of the encryption format.
+const VERSION: u32 = 1;
+
+/// Plaintext encrypted in the header, used to check the encryption password.
+const CHECK_PLAINTEXT: &[u8] = b"substrate-keystore";
+
+const SCRYPT_LOG_N: u8 = 15;
+const SCRYPT_R: u32 = 8;
+const SCRYPT_P: u32 = 1;
+const NONCE_LEN: usize = 12;
+const SALT_LEN: usize = 32;
+
+/// Content of a key file.
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+pub(crate) enum KeyFile {
+	/// The secret uri stored as plaintext.
+	Plain(String),
+	/// The encrypted secret uri.
+	Encrypted(EncryptedData),
+}
+
+/// Data encrypted with AES-256-GCM.
+#[derive(Serialize, Deserialize)]
+pub(crate) struct EncryptedData {
+	/// Hex encoded nonce.
+	nonce: String,
+	/// Hex encoded ciphertext, including the authentication tag.
+	ciphertext: String,
+}
+
+/// Parameters of the key derivation function.
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "function", rename_all = "lowercase")]
+enum Kdf {
+	Scrypt {
+		log_n: u8,
+		r: u32,
+		p: u32,
+		/// Hex encoded salt.
+		salt: String,
+	},
+}
+
+/// Content of the [`HEADER_FILE`].
+#[derive(Serialize, Deserialize)]
+struct Header {
+	version: u32,
+	kdf: Kdf,
+	/// [`CHECK_PLAINTEXT`] encrypted with the derived key.
+	check: EncryptedData,
+}
+
+/// Cipher used to encrypt and decrypt the key files of a keystore.
+pub(crate) struct KeyCipher(Aes256Gcm);
+
+impl KeyCipher {
+	/// Returns `true` if the keystore at `dir` is encrypted.
+	pub(crate) fn is_encrypted(dir: &Path) -> bool {
+		dir.join(HEADER_FILE).exists()
+	}
+
+	/// Turn the keystore at `dir` into an encrypted keystore, using `password` to derive the
+	/// encryption key.
+	///
+	/// Existing key files are left untouched.
+	pub(crate) fn create(dir: &Path, password: &SecretString) -> Result<Self> {
+		let mut salt = [0; SALT_LEN];
+		rand::thread_rng().fill_bytes(&mut salt);
+		let kdf = Kdf::Scrypt {
+			log_n: SCRYPT_LOG_N,
+			r: SCRYPT_R,
+			p: SCRYPT_P,
+			salt: array_bytes::bytes2hex("", salt),
+		};
+
+		let cipher = Self::derive(&kdf, password)?;
+		let check = cipher.encrypt(HEADER_FILE.as_bytes(), CHECK_PLAINTEXT)?;
+		let header = Header { version: VERSION, kdf, check };
+
+		let tmp = dir.join(format!("{HEADER_FILE}.tmp"));
+		write_private_file(&tmp, &serde_json::to_vec(&header)?)?;
+		fs::rename(tmp, dir.join(HEADER_FILE))?;
+
+		Ok(cipher)
+	}
+
+	/// Load the cipher of the encrypted keystore at `dir`, checking `password` is correct.
+	///
+	/// Returns `Ok(None)` if the keystore isn't encrypted.
+	pub(crate) fn load(dir: &Path, password: &SecretString) -> Result<Option<Self>> {
+		let path = dir.join(HEADER_FILE);
+		if !path.exists() {
+			return Ok(None)
+		}
+
+		let header: Header = serde_json::from_reader(File::open(path)?)?;
+		if header.version != VERSION {
+			return Err(Error::Encryption)
+		}
+
+		let cipher = Self::derive(&header.kdf, password)?;
+		match cipher.decrypt(HEADER_FILE.as_bytes(), &header.check) {
+			Ok(check) if check == CHECK_PLAINTEXT => Ok(Some(cipher)),
+			_ => Err(Error::InvalidEncryptionPassword),
+		}
+	}
+
+	fn derive(kdf: &Kdf, password: &SecretString) -> Result<Self> {
+		let Kdf::Scrypt { log_n, r, p, salt } = kdf;
+		let salt = array_bytes::hex2bytes(salt).map_err(|_| Error::Encryption)?;
+		let params = scrypt::Params::new(*log_n, *r, *p, 32).map_err(|_| Error::Encryption)?;
+
+		let mut key = Zeroizing::new([0; 32]);
+		scrypt::scrypt(password.expose_secret().as_bytes(), &salt, &params, &mut key[..])
+			.map_err(|_| Error::Encryption)?;
+
+		Ok(Self(Aes256Gcm::new(key.as_ref().into())))
+	}
+
+	/// Encrypt `plaintext`, binding it to `aad`.
+	pub(crate) fn encrypt(&self, aad: &[u8], plaintext: &[u8]) -> Result<EncryptedData> {
+		let mut nonce = [0; NONCE_LEN];
+		rand::thread_rng().fill_bytes(&mut nonce);
+
+		let ciphertext = self
+			.0
+			.encrypt(Nonce::from_slice(&nonce), Payload { msg: plaintext, aad })
+			.map_err(|_| Error::Encryption)?;
+
+		Ok(EncryptedData {
+			nonce: array_bytes::bytes2hex("", nonce),
+			ciphertext: array_bytes::bytes2hex("", ciphertext),
+		})
+	}
+
+	/// Decrypt `data`, which must have been encrypted with the same `aad`.
+	pub(crate) fn decrypt(&self, aad: &[u8], data: &EncryptedData) -> Result<Vec<u8>> {
+		let nonce = array_bytes::hex2bytes(&data.nonce).map_err(|_| Error::Encryption)?;
+		let ciphertext = array_bytes::hex2bytes(&data.ciphertext).map_err(|_| Error::Encryption)?;
+		if nonce.len() != NONCE_LEN {
+			return Err(Error::Encryption)
+		}
+
+		self.0
+			.decrypt(Nonce::from_slice(&nonce), Payload { msg: &ciphertext, aad })
+			.map_err(|_| Error::Encryption)
+	}
+}
+
+/// Write `data` to a new file at `path`, only readable by its owner.
+pub(crate) fn write_private_file(path: &Path, data: &[u8]) -> io::Result<()> {
+	let mut file = File::create(path)?;
+
+	#[cfg(target_family = "unix")]
+	{
+		use std::os::unix::fs::PermissionsExt;
+		file.set_permissions(fs::Permissions::from_mode(0o600))?;
+	}
+
+	file.write_all(data)?;
+	file.sync_all()?;
+	Ok(())
+}
@@ -23,9 +23,17 @@ use sp_core::crypto::KeyTypeId;
 use sp_keystore::Error as TraitError;
 use std::io;
 
+/// Encryption of the local keystore at rest
+mod encryption;
 /// Local keystore implementation
 mod local;
 pub use local::LocalKeystore;
+/// Remote keystore implementation
+pub mod remote;
+pub use remote::RemoteKeystore;
+/// Slashing protection of validator signatures
+pub mod slashing_protection;
+pub use slashing_protection::{SlashingProtection, SlashingProtectionPtr};
 pub use sp_keystore::Keystore;
 
 /// Keystore error.
@@ -55,6 +63,20 @@ pub enum Error {
 	/// Keystore unavailable
 	#[error("Keystore unavailable")]
 	Unavailable,
+	/// The keystore is encrypted, but no encryption password was given.
+	#[error("Keystore is encrypted, an encryption password is required to unlock it")]
+	Locked,
+	/// Invalid encryption password.
+	#[error("Invalid keystore encryption password")]
+	InvalidEncryptionPassword,
+	/// The keystore contains key files which are not encrypted.
+	#[error(
+		"Keystore contains unencrypted keys, run `key encrypt-keystore` to encrypt them first"
+	)]
+	NotEncrypted,
+	/// A key file couldn't be encrypted or decrypted.
+	#[error("Failed to encrypt or decrypt a key file")]
+	Encryption,
 }
 
 /// Keystore Result
@@ -66,7 +88,10 @@ impl From<Error> for TraitError {
 			Error::KeyNotSupported(id) => TraitError::KeyNotSupported(id),
 			Error::InvalidSeed | Error::InvalidPhrase | Error::PublicKeyMismatch =>
 				TraitError::ValidationError(error.to_string()),
-			Error::Unavailable => TraitError::Unavailable,
+			Error::Unavailable | Error::Locked => TraitError::Unavailable,
+			Error::InvalidEncryptionPassword | Error::NotEncrypted =>
+				TraitError::ValidationError(error.to_string()),
+			Error::Encryption => TraitError::Other(error.to_string()),
 			Error::Io(e) => TraitError::Other(e.to_string()),
 			Error::Json(e) => TraitError::Other(e.to_string()),
 		}
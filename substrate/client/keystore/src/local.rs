@@ -28,8 +28,7 @@ use sp_keystore::{Error as TraitError, Keystore, KeystorePtr};
 use std::{
 	collections::HashMap,
 	fs::{self, File},
-	io::Write,
-	path::PathBuf,
+	path::{Path, PathBuf},
 	sync::Arc,
 };
 
@@ -41,7 +40,10 @@ sp_keystore::bls_experimental_enabled! {
 use sp_core::{bls381, ecdsa_bls381, KeccakHasher};
 }
 
-use crate::{Error, Result};
+use crate::{
+	encryption::{write_private_file, KeyCipher, KeyFile},
+	Error, Result,
+};
 
 /// A local based keystore that is either memory-based or filesystem-based.
 pub struct LocalKeystore(RwLock<KeystoreInner>);
@@ -60,6 +62,52 @@ impl LocalKeystore {
 		Ok(Self(RwLock::new(inner)))
 	}
 
+	/// Create a local keystore from filesystem, encrypting the key files at rest.
+	///
+	/// Same as [`Self::open`], but the key files are encrypted with a key derived from
+	/// `encryption_password`. A new keystore is created encrypted. An existing keystore which
+	/// isn't encrypted yet has to be migrated first with [`Self::encrypt`].
+	///
+	/// Opening an encrypted keystore without `encryption_password` fails.
+	pub fn open_with_encryption<T: Into<PathBuf>>(
+		path: T,
+		password: Option<SecretString>,
+		encryption_password: Option<SecretString>,
+	) -> Result<Self> {
+		let inner = KeystoreInner::open_with_encryption(path, password, encryption_password)?;
+		Ok(Self(RwLock::new(inner)))
+	}
+
+	/// Encrypt the key files of the keystore at `path` with a key derived from
+	/// `encryption_password`.
+	///
+	/// Key files which are already encrypted are skipped, so an interrupted migration can be
+	/// resumed by calling this again. Returns the number of key files encrypted.
+	pub fn encrypt<T: Into<PathBuf>>(path: T, encryption_password: &SecretString) -> Result<usize> {
+		let path = path.into();
+		let cipher = match KeyCipher::load(&path, encryption_password)? {
+			Some(cipher) => cipher,
+			None => KeyCipher::create(&path, encryption_password)?,
+		};
+
+		let mut encrypted = 0;
+		for file in KeystoreInner::key_files(&path)? {
+			let name = file.file_name().and_then(|n| n.to_str()).unwrap_or_default().to_owned();
+			match serde_json::from_reader(File::open(&file)?)? {
+				KeyFile::Plain(suri) => {
+					let data = cipher.encrypt(name.as_bytes(), suri.as_bytes())?;
+					let tmp = file.with_extension("tmp");
+					write_private_file(&tmp, &serde_json::to_vec(&KeyFile::Encrypted(data))?)?;
+					fs::rename(tmp, &file)?;
+					encrypted += 1;
+				},
+				KeyFile::Encrypted(_) => continue,
+			}
+		}
+
+		Ok(encrypted)
+	}
+
 	/// Create a local keystore in memory.
 	pub fn in_memory() -> Self {
 		let inner = KeystoreInner::new_in_memory();
@@ -413,6 +461,8 @@ struct KeystoreInner {
 	/// Map over `(KeyTypeId, Raw public key)` -> `Key phrase/seed`
 	additional: HashMap<(KeyTypeId, Vec<u8>), String>,
 	password: Option<SecretString>,
+	/// Cipher of the key files, if they are encrypted at rest.
+	cipher: Option<KeyCipher>,
 }
 
 impl KeystoreInner {
@@ -420,10 +470,45 @@ impl KeystoreInner {
 	///
 	/// Optionally takes a password that will be used to encrypt/decrypt the keys.
 	fn open<T: Into<PathBuf>>(path: T, password: Option<SecretString>) -> Result<Self> {
+		Self::open_with_encryption(path, password, None)
+	}
+
+	/// Open the store at the given path, with key files encrypted using `encryption_password`.
+	fn open_with_encryption<T: Into<PathBuf>>(
+		path: T,
+		password: Option<SecretString>,
+		encryption_password: Option<SecretString>,
+	) -> Result<Self> {
 		let path = path.into();
 		fs::create_dir_all(&path)?;
 
-		Ok(Self { path: Some(path), additional: HashMap::new(), password })
+		let cipher = match encryption_password {
+			Some(encryption_password) => match KeyCipher::load(&path, &encryption_password)? {
+				Some(cipher) => Some(cipher),
+				None if !Self::key_files(&path)?.is_empty() => return Err(Error::NotEncrypted),
+				None => Some(KeyCipher::create(&path, &encryption_password)?),
+			},
+			None if KeyCipher::is_encrypted(&path) => return Err(Error::Locked),
+			None => None,
+		};
+
+		Ok(Self { path: Some(path), additional: HashMap::new(), password, cipher })
+	}
+
+	/// Returns the paths of all the key files of the store at `path`.
+	fn key_files(path: &Path) -> Result<Vec<PathBuf>> {
+		let mut files = Vec::new();
+		for entry in fs::read_dir(path)? {
+			let path = entry?.path();
+			let is_key_file = path
+				.file_name()
+				.and_then(|n| n.to_str())
+				.map_or(false, |n| array_bytes::hex2bytes(n).map_or(false, |hex| hex.len() > 4));
+			if is_key_file && path.is_file() {
+				files.push(path);
+			}
+		}
+		Ok(files)
 	}
 
 	/// Get the password for this store.
@@ -433,7 +518,7 @@ impl KeystoreInner {
 
 	/// Create a new in-memory store.
 	fn new_in_memory() -> Self {
-		Self { path: None, additional: HashMap::new(), password: None }
+		Self { path: None, additional: HashMap::new(), password: None, cipher: None }
 	}
 
 	/// Get the key phrase for the given public key and key type from the in-memory store.
@@ -460,7 +545,7 @@ impl KeystoreInner {
 	/// Places it into the file system store, if a path is configured.
 	fn insert(&self, key_type: KeyTypeId, suri: &str, public: &[u8]) -> Result<()> {
 		if let Some(path) = self.key_file_path(public, key_type) {
-			Self::write_to_file(path, suri)?;
+			self.write_to_file(path, suri)?;
 		}
 
 		Ok(())
@@ -473,7 +558,7 @@ impl KeystoreInner {
 	fn generate_by_type<Pair: CorePair>(&mut self, key_type: KeyTypeId) -> Result<Pair> {
 		let (pair, phrase, _) = Pair::generate_with_phrase(self.password());
 		if let Some(path) = self.key_file_path(pair.public().as_slice(), key_type) {
-			Self::write_to_file(path, &phrase)?;
+			self.write_to_file(path, &phrase)?;
 		} else {
 			self.insert_ephemeral_pair(&pair, &phrase, key_type);
 		}
@@ -481,21 +566,34 @@ impl KeystoreInner {
 		Ok(pair)
 	}
 
-	/// Write the given `data` to `file`.
-	fn write_to_file(file: PathBuf, data: &str) -> Result<()> {
-		let mut file = File::create(file)?;
-
-		#[cfg(target_family = "unix")]
-		{
-			use std::os::unix::fs::PermissionsExt;
-			file.set_permissions(fs::Permissions::from_mode(0o600))?;
-		}
+	/// Write the given `data` to `file`, encrypted if the store is.
+	fn write_to_file(&self, file: PathBuf, data: &str) -> Result<()> {
+		let content = match &self.cipher {
+			Some(cipher) => {
+				let name = file.file_name().and_then(|n| n.to_str()).unwrap_or_default();
+				KeyFile::Encrypted(cipher.encrypt(name.as_bytes(), data.as_bytes())?)
+			},
+			None => KeyFile::Plain(data.into()),
+		};
 
-		serde_json::to_writer(&file, data)?;
-		file.flush()?;
+		write_private_file(&file, &serde_json::to_vec(&content)?)?;
 		Ok(())
 	}
 
+	/// Read the key phrase stored in `file`, decrypting it if the store is encrypted.
+	fn read_from_file(&self, file: &Path) -> Result<String> {
+		match (serde_json::from_reader(File::open(file)?)?, &self.cipher) {
+			(KeyFile::Plain(phrase), None) => Ok(phrase),
+			(KeyFile::Encrypted(data), Some(cipher)) => {
+				let name = file.file_name().and_then(|n| n.to_str()).unwrap_or_default();
+				let phrase = cipher.decrypt(name.as_bytes(), &data)?;
+				String::from_utf8(phrase).map_err(|_| Error::Encryption)
+			},
+			(KeyFile::Plain(_), Some(_)) => Err(Error::NotEncrypted),
+			(KeyFile::Encrypted(_), None) => Err(Error::Locked),
+		}
+	}
+
 	/// Create a new key from seed.
 	///
 	/// Does not place it into the file system store.
@@ -522,9 +620,7 @@ impl KeystoreInner {
 		};
 
 		if path.exists() {
-			let file = File::open(path)?;
-
-			serde_json::from_reader(&file).map_err(Into::into).map(Some)
+			self.read_from_file(&path).map(Some)
 		} else {
 			Ok(None)
 		}
@@ -803,6 +899,80 @@ mod tests {
 		assert_eq!(store.sr25519_public_keys(TEST_KEY_TYPE).len(), 2);
 	}
 
+	#[test]
+	fn encrypted_keystore_works() {
+		let temp_dir = TempDir::new().unwrap();
+		let encryption_password = || Some(SecretString::from_str("encryption").unwrap());
+		let store =
+			LocalKeystore::open_with_encryption(temp_dir.path(), None, encryption_password())
+				.unwrap();
+
+		let public = store.sr25519_generate_new(TEST_KEY_TYPE, None).unwrap();
+		let path = store.0.read().key_file_path(public.as_ref(), TEST_KEY_TYPE).unwrap();
+		let content = fs::read_to_string(path).unwrap();
+		assert!(serde_json::from_str::<String>(&content).is_err());
+		drop(store);
+
+		assert!(matches!(LocalKeystore::open(temp_dir.path(), None), Err(Error::Locked)));
+		assert!(matches!(
+			LocalKeystore::open_with_encryption(
+				temp_dir.path(),
+				None,
+				Some(SecretString::from_str("wrong").unwrap())
+			),
+			Err(Error::InvalidEncryptionPassword)
+		));
+
+		let store =
+			LocalKeystore::open_with_encryption(temp_dir.path(), None, encryption_password())
+				.unwrap();
+		assert_eq!(store.sr25519_public_keys(TEST_KEY_TYPE), vec![public]);
+		assert!(store.sr25519_sign(TEST_KEY_TYPE, &public, b"msg").unwrap().is_some());
+	}
+
+	#[test]
+	fn encrypted_key_files_can_not_be_swapped() {
+		let temp_dir = TempDir::new().unwrap();
+		let encryption_password = SecretString::from_str("encryption").unwrap();
+		let store =
+			LocalKeystore::open_with_encryption(temp_dir.path(), None, Some(encryption_password))
+				.unwrap();
+
+		let first = store.sr25519_generate_new(TEST_KEY_TYPE, None).unwrap();
+		let second = store.sr25519_generate_new(TEST_KEY_TYPE, None).unwrap();
+		let first_path = store.0.read().key_file_path(first.as_ref(), TEST_KEY_TYPE).unwrap();
+		let second_path = store.0.read().key_file_path(second.as_ref(), TEST_KEY_TYPE).unwrap();
+		fs::copy(first_path, second_path).unwrap();
+
+		assert!(store.sr25519_sign(TEST_KEY_TYPE, &second, b"msg").is_err());
+	}
+
+	#[test]
+	fn plaintext_keystore_can_be_encrypted() {
+		let temp_dir = TempDir::new().unwrap();
+		let encryption_password = SecretString::from_str("encryption").unwrap();
+		let store = LocalKeystore::open(temp_dir.path(), None).unwrap();
+		let public = store.sr25519_generate_new(TEST_KEY_TYPE, None).unwrap();
+		drop(store);
+
+		assert!(matches!(
+			LocalKeystore::open_with_encryption(
+				temp_dir.path(),
+				None,
+				Some(encryption_password.clone())
+			),
+			Err(Error::NotEncrypted)
+		));
+
+		assert_eq!(LocalKeystore::encrypt(temp_dir.path(), &encryption_password).unwrap(), 1);
+		assert_eq!(LocalKeystore::encrypt(temp_dir.path(), &encryption_password).unwrap(), 0);
+
+		let store =
+			LocalKeystore::open_with_encryption(temp_dir.path(), None, Some(encryption_password))
+				.unwrap();
+		assert!(store.sr25519_sign(TEST_KEY_TYPE, &public, b"msg").unwrap().is_some());
+	}
+
 	#[test]
 	#[cfg(target_family = "unix")]
 	fn uses_correct_file_permissions_on_unix() {
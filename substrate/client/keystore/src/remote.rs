@@ -0,0 +1,553 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+//
+//! Remote keystore implementation.
+//!
+//! The [`RemoteKeystore`] doesn't hold any secret key itself, but forwards all the operations
+//! to a remote signer over a unix or TCP socket. The remote signer can be any process serving
+//! the protocol, for example one built around [`serve`].
+
+mod protocol;
+mod server;
+
+pub use protocol::{RemoteSignerAddress, RemoteSignerSecret};
+pub use server::serve;
+
+use codec::{Decode, Encode};
+use parking_lot::Mutex;
+use protocol::{Connection, Request, Response, Stream, Transcript};
+use sp_core::{
+	crypto::{ByteArray, CryptoTypeId, KeyTypeId},
+	ecdsa, ed25519, sr25519,
+};
+use sp_keystore::{Error as TraitError, Keystore, KeystorePtr};
+use std::sync::Arc;
+
+sp_keystore::bandersnatch_experimental_enabled! {
+use sp_core::bandersnatch;
+}
+
+sp_keystore::bls_experimental_enabled! {
+use sp_core::{bls381, ecdsa_bls381};
+}
+
+use crate::Result;
+
+const LOG_TARGET: &str = "keystore";
+
+/// A keystore forwarding all the operations to a remote signer.
+///
+/// Keys can't be inserted and keys can't be generated from a seed, as secret keys never leave
+/// the remote signer.
+pub struct RemoteKeystore {
+	address: RemoteSignerAddress,
+	secret: RemoteSignerSecret,
+	connection: Mutex<Option<Connection<Stream>>>,
+}
+
+impl RemoteKeystore {
+	/// Connect to the remote signer at `address`, authenticating with `secret`.
+	///
+	/// Fails if the remote signer can't be reached, so misconfigurations are caught early. The
+	/// connection is transparently reopened later if it breaks.
+	pub fn open(address: RemoteSignerAddress, secret: RemoteSignerSecret) -> Result<Self> {
+		let connection = Connection::client(Stream::connect(&address)?, &secret)?;
+		Ok(Self { address, secret, connection: Mutex::new(Some(connection)) })
+	}
+
+	/// Send `request` to the remote signer and decode the result.
+	///
+	/// If the request fails on an already open connection, which may have been closed by the
+	/// signer in the meantime, the request is sent again on a new connection. Requests which
+	/// can't be safely repeated, as the signer may have handled them before the connection
+	/// broke, are always sent on a new connection and never sent again.
+	fn call<T: Decode>(&self, request: Request) -> std::result::Result<T, TraitError> {
+		let message = request.encode();
+		let mut connection = self.connection.lock();
+		if !request.is_idempotent() {
+			*connection = None;
+		}
+
+		let response = loop {
+			let reused = connection.is_some();
+			if !reused {
+				let connected = Stream::connect(&self.address)
+					.and_then(|stream| Connection::client(stream, &self.secret));
+				match connected {
+					Ok(c) => *connection = Some(c),
+					Err(e) => {
+						log::error!(
+							target: LOG_TARGET,
+							"Remote signer at {} unavailable: {e}",
+							self.address,
+						);
+						return Err(TraitError::Unavailable)
+					},
+				}
+			}
+
+			let open = connection.as_mut().expect("Connection opened above; qed");
+			match open.send(&message).and_then(|()| open.receive()) {
+				Ok(response) => break response,
+				Err(e) if reused => {
+					log::debug!(target: LOG_TARGET, "Reconnecting to remote signer: {e}");
+					*connection = None;
+				},
+				Err(e) => {
+					log::error!(target: LOG_TARGET, "Remote signer request failed: {e}");
+					*connection = None;
+					return Err(TraitError::Unavailable)
+				},
+			}
+		};
+
+		let decode_error = |e: codec::Error| TraitError::Other(format!("Invalid response: {e}"));
+		let result = Response::decode(&mut &response[..]).map_err(decode_error)??;
+		T::decode(&mut &result[..]).map_err(decode_error)
+	}
+
+	fn public_keys<T: Decode>(&self, crypto: CryptoTypeId, key_type: KeyTypeId) -> Vec<T> {
+		self.call(Request::PublicKeys { crypto, key_type }).unwrap_or_else(|e| {
+			log::warn!(target: LOG_TARGET, "Failed to fetch public keys: {e}");
+			Vec::new()
+		})
+	}
+
+	fn generate_new<T: Decode>(
+		&self,
+		crypto: CryptoTypeId,
+		key_type: KeyTypeId,
+		seed: Option<&str>,
+	) -> std::result::Result<T, TraitError> {
+		if seed.is_some() {
+			return Err(TraitError::Other(
+				"Keys can't be generated from a seed by a remote signer".into(),
+			))
+		}
+		self.call(Request::GenerateNew { crypto, key_type })
+	}
+
+	fn sign<T: Decode>(
+		&self,
+		crypto: CryptoTypeId,
+		key_type: KeyTypeId,
+		public: &impl ByteArray,
+		msg: &[u8],
+	) -> std::result::Result<Option<T>, TraitError> {
+		let request =
+			Request::Sign { crypto, key_type, public: public.to_raw_vec(), msg: msg.to_vec() };
+		self.call::<Option<Vec<u8>>>(request)?
+			.map(|signature| {
+				T::decode(&mut &signature[..])
+					.map_err(|e| TraitError::Other(format!("Invalid signature: {e}")))
+			})
+			.transpose()
+	}
+}
+
+impl Keystore for RemoteKeystore {
+	fn sr25519_public_keys(&self, key_type: KeyTypeId) -> Vec<sr25519::Public> {
+		self.public_keys(sr25519::CRYPTO_ID, key_type)
+	}
+
+	fn sr25519_generate_new(
+		&self,
+		key_type: KeyTypeId,
+		seed: Option<&str>,
+	) -> std::result::Result<sr25519::Public, TraitError> {
+		self.generate_new(sr25519::CRYPTO_ID, key_type, seed)
+	}
+
+	fn sr25519_sign(
+		&self,
+		key_type: KeyTypeId,
+		public: &sr25519::Public,
+		msg: &[u8],
+	) -> std::result::Result<Option<sr25519::Signature>, TraitError> {
+		self.sign(sr25519::CRYPTO_ID, key_type, public, msg)
+	}
+
+	fn sr25519_vrf_sign(
+		&self,
+		key_type: KeyTypeId,
+		public: &sr25519::Public,
+		data: &sr25519::vrf::VrfSignData,
+	) -> std::result::Result<Option<sr25519::vrf::VrfSignature>, TraitError> {
+		let Some((transcript, extra)) = data.transcript_data() else {
+			return Err(TraitError::Other(
+				"Only VRF sign data built from `VrfTranscriptData` can be sent to a remote signer"
+					.into(),
+			))
+		};
+		self.call(Request::Sr25519VrfSign {
+			key_type,
+			public: *public,
+			transcript: Transcript::from(transcript),
+			extra: extra.map(Transcript::from),
+		})
+	}
+
+	fn sr25519_vrf_pre_output(
+		&self,
+		_key_type: KeyTypeId,
+		_public: &sr25519::Public,
+		_input: &sr25519::vrf::VrfInput,
+	) -> std::result::Result<Option<sr25519::vrf::VrfPreOutput>, TraitError> {
+		// A `VrfInput` can't be inspected, so it can't be sent to the signer.
+		Err(TraitError::Other("VRF pre-outputs can't be computed by a remote signer".into()))
+	}
+
+	fn ed25519_public_keys(&self, key_type: KeyTypeId) -> Vec<ed25519::Public> {
+		self.public_keys(ed25519::CRYPTO_ID, key_type)
+	}
+
+	fn ed25519_generate_new(
+		&self,
+		key_type: KeyTypeId,
+		seed: Option<&str>,
+	) -> std::result::Result<ed25519::Public, TraitError> {
+		self.generate_new(ed25519::CRYPTO_ID, key_type, seed)
+	}
+
+	fn ed25519_sign(
+		&self,
+		key_type: KeyTypeId,
+		public: &ed25519::Public,
+		msg: &[u8],
+	) -> std::result::Result<Option<ed25519::Signature>, TraitError> {
+		self.sign(ed25519::CRYPTO_ID, key_type, public, msg)
+	}
+
+	fn ecdsa_public_keys(&self, key_type: KeyTypeId) -> Vec<ecdsa::Public> {
+		self.public_keys(ecdsa::CRYPTO_ID, key_type)
+	}
+
+	fn ecdsa_generate_new(
+		&self,
+		key_type: KeyTypeId,
+		seed: Option<&str>,
+	) -> std::result::Result<ecdsa::Public, TraitError> {
+		self.generate_new(ecdsa::CRYPTO_ID, key_type, seed)
+	}
+
+	fn ecdsa_sign(
+		&self,
+		key_type: KeyTypeId,
+		public: &ecdsa::Public,
+		msg: &[u8],
+	) -> std::result::Result<Option<ecdsa::Signature>, TraitError> {
+		self.sign(ecdsa::CRYPTO_ID, key_type, public, msg)
+	}
+
+	fn ecdsa_sign_prehashed(
+		&self,
+		key_type: KeyTypeId,
+		public: &ecdsa::Public,
+		msg: &[u8; 32],
+	) -> std::result::Result<Option<ecdsa::Signature>, TraitError> {
+		self.call(Request::EcdsaSignPrehashed { key_type, public: *public, msg: *msg })
+	}
+
+	sp_keystore::bandersnatch_experimental_enabled! {
+		fn bandersnatch_public_keys(&self, key_type: KeyTypeId) -> Vec<bandersnatch::Public> {
+			self.public_keys(bandersnatch::CRYPTO_ID, key_type)
+		}
+
+		fn bandersnatch_generate_new(
+			&self,
+			key_type: KeyTypeId,
+			seed: Option<&str>,
+		) -> std::result::Result<bandersnatch::Public, TraitError> {
+			self.generate_new(bandersnatch::CRYPTO_ID, key_type, seed)
+		}
+
+		fn bandersnatch_sign(
+			&self,
+			key_type: KeyTypeId,
+			public: &bandersnatch::Public,
+			msg: &[u8],
+		) -> std::result::Result<Option<bandersnatch::Signature>, TraitError> {
+			self.sign(bandersnatch::CRYPTO_ID, key_type, public, msg)
+		}
+
+		fn bandersnatch_vrf_sign(
+			&self,
+			key_type: KeyTypeId,
+			public: &bandersnatch::Public,
+			data: &bandersnatch::vrf::VrfSignData,
+		) -> std::result::Result<Option<bandersnatch::vrf::VrfSignature>, TraitError> {
+			self.call(Request::BandersnatchVrfSign {
+				key_type,
+				public: public.to_raw_vec(),
+				input: data.vrf_input.encode(),
+				aux_data: data.aux_data.clone(),
+			})
+		}
+
+		fn bandersnatch_vrf_pre_output(
+			&self,
+			key_type: KeyTypeId,
+			public: &bandersnatch::Public,
+			input: &bandersnatch::vrf::VrfInput,
+		) -> std::result::Result<Option<bandersnatch::vrf::VrfPreOutput>, TraitError> {
+			self.call(Request::BandersnatchVrfPreOutput {
+				key_type,
+				public: public.to_raw_vec(),
+				input: input.encode(),
+			})
+		}
+
+		/// Not supported, as the ring prover can't be sent to the remote signer.
+		fn bandersnatch_ring_vrf_sign(
+			&self,
+			_key_type: KeyTypeId,
+			_public: &bandersnatch::Public,
+			_data: &bandersnatch::vrf::VrfSignData,
+			_prover: &bandersnatch::ring_vrf::RingProver,
+		) -> std::result::Result<Option<bandersnatch::ring_vrf::RingVrfSignature>, TraitError> {
+			Err(TraitError::Other("Ring VRF signing is not supported by remote signers".into()))
+		}
+	}
+
+	sp_keystore::bls_experimental_enabled! {
+		fn bls381_public_keys(&self, key_type: KeyTypeId) -> Vec<bls381::Public> {
+			self.public_keys(bls381::CRYPTO_ID, key_type)
+		}
+
+		fn bls381_generate_new(
+			&self,
+			key_type: KeyTypeId,
+			seed: Option<&str>,
+		) -> std::result::Result<bls381::Public, TraitError> {
+			self.generate_new(bls381::CRYPTO_ID, key_type, seed)
+		}
+
+		fn bls381_sign(
+			&self,
+			key_type: KeyTypeId,
+			public: &bls381::Public,
+			msg: &[u8],
+		) -> std::result::Result<Option<bls381::Signature>, TraitError> {
+			self.sign(bls381::CRYPTO_ID, key_type, public, msg)
+		}
+
+		fn ecdsa_bls381_public_keys(&self, key_type: KeyTypeId) -> Vec<ecdsa_bls381::Public> {
+			self.public_keys(ecdsa_bls381::CRYPTO_ID, key_type)
+		}
+
+		fn ecdsa_bls381_generate_new(
+			&self,
+			key_type: KeyTypeId,
+			seed: Option<&str>,
+		) -> std::result::Result<ecdsa_bls381::Public, TraitError> {
+			self.generate_new(ecdsa_bls381::CRYPTO_ID, key_type, seed)
+		}
+
+		fn ecdsa_bls381_sign(
+			&self,
+			key_type: KeyTypeId,
+			public: &ecdsa_bls381::Public,
+			msg: &[u8],
+		) -> std::result::Result<Option<ecdsa_bls381::Signature>, TraitError> {
+			self.sign(ecdsa_bls381::CRYPTO_ID, key_type, public, msg)
+		}
+
+		fn ecdsa_bls381_sign_with_keccak256(
+			&self,
+			key_type: KeyTypeId,
+			public: &ecdsa_bls381::Public,
+			msg: &[u8],
+		) -> std::result::Result<Option<ecdsa_bls381::Signature>, TraitError> {
+			self.call(Request::EcdsaBls381SignWithKeccak256 {
+				key_type,
+				public: public.to_raw_vec(),
+				msg: msg.to_vec(),
+			})
+		}
+	}
+
+	/// Not supported, keys have to be inserted into the remote signer directly.
+	fn insert(
+		&self,
+		key_type: KeyTypeId,
+		_suri: &str,
+		_public: &[u8],
+	) -> std::result::Result<(), ()> {
+		log::warn!(
+			target: LOG_TARGET,
+			"Refusing to send a secret key of type {key_type:?} to the remote signer, insert it \
+			 into the remote signer directly",
+		);
+		Err(())
+	}
+
+	fn keys(&self, key_type: KeyTypeId) -> std::result::Result<Vec<Vec<u8>>, TraitError> {
+		self.call(Request::Keys { key_type })
+	}
+
+	fn has_keys(&self, public_keys: &[(Vec<u8>, KeyTypeId)]) -> bool {
+		self.call(Request::HasKeys { public_keys: public_keys.to_vec() })
+			.unwrap_or_else(|e| {
+				log::warn!(target: LOG_TARGET, "Failed to check keys: {e}");
+				false
+			})
+	}
+}
+
+impl Into<KeystorePtr> for RemoteKeystore {
+	fn into(self) -> KeystorePtr {
+		Arc::new(self)
+	}
+}
+
+#[cfg(test)]
+#[cfg(target_family = "unix")]
+mod tests {
+	use super::*;
+	use crate::LocalKeystore;
+	use sp_core::{crypto::VrfPublic, Pair};
+	use tempfile::TempDir;
+
+	const KEY_TYPE: KeyTypeId = KeyTypeId(*b"test");
+
+	fn remote_keystore() -> (RemoteKeystore, Arc<LocalKeystore>, TempDir) {
+		let temp_dir = TempDir::new().unwrap();
+		let address = RemoteSignerAddress::Unix(temp_dir.path().join("signer.sock"));
+		let secret = RemoteSignerSecret::generate();
+		let local = Arc::new(LocalKeystore::in_memory());
+
+		let (server_address, server_secret, server_keystore) =
+			(address.clone(), secret.clone(), local.clone());
+		std::thread::spawn(move || serve(&server_address, server_secret, server_keystore));
+
+		let remote = (0..100)
+			.find_map(|_| {
+				std::thread::sleep(std::time::Duration::from_millis(10));
+				RemoteKeystore::open(address.clone(), secret.clone()).ok()
+			})
+			.expect("remote signer starts");
+
+		(remote, local, temp_dir)
+	}
+
+	#[test]
+	fn forwards_signing_requests() {
+		let (remote, local, _temp_dir) = remote_keystore();
+
+		let public = remote.sr25519_generate_new(KEY_TYPE, None).unwrap();
+		assert_eq!(local.sr25519_public_keys(KEY_TYPE), vec![public]);
+		assert_eq!(remote.sr25519_public_keys(KEY_TYPE), vec![public]);
+		assert!(remote.has_keys(&[(public.to_raw_vec(), KEY_TYPE)]));
+
+		let signature = remote.sr25519_sign(KEY_TYPE, &public, b"msg").unwrap().unwrap();
+		assert!(sr25519::Pair::verify(&signature, b"msg", &public));
+
+		let unknown = sr25519::Pair::generate().0.public();
+		assert_eq!(remote.sr25519_sign(KEY_TYPE, &unknown, b"msg").unwrap(), None);
+
+		let public = remote.ecdsa_generate_new(KEY_TYPE, None).unwrap();
+		let signature = remote.ecdsa_sign_prehashed(KEY_TYPE, &public, &[1; 32]).unwrap().unwrap();
+		assert_eq!(
+			local.ecdsa_sign_prehashed(KEY_TYPE, &public, &[1; 32]).unwrap(),
+			Some(signature)
+		);
+	}
+
+	#[test]
+	fn forwards_vrf_requests() {
+		let (remote, local, _temp_dir) = remote_keystore();
+		let public = remote.sr25519_generate_new(KEY_TYPE, None).unwrap();
+
+		let transcript = sr25519::vrf::VrfTranscriptData::new(b"test", &[(b"data", b"input")]);
+		let extra = sr25519::vrf::VrfTranscriptData::new(b"extra", &[(b"data", b"extra")]);
+		let data = sr25519::vrf::VrfSignData::from(transcript.clone()).with_extra_data(extra);
+
+		let signature = remote.sr25519_vrf_sign(KEY_TYPE, &public, &data).unwrap().unwrap();
+		assert!(public.vrf_verify(&data, &signature));
+		assert_eq!(
+			local
+				.sr25519_vrf_pre_output(KEY_TYPE, &public, &transcript.transcript())
+				.unwrap(),
+			Some(signature.pre_output)
+		);
+
+		// Transcripts built without keeping their data can't be sent to the signer.
+		let data = transcript.transcript().into_sign_data();
+		assert!(remote.sr25519_vrf_sign(KEY_TYPE, &public, &data).is_err());
+	}
+
+	#[test]
+	fn only_idempotent_requests_are_retried() {
+		let temp_dir = TempDir::new().unwrap();
+		let path = temp_dir.path().join("signer.sock");
+		let secret = RemoteSignerSecret::generate();
+
+		// A signer which drops every connection after receiving a request, without answering.
+		let listener = std::os::unix::net::UnixListener::bind(&path).unwrap();
+		let received = Arc::new(Mutex::new(Vec::new()));
+		std::thread::spawn({
+			let (secret, received) = (secret.clone(), received.clone());
+			move || {
+				for stream in listener.incoming() {
+					let Ok(mut connection) = Connection::server(stream.unwrap(), &secret) else {
+						continue
+					};
+					if let Ok(message) = connection.receive() {
+						received.lock().push(Request::decode(&mut &message[..]).unwrap());
+					}
+				}
+			}
+		});
+		let remote = RemoteKeystore::open(RemoteSignerAddress::Unix(path), secret).unwrap();
+
+		// Signing requests failing on the open connection are sent again on a new one, once.
+		let public = sr25519::Pair::generate().0.public();
+		assert!(matches!(
+			remote.sr25519_sign(KEY_TYPE, &public, b"msg"),
+			Err(TraitError::Unavailable)
+		));
+		assert_eq!(received.lock().len(), 2);
+
+		// Generating a key is never sent twice, as the first request may have been handled.
+		assert!(matches!(
+			remote.sr25519_generate_new(KEY_TYPE, None),
+			Err(TraitError::Unavailable)
+		));
+		let received = received.lock();
+		assert_eq!(received.len(), 3);
+		assert!(matches!(received[..2], [Request::Sign { .. }, Request::Sign { .. }]));
+		assert!(matches!(received[2], Request::GenerateNew { .. }));
+	}
+
+	#[test]
+	fn secrets_never_leave_the_signer() {
+		let (remote, local, _temp_dir) = remote_keystore();
+
+		assert!(remote.sr25519_generate_new(KEY_TYPE, Some("//Alice")).is_err());
+		assert!(remote.insert(KEY_TYPE, "//Alice", &[0; 32]).is_err());
+		assert!(local.keys(KEY_TYPE).unwrap().is_empty());
+	}
+
+	#[test]
+	fn wrong_secret_is_rejected() {
+		let (_remote, _local, temp_dir) = remote_keystore();
+		let address = RemoteSignerAddress::Unix(temp_dir.path().join("signer.sock"));
+
+		assert!(RemoteKeystore::open(address, RemoteSignerSecret::generate()).is_err());
+	}
+}
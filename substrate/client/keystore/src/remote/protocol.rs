@@ -0,0 +1,603 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Wire protocol spoken between the [`RemoteKeystore`](super::RemoteKeystore) and the remote
+//! signer.
+//!
+//! Both sides share a 32 bytes secret. When a connection is opened, the client sends the
+//! protocol name and a random nonce, the server answers with its own random nonce and a proof
+//! of knowledge of the secret bound to both nonces, and the client finally sends its own proof.
+//! A session key derived from the secret and both nonces is then used to authenticate every
+//! message exchanged over the connection, which also protects against replayed or reordered
+//! messages.
+//!
+//! The messages themselves are not encrypted. They only contain public keys, payloads to sign
+//! and signatures, secret keys never leave the signer.
+
+use codec::{Decode, Encode};
+use rand::RngCore;
+use sp_core::{
+	crypto::{CryptoTypeId, KeyTypeId},
+	ecdsa, sr25519,
+};
+use sp_crypto_hashing::blake2_256;
+use sp_keystore::Error as TraitError;
+use std::{
+	collections::BTreeSet,
+	fmt, fs,
+	io::{self, Read, Write},
+	net::TcpStream,
+	path::{Path, PathBuf},
+	str::FromStr,
+	sync::Mutex,
+	time::Duration,
+};
+
+/// Name and version of the protocol, sent by the client when opening a connection.
+const PROTOCOL_NAME: &[u8; 25] = b"substrate-remote-signer/1";
+
+/// Maximum size of a single message.
+const MAX_MESSAGE_SIZE: usize = 16 * 1024 * 1024;
+
+/// Maximum number of distinct transcript labels the signer will ever accept.
+const MAX_TRANSCRIPT_LABELS: usize = 1024;
+
+/// Timeout applied to all reads and writes of a connection.
+pub(super) const IO_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Address of a remote signer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RemoteSignerAddress {
+	/// Unix domain socket at the given path.
+	Unix(PathBuf),
+	/// TCP socket at the given `host:port`.
+	Tcp(String),
+}
+
+impl FromStr for RemoteSignerAddress {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		if let Some(path) = s.strip_prefix("unix:") {
+			if path.is_empty() {
+				return Err("Missing path of the unix socket".into())
+			}
+			Ok(Self::Unix(path.into()))
+		} else if let Some(addr) = s.strip_prefix("tcp:") {
+			if !addr.contains(':') {
+				return Err(format!("Missing port in the TCP address `{addr}`"))
+			}
+			Ok(Self::Tcp(addr.into()))
+		} else {
+			Err(format!("Invalid remote signer address `{s}`, expected `unix:<PATH>` or `tcp:<HOST>:<PORT>`"))
+		}
+	}
+}
+
+impl fmt::Display for RemoteSignerAddress {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::Unix(path) => write!(f, "unix:{}", path.display()),
+			Self::Tcp(addr) => write!(f, "tcp:{addr}"),
+		}
+	}
+}
+
+/// Secret shared between a node and its remote signer, used to authenticate each other.
+#[derive(Clone)]
+pub struct RemoteSignerSecret([u8; 32]);
+
+impl RemoteSignerSecret {
+	/// Generate a new random secret.
+	pub fn generate() -> Self {
+		let mut secret = [0; 32];
+		rand::thread_rng().fill_bytes(&mut secret);
+		Self(secret)
+	}
+
+	/// Read the hex encoded secret stored in the file at `path`.
+	pub fn from_file(path: &Path) -> io::Result<Self> {
+		let content = fs::read_to_string(path)?;
+		array_bytes::hex2array(content.trim()).map(Self).map_err(|_| {
+			io::Error::new(
+				io::ErrorKind::InvalidData,
+				format!("{} doesn't contain a hex encoded 32 bytes secret", path.display()),
+			)
+		})
+	}
+
+	/// Write the secret hex encoded to a new file at `path`, only readable by its owner.
+	pub fn write_to_file(&self, path: &Path) -> io::Result<()> {
+		let mut options = fs::OpenOptions::new();
+		options.write(true).create_new(true);
+		#[cfg(target_family = "unix")]
+		{
+			use std::os::unix::fs::OpenOptionsExt;
+			options.mode(0o600);
+		}
+		let mut file = options.open(path)?;
+		file.write_all(array_bytes::bytes2hex("0x", self.0).as_bytes())?;
+		file.sync_all()
+	}
+}
+
+impl From<[u8; 32]> for RemoteSignerSecret {
+	fn from(secret: [u8; 32]) -> Self {
+		Self(secret)
+	}
+}
+
+impl fmt::Debug for RemoteSignerSecret {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str("RemoteSignerSecret(<redacted>)")
+	}
+}
+
+/// Keyed hash of `parts` under `key`.
+fn mac(key: &[u8; 32], parts: &[&[u8]]) -> [u8; 32] {
+	blake2_256(&(key, parts).encode())
+}
+
+/// Compare two MACs without leaking where they differ.
+fn mac_eq(a: &[u8; 32], b: &[u8; 32]) -> bool {
+	a.iter().zip(b.iter()).fold(0, |acc, (a, b)| acc | (a ^ b)) == 0
+}
+
+fn random_nonce() -> [u8; 32] {
+	let mut nonce = [0; 32];
+	rand::thread_rng().fill_bytes(&mut nonce);
+	nonce
+}
+
+fn authentication_failed() -> io::Error {
+	io::Error::new(io::ErrorKind::PermissionDenied, "remote signer authentication failed")
+}
+
+/// Byte stream a connection is running on.
+pub(super) enum Stream {
+	Tcp(TcpStream),
+	#[cfg(target_family = "unix")]
+	Unix(std::os::unix::net::UnixStream),
+}
+
+impl Stream {
+	/// Open a stream to the given address.
+	pub(super) fn connect(address: &RemoteSignerAddress) -> io::Result<Self> {
+		let stream = match address {
+			RemoteSignerAddress::Tcp(addr) => {
+				let stream = TcpStream::connect(addr.as_str())?;
+				stream.set_nodelay(true)?;
+				Self::Tcp(stream)
+			},
+			#[cfg(target_family = "unix")]
+			RemoteSignerAddress::Unix(path) => Self::Unix(std::os::unix::net::UnixStream::connect(path)?),
+			#[cfg(not(target_family = "unix"))]
+			RemoteSignerAddress::Unix(_) =>
+				return Err(io::Error::new(
+					io::ErrorKind::Unsupported,
+					"unix sockets are not supported on this platform",
+				)),
+		};
+		stream.set_timeout(Some(IO_TIMEOUT))?;
+		Ok(stream)
+	}
+
+	pub(super) fn set_timeout(&self, timeout: Option<Duration>) -> io::Result<()> {
+		match self {
+			Self::Tcp(s) => {
+				s.set_read_timeout(timeout)?;
+				s.set_write_timeout(timeout)
+			},
+			#[cfg(target_family = "unix")]
+			Self::Unix(s) => {
+				s.set_read_timeout(timeout)?;
+				s.set_write_timeout(timeout)
+			},
+		}
+	}
+}
+
+impl Read for Stream {
+	fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+		match self {
+			Self::Tcp(s) => s.read(buf),
+			#[cfg(target_family = "unix")]
+			Self::Unix(s) => s.read(buf),
+		}
+	}
+}
+
+impl Write for Stream {
+	fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+		match self {
+			Self::Tcp(s) => s.write(buf),
+			#[cfg(target_family = "unix")]
+			Self::Unix(s) => s.write(buf),
+		}
+	}
+
+	fn flush(&mut self) -> io::Result<()> {
+		match self {
+			Self::Tcp(s) => s.flush(),
+			#[cfg(target_family = "unix")]
+			Self::Unix(s) => s.flush(),
+		}
+	}
+}
+
+/// Side of a connection.
+#[derive(Clone, Copy)]
+enum Side {
+	Client,
+	Server,
+}
+
+impl Side {
+	fn label(self) -> &'static [u8] {
+		match self {
+			Self::Client => b"client",
+			Self::Server => b"server",
+		}
+	}
+
+	fn other(self) -> Self {
+		match self {
+			Self::Client => Self::Server,
+			Self::Server => Self::Client,
+		}
+	}
+}
+
+/// An authenticated connection between a node and a remote signer.
+pub(super) struct Connection<S> {
+	stream: S,
+	side: Side,
+	session_key: [u8; 32],
+	sent: u64,
+	received: u64,
+}
+
+impl<S: Read + Write> Connection<S> {
+	/// Authenticate to the remote signer at the other end of `stream`.
+	pub(super) fn client(mut stream: S, secret: &RemoteSignerSecret) -> io::Result<Self> {
+		let client_nonce = random_nonce();
+		stream.write_all(PROTOCOL_NAME)?;
+		stream.write_all(&client_nonce)?;
+		stream.flush()?;
+
+		let mut server_nonce = [0; 32];
+		let mut server_proof = [0; 32];
+		stream.read_exact(&mut server_nonce)?;
+		stream.read_exact(&mut server_proof)?;
+
+		let expected = mac(&secret.0, &[Side::Server.label(), &client_nonce, &server_nonce]);
+		if !mac_eq(&server_proof, &expected) {
+			return Err(authentication_failed())
+		}
+
+		let proof = mac(&secret.0, &[Side::Client.label(), &client_nonce, &server_nonce]);
+		stream.write_all(&proof)?;
+		stream.flush()?;
+
+		Ok(Self::new(stream, Side::Client, secret, &client_nonce, &server_nonce))
+	}
+
+	/// Authenticate the node at the other end of `stream`.
+	pub(super) fn server(mut stream: S, secret: &RemoteSignerSecret) -> io::Result<Self> {
+		let mut protocol = [0; PROTOCOL_NAME.len()];
+		let mut client_nonce = [0; 32];
+		stream.read_exact(&mut protocol)?;
+		if &protocol != PROTOCOL_NAME {
+			return Err(io::Error::new(io::ErrorKind::InvalidData, "unsupported protocol"))
+		}
+		stream.read_exact(&mut client_nonce)?;
+
+		let server_nonce = random_nonce();
+		let proof = mac(&secret.0, &[Side::Server.label(), &client_nonce, &server_nonce]);
+		stream.write_all(&server_nonce)?;
+		stream.write_all(&proof)?;
+		stream.flush()?;
+
+		let mut client_proof = [0; 32];
+		stream.read_exact(&mut client_proof)?;
+		let expected = mac(&secret.0, &[Side::Client.label(), &client_nonce, &server_nonce]);
+		if !mac_eq(&client_proof, &expected) {
+			return Err(authentication_failed())
+		}
+
+		Ok(Self::new(stream, Side::Server, secret, &client_nonce, &server_nonce))
+	}
+
+	fn new(
+		stream: S,
+		side: Side,
+		secret: &RemoteSignerSecret,
+		client_nonce: &[u8; 32],
+		server_nonce: &[u8; 32],
+	) -> Self {
+		let session_key = mac(&secret.0, &[b"session", client_nonce, server_nonce]);
+		Self { stream, side, session_key, sent: 0, received: 0 }
+	}
+
+	/// The underlying stream.
+	pub(super) fn stream(&self) -> &S {
+		&self.stream
+	}
+
+	/// Send an authenticated message.
+	pub(super) fn send(&mut self, message: &[u8]) -> io::Result<()> {
+		if message.len() > MAX_MESSAGE_SIZE {
+			return Err(io::Error::new(io::ErrorKind::InvalidInput, "message too large"))
+		}
+
+		let tag = mac(&self.session_key, &[self.side.label(), &self.sent.to_le_bytes(), message]);
+		self.sent += 1;
+
+		self.stream.write_all(&(message.len() as u32).to_le_bytes())?;
+		self.stream.write_all(message)?;
+		self.stream.write_all(&tag)?;
+		self.stream.flush()
+	}
+
+	/// Receive an authenticated message.
+	pub(super) fn receive(&mut self) -> io::Result<Vec<u8>> {
+		let mut len = [0; 4];
+		self.stream.read_exact(&mut len)?;
+		let len = u32::from_le_bytes(len) as usize;
+		if len > MAX_MESSAGE_SIZE {
+			return Err(io::Error::new(io::ErrorKind::InvalidData, "message too large"))
+		}
+
+		let mut message = vec![0; len];
+		let mut tag = [0; 32];
+		self.stream.read_exact(&mut message)?;
+		self.stream.read_exact(&mut tag)?;
+
+		let expected = mac(
+			&self.session_key,
+			&[self.side.other().label(), &self.received.to_le_bytes(), &message],
+		);
+		if !mac_eq(&tag, &expected) {
+			return Err(authentication_failed())
+		}
+		self.received += 1;
+
+		Ok(message)
+	}
+}
+
+/// A request sent by the node to the remote signer.
+///
+/// Payloads of the experimental crypto schemes are sent SCALE encoded, so that the requests are
+/// the same no matter which features the node and the signer have been compiled with.
+#[derive(Debug, Encode, Decode)]
+pub(super) enum Request {
+	/// Public keys of the given crypto scheme and key type.
+	#[codec(index = 0)]
+	PublicKeys { crypto: CryptoTypeId, key_type: KeyTypeId },
+	/// Generate a new key of the given crypto scheme and key type.
+	#[codec(index = 1)]
+	GenerateNew { crypto: CryptoTypeId, key_type: KeyTypeId },
+	/// Sign `msg` with the given crypto scheme, see [`sp_keystore::Keystore::sign_with`].
+	#[codec(index = 2)]
+	Sign { crypto: CryptoTypeId, key_type: KeyTypeId, public: Vec<u8>, msg: Vec<u8> },
+	/// Sign the pre-hashed `msg` with an ecdsa key.
+	#[codec(index = 3)]
+	EcdsaSignPrehashed { key_type: KeyTypeId, public: ecdsa::Public, msg: [u8; 32] },
+	/// Generate a sr25519 VRF signature.
+	#[codec(index = 4)]
+	Sr25519VrfSign {
+		key_type: KeyTypeId,
+		public: sr25519::Public,
+		transcript: Transcript,
+		extra: Option<Transcript>,
+	},
+	/// Generate a bandersnatch VRF signature.
+	#[codec(index = 6)]
+	BandersnatchVrfSign { key_type: KeyTypeId, public: Vec<u8>, input: Vec<u8>, aux_data: Vec<u8> },
+	/// Generate a bandersnatch VRF pre-output.
+	#[codec(index = 7)]
+	BandersnatchVrfPreOutput { key_type: KeyTypeId, public: Vec<u8>, input: Vec<u8> },
+	/// Sign the keccak256 hash of `msg` with an (ecdsa,bls381) paired key.
+	#[codec(index = 8)]
+	EcdsaBls381SignWithKeccak256 { key_type: KeyTypeId, public: Vec<u8>, msg: Vec<u8> },
+	/// All the public keys of the given key type.
+	#[codec(index = 9)]
+	Keys { key_type: KeyTypeId },
+	/// Whether the signer has all the given keys.
+	#[codec(index = 10)]
+	HasKeys { public_keys: Vec<(Vec<u8>, KeyTypeId)> },
+}
+
+impl Request {
+	/// Whether handling the request twice has the same effect as handling it once.
+	pub(super) fn is_idempotent(&self) -> bool {
+		!matches!(self, Self::GenerateNew { .. })
+	}
+}
+
+/// The response to a [`Request`], containing the SCALE encoded result on success.
+pub(super) type Response = Result<Vec<u8>, RemoteError>;
+
+/// Error returned by the remote signer, mirroring [`sp_keystore::Error`].
+#[derive(Debug, Encode, Decode)]
+pub(super) enum RemoteError {
+	KeyNotSupported(KeyTypeId),
+	ValidationError(String),
+	Unavailable,
+	Other(String),
+}
+
+impl From<TraitError> for RemoteError {
+	fn from(error: TraitError) -> Self {
+		match error {
+			TraitError::KeyNotSupported(id) => Self::KeyNotSupported(id),
+			TraitError::ValidationError(e) => Self::ValidationError(e),
+			TraitError::Unavailable => Self::Unavailable,
+			TraitError::Other(e) => Self::Other(e),
+		}
+	}
+}
+
+impl From<RemoteError> for TraitError {
+	fn from(error: RemoteError) -> Self {
+		match error {
+			RemoteError::KeyNotSupported(id) => Self::KeyNotSupported(id),
+			RemoteError::ValidationError(e) => Self::ValidationError(e),
+			RemoteError::Unavailable => Self::Unavailable,
+			RemoteError::Other(e) => Self::Other(e),
+		}
+	}
+}
+
+/// The data a sr25519 VRF transcript is built from.
+#[derive(Debug, Encode, Decode)]
+pub(super) struct Transcript {
+	label: Vec<u8>,
+	items: Vec<(Vec<u8>, Vec<u8>)>,
+}
+
+impl From<&sr25519::vrf::VrfTranscriptData> for Transcript {
+	fn from(transcript: &sr25519::vrf::VrfTranscriptData) -> Self {
+		Self {
+			label: transcript.label().to_vec(),
+			items: transcript.items().map(|(l, b)| (l.to_vec(), b.to_vec())).collect(),
+		}
+	}
+}
+
+impl Transcript {
+	/// Rebuild the data of the VRF transcript.
+	pub(super) fn into_vrf_transcript_data(
+		self,
+	) -> Result<sr25519::vrf::VrfTranscriptData, RemoteError> {
+		let label = intern_label(self.label)?;
+		let items = self
+			.items
+			.into_iter()
+			.map(|(l, b)| Ok((intern_label(l)?, b)))
+			.collect::<Result<Vec<_>, RemoteError>>()?;
+		let items = items.iter().map(|(l, b)| (*l, &b[..])).collect::<Vec<_>>();
+		Ok(sr25519::vrf::VrfTranscriptData::new(label, &items))
+	}
+}
+
+/// Transcripts labels are required to be `'static`.
+///
+/// Labels are constants chosen by the consensus engines, so only a handful of them are ever
+/// leaked. The number of labels is nevertheless bounded.
+fn intern_label(label: Vec<u8>) -> Result<&'static [u8], RemoteError> {
+	static LABELS: Mutex<BTreeSet<&'static [u8]>> = Mutex::new(BTreeSet::new());
+
+	let mut labels = LABELS.lock().unwrap_or_else(|e| e.into_inner());
+	if let Some(label) = labels.get(&label[..]) {
+		return Ok(label)
+	}
+	if labels.len() >= MAX_TRANSCRIPT_LABELS {
+		return Err(RemoteError::Other("Too many distinct VRF transcript labels".into()))
+	}
+
+	let label: &'static [u8] = Box::leak(label.into_boxed_slice());
+	labels.insert(label);
+	Ok(label)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	#[cfg(target_family = "unix")]
+	use std::{os::unix::net::UnixStream, thread};
+
+	#[test]
+	fn parse_addresses() {
+		assert_eq!(
+			"unix:/tmp/signer.sock".parse(),
+			Ok(RemoteSignerAddress::Unix("/tmp/signer.sock".into()))
+		);
+		assert_eq!(
+			"tcp:127.0.0.1:9955".parse(),
+			Ok(RemoteSignerAddress::Tcp("127.0.0.1:9955".into()))
+		);
+		assert!("unix:".parse::<RemoteSignerAddress>().is_err());
+		assert!("tcp:localhost".parse::<RemoteSignerAddress>().is_err());
+		assert!("/tmp/signer.sock".parse::<RemoteSignerAddress>().is_err());
+	}
+
+	#[test]
+	#[cfg(target_family = "unix")]
+	fn connection_requires_same_secret() {
+		let (client, server) = UnixStream::pair().unwrap();
+		let secret = RemoteSignerSecret::generate();
+		let server_secret = secret.clone();
+		let server = thread::spawn(move || {
+			let mut connection = Connection::server(server, &server_secret).unwrap();
+			let message = connection.receive().unwrap();
+			connection.send(&message).unwrap();
+		});
+
+		let mut connection = Connection::client(client, &secret).unwrap();
+		connection.send(b"hello").unwrap();
+		assert_eq!(connection.receive().unwrap(), b"hello");
+		server.join().unwrap();
+
+		let (client, server) = UnixStream::pair().unwrap();
+		let server = thread::spawn(move || {
+			Connection::server(server, &RemoteSignerSecret::generate()).map(drop)
+		});
+		assert_eq!(
+			Connection::client(client, &secret).map(drop).unwrap_err().kind(),
+			io::ErrorKind::PermissionDenied,
+		);
+		assert!(server.join().unwrap().is_err());
+	}
+
+	#[test]
+	#[cfg(target_family = "unix")]
+	fn tampered_messages_are_rejected() {
+		let (client, server) = UnixStream::pair().unwrap();
+		let secret = RemoteSignerSecret::generate();
+		let server_secret = secret.clone();
+		let server = thread::spawn(move || {
+			let mut connection = Connection::server(server, &server_secret).unwrap();
+			connection.receive()
+		});
+
+		let mut connection = Connection::client(client, &secret).unwrap();
+		// Bypass the session key by writing a frame with a bogus tag.
+		connection.stream.write_all(&5u32.to_le_bytes()).unwrap();
+		connection.stream.write_all(b"hello").unwrap();
+		connection.stream.write_all(&[0; 32]).unwrap();
+		assert_eq!(server.join().unwrap().unwrap_err().kind(), io::ErrorKind::PermissionDenied);
+	}
+
+	#[test]
+	fn transcripts_are_rebuilt() {
+		let transcript = sr25519::vrf::VrfTranscriptData::new(
+			b"label",
+			&[(b"domain", b"data"), (b"other", b"")],
+		);
+
+		let encoded = Transcript::from(&transcript).encode();
+		let rebuilt = Transcript::decode(&mut &encoded[..])
+			.unwrap()
+			.into_vrf_transcript_data()
+			.unwrap();
+
+		assert_eq!(rebuilt, transcript);
+	}
+}
@@ -0,0 +1,287 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Remote signer side of the protocol.
+
+use super::protocol::{
+	Connection, RemoteError, RemoteSignerAddress, RemoteSignerSecret, Request, Response, Stream,
+	IO_TIMEOUT,
+};
+use codec::{Decode, Encode};
+use sp_core::{
+	crypto::{CryptoTypeId, KeyTypeId},
+	ecdsa, ed25519, sr25519,
+};
+use sp_keystore::{Keystore, KeystorePtr};
+use std::{io, net::TcpListener, sync::Arc, thread};
+
+const LOG_TARGET: &str = "remote-signer";
+
+/// Serve the keys of `keystore` to the nodes connecting to `address`.
+///
+/// Only nodes knowing `secret` are served. Every connection is handled in its own thread. This
+/// function only returns if listening on `address` fails.
+pub fn serve(
+	address: &RemoteSignerAddress,
+	secret: RemoteSignerSecret,
+	keystore: KeystorePtr,
+) -> io::Result<()> {
+	let secret = Arc::new(secret);
+	let mut listener = Listener::bind(address)?;
+	log::info!(target: LOG_TARGET, "Remote signer listening on {address}");
+
+	loop {
+		let stream = match listener.accept() {
+			Ok(stream) => stream,
+			Err(e) => {
+				log::warn!(target: LOG_TARGET, "Failed to accept connection: {e}");
+				continue
+			},
+		};
+
+		let secret = secret.clone();
+		let keystore = keystore.clone();
+		thread::Builder::new().name("remote-signer-connection".into()).spawn(move || {
+			if let Err(e) = handle_connection(stream, &secret, &*keystore) {
+				if e.kind() != io::ErrorKind::UnexpectedEof {
+					log::warn!(target: LOG_TARGET, "Connection closed: {e}");
+				}
+			}
+		})?;
+	}
+}
+
+enum Listener {
+	Tcp(TcpListener),
+	#[cfg(target_family = "unix")]
+	Unix(std::os::unix::net::UnixListener),
+}
+
+impl Listener {
+	fn bind(address: &RemoteSignerAddress) -> io::Result<Self> {
+		match address {
+			RemoteSignerAddress::Tcp(addr) => Ok(Self::Tcp(TcpListener::bind(addr.as_str())?)),
+			#[cfg(target_family = "unix")]
+			RemoteSignerAddress::Unix(path) => {
+				use std::os::unix::fs::{FileTypeExt, PermissionsExt};
+
+				// Remove the socket left over by a previous run, but never anything else.
+				if std::fs::symlink_metadata(path).map_or(false, |m| m.file_type().is_socket()) {
+					std::fs::remove_file(path)?;
+				}
+				let listener = std::os::unix::net::UnixListener::bind(path)?;
+				std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))?;
+				Ok(Self::Unix(listener))
+			},
+			#[cfg(not(target_family = "unix"))]
+			RemoteSignerAddress::Unix(_) => Err(io::Error::new(
+				io::ErrorKind::Unsupported,
+				"unix sockets are not supported on this platform",
+			)),
+		}
+	}
+
+	fn accept(&mut self) -> io::Result<Stream> {
+		let stream = match self {
+			Self::Tcp(listener) => {
+				let (stream, _) = listener.accept()?;
+				stream.set_nodelay(true)?;
+				Stream::Tcp(stream)
+			},
+			#[cfg(target_family = "unix")]
+			Self::Unix(listener) => Stream::Unix(listener.accept()?.0),
+		};
+		Ok(stream)
+	}
+}
+
+fn handle_connection(
+	stream: Stream,
+	secret: &RemoteSignerSecret,
+	keystore: &dyn Keystore,
+) -> io::Result<()> {
+	// Nodes may stay idle for a long time, only the handshake is bounded.
+	stream.set_timeout(Some(IO_TIMEOUT))?;
+	let mut connection = Connection::server(stream, secret)?;
+	connection.stream().set_timeout(None)?;
+
+	loop {
+		let message = connection.receive()?;
+		let request = Request::decode(&mut &message[..])
+			.map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+		log::trace!(target: LOG_TARGET, "Handling {request:?}");
+
+		let response: Response = handle_request(keystore, request);
+		connection.send(&response.encode())?;
+	}
+}
+
+/// Handle a single `request` using `keystore`.
+pub(super) fn handle_request(keystore: &dyn Keystore, request: Request) -> Response {
+	let response = match request {
+		Request::PublicKeys { crypto, key_type } => public_keys(keystore, crypto, key_type)?,
+		Request::GenerateNew { crypto, key_type } => generate_new(keystore, crypto, key_type)?,
+		Request::Sign { crypto, key_type, public, msg } =>
+			keystore.sign_with(key_type, crypto, &public, &msg)?.encode(),
+		Request::EcdsaSignPrehashed { key_type, public, msg } =>
+			keystore.ecdsa_sign_prehashed(key_type, &public, &msg)?.encode(),
+		Request::Sr25519VrfSign { key_type, public, transcript, extra } => {
+			let mut data = sr25519::vrf::VrfSignData::from(transcript.into_vrf_transcript_data()?);
+			if let Some(extra) = extra {
+				data = data.with_extra_data(extra.into_vrf_transcript_data()?);
+			}
+			keystore.sr25519_vrf_sign(key_type, &public, &data)?.encode()
+		},
+		Request::BandersnatchVrfSign { key_type, public, input, aux_data } =>
+			bandersnatch::vrf_sign(keystore, key_type, &public, &input, aux_data)?,
+		Request::BandersnatchVrfPreOutput { key_type, public, input } =>
+			bandersnatch::vrf_pre_output(keystore, key_type, &public, &input)?,
+		Request::EcdsaBls381SignWithKeccak256 { key_type, public, msg } =>
+			bls::ecdsa_bls381_sign_with_keccak256(keystore, key_type, &public, &msg)?,
+		Request::Keys { key_type } => keystore.keys(key_type)?.encode(),
+		Request::HasKeys { public_keys } => keystore.has_keys(&public_keys).encode(),
+	};
+	Ok(response)
+}
+
+fn public_keys(
+	keystore: &dyn Keystore,
+	crypto: CryptoTypeId,
+	key_type: KeyTypeId,
+) -> Result<Vec<u8>, RemoteError> {
+	let keys = match crypto {
+		sr25519::CRYPTO_ID => keystore.sr25519_public_keys(key_type).encode(),
+		ed25519::CRYPTO_ID => keystore.ed25519_public_keys(key_type).encode(),
+		ecdsa::CRYPTO_ID => keystore.ecdsa_public_keys(key_type).encode(),
+		#[cfg(feature = "bandersnatch-experimental")]
+		sp_core::bandersnatch::CRYPTO_ID => keystore.bandersnatch_public_keys(key_type).encode(),
+		#[cfg(feature = "bls-experimental")]
+		sp_core::bls381::CRYPTO_ID => keystore.bls381_public_keys(key_type).encode(),
+		#[cfg(feature = "bls-experimental")]
+		sp_core::ecdsa_bls381::CRYPTO_ID => keystore.ecdsa_bls381_public_keys(key_type).encode(),
+		_ => return Err(RemoteError::KeyNotSupported(key_type)),
+	};
+	Ok(keys)
+}
+
+fn generate_new(
+	keystore: &dyn Keystore,
+	crypto: CryptoTypeId,
+	key_type: KeyTypeId,
+) -> Result<Vec<u8>, RemoteError> {
+	let public = match crypto {
+		sr25519::CRYPTO_ID => keystore.sr25519_generate_new(key_type, None)?.encode(),
+		ed25519::CRYPTO_ID => keystore.ed25519_generate_new(key_type, None)?.encode(),
+		ecdsa::CRYPTO_ID => keystore.ecdsa_generate_new(key_type, None)?.encode(),
+		#[cfg(feature = "bandersnatch-experimental")]
+		sp_core::bandersnatch::CRYPTO_ID => keystore.bandersnatch_generate_new(key_type, None)?.encode(),
+		#[cfg(feature = "bls-experimental")]
+		sp_core::bls381::CRYPTO_ID => keystore.bls381_generate_new(key_type, None)?.encode(),
+		#[cfg(feature = "bls-experimental")]
+		sp_core::ecdsa_bls381::CRYPTO_ID => keystore.ecdsa_bls381_generate_new(key_type, None)?.encode(),
+		_ => return Err(RemoteError::KeyNotSupported(key_type)),
+	};
+	Ok(public)
+}
+
+#[cfg(any(feature = "bandersnatch-experimental", feature = "bls-experimental"))]
+fn decode<T: Decode>(mut bytes: &[u8]) -> Result<T, RemoteError> {
+	T::decode(&mut bytes).map_err(|e| RemoteError::ValidationError(e.to_string()))
+}
+
+#[cfg(feature = "bandersnatch-experimental")]
+mod bandersnatch {
+	use super::*;
+	use sp_core::bandersnatch::{vrf, Public};
+
+	pub(super) fn vrf_sign(
+		keystore: &dyn Keystore,
+		key_type: KeyTypeId,
+		public: &[u8],
+		input: &[u8],
+		aux_data: Vec<u8>,
+	) -> Result<Vec<u8>, RemoteError> {
+		let public = decode::<Public>(public)?;
+		let data = vrf::VrfSignData { vrf_input: decode(input)?, aux_data };
+		Ok(keystore.bandersnatch_vrf_sign(key_type, &public, &data)?.encode())
+	}
+
+	pub(super) fn vrf_pre_output(
+		keystore: &dyn Keystore,
+		key_type: KeyTypeId,
+		public: &[u8],
+		input: &[u8],
+	) -> Result<Vec<u8>, RemoteError> {
+		let public = decode::<Public>(public)?;
+		let input = decode::<vrf::VrfInput>(input)?;
+		Ok(keystore.bandersnatch_vrf_pre_output(key_type, &public, &input)?.encode())
+	}
+}
+
+#[cfg(not(feature = "bandersnatch-experimental"))]
+mod bandersnatch {
+	use super::*;
+
+	pub(super) fn vrf_sign(
+		_: &dyn Keystore,
+		key_type: KeyTypeId,
+		_: &[u8],
+		_: &[u8],
+		_: Vec<u8>,
+	) -> Result<Vec<u8>, RemoteError> {
+		Err(RemoteError::KeyNotSupported(key_type))
+	}
+
+	pub(super) fn vrf_pre_output(
+		_: &dyn Keystore,
+		key_type: KeyTypeId,
+		_: &[u8],
+		_: &[u8],
+	) -> Result<Vec<u8>, RemoteError> {
+		Err(RemoteError::KeyNotSupported(key_type))
+	}
+}
+
+#[cfg(feature = "bls-experimental")]
+mod bls {
+	use super::*;
+
+	pub(super) fn ecdsa_bls381_sign_with_keccak256(
+		keystore: &dyn Keystore,
+		key_type: KeyTypeId,
+		public: &[u8],
+		msg: &[u8],
+	) -> Result<Vec<u8>, RemoteError> {
+		let public = decode::<sp_core::ecdsa_bls381::Public>(public)?;
+		Ok(keystore.ecdsa_bls381_sign_with_keccak256(key_type, &public, msg)?.encode())
+	}
+}
+
+#[cfg(not(feature = "bls-experimental"))]
+mod bls {
+	use super::*;
+
+	pub(super) fn ecdsa_bls381_sign_with_keccak256(
+		_: &dyn Keystore,
+		key_type: KeyTypeId,
+		_: &[u8],
+		_: &[u8],
+	) -> Result<Vec<u8>, RemoteError> {
+		Err(RemoteError::KeyNotSupported(key_type))
+	}
+}
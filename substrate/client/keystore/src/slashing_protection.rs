@@ -0,0 +1,464 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Slashing protection of the signatures made by validators.
+//!
+//! The [`SlashingProtection`] store remembers, for every key and [`SigningDomain`], the last
+//! [`SigningPosition`] (slot or voting round) a message was signed at, together with the hash
+//! of the signed message. Consensus engines consult it before signing and refuse to sign a
+//! message which could lead to an equivocation:
+//!
+//! - a different message at the last signed position,
+//! - any message at a position lower than the last signed one.
+//!
+//! The second rule makes sure that a node restored from an old backup doesn't sign again for
+//! positions it already signed after the backup was made. The history can be moved between
+//! machines with [`SlashingProtection::export`] and [`SlashingProtection::import`].
+
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use sp_crypto_hashing::blake2_256;
+use std::{
+	collections::{btree_map::Entry, BTreeMap},
+	fmt, fs, io,
+	path::PathBuf,
+	sync::Arc,
+};
+
+use crate::encryption::write_private_file;
+
+/// Name of the file storing the slashing protection history inside the keystore directory.
+pub const SLASHING_PROTECTION_FILE: &str = "slashing_protection.json";
+
+/// Version of the [`Interchange`] format.
+const INTERCHANGE_VERSION: u32 = 1;
+
+/// A shared pointer to a [`SlashingProtection`] store.
+pub type SlashingProtectionPtr = Arc<SlashingProtection>;
+
+/// Slashing protection errors.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+	/// Signing the message could lead to an equivocation.
+	#[error(
+		"Refusing to sign {domain} message at {position}, a conflicting message was already \
+		 signed at {last}"
+	)]
+	Conflict {
+		/// Domain of the refused message.
+		domain: SigningDomain,
+		/// Position of the refused message.
+		position: SigningPosition,
+		/// Last position a message was signed at.
+		last: SigningPosition,
+	},
+	/// The interchange data is not supported.
+	#[error("Unsupported slashing protection interchange version {0}")]
+	UnsupportedVersion(u32),
+	/// The interchange data is invalid.
+	#[error("Invalid slashing protection interchange data: {0}")]
+	InvalidInterchange(String),
+	/// IO error.
+	#[error(transparent)]
+	Io(#[from] io::Error),
+	/// JSON error.
+	#[error(transparent)]
+	Json(#[from] serde_json::Error),
+}
+
+/// Kind of messages protected against equivocations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SigningDomain {
+	/// Block authored with BABE.
+	Babe,
+	/// Block authored with Aura.
+	Aura,
+	/// GRANDPA primary proposal.
+	GrandpaPrimaryPropose,
+	/// GRANDPA prevote.
+	GrandpaPrevote,
+	/// GRANDPA precommit.
+	GrandpaPrecommit,
+	/// BEEFY vote.
+	Beefy,
+}
+
+impl fmt::Display for SigningDomain {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let name = match self {
+			Self::Babe => "BABE block",
+			Self::Aura => "Aura block",
+			Self::GrandpaPrimaryPropose => "GRANDPA primary propose",
+			Self::GrandpaPrevote => "GRANDPA prevote",
+			Self::GrandpaPrecommit => "GRANDPA precommit",
+			Self::Beefy => "BEEFY vote",
+		};
+		f.write_str(name)
+	}
+}
+
+/// Position of a signed message within its [`SigningDomain`].
+///
+/// Positions are ordered by set id first, by round second and by sub-slot last.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct SigningPosition {
+	/// Authority set id, `0` for block authoring.
+	pub set_id: u64,
+	/// Slot for block authoring, round (GRANDPA) or block number (BEEFY) for votes.
+	pub round: u64,
+	/// Index of the block within its slot when several blocks are authored per slot, `0`
+	/// otherwise.
+	#[serde(default)]
+	pub sub_slot: u32,
+}
+
+impl SigningPosition {
+	/// Position of a block authored at `slot`.
+	pub fn slot(slot: u64) -> Self {
+		Self::sub_slot(slot, 0)
+	}
+
+	/// Position of the `index`-th block authored within `slot`.
+	pub fn sub_slot(slot: u64, index: u32) -> Self {
+		Self { set_id: 0, round: slot, sub_slot: index }
+	}
+
+	/// Position of a vote cast in `round` of the authority set `set_id`.
+	pub fn round(set_id: u64, round: u64) -> Self {
+		Self { set_id, round, sub_slot: 0 }
+	}
+}
+
+impl fmt::Display for SigningPosition {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "set {} round {}", self.set_id, self.round)?;
+		if self.sub_slot > 0 {
+			write!(f, " sub-slot {}", self.sub_slot)?;
+		}
+		Ok(())
+	}
+}
+
+/// Last message signed by a key in a [`SigningDomain`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignedRecord {
+	/// Domain of the message.
+	pub domain: SigningDomain,
+	/// Hex encoded public key the message was signed with.
+	pub public: String,
+	/// Position of the message.
+	pub position: SigningPosition,
+	/// Hex encoded blake2-256 hash of the message.
+	pub message_hash: String,
+}
+
+/// Slashing protection history, as exported and imported between nodes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Interchange {
+	/// Version of the format.
+	pub version: u32,
+	/// Last signed message of every key and domain.
+	pub records: Vec<SignedRecord>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Record {
+	position: SigningPosition,
+	message_hash: [u8; 32],
+}
+
+type Records = BTreeMap<(SigningDomain, Vec<u8>), Record>;
+
+/// Store of the last messages signed by the keys of a node.
+///
+/// See the [module documentation](self) for the rules that are enforced.
+pub struct SlashingProtection {
+	path: Option<PathBuf>,
+	records: Mutex<Records>,
+}
+
+impl SlashingProtection {
+	/// Open the store persisted at `path`, creating it if it doesn't exist.
+	pub fn open<T: Into<PathBuf>>(path: T) -> Result<Self, Error> {
+		let path = path.into();
+		let records = match fs::read(&path) {
+			Ok(data) => records_from_interchange(serde_json::from_slice(&data)?)?,
+			Err(e) if e.kind() == io::ErrorKind::NotFound => Records::new(),
+			Err(e) => return Err(e.into()),
+		};
+
+		Ok(Self { path: Some(path), records: Mutex::new(records) })
+	}
+
+	/// Create a new store which is not persisted.
+	pub fn in_memory() -> Self {
+		Self { path: None, records: Mutex::new(Records::new()) }
+	}
+
+	/// Check that `message` can be signed with `public` at `position` without risking an
+	/// equivocation, and record it as signed.
+	///
+	/// The record is persisted before returning, so the message must only be signed if this
+	/// returns `Ok(())`.
+	pub fn check_and_record(
+		&self,
+		domain: SigningDomain,
+		public: &[u8],
+		position: SigningPosition,
+		message: &[u8],
+	) -> Result<(), Error> {
+		let key = (domain, public.to_vec());
+		let record = Record { position, message_hash: blake2_256(message) };
+		let mut records = self.records.lock();
+
+		if let Some(last) = records.get(&key) {
+			if *last == record {
+				return Ok(())
+			}
+			if position <= last.position {
+				return Err(Error::Conflict { domain, position, last: last.position })
+			}
+		}
+
+		let previous = records.insert(key.clone(), record);
+		if let Err(e) = self.persist(&records) {
+			match previous {
+				Some(previous) => records.insert(key, previous),
+				None => records.remove(&key),
+			};
+			return Err(e)
+		}
+		Ok(())
+	}
+
+	/// Export the history of the signed messages.
+	pub fn export(&self) -> Interchange {
+		Self::interchange(&self.records.lock())
+	}
+
+	/// Merge the history exported by another node into this store.
+	///
+	/// Imported records only replace the local ones if they are at a higher position. Returns
+	/// the number of records which have been replaced or added.
+	pub fn import(&self, interchange: Interchange) -> Result<usize, Error> {
+		let mut records = self.records.lock();
+
+		let mut merged = records.clone();
+		let mut imported = 0;
+		for (key, record) in records_from_interchange(interchange)? {
+			if merge(&mut merged, key, record) {
+				imported += 1;
+			}
+		}
+
+		if imported > 0 {
+			self.persist(&merged)?;
+			*records = merged;
+		}
+		Ok(imported)
+	}
+
+	fn persist(&self, records: &Records) -> Result<(), Error> {
+		let Some(path) = &self.path else { return Ok(()) };
+
+		let data = serde_json::to_vec(&Self::interchange(records))?;
+		let mut tmp = path.clone().into_os_string();
+		tmp.push(".tmp");
+		write_private_file(tmp.as_ref(), &data)?;
+		fs::rename(tmp, path)?;
+		Ok(())
+	}
+
+	fn interchange(records: &Records) -> Interchange {
+		let records = records
+			.iter()
+			.map(|((domain, public), record)| SignedRecord {
+				domain: *domain,
+				public: array_bytes::bytes2hex("", public),
+				position: record.position,
+				message_hash: array_bytes::bytes2hex("", record.message_hash),
+			})
+			.collect();
+		Interchange { version: INTERCHANGE_VERSION, records }
+	}
+}
+
+fn records_from_interchange(interchange: Interchange) -> Result<Records, Error> {
+	if interchange.version != INTERCHANGE_VERSION {
+		return Err(Error::UnsupportedVersion(interchange.version))
+	}
+
+	let mut records = Records::new();
+	for record in interchange.records {
+		let public = array_bytes::hex2bytes(&record.public)
+			.map_err(|_| Error::InvalidInterchange(format!("invalid key {}", record.public)))?;
+		let message_hash = array_bytes::hex2array(&record.message_hash).map_err(|_| {
+			Error::InvalidInterchange(format!("invalid message hash {}", record.message_hash))
+		})?;
+
+		merge(
+			&mut records,
+			(record.domain, public),
+			Record { position: record.position, message_hash },
+		);
+	}
+	Ok(records)
+}
+
+/// Insert `record` into `records` unless there is already a record at the same or a higher
+/// position. Returns `true` if it was inserted.
+fn merge(records: &mut Records, key: (SigningDomain, Vec<u8>), record: Record) -> bool {
+	match records.entry(key) {
+		Entry::Occupied(entry) if entry.get().position >= record.position => false,
+		Entry::Occupied(mut entry) => {
+			entry.insert(record);
+			true
+		},
+		Entry::Vacant(entry) => {
+			entry.insert(record);
+			true
+		},
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use tempfile::TempDir;
+
+	const PUBLIC: &[u8] = &[1; 32];
+
+	#[test]
+	fn conflicting_messages_are_refused() {
+		let store = SlashingProtection::in_memory();
+		let domain = SigningDomain::GrandpaPrevote;
+
+		store
+			.check_and_record(domain, PUBLIC, SigningPosition::round(1, 5), b"a")
+			.unwrap();
+		// Signing the same message again is fine.
+		store
+			.check_and_record(domain, PUBLIC, SigningPosition::round(1, 5), b"a")
+			.unwrap();
+
+		assert!(matches!(
+			store.check_and_record(domain, PUBLIC, SigningPosition::round(1, 5), b"b"),
+			Err(Error::Conflict { .. })
+		));
+		assert!(matches!(
+			store.check_and_record(domain, PUBLIC, SigningPosition::round(1, 4), b"a"),
+			Err(Error::Conflict { .. })
+		));
+		assert!(matches!(
+			store.check_and_record(domain, PUBLIC, SigningPosition::round(0, 10), b"a"),
+			Err(Error::Conflict { .. })
+		));
+
+		// Other domains, keys and higher positions are not affected.
+		store
+			.check_and_record(
+				SigningDomain::GrandpaPrecommit,
+				PUBLIC,
+				SigningPosition::round(1, 5),
+				b"b",
+			)
+			.unwrap();
+		store
+			.check_and_record(domain, &[2; 32], SigningPosition::round(1, 5), b"b")
+			.unwrap();
+		store
+			.check_and_record(domain, PUBLIC, SigningPosition::round(1, 6), b"b")
+			.unwrap();
+		store
+			.check_and_record(domain, PUBLIC, SigningPosition::round(2, 1), b"b")
+			.unwrap();
+	}
+
+	#[test]
+	fn sub_slots_are_ordered_within_their_slot() {
+		let store = SlashingProtection::in_memory();
+		let domain = SigningDomain::Aura;
+
+		store.check_and_record(domain, PUBLIC, SigningPosition::slot(5), b"a").unwrap();
+		store
+			.check_and_record(domain, PUBLIC, SigningPosition::sub_slot(5, 1), b"b")
+			.unwrap();
+
+		assert!(store
+			.check_and_record(domain, PUBLIC, SigningPosition::sub_slot(5, 1), b"c")
+			.is_err());
+		assert!(store.check_and_record(domain, PUBLIC, SigningPosition::slot(5), b"c").is_err());
+		store.check_and_record(domain, PUBLIC, SigningPosition::slot(6), b"c").unwrap();
+
+		// Positions recorded without sub-slot are at the first sub-slot.
+		let position: SigningPosition =
+			serde_json::from_str(r#"{"set_id": 0, "round": 7}"#).unwrap();
+		assert_eq!(position, SigningPosition::slot(7));
+	}
+
+	#[test]
+	fn history_is_persisted() {
+		let temp_dir = TempDir::new().unwrap();
+		let path = temp_dir.path().join(SLASHING_PROTECTION_FILE);
+
+		let store = SlashingProtection::open(&path).unwrap();
+		store
+			.check_and_record(SigningDomain::Babe, PUBLIC, SigningPosition::slot(42), b"block")
+			.unwrap();
+		drop(store);
+
+		let store = SlashingProtection::open(&path).unwrap();
+		assert!(store
+			.check_and_record(SigningDomain::Babe, PUBLIC, SigningPosition::slot(42), b"other")
+			.is_err());
+		store
+			.check_and_record(SigningDomain::Babe, PUBLIC, SigningPosition::slot(42), b"block")
+			.unwrap();
+	}
+
+	#[test]
+	fn export_and_import() {
+		let old = SlashingProtection::in_memory();
+		old.check_and_record(SigningDomain::Beefy, PUBLIC, SigningPosition::round(3, 100), b"a")
+			.unwrap();
+		old.check_and_record(SigningDomain::Aura, PUBLIC, SigningPosition::slot(10), b"a")
+			.unwrap();
+
+		let new = SlashingProtection::in_memory();
+		new.check_and_record(SigningDomain::Aura, PUBLIC, SigningPosition::slot(20), b"b")
+			.unwrap();
+
+		let interchange: Interchange =
+			serde_json::from_slice(&serde_json::to_vec(&old.export()).unwrap()).unwrap();
+		// Only the BEEFY record is newer than the local history.
+		assert_eq!(new.import(interchange).unwrap(), 1);
+
+		assert!(new
+			.check_and_record(SigningDomain::Beefy, PUBLIC, SigningPosition::round(3, 99), b"b")
+			.is_err());
+		assert!(new
+			.check_and_record(SigningDomain::Aura, PUBLIC, SigningPosition::slot(15), b"b")
+			.is_err());
+		new.check_and_record(SigningDomain::Beefy, PUBLIC, SigningPosition::round(3, 101), b"b")
+			.unwrap();
+
+		let unsupported = Interchange { version: 2, records: vec![] };
+		assert!(matches!(new.import(unsupported), Err(Error::UnsupportedVersion(2))));
+	}
+}
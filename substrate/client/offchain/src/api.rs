@@ -22,6 +22,8 @@ use crate::NetworkProvider;
 use codec::{Decode, Encode};
 use futures::Future;
 pub use http::SharedClient;
+pub(crate) use http_policy::{HttpLimiter, HttpMetrics};
+pub use http_policy::{HttpRequestPolicy, UrlPattern};
 use sc_network::Multiaddr;
 use sc_network_types::PeerId;
 use sp_core::{
@@ -33,6 +35,7 @@ use sp_core::{
 };
 
 mod http;
+mod http_policy;
 
 mod timestamp;
 
@@ -201,8 +204,9 @@ impl AsyncApi {
 		network_provider: Arc<dyn NetworkProvider + Send + Sync>,
 		is_validator: bool,
 		shared_http_client: SharedClient,
+		http_limiter: Arc<HttpLimiter>,
 	) -> (Api, Self) {
-		let (http_api, http_worker) = http::http(shared_http_client);
+		let (http_api, http_worker) = http::http(shared_http_client, http_limiter);
 
 		let api = Api { network_provider, is_validator, http: http_api };
 
@@ -328,7 +332,7 @@ mod tests {
 		let mock = Arc::new(TestNetwork());
 		let shared_client = SharedClient::new().unwrap();
 
-		AsyncApi::new(mock, false, shared_client)
+		AsyncApi::new(mock, false, shared_client, Default::default())
 	}
 
 	fn offchain_db() -> OffchainDb<LocalStorage> {
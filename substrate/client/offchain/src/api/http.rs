@@ -27,7 +27,10 @@
 //! (i.e.: the socket should continue being processed) in the background even if the runtime isn't
 //! actively calling any function.
 
-use crate::api::timestamp;
+use crate::api::{
+	http_policy::{DenyReason, HttpLimiter},
+	timestamp,
+};
 use bytes::buf::{Buf, Reader};
 use fnv::FnvHashMap;
 use futures::{channel::mpsc, future, prelude::*};
@@ -72,7 +75,13 @@ impl SharedClient {
 }
 
 /// Creates a pair of [`HttpApi`] and [`HttpWorker`].
-pub fn http(shared_client: SharedClient) -> (HttpApi, HttpWorker) {
+///
+/// The requests started through the [`HttpApi`] are restricted by the given `limiter`, which is
+/// shared with the other offchain worker runs.
+pub(crate) fn http(
+	shared_client: SharedClient,
+	limiter: Arc<HttpLimiter>,
+) -> (HttpApi, HttpWorker) {
 	let (to_worker, from_api) = tracing_unbounded("mpsc_ocw_to_worker", 100_000);
 	let (to_api, from_worker) = tracing_unbounded("mpsc_ocw_to_api", 100_000);
 
@@ -83,6 +92,7 @@ pub fn http(shared_client: SharedClient) -> (HttpApi, HttpWorker) {
 		// writing runtime code with hardcoded IDs.
 		next_id: HttpRequestId(rand::random::<u16>() % 2000),
 		requests: FnvHashMap::default(),
+		limiter,
 	};
 
 	let engine =
@@ -105,6 +115,8 @@ pub struct HttpApi {
 	next_id: HttpRequestId,
 	/// List of HTTP requests in preparation or in progress.
 	requests: FnvHashMap<HttpRequestId, HttpApiRequest>,
+	/// Restrictions of the requests, shared by all offchain worker runs.
+	limiter: Arc<HttpLimiter>,
 }
 
 /// One active request within `HttpApi`.
@@ -156,6 +168,11 @@ impl HttpApi {
 		*request.method_mut() = hyper::Method::from_bytes(method.as_bytes()).map_err(|_| ())?;
 		*request.uri_mut() = hyper::Uri::from_maybe_shared(uri.to_owned()).map_err(|_| ())?;
 
+		if let Err(reason) = self.limiter.start_request(request.uri()) {
+			self.deny(reason, uri);
+			return Err(())
+		}
+
 		let new_id = self.next_id;
 		debug_assert!(!self.requests.contains_key(&new_id));
 		match self.next_id.0.checked_add(1) {
@@ -216,6 +233,11 @@ impl HttpApi {
 		// Don't forget to add it back if necessary when returning.
 		let mut request = self.requests.remove(&request_id).ok_or(HttpError::Invalid)?;
 
+		if !self.limiter.consume_bandwidth(chunk.len()) {
+			self.deny(DenyReason::BandwidthExceeded, &format!("request {}", request_id.0));
+			return Err(HttpError::IoError)
+		}
+
 		let mut deadline = timestamp::deadline_to_future(deadline);
 		// Closure that writes data to a sender, taking the deadline into account. Can return `Ok`
 		// (if the body has been written), or `DeadlineReached`, or `IoError`.
@@ -520,6 +542,20 @@ impl HttpApi {
 			None => return Err(HttpError::Invalid),
 		};
 
+		// Never read more than the remaining bandwidth. The request is dropped if there's none
+		// left, as reading nothing would be interpreted as the end of the body.
+		let buffer = match self.limiter.remaining_bandwidth() {
+			Some(0) if !buffer.is_empty() => {
+				self.deny(DenyReason::BandwidthExceeded, &format!("request {}", request_id.0));
+				return Err(HttpError::IoError)
+			},
+			Some(remaining) => {
+				let len = buffer.len().min(usize::try_from(remaining).unwrap_or(usize::MAX));
+				&mut buffer[..len]
+			},
+			None => buffer,
+		};
+
 		// Convert the deadline into a `Future` that resolves when the deadline is reached.
 		let mut deadline = timestamp::deadline_to_future(deadline);
 
@@ -529,6 +565,7 @@ impl HttpApi {
 				match current_read_chunk.read(buffer) {
 					Ok(0) => {},
 					Ok(n) => {
+						let _ = self.limiter.consume_bandwidth(n);
 						self.requests.insert(
 							request_id,
 							HttpApiRequest::Response(HttpApiRequestRp {
@@ -571,6 +608,14 @@ impl HttpApi {
 	}
 }
 
+impl HttpApi {
+	/// Records a request denied by the policy.
+	fn deny(&self, reason: DenyReason, target: &str) {
+		tracing::debug!(target: LOG_TARGET, ?reason, %target, "Request denied by the node policy");
+		self.limiter.report_denied(reason);
+	}
+}
+
 impl fmt::Debug for HttpApi {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
 		f.debug_list().entries(self.requests.iter()).finish()
@@ -775,12 +820,12 @@ mod tests {
 		super::{tests::TestNetwork, AsyncApi},
 		*,
 	};
-	use crate::api::timestamp;
+	use crate::api::{http_policy::HttpRequestPolicy, timestamp};
 	use core::convert::Infallible;
 	use futures::future;
 	use http_body_util::BodyExt;
 	use sp_core::offchain::{Duration, Externalities, HttpError, HttpRequestId, HttpRequestStatus};
-	use std::sync::LazyLock;
+	use std::{num::NonZeroU32, sync::LazyLock};
 
 	// Using LazyLock to avoid spawning lots of different SharedClients,
 	// as spawning a SharedClient is CPU-intensive and opens lots of fds.
@@ -794,9 +839,12 @@ mod tests {
 				hyper::body::Bytes::from("Hello World!")
 			)))
 		};
-		( $response:expr ) => {{
+		( $response:expr ) => {
+			build_api_server!(Default::default(), $response)
+		};
+		( $limiter:expr, $response:expr ) => {{
 			let hyper_client = SHARED_CLIENT.clone();
-			let (api, worker) = http(hyper_client.clone());
+			let (api, worker) = http(hyper_client.clone(), $limiter);
 
 			let (addr_tx, addr_rx) = std::sync::mpsc::channel();
 			std::thread::spawn(move || {
@@ -1124,13 +1172,105 @@ mod tests {
 		}
 	}
 
+	fn hello_world() -> hyper::Response<http_body_util::Full<hyper::body::Bytes>> {
+		hyper::Response::new(http_body_util::Full::new(hyper::body::Bytes::from("Hello World!")))
+	}
+
+	#[test]
+	fn requests_outside_allow_list_are_denied() {
+		let policy = HttpRequestPolicy {
+			allow_list: Some(vec!["127.0.0.1".parse().unwrap()]),
+			..Default::default()
+		};
+		let (mut api, addr) =
+			build_api_server!(Arc::new(HttpLimiter::new(policy, None)), hello_world());
+
+		assert!(api.request_start("GET", "http://localhost:1234").is_err());
+		assert!(api.request_start("GET", "https://example.com").is_err());
+		assert!(api.request_start("GET", &format!("http://{}", addr)).is_ok());
+	}
+
+	#[test]
+	fn requests_exceeding_rate_are_denied() {
+		let policy = HttpRequestPolicy {
+			max_requests_per_second: Some(NonZeroU32::new(2).unwrap()),
+			..Default::default()
+		};
+		let (mut api, addr) =
+			build_api_server!(Arc::new(HttpLimiter::new(policy, None)), hello_world());
+		let url = format!("http://{}", addr);
+
+		assert!(api.request_start("GET", &url).is_ok());
+		assert!(api.request_start("GET", &url).is_ok());
+		assert!(api.request_start("GET", &url).is_err());
+
+		std::thread::sleep(std::time::Duration::from_millis(1100));
+		assert!(api.request_start("GET", &url).is_ok());
+	}
+
+	#[test]
+	fn limits_are_shared_by_offchain_worker_runs() {
+		let policy = HttpRequestPolicy {
+			max_requests_per_second: Some(NonZeroU32::new(2).unwrap()),
+			max_bandwidth: Some(8),
+			..Default::default()
+		};
+		let limiter = Arc::new(HttpLimiter::new(policy, None));
+		let (mut api, addr) = build_api_server!(limiter.clone(), hello_world());
+		let url = format!("http://{}", addr);
+		let deadline = timestamp::now().add(Duration::from_millis(10_000));
+
+		let id = api.request_start("POST", &url).unwrap();
+		api.request_write_body(id, &[1, 2, 3, 4, 5, 6], Some(deadline)).unwrap();
+		assert!(api.request_start("GET", &url).is_ok());
+		drop(api);
+
+		// The next run, e.g. for the following block, can't go over the limits either.
+		let (mut api, _) = http(SHARED_CLIENT.clone(), limiter);
+		assert!(api.request_start("GET", &url).is_err());
+
+		std::thread::sleep(std::time::Duration::from_millis(1100));
+		let id = api.request_start("POST", &url).unwrap();
+		api.request_write_body(id, &[1, 2, 3, 4, 5, 6, 7, 8], Some(deadline)).unwrap();
+		assert_eq!(api.request_write_body(id, &[1], Some(deadline)), Err(HttpError::IoError));
+	}
+
+	#[test]
+	fn bandwidth_limit_is_enforced() {
+		let deadline = timestamp::now().add(Duration::from_millis(10_000));
+		let policy = HttpRequestPolicy { max_bandwidth: Some(8), ..Default::default() };
+		let (mut api, addr) =
+			build_api_server!(Arc::new(HttpLimiter::new(policy, None)), hello_world());
+
+		let id = api.request_start("POST", &format!("http://{}", addr)).unwrap();
+		api.request_write_body(id, &[1, 2, 3], Some(deadline)).unwrap();
+		api.request_write_body(id, &[], Some(deadline)).unwrap();
+
+		match api.response_wait(&[id], Some(deadline))[0] {
+			HttpRequestStatus::Finished(200) => {},
+			v => panic!("Connecting to localhost failed: {:?}", v),
+		}
+
+		// Only 5 bytes of bandwidth are left.
+		let mut buf = vec![0; 2048];
+		let n = api.response_read_body(id, &mut buf, Some(deadline)).unwrap();
+		assert_eq!(&buf[..n], b"Hello");
+		assert_eq!(api.response_read_body(id, &mut buf, Some(deadline)), Err(HttpError::IoError));
+
+		// The request is gone and no more data can be sent.
+		assert_eq!(api.response_read_body(id, &mut buf, Some(deadline)), Err(HttpError::Invalid));
+		let id = api.request_start("POST", &format!("http://{}", addr)).unwrap();
+		assert_eq!(api.request_write_body(id, &[1], Some(deadline)), Err(HttpError::IoError));
+	}
+
 	#[test]
 	fn shared_http_client_is_only_initialized_on_access() {
 		let shared_client = SharedClient::new().unwrap();
 
 		{
 			let mock = Arc::new(TestNetwork());
-			let (mut api, async_api) = AsyncApi::new(mock, false, shared_client.clone());
+			let (mut api, async_api) =
+				AsyncApi::new(mock, false, shared_client.clone(), Default::default());
 			api.timestamp();
 
 			futures::executor::block_on(async move {
@@ -1145,7 +1285,8 @@ mod tests {
 
 		{
 			let mock = Arc::new(TestNetwork());
-			let (mut api, async_api) = AsyncApi::new(mock, false, shared_client.clone());
+			let (mut api, async_api) =
+				AsyncApi::new(mock, false, shared_client.clone(), Default::default());
 			let id = api.http_request_start("lol", "nope", &[]).unwrap();
 			api.http_request_write_body(id, &[], None).unwrap();
 			futures::executor::block_on(async move {
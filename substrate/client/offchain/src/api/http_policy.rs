@@ -0,0 +1,351 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Node-level restrictions of the HTTP requests made by offchain workers.
+//!
+//! The [`HttpRequestPolicy`] is enforced by a single [`HttpLimiter`] shared by the
+//! [`HttpApi`](super::http::HttpApi) of every offchain worker run, so the limits apply to all the
+//! offchain workers of the node together and don't reset with every new block.
+
+use parking_lot::Mutex;
+use prometheus_endpoint::{register, CounterVec, Opts, PrometheusError, Registry, U64};
+use std::{
+	collections::VecDeque,
+	fmt,
+	num::NonZeroU32,
+	str::FromStr,
+	time::{Duration, Instant},
+};
+
+/// The window over which the request rate and bandwidth are limited.
+const LIMIT_WINDOW: Duration = Duration::from_secs(1);
+
+/// Restrictions applied to the HTTP requests made by offchain workers.
+#[derive(Debug, Clone, Default)]
+pub struct HttpRequestPolicy {
+	/// URLs the offchain workers are allowed to contact.
+	///
+	/// All URLs are allowed if `None`.
+	pub allow_list: Option<Vec<UrlPattern>>,
+	/// Maximum number of HTTP requests all offchain workers together can start per second.
+	///
+	/// Requests exceeding this rate are denied.
+	pub max_requests_per_second: Option<NonZeroU32>,
+	/// Maximum number of body bytes all offchain workers together can send and receive per
+	/// second.
+	///
+	/// Requests are failed once this limit is reached.
+	pub max_bandwidth: Option<u64>,
+}
+
+impl HttpRequestPolicy {
+	/// Returns `true` if the allow-list permits requests to the given `uri`.
+	pub fn is_allowed(&self, uri: &hyper::Uri) -> bool {
+		self.allow_list
+			.as_ref()
+			.map_or(true, |allow_list| allow_list.iter().any(|pattern| pattern.matches(uri)))
+	}
+}
+
+/// A pattern matching the URLs offchain workers are allowed to contact.
+///
+/// Parsed from either:
+/// - a host name, e.g. `example.com`, matching all URLs of this exact host,
+/// - a host name prefixed with `*.`, e.g. `*.example.com`, matching all URLs of its subdomains,
+/// - a URL with scheme, e.g. `https://example.com/api/`, matching all URLs with the same scheme and
+///   authority whose path starts with the given path.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UrlPattern {
+	/// Matches the given host.
+	Host(String),
+	/// Matches all subdomains of the given domain, but not the domain itself.
+	Subdomains(String),
+	/// Matches all URLs with the given scheme and authority, whose path starts with `path`.
+	Prefix {
+		/// Scheme of the URL, e.g. `https`.
+		scheme: String,
+		/// Authority of the URL, e.g. `example.com:8080`.
+		authority: String,
+		/// Prefix of the path of the URL.
+		path: String,
+	},
+}
+
+impl UrlPattern {
+	/// Returns `true` if the given `uri` matches this pattern.
+	pub fn matches(&self, uri: &hyper::Uri) -> bool {
+		match self {
+			Self::Host(host) => uri.host().map_or(false, |h| h.eq_ignore_ascii_case(host)),
+			Self::Subdomains(domain) => uri.host().map_or(false, |h| {
+				let h = h.to_ascii_lowercase();
+				h.strip_suffix(domain.as_str()).map_or(false, |sub| sub.ends_with('.'))
+			}),
+			Self::Prefix { scheme, authority, path } =>
+				uri.scheme_str().map_or(false, |s| s.eq_ignore_ascii_case(scheme)) &&
+					uri.authority().map_or(false, |a| a.as_str().eq_ignore_ascii_case(authority)) &&
+					uri.path().starts_with(path.as_str()),
+		}
+	}
+}
+
+impl FromStr for UrlPattern {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		if s.contains("://") {
+			let uri = hyper::Uri::from_str(s).map_err(|e| format!("Invalid URL `{s}`: {e}"))?;
+			let (Some(scheme), Some(authority)) = (uri.scheme_str(), uri.authority()) else {
+				return Err(format!("URL `{s}` must contain a scheme and a host"))
+			};
+
+			return Ok(Self::Prefix {
+				scheme: scheme.to_ascii_lowercase(),
+				authority: authority.as_str().to_ascii_lowercase(),
+				path: uri.path().to_owned(),
+			})
+		}
+
+		let (host, subdomains) = match s.strip_prefix("*.") {
+			Some(domain) => (domain, true),
+			None => (s, false),
+		};
+
+		if host.is_empty() ||
+			!host.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '.')
+		{
+			return Err(format!("Invalid host pattern `{s}`"))
+		}
+
+		let host = host.to_ascii_lowercase();
+		Ok(if subdomains { Self::Subdomains(host) } else { Self::Host(host) })
+	}
+}
+
+impl fmt::Display for UrlPattern {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::Host(host) => write!(f, "{host}"),
+			Self::Subdomains(domain) => write!(f, "*.{domain}"),
+			Self::Prefix { scheme, authority, path } => write!(f, "{scheme}://{authority}{path}"),
+		}
+	}
+}
+
+/// The reason why an HTTP request was denied by the [`HttpRequestPolicy`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum DenyReason {
+	/// The URL is not part of the allow-list.
+	NotAllowed,
+	/// Too many requests have been started in the last second.
+	RateLimited,
+	/// The bandwidth limit has been reached.
+	BandwidthExceeded,
+}
+
+impl DenyReason {
+	fn as_str(&self) -> &'static str {
+		match self {
+			Self::NotAllowed => "not_allowed",
+			Self::RateLimited => "rate_limited",
+			Self::BandwidthExceeded => "bandwidth_exceeded",
+		}
+	}
+}
+
+/// Enforces a [`HttpRequestPolicy`] across all the offchain worker runs of a node.
+pub(crate) struct HttpLimiter {
+	policy: HttpRequestPolicy,
+	metrics: Option<HttpMetrics>,
+	state: Mutex<LimiterState>,
+}
+
+/// The usage of the limited resources, shared by all offchain worker runs.
+struct LimiterState {
+	/// Start times of the requests started during the last [`LIMIT_WINDOW`].
+	recent_requests: VecDeque<Instant>,
+	/// Start of the current bandwidth window.
+	bandwidth_window_start: Instant,
+	/// Number of body bytes sent and received in the current bandwidth window.
+	bandwidth_used: u64,
+}
+
+impl LimiterState {
+	/// Starts a new bandwidth window if the current one is over.
+	fn refresh_bandwidth_window(&mut self, now: Instant) {
+		if now.saturating_duration_since(self.bandwidth_window_start) >= LIMIT_WINDOW {
+			self.bandwidth_window_start = now;
+			self.bandwidth_used = 0;
+		}
+	}
+}
+
+impl Default for HttpLimiter {
+	fn default() -> Self {
+		Self::new(Default::default(), None)
+	}
+}
+
+impl HttpLimiter {
+	/// Creates a new limiter enforcing `policy`, reporting denied requests to `metrics`.
+	pub(crate) fn new(policy: HttpRequestPolicy, metrics: Option<HttpMetrics>) -> Self {
+		Self {
+			policy,
+			metrics,
+			state: Mutex::new(LimiterState {
+				recent_requests: VecDeque::new(),
+				bandwidth_window_start: Instant::now(),
+				bandwidth_used: 0,
+			}),
+		}
+	}
+
+	/// Checks whether a new request to `uri` can be started, and accounts for it if so.
+	pub(crate) fn start_request(&self, uri: &hyper::Uri) -> Result<(), DenyReason> {
+		if !self.policy.is_allowed(uri) {
+			return Err(DenyReason::NotAllowed)
+		}
+
+		if let Some(max_requests) = self.policy.max_requests_per_second {
+			let now = Instant::now();
+			let mut state = self.state.lock();
+			while state
+				.recent_requests
+				.front()
+				.map_or(false, |start| now.saturating_duration_since(*start) >= LIMIT_WINDOW)
+			{
+				state.recent_requests.pop_front();
+			}
+
+			if state.recent_requests.len() >= max_requests.get() as usize {
+				return Err(DenyReason::RateLimited)
+			}
+			state.recent_requests.push_back(now);
+		}
+
+		Ok(())
+	}
+
+	/// Returns the number of body bytes which can still be sent or received, if limited.
+	pub(crate) fn remaining_bandwidth(&self) -> Option<u64> {
+		let max_bandwidth = self.policy.max_bandwidth?;
+		let mut state = self.state.lock();
+		state.refresh_bandwidth_window(Instant::now());
+		Some(max_bandwidth.saturating_sub(state.bandwidth_used))
+	}
+
+	/// Accounts for `len` body bytes, returns `false` if this exceeds the bandwidth limit.
+	pub(crate) fn consume_bandwidth(&self, len: usize) -> bool {
+		let Some(max_bandwidth) = self.policy.max_bandwidth else { return true };
+		let len = len as u64;
+		let mut state = self.state.lock();
+		state.refresh_bandwidth_window(Instant::now());
+		if max_bandwidth.saturating_sub(state.bandwidth_used) < len {
+			return false
+		}
+		state.bandwidth_used = state.bandwidth_used.saturating_add(len);
+		true
+	}
+
+	/// Records a request denied by the policy.
+	pub(crate) fn report_denied(&self, reason: DenyReason) {
+		if let Some(metrics) = &self.metrics {
+			metrics.report_denied(reason);
+		}
+	}
+}
+
+/// Prometheus metrics of the offchain HTTP requests.
+#[derive(Clone)]
+pub(crate) struct HttpMetrics {
+	denied_requests: CounterVec<U64>,
+}
+
+impl HttpMetrics {
+	pub(crate) fn register(registry: &Registry) -> Result<Self, PrometheusError> {
+		Ok(Self {
+			denied_requests: register(
+				CounterVec::new(
+					Opts::new(
+						"substrate_offchain_http_requests_denied_total",
+						"Number of offchain worker HTTP requests denied by the node policy",
+					),
+					&["reason"],
+				)?,
+				registry,
+			)?,
+		})
+	}
+
+	pub(crate) fn report_denied(&self, reason: DenyReason) {
+		self.denied_requests.with_label_values(&[reason.as_str()]).inc();
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn uri(s: &str) -> hyper::Uri {
+		hyper::Uri::from_str(s).unwrap()
+	}
+
+	#[test]
+	fn parse_url_patterns() {
+		assert_eq!("Example.com".parse(), Ok(UrlPattern::Host("example.com".into())));
+		assert_eq!("*.example.com".parse(), Ok(UrlPattern::Subdomains("example.com".into())));
+		assert_eq!(
+			"https://example.com:8080/api/".parse(),
+			Ok(UrlPattern::Prefix {
+				scheme: "https".into(),
+				authority: "example.com:8080".into(),
+				path: "/api/".into(),
+			})
+		);
+		assert!("".parse::<UrlPattern>().is_err());
+		assert!("*.".parse::<UrlPattern>().is_err());
+		assert!("example.com/api".parse::<UrlPattern>().is_err());
+	}
+
+	#[test]
+	fn url_patterns_match() {
+		let host: UrlPattern = "example.com".parse().unwrap();
+		assert!(host.matches(&uri("https://EXAMPLE.com/foo")));
+		assert!(!host.matches(&uri("https://api.example.com/foo")));
+		assert!(!host.matches(&uri("https://example.com.evil.org/foo")));
+
+		let subdomains: UrlPattern = "*.example.com".parse().unwrap();
+		assert!(subdomains.matches(&uri("https://api.example.com/foo")));
+		assert!(!subdomains.matches(&uri("https://example.com/foo")));
+		assert!(!subdomains.matches(&uri("https://evilexample.com/foo")));
+
+		let prefix: UrlPattern = "https://example.com/api/".parse().unwrap();
+		assert!(prefix.matches(&uri("https://example.com/api/v1?x=1")));
+		assert!(!prefix.matches(&uri("http://example.com/api/v1")));
+		assert!(!prefix.matches(&uri("https://example.com/other")));
+		assert!(!prefix.matches(&uri("https://example.com.evil.org/api/v1")));
+	}
+
+	#[test]
+	fn everything_allowed_without_allow_list() {
+		let policy = HttpRequestPolicy::default();
+		assert!(policy.is_allowed(&uri("https://anything.org")));
+
+		let policy = HttpRequestPolicy { allow_list: Some(vec![]), ..Default::default() };
+		assert!(!policy.is_allowed(&uri("https://anything.org")));
+	}
+}
@@ -42,6 +42,7 @@ use futures::{
 	prelude::*,
 };
 use parking_lot::Mutex;
+use prometheus_endpoint::Registry;
 use sc_client_api::BlockchainEvents;
 use sc_network::{NetworkPeers, NetworkStateInfo};
 use sc_transaction_pool_api::OffchainTransactionPoolFactory;
@@ -54,6 +55,7 @@ use threadpool::ThreadPool;
 
 mod api;
 
+pub use api::{HttpRequestPolicy, UrlPattern};
 pub use sp_core::offchain::storage::OffchainDb;
 pub use sp_offchain::{OffchainWorkerApi, STORAGE_PREFIX};
 
@@ -110,6 +112,10 @@ pub struct OffchainWorkerOptions<RA, Block: traits::Block, Storage, CE> {
 	///
 	/// If not enabled, any http request will panic.
 	pub enable_http_requests: bool,
+	/// Restrictions applied to the http requests of the offchain workers.
+	pub http_request_policy: HttpRequestPolicy,
+	/// Registry to register the offchain worker metrics with.
+	pub prometheus_registry: Option<Registry>,
 	/// Callback to create custom [`Extension`]s that should be registered for the
 	/// `offchain_worker` runtime call.
 	///
@@ -132,6 +138,7 @@ pub struct OffchainWorkers<RA, Block: traits::Block, Storage> {
 	thread_pool: Mutex<ThreadPool>,
 	shared_http_client: api::SharedClient,
 	enable_http_requests: bool,
+	http_limiter: Arc<api::HttpLimiter>,
 	keystore: Option<KeystorePtr>,
 	offchain_db: Option<OffchainDb<Storage>>,
 	transaction_pool: Option<OffchainTransactionPoolFactory<Block>>,
@@ -151,9 +158,22 @@ impl<RA, Block: traits::Block, Storage> OffchainWorkers<RA, Block, Storage> {
 			network_provider,
 			is_validator,
 			enable_http_requests,
+			http_request_policy,
+			prometheus_registry,
 			custom_extensions,
 		}: OffchainWorkerOptions<RA, Block, Storage, CE>,
 	) -> std::io::Result<Self> {
+		let http_metrics = prometheus_registry.as_ref().and_then(|registry| {
+			api::HttpMetrics::register(registry)
+				.map_err(|error| {
+					tracing::error!(
+						target: LOG_TARGET,
+						"Failed to register offchain worker metrics: {error}",
+					)
+				})
+				.ok()
+		});
+
 		Ok(Self {
 			runtime_api_provider,
 			thread_pool: Mutex::new(ThreadPool::with_name(
@@ -162,6 +182,7 @@ impl<RA, Block: traits::Block, Storage> OffchainWorkers<RA, Block, Storage> {
 			)),
 			shared_http_client: api::SharedClient::new()?,
 			enable_http_requests,
+			http_limiter: Arc::new(api::HttpLimiter::new(http_request_policy, http_metrics)),
 			keystore,
 			offchain_db: offchain_db.map(OffchainDb::new),
 			transaction_pool,
@@ -247,6 +268,7 @@ where
 				self.network_provider.clone(),
 				self.is_validator,
 				self.shared_http_client.clone(),
+				self.http_limiter.clone(),
 			);
 			tracing::debug!(target: LOG_TARGET, "Spawning offchain workers at {hash:?}");
 			let header = header.clone();
@@ -465,6 +487,8 @@ mod tests {
 			network_provider: network,
 			is_validator: false,
 			enable_http_requests: false,
+			http_request_policy: Default::default(),
+			prometheus_registry: None,
 			custom_extensions: |_| Vec::new(),
 		})
 		.unwrap();
@@ -52,6 +52,8 @@ pub enum EndProposingReason {
 	HitBlockWeightLimit,
 	/// No transactions are allowed in the block.
 	TransactionForbidden,
+	/// The maximum number of transactions per block was reached.
+	HitTransactionLimit,
 }
 
 /// Authorship metrics.
@@ -116,6 +118,7 @@ impl Metrics {
 			EndProposingReason::HitBlockSizeLimit => "hit_block_size_limit",
 			EndProposingReason::HitBlockWeightLimit => "hit_block_weight_limit",
 			EndProposingReason::TransactionForbidden => "transactions_forbidden",
+			EndProposingReason::HitTransactionLimit => "hit_transaction_limit",
 		};
 
 		self.end_proposing_reason.with_label_values(&[reason]).inc();
@@ -40,7 +40,10 @@ use sc_executor::{
 	sp_wasm_interface::HostFunctions, HeapAllocStrategy, NativeExecutionDispatch, RuntimeVersionOf,
 	WasmExecutor, DEFAULT_HEAP_ALLOC_STRATEGY,
 };
-use sc_keystore::LocalKeystore;
+use sc_keystore::{
+	slashing_protection::SLASHING_PROTECTION_FILE, LocalKeystore, RemoteKeystore,
+	SlashingProtection, SlashingProtectionPtr,
+};
 use sc_network::{
 	config::{FullNetworkConfiguration, ProtocolId, SyncMode},
 	multiaddr::Protocol,
@@ -110,28 +113,70 @@ type TFullParts<TBl, TRtApi, TExec> =
 	(TFullClient<TBl, TRtApi, TExec>, Arc<TFullBackend<TBl>>, KeystoreContainer, TaskManager);
 
 /// Construct a local keystore shareable container
-pub struct KeystoreContainer(Arc<LocalKeystore>);
+pub struct KeystoreContainer {
+	keystore: KeystorePtr,
+	local: Option<Arc<LocalKeystore>>,
+	slashing_protection: SlashingProtectionPtr,
+}
 
 impl KeystoreContainer {
 	/// Construct KeystoreContainer
 	pub fn new(config: &KeystoreConfig) -> Result<Self, Error> {
-		let keystore = Arc::new(match config {
-			KeystoreConfig::Path { path, password } =>
-				LocalKeystore::open(path.clone(), password.clone())?,
-			KeystoreConfig::InMemory => LocalKeystore::in_memory(),
-		});
+		let local = |keystore: LocalKeystore, slashing_protection: SlashingProtection| {
+			let keystore = Arc::new(keystore);
+			Self {
+				keystore: keystore.clone(),
+				local: Some(keystore),
+				slashing_protection: Arc::new(slashing_protection),
+			}
+		};
 
-		Ok(Self(keystore))
+		Ok(match config {
+			KeystoreConfig::Path { path, password, encryption_password } => local(
+				LocalKeystore::open_with_encryption(
+					path.clone(),
+					password.clone(),
+					encryption_password.clone(),
+				)?,
+				SlashingProtection::open(path.join(SLASHING_PROTECTION_FILE))?,
+			),
+			KeystoreConfig::InMemory =>
+				local(LocalKeystore::in_memory(), SlashingProtection::in_memory()),
+			KeystoreConfig::Remote { address, secret, path } => {
+				let keystore = RemoteKeystore::open(address.clone(), secret.clone())?;
+				info!("🔑 Using remote signer at {address}");
+				std::fs::create_dir_all(path)?;
+				Self {
+					keystore: Arc::new(keystore),
+					local: None,
+					slashing_protection: Arc::new(SlashingProtection::open(
+						path.join(SLASHING_PROTECTION_FILE),
+					)?),
+				}
+			},
+		})
 	}
 
 	/// Returns a shared reference to a dynamic `Keystore` trait implementation.
 	pub fn keystore(&self) -> KeystorePtr {
-		self.0.clone()
+		self.keystore.clone()
+	}
+
+	/// Returns a shared reference to the local keystore.
+	///
+	/// Returns `None` when a remote signer is used, as the keys are then only accessible through
+	/// [`Self::keystore`]. Components that need direct access to the keys can't be used in this
+	/// case.
+	pub fn local_keystore(&self) -> Option<Arc<LocalKeystore>> {
+		self.local.clone()
 	}
 
-	/// Returns a shared reference to the local keystore .
-	pub fn local_keystore(&self) -> Arc<LocalKeystore> {
-		self.0.clone()
+	/// Returns a shared reference to the slashing protection store of the keystore.
+	///
+	/// It is persisted next to the keys of a keystore stored on disk, and kept in memory
+	/// otherwise.
+	pub fn slashing_protection(&self) -> SlashingProtectionPtr {
+		self.slashing_protection.clone()
 	}
 }
 
@@ -23,6 +23,7 @@ use prometheus_endpoint::Registry;
 use sc_chain_spec::ChainSpec;
 pub use sc_client_db::{BlocksPruning, Database, DatabaseSource, PruningMode};
 pub use sc_executor::{WasmExecutionMethod, WasmtimeInstantiationStrategy};
+pub use sc_keystore::remote::{RemoteSignerAddress, RemoteSignerSecret};
 pub use sc_network::{
 	config::{
 		MultiaddrWithPeerId, NetworkConfiguration, NodeKeyConfig, NonDefaultSetConfig, ProtocolId,
@@ -33,6 +34,7 @@ pub use sc_network::{
 	},
 	Multiaddr,
 };
+pub use sc_offchain::{HttpRequestPolicy as OffchainHttpRequestPolicy, UrlPattern};
 pub use sc_rpc_server::{
 	IpNetwork, RpcEndpoint, RpcMethods, SubscriptionIdProvider as RpcSubscriptionIdProvider,
 };
@@ -133,9 +135,20 @@ pub enum KeystoreConfig {
 		path: PathBuf,
 		/// Node keystore's password.
 		password: Option<SecretString>,
+		/// Password used to encrypt the key files at rest, if they are encrypted.
+		encryption_password: Option<SecretString>,
 	},
 	/// In-memory keystore. Recommended for in-browser nodes.
 	InMemory,
+	/// Keystore forwarding all operations to a remote signer holding the keys.
+	Remote {
+		/// Address of the remote signer.
+		address: RemoteSignerAddress,
+		/// Secret shared with the remote signer, used to authenticate each other.
+		secret: RemoteSignerSecret,
+		/// Directory where the slashing protection history is persisted.
+		path: PathBuf,
+	},
 }
 
 impl KeystoreConfig {
@@ -143,7 +156,7 @@ impl KeystoreConfig {
 	pub fn path(&self) -> Option<&Path> {
 		match self {
 			Self::Path { path, .. } => Some(path),
-			Self::InMemory => None,
+			Self::InMemory | Self::Remote { .. } => None,
 		}
 	}
 }
@@ -154,6 +167,8 @@ pub struct OffchainWorkerConfig {
 	pub enabled: bool,
 	/// allow writes from the runtime to the offchain worker database.
 	pub indexing_enabled: bool,
+	/// Restrictions applied to the HTTP requests of the offchain workers.
+	pub http_request_policy: OffchainHttpRequestPolicy,
 }
 
 /// Configuration of the Prometheus endpoint.
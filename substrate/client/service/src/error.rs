@@ -45,6 +45,9 @@ pub enum Error {
 	#[error(transparent)]
 	Keystore(#[from] sc_keystore::Error),
 
+	#[error(transparent)]
+	SlashingProtection(#[from] sc_keystore::slashing_protection::Error),
+
 	#[error(transparent)]
 	Telemetry(#[from] sc_telemetry::Error),
 
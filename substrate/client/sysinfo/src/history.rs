@@ -0,0 +1,250 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! History of the hardware benchmark results of a node, persisted in its base path.
+
+use crate::{CheckFailures, HwBench, Requirements};
+
+use serde::{Deserialize, Serialize};
+use std::{
+	fs::{self, File},
+	io::{self, BufReader},
+	path::{Path, PathBuf},
+	time::{SystemTime, UNIX_EPOCH},
+};
+
+/// Name of the file storing the history.
+pub const HWBENCH_HISTORY_FILE: &str = "hwbench_history.json";
+
+/// Maximum number of records kept in the history.
+const MAX_RECORDS: usize = 100;
+
+/// Number of most recent records the benchmark results are compared with to detect a
+/// degradation.
+const DEGRADATION_WINDOW: usize = 10;
+
+/// Hardware benchmark results along with the time they were measured at.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct HwBenchRecord {
+	/// Seconds since the UNIX epoch at which the benchmarks were run.
+	pub timestamp: u64,
+	/// The benchmark results.
+	pub hwbench: HwBench,
+}
+
+/// History of the hardware benchmark results of a node.
+#[derive(Debug)]
+pub struct HwBenchHistory {
+	path: PathBuf,
+	records: Vec<HwBenchRecord>,
+}
+
+impl HwBenchHistory {
+	/// Open the history stored in `dir`.
+	///
+	/// The history is empty if it was never written.
+	pub fn open(dir: &Path) -> io::Result<Self> {
+		let path = dir.join(HWBENCH_HISTORY_FILE);
+		let records = match File::open(&path) {
+			Ok(file) => serde_json::from_reader(BufReader::new(file))?,
+			Err(e) if e.kind() == io::ErrorKind::NotFound => Vec::new(),
+			Err(e) => return Err(e),
+		};
+
+		Ok(Self { path, records })
+	}
+
+	/// The records of the history, from the oldest to the most recent.
+	pub fn records(&self) -> &[HwBenchRecord] {
+		&self.records
+	}
+
+	/// The most recent record of the history.
+	pub fn latest(&self) -> Option<&HwBenchRecord> {
+		self.records.last()
+	}
+
+	/// Append `hwbench` to the history and write it to disk.
+	///
+	/// Only the [`MAX_RECORDS`] most recent records are kept.
+	pub fn push(&mut self, hwbench: HwBench) -> io::Result<()> {
+		let timestamp = SystemTime::now()
+			.duration_since(UNIX_EPOCH)
+			.map(|time| time.as_secs())
+			.unwrap_or_default();
+		self.records.push(HwBenchRecord { timestamp, hwbench });
+		let excess = self.records.len().saturating_sub(MAX_RECORDS);
+		self.records.drain(..excess);
+
+		if let Some(dir) = self.path.parent() {
+			fs::create_dir_all(dir)?;
+		}
+		let tmp = self.path.with_extension("json.tmp");
+		fs::write(&tmp, serde_json::to_vec_pretty(&self.records)?)?;
+		fs::rename(tmp, &self.path)
+	}
+
+	/// Whether `hwbench` still meets the `requirements` usually met by the node.
+	///
+	/// `hwbench` is compared with the [`DEGRADATION_WINDOW`] most recent records, so that a
+	/// single unusually good or bad run doesn't hide or cause a degradation: a requirement is
+	/// usually met if the median of the recent results for its metric meets it, i.e. if most of
+	/// the recent records meet it.
+	///
+	/// Returns the requirements usually met which `hwbench` fails to meet, if any.
+	pub fn check_degradation(
+		&self,
+		requirements: &Requirements,
+		hwbench: &HwBench,
+		is_rc_authority: bool,
+	) -> Result<(), CheckFailures> {
+		let window = &self.records[self.records.len().saturating_sub(DEGRADATION_WINDOW)..];
+		if window.is_empty() {
+			return Ok(())
+		}
+		let Err(CheckFailures(failures)) = requirements.check_hardware(hwbench, is_rc_authority)
+		else {
+			return Ok(())
+		};
+		let previous_failures = window
+			.iter()
+			.map(|record| {
+				requirements
+					.check_hardware(&record.hwbench, is_rc_authority)
+					.err()
+					.map(|failures| failures.0)
+					.unwrap_or_default()
+			})
+			.collect::<Vec<_>>();
+
+		let degradations = failures
+			.into_iter()
+			.filter(|failure| {
+				let met = previous_failures
+					.iter()
+					.filter(|previous| !previous.iter().any(|p| p.metric == failure.metric))
+					.count();
+				met * 2 > window.len()
+			})
+			.collect::<Vec<_>>();
+		if degradations.is_empty() {
+			Ok(())
+		} else {
+			Err(degradations.into())
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{Metric, Requirement, Throughput};
+
+	fn hwbench(cpu_mibs: f64) -> HwBench {
+		HwBench {
+			cpu_hashrate_score: Throughput::from_mibs(cpu_mibs),
+			parallel_cpu_hashrate_score: Throughput::from_mibs(cpu_mibs),
+			parallel_cpu_cores: 1,
+			memory_memcpy_score: Throughput::from_mibs(1000.0),
+			disk_sequential_write_score: Some(Throughput::from_mibs(500.0)),
+			disk_random_write_score: None,
+		}
+	}
+
+	#[test]
+	fn history_is_persisted() {
+		let dir = tempfile::tempdir().unwrap();
+
+		let mut history = HwBenchHistory::open(dir.path()).unwrap();
+		assert!(history.latest().is_none());
+		history.push(hwbench(100.0)).unwrap();
+		history.push(hwbench(200.0)).unwrap();
+
+		let history = HwBenchHistory::open(dir.path()).unwrap();
+		assert_eq!(history.records().len(), 2);
+		assert_eq!(history.records()[0].hwbench, hwbench(100.0));
+		assert_eq!(history.latest().unwrap().hwbench, hwbench(200.0));
+	}
+
+	#[test]
+	fn history_is_bounded() {
+		let dir = tempfile::tempdir().unwrap();
+
+		let mut history = HwBenchHistory::open(dir.path()).unwrap();
+		for cpu in 0..MAX_RECORDS + 5 {
+			history.push(hwbench(cpu as f64)).unwrap();
+		}
+
+		let history = HwBenchHistory::open(dir.path()).unwrap();
+		assert_eq!(history.records().len(), MAX_RECORDS);
+		assert_eq!(history.records()[0].hwbench, hwbench(5.0));
+	}
+
+	#[test]
+	fn degradation_is_detected() {
+		let dir = tempfile::tempdir().unwrap();
+		let requirements = Requirements(vec![Requirement {
+			metric: Metric::Blake2256,
+			minimum: Throughput::from_mibs(150.0),
+			validator_only: false,
+		}]);
+
+		let mut history = HwBenchHistory::open(dir.path()).unwrap();
+		// Nothing to compare with.
+		assert!(history.check_degradation(&requirements, &hwbench(100.0), false).is_ok());
+
+		// The hardware never met the requirement.
+		history.push(hwbench(100.0)).unwrap();
+		assert!(history.check_degradation(&requirements, &hwbench(90.0), false).is_ok());
+
+		// The hardware met the requirement on a single run out of two.
+		history.push(hwbench(200.0)).unwrap();
+		assert!(history.check_degradation(&requirements, &hwbench(100.0), false).is_ok());
+
+		// The hardware usually meets the requirement.
+		history.push(hwbench(190.0)).unwrap();
+		assert!(history.check_degradation(&requirements, &hwbench(180.0), false).is_ok());
+		let failures = history.check_degradation(&requirements, &hwbench(100.0), false);
+		assert_eq!(failures.unwrap_err().0[0].metric, Metric::Blake2256);
+	}
+
+	#[test]
+	fn degradation_is_checked_against_recent_records() {
+		let dir = tempfile::tempdir().unwrap();
+		let requirements = Requirements(vec![Requirement {
+			metric: Metric::Blake2256,
+			minimum: Throughput::from_mibs(150.0),
+			validator_only: false,
+		}]);
+
+		let mut history = HwBenchHistory::open(dir.path()).unwrap();
+		for _ in 0..DEGRADATION_WINDOW {
+			history.push(hwbench(200.0)).unwrap();
+		}
+
+		// A single bad run doesn't hide a degradation.
+		history.push(hwbench(100.0)).unwrap();
+		assert!(history.check_degradation(&requirements, &hwbench(100.0), false).is_err());
+
+		// The hardware doesn't meet the requirement anymore since long enough.
+		for _ in 0..DEGRADATION_WINDOW / 2 {
+			history.push(hwbench(100.0)).unwrap();
+		}
+		assert!(history.check_degradation(&requirements, &hwbench(100.0), false).is_ok());
+	}
+}
@@ -20,17 +20,21 @@
 //! and software telemetry information about the node on which we're running.
 
 use futures::prelude::*;
+use prometheus_endpoint::{register, GaugeVec, Opts, PrometheusError, Registry, F64};
 use std::time::Duration;
 
+mod history;
 mod sysinfo;
 #[cfg(target_os = "linux")]
 mod sysinfo_linux;
 
+pub use history::{HwBenchHistory, HwBenchRecord, HWBENCH_HISTORY_FILE};
 pub use sysinfo::{
 	benchmark_cpu, benchmark_cpu_parallelism, benchmark_disk_random_writes,
-	benchmark_disk_sequential_writes, benchmark_memory, benchmark_sr25519_verify, gather_hwbench,
-	gather_sysinfo, serialize_throughput, serialize_throughput_option, Metric, Requirement,
-	Requirements, Throughput,
+	benchmark_disk_sequential_writes, benchmark_memory, benchmark_sr25519_verify,
+	deserialize_throughput, deserialize_throughput_option, gather_hwbench, gather_sysinfo,
+	serialize_throughput, serialize_throughput_option, CheckFailure, CheckFailures, Metric,
+	Requirement, Requirements, Throughput,
 };
 
 /// The operating system part of the current target triplet.
@@ -43,34 +47,52 @@ pub const TARGET_ARCH: &str = include_str!(concat!(env!("OUT_DIR"), "/target_arc
 pub const TARGET_ENV: &str = include_str!(concat!(env!("OUT_DIR"), "/target_env.txt"));
 
 /// Hardware benchmark results for the node.
-#[derive(Clone, Debug, serde::Serialize)]
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct HwBench {
 	/// The CPU speed, as measured in how many MB/s it can hash using the BLAKE2b-256 hash.
-	#[serde(serialize_with = "serialize_throughput")]
+	#[serde(serialize_with = "serialize_throughput", deserialize_with = "deserialize_throughput")]
 	pub cpu_hashrate_score: Throughput,
 	/// The parallel CPU speed, as measured in how many MB/s it can hash in parallel using the
 	/// BLAKE2b-256 hash.
-	#[serde(serialize_with = "serialize_throughput")]
+	#[serde(serialize_with = "serialize_throughput", deserialize_with = "deserialize_throughput")]
 	pub parallel_cpu_hashrate_score: Throughput,
 	/// The number of expected cores used for computing the parallel CPU speed.
 	pub parallel_cpu_cores: usize,
 	/// Memory bandwidth in MB/s, calculated by measuring the throughput of `memcpy`.
-	#[serde(serialize_with = "serialize_throughput")]
+	#[serde(serialize_with = "serialize_throughput", deserialize_with = "deserialize_throughput")]
 	pub memory_memcpy_score: Throughput,
 	/// Sequential disk write speed in MB/s.
 	#[serde(
+		default,
 		serialize_with = "serialize_throughput_option",
+		deserialize_with = "deserialize_throughput_option",
 		skip_serializing_if = "Option::is_none"
 	)]
 	pub disk_sequential_write_score: Option<Throughput>,
 	/// Random disk write speed in MB/s.
 	#[serde(
+		default,
 		serialize_with = "serialize_throughput_option",
+		deserialize_with = "deserialize_throughput_option",
 		skip_serializing_if = "Option::is_none"
 	)]
 	pub disk_random_write_score: Option<Throughput>,
 }
 
+impl HwBench {
+	/// The score measured for `metric`, if it is part of the results.
+	pub fn score(&self, metric: &Metric) -> Option<Throughput> {
+		match metric {
+			Metric::Blake2256 => Some(self.cpu_hashrate_score),
+			Metric::Blake2256Parallel { .. } => Some(self.parallel_cpu_hashrate_score),
+			Metric::MemCopy => Some(self.memory_memcpy_score),
+			Metric::DiskSeqWrite => self.disk_sequential_write_score,
+			Metric::DiskRndWrite => self.disk_random_write_score,
+			Metric::Sr25519Verify => None,
+		}
+	}
+}
+
 #[derive(Copy, Clone, Debug)]
 /// Limit the execution time of a benchmark.
 pub enum ExecutionLimit {
@@ -174,3 +196,35 @@ pub fn initialize_hwbench_telemetry(
 		}
 	}
 }
+
+/// Registers the results of the hardware benchmarks as Prometheus metrics.
+pub fn register_hwbench_metrics(
+	registry: &Registry,
+	hwbench: &HwBench,
+) -> Result<(), PrometheusError> {
+	let scores = register(
+		GaugeVec::<F64>::new(
+			Opts::new(
+				"substrate_hwbench_score_mibs",
+				"Scores of the hardware benchmarks run at startup, in MiB/s",
+			),
+			&["benchmark"],
+		)?,
+		registry,
+	)?;
+
+	let benchmarks = [
+		("cpu", Some(hwbench.cpu_hashrate_score)),
+		("cpu_parallel", Some(hwbench.parallel_cpu_hashrate_score)),
+		("memory", Some(hwbench.memory_memcpy_score)),
+		("disk_seq_write", hwbench.disk_sequential_write_score),
+		("disk_rnd_write", hwbench.disk_random_write_score),
+	];
+	for (benchmark, score) in benchmarks {
+		if let Some(score) = score {
+			scores.with_label_values(&[benchmark]).set(score.as_mibs());
+		}
+	}
+
+	Ok(())
+}
@@ -235,15 +235,36 @@ impl<'de> Visitor<'de> for ThroughputVisitor {
 	{
 		Ok(Throughput::from_mibs(value))
 	}
+
+	fn visit_u64<E>(self, value: u64) -> Result<Self::Value, E>
+	where
+		E: serde::de::Error,
+	{
+		Ok(Throughput::from_mibs(value as f64))
+	}
 }
 
-fn deserialize_throughput<'de, D>(deserializer: D) -> Result<Throughput, D::Error>
+/// Deserializes `Throughput` from MiBs.
+pub fn deserialize_throughput<'de, D>(deserializer: D) -> Result<Throughput, D::Error>
 where
 	D: Deserializer<'de>,
 {
 	Ok(deserializer.deserialize_f64(ThroughputVisitor))?
 }
 
+/// Deserializes `Option<Throughput>` from MiBs.
+pub fn deserialize_throughput_option<'de, D>(
+	deserializer: D,
+) -> Result<Option<Throughput>, D::Error>
+where
+	D: Deserializer<'de>,
+{
+	#[derive(Deserialize)]
+	struct Wrapper(#[serde(deserialize_with = "deserialize_throughput")] Throughput);
+
+	Ok(Option::<Wrapper>::deserialize(deserializer)?.map(|Wrapper(throughput)| throughput))
+}
+
 /// Multiple requirements for the hardware.
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct Requirements(pub Vec<Requirement>);
@@ -868,4 +889,22 @@ mod tests {
 		// Throughput from all of the benchmarks should be converted to MiBs.
 		assert_eq!(serialized, "{\"cpu_hashrate_score\":1351,\"parallel_cpu_hashrate_score\":1351,\"parallel_cpu_cores\":4,\"memory_memcpy_score\":9,\"disk_sequential_write_score\":4}");
 	}
+
+	/// Test the [`HwBench`] deserialization.
+	#[test]
+	fn hwbench_deserialize_works() {
+		let hwbench: HwBench = serde_json::from_str("{\"cpu_hashrate_score\":1351,\"parallel_cpu_hashrate_score\":1351,\"parallel_cpu_cores\":4,\"memory_memcpy_score\":9,\"disk_sequential_write_score\":4}").unwrap();
+
+		assert_eq!(
+			hwbench,
+			HwBench {
+				cpu_hashrate_score: Throughput::from_mibs(1351.0),
+				parallel_cpu_hashrate_score: Throughput::from_mibs(1351.0),
+				parallel_cpu_cores: 4,
+				memory_memcpy_score: Throughput::from_mibs(9.0),
+				disk_sequential_write_score: Some(Throughput::from_mibs(4.0)),
+				disk_random_write_score: None,
+			}
+		);
+	}
 }
@@ -0,0 +1,207 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//  http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Asset Conversion Orders pallet benchmarking.
+
+use super::*;
+use crate::Pallet as AssetConversionOrders;
+use frame_benchmarking::{v2::*, whitelisted_caller};
+use frame_support::{
+	assert_ok,
+	traits::{
+		fungibles::{Create, Inspect, Mutate},
+		Get,
+	},
+};
+use frame_system::RawOrigin as SystemOrigin;
+use pallet_asset_conversion::{BenchmarkHelper, Pallet as AssetConversion};
+
+/// Create the `asset` and mint the `amount` for the `caller`.
+fn create_asset<T: Config>(caller: &T::AccountId, asset: &T::AssetKind, amount: T::Balance)
+where
+	T::Assets: Create<T::AccountId> + Mutate<T::AccountId>,
+{
+	if !T::Assets::asset_exists(asset.clone()) {
+		assert_ok!(T::Assets::create(asset.clone(), caller.clone(), true, T::Balance::one()));
+	}
+	assert_ok!(T::Assets::mint_into(
+		asset.clone(),
+		&caller,
+		amount + T::Assets::minimum_balance(asset.clone())
+	));
+}
+
+/// Creates a pool with liquidity for a given asset pair and mints an amount of `asset1` for the
+/// `caller` to sell by an order. Returns the amount to sell.
+fn create_pool_with_liquidity<T: Config>(
+	caller: &T::AccountId,
+	asset1: &T::AssetKind,
+	asset2: &T::AssetKind,
+) -> T::Balance
+where
+	T::Assets: Create<T::AccountId> + Mutate<T::AccountId>,
+{
+	let fee_asset = T::PoolSetupFeeAsset::get();
+	create_asset::<T>(caller, &fee_asset, T::Balance::zero());
+
+	let ed =
+		T::Assets::minimum_balance(asset1.clone()).max(T::Assets::minimum_balance(asset2.clone()));
+	// Large enough for the search of `fill_order` to take all its steps.
+	let liquidity = (ed + T::MintMinLiquidity::get()) * 10_000u32.into();
+	let amount_in = ed * 10u32.into();
+	create_asset::<T>(caller, asset1, liquidity + amount_in);
+	create_asset::<T>(caller, asset2, liquidity);
+
+	let lp_token = AssetConversion::<T>::get_next_pool_asset_id();
+	assert_ok!(T::Assets::mint_into(
+		fee_asset,
+		&caller,
+		T::PoolSetupFee::get() +
+			T::Assets::deposit_required(asset1.clone()) +
+			T::Assets::deposit_required(asset2.clone()) +
+			T::PoolAssets::deposit_required(lp_token)
+	));
+	assert_ok!(AssetConversion::<T>::create_pool(
+		SystemOrigin::Signed(caller.clone()).into(),
+		Box::new(asset1.clone()),
+		Box::new(asset2.clone())
+	));
+	assert_ok!(AssetConversion::<T>::add_liquidity(
+		SystemOrigin::Signed(caller.clone()).into(),
+		Box::new(asset1.clone()),
+		Box::new(asset2.clone()),
+		liquidity,
+		liquidity,
+		T::Balance::one(),
+		T::Balance::one(),
+		caller.clone(),
+	));
+
+	amount_in
+}
+
+/// Places an order selling `asset1` for `asset2` which can be filled at the price of the pool.
+fn place_order<T: Config>(
+	caller: &T::AccountId,
+	asset1: &T::AssetKind,
+	asset2: &T::AssetKind,
+) -> OrderId
+where
+	T::Assets: Create<T::AccountId> + Mutate<T::AccountId>,
+{
+	let amount_in = create_pool_with_liquidity::<T>(caller, asset1, asset2);
+	assert_ok!(AssetConversionOrders::<T>::place_limit_order(
+		SystemOrigin::Signed(caller.clone()).into(),
+		Box::new(asset1.clone()),
+		Box::new(asset2.clone()),
+		amount_in,
+		T::Balance::one(),
+		frame_system::Pallet::<T>::block_number() + 10u32.into(),
+	));
+	NextOrderId::<T>::get() - 1
+}
+
+fn assert_last_event<T: Config>(generic_event: <T as Config>::RuntimeEvent) {
+	let events = frame_system::Pallet::<T>::events();
+	let system_event: <T as frame_system::Config>::RuntimeEvent = generic_event.into();
+	// compare to the last event record
+	let frame_system::EventRecord { event, .. } = &events[events.len() - 1];
+	assert_eq!(event, &system_event);
+}
+
+#[benchmarks(where T::Assets: Create<T::AccountId> + Mutate<T::AccountId>)]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn place_order() {
+		let caller: T::AccountId = whitelisted_caller();
+		let (asset1, asset2) = T::BenchmarkHelper::create_pair(0, 1);
+		let amount_in = create_pool_with_liquidity::<T>(&caller, &asset1, &asset2);
+		let expiry = frame_system::Pallet::<T>::block_number() + 10u32.into();
+
+		#[extrinsic_call]
+		place_limit_order(
+			SystemOrigin::Signed(caller.clone()),
+			Box::new(asset1.clone()),
+			Box::new(asset2.clone()),
+			amount_in,
+			T::Balance::one(),
+			expiry,
+		);
+
+		assert_last_event::<T>(
+			Event::OrderPlaced {
+				order_id: 0,
+				owner: caller,
+				asset_in: asset1,
+				asset_out: asset2,
+				amount_in,
+				min_amount_out: T::Balance::one(),
+				max_amount_out: T::Balance::one(),
+				expiry,
+			}
+			.into(),
+		);
+	}
+
+	#[benchmark]
+	fn cancel_order() {
+		let caller: T::AccountId = whitelisted_caller();
+		let (asset1, asset2) = T::BenchmarkHelper::create_pair(0, 1);
+		let order_id = place_order::<T>(&caller, &asset1, &asset2);
+
+		#[extrinsic_call]
+		_(SystemOrigin::Signed(caller.clone()), order_id);
+
+		assert!(Orders::<T>::get(order_id).is_none());
+	}
+
+	#[benchmark]
+	fn fill_order() {
+		let caller: T::AccountId = whitelisted_caller();
+		let (asset1, asset2) = T::BenchmarkHelper::create_pair(0, 1);
+		create_pool_with_liquidity::<T>(&caller, &asset1, &asset2);
+
+		// The worst case is a partial fill, for which the whole amount is quoted before the
+		// largest fillable amount is searched: sell as much as the reserve at half its price.
+		let (reserve_in, _) = AssetConversion::<T>::get_reserves(asset1.clone(), asset2.clone())
+			.expect("pool was created with liquidity");
+		assert_ok!(T::Assets::mint_into(asset1.clone(), &caller, reserve_in));
+		assert_ok!(AssetConversionOrders::<T>::place_limit_order(
+			SystemOrigin::Signed(caller.clone()).into(),
+			Box::new(asset1),
+			Box::new(asset2),
+			reserve_in,
+			reserve_in / 2u32.into(),
+			frame_system::Pallet::<T>::block_number() + 10u32.into(),
+		));
+		let order_id = NextOrderId::<T>::get() - 1;
+
+		#[extrinsic_call]
+		_(SystemOrigin::Signed(caller.clone()), order_id);
+
+		let order = Orders::<T>::get(order_id).expect("order is partially filled");
+		assert!(!order.filled_in.is_zero() && order.filled_in < order.amount_in);
+	}
+
+	impl_benchmark_test_suite!(
+		AssetConversionOrders,
+		crate::mock::new_test_ext(),
+		crate::mock::Test
+	);
+}
@@ -0,0 +1,609 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//  http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Asset Conversion Orders.
+//!
+//! This pallet provides limit and range orders on top of the pools of the Asset Conversion pallet.
+//!
+//! ## Overview
+//!
+//! An order sells `amount_in` of `asset_in` for `asset_out` through the pool of the pair, once the
+//! pool offers at least the price asked by the order. The funds to sell are held by the
+//! [`Config::AssetsHold`] registry from the moment the order is placed until they are swapped, the
+//! order is cancelled by its owner or the order expires.
+//!
+//! - A limit order asks for at least `min_amount_out` for its whole `amount_in`, i.e. a single
+//!   price.
+//! - A range order asks for a price rising linearly from `min_amount_out` to `max_amount_out` (for
+//!   the whole `amount_in`) as the order is filled. It is thus sold progressively while the pool
+//!   price moves through the range.
+//!
+//! Orders may be partially filled: each fill sells the largest amount found for which the pool
+//! quote (see [`pallet_asset_conversion::Pallet::quote_price_exact_tokens_for_tokens`]) meets the
+//! price asked for it. Orders are filled automatically in `on_idle`, as far as the remaining weight
+//! of the block allows, and may be filled by anyone with [`Pallet::fill_order`]. Expired orders
+//! are removed in `on_idle` and their remaining funds released.
+
+#![deny(missing_docs)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+pub mod weights;
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+extern crate alloc;
+
+use alloc::{boxed::Box, vec};
+use codec::{Decode, DecodeWithMemTracking, Encode, MaxEncodedLen};
+use frame_support::{
+	storage::with_storage_layer,
+	traits::{
+		fungibles::{Inspect, MutateHold},
+		tokens::Precision,
+	},
+};
+use pallet_asset_conversion::{PoolLocator, Pools, Swap};
+use scale_info::TypeInfo;
+use sp_runtime::{
+	traits::{EnsureAdd, EnsureDiv, EnsureMul, EnsureSub, One, Saturating, Zero},
+	ArithmeticError, DispatchError, RuntimeDebug,
+};
+
+/// The identifier of an order.
+pub type OrderId = u32;
+
+/// An order to sell an asset for another one through their pool.
+#[derive(
+	Clone,
+	Encode,
+	Decode,
+	DecodeWithMemTracking,
+	Eq,
+	PartialEq,
+	RuntimeDebug,
+	MaxEncodedLen,
+	TypeInfo,
+)]
+pub struct Order<AccountId, AssetKind, Balance, BlockNumber> {
+	/// The account which placed the order and whose funds are sold.
+	pub owner: AccountId,
+	/// The asset to sell.
+	pub asset_in: AssetKind,
+	/// The asset to buy.
+	pub asset_out: AssetKind,
+	/// The total amount of `asset_in` to sell.
+	pub amount_in: Balance,
+	/// The amount of `asset_out` asked for the whole `amount_in` at the lowest price.
+	pub min_amount_out: Balance,
+	/// The amount of `asset_out` asked for the whole `amount_in` at the highest price. Equal to
+	/// `min_amount_out` for limit orders.
+	pub max_amount_out: Balance,
+	/// The amount of `asset_in` sold so far.
+	pub filled_in: Balance,
+	/// The amount of `asset_out` bought so far.
+	pub filled_out: Balance,
+	/// The block after which the order is no longer filled.
+	pub expiry: BlockNumber,
+}
+
+/// The [`Order`] type of the pallet.
+pub type OrderOf<T> = Order<
+	<T as frame_system::Config>::AccountId,
+	<T as pallet_asset_conversion::Config>::AssetKind,
+	<T as pallet_asset_conversion::Config>::Balance,
+	frame_system::pallet_prelude::BlockNumberFor<T>,
+>;
+
+/// The number of steps of the search for the largest fillable amount of an order.
+const FILL_SEARCH_STEPS: u32 = 16;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	/// A reason for the pallet placing a hold on funds.
+	#[pallet::composite_enum]
+	pub enum HoldReason {
+		/// Funds are held to be sold by an order.
+		#[codec(index = 0)]
+		Order,
+	}
+
+	#[pallet::config]
+	pub trait Config: pallet_asset_conversion::Config + frame_system::Config {
+		/// Overarching event type.
+		#[allow(deprecated)]
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// Overarching hold reason.
+		type RuntimeHoldReason: From<HoldReason>;
+
+		/// Registry of the assets of [`pallet_asset_conversion::Config::Assets`] able to hold the
+		/// funds of the orders.
+		type AssetsHold: MutateHold<
+			Self::AccountId,
+			AssetId = Self::AssetKind,
+			Balance = Self::Balance,
+			Reason = Self::RuntimeHoldReason,
+		>;
+
+		/// The maximum number of open orders per account.
+		#[pallet::constant]
+		type MaxOrdersPerAccount: Get<u32>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	/// The open orders.
+	#[pallet::storage]
+	pub type Orders<T: Config> = StorageMap<_, Twox64Concat, OrderId, OrderOf<T>>;
+
+	/// The identifier of the next order.
+	#[pallet::storage]
+	pub type NextOrderId<T: Config> = StorageValue<_, OrderId, ValueQuery>;
+
+	/// The number of open orders per account.
+	#[pallet::storage]
+	pub type OrderCount<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, u32, ValueQuery>;
+
+	/// The last order serviced in `on_idle`, from which the servicing resumes.
+	#[pallet::storage]
+	pub type ServiceCursor<T: Config> = StorageValue<_, OrderId, OptionQuery>;
+
+	// Pallet's events.
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// An order was placed.
+		OrderPlaced {
+			/// The order's ID.
+			order_id: OrderId,
+			/// The account which placed the order.
+			owner: T::AccountId,
+			/// The asset to sell.
+			asset_in: T::AssetKind,
+			/// The asset to buy.
+			asset_out: T::AssetKind,
+			/// The amount of `asset_in` to sell.
+			amount_in: T::Balance,
+			/// The amount of `asset_out` asked at the lowest price.
+			min_amount_out: T::Balance,
+			/// The amount of `asset_out` asked at the highest price.
+			max_amount_out: T::Balance,
+			/// The block after which the order is no longer filled.
+			expiry: BlockNumberFor<T>,
+		},
+		/// An order was filled, possibly partially.
+		OrderFilled {
+			/// The order's ID.
+			order_id: OrderId,
+			/// The amount of `asset_in` sold by this fill.
+			amount_in: T::Balance,
+			/// The amount of `asset_out` bought by this fill.
+			amount_out: T::Balance,
+			/// The amount of `asset_in` which remains to be sold.
+			remaining: T::Balance,
+		},
+		/// An order was cancelled by its owner.
+		OrderCancelled {
+			/// The order's ID.
+			order_id: OrderId,
+			/// The amount of `asset_in` released to the owner.
+			released: T::Balance,
+		},
+		/// An order expired.
+		OrderExpired {
+			/// The order's ID.
+			order_id: OrderId,
+			/// The amount of `asset_in` released to the owner.
+			released: T::Balance,
+		},
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// Provided asset pair is not supported for pool.
+		InvalidAssetPair,
+		/// The pool doesn't exist.
+		PoolNotFound,
+		/// Provided amount should be greater than or equal to the existential deposit/asset's
+		/// minimal amount.
+		AmountTooLow,
+		/// The highest price of a range order must be above its lowest price.
+		InvalidRange,
+		/// The expiry must be in the future.
+		InvalidExpiry,
+		/// The account has too many open orders.
+		TooManyOrders,
+		/// The order doesn't exist.
+		OrderNotFound,
+		/// The origin is not the owner of the order.
+		NotOwner,
+		/// The order has expired.
+		OrderExpired,
+		/// The pool doesn't offer the price asked by the order.
+		PriceNotReached,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_idle(now: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+			Self::service_orders(now, remaining_weight)
+		}
+	}
+
+	/// Pallet's callable functions.
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Place an order to sell `amount_in` of `asset_in` for at least `min_amount_out` of
+		/// `asset_out`, until the block `expiry`.
+		///
+		/// The `amount_in` is held until it is sold, the order is cancelled or it expires.
+		///
+		/// Must be signed.
+		#[pallet::call_index(0)]
+		#[pallet::weight(<T as Config>::WeightInfo::place_order())]
+		pub fn place_limit_order(
+			origin: OriginFor<T>,
+			asset_in: Box<T::AssetKind>,
+			asset_out: Box<T::AssetKind>,
+			amount_in: T::Balance,
+			min_amount_out: T::Balance,
+			expiry: BlockNumberFor<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_place_order(
+				who,
+				*asset_in,
+				*asset_out,
+				amount_in,
+				min_amount_out,
+				min_amount_out,
+				expiry,
+			)
+			.map(|_| ())
+		}
+
+		/// Place an order to sell `amount_in` of `asset_in` for `asset_out`, at a price rising
+		/// linearly from `min_amount_out` to `max_amount_out` (for the whole `amount_in`) as the
+		/// order is filled, until the block `expiry`.
+		///
+		/// The `amount_in` is held until it is sold, the order is cancelled or it expires.
+		///
+		/// Must be signed.
+		#[pallet::call_index(1)]
+		#[pallet::weight(<T as Config>::WeightInfo::place_order())]
+		pub fn place_range_order(
+			origin: OriginFor<T>,
+			asset_in: Box<T::AssetKind>,
+			asset_out: Box<T::AssetKind>,
+			amount_in: T::Balance,
+			min_amount_out: T::Balance,
+			max_amount_out: T::Balance,
+			expiry: BlockNumberFor<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(max_amount_out > min_amount_out, Error::<T>::InvalidRange);
+			Self::do_place_order(
+				who,
+				*asset_in,
+				*asset_out,
+				amount_in,
+				min_amount_out,
+				max_amount_out,
+				expiry,
+			)
+			.map(|_| ())
+		}
+
+		/// Cancel an order and release its remaining funds.
+		///
+		/// Must be signed by the owner of the order.
+		#[pallet::call_index(2)]
+		#[pallet::weight(<T as Config>::WeightInfo::cancel_order())]
+		pub fn cancel_order(origin: OriginFor<T>, order_id: OrderId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let order = Orders::<T>::get(order_id).ok_or(Error::<T>::OrderNotFound)?;
+			ensure!(order.owner == who, Error::<T>::NotOwner);
+
+			let released = Self::close_order(order_id, &order)?;
+			Self::deposit_event(Event::OrderCancelled { order_id, released });
+			Ok(())
+		}
+
+		/// Fill an order as far as the price offered by the pool allows.
+		///
+		/// Must be signed. If the order is filled, transaction fees are refunded to the caller.
+		#[pallet::call_index(3)]
+		#[pallet::weight(Pallet::<T>::fill_order_weight())]
+		pub fn fill_order(origin: OriginFor<T>, order_id: OrderId) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+			let mut order = Orders::<T>::get(order_id).ok_or(Error::<T>::OrderNotFound)?;
+			ensure!(
+				frame_system::Pallet::<T>::block_number() <= order.expiry,
+				Error::<T>::OrderExpired
+			);
+
+			Self::do_fill_order(order_id, &mut order)?;
+			Ok(Pays::No.into())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Place an order, holding its `amount_in`. Returns the ID of the order.
+		pub(crate) fn do_place_order(
+			who: T::AccountId,
+			asset_in: T::AssetKind,
+			asset_out: T::AssetKind,
+			amount_in: T::Balance,
+			min_amount_out: T::Balance,
+			max_amount_out: T::Balance,
+			expiry: BlockNumberFor<T>,
+		) -> Result<OrderId, DispatchError> {
+			ensure!(asset_in != asset_out, Error::<T>::InvalidAssetPair);
+			let pool_id = T::PoolLocator::pool_id(&asset_in, &asset_out)
+				.map_err(|_| Error::<T>::InvalidAssetPair)?;
+			ensure!(Pools::<T>::contains_key(&pool_id), Error::<T>::PoolNotFound);
+			ensure!(
+				amount_in >= T::AssetsHold::minimum_balance(asset_in.clone()) &&
+					!amount_in.is_zero() &&
+					!min_amount_out.is_zero(),
+				Error::<T>::AmountTooLow
+			);
+			ensure!(expiry > frame_system::Pallet::<T>::block_number(), Error::<T>::InvalidExpiry);
+			OrderCount::<T>::try_mutate(&who, |count| {
+				ensure!(*count < T::MaxOrdersPerAccount::get(), Error::<T>::TooManyOrders);
+				count.saturating_inc();
+				Ok::<_, Error<T>>(())
+			})?;
+
+			T::AssetsHold::hold(asset_in.clone(), &HoldReason::Order.into(), &who, amount_in)?;
+
+			let order_id = NextOrderId::<T>::get();
+			NextOrderId::<T>::put(order_id.saturating_add(1));
+			Orders::<T>::insert(
+				order_id,
+				Order {
+					owner: who.clone(),
+					asset_in: asset_in.clone(),
+					asset_out: asset_out.clone(),
+					amount_in,
+					min_amount_out,
+					max_amount_out,
+					filled_in: Zero::zero(),
+					filled_out: Zero::zero(),
+					expiry,
+				},
+			);
+
+			Self::deposit_event(Event::OrderPlaced {
+				order_id,
+				owner: who,
+				asset_in,
+				asset_out,
+				amount_in,
+				min_amount_out,
+				max_amount_out,
+				expiry,
+			});
+			Ok(order_id)
+		}
+
+		/// Fill the order as far as the price offered by the pool allows and store the outcome.
+		///
+		/// Returns the amounts of `asset_in` sold and `asset_out` bought.
+		pub(crate) fn do_fill_order(
+			order_id: OrderId,
+			order: &mut OrderOf<T>,
+		) -> Result<(T::Balance, T::Balance), DispatchError> {
+			let remaining = order.amount_in.saturating_sub(order.filled_in);
+			let amount_in =
+				Self::fillable_amount(order, remaining).ok_or(Error::<T>::PriceNotReached)?;
+			let amount_out_min = Self::required_amount_out(order, amount_in)?;
+
+			let amount_out = with_storage_layer(|| {
+				T::AssetsHold::release(
+					order.asset_in.clone(),
+					&HoldReason::Order.into(),
+					&order.owner,
+					amount_in,
+					Precision::Exact,
+				)?;
+				<pallet_asset_conversion::Pallet<T> as Swap<T::AccountId>>::swap_exact_tokens_for_tokens(
+					order.owner.clone(),
+					vec![order.asset_in.clone(), order.asset_out.clone()],
+					amount_in,
+					Some(amount_out_min),
+					order.owner.clone(),
+					true,
+				)
+			})?;
+
+			order.filled_in.saturating_accrue(amount_in);
+			order.filled_out.saturating_accrue(amount_out);
+			let remaining = remaining.saturating_sub(amount_in);
+			if remaining.is_zero() {
+				Orders::<T>::remove(order_id);
+				OrderCount::<T>::mutate(&order.owner, |count| count.saturating_dec());
+			} else {
+				Orders::<T>::insert(order_id, &*order);
+			}
+
+			Self::deposit_event(Event::OrderFilled { order_id, amount_in, amount_out, remaining });
+			Ok((amount_in, amount_out))
+		}
+
+		/// Remove the order and release its remaining funds. Returns the amount released.
+		fn close_order(order_id: OrderId, order: &OrderOf<T>) -> Result<T::Balance, DispatchError> {
+			let released = T::AssetsHold::release(
+				order.asset_in.clone(),
+				&HoldReason::Order.into(),
+				&order.owner,
+				order.amount_in.saturating_sub(order.filled_in),
+				Precision::BestEffort,
+			)?;
+			Orders::<T>::remove(order_id);
+			OrderCount::<T>::mutate(&order.owner, |count| count.saturating_dec());
+			Ok(released)
+		}
+
+		/// The largest amount, up to `remaining`, which can be sold by the order at the price
+		/// currently offered by the pool, if any.
+		///
+		/// The pool offers a lower price for larger amounts while the order asks for a price which
+		/// does not decrease with the amount, hence the amount is found by bisection. At most
+		/// `FILL_SEARCH_STEPS + 1` quotes are made.
+		pub(crate) fn fillable_amount(
+			order: &OrderOf<T>,
+			remaining: T::Balance,
+		) -> Option<T::Balance> {
+			let fillable = |amount: T::Balance| -> bool {
+				let Ok(required) = Self::required_amount_out(order, amount) else { return false };
+				pallet_asset_conversion::Pallet::<T>::quote_price_exact_tokens_for_tokens(
+					order.asset_in.clone(),
+					order.asset_out.clone(),
+					amount,
+					true,
+				)
+				.is_some_and(|amount_out| amount_out >= required)
+			};
+
+			if remaining.is_zero() {
+				return None
+			}
+			let (_, reserve_out) = pallet_asset_conversion::Pallet::<T>::get_reserves(
+				order.asset_in.clone(),
+				order.asset_out.clone(),
+			)
+			.ok()?;
+			if fillable(remaining) {
+				return Some(remaining)
+			}
+
+			// The pool pays out less than its reserve of `asset_out`, while the order asks for at
+			// least `min_amount_out` per `amount_in`, which bounds the amount it can sell.
+			let hp = |balance: T::Balance| -> T::HigherPrecisionBalance { balance.into() };
+			let max_amount = hp(reserve_out)
+				.ensure_mul(hp(order.amount_in))
+				.and_then(|amount| amount.ensure_div(hp(order.min_amount_out)))
+				.ok()
+				.and_then(|amount| amount.try_into().ok())
+				.unwrap_or(remaining);
+			let (mut low, mut high) =
+				(T::Balance::zero(), remaining.saturating_sub(One::one()).min(max_amount));
+			for _ in 0..FILL_SEARCH_STEPS {
+				if low >= high {
+					break
+				}
+				let mid = low + (high - low + One::one()) / 2u32.into();
+				if fillable(mid) {
+					low = mid;
+				} else {
+					high = mid.saturating_sub(One::one());
+				}
+			}
+			(!low.is_zero()).then_some(low)
+		}
+
+		/// The amount of `asset_out` asked by the order for its next `amount` of `asset_in`.
+		///
+		/// The price asked rises linearly from `min_amount_out / amount_in` to
+		/// `max_amount_out / amount_in` as the order is filled, so this is `amount` times the
+		/// average of the prices asked at the start and at the end of the fill, rounded up.
+		pub(crate) fn required_amount_out(
+			order: &OrderOf<T>,
+			amount: T::Balance,
+		) -> Result<T::Balance, DispatchError> {
+			let hp = |balance: T::Balance| -> T::HigherPrecisionBalance { balance.into() };
+
+			// The amount of `asset_out` asked for the whole `amount_in` at the average price.
+			let average = hp(order.max_amount_out)
+				.ensure_sub(hp(order.min_amount_out))?
+				.ensure_mul(hp(order.filled_in).ensure_mul(2u32.into())?.ensure_add(hp(amount))?)?
+				.ensure_div(hp(order.amount_in).ensure_mul(2u32.into())?)?
+				.ensure_add(hp(order.min_amount_out))?;
+
+			let required = hp(amount)
+				.ensure_mul(average)?
+				.ensure_add(hp(order.amount_in))?
+				.ensure_sub(One::one())?
+				.ensure_div(hp(order.amount_in))?;
+			required.try_into().map_err(|_| ArithmeticError::Overflow.into())
+		}
+
+		/// The weight of filling an order, whose quotes and swap may be made on a pool of the
+		/// StableSwap curve, heavier than the constant product pool it is benchmarked on.
+		pub(crate) fn fill_order_weight() -> Weight {
+			use pallet_asset_conversion::WeightInfo as _;
+			let quotes_and_swap = FILL_SEARCH_STEPS.saturating_add(2);
+			<T as Config>::WeightInfo::fill_order().saturating_add(
+				<T as pallet_asset_conversion::Config>::WeightInfo::stable_swap_curve()
+					.saturating_mul(quotes_and_swap.into()),
+			)
+		}
+
+		/// Fill and expire orders with the `remaining_weight` of the block, resuming after the last
+		/// order serviced in a previous block.
+		fn service_orders(now: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+			let db_weight = T::DbWeight::get();
+			let service_weight =
+				Self::fill_order_weight().max(<T as Config>::WeightInfo::cancel_order());
+			let mut used_weight = db_weight.reads_writes(1, 1);
+			if remaining_weight.any_lt(used_weight.saturating_add(service_weight)) {
+				return Weight::zero()
+			}
+
+			let mut orders = match ServiceCursor::<T>::get() {
+				Some(cursor) => Orders::<T>::iter_from(Orders::<T>::hashed_key_for(cursor)),
+				None => Orders::<T>::iter(),
+			};
+			let mut cursor = None;
+			while remaining_weight.all_gte(used_weight.saturating_add(service_weight)) {
+				let Some((order_id, mut order)) = orders.next() else {
+					// Resume from the first order in the next block.
+					cursor = None;
+					break
+				};
+				used_weight.saturating_accrue(service_weight);
+				cursor = Some(order_id);
+
+				if order.expiry < now {
+					if let Ok(released) = Self::close_order(order_id, &order) {
+						Self::deposit_event(Event::OrderExpired { order_id, released });
+					}
+				} else {
+					// The order is left as it is if it cannot be filled.
+					let _ = Self::do_fill_order(order_id, &mut order);
+				}
+			}
+			ServiceCursor::<T>::set(cursor);
+			used_weight
+		}
+	}
+}
@@ -0,0 +1,336 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//  http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Asset Conversion Orders pallet tests.
+
+use crate::{mock::*, *};
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::fungibles::{Inspect, InspectHold},
+};
+
+fn held(who: u64, asset: NativeOrWithId<u32>) -> u64 {
+	NativeAndAssetsHold::balance_on_hold(asset, &HoldReason::Order.into(), &who)
+}
+
+fn last_event() -> Event<Test> {
+	System::events()
+		.into_iter()
+		.filter_map(|e| {
+			if let RuntimeEvent::AssetConversionOrders(e) = e.event {
+				Some(e)
+			} else {
+				None
+			}
+		})
+		.last()
+		.unwrap()
+}
+
+/// Swaps 1000 of `ASSET` for the native currency, raising the price of the native currency.
+fn raise_native_price() {
+	assert_ok!(AssetConversion::swap_exact_tokens_for_tokens(
+		RuntimeOrigin::signed(3),
+		vec![Box::new(ASSET), Box::new(NATIVE)],
+		1000,
+		1,
+		3,
+		false,
+	));
+}
+
+fn place_limit_order(who: u64, amount_in: u64, min_amount_out: u64, expiry: u64) -> OrderId {
+	assert_ok!(AssetConversionOrders::place_limit_order(
+		RuntimeOrigin::signed(who),
+		Box::new(NATIVE),
+		Box::new(ASSET),
+		amount_in,
+		min_amount_out,
+		expiry,
+	));
+	NextOrderId::<Test>::get() - 1
+}
+
+#[test]
+fn place_order_works() {
+	new_test_ext().execute_with(|| {
+		create_pool();
+
+		let order_id = place_limit_order(2, 100, 110, 10);
+		assert_eq!(
+			last_event(),
+			Event::OrderPlaced {
+				order_id,
+				owner: 2,
+				asset_in: NATIVE,
+				asset_out: ASSET,
+				amount_in: 100,
+				min_amount_out: 110,
+				max_amount_out: 110,
+				expiry: 10,
+			}
+		);
+		assert_eq!(held(2, NATIVE), 100);
+		assert_eq!(OrderCount::<Test>::get(2), 1);
+
+		// Orders may sell assets other than the native currency.
+		assert_ok!(AssetConversionOrders::place_range_order(
+			RuntimeOrigin::signed(2),
+			Box::new(ASSET),
+			Box::new(NATIVE),
+			200,
+			100,
+			300,
+			10,
+		));
+		assert_eq!(held(2, ASSET), 200);
+		assert_eq!(NativeAndAssets::balance(ASSET, &2), 20000 - 200);
+		assert_eq!(OrderCount::<Test>::get(2), 2);
+	});
+}
+
+#[test]
+fn place_order_fails_for_invalid_orders() {
+	new_test_ext().execute_with(|| {
+		create_pool();
+
+		let place = |asset_out, amount_in, expiry| {
+			AssetConversionOrders::place_limit_order(
+				RuntimeOrigin::signed(2),
+				Box::new(NATIVE),
+				Box::new(asset_out),
+				amount_in,
+				110,
+				expiry,
+			)
+		};
+		assert_noop!(place(NATIVE, 100, 10), Error::<Test>::InvalidAssetPair);
+		assert_noop!(place(NativeOrWithId::WithId(3), 100, 10), Error::<Test>::PoolNotFound);
+		assert_noop!(place(ASSET, 0, 10), Error::<Test>::AmountTooLow);
+		assert_noop!(place(ASSET, 100, 1), Error::<Test>::InvalidExpiry);
+		assert_noop!(
+			AssetConversionOrders::place_range_order(
+				RuntimeOrigin::signed(2),
+				Box::new(NATIVE),
+				Box::new(ASSET),
+				100,
+				110,
+				110,
+				10,
+			),
+			Error::<Test>::InvalidRange
+		);
+
+		for _ in 0..3 {
+			assert_ok!(place(ASSET, 100, 10));
+		}
+		assert_noop!(place(ASSET, 100, 10), Error::<Test>::TooManyOrders);
+	});
+}
+
+#[test]
+fn limit_order_is_filled_when_price_is_reached() {
+	new_test_ext().execute_with(|| {
+		create_pool();
+		let order_id = place_limit_order(2, 100, 110, 10);
+
+		// The pool doesn't offer the price yet.
+		assert_noop!(
+			AssetConversionOrders::fill_order(RuntimeOrigin::signed(4), order_id),
+			Error::<Test>::PriceNotReached
+		);
+		run_on_idle();
+		assert_eq!(Orders::<Test>::get(order_id).unwrap().filled_in, 0);
+
+		raise_native_price();
+		let amount_out =
+			AssetConversion::quote_price_exact_tokens_for_tokens(NATIVE, ASSET, 100, true).unwrap();
+		assert!(amount_out >= 110);
+
+		run_on_idle();
+		assert_eq!(
+			last_event(),
+			Event::OrderFilled { order_id, amount_in: 100, amount_out, remaining: 0 }
+		);
+		assert!(Orders::<Test>::get(order_id).is_none());
+		assert_eq!(OrderCount::<Test>::get(2), 0);
+		assert_eq!(held(2, NATIVE), 0);
+		assert_eq!(NativeAndAssets::balance(NATIVE, &2), 20000 - 100);
+		assert_eq!(NativeAndAssets::balance(ASSET, &2), 20000 + amount_out);
+	});
+}
+
+#[test]
+fn limit_order_is_partially_filled() {
+	new_test_ext().execute_with(|| {
+		create_pool();
+		raise_native_price();
+		let order_id = place_limit_order(2, 2000, 2400, 10);
+
+		// Only a part of the order can be sold at the price asked.
+		assert_ok!(AssetConversionOrders::fill_order(RuntimeOrigin::signed(4), order_id));
+		let order = Orders::<Test>::get(order_id).unwrap();
+		assert!(order.filled_in > 0 && order.filled_in < 2000);
+		assert!(order.filled_out * 2000 >= order.filled_in * 2400);
+		assert_eq!(
+			last_event(),
+			Event::OrderFilled {
+				order_id,
+				amount_in: order.filled_in,
+				amount_out: order.filled_out,
+				remaining: 2000 - order.filled_in,
+			}
+		);
+		assert_eq!(held(2, NATIVE), 2000 - order.filled_in);
+		assert_eq!(NativeAndAssets::balance(ASSET, &2), 20000 + order.filled_out);
+		assert_eq!(OrderCount::<Test>::get(2), 1);
+	});
+}
+
+#[test]
+fn fill_search_is_bounded_by_the_reserves() {
+	new_test_ext().execute_with(|| {
+		create_pool();
+		// The order is much larger than what the pool could ever pay for at the price asked.
+		let order_id = place_limit_order(1, 80000, 72000, 10);
+		let order = Orders::<Test>::get(order_id).unwrap();
+		let fillable = |amount| {
+			AssetConversion::quote_price_exact_tokens_for_tokens(NATIVE, ASSET, amount, true)
+				.unwrap() >= AssetConversionOrders::required_amount_out(&order, amount).unwrap()
+		};
+
+		// The search only covers the amounts the reserves allow, so it finds the largest one.
+		let amount_in = AssetConversionOrders::fillable_amount(&order, 80000).unwrap();
+		assert!(fillable(amount_in) && !fillable(amount_in + 1));
+
+		assert_ok!(AssetConversionOrders::fill_order(RuntimeOrigin::signed(4), order_id));
+		assert_eq!(Orders::<Test>::get(order_id).unwrap().filled_in, amount_in);
+	});
+}
+
+#[test]
+fn range_order_is_filled_progressively() {
+	new_test_ext().execute_with(|| {
+		create_pool();
+		assert_ok!(AssetConversionOrders::place_range_order(
+			RuntimeOrigin::signed(2),
+			Box::new(NATIVE),
+			Box::new(ASSET),
+			1000,
+			1000,
+			1500,
+			10,
+		));
+		let order_id = NextOrderId::<Test>::get() - 1;
+
+		// The price is below the range.
+		run_on_idle();
+		assert_eq!(Orders::<Test>::get(order_id).unwrap().filled_in, 0);
+
+		// The price moves into the range, so a part of the order is sold.
+		raise_native_price();
+		run_on_idle();
+		let order = Orders::<Test>::get(order_id).unwrap();
+		assert!(order.filled_in > 0 && order.filled_in < 1000);
+		assert!(order.filled_out >= order.filled_in);
+
+		// The price moves further, so more of the order is sold.
+		raise_native_price();
+		run_on_idle();
+		let filled_in = order.filled_in;
+		let order = Orders::<Test>::get(order_id).unwrap();
+		assert!(order.filled_in > filled_in);
+		assert_eq!(held(2, NATIVE), 1000 - order.filled_in);
+	});
+}
+
+#[test]
+fn required_amount_out_works() {
+	new_test_ext().execute_with(|| {
+		let mut order = Order {
+			owner: 2,
+			asset_in: NATIVE,
+			asset_out: ASSET,
+			amount_in: 100,
+			min_amount_out: 120,
+			max_amount_out: 120,
+			filled_in: 0,
+			filled_out: 0,
+			expiry: 10,
+		};
+		assert_eq!(AssetConversionOrders::required_amount_out(&order, 50), Ok(60));
+		assert_eq!(AssetConversionOrders::required_amount_out(&order, 100), Ok(120));
+
+		// The price rises from 1 to 2 over the range.
+		order.min_amount_out = 100;
+		order.max_amount_out = 200;
+		assert_eq!(AssetConversionOrders::required_amount_out(&order, 100), Ok(150));
+		assert_eq!(AssetConversionOrders::required_amount_out(&order, 50), Ok(63));
+		order.filled_in = 50;
+		assert_eq!(AssetConversionOrders::required_amount_out(&order, 50), Ok(88));
+	});
+}
+
+#[test]
+fn cancel_order_works() {
+	new_test_ext().execute_with(|| {
+		create_pool();
+		let order_id = place_limit_order(2, 100, 110, 10);
+
+		assert_noop!(
+			AssetConversionOrders::cancel_order(RuntimeOrigin::signed(3), order_id),
+			Error::<Test>::NotOwner
+		);
+		assert_ok!(AssetConversionOrders::cancel_order(RuntimeOrigin::signed(2), order_id));
+		assert_eq!(last_event(), Event::OrderCancelled { order_id, released: 100 });
+		assert!(Orders::<Test>::get(order_id).is_none());
+		assert_eq!(OrderCount::<Test>::get(2), 0);
+		assert_eq!(held(2, NATIVE), 0);
+		assert_eq!(NativeAndAssets::balance(NATIVE, &2), 20000);
+
+		assert_noop!(
+			AssetConversionOrders::cancel_order(RuntimeOrigin::signed(2), order_id),
+			Error::<Test>::OrderNotFound
+		);
+	});
+}
+
+#[test]
+fn expired_order_is_removed() {
+	new_test_ext().execute_with(|| {
+		create_pool();
+		let order_id = place_limit_order(2, 100, 110, 3);
+
+		System::set_block_number(3);
+		run_on_idle();
+		assert!(Orders::<Test>::get(order_id).is_some());
+
+		// The order isn't filled past its expiry, even at the price asked.
+		System::set_block_number(4);
+		raise_native_price();
+		assert_noop!(
+			AssetConversionOrders::fill_order(RuntimeOrigin::signed(4), order_id),
+			Error::<Test>::OrderExpired
+		);
+		run_on_idle();
+		assert_eq!(last_event(), Event::OrderExpired { order_id, released: 100 });
+		assert!(Orders::<Test>::get(order_id).is_none());
+		assert_eq!(OrderCount::<Test>::get(2), 0);
+		assert_eq!(held(2, NATIVE), 0);
+		assert_eq!(NativeAndAssets::balance(NATIVE, &2), 20000);
+	});
+}
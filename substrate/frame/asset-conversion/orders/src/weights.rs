@@ -0,0 +1,121 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Weights for `pallet_asset_conversion_orders`.
+//!
+//! The weights are estimated, not measured, and are to be regenerated with the benchmark CLI.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+#![allow(dead_code)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+/// Weight functions needed for `pallet_asset_conversion_orders`.
+pub trait WeightInfo {
+	fn place_order() -> Weight;
+	fn cancel_order() -> Weight;
+	fn fill_order() -> Weight;
+}
+
+/// Weights for `pallet_asset_conversion_orders` using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// Storage: `AssetConversion::Pools` (r:1 w:0)
+	/// Storage: `AssetConversionOrders::OrderCount` (r:1 w:1)
+	/// Storage: `AssetConversionOrders::NextOrderId` (r:1 w:1)
+	/// Storage: `AssetConversionOrders::Orders` (r:0 w:1)
+	/// Storage: `Assets::Account` (r:1 w:1)
+	/// Storage: `AssetsHolder::Holds` (r:1 w:1)
+	/// Storage: `AssetsHolder::BalancesOnHold` (r:1 w:1)
+	fn place_order() -> Weight {
+		// Estimated, not measured: to be regenerated with the benchmark CLI.
+		Weight::from_parts(60_000_000, 5_000)
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(6_u64))
+	}
+	/// Storage: `AssetConversionOrders::Orders` (r:1 w:1)
+	/// Storage: `AssetConversionOrders::OrderCount` (r:1 w:1)
+	/// Storage: `Assets::Account` (r:1 w:1)
+	/// Storage: `AssetsHolder::Holds` (r:1 w:1)
+	/// Storage: `AssetsHolder::BalancesOnHold` (r:1 w:1)
+	fn cancel_order() -> Weight {
+		// Estimated, not measured: to be regenerated with the benchmark CLI.
+		Weight::from_parts(50_000_000, 5_000)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	/// Storage: `AssetConversionOrders::Orders` (r:1 w:1)
+	/// Storage: `AssetConversionOrders::OrderCount` (r:1 w:1)
+	/// Storage: `AssetsHolder::Holds` (r:1 w:1)
+	/// Storage: `AssetsHolder::BalancesOnHold` (r:1 w:1)
+	/// Storage: `Assets::Asset` (r:2 w:2)
+	/// Storage: `Assets::Account` (r:4 w:4)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Storage: `AssetConversion::Pools` (r:1 w:0)
+	fn fill_order() -> Weight {
+		// Estimated, not measured: to be regenerated with the benchmark CLI.
+		Weight::from_parts(330_000_000, 12_000)
+			.saturating_add(T::DbWeight::get().reads(13_u64))
+			.saturating_add(T::DbWeight::get().writes(12_u64))
+	}
+}
+
+// For backwards compatibility and tests.
+impl WeightInfo for () {
+	/// Storage: `AssetConversion::Pools` (r:1 w:0)
+	/// Storage: `AssetConversionOrders::OrderCount` (r:1 w:1)
+	/// Storage: `AssetConversionOrders::NextOrderId` (r:1 w:1)
+	/// Storage: `AssetConversionOrders::Orders` (r:0 w:1)
+	/// Storage: `Assets::Account` (r:1 w:1)
+	/// Storage: `AssetsHolder::Holds` (r:1 w:1)
+	/// Storage: `AssetsHolder::BalancesOnHold` (r:1 w:1)
+	fn place_order() -> Weight {
+		// Estimated, not measured: to be regenerated with the benchmark CLI.
+		Weight::from_parts(60_000_000, 5_000)
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(6_u64))
+	}
+	/// Storage: `AssetConversionOrders::Orders` (r:1 w:1)
+	/// Storage: `AssetConversionOrders::OrderCount` (r:1 w:1)
+	/// Storage: `Assets::Account` (r:1 w:1)
+	/// Storage: `AssetsHolder::Holds` (r:1 w:1)
+	/// Storage: `AssetsHolder::BalancesOnHold` (r:1 w:1)
+	fn cancel_order() -> Weight {
+		// Estimated, not measured: to be regenerated with the benchmark CLI.
+		Weight::from_parts(50_000_000, 5_000)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	/// Storage: `AssetConversionOrders::Orders` (r:1 w:1)
+	/// Storage: `AssetConversionOrders::OrderCount` (r:1 w:1)
+	/// Storage: `AssetsHolder::Holds` (r:1 w:1)
+	/// Storage: `AssetsHolder::BalancesOnHold` (r:1 w:1)
+	/// Storage: `Assets::Asset` (r:2 w:2)
+	/// Storage: `Assets::Account` (r:4 w:4)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Storage: `AssetConversion::Pools` (r:1 w:0)
+	fn fill_order() -> Weight {
+		// Estimated, not measured: to be regenerated with the benchmark CLI.
+		Weight::from_parts(330_000_000, 12_000)
+			.saturating_add(RocksDbWeight::get().reads(13_u64))
+			.saturating_add(RocksDbWeight::get().writes(12_u64))
+	}
+}
@@ -410,5 +410,40 @@ mod benchmarks {
 		assert_last_event::<T>(Event::Touched { pool_id, who: caller }.into());
 	}
 
+	#[benchmark]
+	fn stable_swap_curve() {
+		// The read of the curve of a pool and the computation of a swap on the StableSwap curve at
+		// the maximum number of iterations of Newton's method, for the invariant and then for the
+		// new reserve. The steps are repeated regardless of the convergence, which doesn't change
+		// their cost.
+		let (asset1, asset2) = T::BenchmarkHelper::create_pair(0, 1);
+		let reserve = T::HigherPrecisionBalance::from(u32::MAX)
+			.ensure_mul(256u32.into())
+			.expect("fits the higher precision balance");
+		let (x, y) = (reserve, reserve);
+		let new_x = x.ensure_add(reserve / 2u32.into()).expect("fits the higher precision balance");
+		let ann = AssetConversion::<T>::stable_swap_ann(PoolCurve::MAX_AMPLIFICATION)
+			.expect("maximum amplification is valid");
+		let mut d = x.ensure_add(y).expect("fits the higher precision balance");
+		let mut new_y = d;
+
+		#[block]
+		{
+			let _ = AssetConversion::<T>::pool_curve(&asset1, &asset2);
+			for _ in 0..STABLE_SWAP_ITERATIONS {
+				d = AssetConversion::<T>::stable_swap_invariant_step(ann, x, y, d)
+					.expect("balanced reserves have an invariant");
+			}
+			let (b, c) = AssetConversion::<T>::stable_swap_reserve_coefficients(ann, new_x, d)
+				.expect("the invariant has coefficients");
+			for _ in 0..STABLE_SWAP_ITERATIONS {
+				new_y = AssetConversion::<T>::stable_swap_reserve_step(b, c, d, new_y)
+					.expect("the new reserve is found");
+			}
+		}
+
+		assert!(new_y < y);
+	}
+
 	impl_benchmark_test_suite!(AssetConversion, crate::mock::new_test_ext(), crate::mock::Test);
 }
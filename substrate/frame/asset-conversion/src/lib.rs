@@ -23,7 +23,8 @@
 //!
 //! This pallet allows you to:
 //!
-//!  - [create a liquidity pool](`Pallet::create_pool()`) for 2 assets
+//!  - [create a liquidity pool](`Pallet::create_pool()`) for 2 assets, or [one with a specific
+//!    curve](`Pallet::create_pool_with_curve()`), such as the StableSwap curve for pegged assets
 //!  - [provide the liquidity](`Pallet::add_liquidity()`) and receive back an LP token
 //!  - [exchange the LP token back to assets](`Pallet::remove_liquidity()`)
 //!  - [swap a specific amount of assets for another](`Pallet::swap_exact_tokens_for_tokens()`) if
@@ -91,12 +92,15 @@ use frame_support::{
 use sp_core::Get;
 use sp_runtime::{
 	traits::{
-		CheckedAdd, CheckedDiv, CheckedMul, CheckedSub, Ensure, IntegerSquareRoot, MaybeDisplay,
-		One, TrailingZeroInput, Zero,
+		CheckedAdd, CheckedDiv, CheckedMul, CheckedSub, Ensure, EnsureAdd, EnsureDiv, EnsureMul,
+		EnsureSub, IntegerSquareRoot, MaybeDisplay, One, TrailingZeroInput, Zero,
 	},
-	DispatchError, Saturating, TokenError, TransactionOutcome,
+	ArithmeticError, DispatchError, RuntimeDebug, Saturating, TokenError, TransactionOutcome,
 };
 
+/// The maximum number of iterations of Newton's method for the StableSwap curve.
+const STABLE_SWAP_ITERATIONS: u32 = 255;
+
 #[frame_support::pallet]
 pub mod pallet {
 	use super::*;
@@ -121,6 +125,7 @@ pub mod pallet {
 
 		/// A type used for calculations concerning the `Balance` type to avoid possible overflows.
 		type HigherPrecisionBalance: IntegerSquareRoot
+			+ Copy
 			+ One
 			+ Ensure
 			+ Unsigned
@@ -204,6 +209,11 @@ pub mod pallet {
 	pub type Pools<T: Config> =
 		StorageMap<_, Blake2_128Concat, T::PoolId, PoolInfo<T::PoolAssetId>, OptionQuery>;
 
+	/// The curve of each pool. Pools without an entry use the constant product curve.
+	#[pallet::storage]
+	pub type PoolCurves<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::PoolId, PoolCurve, ValueQuery>;
+
 	/// Stores the `PoolAssetId` that is going to be used for the next lp token.
 	/// This gets incremented whenever a new lp pool is created.
 	#[pallet::storage]
@@ -296,6 +306,13 @@ pub mod pallet {
 			/// The account initiating the touch.
 			who: T::AccountId,
 		},
+		/// A pool has been created with a curve other than the constant product one.
+		PoolCurveSet {
+			/// The ID of the pool.
+			pool_id: T::PoolId,
+			/// The curve of the pool.
+			curve: PoolCurve,
+		},
 	}
 
 	#[pallet::error]
@@ -349,6 +366,8 @@ pub mod pallet {
 		IncorrectPoolAssetId,
 		/// The destination account cannot exist with the swapped funds.
 		BelowMinimum,
+		/// The parameters of the pool curve are invalid.
+		InvalidCurve,
 	}
 
 	#[pallet::hooks]
@@ -376,51 +395,23 @@ pub mod pallet {
 			asset2: Box<T::AssetKind>,
 		) -> DispatchResult {
 			let sender = ensure_signed(origin)?;
-			ensure!(asset1 != asset2, Error::<T>::InvalidAssetPair);
-
-			// prepare pool_id
-			let pool_id = T::PoolLocator::pool_id(&asset1, &asset2)
-				.map_err(|_| Error::<T>::InvalidAssetPair)?;
-			ensure!(!Pools::<T>::contains_key(&pool_id), Error::<T>::PoolExists);
-
-			let pool_account =
-				T::PoolLocator::address(&pool_id).map_err(|_| Error::<T>::InvalidAssetPair)?;
-
-			// pay the setup fee
-			let fee =
-				Self::withdraw(T::PoolSetupFeeAsset::get(), &sender, T::PoolSetupFee::get(), true)?;
-			T::PoolSetupFeeTarget::on_unbalanced(fee);
-
-			if T::Assets::should_touch(*asset1.clone(), &pool_account) {
-				T::Assets::touch(*asset1, &pool_account, &sender)?
-			};
-
-			if T::Assets::should_touch(*asset2.clone(), &pool_account) {
-				T::Assets::touch(*asset2, &pool_account, &sender)?
-			};
-
-			let lp_token = NextPoolAssetId::<T>::get()
-				.or(T::PoolAssetId::initial_value())
-				.ok_or(Error::<T>::IncorrectPoolAssetId)?;
-			let next_lp_token_id = lp_token.increment().ok_or(Error::<T>::IncorrectPoolAssetId)?;
-			NextPoolAssetId::<T>::set(Some(next_lp_token_id));
-
-			T::PoolAssets::create(lp_token.clone(), pool_account.clone(), false, 1u32.into())?;
-			if T::PoolAssets::should_touch(lp_token.clone(), &pool_account) {
-				T::PoolAssets::touch(lp_token.clone(), &pool_account, &sender)?
-			};
-
-			let pool_info = PoolInfo { lp_token: lp_token.clone() };
-			Pools::<T>::insert(pool_id.clone(), pool_info);
-
-			Self::deposit_event(Event::PoolCreated {
-				creator: sender,
-				pool_id,
-				pool_account,
-				lp_token,
-			});
+			Self::do_create_pool(sender, *asset1, *asset2, PoolCurve::ConstantProduct)
+		}
 
-			Ok(())
+		/// Creates an empty liquidity pool, whose swaps follow the given `curve`, and an associated
+		/// new `lp_token` asset (the id of which is returned in the `Event::PoolCreated` event).
+		///
+		/// Once a pool is created, someone may [`Pallet::add_liquidity`] to it.
+		#[pallet::call_index(6)]
+		#[pallet::weight(T::WeightInfo::create_pool().saturating_add(T::DbWeight::get().writes(1)))]
+		pub fn create_pool_with_curve(
+			origin: OriginFor<T>,
+			asset1: Box<T::AssetKind>,
+			asset2: Box<T::AssetKind>,
+			curve: PoolCurve,
+		) -> DispatchResult {
+			let sender = ensure_signed(origin)?;
+			Self::do_create_pool(sender, *asset1, *asset2, curve)
 		}
 
 		/// Provide liquidity into the pool of `asset1` and `asset2`.
@@ -632,7 +623,10 @@ pub mod pallet {
 		/// [`AssetConversionApi::quote_price_exact_tokens_for_tokens`] runtime call can be called
 		/// for a quote.
 		#[pallet::call_index(3)]
-		#[pallet::weight(T::WeightInfo::swap_exact_tokens_for_tokens(path.len() as u32))]
+		#[pallet::weight(Pallet::<T>::swap_weight(
+			T::WeightInfo::swap_exact_tokens_for_tokens(path.len() as u32),
+			path.len() as u32,
+		))]
 		pub fn swap_exact_tokens_for_tokens(
 			origin: OriginFor<T>,
 			path: Vec<Box<T::AssetKind>>,
@@ -660,7 +654,10 @@ pub mod pallet {
 		/// [`AssetConversionApi::quote_price_tokens_for_exact_tokens`] runtime call can be called
 		/// for a quote.
 		#[pallet::call_index(4)]
-		#[pallet::weight(T::WeightInfo::swap_tokens_for_exact_tokens(path.len() as u32))]
+		#[pallet::weight(Pallet::<T>::swap_weight(
+			T::WeightInfo::swap_tokens_for_exact_tokens(path.len() as u32),
+			path.len() as u32,
+		))]
 		pub fn swap_tokens_for_exact_tokens(
 			origin: OriginFor<T>,
 			path: Vec<Box<T::AssetKind>>,
@@ -726,6 +723,67 @@ pub mod pallet {
 	}
 
 	impl<T: Config> Pallet<T> {
+		/// Create an empty liquidity pool of `asset1` and `asset2` following `curve`.
+		pub(crate) fn do_create_pool(
+			sender: T::AccountId,
+			asset1: T::AssetKind,
+			asset2: T::AssetKind,
+			curve: PoolCurve,
+		) -> DispatchResult {
+			ensure!(asset1 != asset2, Error::<T>::InvalidAssetPair);
+			ensure!(curve.is_valid(), Error::<T>::InvalidCurve);
+
+			// prepare pool_id
+			let pool_id = T::PoolLocator::pool_id(&asset1, &asset2)
+				.map_err(|_| Error::<T>::InvalidAssetPair)?;
+			ensure!(!Pools::<T>::contains_key(&pool_id), Error::<T>::PoolExists);
+
+			let pool_account =
+				T::PoolLocator::address(&pool_id).map_err(|_| Error::<T>::InvalidAssetPair)?;
+
+			// pay the setup fee
+			let fee =
+				Self::withdraw(T::PoolSetupFeeAsset::get(), &sender, T::PoolSetupFee::get(), true)?;
+			T::PoolSetupFeeTarget::on_unbalanced(fee);
+
+			if T::Assets::should_touch(asset1.clone(), &pool_account) {
+				T::Assets::touch(asset1, &pool_account, &sender)?
+			};
+
+			if T::Assets::should_touch(asset2.clone(), &pool_account) {
+				T::Assets::touch(asset2, &pool_account, &sender)?
+			};
+
+			let lp_token = NextPoolAssetId::<T>::get()
+				.or(T::PoolAssetId::initial_value())
+				.ok_or(Error::<T>::IncorrectPoolAssetId)?;
+			let next_lp_token_id = lp_token.increment().ok_or(Error::<T>::IncorrectPoolAssetId)?;
+			NextPoolAssetId::<T>::set(Some(next_lp_token_id));
+
+			T::PoolAssets::create(lp_token.clone(), pool_account.clone(), false, 1u32.into())?;
+			if T::PoolAssets::should_touch(lp_token.clone(), &pool_account) {
+				T::PoolAssets::touch(lp_token.clone(), &pool_account, &sender)?
+			};
+
+			let pool_info = PoolInfo { lp_token: lp_token.clone() };
+			Pools::<T>::insert(pool_id.clone(), pool_info);
+			if curve != PoolCurve::ConstantProduct {
+				PoolCurves::<T>::insert(pool_id.clone(), curve);
+			}
+
+			Self::deposit_event(Event::PoolCreated {
+				creator: sender,
+				pool_id: pool_id.clone(),
+				pool_account,
+				lp_token,
+			});
+			if curve != PoolCurve::ConstantProduct {
+				Self::deposit_event(Event::PoolCurveSet { pool_id, curve });
+			}
+
+			Ok(())
+		}
+
 		/// Swap exactly `amount_in` of asset `path[0]` for asset `path[1]`.
 		/// If an `amount_out_min` is specified, it will return an error if it is unable to acquire
 		/// the amount desired.
@@ -1066,8 +1124,10 @@ pub mod pallet {
 					},
 				};
 				let (reserve_in, reserve_out) = Self::get_reserves(asset1.clone(), asset2.clone())?;
+				let curve = Self::pool_curve(asset1, &asset2);
 				balance_path.push((asset2, amount_in));
-				amount_in = Self::get_amount_in(&amount_in, &reserve_in, &reserve_out)?;
+				amount_in =
+					Self::get_amount_in_on_curve(curve, &amount_in, &reserve_in, &reserve_out)?;
 			}
 			balance_path.reverse();
 
@@ -1092,8 +1152,10 @@ pub mod pallet {
 					},
 				};
 				let (reserve_in, reserve_out) = Self::get_reserves(asset1.clone(), asset2.clone())?;
+				let curve = Self::pool_curve(&asset1, asset2);
 				balance_path.push((asset1, amount_out));
-				amount_out = Self::get_amount_out(&amount_out, &reserve_in, &reserve_out)?;
+				amount_out =
+					Self::get_amount_out_on_curve(curve, &amount_out, &reserve_in, &reserve_out)?;
 			}
 			Ok(balance_path)
 		}
@@ -1106,14 +1168,25 @@ pub mod pallet {
 			include_fee: bool,
 		) -> Option<T::Balance> {
 			let pool_account = T::PoolLocator::pool_address(&asset1, &asset2).ok()?;
+			let curve = Self::pool_curve(&asset1, &asset2);
 
 			let balance1 = Self::get_balance(&pool_account, asset1);
 			let balance2 = Self::get_balance(&pool_account, asset2);
 			if !balance1.is_zero() {
-				if include_fee {
-					Self::get_amount_out(&amount, &balance1, &balance2).ok()
-				} else {
-					Self::quote(&amount, &balance1, &balance2).ok()
+				match (curve, include_fee) {
+					(_, true) =>
+						Self::get_amount_out_on_curve(curve, &amount, &balance1, &balance2).ok(),
+					(PoolCurve::ConstantProduct, false) =>
+						Self::quote(&amount, &balance1, &balance2).ok(),
+					(PoolCurve::StableSwap { amplification }, false) =>
+						Self::stable_swap_amount_out(
+							amplification,
+							0,
+							&amount,
+							&balance1,
+							&balance2,
+						)
+						.ok(),
 				}
 			} else {
 				None
@@ -1128,14 +1201,19 @@ pub mod pallet {
 			include_fee: bool,
 		) -> Option<T::Balance> {
 			let pool_account = T::PoolLocator::pool_address(&asset1, &asset2).ok()?;
+			let curve = Self::pool_curve(&asset1, &asset2);
 
 			let balance1 = Self::get_balance(&pool_account, asset1);
 			let balance2 = Self::get_balance(&pool_account, asset2);
 			if !balance1.is_zero() {
-				if include_fee {
-					Self::get_amount_in(&amount, &balance1, &balance2).ok()
-				} else {
-					Self::quote(&amount, &balance2, &balance1).ok()
+				match (curve, include_fee) {
+					(_, true) =>
+						Self::get_amount_in_on_curve(curve, &amount, &balance1, &balance2).ok(),
+					(PoolCurve::ConstantProduct, false) =>
+						Self::quote(&amount, &balance2, &balance1).ok(),
+					(PoolCurve::StableSwap { amplification }, false) =>
+						Self::stable_swap_amount_in(amplification, 0, &amount, &balance1, &balance2)
+							.ok(),
 				}
 			} else {
 				None
@@ -1260,6 +1338,258 @@ pub mod pallet {
 			result.try_into().map_err(|_| Error::<T>::Overflow)
 		}
 
+		/// Returns the curve of the pool of `asset1` and `asset2`.
+		pub(crate) fn pool_curve(asset1: &T::AssetKind, asset2: &T::AssetKind) -> PoolCurve {
+			match T::PoolLocator::pool_id(asset1, asset2) {
+				Ok(pool_id) => PoolCurves::<T>::get(pool_id),
+				Err(()) => PoolCurve::ConstantProduct,
+			}
+		}
+
+		/// Calculates amount out for a pool following `curve`.
+		///
+		/// Given an input amount of an asset and pair reserves, returns the maximum output amount
+		/// of the other asset.
+		pub fn get_amount_out_on_curve(
+			curve: PoolCurve,
+			amount_in: &T::Balance,
+			reserve_in: &T::Balance,
+			reserve_out: &T::Balance,
+		) -> Result<T::Balance, Error<T>> {
+			match curve {
+				PoolCurve::ConstantProduct =>
+					Self::get_amount_out(amount_in, reserve_in, reserve_out),
+				PoolCurve::StableSwap { amplification } => {
+					ensure!(
+						!reserve_in.is_zero() && !reserve_out.is_zero(),
+						Error::<T>::ZeroLiquidity
+					);
+					Self::stable_swap_amount_out(
+						amplification,
+						T::LPFee::get(),
+						amount_in,
+						reserve_in,
+						reserve_out,
+					)
+					.map_err(|_| Error::<T>::Overflow)
+				},
+			}
+		}
+
+		/// Calculates amount in for a pool following `curve`.
+		///
+		/// Given an output amount of an asset and pair reserves, returns a required input amount
+		/// of the other asset.
+		pub fn get_amount_in_on_curve(
+			curve: PoolCurve,
+			amount_out: &T::Balance,
+			reserve_in: &T::Balance,
+			reserve_out: &T::Balance,
+		) -> Result<T::Balance, Error<T>> {
+			match curve {
+				PoolCurve::ConstantProduct =>
+					Self::get_amount_in(amount_out, reserve_in, reserve_out),
+				PoolCurve::StableSwap { amplification } => {
+					ensure!(
+						!reserve_in.is_zero() && !reserve_out.is_zero(),
+						Error::<T>::ZeroLiquidity
+					);
+					ensure!(amount_out < reserve_out, Error::<T>::AmountOutTooHigh);
+					Self::stable_swap_amount_in(
+						amplification,
+						T::LPFee::get(),
+						amount_out,
+						reserve_in,
+						reserve_out,
+					)
+					.map_err(|_| Error::<T>::Overflow)
+				},
+			}
+		}
+
+		/// Calculates amount out on the StableSwap curve with the given `amplification`, charging
+		/// a fee of `fee` per mille of the amount in.
+		fn stable_swap_amount_out(
+			amplification: u32,
+			fee: u32,
+			amount_in: &T::Balance,
+			reserve_in: &T::Balance,
+			reserve_out: &T::Balance,
+		) -> Result<T::Balance, ArithmeticError> {
+			let amount_in = T::HigherPrecisionBalance::from(*amount_in);
+			let reserve_in = T::HigherPrecisionBalance::from(*reserve_in);
+			let reserve_out = T::HigherPrecisionBalance::from(*reserve_out);
+
+			let amount_in_with_fee = amount_in
+				.ensure_mul(T::HigherPrecisionBalance::from(1000u32).ensure_sub(fee.into())?)?
+				.ensure_div(1000u32.into())?;
+			let invariant = Self::stable_swap_invariant(amplification, reserve_in, reserve_out)?;
+			let new_reserve_out = Self::stable_swap_reserve(
+				amplification,
+				reserve_in.ensure_add(amount_in_with_fee)?,
+				invariant,
+			)?;
+
+			// Round down in favour of the pool.
+			let result = reserve_out.ensure_sub(new_reserve_out)?;
+			let result = if result.is_zero() { result } else { result.ensure_sub(One::one())? };
+
+			result.try_into().map_err(|_| ArithmeticError::Overflow)
+		}
+
+		/// Calculates amount in on the StableSwap curve with the given `amplification`, charging
+		/// a fee of `fee` per mille of the amount in.
+		fn stable_swap_amount_in(
+			amplification: u32,
+			fee: u32,
+			amount_out: &T::Balance,
+			reserve_in: &T::Balance,
+			reserve_out: &T::Balance,
+		) -> Result<T::Balance, ArithmeticError> {
+			let amount_out = T::HigherPrecisionBalance::from(*amount_out);
+			let reserve_in = T::HigherPrecisionBalance::from(*reserve_in);
+			let reserve_out = T::HigherPrecisionBalance::from(*reserve_out);
+
+			let invariant = Self::stable_swap_invariant(amplification, reserve_in, reserve_out)?;
+			let new_reserve_in = Self::stable_swap_reserve(
+				amplification,
+				reserve_out.ensure_sub(amount_out)?,
+				invariant,
+			)?;
+
+			// Round up in favour of the pool.
+			let amount_in_with_fee =
+				new_reserve_in.ensure_sub(reserve_in)?.ensure_add(One::one())?;
+			let result = amount_in_with_fee
+				.ensure_mul(1000u32.into())?
+				.ensure_div(T::HigherPrecisionBalance::from(1000u32).ensure_sub(fee.into())?)?
+				.ensure_add(One::one())?;
+
+			result.try_into().map_err(|_| ArithmeticError::Overflow)
+		}
+
+		/// Calculates the invariant `D` of the StableSwap curve for two assets with reserves `x`
+		/// and `y`, i.e. the solution of `4A(x + y) + D = 4AD + D^3 / 4xy`, by Newton's method.
+		fn stable_swap_invariant(
+			amplification: u32,
+			x: T::HigherPrecisionBalance,
+			y: T::HigherPrecisionBalance,
+		) -> Result<T::HigherPrecisionBalance, ArithmeticError> {
+			let ann = Self::stable_swap_ann(amplification)?;
+
+			let mut d = x.ensure_add(y)?;
+			for _ in 0..STABLE_SWAP_ITERATIONS {
+				let previous = d;
+				d = Self::stable_swap_invariant_step(ann, x, y, d)?;
+				if Self::converged(d, previous) {
+					return Ok(d)
+				}
+			}
+			Err(ArithmeticError::Overflow)
+		}
+
+		/// The amplification coefficient of the StableSwap curve for two assets, `4A`.
+		pub(crate) fn stable_swap_ann(
+			amplification: u32,
+		) -> Result<T::HigherPrecisionBalance, ArithmeticError> {
+			T::HigherPrecisionBalance::from(amplification).ensure_mul(4u32.into())
+		}
+
+		/// A step of Newton's method for the invariant of the StableSwap curve, improving the
+		/// approximation `d`.
+		pub(crate) fn stable_swap_invariant_step(
+			ann: T::HigherPrecisionBalance,
+			x: T::HigherPrecisionBalance,
+			y: T::HigherPrecisionBalance,
+			d: T::HigherPrecisionBalance,
+		) -> Result<T::HigherPrecisionBalance, ArithmeticError> {
+			let two = T::HigherPrecisionBalance::from(2u32);
+			let d_p = d
+				.ensure_mul(d)?
+				.ensure_div(x.ensure_mul(two)?)?
+				.ensure_mul(d)?
+				.ensure_div(y.ensure_mul(two)?)?;
+			let numerator = ann
+				.ensure_mul(x.ensure_add(y)?)?
+				.ensure_add(d_p.ensure_mul(two)?)?
+				.ensure_mul(d)?;
+			let denominator = ann
+				.ensure_sub(One::one())?
+				.ensure_mul(d)?
+				.ensure_add(d_p.ensure_mul(3u32.into())?)?;
+			numerator.ensure_div(denominator)
+		}
+
+		/// Calculates the reserve of one asset of the StableSwap curve with invariant `d` for the
+		/// reserve `x` of the other asset, by Newton's method.
+		fn stable_swap_reserve(
+			amplification: u32,
+			x: T::HigherPrecisionBalance,
+			d: T::HigherPrecisionBalance,
+		) -> Result<T::HigherPrecisionBalance, ArithmeticError> {
+			let ann = Self::stable_swap_ann(amplification)?;
+			let (b, c) = Self::stable_swap_reserve_coefficients(ann, x, d)?;
+
+			let mut y = d;
+			for _ in 0..STABLE_SWAP_ITERATIONS {
+				let previous = y;
+				y = Self::stable_swap_reserve_step(b, c, d, y)?;
+				if Self::converged(y, previous) {
+					return Ok(y)
+				}
+			}
+			Err(ArithmeticError::Overflow)
+		}
+
+		/// The coefficients `b` and `c` of the quadratic equation `y^2 + (b - d)y = c` solved for
+		/// the reserve `y` of the StableSwap curve with invariant `d` and reserve `x`.
+		pub(crate) fn stable_swap_reserve_coefficients(
+			ann: T::HigherPrecisionBalance,
+			x: T::HigherPrecisionBalance,
+			d: T::HigherPrecisionBalance,
+		) -> Result<(T::HigherPrecisionBalance, T::HigherPrecisionBalance), ArithmeticError> {
+			let two = T::HigherPrecisionBalance::from(2u32);
+			let c = d
+				.ensure_mul(d)?
+				.ensure_div(x.ensure_mul(two)?)?
+				.ensure_mul(d)?
+				.ensure_div(ann.ensure_mul(two)?)?;
+			let b = x.ensure_add(d.ensure_div(ann)?)?;
+			Ok((b, c))
+		}
+
+		/// A step of Newton's method for the reserve of the StableSwap curve with invariant `d`,
+		/// improving the approximation `y`, where `b` and `c` are the coefficients of the
+		/// quadratic equation solved.
+		pub(crate) fn stable_swap_reserve_step(
+			b: T::HigherPrecisionBalance,
+			c: T::HigherPrecisionBalance,
+			d: T::HigherPrecisionBalance,
+			y: T::HigherPrecisionBalance,
+		) -> Result<T::HigherPrecisionBalance, ArithmeticError> {
+			y.ensure_mul(y)?
+				.ensure_add(c)?
+				.ensure_div(y.ensure_mul(2u32.into())?.ensure_add(b)?.ensure_sub(d)?)
+		}
+
+		/// The weight of swapping along a path of `path_len` assets on top of the given `weight`,
+		/// which is measured on constant product pools.
+		///
+		/// The pools on the path may follow the StableSwap curve, whose swaps are heavier: the
+		/// read of the curve and the StableSwap computation at the maximum number of iterations
+		/// are added for each pool. This applies to swaps through [`SwapCredit`] and to quotes as
+		/// well.
+		pub fn swap_weight(weight: Weight, path_len: u32) -> Weight {
+			let pools = path_len.saturating_sub(1) as u64;
+			weight.saturating_add(T::WeightInfo::stable_swap_curve().saturating_mul(pools))
+		}
+
+		/// Whether two successive approximations of Newton's method are within one unit.
+		fn converged(a: T::HigherPrecisionBalance, b: T::HigherPrecisionBalance) -> bool {
+			let difference = if a > b { a - b } else { b - a };
+			difference <= One::one()
+		}
+
 		/// Ensure that a path is valid.
 		fn validate_swap_path(path: &Vec<T::AssetKind>) -> Result<(), DispatchError> {
 			ensure!(path.len() >= 2, Error::<T>::InvalidPath);
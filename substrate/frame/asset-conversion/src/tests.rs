@@ -2493,3 +2493,238 @@ fn swap_credit_invalid_path() {
 		assert_eq!(error, (expected_credit_in, Error::<Test>::InvalidPath.into()));
 	});
 }
+
+#[test]
+fn can_create_pool_with_curve() {
+	new_test_ext().execute_with(|| {
+		let user = 1;
+		let token_1 = NativeOrWithId::WithId(2);
+		let token_2 = NativeOrWithId::WithId(3);
+		let pool_id = (token_1.clone(), token_2.clone());
+		let curve = PoolCurve::StableSwap { amplification: 100 };
+
+		create_tokens(user, vec![token_1.clone(), token_2.clone()]);
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), user, 1000));
+
+		assert_noop!(
+			AssetConversion::create_pool_with_curve(
+				RuntimeOrigin::signed(user),
+				Box::new(token_1.clone()),
+				Box::new(token_2.clone()),
+				PoolCurve::StableSwap { amplification: 0 },
+			),
+			Error::<Test>::InvalidCurve
+		);
+		assert_noop!(
+			AssetConversion::create_pool_with_curve(
+				RuntimeOrigin::signed(user),
+				Box::new(token_1.clone()),
+				Box::new(token_2.clone()),
+				PoolCurve::StableSwap { amplification: PoolCurve::MAX_AMPLIFICATION + 1 },
+			),
+			Error::<Test>::InvalidCurve
+		);
+
+		let lp_token = AssetConversion::get_next_pool_asset_id();
+		assert_ok!(AssetConversion::create_pool_with_curve(
+			RuntimeOrigin::signed(user),
+			Box::new(token_1.clone()),
+			Box::new(token_2.clone()),
+			curve,
+		));
+
+		assert_eq!(
+			events(),
+			[
+				Event::<Test>::PoolCreated {
+					creator: user,
+					pool_id: pool_id.clone(),
+					pool_account: <Test as Config>::PoolLocator::address(&pool_id).unwrap(),
+					lp_token
+				},
+				Event::<Test>::PoolCurveSet { pool_id: pool_id.clone(), curve },
+			]
+		);
+		assert_eq!(PoolCurves::<Test>::get(&pool_id), curve);
+		assert_eq!(pools(), vec![pool_id]);
+	});
+}
+
+#[test]
+fn stable_swap_curve_quotes_close_to_parity() {
+	new_test_ext().execute_with(|| {
+		let user = 1;
+		let token_1 = NativeOrWithId::WithId(2);
+		let token_2 = NativeOrWithId::WithId(3);
+		let token_3 = NativeOrWithId::WithId(4);
+		let liquidity = 1_000_000;
+
+		create_tokens(user, vec![token_1.clone(), token_2.clone(), token_3.clone()]);
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), user, 1000));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(user), 2, user, 2 * liquidity));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(user), 3, user, liquidity));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(user), 4, user, liquidity));
+
+		assert_ok!(AssetConversion::create_pool_with_curve(
+			RuntimeOrigin::signed(user),
+			Box::new(token_1.clone()),
+			Box::new(token_2.clone()),
+			PoolCurve::StableSwap { amplification: 100 },
+		));
+		assert_ok!(AssetConversion::create_pool(
+			RuntimeOrigin::signed(user),
+			Box::new(token_1.clone()),
+			Box::new(token_3.clone()),
+		));
+		for token in [token_2.clone(), token_3.clone()] {
+			assert_ok!(AssetConversion::add_liquidity(
+				RuntimeOrigin::signed(user),
+				Box::new(token_1.clone()),
+				Box::new(token),
+				liquidity,
+				liquidity,
+				1,
+				1,
+				user,
+			));
+		}
+
+		let amount = 10_000;
+		let stable = AssetConversion::quote_price_exact_tokens_for_tokens(
+			token_1.clone(),
+			token_2.clone(),
+			amount,
+			true,
+		)
+		.unwrap();
+		let constant_product = AssetConversion::quote_price_exact_tokens_for_tokens(
+			token_1.clone(),
+			token_3.clone(),
+			amount,
+			true,
+		)
+		.unwrap();
+		assert_eq!(constant_product, 9871);
+		// Only the 0.3% fee and rounding are lost on a balanced stable pool.
+		assert_eq!(stable, 9969);
+		assert_eq!(
+			AssetConversion::quote_price_exact_tokens_for_tokens(
+				token_1.clone(),
+				token_2.clone(),
+				amount,
+				false,
+			),
+			Some(9999)
+		);
+
+		let stable_in = AssetConversion::quote_price_tokens_for_exact_tokens(
+			token_1.clone(),
+			token_2.clone(),
+			amount,
+			true,
+		)
+		.unwrap();
+		let constant_product_in = AssetConversion::quote_price_tokens_for_exact_tokens(
+			token_1.clone(),
+			token_3.clone(),
+			amount,
+			true,
+		)
+		.unwrap();
+		assert!(stable_in < constant_product_in);
+		assert!(stable_in > amount);
+
+		assert_noop!(
+			AssetConversion::get_amount_in_on_curve(
+				PoolCurve::StableSwap { amplification: 100 },
+				&liquidity,
+				&liquidity,
+				&liquidity,
+			),
+			Error::<Test>::AmountOutTooHigh
+		);
+		assert_noop!(
+			AssetConversion::get_amount_out_on_curve(
+				PoolCurve::StableSwap { amplification: 100 },
+				&amount,
+				&0,
+				&liquidity,
+			),
+			Error::<Test>::ZeroLiquidity
+		);
+	});
+}
+
+#[test]
+fn can_swap_on_stable_swap_curve() {
+	new_test_ext().execute_with(|| {
+		let user = 1;
+		let token_1 = NativeOrWithId::WithId(2);
+		let token_2 = NativeOrWithId::WithId(3);
+		let pool_id = (token_1.clone(), token_2.clone());
+		let liquidity = 1_000_000;
+
+		create_tokens(user, vec![token_1.clone(), token_2.clone()]);
+		assert_ok!(Balances::force_set_balance(RuntimeOrigin::root(), user, 1000));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(user), 2, user, 2 * liquidity));
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(user), 3, user, liquidity));
+
+		assert_ok!(AssetConversion::create_pool_with_curve(
+			RuntimeOrigin::signed(user),
+			Box::new(token_1.clone()),
+			Box::new(token_2.clone()),
+			PoolCurve::StableSwap { amplification: 100 },
+		));
+		assert_ok!(AssetConversion::add_liquidity(
+			RuntimeOrigin::signed(user),
+			Box::new(token_1.clone()),
+			Box::new(token_2.clone()),
+			liquidity,
+			liquidity,
+			1,
+			1,
+			user,
+		));
+		let pool_account = <Test as Config>::PoolLocator::address(&pool_id).unwrap();
+
+		let amount_in = 10_000;
+		let expect_receive = AssetConversion::quote_price_exact_tokens_for_tokens(
+			token_1.clone(),
+			token_2.clone(),
+			amount_in,
+			true,
+		)
+		.unwrap();
+		assert_ok!(AssetConversion::swap_exact_tokens_for_tokens(
+			RuntimeOrigin::signed(user),
+			bvec![token_1.clone(), token_2.clone()],
+			amount_in,
+			1,
+			user,
+			false,
+		));
+		assert_eq!(balance(user, token_1.clone()), liquidity - amount_in);
+		assert_eq!(balance(user, token_2.clone()), expect_receive);
+		assert_eq!(balance(pool_account, token_1.clone()), liquidity + amount_in);
+		assert_eq!(balance(pool_account, token_2.clone()), liquidity - expect_receive);
+
+		let amount_out = 5_000;
+		let expect_pay = AssetConversion::quote_price_tokens_for_exact_tokens(
+			token_1.clone(),
+			token_2.clone(),
+			amount_out,
+			true,
+		)
+		.unwrap();
+		assert_ok!(AssetConversion::swap_tokens_for_exact_tokens(
+			RuntimeOrigin::signed(user),
+			bvec![token_1.clone(), token_2.clone()],
+			amount_out,
+			expect_pay,
+			user,
+			false,
+		));
+		assert_eq!(balance(user, token_1.clone()), liquidity - amount_in - expect_pay);
+		assert_eq!(balance(user, token_2.clone()), expect_receive + amount_out);
+	});
+}
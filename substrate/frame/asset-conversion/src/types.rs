@@ -16,7 +16,7 @@
 // limitations under the License.
 
 use super::*;
-use codec::{Decode, Encode, MaxEncodedLen};
+use codec::{Decode, DecodeWithMemTracking, Encode, MaxEncodedLen};
 use core::marker::PhantomData;
 use scale_info::TypeInfo;
 use sp_runtime::traits::TryConvert;
@@ -41,6 +41,47 @@ pub struct PoolInfo<PoolAssetId> {
 	pub lp_token: PoolAssetId,
 }
 
+/// The curve of a pool, i.e. the invariant kept by its swaps, which determines their rates.
+#[derive(
+	Decode,
+	DecodeWithMemTracking,
+	Encode,
+	Default,
+	Clone,
+	Copy,
+	PartialEq,
+	Eq,
+	MaxEncodedLen,
+	TypeInfo,
+	RuntimeDebug,
+)]
+pub enum PoolCurve {
+	/// The constant product curve `x * y = k` of Uniswap V2, suited to any pair of assets.
+	#[default]
+	ConstantProduct,
+	/// The StableSwap curve of Curve Finance, suited to pairs of assets pegged to each other.
+	///
+	/// The higher the `amplification`, the closer the rate stays to 1:1 for balanced reserves.
+	StableSwap {
+		/// The amplification coefficient, between 1 and [`PoolCurve::MAX_AMPLIFICATION`].
+		amplification: u32,
+	},
+}
+
+impl PoolCurve {
+	/// The maximum amplification coefficient of a StableSwap curve.
+	pub const MAX_AMPLIFICATION: u32 = 1_000_000;
+
+	/// Whether the parameters of the curve are valid.
+	pub fn is_valid(&self) -> bool {
+		match self {
+			PoolCurve::ConstantProduct => true,
+			PoolCurve::StableSwap { amplification } =>
+				(1..=Self::MAX_AMPLIFICATION).contains(amplification),
+		}
+	}
+}
+
 /// Provides means to resolve the `PoolId` and `AccountId` from a pair of assets.
 ///
 /// Resulting `PoolId` remains consistent whether the asset pair is presented as (asset1, asset2)
@@ -78,6 +78,7 @@ pub trait WeightInfo {
 	fn swap_exact_tokens_for_tokens(n: u32, ) -> Weight;
 	fn swap_tokens_for_exact_tokens(n: u32, ) -> Weight;
 	fn touch(n: u32, ) -> Weight;
+	fn stable_swap_curve() -> Weight;
 }
 
 /// Weights for `pallet_asset_conversion` using the Substrate node and recommended hardware.
@@ -204,6 +205,13 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(8_u64))
 			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(n.into())))
 	}
+	/// Storage: `AssetConversion::PoolCurves` (r:1 w:0)
+	/// Proof: `AssetConversion::PoolCurves` (`max_values`: None, `max_size`: Some(51), added: 2526, mode: `MaxEncodedLen`)
+	fn stable_swap_curve() -> Weight {
+		// Estimated, not measured: to be regenerated with the benchmark CLI.
+		Weight::from_parts(180_000_000, 3516)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+	}
 }
 
 // For backwards compatibility and tests.
@@ -329,4 +337,11 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(8_u64))
 			.saturating_add(RocksDbWeight::get().writes((2_u64).saturating_mul(n.into())))
 	}
+	/// Storage: `AssetConversion::PoolCurves` (r:1 w:0)
+	/// Proof: `AssetConversion::PoolCurves` (`max_values`: None, `max_size`: Some(51), added: 2526, mode: `MaxEncodedLen`)
+	fn stable_swap_curve() -> Weight {
+		// Estimated, not measured: to be regenerated with the benchmark CLI.
+		Weight::from_parts(180_000_000, 3516)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+	}
 }
@@ -0,0 +1,132 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//  http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Attestation pallet benchmarking.
+
+use super::*;
+use crate::Pallet as Attestation;
+use frame_benchmarking::{v2::*, whitelisted_caller};
+use frame_support::traits::{EnsureOrigin, Get};
+use frame_system::{pallet_prelude::BlockNumberFor, RawOrigin};
+use sp_runtime::traits::{Hash, One, Saturating};
+
+/// Make the `IssuerOrigin` issue an attestation about a subject and return the issuer, the
+/// subject and the schema.
+fn attested<T: Config>() -> Result<(T::AccountId, T::AccountId, T::Hash), BenchmarkError> {
+	let origin =
+		T::IssuerOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+	let issuer =
+		T::IssuerOrigin::ensure_origin(origin.clone()).map_err(|_| BenchmarkError::Weightless)?;
+	fund::<T>(&issuer);
+	let subject: T::AccountId = whitelisted_caller();
+	let schema = T::Hashing::hash(b"schema");
+	Attestation::<T>::attest(
+		origin,
+		T::Lookup::unlookup(subject.clone()),
+		schema,
+		T::Hashing::hash(b"payload"),
+		Some(frame_system::Pallet::<T>::block_number().saturating_add(One::one())),
+	)?;
+	Ok((issuer, subject, schema))
+}
+
+fn fund<T: Config>(who: &T::AccountId) {
+	let amount = T::Currency::minimum_balance()
+		.saturating_add(T::AttestationDeposit::get())
+		.saturating_add(T::AttestationDeposit::get());
+	T::Currency::set_balance(who, amount);
+}
+
+#[benchmarks]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn attest() -> Result<(), BenchmarkError> {
+		let origin =
+			T::IssuerOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let issuer = T::IssuerOrigin::ensure_origin(origin.clone())
+			.map_err(|_| BenchmarkError::Weightless)?;
+		fund::<T>(&issuer);
+		let subject: T::AccountId = whitelisted_caller();
+		let schema = T::Hashing::hash(b"schema");
+		let expiry: BlockNumberFor<T> = frame_system::Pallet::<T>::block_number() + One::one();
+
+		#[extrinsic_call]
+		_(
+			origin as T::RuntimeOrigin,
+			T::Lookup::unlookup(subject.clone()),
+			schema,
+			T::Hashing::hash(b"payload"),
+			Some(expiry),
+		);
+
+		assert!(Attestation::<T>::is_valid(&subject, &schema, &issuer));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn revoke() -> Result<(), BenchmarkError> {
+		let (issuer, subject, schema) = attested::<T>()?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(issuer.clone()), T::Lookup::unlookup(subject.clone()), schema);
+
+		assert!(!Attestation::<T>::is_valid(&subject, &schema, &issuer));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn force_revoke() -> Result<(), BenchmarkError> {
+		let (issuer, subject, schema) = attested::<T>()?;
+		let origin =
+			T::ForceOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+
+		#[extrinsic_call]
+		_(
+			origin as T::RuntimeOrigin,
+			T::Lookup::unlookup(subject.clone()),
+			schema,
+			T::Lookup::unlookup(issuer.clone()),
+		);
+
+		assert!(!Attestation::<T>::is_valid(&subject, &schema, &issuer));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn remove_attestation() -> Result<(), BenchmarkError> {
+		let (issuer, subject, schema) = attested::<T>()?;
+		// Removed by someone else once expired, which is the most expensive path.
+		let caller: T::AccountId = account("caller", 0, 0);
+		frame_system::Pallet::<T>::set_block_number(
+			frame_system::Pallet::<T>::block_number() + 2u32.into(),
+		);
+
+		#[extrinsic_call]
+		_(
+			RawOrigin::Signed(caller),
+			T::Lookup::unlookup(subject.clone()),
+			schema,
+			T::Lookup::unlookup(issuer.clone()),
+		);
+
+		assert!(Attestations::<T>::get((&subject, &schema, &issuer)).is_none());
+		Ok(())
+	}
+
+	impl_benchmark_test_suite!(Attestation, crate::mock::new_test_ext(), crate::mock::Test);
+}
@@ -0,0 +1,392 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//  http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! # Attestation Pallet
+//!
+//! A registry of attestations: typed claims which issuers make about accounts.
+//!
+//! ## Overview
+//!
+//! An [`Attestation`] is made by an issuer about a subject account and follows a schema. It
+//! records the hash of the claim payload, which is kept off-chain, and an optional expiry. Schemas
+//! are identified by the hash of their definition. There is at most one attestation per subject,
+//! schema and issuer, so issuing it again replaces it.
+//!
+//! Issuers are the accounts allowed by [`Config::IssuerOrigin`]. Using
+//! `pallet_identity::EnsureRegistrar` there lets the registrars of the identity pallet issue
+//! attestations without registering again.
+//!
+//! The issuer holds [`Config::AttestationDeposit`] for each attestation, which is released when
+//! the attestation is removed. An issuer can revoke its attestations, and [`Config::ForceOrigin`]
+//! can revoke any of them. Revoked attestations stay in storage until they are removed, so that
+//! verifiers can tell them apart from claims that were never made. Use [`Pallet::is_valid`] to
+//! check that an attestation is neither revoked nor expired. An issuer can lift its own
+//! revocation by attesting again, but not one by [`Config::ForceOrigin`], which is kept even once
+//! the attestation is removed.
+
+#![deny(missing_docs)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+pub mod weights;
+pub use pallet::*;
+pub use weights::WeightInfo;
+
+use codec::{Decode, DecodeWithMemTracking, Encode, MaxEncodedLen};
+use frame_support::{
+	ensure,
+	traits::{
+		fungible::{Inspect, Mutate, MutateHold},
+		tokens::Precision,
+	},
+};
+use scale_info::TypeInfo;
+use sp_runtime::{traits::StaticLookup, DispatchError, DispatchResult, RuntimeDebug};
+
+/// An attestation about an account.
+#[derive(
+	Clone,
+	Encode,
+	Decode,
+	DecodeWithMemTracking,
+	Eq,
+	PartialEq,
+	RuntimeDebug,
+	MaxEncodedLen,
+	TypeInfo,
+)]
+pub struct Attestation<Hash, BlockNumber, Balance> {
+	/// The hash of the claim payload.
+	pub payload_hash: Hash,
+	/// The block in which the attestation was issued.
+	pub issued: BlockNumber,
+	/// The block after which the attestation is no longer valid, if any.
+	pub expiry: Option<BlockNumber>,
+	/// The block in which the attestation was revoked, if it was.
+	pub revoked: Option<BlockNumber>,
+	/// The amount held from the issuer for this attestation.
+	pub deposit: Balance,
+}
+
+/// The balance type of the pallet.
+pub type BalanceOf<T> =
+	<<T as Config>::Currency as Inspect<<T as frame_system::Config>::AccountId>>::Balance;
+
+/// The [`Attestation`] type of the pallet.
+pub type AttestationOf<T> = Attestation<
+	<T as frame_system::Config>::Hash,
+	frame_system::pallet_prelude::BlockNumberFor<T>,
+	BalanceOf<T>,
+>;
+
+type AccountIdLookupOf<T> = <<T as frame_system::Config>::Lookup as StaticLookup>::Source;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	/// A reason for the pallet placing a hold on funds.
+	#[pallet::composite_enum]
+	pub enum HoldReason {
+		/// Funds are held for storing an attestation.
+		#[codec(index = 0)]
+		Attestation,
+	}
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// Overarching event type.
+		#[allow(deprecated)]
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// Overarching hold reason.
+		type RuntimeHoldReason: From<HoldReason>;
+
+		/// The currency holding the deposits of the attestations.
+		type Currency: Mutate<Self::AccountId>
+			+ MutateHold<Self::AccountId, Reason = Self::RuntimeHoldReason>;
+
+		/// The origin which may issue attestations. The success value is the issuer.
+		///
+		/// `pallet_identity::EnsureRegistrar` allows the identity registrars.
+		type IssuerOrigin: EnsureOrigin<Self::RuntimeOrigin, Success = Self::AccountId>;
+
+		/// The origin which may revoke any attestation.
+		type ForceOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// The amount held from the issuer for each attestation.
+		#[pallet::constant]
+		type AttestationDeposit: Get<BalanceOf<Self>>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
+	}
+
+	/// The attestations, keyed by the subject, the schema and the issuer.
+	#[pallet::storage]
+	pub type Attestations<T: Config> = StorageNMap<
+		_,
+		(
+			NMapKey<Blake2_128Concat, T::AccountId>,
+			NMapKey<Blake2_128Concat, T::Hash>,
+			NMapKey<Blake2_128Concat, T::AccountId>,
+		),
+		AttestationOf<T>,
+	>;
+
+	/// The blocks in which attestations were revoked by the `ForceOrigin`, keyed like
+	/// [`Attestations`]. The issuer can't attest the same subject and schema again.
+	#[pallet::storage]
+	pub type ForceRevocations<T: Config> = StorageNMap<
+		_,
+		(
+			NMapKey<Blake2_128Concat, T::AccountId>,
+			NMapKey<Blake2_128Concat, T::Hash>,
+			NMapKey<Blake2_128Concat, T::AccountId>,
+		),
+		BlockNumberFor<T>,
+	>;
+
+	// Pallet's events.
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// An attestation was issued.
+		Attested {
+			/// The account which issued the attestation.
+			issuer: T::AccountId,
+			/// The account the attestation is about.
+			subject: T::AccountId,
+			/// The hash of the schema of the attestation.
+			schema: T::Hash,
+			/// The hash of the claim payload.
+			payload_hash: T::Hash,
+			/// The block after which the attestation is no longer valid, if any.
+			expiry: Option<BlockNumberFor<T>>,
+		},
+		/// An attestation was revoked.
+		Revoked {
+			/// The account which issued the attestation.
+			issuer: T::AccountId,
+			/// The account the attestation is about.
+			subject: T::AccountId,
+			/// The hash of the schema of the attestation.
+			schema: T::Hash,
+		},
+		/// An attestation was removed and its deposit released.
+		AttestationRemoved {
+			/// The account which issued the attestation.
+			issuer: T::AccountId,
+			/// The account the attestation is about.
+			subject: T::AccountId,
+			/// The hash of the schema of the attestation.
+			schema: T::Hash,
+		},
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The expiry must be in the future.
+		InvalidExpiry,
+		/// The attestation doesn't exist.
+		UnknownAttestation,
+		/// The attestation is already revoked.
+		AlreadyRevoked,
+		/// Only the issuer may remove an attestation before it expires.
+		NotExpired,
+		/// The attestation was revoked by the `ForceOrigin` and can't be issued again.
+		ForceRevoked,
+	}
+
+	/// Pallet's callable functions.
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Attest the claim with the hash `payload_hash`, following the schema with the hash
+		/// `schema`, about `subject`, until the block `expiry`.
+		///
+		/// An attestation by the same issuer about the same subject and schema is replaced, and
+		/// is no longer revoked. Otherwise `AttestationDeposit` is held from the issuer. An
+		/// attestation revoked by the `ForceOrigin` can't be issued again.
+		///
+		/// Must be called by the `IssuerOrigin`.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::attest())]
+		pub fn attest(
+			origin: OriginFor<T>,
+			subject: AccountIdLookupOf<T>,
+			schema: T::Hash,
+			payload_hash: T::Hash,
+			expiry: Option<BlockNumberFor<T>>,
+		) -> DispatchResult {
+			let issuer = T::IssuerOrigin::ensure_origin(origin)?;
+			let subject = T::Lookup::lookup(subject)?;
+			let now = frame_system::Pallet::<T>::block_number();
+			ensure!(expiry.map_or(true, |expiry| expiry > now), Error::<T>::InvalidExpiry);
+
+			let key = (&subject, &schema, &issuer);
+			ensure!(!ForceRevocations::<T>::contains_key(key), Error::<T>::ForceRevoked);
+			let deposit = match Attestations::<T>::get(key) {
+				Some(attestation) => attestation.deposit,
+				None => {
+					let deposit = T::AttestationDeposit::get();
+					T::Currency::hold(&HoldReason::Attestation.into(), &issuer, deposit)?;
+					deposit
+				},
+			};
+			Attestations::<T>::insert(
+				key,
+				Attestation { payload_hash, issued: now, expiry, revoked: None, deposit },
+			);
+
+			Self::deposit_event(Event::<T>::Attested {
+				issuer,
+				subject,
+				schema,
+				payload_hash,
+				expiry,
+			});
+			Ok(())
+		}
+
+		/// Revoke the attestation about `subject` following the schema `schema`.
+		///
+		/// Must be signed by the issuer of the attestation.
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::WeightInfo::revoke())]
+		pub fn revoke(
+			origin: OriginFor<T>,
+			subject: AccountIdLookupOf<T>,
+			schema: T::Hash,
+		) -> DispatchResult {
+			let issuer = ensure_signed(origin)?;
+			let subject = T::Lookup::lookup(subject)?;
+			Self::do_revoke(issuer, subject, schema)
+		}
+
+		/// Revoke the attestation by `issuer` about `subject` following the schema `schema`, for
+		/// good: the issuer can't attest it again.
+		///
+		/// Must be called by the `ForceOrigin`.
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::WeightInfo::force_revoke())]
+		pub fn force_revoke(
+			origin: OriginFor<T>,
+			subject: AccountIdLookupOf<T>,
+			schema: T::Hash,
+			issuer: AccountIdLookupOf<T>,
+		) -> DispatchResult {
+			T::ForceOrigin::ensure_origin(origin)?;
+			let subject = T::Lookup::lookup(subject)?;
+			let issuer = T::Lookup::lookup(issuer)?;
+			let key = (&subject, &schema, &issuer);
+			ensure!(!ForceRevocations::<T>::contains_key(key), Error::<T>::AlreadyRevoked);
+			let now = frame_system::Pallet::<T>::block_number();
+			Attestations::<T>::try_mutate(key, |maybe_attestation| {
+				let attestation =
+					maybe_attestation.as_mut().ok_or(Error::<T>::UnknownAttestation)?;
+				// Also when already revoked by the issuer, which attesting again would lift.
+				attestation.revoked.get_or_insert(now);
+				Ok::<_, DispatchError>(())
+			})?;
+			ForceRevocations::<T>::insert(key, now);
+
+			Self::deposit_event(Event::<T>::Revoked { issuer, subject, schema });
+			Ok(())
+		}
+
+		/// Remove the attestation by `issuer` about `subject` following the schema `schema`, and
+		/// release its deposit to the issuer.
+		///
+		/// Must be signed by the issuer, or by any account once the attestation has expired.
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::WeightInfo::remove_attestation())]
+		pub fn remove_attestation(
+			origin: OriginFor<T>,
+			subject: AccountIdLookupOf<T>,
+			schema: T::Hash,
+			issuer: AccountIdLookupOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let subject = T::Lookup::lookup(subject)?;
+			let issuer = T::Lookup::lookup(issuer)?;
+			let key = (&subject, &schema, &issuer);
+			let attestation = Attestations::<T>::get(key).ok_or(Error::<T>::UnknownAttestation)?;
+			if who != issuer {
+				let now = frame_system::Pallet::<T>::block_number();
+				ensure!(
+					attestation.expiry.map_or(false, |expiry| expiry < now),
+					Error::<T>::NotExpired
+				);
+			}
+
+			T::Currency::release(
+				&HoldReason::Attestation.into(),
+				&issuer,
+				attestation.deposit,
+				Precision::BestEffort,
+			)?;
+			Attestations::<T>::remove(key);
+
+			Self::deposit_event(Event::<T>::AttestationRemoved { issuer, subject, schema });
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Revoke the attestation by `issuer` about `subject` following the schema `schema`.
+	fn do_revoke(issuer: T::AccountId, subject: T::AccountId, schema: T::Hash) -> DispatchResult {
+		Attestations::<T>::try_mutate((&subject, &schema, &issuer), |maybe_attestation| {
+			let attestation = maybe_attestation.as_mut().ok_or(Error::<T>::UnknownAttestation)?;
+			ensure!(attestation.revoked.is_none(), Error::<T>::AlreadyRevoked);
+			attestation.revoked = Some(frame_system::Pallet::<T>::block_number());
+			Ok::<_, DispatchError>(())
+		})?;
+
+		Self::deposit_event(Event::<T>::Revoked { issuer, subject, schema });
+		Ok(())
+	}
+
+	/// The attestation by `issuer` about `subject` following the schema `schema`, if it is
+	/// neither revoked nor expired.
+	pub fn valid_attestation(
+		subject: &T::AccountId,
+		schema: &T::Hash,
+		issuer: &T::AccountId,
+	) -> Option<AttestationOf<T>> {
+		let now = frame_system::Pallet::<T>::block_number();
+		Attestations::<T>::get((subject, schema, issuer)).filter(|attestation| {
+			attestation.revoked.is_none() && attestation.expiry.map_or(true, |expiry| expiry >= now)
+		})
+	}
+
+	/// Whether `issuer` attests a claim about `subject` following the schema `schema`, which is
+	/// neither revoked nor expired.
+	pub fn is_valid(subject: &T::AccountId, schema: &T::Hash, issuer: &T::AccountId) -> bool {
+		Self::valid_attestation(subject, schema, issuer).is_some()
+	}
+}
@@ -0,0 +1,127 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//  http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Test environment for Attestation pallet.
+
+use crate as pallet_attestation;
+use codec::{Decode, DecodeWithMemTracking, Encode};
+use frame_support::{
+	construct_runtime, derive_impl, parameter_types,
+	traits::{ConstU32, ConstU64, EitherOf, SortedMembers},
+};
+use frame_system::{EnsureRoot, EnsureSignedBy};
+use pallet_identity::legacy::IdentityInfo;
+use scale_info::TypeInfo;
+use sp_runtime::{
+	traits::{IdentifyAccount, Lazy, Verify},
+	BuildStorage,
+};
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+construct_runtime!(
+	pub enum Test
+	{
+		System: frame_system,
+		Balances: pallet_balances,
+		Identity: pallet_identity,
+		Attestation: pallet_attestation,
+	}
+);
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Test {
+	type Block = Block;
+	type AccountData = pallet_balances::AccountData<u64>;
+}
+
+#[derive_impl(pallet_balances::config_preludes::TestDefaultConfig)]
+impl pallet_balances::Config for Test {
+	type AccountStore = System;
+}
+
+parameter_types! {
+	pub static IssuerAccounts: Vec<u64> = vec![1, 2];
+}
+
+pub struct Issuers;
+impl SortedMembers<u64> for Issuers {
+	fn sorted_members() -> Vec<u64> {
+		IssuerAccounts::get()
+	}
+}
+
+#[derive(Clone, Debug, Encode, Decode, DecodeWithMemTracking, PartialEq, Eq, TypeInfo)]
+pub struct AccountU64(u64);
+impl IdentifyAccount for AccountU64 {
+	type AccountId = u64;
+	fn into_account(self) -> u64 {
+		self.0
+	}
+}
+impl Verify for AccountU64 {
+	type Signer = AccountU64;
+	fn verify<L: Lazy<[u8]>>(&self, _msg: L, _signer: &u64) -> bool {
+		false
+	}
+}
+
+impl pallet_identity::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type Currency = Balances;
+	type BasicDeposit = ConstU64<10>;
+	type ByteDeposit = ConstU64<1>;
+	type UsernameDeposit = ConstU64<10>;
+	type SubAccountDeposit = ConstU64<10>;
+	type MaxSubAccounts = ConstU32<2>;
+	type IdentityInformation = IdentityInfo<ConstU32<2>>;
+	type MaxRegistrars = ConstU32<20>;
+	type Slashed = ();
+	type RegistrarOrigin = EnsureRoot<u64>;
+	type ForceOrigin = EnsureRoot<u64>;
+	type OffchainSignature = AccountU64;
+	type SigningPublicKey = AccountU64;
+	type UsernameAuthorityOrigin = EnsureRoot<u64>;
+	type PendingUsernameExpiration = ConstU64<100>;
+	type UsernameGracePeriod = ConstU64<2>;
+	type MaxSuffixLength = ConstU32<7>;
+	type MaxUsernameLength = ConstU32<32>;
+	type WeightInfo = ();
+}
+
+impl pallet_attestation::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type Currency = Balances;
+	type IssuerOrigin =
+		EitherOf<EnsureSignedBy<Issuers, u64>, pallet_identity::EnsureRegistrar<Test>>;
+	type ForceOrigin = EnsureRoot<u64>;
+	type AttestationDeposit = ConstU64<10>;
+	type WeightInfo = ();
+}
+
+pub(crate) fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+	pallet_balances::GenesisConfig::<Test> {
+		balances: vec![(1, 100), (2, 100), (3, 100), (4, 100)],
+		..Default::default()
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
@@ -0,0 +1,211 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//  http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! Attestation pallet tests.
+
+use crate::{mock::*, *};
+use frame_support::{assert_noop, assert_ok, traits::fungible::InspectHold};
+use sp_runtime::{testing::H256, traits::BadOrigin};
+
+fn held(who: u64) -> u64 {
+	Balances::balance_on_hold(&HoldReason::Attestation.into(), &who)
+}
+
+fn schema() -> H256 {
+	H256::repeat_byte(1)
+}
+
+fn payload() -> H256 {
+	H256::repeat_byte(2)
+}
+
+#[test]
+fn attest_works() {
+	new_test_ext().execute_with(|| {
+		// Only issuers can attest
+		assert_noop!(
+			Attestation::attest(RuntimeOrigin::signed(3), 4, schema(), payload(), None),
+			BadOrigin
+		);
+		assert_noop!(
+			Attestation::attest(RuntimeOrigin::signed(1), 4, schema(), payload(), Some(1)),
+			Error::<Test>::InvalidExpiry
+		);
+		assert_ok!(Attestation::attest(RuntimeOrigin::signed(1), 4, schema(), payload(), Some(10)));
+		System::assert_last_event(
+			Event::<Test>::Attested {
+				issuer: 1,
+				subject: 4,
+				schema: schema(),
+				payload_hash: payload(),
+				expiry: Some(10),
+			}
+			.into(),
+		);
+		assert_eq!(held(1), 10);
+		assert_eq!(
+			Attestations::<Test>::get((4, schema(), 1)),
+			Some(Attestation {
+				payload_hash: payload(),
+				issued: 1,
+				expiry: Some(10),
+				revoked: None,
+				deposit: 10
+			})
+		);
+		assert!(Attestation::is_valid(&4, &schema(), &1));
+		assert!(!Attestation::is_valid(&4, &schema(), &2));
+
+		// Attesting again replaces the attestation without holding more
+		let other_payload = H256::repeat_byte(3);
+		assert_ok!(Attestation::attest(RuntimeOrigin::signed(1), 4, schema(), other_payload, None));
+		assert_eq!(held(1), 10);
+		assert_eq!(
+			Attestation::valid_attestation(&4, &schema(), &1).unwrap().payload_hash,
+			other_payload
+		);
+
+		// Another issuer holds its own attestation
+		assert_ok!(Attestation::attest(RuntimeOrigin::signed(2), 4, schema(), payload(), None));
+		assert_eq!(held(2), 10);
+	});
+}
+
+#[test]
+fn attestation_expires() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Attestation::attest(RuntimeOrigin::signed(1), 4, schema(), payload(), Some(5)));
+		System::set_block_number(5);
+		assert!(Attestation::is_valid(&4, &schema(), &1));
+		// Only the issuer can remove it before it expires
+		assert_noop!(
+			Attestation::remove_attestation(RuntimeOrigin::signed(3), 4, schema(), 1),
+			Error::<Test>::NotExpired
+		);
+		System::set_block_number(6);
+		assert!(!Attestation::is_valid(&4, &schema(), &1));
+		// Then anyone can clean it up
+		assert_ok!(Attestation::remove_attestation(RuntimeOrigin::signed(3), 4, schema(), 1));
+		System::assert_last_event(
+			Event::<Test>::AttestationRemoved { issuer: 1, subject: 4, schema: schema() }.into(),
+		);
+		assert_eq!(held(1), 0);
+		assert_eq!(Attestations::<Test>::get((4, schema(), 1)), None);
+		assert_noop!(
+			Attestation::remove_attestation(RuntimeOrigin::signed(1), 4, schema(), 1),
+			Error::<Test>::UnknownAttestation
+		);
+	});
+}
+
+#[test]
+fn revoke_works() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Attestation::revoke(RuntimeOrigin::signed(1), 4, schema()),
+			Error::<Test>::UnknownAttestation
+		);
+		assert_ok!(Attestation::attest(RuntimeOrigin::signed(1), 4, schema(), payload(), None));
+		assert_ok!(Attestation::attest(RuntimeOrigin::signed(2), 4, schema(), payload(), None));
+		// The issuer can revoke its attestation, even once it is no longer an issuer
+		IssuerAccounts::set(vec![2]);
+		assert_ok!(Attestation::revoke(RuntimeOrigin::signed(1), 4, schema()));
+		System::assert_last_event(
+			Event::<Test>::Revoked { issuer: 1, subject: 4, schema: schema() }.into(),
+		);
+		assert_eq!(Attestations::<Test>::get((4, schema(), 1)).unwrap().revoked, Some(1));
+		assert!(!Attestation::is_valid(&4, &schema(), &1));
+		assert_noop!(
+			Attestation::revoke(RuntimeOrigin::signed(1), 4, schema()),
+			Error::<Test>::AlreadyRevoked
+		);
+		// A revoked attestation is kept until it is removed, and never expires by itself
+		assert_noop!(
+			Attestation::remove_attestation(RuntimeOrigin::signed(3), 4, schema(), 1),
+			Error::<Test>::NotExpired
+		);
+		assert_ok!(Attestation::remove_attestation(RuntimeOrigin::signed(1), 4, schema(), 1));
+		assert_eq!(held(1), 0);
+
+		// Any attestation can be force revoked
+		assert_noop!(
+			Attestation::force_revoke(RuntimeOrigin::signed(1), 4, schema(), 2),
+			BadOrigin
+		);
+		assert_ok!(Attestation::force_revoke(RuntimeOrigin::root(), 4, schema(), 2));
+		System::assert_last_event(
+			Event::<Test>::Revoked { issuer: 2, subject: 4, schema: schema() }.into(),
+		);
+		assert!(!Attestation::is_valid(&4, &schema(), &2));
+		assert_noop!(
+			Attestation::force_revoke(RuntimeOrigin::root(), 4, schema(), 2),
+			Error::<Test>::AlreadyRevoked
+		);
+	});
+}
+
+#[test]
+fn only_issuer_revocations_can_be_lifted() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Attestation::attest(RuntimeOrigin::signed(1), 4, schema(), payload(), None));
+		assert_ok!(Attestation::attest(RuntimeOrigin::signed(2), 4, schema(), payload(), None));
+
+		// Attesting again lifts a revocation by the issuer
+		assert_ok!(Attestation::revoke(RuntimeOrigin::signed(1), 4, schema()));
+		assert_ok!(Attestation::attest(RuntimeOrigin::signed(1), 4, schema(), payload(), None));
+		assert!(Attestation::is_valid(&4, &schema(), &1));
+		assert_eq!(held(1), 10);
+
+		// But not one by the force origin, even once the attestation is removed, including when
+		// the issuer had revoked it already
+		assert_ok!(Attestation::revoke(RuntimeOrigin::signed(2), 4, schema()));
+		assert_ok!(Attestation::force_revoke(RuntimeOrigin::root(), 4, schema(), 2));
+		assert_noop!(
+			Attestation::attest(RuntimeOrigin::signed(2), 4, schema(), payload(), None),
+			Error::<Test>::ForceRevoked
+		);
+		assert_ok!(Attestation::remove_attestation(RuntimeOrigin::signed(2), 4, schema(), 2));
+		assert_eq!(held(2), 0);
+		assert_noop!(
+			Attestation::attest(RuntimeOrigin::signed(2), 4, schema(), payload(), None),
+			Error::<Test>::ForceRevoked
+		);
+		assert_eq!(ForceRevocations::<Test>::get((4, schema(), 2)), Some(1));
+
+		// The issuer can still attest the subject following other schemas
+		assert_ok!(Attestation::attest(
+			RuntimeOrigin::signed(2),
+			4,
+			H256::repeat_byte(4),
+			payload(),
+			None
+		));
+	});
+}
+
+#[test]
+fn identity_registrars_can_attest() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Attestation::attest(RuntimeOrigin::signed(3), 4, schema(), payload(), None),
+			BadOrigin
+		);
+		assert_ok!(Identity::add_registrar(RuntimeOrigin::root(), 3));
+		assert_ok!(Attestation::attest(RuntimeOrigin::signed(3), 4, schema(), payload(), None));
+		assert!(Attestation::is_valid(&4, &schema(), &3));
+		assert_eq!(held(3), 10);
+	});
+}
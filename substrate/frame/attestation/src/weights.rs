@@ -0,0 +1,134 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Weights for `pallet_attestation`.
+//!
+//! The weights are estimated, not measured, and are to be regenerated with the benchmark CLI.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(missing_docs)]
+#![allow(dead_code)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+/// Weight functions needed for `pallet_attestation`.
+pub trait WeightInfo {
+	fn attest() -> Weight;
+	fn revoke() -> Weight;
+	fn force_revoke() -> Weight;
+	fn remove_attestation() -> Weight;
+}
+
+/// Weights for `pallet_attestation` using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// Storage: `Attestation::ForceRevocations` (r:1 w:0)
+	/// Proof: `Attestation::ForceRevocations` (`max_values`: None, `max_size`: Some(148), added: 2623, mode: `MaxEncodedLen`)
+	/// Storage: `Attestation::Attestations` (r:1 w:1)
+	/// Proof: `Attestation::Attestations` (`max_values`: None, `max_size`: Some(206), added: 2681, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(427), added: 2902, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	fn attest() -> Weight {
+		// Estimated, not measured: to be regenerated with the benchmark CLI.
+		Weight::from_parts(52_000_000, 3892)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: `Attestation::Attestations` (r:1 w:1)
+	/// Proof: `Attestation::Attestations` (`max_values`: None, `max_size`: Some(206), added: 2681, mode: `MaxEncodedLen`)
+	fn revoke() -> Weight {
+		// Estimated, not measured: to be regenerated with the benchmark CLI.
+		Weight::from_parts(20_000_000, 3671)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Attestation::ForceRevocations` (r:1 w:1)
+	/// Proof: `Attestation::ForceRevocations` (`max_values`: None, `max_size`: Some(148), added: 2623, mode: `MaxEncodedLen`)
+	/// Storage: `Attestation::Attestations` (r:1 w:1)
+	/// Proof: `Attestation::Attestations` (`max_values`: None, `max_size`: Some(206), added: 2681, mode: `MaxEncodedLen`)
+	fn force_revoke() -> Weight {
+		// Estimated, not measured: to be regenerated with the benchmark CLI.
+		Weight::from_parts(26_000_000, 3671)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `Attestation::Attestations` (r:1 w:1)
+	/// Proof: `Attestation::Attestations` (`max_values`: None, `max_size`: Some(206), added: 2681, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(427), added: 2902, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	fn remove_attestation() -> Weight {
+		// Estimated, not measured: to be regenerated with the benchmark CLI.
+		Weight::from_parts(45_000_000, 3892)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+}
+
+// For backwards compatibility and tests.
+impl WeightInfo for () {
+	/// Storage: `Attestation::ForceRevocations` (r:1 w:0)
+	/// Proof: `Attestation::ForceRevocations` (`max_values`: None, `max_size`: Some(148), added: 2623, mode: `MaxEncodedLen`)
+	/// Storage: `Attestation::Attestations` (r:1 w:1)
+	/// Proof: `Attestation::Attestations` (`max_values`: None, `max_size`: Some(206), added: 2681, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(427), added: 2902, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	fn attest() -> Weight {
+		// Estimated, not measured: to be regenerated with the benchmark CLI.
+		Weight::from_parts(52_000_000, 3892)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: `Attestation::Attestations` (r:1 w:1)
+	/// Proof: `Attestation::Attestations` (`max_values`: None, `max_size`: Some(206), added: 2681, mode: `MaxEncodedLen`)
+	fn revoke() -> Weight {
+		// Estimated, not measured: to be regenerated with the benchmark CLI.
+		Weight::from_parts(20_000_000, 3671)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Attestation::ForceRevocations` (r:1 w:1)
+	/// Proof: `Attestation::ForceRevocations` (`max_values`: None, `max_size`: Some(148), added: 2623, mode: `MaxEncodedLen`)
+	/// Storage: `Attestation::Attestations` (r:1 w:1)
+	/// Proof: `Attestation::Attestations` (`max_values`: None, `max_size`: Some(206), added: 2681, mode: `MaxEncodedLen`)
+	fn force_revoke() -> Weight {
+		// Estimated, not measured: to be regenerated with the benchmark CLI.
+		Weight::from_parts(26_000_000, 3671)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `Attestation::Attestations` (r:1 w:1)
+	/// Proof: `Attestation::Attestations` (`max_values`: None, `max_size`: Some(206), added: 2681, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Holds` (r:1 w:1)
+	/// Proof: `Balances::Holds` (`max_values`: None, `max_size`: Some(427), added: 2902, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	fn remove_attestation() -> Weight {
+		// Estimated, not measured: to be regenerated with the benchmark CLI.
+		Weight::from_parts(45_000_000, 3892)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+}
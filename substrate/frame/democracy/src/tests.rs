@@ -95,6 +95,13 @@ impl pallet_preimage::Config for Test {
 	type Consideration = ();
 }
 
+pub struct SchedulerTime;
+impl frame_support::traits::Time for SchedulerTime {
+	type Moment = u64;
+	fn now() -> u64 {
+		0
+	}
+}
 impl pallet_scheduler::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type RuntimeOrigin = RuntimeOrigin;
@@ -107,6 +114,8 @@ impl pallet_scheduler::Config for Test {
 	type OriginPrivilegeCmp = EqualPrivilegeOnly;
 	type Preimages = ();
 	type BlockNumberProvider = frame_system::Pallet<Test>;
+	type Time = SchedulerTime;
+	type MaxRecurring = ConstU32<50>;
 }
 
 #[derive_impl(pallet_balances::config_preludes::TestDefaultConfig)]
@@ -119,11 +119,13 @@ extern crate alloc;
 use crate::types::{AuthorityProperties, Provider, Suffix, Username, UsernameInformation};
 use alloc::{boxed::Box, vec::Vec};
 use codec::Encode;
+use core::marker::PhantomData;
 use frame_support::{
 	ensure,
 	pallet_prelude::{DispatchError, DispatchResult},
 	traits::{
-		BalanceStatus, Currency, Defensive, Get, OnUnbalanced, ReservableCurrency, StorageVersion,
+		BalanceStatus, Currency, Defensive, EnsureOrigin, Get, OnUnbalanced, OriginTrait,
+		ReservableCurrency, StorageVersion,
 	},
 	BoundedVec,
 };
@@ -1409,6 +1411,14 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
+	/// Get the index of the registrar whose account is `who`, if any.
+	pub fn registrar_index_of(who: &T::AccountId) -> Option<RegistrarIndex> {
+		Registrars::<T>::get()
+			.iter()
+			.position(|r| r.as_ref().map_or(false, |r| &r.account == who))
+			.map(|i| i as RegistrarIndex)
+	}
+
 	/// Check if the account has corresponding identity information by the identity field.
 	pub fn has_identity(
 		who: &T::AccountId,
@@ -1657,3 +1667,36 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 }
+
+/// Ensures that the origin is signed by the account of a registrar. The account is the
+/// `Success` value.
+///
+/// This allows other pallets to grant rights to the registrars of this pallet without them having
+/// to register again.
+pub struct EnsureRegistrar<T>(PhantomData<T>);
+impl<T: Config> EnsureOrigin<T::RuntimeOrigin> for EnsureRegistrar<T> {
+	type Success = T::AccountId;
+
+	fn try_origin(o: T::RuntimeOrigin) -> Result<Self::Success, T::RuntimeOrigin> {
+		match o.as_signer() {
+			Some(who) if Pallet::<T>::registrar_index_of(who).is_some() => Ok(who.clone()),
+			_ => Err(o),
+		}
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn try_successful_origin() -> Result<T::RuntimeOrigin, ()> {
+		let who = frame_benchmarking::account::<T::AccountId>("successful_origin", 0, 0);
+		if Pallet::<T>::registrar_index_of(&who).is_none() {
+			Registrars::<T>::try_mutate(|registrars| {
+				registrars.try_push(Some(RegistrarInfo {
+					account: who.clone(),
+					fee: Zero::zero(),
+					fields: Default::default(),
+				}))
+			})
+			.map_err(|_| ())?;
+		}
+		Ok(frame_system::RawOrigin::Signed(who).into())
+	}
+}
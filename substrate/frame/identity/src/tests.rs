@@ -364,6 +364,22 @@ fn adding_registrar_should_work() {
 	});
 }
 
+#[test]
+fn ensure_registrar_works() {
+	new_test_ext().execute_with(|| {
+		let [_, _, three, four, _, _, _, _] = accounts();
+		assert_ok!(Identity::add_registrar(RuntimeOrigin::root(), three.clone()));
+		assert_eq!(Identity::registrar_index_of(&three), Some(0));
+		assert_eq!(Identity::registrar_index_of(&four), None);
+		assert_eq!(
+			EnsureRegistrar::<Test>::try_origin(RuntimeOrigin::signed(three.clone())).ok(),
+			Some(three)
+		);
+		assert!(EnsureRegistrar::<Test>::try_origin(RuntimeOrigin::signed(four)).is_err());
+		assert!(EnsureRegistrar::<Test>::try_origin(RuntimeOrigin::root()).is_err());
+	});
+}
+
 #[test]
 fn amount_of_registrars_is_limited() {
 	new_test_ext().execute_with(|| {
@@ -45,6 +45,20 @@ fn setup_multi<T: Config>(
 	Ok((signatories, Box::new(call)))
 }
 
+/// Create a stored multisig of the first `s` signatories with the given `threshold`.
+fn setup_stored<T: Config>(
+	s: u32,
+	z: u32,
+	threshold: u16,
+) -> Result<(Vec<T::AccountId>, T::AccountId, Box<<T as Config>::RuntimeCall>), &'static str> {
+	let (signatories, call) = setup_multi::<T>(s, z)?;
+	let creator = signatories[0].clone();
+	let multisig = Multisig::<T>::stored_account_id(&creator, Multisig::<T>::timepoint());
+	Multisig::<T>::create_stored(RawOrigin::Signed(creator).into(), signatories.clone(), threshold)
+		.map_err(|_| "stored multisig could not be created")?;
+	Ok((signatories, multisig, call))
+}
+
 #[benchmarks]
 mod benchmarks {
 	use super::*;
@@ -359,5 +373,272 @@ mod benchmarks {
 		Ok(())
 	}
 
+	/// `s`: Signatories, need at least 2 people
+	#[benchmark]
+	fn create_stored(s: Linear<2, { T::MaxSignatories::get() }>) -> Result<(), BenchmarkError> {
+		let (signatories, _) = setup_multi::<T>(s, 0)?;
+		let caller = signatories[0].clone();
+		let multisig = Multisig::<T>::stored_account_id(&caller, Multisig::<T>::timepoint());
+		// Whitelist caller account from further DB operations.
+		let caller_key = frame_system::Account::<T>::hashed_key_for(&caller);
+		add_to_whitelist(caller_key.into());
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), signatories, s as u16);
+
+		assert!(StoredMultisigs::<T>::contains_key(multisig));
+
+		Ok(())
+	}
+
+	/// `z`: Transaction Length
+	/// `s`: Signatories, need at least 2 people
+	#[benchmark]
+	fn as_stored_multi_create(
+		s: Linear<2, { T::MaxSignatories::get() }>,
+		z: Linear<0, 10_000>,
+	) -> Result<(), BenchmarkError> {
+		let (signatories, multisig, call) = setup_stored::<T>(s, z, s as u16)?;
+		let call_hash = call.using_encoded(blake2_256);
+		let caller = signatories[s as usize - 1].clone();
+		// Whitelist caller account from further DB operations.
+		let caller_key = frame_system::Account::<T>::hashed_key_for(&caller);
+		add_to_whitelist(caller_key.into());
+
+		#[extrinsic_call]
+		as_stored_multi(RawOrigin::Signed(caller), multisig.clone(), None, call, Weight::zero());
+
+		assert!(Multisigs::<T>::contains_key(multisig, call_hash));
+
+		Ok(())
+	}
+
+	/// `z`: Transaction Length
+	/// `s`: Signatories, need at least 3 people (so we don't complete the multisig)
+	#[benchmark]
+	fn as_stored_multi_approve(
+		s: Linear<3, { T::MaxSignatories::get() }>,
+		z: Linear<0, 10_000>,
+	) -> Result<(), BenchmarkError> {
+		let (signatories, multisig, call) = setup_stored::<T>(s, z, s as u16)?;
+		let call_hash = call.using_encoded(blake2_256);
+		let caller = signatories[s as usize - 1].clone();
+		// before the call, get the timepoint
+		let timepoint = Multisig::<T>::timepoint();
+		// Create the multi
+		Multisig::<T>::as_stored_multi(
+			RawOrigin::Signed(caller).into(),
+			multisig.clone(),
+			None,
+			call.clone(),
+			Weight::zero(),
+		)?;
+		let caller2 = signatories[0].clone();
+		// Whitelist caller account from further DB operations.
+		let caller_key = frame_system::Account::<T>::hashed_key_for(&caller2);
+		add_to_whitelist(caller_key.into());
+
+		#[extrinsic_call]
+		as_stored_multi(
+			RawOrigin::Signed(caller2),
+			multisig.clone(),
+			Some(timepoint),
+			call,
+			Weight::zero(),
+		);
+
+		let operation = Multisigs::<T>::get(multisig, call_hash).ok_or("multisig not created")?;
+		assert_eq!(operation.approvals.len(), 2);
+
+		Ok(())
+	}
+
+	/// `z`: Transaction Length
+	/// `s`: Signatories, need at least 2 people
+	#[benchmark]
+	fn as_stored_multi_complete(
+		s: Linear<2, { T::MaxSignatories::get() }>,
+		z: Linear<0, 10_000>,
+	) -> Result<(), BenchmarkError> {
+		let (signatories, multisig, call) = setup_stored::<T>(s, z, s as u16)?;
+		let call_hash = call.using_encoded(blake2_256);
+		// before the call, get the timepoint
+		let timepoint = Multisig::<T>::timepoint();
+		// Everyone except the first person approves
+		for (i, signatory) in signatories.iter().enumerate().skip(1) {
+			Multisig::<T>::as_stored_multi(
+				RawOrigin::Signed(signatory.clone()).into(),
+				multisig.clone(),
+				if i == 1 { None } else { Some(timepoint) },
+				call.clone(),
+				Weight::zero(),
+			)?;
+		}
+		let caller = signatories[0].clone();
+		assert!(Multisigs::<T>::contains_key(&multisig, call_hash));
+		// Whitelist caller account from further DB operations.
+		let caller_key = frame_system::Account::<T>::hashed_key_for(&caller);
+		add_to_whitelist(caller_key.into());
+
+		#[extrinsic_call]
+		as_stored_multi(
+			RawOrigin::Signed(caller),
+			multisig.clone(),
+			Some(timepoint),
+			call,
+			Weight::MAX,
+		);
+
+		assert!(!Multisigs::<T>::contains_key(&multisig, call_hash));
+
+		Ok(())
+	}
+
+	/// `s`: Signatories, need at least 2 people
+	#[benchmark]
+	fn approve_as_stored_multi_create(
+		s: Linear<2, { T::MaxSignatories::get() }>,
+	) -> Result<(), BenchmarkError> {
+		// The call is neither in storage or an argument, so just use any:
+		let call_len = 10_000;
+		let (signatories, multisig, call) = setup_stored::<T>(s, call_len, s as u16)?;
+		let call_hash = call.using_encoded(blake2_256);
+		let caller = signatories[s as usize - 1].clone();
+		// Whitelist caller account from further DB operations.
+		let caller_key = frame_system::Account::<T>::hashed_key_for(&caller);
+		add_to_whitelist(caller_key.into());
+
+		#[extrinsic_call]
+		approve_as_stored_multi(
+			RawOrigin::Signed(caller),
+			multisig.clone(),
+			None,
+			call_hash,
+			Weight::zero(),
+		);
+
+		assert!(Multisigs::<T>::contains_key(multisig, call_hash));
+
+		Ok(())
+	}
+
+	/// `s`: Signatories, need at least 2 people
+	#[benchmark]
+	fn approve_as_stored_multi_approve(
+		s: Linear<2, { T::MaxSignatories::get() }>,
+	) -> Result<(), BenchmarkError> {
+		// The call is neither in storage or an argument, so just use any:
+		let call_len = 10_000;
+		let (signatories, multisig, call) = setup_stored::<T>(s, call_len, s as u16)?;
+		let call_hash = call.using_encoded(blake2_256);
+		let caller = signatories[s as usize - 1].clone();
+		// before the call, get the timepoint
+		let timepoint = Multisig::<T>::timepoint();
+		// Create the multi
+		Multisig::<T>::as_stored_multi(
+			RawOrigin::Signed(caller).into(),
+			multisig.clone(),
+			None,
+			call,
+			Weight::zero(),
+		)?;
+		let caller2 = signatories[0].clone();
+		// Whitelist caller account from further DB operations.
+		let caller_key = frame_system::Account::<T>::hashed_key_for(&caller2);
+		add_to_whitelist(caller_key.into());
+
+		#[extrinsic_call]
+		approve_as_stored_multi(
+			RawOrigin::Signed(caller2),
+			multisig.clone(),
+			Some(timepoint),
+			call_hash,
+			Weight::zero(),
+		);
+
+		let operation = Multisigs::<T>::get(multisig, call_hash).ok_or("multisig not created")?;
+		assert_eq!(operation.approvals.len(), 2);
+
+		Ok(())
+	}
+
+	/// `s`: Signatories, need at least 2 people
+	#[benchmark]
+	fn cancel_as_stored_multi(
+		s: Linear<2, { T::MaxSignatories::get() }>,
+	) -> Result<(), BenchmarkError> {
+		// The call is neither in storage or an argument, so just use any:
+		let call_len = 10_000;
+		let (signatories, multisig, call) = setup_stored::<T>(s, call_len, s as u16)?;
+		let call_hash = call.using_encoded(blake2_256);
+		let caller = signatories[s as usize - 1].clone();
+		let timepoint = Multisig::<T>::timepoint();
+		// Create the multi
+		Multisig::<T>::as_stored_multi(
+			RawOrigin::Signed(caller.clone()).into(),
+			multisig.clone(),
+			None,
+			call,
+			Weight::zero(),
+		)?;
+		assert!(Multisigs::<T>::contains_key(&multisig, call_hash));
+		// Whitelist caller account from further DB operations.
+		let caller_key = frame_system::Account::<T>::hashed_key_for(&caller);
+		add_to_whitelist(caller_key.into());
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), multisig.clone(), timepoint, call_hash);
+
+		assert!(!Multisigs::<T>::contains_key(multisig, call_hash));
+
+		Ok(())
+	}
+
+	/// `s`: New signatories, need at least 2 people
+	#[benchmark]
+	fn set_members(s: Linear<2, { T::MaxSignatories::get() }>) -> Result<(), BenchmarkError> {
+		let (_, multisig, _) = setup_stored::<T>(2, 0, 2)?;
+		let creator = StoredMultisigs::<T>::get(&multisig).ok_or("multisig not stored")?.depositor;
+		// Remove the creator, so that the deposit moves to the multisig.
+		let (mut signatories, _) = setup_multi::<T>(s + 1, 0)?;
+		signatories.retain(|signatory| *signatory != creator);
+		signatories.truncate(s as usize);
+		T::Currency::make_free_balance_be(&multisig, BalanceOf::<T>::max_value());
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(multisig.clone()), signatories, s as u16);
+
+		let stored = StoredMultisigs::<T>::get(&multisig).ok_or("multisig not stored")?;
+		assert_eq!(stored.signatories.len(), s as usize);
+		assert_eq!(stored.depositor, multisig);
+		assert!(T::Currency::reserved_balance(&creator).is_zero());
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn destroy_stored() -> Result<(), BenchmarkError> {
+		let max_signatories = T::MaxSignatories::get();
+		let (signatories, multisig, _) =
+			setup_stored::<T>(max_signatories, 0, max_signatories as u16)?;
+		// The multisig holds the deposit itself, which is sent to `dest` along with its funds.
+		T::Currency::make_free_balance_be(&multisig, BalanceOf::<T>::max_value() / 2u32.into());
+		Multisig::<T>::set_members(
+			RawOrigin::Signed(multisig.clone()).into(),
+			signatories[1..].to_vec(),
+			2,
+		)?;
+		let dest: T::AccountId = account("dest", 0, SEED);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(multisig.clone()), T::Lookup::unlookup(dest.clone()));
+
+		assert!(!StoredMultisigs::<T>::contains_key(&multisig));
+		assert!(T::Currency::total_balance(&multisig).is_zero());
+		assert!(!T::Currency::free_balance(&dest).is_zero());
+
+		Ok(())
+	}
+
 	impl_benchmark_test_suite!(Multisig, crate::tests::new_test_ext(), crate::tests::Test);
 }
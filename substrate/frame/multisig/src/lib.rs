@@ -39,6 +39,20 @@
 //!   number of signed origins.
 //! * `approve_as_multi` - Approve a call from a composite origin.
 //! * `cancel_as_multi` - Cancel a call from a composite origin.
+//!
+//! ### Stored Multisigs
+//!
+//! Changing a signatory of a deterministic multisig changes its account ID. Alternatively, a
+//! multisig can be stored on-chain with [`Pallet::create_stored`]: its account ID is derived once
+//! and stays the same while the multisig itself changes its signatories and threshold with
+//! [`Pallet::set_members`].
+//!
+//! * `create_stored` - Create a stored multisig with a stable account ID.
+//! * `as_stored_multi` - Approve and if possible dispatch a call from a stored multisig.
+//! * `approve_as_stored_multi` - Approve a call from a stored multisig.
+//! * `cancel_as_stored_multi` - Cancel a call from a stored multisig.
+//! * `set_members` - Change the signatories and threshold of a stored multisig.
+//! * `destroy_stored` - Remove a stored multisig and return its deposit.
 
 // Ensure we're `no_std` when compiling for Wasm.
 #![cfg_attr(not(feature = "std"), no_std)]
@@ -52,7 +66,7 @@ extern crate alloc;
 use alloc::{boxed::Box, vec, vec::Vec};
 use frame::{
 	prelude::*,
-	traits::{Currency, ReservableCurrency},
+	traits::{Currency, ExistenceRequirement, ReservableCurrency},
 };
 use frame_system::RawOrigin;
 pub use weights::WeightInfo;
@@ -74,6 +88,8 @@ macro_rules! log {
 	};
 }
 
+type AccountIdLookupOf<T> = <<T as frame_system::Config>::Lookup as StaticLookup>::Source;
+
 pub type BalanceOf<T> =
 	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
 
@@ -120,6 +136,24 @@ where
 	pub approvals: BoundedVec<AccountId, MaxApprovals>,
 }
 
+/// A multisig account with a stable account ID, whose signatories and threshold may change.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[scale_info(skip_type_params(MaxSignatories))]
+pub struct StoredMultisig<Balance, AccountId, MaxSignatories>
+where
+	MaxSignatories: Get<u32>,
+{
+	/// The account holding the deposit: the creator for as long as it is one of the signatories,
+	/// the multisig itself once the creator has been removed.
+	pub depositor: AccountId,
+	/// The amount held in reserve of the `depositor`, to be returned once it is destroyed.
+	pub deposit: Balance,
+	/// The number of approvals needed to dispatch a call.
+	pub threshold: u16,
+	/// The signatories. Always sorted.
+	pub signatories: BoundedVec<AccountId, MaxSignatories>,
+}
+
 type CallHash = [u8; 32];
 
 enum CallOrHash<T: Config> {
@@ -211,6 +245,15 @@ pub mod pallet {
 		Multisig<BlockNumberFor<T>, BalanceOf<T>, T::AccountId, T::MaxSignatories>,
 	>;
 
+	/// The stored multisigs, keyed by their account ID.
+	#[pallet::storage]
+	pub type StoredMultisigs<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		StoredMultisig<BalanceOf<T>, T::AccountId, T::MaxSignatories>,
+	>;
+
 	#[pallet::error]
 	pub enum Error<T> {
 		/// Threshold must be 2 or greater.
@@ -242,6 +285,16 @@ pub mod pallet {
 		MaxWeightTooLow,
 		/// The data to be stored is already stored.
 		AlreadyStored,
+		/// The account is not a stored multisig.
+		NotStoredMultisig,
+		/// The sender is not a signatory of the stored multisig.
+		NotSignatory,
+		/// The threshold is greater than the number of signatories.
+		ThresholdTooHigh,
+		/// The creator of a stored multisig must be one of its signatories.
+		CreatorNotSignatory,
+		/// The stored multisig account still holds funds or is provided for.
+		StoredMultisigInUse,
 	}
 
 	#[pallet::event]
@@ -278,6 +331,12 @@ pub mod pallet {
 			old_deposit: BalanceOf<T>,
 			new_deposit: BalanceOf<T>,
 		},
+		/// A stored multisig has been created.
+		StoredMultisigCreated { creator: T::AccountId, multisig: T::AccountId, threshold: u16 },
+		/// The signatories or the threshold of a stored multisig have been changed.
+		MembersChanged { multisig: T::AccountId, threshold: u16 },
+		/// A stored multisig has been destroyed.
+		StoredMultisigDestroyed { multisig: T::AccountId },
 	}
 
 	#[pallet::hooks]
@@ -510,21 +569,7 @@ pub mod pallet {
 
 			let id = Self::multi_account_id(&signatories, threshold);
 
-			let m = <Multisigs<T>>::get(&id, call_hash).ok_or(Error::<T>::NotFound)?;
-			ensure!(m.when == timepoint, Error::<T>::WrongTimepoint);
-			ensure!(m.depositor == who, Error::<T>::NotOwner);
-
-			let err_amount = T::Currency::unreserve(&m.depositor, m.deposit);
-			debug_assert!(err_amount.is_zero());
-			<Multisigs<T>>::remove(&id, &call_hash);
-
-			Self::deposit_event(Event::MultisigCancelled {
-				cancelling: who,
-				timepoint,
-				multisig: id,
-				call_hash,
-			});
-			Ok(())
+			Self::cancel(who, id, timepoint, call_hash)
 		}
 
 		/// Poke the deposit reserved for an existing multisig operation.
@@ -604,6 +649,261 @@ pub mod pallet {
 				},
 			)
 		}
+
+		/// Create a stored multisig, whose account ID does not depend on its signatories.
+		///
+		/// Payment: `DepositBase` plus `DepositFactor` times the number of signatories will be
+		/// reserved. It is returned once the stored multisig is destroyed.
+		///
+		/// The dispatch origin for this call must be _Signed_. The sender must be one of the
+		/// signatories and holds the deposit for as long as it stays one.
+		///
+		/// - `signatories`: The sorted accounts who can approve dispatches of the multisig.
+		/// - `threshold`: The number of approvals needed for a dispatch.
+		///
+		/// Emits `StoredMultisigCreated` with the account ID of the multisig.
+		#[pallet::call_index(5)]
+		#[pallet::weight(T::WeightInfo::create_stored(signatories.len() as u32))]
+		pub fn create_stored(
+			origin: OriginFor<T>,
+			signatories: Vec<T::AccountId>,
+			threshold: u16,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let signatories = Self::ensure_valid_members(signatories, threshold)?;
+			ensure!(signatories.binary_search(&who).is_ok(), Error::<T>::CreatorNotSignatory);
+
+			let multisig = Self::stored_account_id(&who, Self::timepoint());
+			ensure!(!StoredMultisigs::<T>::contains_key(&multisig), Error::<T>::AlreadyStored);
+
+			let deposit = Self::stored_deposit(signatories.len());
+			T::Currency::reserve(&who, deposit)?;
+			StoredMultisigs::<T>::insert(
+				&multisig,
+				StoredMultisig { depositor: who.clone(), deposit, threshold, signatories },
+			);
+
+			Self::deposit_event(Event::StoredMultisigCreated { creator: who, multisig, threshold });
+			Ok(())
+		}
+
+		/// Register approval for a dispatch to be made from a stored multisig and dispatch the
+		/// call once it has `threshold` approvals of its current signatories.
+		///
+		/// Payment: as for `as_multi`, with the current threshold of the multisig.
+		///
+		/// The dispatch origin for this call must be _Signed_ by a signatory of `multisig`.
+		///
+		/// - `multisig`: The account ID of the stored multisig.
+		/// - `maybe_timepoint`: If this is the first approval, then this must be `None`. If it is
+		/// not the first approval, then it must be `Some`, with the timepoint (block number and
+		/// transaction index) of the first approval transaction.
+		/// - `call`: The call to be executed.
+		///
+		/// Approvals of accounts that are no longer signatories are disregarded.
+		#[pallet::call_index(6)]
+		#[pallet::weight({
+			let s = T::MaxSignatories::get().saturating_sub(1);
+			let z = call.using_encoded(|d| d.len()) as u32;
+
+			T::WeightInfo::as_stored_multi_create(s, z)
+				.max(T::WeightInfo::as_stored_multi_approve(s, z))
+				.max(T::WeightInfo::as_stored_multi_complete(s, z))
+				.saturating_add(*max_weight)
+		})]
+		pub fn as_stored_multi(
+			origin: OriginFor<T>,
+			multisig: T::AccountId,
+			maybe_timepoint: Option<Timepoint<BlockNumberFor<T>>>,
+			call: Box<<T as Config>::RuntimeCall>,
+			max_weight: Weight,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let stored = Self::ensure_signatory(&multisig, &who)?;
+			with_stored_read::<T>(Self::operate_as(
+				who,
+				multisig,
+				stored.threshold,
+				&stored.signatories,
+				maybe_timepoint,
+				CallOrHash::Call(*call),
+				max_weight,
+			))
+		}
+
+		/// Register approval for a dispatch to be made from a stored multisig.
+		///
+		/// Payment: as for `approve_as_multi`, with the current threshold of the multisig.
+		///
+		/// The dispatch origin for this call must be _Signed_ by a signatory of `multisig`.
+		///
+		/// - `multisig`: The account ID of the stored multisig.
+		/// - `maybe_timepoint`: If this is the first approval, then this must be `None`. If it is
+		/// not the first approval, then it must be `Some`, with the timepoint (block number and
+		/// transaction index) of the first approval transaction.
+		/// - `call_hash`: The hash of the call to be executed.
+		#[pallet::call_index(7)]
+		#[pallet::weight({
+			let s = T::MaxSignatories::get().saturating_sub(1);
+
+			T::WeightInfo::approve_as_stored_multi_create(s)
+				.max(T::WeightInfo::approve_as_stored_multi_approve(s))
+				.saturating_add(*max_weight)
+		})]
+		pub fn approve_as_stored_multi(
+			origin: OriginFor<T>,
+			multisig: T::AccountId,
+			maybe_timepoint: Option<Timepoint<BlockNumberFor<T>>>,
+			call_hash: [u8; 32],
+			max_weight: Weight,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let stored = Self::ensure_signatory(&multisig, &who)?;
+			with_stored_read::<T>(Self::operate_as(
+				who,
+				multisig,
+				stored.threshold,
+				&stored.signatories,
+				maybe_timepoint,
+				CallOrHash::Hash(call_hash),
+				max_weight,
+			))
+		}
+
+		/// Cancel a pre-existing, on-going operation of a stored multisig. Any deposit reserved
+		/// previously for this operation will be unreserved on success.
+		///
+		/// The dispatch origin for this call must be _Signed_ by the account who opened the
+		/// operation, even if it is no longer a signatory or the multisig has been destroyed.
+		///
+		/// - `multisig`: The account ID of the stored multisig.
+		/// - `timepoint`: The timepoint (block number and transaction index) of the first approval
+		/// transaction for this dispatch.
+		/// - `call_hash`: The hash of the call to be executed.
+		#[pallet::call_index(8)]
+		#[pallet::weight(T::WeightInfo::cancel_as_stored_multi(T::MaxSignatories::get()))]
+		pub fn cancel_as_stored_multi(
+			origin: OriginFor<T>,
+			multisig: T::AccountId,
+			timepoint: Timepoint<BlockNumberFor<T>>,
+			call_hash: [u8; 32],
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::cancel(who, multisig, timepoint, call_hash)
+		}
+
+		/// Change the signatories and the threshold of a stored multisig.
+		///
+		/// The deposit is adjusted to the new number of signatories. If the creator holding it is
+		/// removed, its deposit is returned and the multisig reserves the deposit itself. On-going
+		/// operations are kept, but only the approvals of the new signatories count towards the
+		/// new threshold.
+		///
+		/// The dispatch origin for this call must be _Signed_ by the stored multisig itself.
+		///
+		/// - `signatories`: The new sorted accounts who can approve dispatches of the multisig.
+		/// - `threshold`: The new number of approvals needed for a dispatch.
+		///
+		/// Emits `MembersChanged`.
+		#[pallet::call_index(9)]
+		#[pallet::weight(T::WeightInfo::set_members(signatories.len() as u32))]
+		pub fn set_members(
+			origin: OriginFor<T>,
+			signatories: Vec<T::AccountId>,
+			threshold: u16,
+		) -> DispatchResult {
+			let multisig = ensure_signed(origin)?;
+			let signatories = Self::ensure_valid_members(signatories, threshold)?;
+
+			StoredMultisigs::<T>::try_mutate(&multisig, |maybe_stored| -> DispatchResult {
+				let stored = maybe_stored.as_mut().ok_or(Error::<T>::NotStoredMultisig)?;
+
+				let new_deposit = Self::stored_deposit(signatories.len());
+				if stored.depositor != multisig &&
+					signatories.binary_search(&stored.depositor).is_err()
+				{
+					T::Currency::reserve(&multisig, new_deposit)?;
+					let remaining_unreserved =
+						T::Currency::unreserve(&stored.depositor, stored.deposit);
+					if !remaining_unreserved.is_zero() {
+						defensive!(
+							"Failed to unreserve full amount for stored multisig. (Requested, Actual)",
+							(stored.deposit, stored.deposit.saturating_sub(remaining_unreserved))
+						);
+					}
+					stored.depositor = multisig.clone();
+				} else if new_deposit > stored.deposit {
+					T::Currency::reserve(
+						&stored.depositor,
+						new_deposit.saturating_sub(stored.deposit),
+					)?;
+				} else if new_deposit < stored.deposit {
+					let excess = stored.deposit.saturating_sub(new_deposit);
+					let remaining_unreserved = T::Currency::unreserve(&stored.depositor, excess);
+					if !remaining_unreserved.is_zero() {
+						defensive!(
+							"Failed to unreserve full amount for stored multisig. (Requested, Actual)",
+							(excess, excess.saturating_sub(remaining_unreserved))
+						);
+					}
+				}
+
+				stored.deposit = new_deposit;
+				stored.threshold = threshold;
+				stored.signatories = signatories;
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::MembersChanged { multisig, threshold });
+			Ok(())
+		}
+
+		/// Destroy a stored multisig and return its deposit.
+		///
+		/// The account of the multisig must no longer exist afterwards, i.e. it must not hold any
+		/// funds nor be provided for by other pallets, since nobody could dispatch from it.
+		/// On-going operations can still be cancelled by their depositors.
+		///
+		/// The dispatch origin for this call must be _Signed_ by the stored multisig itself.
+		///
+		/// - `dest`: The account receiving the funds of the multisig, including the deposit, if the
+		///   multisig holds the deposit itself after its creator has been removed. Otherwise the
+		///   deposit is returned to the creator and the multisig must not hold any funds.
+		///
+		/// Emits `StoredMultisigDestroyed`.
+		#[pallet::call_index(10)]
+		#[pallet::weight(T::WeightInfo::destroy_stored())]
+		pub fn destroy_stored(origin: OriginFor<T>, dest: AccountIdLookupOf<T>) -> DispatchResult {
+			let multisig = ensure_signed(origin)?;
+			let dest = T::Lookup::lookup(dest)?;
+			let stored =
+				StoredMultisigs::<T>::take(&multisig).ok_or(Error::<T>::NotStoredMultisig)?;
+
+			let remaining_unreserved = T::Currency::unreserve(&stored.depositor, stored.deposit);
+			if !remaining_unreserved.is_zero() {
+				defensive!(
+					"Failed to unreserve full amount for stored multisig. (Requested, Actual)",
+					(stored.deposit, stored.deposit.saturating_sub(remaining_unreserved))
+				);
+			}
+			if stored.depositor == multisig {
+				T::Currency::transfer(
+					&multisig,
+					&dest,
+					T::Currency::free_balance(&multisig),
+					ExistenceRequirement::AllowDeath,
+				)
+				.map_err(|_| Error::<T>::StoredMultisigInUse)?;
+			}
+			ensure!(
+				T::Currency::total_balance(&multisig).is_zero() &&
+					!frame_system::Pallet::<T>::account_exists(&multisig),
+				Error::<T>::StoredMultisigInUse
+			);
+
+			Self::deposit_event(Event::StoredMultisigDestroyed { multisig });
+			Ok(())
+		}
 	}
 }
 
@@ -635,6 +935,30 @@ impl<T: Config> Pallet<T> {
 
 		let id = Self::multi_account_id(&signatories, threshold);
 
+		Self::operate_as(
+			who,
+			id,
+			threshold,
+			&signatories,
+			maybe_timepoint,
+			call_or_hash,
+			max_weight,
+		)
+	}
+
+	/// Approve and if possible dispatch an operation of the multisig `id`, given its `threshold`
+	/// and sorted `signatories`, which include `who`.
+	fn operate_as(
+		who: T::AccountId,
+		id: T::AccountId,
+		threshold: u16,
+		signatories: &[T::AccountId],
+		maybe_timepoint: Option<Timepoint<BlockNumberFor<T>>>,
+		call_or_hash: CallOrHash<T>,
+		max_weight: Weight,
+	) -> DispatchResultWithPostInfo {
+		let other_signatories_len = signatories.len().saturating_sub(1);
+
 		// Threshold > 1; this means it's a multi-step operation. We extract the `call_hash`.
 		let (call_hash, call_len, maybe_call) = match call_or_hash {
 			CallOrHash::Call(call) => {
@@ -650,6 +974,10 @@ impl<T: Config> Pallet<T> {
 			let timepoint = maybe_timepoint.ok_or(Error::<T>::NoTimepoint)?;
 			ensure!(m.when == timepoint, Error::<T>::WrongTimepoint);
 
+			// The signatories of a stored multisig may have changed since the approvals were
+			// given; only those of the current signatories count.
+			m.approvals.retain(|approval| signatories.binary_search(approval).is_ok());
+
 			// Ensure that either we have not yet signed or that it is at threshold.
 			let mut approvals = m.approvals.len() as u16;
 			// We only bother with the approval if we're below threshold.
@@ -747,6 +1075,71 @@ impl<T: Config> Pallet<T> {
 		}
 	}
 
+	/// Cancel the operation `call_hash` of the multisig `id`, opened by `who` at `timepoint`.
+	fn cancel(
+		who: T::AccountId,
+		id: T::AccountId,
+		timepoint: Timepoint<BlockNumberFor<T>>,
+		call_hash: CallHash,
+	) -> DispatchResult {
+		let m = <Multisigs<T>>::get(&id, call_hash).ok_or(Error::<T>::NotFound)?;
+		ensure!(m.when == timepoint, Error::<T>::WrongTimepoint);
+		ensure!(m.depositor == who, Error::<T>::NotOwner);
+
+		let err_amount = T::Currency::unreserve(&m.depositor, m.deposit);
+		debug_assert!(err_amount.is_zero());
+		<Multisigs<T>>::remove(&id, &call_hash);
+
+		Self::deposit_event(Event::MultisigCancelled {
+			cancelling: who,
+			timepoint,
+			multisig: id,
+			call_hash,
+		});
+		Ok(())
+	}
+
+	/// Derive the account ID of a stored multisig created by `who` at `timepoint`.
+	pub fn stored_account_id(
+		who: &T::AccountId,
+		timepoint: Timepoint<BlockNumberFor<T>>,
+	) -> T::AccountId {
+		let entropy = (b"modlpy/multisig_", who, timepoint).using_encoded(blake2_256);
+		Decode::decode(&mut TrailingZeroInput::new(entropy.as_ref()))
+			.expect("infinite length input; no invalid inputs for type; qed")
+	}
+
+	/// Get the stored multisig `multisig`, ensuring that `who` is one of its signatories.
+	fn ensure_signatory(
+		multisig: &T::AccountId,
+		who: &T::AccountId,
+	) -> Result<StoredMultisig<BalanceOf<T>, T::AccountId, T::MaxSignatories>, DispatchError> {
+		let stored = StoredMultisigs::<T>::get(multisig).ok_or(Error::<T>::NotStoredMultisig)?;
+		ensure!(stored.signatories.binary_search(who).is_ok(), Error::<T>::NotSignatory);
+		Ok(stored)
+	}
+
+	/// Check that `signatories` are sorted and bounded and that `threshold` is reachable.
+	fn ensure_valid_members(
+		signatories: Vec<T::AccountId>,
+		threshold: u16,
+	) -> Result<BoundedVec<T::AccountId, T::MaxSignatories>, DispatchError> {
+		ensure!(threshold >= 2, Error::<T>::MinimumThreshold);
+		ensure!(
+			signatories.windows(2).all(|pair| pair[0] < pair[1]),
+			Error::<T>::SignatoriesOutOfOrder
+		);
+		ensure!(threshold as usize <= signatories.len(), Error::<T>::ThresholdTooHigh);
+		signatories.try_into().map_err(|_| Error::<T>::TooManySignatories.into())
+	}
+
+	/// Calculate the deposit for a stored multisig.
+	///
+	/// The deposit is calculated as `DepositBase + DepositFactor * signatories`.
+	pub fn stored_deposit(signatories: usize) -> BalanceOf<T> {
+		T::DepositBase::get() + T::DepositFactor::get() * (signatories as u32).into()
+	}
+
 	/// The current `Timepoint`.
 	pub fn timepoint() -> Timepoint<BlockNumberFor<T>> {
 		Timepoint {
@@ -794,3 +1187,12 @@ fn get_result_weight(result: DispatchResultWithPostInfo) -> Option<Weight> {
 		Err(err) => err.post_info.actual_weight,
 	}
 }
+
+/// Add the read of the stored multisig to the actual weight of an operation made through it.
+fn with_stored_read<T: Config>(result: DispatchResultWithPostInfo) -> DispatchResultWithPostInfo {
+	result.map(|mut post_info| {
+		post_info.actual_weight =
+			post_info.actual_weight.map(|w| w.saturating_add(T::DbWeight::get().reads(1)));
+		post_info
+	})
+}
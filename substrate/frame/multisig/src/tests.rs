@@ -52,6 +52,8 @@ impl Contains<RuntimeCall> for TestBaseCallFilter {
 	fn contains(c: &RuntimeCall) -> bool {
 		match *c {
 			RuntimeCall::Balances(_) => true,
+			RuntimeCall::Multisig(Call::set_members { .. }) |
+			RuntimeCall::Multisig(Call::destroy_stored { .. }) => true,
 			// Needed for benchmarking
 			RuntimeCall::System(frame_system::Call::remark { .. }) => true,
 			_ => false,
@@ -1005,3 +1007,288 @@ fn poke_deposit_handles_insufficient_balance() {
 		);
 	});
 }
+
+fn create_stored(creator: u64, signatories: Vec<u64>, threshold: u16) -> u64 {
+	let multisig = Multisig::stored_account_id(&creator, now());
+	assert_ok!(Multisig::create_stored(RuntimeOrigin::signed(creator), signatories, threshold));
+	System::assert_last_event(
+		pallet_multisig::Event::StoredMultisigCreated { creator, multisig, threshold }.into(),
+	);
+	multisig
+}
+
+#[test]
+fn stored_multisig_works() {
+	new_test_ext().execute_with(|| {
+		let multi = create_stored(1, vec![1, 2, 3], 2);
+		assert_eq!(Balances::reserved_balance(1), 4);
+		assert_ok!(Balances::transfer_allow_death(RuntimeOrigin::signed(2), multi, 5));
+		assert_ok!(Balances::transfer_allow_death(RuntimeOrigin::signed(3), multi, 5));
+
+		let call = call_transfer(6, 10);
+		let call_weight = call.get_dispatch_info().call_weight;
+		assert_noop!(
+			Multisig::as_stored_multi(
+				RuntimeOrigin::signed(4),
+				multi,
+				None,
+				call.clone(),
+				call_weight
+			),
+			Error::<Test>::NotSignatory,
+		);
+		assert_noop!(
+			Multisig::as_stored_multi(RuntimeOrigin::signed(1), 7, None, call.clone(), call_weight),
+			Error::<Test>::NotStoredMultisig,
+		);
+
+		assert_ok!(Multisig::approve_as_stored_multi(
+			RuntimeOrigin::signed(1),
+			multi,
+			None,
+			blake2_256(&call.encode()),
+			Weight::zero()
+		));
+		assert_eq!(Balances::reserved_balance(1), 7);
+		assert_ok!(Multisig::as_stored_multi(
+			RuntimeOrigin::signed(2),
+			multi,
+			Some(now()),
+			call,
+			call_weight
+		));
+		assert_eq!(Balances::free_balance(6), 10);
+		assert_eq!(Balances::reserved_balance(1), 4);
+	});
+}
+
+#[test]
+fn stored_multisig_validates_members() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Multisig::create_stored(RuntimeOrigin::signed(1), vec![1, 2], 1),
+			Error::<Test>::MinimumThreshold,
+		);
+		assert_noop!(
+			Multisig::create_stored(RuntimeOrigin::signed(1), vec![2, 1], 2),
+			Error::<Test>::SignatoriesOutOfOrder,
+		);
+		assert_noop!(
+			Multisig::create_stored(RuntimeOrigin::signed(1), vec![1, 1, 2], 2),
+			Error::<Test>::SignatoriesOutOfOrder,
+		);
+		assert_noop!(
+			Multisig::create_stored(RuntimeOrigin::signed(1), vec![1, 2], 3),
+			Error::<Test>::ThresholdTooHigh,
+		);
+		assert_noop!(
+			Multisig::create_stored(RuntimeOrigin::signed(1), vec![1, 2, 3, 4], 2),
+			Error::<Test>::TooManySignatories,
+		);
+		assert_noop!(
+			Multisig::create_stored(RuntimeOrigin::signed(1), vec![2, 3], 2),
+			Error::<Test>::CreatorNotSignatory,
+		);
+		assert_noop!(
+			Multisig::create_stored(RuntimeOrigin::signed(5), vec![1, 5], 2),
+			BalancesError::<Test, _>::InsufficientBalance,
+		);
+		assert_noop!(
+			Multisig::set_members(RuntimeOrigin::signed(1), vec![1, 2], 2),
+			Error::<Test>::NotStoredMultisig,
+		);
+
+		create_stored(1, vec![1, 2], 2);
+		assert_noop!(
+			Multisig::create_stored(RuntimeOrigin::signed(1), vec![1, 3], 2),
+			Error::<Test>::AlreadyStored,
+		);
+	});
+}
+
+#[test]
+fn stored_multisig_members_can_be_changed() {
+	new_test_ext().execute_with(|| {
+		let multi = create_stored(1, vec![1, 2], 2);
+		assert_eq!(Balances::reserved_balance(1), 3);
+		assert_ok!(Balances::transfer_allow_death(RuntimeOrigin::signed(3), multi, 5));
+
+		// An operation approved by 2 only, who is about to be removed.
+		let transfer = call_transfer(6, 5);
+		let transfer_weight = transfer.get_dispatch_info().call_weight;
+		assert_ok!(Multisig::approve_as_stored_multi(
+			RuntimeOrigin::signed(2),
+			multi,
+			None,
+			blake2_256(&transfer.encode()),
+			Weight::zero()
+		));
+		let transfer_timepoint = now();
+
+		System::set_block_number(2);
+		let call = Box::new(RuntimeCall::Multisig(Call::set_members {
+			signatories: vec![1, 3, 4],
+			threshold: 2,
+		}));
+		let call_weight = call.get_dispatch_info().call_weight;
+		assert_ok!(Multisig::as_stored_multi(
+			RuntimeOrigin::signed(1),
+			multi,
+			None,
+			call.clone(),
+			Weight::zero()
+		));
+		assert_ok!(Multisig::as_stored_multi(
+			RuntimeOrigin::signed(2),
+			multi,
+			Some(now()),
+			call,
+			call_weight
+		));
+		System::assert_has_event(
+			pallet_multisig::Event::MembersChanged { multisig: multi, threshold: 2 }.into(),
+		);
+		let stored = StoredMultisigs::<Test>::get(multi).unwrap();
+		assert_eq!(stored.signatories.into_inner(), vec![1, 3, 4]);
+		// The creator pays for the additional signatory.
+		assert_eq!(Balances::reserved_balance(1), 4);
+
+		assert_noop!(
+			Multisig::as_stored_multi(
+				RuntimeOrigin::signed(2),
+				multi,
+				Some(transfer_timepoint),
+				transfer.clone(),
+				transfer_weight
+			),
+			Error::<Test>::NotSignatory,
+		);
+
+		// The approval of 2 no longer counts.
+		assert_ok!(Multisig::as_stored_multi(
+			RuntimeOrigin::signed(3),
+			multi,
+			Some(transfer_timepoint),
+			transfer.clone(),
+			transfer_weight
+		));
+		assert_eq!(Balances::free_balance(6), 0);
+		assert_ok!(Multisig::as_stored_multi(
+			RuntimeOrigin::signed(4),
+			multi,
+			Some(transfer_timepoint),
+			transfer,
+			transfer_weight
+		));
+		assert_eq!(Balances::free_balance(6), 5);
+		assert_eq!(Balances::reserved_balance(2), 0);
+		assert_eq!(Balances::reserved_balance(1), 4);
+	});
+}
+
+#[test]
+fn stored_multisig_can_be_destroyed() {
+	new_test_ext().execute_with(|| {
+		let multi = create_stored(2, vec![2, 3], 2);
+		assert_eq!(Balances::reserved_balance(2), 3);
+
+		let call = call_transfer(6, 5);
+		let hash = blake2_256(&call.encode());
+		assert_ok!(Multisig::approve_as_stored_multi(
+			RuntimeOrigin::signed(2),
+			multi,
+			None,
+			hash,
+			Weight::zero()
+		));
+		let timepoint = now();
+		assert_eq!(Balances::reserved_balance(2), 6);
+
+		// Nobody could dispatch from the multisig anymore, so it must not hold any funds.
+		assert_ok!(Balances::transfer_allow_death(RuntimeOrigin::signed(3), multi, 5));
+		assert_noop!(
+			Multisig::destroy_stored(RuntimeOrigin::signed(multi), 6),
+			Error::<Test>::StoredMultisigInUse,
+		);
+		assert_ok!(Balances::transfer_all(RuntimeOrigin::signed(multi), 6, false));
+
+		let destroy = Box::new(RuntimeCall::Multisig(Call::destroy_stored { dest: 6 }));
+		let destroy_weight = destroy.get_dispatch_info().call_weight;
+		assert_ok!(Multisig::as_stored_multi(
+			RuntimeOrigin::signed(3),
+			multi,
+			None,
+			destroy.clone(),
+			Weight::zero()
+		));
+		assert_ok!(Multisig::as_stored_multi(
+			RuntimeOrigin::signed(2),
+			multi,
+			Some(now()),
+			destroy,
+			destroy_weight
+		));
+		System::assert_has_event(
+			pallet_multisig::Event::StoredMultisigDestroyed { multisig: multi }.into(),
+		);
+		assert!(!StoredMultisigs::<Test>::contains_key(multi));
+		assert_eq!(Balances::reserved_balance(2), 3);
+
+		// Pending operations can still be cancelled.
+		assert_noop!(
+			Multisig::approve_as_stored_multi(
+				RuntimeOrigin::signed(3),
+				multi,
+				Some(timepoint),
+				hash,
+				Weight::zero()
+			),
+			Error::<Test>::NotStoredMultisig,
+		);
+		assert_noop!(
+			Multisig::cancel_as_stored_multi(RuntimeOrigin::signed(3), multi, timepoint, hash),
+			Error::<Test>::NotOwner,
+		);
+		assert_ok!(Multisig::cancel_as_stored_multi(
+			RuntimeOrigin::signed(2),
+			multi,
+			timepoint,
+			hash
+		));
+		assert_eq!(Balances::reserved_balance(2), 0);
+	});
+}
+
+#[test]
+fn stored_multisig_creator_can_be_removed() {
+	new_test_ext().execute_with(|| {
+		let multi = create_stored(1, vec![1, 2], 2);
+		assert_eq!(Balances::reserved_balance(1), 3);
+
+		// The multisig takes over the deposit, so it must be able to afford it.
+		assert_noop!(
+			Multisig::set_members(RuntimeOrigin::signed(multi), vec![2, 3, 4], 2),
+			BalancesError::<Test, _>::InsufficientBalance,
+		);
+		assert_ok!(Balances::transfer_allow_death(RuntimeOrigin::signed(2), multi, 6));
+		assert_ok!(Multisig::set_members(RuntimeOrigin::signed(multi), vec![2, 3, 4], 2));
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::reserved_balance(multi), 4);
+		assert_eq!(StoredMultisigs::<Test>::get(multi).unwrap().depositor, multi);
+
+		// The multisig keeps holding the deposit, even if the creator is added back.
+		assert_ok!(Multisig::set_members(RuntimeOrigin::signed(multi), vec![1, 2], 2));
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::reserved_balance(multi), 3);
+		assert_eq!(Balances::free_balance(multi), 3);
+
+		// Its funds, including the deposit, go to the given account once destroyed.
+		assert_ok!(Multisig::destroy_stored(RuntimeOrigin::signed(multi), 6));
+		System::assert_last_event(
+			pallet_multisig::Event::StoredMultisigDestroyed { multisig: multi }.into(),
+		);
+		assert_eq!(Balances::total_balance(&multi), 0);
+		assert_eq!(Balances::free_balance(6), 6);
+		assert_eq!(Balances::reserved_balance(1), 0);
+	});
+}
@@ -79,6 +79,15 @@ pub trait WeightInfo {
 	fn approve_as_multi_approve(s: u32, ) -> Weight;
 	fn cancel_as_multi(s: u32, ) -> Weight;
 	fn poke_deposit(s: u32, ) -> Weight;
+	fn create_stored(s: u32, ) -> Weight;
+	fn as_stored_multi_create(s: u32, z: u32, ) -> Weight;
+	fn as_stored_multi_approve(s: u32, z: u32, ) -> Weight;
+	fn as_stored_multi_complete(s: u32, z: u32, ) -> Weight;
+	fn approve_as_stored_multi_create(s: u32, ) -> Weight;
+	fn approve_as_stored_multi_approve(s: u32, ) -> Weight;
+	fn cancel_as_stored_multi(s: u32, ) -> Weight;
+	fn set_members(s: u32, ) -> Weight;
+	fn destroy_stored() -> Weight;
 }
 
 /// Weights for `pallet_multisig` using the Substrate node and recommended hardware.
@@ -212,6 +221,120 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(1_u64))
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
+	/// Storage: `Multisig::StoredMultisigs` (r:1 w:1)
+	/// Proof: `Multisig::StoredMultisigs` (`max_values`: None, `max_size`: Some(3291), added: 5766, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[2, 100]`.
+	fn create_stored(s: u32, ) -> Weight {
+		// Estimated, not measured: to be regenerated with the benchmark CLI.
+		Weight::from_parts(30_631_468, 6756)
+			.saturating_add(Weight::from_parts(121_813, 0).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Multisig::StoredMultisigs` (r:1 w:0)
+	/// Proof: `Multisig::StoredMultisigs` (`max_values`: None, `max_size`: Some(3291), added: 5766, mode: `MaxEncodedLen`)
+	/// Storage: `Multisig::Multisigs` (r:1 w:1)
+	/// Proof: `Multisig::Multisigs` (`max_values`: None, `max_size`: Some(3346), added: 5821, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[2, 100]`.
+	/// The range of component `z` is `[0, 10000]`.
+	fn as_stored_multi_create(s: u32, z: u32, ) -> Weight {
+		// Estimated, not measured: to be regenerated with the benchmark CLI.
+		Weight::from_parts(33_573_973, 6811)
+			.saturating_add(Weight::from_parts(144_320, 0).saturating_mul(s.into()))
+			.saturating_add(Weight::from_parts(1_819, 0).saturating_mul(z.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Multisig::StoredMultisigs` (r:1 w:0)
+	/// Proof: `Multisig::StoredMultisigs` (`max_values`: None, `max_size`: Some(3291), added: 5766, mode: `MaxEncodedLen`)
+	/// Storage: `Multisig::Multisigs` (r:1 w:1)
+	/// Proof: `Multisig::Multisigs` (`max_values`: None, `max_size`: Some(3346), added: 5821, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[3, 100]`.
+	/// The range of component `z` is `[0, 10000]`.
+	fn as_stored_multi_approve(s: u32, z: u32, ) -> Weight {
+		// Estimated, not measured: to be regenerated with the benchmark CLI.
+		Weight::from_parts(16_599_909, 6811)
+			.saturating_add(Weight::from_parts(127_874, 0).saturating_mul(s.into()))
+			.saturating_add(Weight::from_parts(1_968, 0).saturating_mul(z.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Multisig::StoredMultisigs` (r:1 w:0)
+	/// Proof: `Multisig::StoredMultisigs` (`max_values`: None, `max_size`: Some(3291), added: 5766, mode: `MaxEncodedLen`)
+	/// Storage: `Multisig::Multisigs` (r:1 w:1)
+	/// Proof: `Multisig::Multisigs` (`max_values`: None, `max_size`: Some(3346), added: 5821, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `SafeMode::EnteredUntil` (r:1 w:0)
+	/// Proof: `SafeMode::EnteredUntil` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `TxPause::PausedCalls` (r:1 w:0)
+	/// Proof: `TxPause::PausedCalls` (`max_values`: None, `max_size`: Some(532), added: 3007, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[2, 100]`.
+	/// The range of component `z` is `[0, 10000]`.
+	fn as_stored_multi_complete(s: u32, z: u32, ) -> Weight {
+		// Estimated, not measured: to be regenerated with the benchmark CLI.
+		Weight::from_parts(38_914_868, 6811)
+			.saturating_add(Weight::from_parts(193_313, 0).saturating_mul(s.into()))
+			.saturating_add(Weight::from_parts(2_057, 0).saturating_mul(z.into()))
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `Multisig::StoredMultisigs` (r:1 w:0)
+	/// Proof: `Multisig::StoredMultisigs` (`max_values`: None, `max_size`: Some(3291), added: 5766, mode: `MaxEncodedLen`)
+	/// Storage: `Multisig::Multisigs` (r:1 w:1)
+	/// Proof: `Multisig::Multisigs` (`max_values`: None, `max_size`: Some(3346), added: 5821, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[2, 100]`.
+	fn approve_as_stored_multi_create(s: u32, ) -> Weight {
+		// Estimated, not measured: to be regenerated with the benchmark CLI.
+		Weight::from_parts(30_758_868, 6811)
+			.saturating_add(Weight::from_parts(139_226, 0).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Multisig::StoredMultisigs` (r:1 w:0)
+	/// Proof: `Multisig::StoredMultisigs` (`max_values`: None, `max_size`: Some(3291), added: 5766, mode: `MaxEncodedLen`)
+	/// Storage: `Multisig::Multisigs` (r:1 w:1)
+	/// Proof: `Multisig::Multisigs` (`max_values`: None, `max_size`: Some(3346), added: 5821, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[2, 100]`.
+	fn approve_as_stored_multi_approve(s: u32, ) -> Weight {
+		// Estimated, not measured: to be regenerated with the benchmark CLI.
+		Weight::from_parts(15_763_168, 6811)
+			.saturating_add(Weight::from_parts(126_544, 0).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Multisig::Multisigs` (r:1 w:1)
+	/// Proof: `Multisig::Multisigs` (`max_values`: None, `max_size`: Some(3346), added: 5821, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[2, 100]`.
+	fn cancel_as_stored_multi(s: u32, ) -> Weight {
+		// Estimated, not measured: to be regenerated with the benchmark CLI.
+		Weight::from_parts(28_623_566, 6811)
+			.saturating_add(Weight::from_parts(135_351, 0).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Multisig::StoredMultisigs` (r:1 w:1)
+	/// Proof: `Multisig::StoredMultisigs` (`max_values`: None, `max_size`: Some(3291), added: 5766, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[2, 100]`.
+	fn set_members(s: u32, ) -> Weight {
+		// Estimated, not measured: to be regenerated with the benchmark CLI.
+		Weight::from_parts(40_631_468, 10972)
+			.saturating_add(Weight::from_parts(121_813, 0).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: `Multisig::StoredMultisigs` (r:1 w:1)
+	/// Proof: `Multisig::StoredMultisigs` (`max_values`: None, `max_size`: Some(3291), added: 5766, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	fn destroy_stored() -> Weight {
+		// Estimated, not measured: to be regenerated with the benchmark CLI.
+		Weight::from_parts(45_631_468, 10972)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
 }
 
 // For backwards compatibility and tests.
@@ -344,4 +467,118 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(1_u64))
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
+	/// Storage: `Multisig::StoredMultisigs` (r:1 w:1)
+	/// Proof: `Multisig::StoredMultisigs` (`max_values`: None, `max_size`: Some(3291), added: 5766, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[2, 100]`.
+	fn create_stored(s: u32, ) -> Weight {
+		// Estimated, not measured: to be regenerated with the benchmark CLI.
+		Weight::from_parts(30_631_468, 6756)
+			.saturating_add(Weight::from_parts(121_813, 0).saturating_mul(s.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Multisig::StoredMultisigs` (r:1 w:0)
+	/// Proof: `Multisig::StoredMultisigs` (`max_values`: None, `max_size`: Some(3291), added: 5766, mode: `MaxEncodedLen`)
+	/// Storage: `Multisig::Multisigs` (r:1 w:1)
+	/// Proof: `Multisig::Multisigs` (`max_values`: None, `max_size`: Some(3346), added: 5821, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[2, 100]`.
+	/// The range of component `z` is `[0, 10000]`.
+	fn as_stored_multi_create(s: u32, z: u32, ) -> Weight {
+		// Estimated, not measured: to be regenerated with the benchmark CLI.
+		Weight::from_parts(33_573_973, 6811)
+			.saturating_add(Weight::from_parts(144_320, 0).saturating_mul(s.into()))
+			.saturating_add(Weight::from_parts(1_819, 0).saturating_mul(z.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Multisig::StoredMultisigs` (r:1 w:0)
+	/// Proof: `Multisig::StoredMultisigs` (`max_values`: None, `max_size`: Some(3291), added: 5766, mode: `MaxEncodedLen`)
+	/// Storage: `Multisig::Multisigs` (r:1 w:1)
+	/// Proof: `Multisig::Multisigs` (`max_values`: None, `max_size`: Some(3346), added: 5821, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[3, 100]`.
+	/// The range of component `z` is `[0, 10000]`.
+	fn as_stored_multi_approve(s: u32, z: u32, ) -> Weight {
+		// Estimated, not measured: to be regenerated with the benchmark CLI.
+		Weight::from_parts(16_599_909, 6811)
+			.saturating_add(Weight::from_parts(127_874, 0).saturating_mul(s.into()))
+			.saturating_add(Weight::from_parts(1_968, 0).saturating_mul(z.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Multisig::StoredMultisigs` (r:1 w:0)
+	/// Proof: `Multisig::StoredMultisigs` (`max_values`: None, `max_size`: Some(3291), added: 5766, mode: `MaxEncodedLen`)
+	/// Storage: `Multisig::Multisigs` (r:1 w:1)
+	/// Proof: `Multisig::Multisigs` (`max_values`: None, `max_size`: Some(3346), added: 5821, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `SafeMode::EnteredUntil` (r:1 w:0)
+	/// Proof: `SafeMode::EnteredUntil` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `TxPause::PausedCalls` (r:1 w:0)
+	/// Proof: `TxPause::PausedCalls` (`max_values`: None, `max_size`: Some(532), added: 3007, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[2, 100]`.
+	/// The range of component `z` is `[0, 10000]`.
+	fn as_stored_multi_complete(s: u32, z: u32, ) -> Weight {
+		// Estimated, not measured: to be regenerated with the benchmark CLI.
+		Weight::from_parts(38_914_868, 6811)
+			.saturating_add(Weight::from_parts(193_313, 0).saturating_mul(s.into()))
+			.saturating_add(Weight::from_parts(2_057, 0).saturating_mul(z.into()))
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `Multisig::StoredMultisigs` (r:1 w:0)
+	/// Proof: `Multisig::StoredMultisigs` (`max_values`: None, `max_size`: Some(3291), added: 5766, mode: `MaxEncodedLen`)
+	/// Storage: `Multisig::Multisigs` (r:1 w:1)
+	/// Proof: `Multisig::Multisigs` (`max_values`: None, `max_size`: Some(3346), added: 5821, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[2, 100]`.
+	fn approve_as_stored_multi_create(s: u32, ) -> Weight {
+		// Estimated, not measured: to be regenerated with the benchmark CLI.
+		Weight::from_parts(30_758_868, 6811)
+			.saturating_add(Weight::from_parts(139_226, 0).saturating_mul(s.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Multisig::StoredMultisigs` (r:1 w:0)
+	/// Proof: `Multisig::StoredMultisigs` (`max_values`: None, `max_size`: Some(3291), added: 5766, mode: `MaxEncodedLen`)
+	/// Storage: `Multisig::Multisigs` (r:1 w:1)
+	/// Proof: `Multisig::Multisigs` (`max_values`: None, `max_size`: Some(3346), added: 5821, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[2, 100]`.
+	fn approve_as_stored_multi_approve(s: u32, ) -> Weight {
+		// Estimated, not measured: to be regenerated with the benchmark CLI.
+		Weight::from_parts(15_763_168, 6811)
+			.saturating_add(Weight::from_parts(126_544, 0).saturating_mul(s.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Multisig::Multisigs` (r:1 w:1)
+	/// Proof: `Multisig::Multisigs` (`max_values`: None, `max_size`: Some(3346), added: 5821, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[2, 100]`.
+	fn cancel_as_stored_multi(s: u32, ) -> Weight {
+		// Estimated, not measured: to be regenerated with the benchmark CLI.
+		Weight::from_parts(28_623_566, 6811)
+			.saturating_add(Weight::from_parts(135_351, 0).saturating_mul(s.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Multisig::StoredMultisigs` (r:1 w:1)
+	/// Proof: `Multisig::StoredMultisigs` (`max_values`: None, `max_size`: Some(3291), added: 5766, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[2, 100]`.
+	fn set_members(s: u32, ) -> Weight {
+		// Estimated, not measured: to be regenerated with the benchmark CLI.
+		Weight::from_parts(40_631_468, 10972)
+			.saturating_add(Weight::from_parts(121_813, 0).saturating_mul(s.into()))
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: `Multisig::StoredMultisigs` (r:1 w:1)
+	/// Proof: `Multisig::StoredMultisigs` (`max_values`: None, `max_size`: Some(3291), added: 5766, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:2 w:2)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	fn destroy_stored() -> Weight {
+		// Estimated, not measured: to be regenerated with the benchmark CLI.
+		Weight::from_parts(45_631_468, 10972)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
 }
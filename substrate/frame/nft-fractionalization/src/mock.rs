@@ -20,7 +20,11 @@
 use super::*;
 use crate as pallet_nft_fractionalization;
 
-use frame::{deps::sp_runtime::MultiSignature, testing_prelude::*, traits::Verify};
+use frame::{
+	deps::sp_runtime::{MultiSignature, Permill},
+	testing_prelude::*,
+	traits::Verify,
+};
 use pallet_nfts::PalletFeatures;
 
 type Block = MockBlock<Test>;
@@ -80,6 +84,7 @@ impl pallet_assets::Config for Test {
 
 parameter_types! {
 	pub storage Features: PalletFeatures = PalletFeatures::all_enabled();
+	pub const MaxRoyalty: Permill = Permill::from_percent(50);
 }
 
 impl pallet_nfts::Config for Test {
@@ -103,6 +108,8 @@ impl pallet_nfts::Config for Test {
 	type MaxTips = ConstU32<10>;
 	type MaxDeadlineDuration = ConstU64<10000>;
 	type MaxAttributesPerCall = ConstU32<2>;
+	type MaxRoyaltyRecipients = ConstU32<3>;
+	type MaxRoyalty = MaxRoyalty;
 	type Features = Features;
 	type OffchainSignature = Signature;
 	type OffchainPublic = AccountPublic;
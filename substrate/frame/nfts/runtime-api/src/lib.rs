@@ -23,8 +23,10 @@ extern crate alloc;
 
 use alloc::vec::Vec;
 use codec::{Decode, Encode};
+use sp_arithmetic::Permill;
 
 sp_api::decl_runtime_apis! {
+	#[api_version(2)]
 	pub trait NftsApi<AccountId, CollectionId, ItemId>
 	where
 		AccountId: Encode + Decode,
@@ -55,5 +57,10 @@ sp_api::decl_runtime_apis! {
 		) -> Option<Vec<u8>>;
 
 		fn collection_attribute(collection: CollectionId, key: Vec<u8>) -> Option<Vec<u8>>;
+
+		/// The royalties charged on sales of an item, with the share of the price of each
+		/// recipient. Empty if the item has no royalties.
+		#[api_version(2)]
+		fn royalty(collection: CollectionId, item: ItemId) -> Vec<(AccountId, Permill)>;
 	}
 }
@@ -223,6 +223,16 @@ fn make_filled_vec(value: u16, length: usize) -> Vec<u8> {
 	vec
 }
 
+fn make_royalty_recipients<T: Config<I>, I: 'static>() -> RoyaltyRecipientsOf<T, I> {
+	let n = T::MaxRoyaltyRecipients::get();
+	let percentage = Permill::from_parts(T::MaxRoyalty::get().deconstruct() / n.max(1));
+	(0..n)
+		.map(|i| RoyaltyRecipient { account: account("recipient", i, SEED), percentage })
+		.collect::<Vec<_>>()
+		.try_into()
+		.unwrap()
+}
+
 benchmarks_instance_pallet! {
 	create {
 		let collection = T::Helper::collection(0);
@@ -878,5 +888,102 @@ benchmarks_instance_pallet! {
 		);
 	}
 
+	set_royalty {
+		let (collection, caller, _) = create_collection::<T, I>();
+		let (item, ..) = mint_item::<T, I>(0);
+		let recipients = make_royalty_recipients::<T, I>();
+	}: _(SystemOrigin::Signed(caller), collection, Some(item), recipients.clone())
+	verify {
+		assert_last_event::<T, I>(Event::RoyaltySet { collection, maybe_item: Some(item), recipients }.into());
+	}
+
+	clear_royalty {
+		let (collection, caller, _) = create_collection::<T, I>();
+		let (item, ..) = mint_item::<T, I>(0);
+		assert_ok!(Nfts::<T, I>::set_royalty(
+			SystemOrigin::Signed(caller.clone()).into(),
+			collection,
+			Some(item),
+			make_royalty_recipients::<T, I>(),
+		));
+	}: _(SystemOrigin::Signed(caller), collection, Some(item))
+	verify {
+		assert_last_event::<T, I>(Event::RoyaltyCleared { collection, maybe_item: Some(item) }.into());
+	}
+
+	offer_rental {
+		let (collection, caller, _) = create_collection::<T, I>();
+		let (item, ..) = mint_item::<T, I>(0);
+		let renter: T::AccountId = account("renter", 0, SEED);
+		let renter_lookup = T::Lookup::unlookup(renter.clone());
+		let duration = T::MaxDeadlineDuration::get();
+		let fee = ItemPrice::<T, I>::from(100u32);
+	}: _(SystemOrigin::Signed(caller.clone()), collection, item, renter_lookup, duration, Some(fee))
+	verify {
+		assert_last_event::<T, I>(Event::RentalOffered {
+			collection,
+			item,
+			owner: caller,
+			renter,
+			duration,
+			fee: Some(fee),
+		}.into());
+	}
+
+	cancel_rental_offer {
+		let (collection, caller, _) = create_collection::<T, I>();
+		let (item, ..) = mint_item::<T, I>(0);
+		let renter: T::AccountId = account("renter", 0, SEED);
+		let renter_lookup = T::Lookup::unlookup(renter.clone());
+		let duration = T::MaxDeadlineDuration::get();
+		let fee = ItemPrice::<T, I>::from(100u32);
+		let origin = SystemOrigin::Signed(caller.clone()).into();
+		Nfts::<T, I>::offer_rental(origin, collection, item, renter_lookup, duration, Some(fee))?;
+	}: _(SystemOrigin::Signed(caller), collection, item)
+	verify {
+		assert_last_event::<T, I>(Event::RentalOfferCancelled { collection, item }.into());
+	}
+
+	accept_rental {
+		let (collection, caller, _) = create_collection::<T, I>();
+		let (item, ..) = mint_item::<T, I>(0);
+		let renter: T::AccountId = account("renter", 0, SEED);
+		let renter_lookup = T::Lookup::unlookup(renter.clone());
+		let duration = T::MaxDeadlineDuration::get();
+		let fee = ItemPrice::<T, I>::from(100u32);
+		let origin = SystemOrigin::Signed(caller.clone()).into();
+		Nfts::<T, I>::offer_rental(origin, collection, item, renter_lookup, duration, Some(fee))?;
+		T::Currency::make_free_balance_be(&renter, DepositBalanceOf::<T, I>::max_value());
+		T::BlockNumberProvider::set_block_number(One::one());
+	}: _(SystemOrigin::Signed(renter.clone()), collection, item, duration, Some(fee))
+	verify {
+		assert_last_event::<T, I>(Event::RentalStarted {
+			collection,
+			item,
+			owner: caller,
+			user: renter,
+			fee: Some(fee),
+			expires: duration.saturating_add(One::one()),
+		}.into());
+	}
+
+	end_rental {
+		let (collection, caller, _) = create_collection::<T, I>();
+		let (item, ..) = mint_item::<T, I>(0);
+		let renter: T::AccountId = account("renter", 0, SEED);
+		let renter_lookup = T::Lookup::unlookup(renter.clone());
+		let duration: BlockNumberFor<T, I> = One::one();
+		let origin = SystemOrigin::Signed(caller.clone()).into();
+		Nfts::<T, I>::offer_rental(origin, collection, item, renter_lookup, duration, None)?;
+		T::BlockNumberProvider::set_block_number(One::one());
+		let origin = SystemOrigin::Signed(renter.clone()).into();
+		Nfts::<T, I>::accept_rental(origin, collection, item, duration, None)?;
+		// Anyone else than the user may only end an expired rental.
+		T::BlockNumberProvider::set_block_number(3u32.into());
+	}: _(SystemOrigin::Signed(caller), collection, item)
+	verify {
+		assert_last_event::<T, I>(Event::RentalEnded { collection, item, user: renter }.into());
+	}
+
 	impl_benchmark_test_suite!(Nfts, crate::mock::new_test_ext(), crate::mock::Test);
 }
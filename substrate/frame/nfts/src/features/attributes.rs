@@ -472,7 +472,10 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 			AttributeNamespace::Account(account_id) =>
 				if let Some(item) = maybe_item {
 					let approvals = ItemAttributesApprovalsOf::<T, I>::get(&collection, &item);
-					result = account_id == origin && approvals.contains(&origin)
+					// The user of a rented item has the same rights as an approved account.
+					result = account_id == origin &&
+						(approvals.contains(&origin) ||
+							Self::item_user(&collection, &item).as_ref() == Some(origin))
 				},
 			_ => (),
 		};
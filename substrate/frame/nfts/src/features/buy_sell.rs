@@ -23,7 +23,7 @@
 use crate::*;
 use frame_support::{
 	pallet_prelude::*,
-	traits::{Currency, ExistenceRequirement, ExistenceRequirement::KeepAlive},
+	traits::{Currency, ExistenceRequirement::KeepAlive},
 };
 
 impl<T: Config<I>, I: 'static> Pallet<T, I> {
@@ -98,6 +98,10 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 
 		if let Some(ref price) = price {
 			ItemPriceOf::<T, I>::insert(&collection, &item, (price, whitelisted_buyer.clone()));
+			match Self::royalty(&collection, &item) {
+				Some(royalty) => SaleRoyaltyOf::<T, I>::insert(&collection, &item, royalty),
+				None => SaleRoyaltyOf::<T, I>::remove(&collection, &item),
+			}
 			Self::deposit_event(Event::ItemPriceSet {
 				collection,
 				item,
@@ -106,6 +110,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 			});
 		} else {
 			ItemPriceOf::<T, I>::remove(&collection, &item);
+			SaleRoyaltyOf::<T, I>::remove(&collection, &item);
 			Self::deposit_event(Event::ItemPriceRemoved { collection, item });
 		}
 
@@ -125,6 +130,9 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 	/// - `item`: The identifier of the item to be bought.
 	/// - `buyer`: The account that attempts to buy the item.
 	/// - `bid_price`: The bid price offered by the buyer for the item.
+	///
+	/// The royalties of the item when its price was set, if any, are paid out of the price to
+	/// their recipients.
 	pub(crate) fn do_buy_item(
 		collection: T::CollectionId,
 		item: T::ItemId,
@@ -148,11 +156,13 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 			ensure!(only_buyer == buyer, Error::<T, I>::NoPermission);
 		}
 
-		T::Currency::transfer(
+		Self::do_pay_with_royalty(
+			collection,
+			item,
 			&buyer,
 			&details.owner,
 			price_info.0,
-			ExistenceRequirement::KeepAlive,
+			SaleRoyaltyOf::<T, I>::get(&collection, &item),
 		)?;
 
 		let old_owner = details.owner.clone();
@@ -205,6 +205,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 	/// This function returns a dispatch error in the following cases:
 	/// - If the collection ID is invalid ([`UnknownCollection`](crate::Error::UnknownCollection)).
 	/// - If the item is locked ([`ItemLocked`](crate::Error::ItemLocked)).
+	/// - If the item is rented out ([`ItemRented`](crate::Error::ItemRented)).
 	pub fn do_burn(
 		collection: T::CollectionId,
 		item: T::ItemId,
@@ -215,6 +216,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 			!Self::has_system_attribute(&collection, &item, PalletAttributes::TransferDisabled)?,
 			Error::<T, I>::ItemLocked
 		);
+		ensure!(Self::item_user(&collection, &item).is_none(), Error::<T, I>::ItemRented);
 		let item_config = Self::get_item_config(&collection, &item)?;
 		// NOTE: if item's settings are not empty (e.g. item's metadata is locked)
 		// then we keep the config record and don't remove it
@@ -260,8 +262,13 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		Item::<T, I>::remove(&collection, &item);
 		Account::<T, I>::remove((&owner, &collection, &item));
 		ItemPriceOf::<T, I>::remove(&collection, &item);
+		SaleRoyaltyOf::<T, I>::remove(&collection, &item);
 		PendingSwapOf::<T, I>::remove(&collection, &item);
+		SwapRoyaltyOf::<T, I>::remove(&collection, &item);
 		ItemAttributesApprovalsOf::<T, I>::remove(&collection, &item);
+		ItemRoyaltyOf::<T, I>::remove(&collection, &item);
+		PendingRentalOf::<T, I>::remove(&collection, &item);
+		ItemRentalOf::<T, I>::remove(&collection, &item);
 
 		if remove_config {
 			ItemConfigOf::<T, I>::remove(&collection, &item);
@@ -23,6 +23,8 @@ pub mod create_delete_collection;
 pub mod create_delete_item;
 pub mod lock;
 pub mod metadata;
+pub mod rentals;
 pub mod roles;
+pub mod royalties;
 pub mod settings;
 pub mod transfer;
@@ -0,0 +1,186 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module contains helper methods to rent items of the NFTs pallet out, granting their
+//! renters time-limited usage rights.
+//! The bitflag [`PalletFeature::Rentals`] needs to be set in [`Config::Features`] for NFTs
+//! to have the functionality defined in this module.
+
+use crate::*;
+use frame_support::{
+	pallet_prelude::*,
+	traits::{Currency, ExistenceRequirement::KeepAlive},
+};
+
+impl<T: Config<I>, I: 'static> Pallet<T, I> {
+	/// Offers an item for rental to a `renter`.
+	///
+	/// The `owner` must be the owner of the item, which must not be rented out. The `duration`
+	/// can't exceed [`Config::MaxDeadlineDuration`]. A new offer replaces the previous one.
+	///
+	/// - `owner`: The owner of the item.
+	/// - `collection`: The collection of the item.
+	/// - `item`: The item to be rented.
+	/// - `renter`: The account the item is offered to.
+	/// - `duration`: The number of blocks the rental lasts once accepted.
+	/// - `fee`: An optional fee the renter pays to the owner.
+	pub(crate) fn do_offer_rental(
+		owner: T::AccountId,
+		collection: T::CollectionId,
+		item: T::ItemId,
+		renter: T::AccountId,
+		duration: BlockNumberFor<T, I>,
+		fee: Option<ItemPrice<T, I>>,
+	) -> DispatchResult {
+		ensure!(
+			Self::is_pallet_feature_enabled(PalletFeature::Rentals),
+			Error::<T, I>::MethodDisabled
+		);
+		ensure!(
+			!duration.is_zero() && duration <= T::MaxDeadlineDuration::get(),
+			Error::<T, I>::WrongDuration
+		);
+
+		let details = Item::<T, I>::get(&collection, &item).ok_or(Error::<T, I>::UnknownItem)?;
+		ensure!(details.owner == owner, Error::<T, I>::NoPermission);
+		ensure!(renter != owner, Error::<T, I>::NoPermission);
+		ensure!(Self::item_user(&collection, &item).is_none(), Error::<T, I>::ItemRented);
+
+		PendingRentalOf::<T, I>::insert(
+			&collection,
+			&item,
+			PendingRental { renter: renter.clone(), duration, fee },
+		);
+
+		Self::deposit_event(Event::RentalOffered {
+			collection,
+			item,
+			owner,
+			renter,
+			duration,
+			fee,
+		});
+		Ok(())
+	}
+
+	/// Cancels the pending rental offer of an item.
+	///
+	/// - `owner`: The owner of the item.
+	/// - `collection`: The collection of the item.
+	/// - `item`: The item whose rental offer to cancel.
+	pub(crate) fn do_cancel_rental_offer(
+		owner: T::AccountId,
+		collection: T::CollectionId,
+		item: T::ItemId,
+	) -> DispatchResult {
+		let details = Item::<T, I>::get(&collection, &item).ok_or(Error::<T, I>::UnknownItem)?;
+		ensure!(details.owner == owner, Error::<T, I>::NoPermission);
+		PendingRentalOf::<T, I>::take(&collection, &item).ok_or(Error::<T, I>::UnknownRental)?;
+
+		Self::deposit_event(Event::RentalOfferCancelled { collection, item });
+		Ok(())
+	}
+
+	/// Accepts the rental offer of an item.
+	///
+	/// The `renter` pays the fee of the offer, if any, to the owner of the item and becomes the
+	/// user of the item for the duration of the offer.
+	///
+	/// - `renter`: The account the item was offered to.
+	/// - `collection`: The collection of the item.
+	/// - `item`: The item to rent.
+	/// - `witness_duration`: The duration of the offer.
+	/// - `witness_fee`: The fee of the offer.
+	pub(crate) fn do_accept_rental(
+		renter: T::AccountId,
+		collection: T::CollectionId,
+		item: T::ItemId,
+		witness_duration: BlockNumberFor<T, I>,
+		witness_fee: Option<ItemPrice<T, I>>,
+	) -> DispatchResult {
+		ensure!(
+			Self::is_pallet_feature_enabled(PalletFeature::Rentals),
+			Error::<T, I>::MethodDisabled
+		);
+
+		let details = Item::<T, I>::get(&collection, &item).ok_or(Error::<T, I>::UnknownItem)?;
+		let offer =
+			PendingRentalOf::<T, I>::get(&collection, &item).ok_or(Error::<T, I>::UnknownRental)?;
+		ensure!(offer.renter == renter, Error::<T, I>::NoPermission);
+		ensure!(
+			offer.duration == witness_duration && offer.fee == witness_fee,
+			Error::<T, I>::UnknownRental
+		);
+
+		if let Some(fee) = offer.fee {
+			T::Currency::transfer(&renter, &details.owner, fee, KeepAlive)?;
+		}
+
+		let now = T::BlockNumberProvider::current_block_number();
+		let expires = offer.duration.saturating_add(now);
+		PendingRentalOf::<T, I>::remove(&collection, &item);
+		ItemRentalOf::<T, I>::insert(
+			&collection,
+			&item,
+			ItemRental { user: renter.clone(), expires },
+		);
+
+		Self::deposit_event(Event::RentalStarted {
+			collection,
+			item,
+			owner: details.owner,
+			user: renter,
+			fee: offer.fee,
+			expires,
+		});
+		Ok(())
+	}
+
+	/// Ends the rental of an item.
+	///
+	/// The user of the item may end the rental at any time, anyone else only once it has
+	/// expired.
+	///
+	/// - `caller`: The account ending the rental.
+	/// - `collection`: The collection of the item.
+	/// - `item`: The item whose rental to end.
+	pub(crate) fn do_end_rental(
+		caller: T::AccountId,
+		collection: T::CollectionId,
+		item: T::ItemId,
+	) -> DispatchResult {
+		let rental =
+			ItemRentalOf::<T, I>::get(&collection, &item).ok_or(Error::<T, I>::UnknownRental)?;
+		if rental.user != caller {
+			let now = T::BlockNumberProvider::current_block_number();
+			ensure!(rental.expires < now, Error::<T, I>::RentalNotExpired);
+		}
+		ItemRentalOf::<T, I>::remove(&collection, &item);
+
+		Self::deposit_event(Event::RentalEnded { collection, item, user: rental.user });
+		Ok(())
+	}
+
+	/// Returns the user of an item if it is rented out, i.e. the holder of its usage rights.
+	///
+	/// A rental which has expired no longer grants any rights, even before it is ended.
+	pub fn item_user(collection: &T::CollectionId, item: &T::ItemId) -> Option<T::AccountId> {
+		let rental = ItemRentalOf::<T, I>::get(collection, item)?;
+		let now = T::BlockNumberProvider::current_block_number();
+		(now <= rental.expires).then_some(rental.user)
+	}
+}
@@ -0,0 +1,183 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This module contains helper methods to configure the royalties of collections and items, and
+//! to pay them on sales of items.
+
+use crate::*;
+use frame_support::{
+	pallet_prelude::*,
+	traits::{Currency, ExistenceRequirement::KeepAlive},
+};
+
+impl<T: Config<I>, I: 'static> Pallet<T, I> {
+	/// Sets the royalties of a collection or of one of its items.
+	///
+	/// - `maybe_check_origin`: An optional account ID that is allowed to set the royalties. If
+	///   `None`, it's considered the root account.
+	/// - `collection`: The ID of the collection whose royalties to set.
+	/// - `maybe_item`: The ID of the item whose royalties to set, or `None` for the collection.
+	/// - `recipients`: The accounts receiving the royalties, each with its share of the price.
+	///
+	/// Emits `RoyaltySet` event upon successful setting of the royalties.
+	/// Returns `Ok(())` on success, or one of the following dispatch errors:
+	/// - `NoPermission`: The caller is not the Admin of the collection.
+	/// - `LockedCollectionRoyalties`: The royalties of the collection are locked.
+	/// - `UnknownItem`: The specified item does not exist.
+	/// - `InvalidRoyalty`: There are no recipients or their shares add up to more than
+	///   `MaxRoyalty`.
+	pub(crate) fn do_set_royalty(
+		maybe_check_origin: Option<T::AccountId>,
+		collection: T::CollectionId,
+		maybe_item: Option<T::ItemId>,
+		recipients: RoyaltyRecipientsOf<T, I>,
+	) -> DispatchResult {
+		Self::ensure_royalty_mutable(&maybe_check_origin, &collection, maybe_item.as_ref())?;
+
+		let total = recipients
+			.iter()
+			.map(|recipient| recipient.percentage.deconstruct() as u64)
+			.sum::<u64>();
+		ensure!(
+			!recipients.is_empty() && total <= T::MaxRoyalty::get().deconstruct() as u64,
+			Error::<T, I>::InvalidRoyalty
+		);
+
+		match maybe_item {
+			Some(item) => ItemRoyaltyOf::<T, I>::insert(&collection, &item, &recipients),
+			None => CollectionRoyaltyOf::<T, I>::insert(&collection, &recipients),
+		}
+
+		Self::deposit_event(Event::RoyaltySet { collection, maybe_item, recipients });
+		Ok(())
+	}
+
+	/// Clears the royalties of a collection or of one of its items.
+	///
+	/// - `maybe_check_origin`: An optional account ID that is allowed to clear the royalties. If
+	///   `None`, it's considered the root account.
+	/// - `collection`: The ID of the collection whose royalties to clear.
+	/// - `maybe_item`: The ID of the item whose royalties to clear, or `None` for the collection.
+	///
+	/// Emits `RoyaltyCleared` event upon successful clearing of the royalties.
+	/// Returns `Ok(())` on success, or one of the following dispatch errors:
+	/// - `NoPermission`: The caller is not the Admin of the collection.
+	/// - `LockedCollectionRoyalties`: The royalties of the collection are locked.
+	/// - `UnknownItem`: The specified item does not exist.
+	/// - `RoyaltyNotFound`: No royalties were set for the collection or item.
+	pub(crate) fn do_clear_royalty(
+		maybe_check_origin: Option<T::AccountId>,
+		collection: T::CollectionId,
+		maybe_item: Option<T::ItemId>,
+	) -> DispatchResult {
+		Self::ensure_royalty_mutable(&maybe_check_origin, &collection, maybe_item.as_ref())?;
+
+		let removed = match maybe_item {
+			Some(item) => ItemRoyaltyOf::<T, I>::take(&collection, &item),
+			None => CollectionRoyaltyOf::<T, I>::take(&collection),
+		};
+		ensure!(removed.is_some(), Error::<T, I>::RoyaltyNotFound);
+
+		Self::deposit_event(Event::RoyaltyCleared { collection, maybe_item });
+		Ok(())
+	}
+
+	/// Returns the royalties charged on sales of an item, which are those of the item if set, or
+	/// those of its collection otherwise.
+	pub fn royalty(
+		collection: &T::CollectionId,
+		item: &T::ItemId,
+	) -> Option<RoyaltyRecipientsOf<T, I>> {
+		ItemRoyaltyOf::<T, I>::get(collection, item)
+			.or_else(|| CollectionRoyaltyOf::<T, I>::get(collection))
+	}
+
+	/// Returns the royalties charged on a swap whose price pays for `maybe_item` of `collection`,
+	/// which are those of the collection if the item is not known yet.
+	pub(crate) fn swap_royalty(
+		collection: &T::CollectionId,
+		maybe_item: Option<&T::ItemId>,
+	) -> Option<RoyaltyRecipientsOf<T, I>> {
+		match maybe_item {
+			Some(item) => Self::royalty(collection, item),
+			None => CollectionRoyaltyOf::<T, I>::get(collection),
+		}
+	}
+
+	/// Pays the `price` of an item from the `payer` to the `payee`, less the `royalty` which is
+	/// paid to its recipients.
+	///
+	/// A share below the existential deposit can't create the account of its recipient, so it is
+	/// left to the `payee` rather than failing the sale.
+	///
+	/// Emits a `RoyaltyPaid` event for each royalty paid.
+	pub(crate) fn do_pay_with_royalty(
+		collection: T::CollectionId,
+		item: T::ItemId,
+		payer: &T::AccountId,
+		payee: &T::AccountId,
+		price: ItemPrice<T, I>,
+		royalty: Option<RoyaltyRecipientsOf<T, I>>,
+	) -> DispatchResult {
+		let mut remaining = price;
+		for RoyaltyRecipient { account, percentage } in royalty.unwrap_or_default() {
+			let amount = percentage.mul_floor(price);
+			if amount.is_zero() ||
+				(amount < T::Currency::minimum_balance() &&
+					T::Currency::total_balance(&account).is_zero())
+			{
+				continue
+			}
+			T::Currency::transfer(payer, &account, amount, KeepAlive)?;
+			remaining.saturating_reduce(amount);
+			Self::deposit_event(Event::RoyaltyPaid {
+				collection,
+				item,
+				payer: payer.clone(),
+				receiver: account,
+				amount,
+			});
+		}
+		T::Currency::transfer(payer, payee, remaining, KeepAlive)
+	}
+
+	/// Ensures the royalties of a collection, or of one of its items, can be modified.
+	fn ensure_royalty_mutable(
+		maybe_check_origin: &Option<T::AccountId>,
+		collection: &T::CollectionId,
+		maybe_item: Option<&T::ItemId>,
+	) -> DispatchResult {
+		if let Some(check_origin) = maybe_check_origin {
+			ensure!(
+				Self::has_role(collection, check_origin, CollectionRole::Admin),
+				Error::<T, I>::NoPermission
+			);
+		}
+
+		let is_root = maybe_check_origin.is_none();
+		let collection_config = Self::get_collection_config(collection)?;
+		ensure!(
+			is_root || collection_config.is_setting_enabled(CollectionSetting::UnlockedRoyalties),
+			Error::<T, I>::LockedCollectionRoyalties
+		);
+
+		if let Some(item) = maybe_item {
+			ensure!(Item::<T, I>::contains_key(collection, item), Error::<T, I>::UnknownItem);
+		}
+		Ok(())
+	}
+}
@@ -41,6 +41,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 	/// - If the item ID is invalid ([`UnknownItem`](crate::Error::UnknownItem)).
 	/// - If the item is locked or transferring it is disabled
 	///   ([`ItemLocked`](crate::Error::ItemLocked)).
+	/// - If the item is rented out ([`ItemRented`](crate::Error::ItemRented)).
 	/// - If the collection or item is non-transferable
 	///   ([`ItemsNonTransferable`](crate::Error::ItemsNonTransferable)).
 	pub fn do_transfer(
@@ -65,6 +66,9 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 			Error::<T, I>::ItemLocked
 		);
 
+		// Ensure the item is not rented out.
+		ensure!(Self::item_user(&collection, &item).is_none(), Error::<T, I>::ItemRented);
+
 		// Retrieve collection config and check if items are transferable.
 		let collection_config = Self::get_collection_config(&collection)?;
 		ensure!(
@@ -100,7 +104,11 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		// Update item details.
 		Item::<T, I>::insert(&collection, &item, &details);
 		ItemPriceOf::<T, I>::remove(&collection, &item);
+		SaleRoyaltyOf::<T, I>::remove(&collection, &item);
 		PendingSwapOf::<T, I>::remove(&collection, &item);
+		SwapRoyaltyOf::<T, I>::remove(&collection, &item);
+		PendingRentalOf::<T, I>::remove(&collection, &item);
+		ItemRentalOf::<T, I>::remove(&collection, &item);
 
 		// Emit `Transferred` event.
 		Self::deposit_event(Event::Transferred {
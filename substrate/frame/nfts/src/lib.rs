@@ -59,7 +59,7 @@ use frame_support::traits::{
 use frame_system::Config as SystemConfig;
 use sp_runtime::{
 	traits::{BlockNumberProvider, IdentifyAccount, Saturating, StaticLookup, Verify, Zero},
-	RuntimeDebug,
+	Permill, RuntimeDebug,
 };
 
 pub use pallet::*;
@@ -79,7 +79,7 @@ pub mod pallet {
 	use frame_system::{ensure_signed, pallet_prelude::OriginFor};
 
 	/// The in-code storage version.
-	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
 
 	#[pallet::pallet]
 	#[pallet::storage_version(STORAGE_VERSION)]
@@ -217,6 +217,14 @@ pub mod pallet {
 		#[pallet::constant]
 		type MaxAttributesPerCall: Get<u32>;
 
+		/// The max number of royalty recipients of a collection or an item.
+		#[pallet::constant]
+		type MaxRoyaltyRecipients: Get<u32>;
+
+		/// The max share of the price that the royalties of a collection or an item may take.
+		#[pallet::constant]
+		type MaxRoyalty: Get<Permill>;
+
 		/// Disables some of pallet's features.
 		#[pallet::constant]
 		type Features: Get<PalletFeatures>;
@@ -414,6 +422,72 @@ pub mod pallet {
 		OptionQuery,
 	>;
 
+	/// Handles all the pending rental offers.
+	#[pallet::storage]
+	pub type PendingRentalOf<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::CollectionId,
+		Blake2_128Concat,
+		T::ItemId,
+		PendingRentalFor<T, I>,
+		OptionQuery,
+	>;
+
+	/// The rentals of items, granting their users the usage rights of the items until expiry.
+	#[pallet::storage]
+	pub type ItemRentalOf<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::CollectionId,
+		Blake2_128Concat,
+		T::ItemId,
+		ItemRentalFor<T, I>,
+		OptionQuery,
+	>;
+
+	/// Royalties charged on sales of the items of a collection.
+	#[pallet::storage]
+	pub type CollectionRoyaltyOf<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, T::CollectionId, RoyaltyRecipientsOf<T, I>, OptionQuery>;
+
+	/// Royalties charged on sales of an item, taking precedence over those of its collection.
+	#[pallet::storage]
+	pub type ItemRoyaltyOf<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::CollectionId,
+		Blake2_128Concat,
+		T::ItemId,
+		RoyaltyRecipientsOf<T, I>,
+		OptionQuery,
+	>;
+
+	/// Royalties of an item when its price was set, which are the ones charged when it is bought.
+	#[pallet::storage]
+	pub type SaleRoyaltyOf<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::CollectionId,
+		Blake2_128Concat,
+		T::ItemId,
+		RoyaltyRecipientsOf<T, I>,
+		OptionQuery,
+	>;
+
+	/// Royalties of the item paid for by the price of a swap when it was created, which are the
+	/// ones charged when it is claimed. Keyed by the offered item.
+	#[pallet::storage]
+	pub type SwapRoyaltyOf<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::CollectionId,
+		Blake2_128Concat,
+		T::ItemId,
+		RoyaltyRecipientsOf<T, I>,
+		OptionQuery,
+	>;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config<I>, I: 'static = ()> {
@@ -594,6 +668,44 @@ pub mod pallet {
 			attribute: PalletAttributes<T::CollectionId>,
 			value: BoundedVec<u8, T::ValueLimit>,
 		},
+		/// Royalties have been set for a `collection` or an `item`.
+		RoyaltySet {
+			collection: T::CollectionId,
+			maybe_item: Option<T::ItemId>,
+			recipients: RoyaltyRecipientsOf<T, I>,
+		},
+		/// Royalties have been cleared for a `collection` or an `item`.
+		RoyaltyCleared { collection: T::CollectionId, maybe_item: Option<T::ItemId> },
+		/// A royalty was paid on the sale of an item.
+		RoyaltyPaid {
+			collection: T::CollectionId,
+			item: T::ItemId,
+			payer: T::AccountId,
+			receiver: T::AccountId,
+			amount: ItemPrice<T, I>,
+		},
+		/// An `item` was offered for rental to a `renter`.
+		RentalOffered {
+			collection: T::CollectionId,
+			item: T::ItemId,
+			owner: T::AccountId,
+			renter: T::AccountId,
+			duration: BlockNumberFor<T, I>,
+			fee: Option<ItemPrice<T, I>>,
+		},
+		/// The rental offer of an `item` was cancelled.
+		RentalOfferCancelled { collection: T::CollectionId, item: T::ItemId },
+		/// An `item` was rented, granting its usage rights to the `user` until `expires`.
+		RentalStarted {
+			collection: T::CollectionId,
+			item: T::ItemId,
+			owner: T::AccountId,
+			user: T::AccountId,
+			fee: Option<ItemPrice<T, I>>,
+			expires: BlockNumberFor<T, I>,
+		},
+		/// The rental of an `item` ended.
+		RentalEnded { collection: T::CollectionId, item: T::ItemId, user: T::AccountId },
 	}
 
 	#[pallet::error]
@@ -688,6 +800,18 @@ pub mod pallet {
 		CollectionNotEmpty,
 		/// The witness data should be provided.
 		WitnessRequired,
+		/// The collection's royalties are locked.
+		LockedCollectionRoyalties,
+		/// The royalties have no recipients or add up to more than `MaxRoyalty`.
+		InvalidRoyalty,
+		/// The royalties of the collection or item were not found.
+		RoyaltyNotFound,
+		/// The item is rented out.
+		ItemRented,
+		/// The item has no pending rental offer or rental.
+		UnknownRental,
+		/// The rental has not expired yet.
+		RentalNotExpired,
 	}
 
 	#[pallet::call]
@@ -1737,7 +1861,8 @@ pub mod pallet {
 		/// Emits `ItemPriceSet` on success if the price is not `None`.
 		/// Emits `ItemPriceRemoved` on success if the price is `None`.
 		#[pallet::call_index(31)]
-		#[pallet::weight(T::WeightInfo::set_price())]
+		#[pallet::weight(T::WeightInfo::set_price()
+			.saturating_add(T::DbWeight::get().reads_writes(2, 1)))]
 		pub fn set_price(
 			origin: OriginFor<T>,
 			collection: T::CollectionId,
@@ -1759,8 +1884,11 @@ pub mod pallet {
 		/// - `bid_price`: The price the sender is willing to pay.
 		///
 		/// Emits `ItemBought` on success.
+		/// Emits `RoyaltyPaid` for each royalty paid out of the price.
 		#[pallet::call_index(32)]
-		#[pallet::weight(T::WeightInfo::buy_item())]
+		#[pallet::weight(T::WeightInfo::buy_item()
+			.saturating_add(T::WeightInfo::pay_tips(T::MaxRoyaltyRecipients::get()))
+			.saturating_add(T::DbWeight::get().reads_writes(1, 2)))]
 		pub fn buy_item(
 			origin: OriginFor<T>,
 			collection: T::CollectionId,
@@ -1791,7 +1919,9 @@ pub mod pallet {
 		/// Register a new atomic swap, declaring an intention to send an `item` in exchange for
 		/// `desired_item` from origin to target on the current blockchain.
 		/// The target can execute the swap during the specified `duration` of blocks (if set).
-		/// Additionally, the price could be set for the desired `item`.
+		/// Additionally, the price could be set for the desired `item`. The royalties charged on
+		/// the price are those of the item it pays for when the swap is created, or those of the
+		/// desired collection if no desired item is specified and the price is sent.
 		///
 		/// Origin must be Signed and must be an owner of the `item`.
 		///
@@ -1805,7 +1935,8 @@ pub mod pallet {
 		///
 		/// Emits `SwapCreated` on success.
 		#[pallet::call_index(34)]
-		#[pallet::weight(T::WeightInfo::create_swap())]
+		#[pallet::weight(T::WeightInfo::create_swap()
+			.saturating_add(T::DbWeight::get().reads_writes(2, 1)))]
 		pub fn create_swap(
 			origin: OriginFor<T>,
 			offered_collection: T::CollectionId,
@@ -1859,8 +1990,11 @@ pub mod pallet {
 		/// - `witness_price`: A price that was previously agreed on.
 		///
 		/// Emits `SwapClaimed` on success.
+		/// Emits `RoyaltyPaid` for each royalty paid out of the price.
 		#[pallet::call_index(36)]
-		#[pallet::weight(T::WeightInfo::claim_swap())]
+		#[pallet::weight(T::WeightInfo::claim_swap()
+			.saturating_add(T::WeightInfo::pay_tips(T::MaxRoyaltyRecipients::get()))
+			.saturating_add(T::DbWeight::get().reads_writes(1, 4)))]
 		pub fn claim_swap(
 			origin: OriginFor<T>,
 			send_collection: T::CollectionId,
@@ -1931,6 +2065,156 @@ pub mod pallet {
 			Self::validate_signature(&Encode::encode(&data), &signature, &signer)?;
 			Self::do_set_attributes_pre_signed(origin, data, signer)
 		}
+
+		/// Set the royalties charged on sales of the items of a collection, or of a single item.
+		///
+		/// Origin must be either `ForceOrigin` or `Signed` and the sender should be the Admin of
+		/// the `collection`.
+		///
+		/// The royalties are paid out of the price whenever an item is sold with `buy_item` or
+		/// traded for a price with `claim_swap`. The royalties of an item take precedence over
+		/// those of its collection. A sale or a swap is charged the royalties in place when the
+		/// price was set or the swap was created, so changing them doesn't affect pending ones.
+		///
+		/// - `collection`: The identifier of the collection whose royalties to set.
+		/// - `maybe_item`: The identifier of the item whose royalties to set, or `None` to set the
+		///   royalties of the collection.
+		/// - `recipients`: The accounts receiving the royalties, each with its share of the price.
+		///   The shares must not add up to more than `MaxRoyalty`.
+		///
+		/// Emits `RoyaltySet`.
+		///
+		/// Weight: `O(1)`
+		#[pallet::call_index(39)]
+		#[pallet::weight(T::WeightInfo::set_royalty())]
+		pub fn set_royalty(
+			origin: OriginFor<T>,
+			collection: T::CollectionId,
+			maybe_item: Option<T::ItemId>,
+			recipients: RoyaltyRecipientsOf<T, I>,
+		) -> DispatchResult {
+			let maybe_check_origin = T::ForceOrigin::try_origin(origin)
+				.map(|_| None)
+				.or_else(|origin| ensure_signed(origin).map(Some).map_err(DispatchError::from))?;
+			Self::do_set_royalty(maybe_check_origin, collection, maybe_item, recipients)
+		}
+
+		/// Clear the royalties of a collection or of a single item.
+		///
+		/// Origin must be either `ForceOrigin` or `Signed` and the sender should be the Admin of
+		/// the `collection`.
+		///
+		/// - `collection`: The identifier of the collection whose royalties to clear.
+		/// - `maybe_item`: The identifier of the item whose royalties to clear, or `None` to clear
+		///   the royalties of the collection.
+		///
+		/// Emits `RoyaltyCleared`.
+		///
+		/// Weight: `O(1)`
+		#[pallet::call_index(40)]
+		#[pallet::weight(T::WeightInfo::clear_royalty())]
+		pub fn clear_royalty(
+			origin: OriginFor<T>,
+			collection: T::CollectionId,
+			maybe_item: Option<T::ItemId>,
+		) -> DispatchResult {
+			let maybe_check_origin = T::ForceOrigin::try_origin(origin)
+				.map(|_| None)
+				.or_else(|origin| ensure_signed(origin).map(Some).map_err(DispatchError::from))?;
+			Self::do_clear_royalty(maybe_check_origin, collection, maybe_item)
+		}
+
+		/// Offer an item for rental to a `renter`.
+		///
+		/// Origin must be Signed and must be the owner of the `item`.
+		///
+		/// Once the renter accepts the offer, it becomes the user of the item for `duration`
+		/// blocks, during which the item can't be transferred and the renter may set attributes
+		/// of the item in its own `Account` namespace. A new offer replaces the previous one.
+		///
+		/// - `collection`: The collection of the item.
+		/// - `item`: The item to be rented.
+		/// - `renter`: The account the item is offered to.
+		/// - `duration`: The number of blocks the rental lasts once accepted.
+		/// - `fee`: An optional fee the renter pays to the owner when accepting the offer.
+		///
+		/// Emits `RentalOffered` on success.
+		#[pallet::call_index(41)]
+		#[pallet::weight(T::WeightInfo::offer_rental())]
+		pub fn offer_rental(
+			origin: OriginFor<T>,
+			collection: T::CollectionId,
+			item: T::ItemId,
+			renter: AccountIdLookupOf<T>,
+			duration: BlockNumberFor<T, I>,
+			fee: Option<ItemPrice<T, I>>,
+		) -> DispatchResult {
+			let origin = ensure_signed(origin)?;
+			let renter = T::Lookup::lookup(renter)?;
+			Self::do_offer_rental(origin, collection, item, renter, duration, fee)
+		}
+
+		/// Cancel the pending rental offer of an item.
+		///
+		/// Origin must be Signed and must be the owner of the `item`.
+		///
+		/// - `collection`: The collection of the item.
+		/// - `item`: The item whose rental offer to cancel.
+		///
+		/// Emits `RentalOfferCancelled` on success.
+		#[pallet::call_index(42)]
+		#[pallet::weight(T::WeightInfo::cancel_rental_offer())]
+		pub fn cancel_rental_offer(
+			origin: OriginFor<T>,
+			collection: T::CollectionId,
+			item: T::ItemId,
+		) -> DispatchResult {
+			let origin = ensure_signed(origin)?;
+			Self::do_cancel_rental_offer(origin, collection, item)
+		}
+
+		/// Accept the rental offer of an item, paying its fee if any.
+		///
+		/// Origin must be Signed and must be the renter of the offer.
+		///
+		/// - `collection`: The collection of the item.
+		/// - `item`: The item to rent.
+		/// - `witness_duration`: The duration of the offer, to guard against changes of the offer.
+		/// - `witness_fee`: The fee of the offer, to guard against changes of the offer.
+		///
+		/// Emits `RentalStarted` on success.
+		#[pallet::call_index(43)]
+		#[pallet::weight(T::WeightInfo::accept_rental())]
+		pub fn accept_rental(
+			origin: OriginFor<T>,
+			collection: T::CollectionId,
+			item: T::ItemId,
+			witness_duration: BlockNumberFor<T, I>,
+			witness_fee: Option<ItemPrice<T, I>>,
+		) -> DispatchResult {
+			let origin = ensure_signed(origin)?;
+			Self::do_accept_rental(origin, collection, item, witness_duration, witness_fee)
+		}
+
+		/// End the rental of an item.
+		///
+		/// Origin must be Signed. The user of the item may end the rental at any time, anyone
+		/// else only once it has expired.
+		///
+		/// - `collection`: The collection of the item.
+		/// - `item`: The item whose rental to end.
+		///
+		/// Emits `RentalEnded` on success.
+		#[pallet::call_index(44)]
+		#[pallet::weight(T::WeightInfo::end_rental())]
+		pub fn end_rental(
+			origin: OriginFor<T>,
+			collection: T::CollectionId,
+			item: T::ItemId,
+		) -> DispatchResult {
+			let origin = ensure_signed(origin)?;
+			Self::do_end_rental(origin, collection, item)
+		}
 	}
 }
 
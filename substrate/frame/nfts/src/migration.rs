@@ -64,7 +64,7 @@ pub mod v1 {
 				on_chain_version
 			);
 
-			if on_chain_version == 0 && in_code_version == 1 {
+			if on_chain_version == 0 && in_code_version >= 1 {
 				let mut translated = 0u64;
 				let mut configs_iterated = 0u64;
 				Collection::<T>::translate::<
@@ -77,13 +77,12 @@ pub mod v1 {
 					Some(old_value.migrate_to_v1(item_configs))
 				});
 
-				in_code_version.put::<Pallet<T>>();
+				StorageVersion::new(1).put::<Pallet<T>>();
 
 				log::info!(
 					target: LOG_TARGET,
-					"Upgraded {} records, storage to version {:?}",
+					"Upgraded {} records, storage to version 1",
 					translated,
-					in_code_version
 				);
 				T::DbWeight::get().reads_writes(translated + configs_iterated + 1, translated + 1)
 			} else {
@@ -118,3 +117,82 @@ pub mod v1 {
 		}
 	}
 }
+
+pub mod v2 {
+	use frame_support::{pallet_prelude::*, weights::Weight};
+
+	use super::*;
+
+	/// A migration utility to update the storage version from v1 to v2 for the pallet.
+	///
+	/// The royalties of the collections created before v2 are locked: their items were acquired
+	/// without royalties, which only the force origin can set from then on.
+	pub struct MigrateToV2<T>(core::marker::PhantomData<T>);
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV2<T> {
+		fn on_runtime_upgrade() -> Weight {
+			let in_code_version = Pallet::<T>::in_code_storage_version();
+			let on_chain_version = Pallet::<T>::on_chain_storage_version();
+
+			log::info!(
+				target: LOG_TARGET,
+				"Running migration with in-code storage version {:?} / onchain {:?}",
+				in_code_version,
+				on_chain_version
+			);
+
+			if on_chain_version == 1 && in_code_version >= 2 {
+				let mut translated = 0u64;
+				CollectionConfigOf::<T>::translate_values::<CollectionConfigFor<T>, _>(
+					|mut config| {
+						config.disable_setting(CollectionSetting::UnlockedRoyalties);
+						translated.saturating_inc();
+						Some(config)
+					},
+				);
+
+				StorageVersion::new(2).put::<Pallet<T>>();
+
+				log::info!(
+					target: LOG_TARGET,
+					"Upgraded {} records, storage to version 2",
+					translated,
+				);
+				T::DbWeight::get().reads_writes(translated + 1, translated + 1)
+			} else {
+				log::info!(
+					target: LOG_TARGET,
+					"Migration did not execute. This probably should be removed"
+				);
+				T::DbWeight::get().reads(1)
+			}
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
+			let prev_count = CollectionConfigOf::<T>::iter().count();
+			Ok((prev_count as u32).encode())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(prev_count: Vec<u8>) -> Result<(), TryRuntimeError> {
+			let prev_count: u32 = Decode::decode(&mut prev_count.as_slice()).expect(
+				"the state parameter should be something that was generated by pre_upgrade",
+			);
+			let post_count = CollectionConfigOf::<T>::iter().count() as u32;
+			ensure!(
+				prev_count == post_count,
+				"the records count before and after the migration should be the same"
+			);
+			ensure!(
+				CollectionConfigOf::<T>::iter_values().all(|config| {
+					!config.is_setting_enabled(CollectionSetting::UnlockedRoyalties)
+				}),
+				"the royalties of all the collections should be locked"
+			);
+
+			ensure!(Pallet::<T>::on_chain_storage_version() >= 2, "wrong storage version");
+
+			Ok(())
+		}
+	}
+}
@@ -55,11 +55,14 @@ impl frame_system::Config for Test {
 
 #[derive_impl(pallet_balances::config_preludes::TestDefaultConfig)]
 impl pallet_balances::Config for Test {
+	type ExistentialDeposit = ExistentialDeposit;
 	type AccountStore = System;
 }
 
 parameter_types! {
+	pub storage ExistentialDeposit: u64 = 1;
 	pub storage Features: PalletFeatures = PalletFeatures::all_enabled();
+	pub const MaxRoyalty: Permill = Permill::from_percent(50);
 }
 
 impl Config for Test {
@@ -83,6 +86,8 @@ impl Config for Test {
 	type MaxTips = ConstU32<10>;
 	type MaxDeadlineDuration = ConstU64<10000>;
 	type MaxAttributesPerCall = ConstU32<2>;
+	type MaxRoyaltyRecipients = ConstU32<3>;
+	type MaxRoyalty = MaxRoyalty;
 	type Features = Features;
 	/// Off-chain = signature On-chain - therefore no conversion needed.
 	/// It needs to be From<MultiSignature> for benchmarking.
@@ -23,7 +23,7 @@ use frame_support::{
 	assert_noop, assert_ok,
 	traits::{
 		tokens::nonfungibles_v2::{Create, Destroy, Inspect, Mutate},
-		Currency, Get,
+		Currency, Get, GetStorageVersion, OnRuntimeUpgrade, StorageVersion,
 	},
 };
 use pallet_balances::Error as BalancesError;
@@ -3875,3 +3875,847 @@ fn clear_collection_metadata_works() {
 		assert_eq!(Balances::reserved_balance(&account(1)), 10);
 	});
 }
+
+#[test]
+fn set_royalty_should_work() {
+	new_test_ext().execute_with(|| {
+		let user_1 = account(1);
+		let user_2 = account(2);
+		let collection_id = 0;
+		let item_id = 1;
+		let recipients: RoyaltyRecipientsOf<Test> = bvec![
+			RoyaltyRecipient { account: user_1.clone(), percentage: Permill::from_percent(5) },
+			RoyaltyRecipient { account: user_2.clone(), percentage: Permill::from_percent(2) },
+		];
+
+		assert_ok!(Nfts::force_create(
+			RuntimeOrigin::root(),
+			user_1.clone(),
+			default_collection_config()
+		));
+
+		assert_noop!(
+			Nfts::set_royalty(
+				RuntimeOrigin::signed(user_2.clone()),
+				collection_id,
+				None,
+				recipients.clone(),
+			),
+			Error::<Test>::NoPermission
+		);
+		assert_noop!(
+			Nfts::set_royalty(
+				RuntimeOrigin::signed(user_1.clone()),
+				collection_id,
+				Some(item_id),
+				recipients.clone(),
+			),
+			Error::<Test>::UnknownItem
+		);
+		assert_noop!(
+			Nfts::set_royalty(RuntimeOrigin::signed(user_1.clone()), collection_id, None, bvec![],),
+			Error::<Test>::InvalidRoyalty
+		);
+		assert_noop!(
+			Nfts::set_royalty(
+				RuntimeOrigin::signed(user_1.clone()),
+				collection_id,
+				None,
+				bvec![
+					RoyaltyRecipient { account: user_1.clone(), percentage: Permill::one() },
+					RoyaltyRecipient {
+						account: user_2.clone(),
+						percentage: Permill::from_parts(1)
+					},
+				],
+			),
+			Error::<Test>::InvalidRoyalty
+		);
+		// the royalties can't exceed `MaxRoyalty`
+		assert_noop!(
+			Nfts::set_royalty(
+				RuntimeOrigin::root(),
+				collection_id,
+				None,
+				bvec![RoyaltyRecipient {
+					account: user_1.clone(),
+					percentage: Permill::from_percent(51)
+				}],
+			),
+			Error::<Test>::InvalidRoyalty
+		);
+
+		assert_ok!(Nfts::set_royalty(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			None,
+			recipients.clone(),
+		));
+		assert!(events().contains(&Event::<Test>::RoyaltySet {
+			collection: collection_id,
+			maybe_item: None,
+			recipients: recipients.clone(),
+		}));
+
+		assert_ok!(Nfts::mint(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			item_id,
+			user_1.clone(),
+			None,
+		));
+		assert_eq!(Nfts::royalty(&collection_id, &item_id), Some(recipients.clone()));
+
+		let item_recipients: RoyaltyRecipientsOf<Test> = bvec![RoyaltyRecipient {
+			account: user_2.clone(),
+			percentage: Permill::from_percent(10)
+		}];
+		assert_ok!(Nfts::set_royalty(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			Some(item_id),
+			item_recipients.clone(),
+		));
+		assert_eq!(Nfts::royalty(&collection_id, &item_id), Some(item_recipients));
+
+		assert_ok!(Nfts::clear_royalty(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			Some(item_id),
+		));
+		assert!(events().contains(&Event::<Test>::RoyaltyCleared {
+			collection: collection_id,
+			maybe_item: Some(item_id),
+		}));
+		assert_eq!(Nfts::royalty(&collection_id, &item_id), Some(recipients.clone()));
+		assert_noop!(
+			Nfts::clear_royalty(
+				RuntimeOrigin::signed(user_1.clone()),
+				collection_id,
+				Some(item_id)
+			),
+			Error::<Test>::RoyaltyNotFound
+		);
+
+		// locking the royalties prevents any change but by the force origin
+		assert_ok!(Nfts::lock_collection(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			CollectionSettings::from_disabled(CollectionSetting::UnlockedRoyalties.into())
+		));
+		assert_noop!(
+			Nfts::clear_royalty(RuntimeOrigin::signed(user_1.clone()), collection_id, None),
+			Error::<Test>::LockedCollectionRoyalties
+		);
+		assert_noop!(
+			Nfts::set_royalty(
+				RuntimeOrigin::signed(user_1.clone()),
+				collection_id,
+				Some(item_id),
+				recipients.clone(),
+			),
+			Error::<Test>::LockedCollectionRoyalties
+		);
+		assert_ok!(Nfts::clear_royalty(RuntimeOrigin::root(), collection_id, None));
+		assert_eq!(Nfts::royalty(&collection_id, &item_id), None);
+
+		// burning an item removes its royalties
+		assert_ok!(Nfts::set_royalty(
+			RuntimeOrigin::root(),
+			collection_id,
+			Some(item_id),
+			recipients.clone(),
+		));
+		assert_ok!(Nfts::burn(RuntimeOrigin::signed(user_1.clone()), collection_id, item_id));
+		assert!(!ItemRoyaltyOf::<Test>::contains_key(collection_id, item_id));
+	});
+}
+
+#[test]
+fn migration_to_v2_locks_royalties_of_existing_collections() {
+	new_test_ext().execute_with(|| {
+		let user_1 = account(1);
+		let collection_id = 0;
+
+		assert_ok!(Nfts::force_create(
+			RuntimeOrigin::root(),
+			user_1.clone(),
+			default_collection_config()
+		));
+		StorageVersion::new(1).put::<Nfts>();
+
+		migration::v2::MigrateToV2::<Test>::on_runtime_upgrade();
+
+		assert_eq!(Nfts::on_chain_storage_version(), 2);
+		assert_noop!(
+			Nfts::set_royalty(
+				RuntimeOrigin::signed(user_1.clone()),
+				collection_id,
+				None,
+				bvec![RoyaltyRecipient { account: user_1, percentage: Permill::from_percent(5) }],
+			),
+			Error::<Test>::LockedCollectionRoyalties
+		);
+	});
+}
+
+#[test]
+fn buy_item_should_pay_royalties() {
+	new_test_ext().execute_with(|| {
+		let user_1 = account(1);
+		let user_2 = account(2);
+		let user_3 = account(3);
+		let user_4 = account(4);
+		let collection_id = 0;
+		let item_id = 1;
+		let price = 200;
+		let initial_balance = 1000;
+
+		Balances::make_free_balance_be(&user_1, initial_balance);
+		Balances::make_free_balance_be(&user_2, initial_balance);
+		Balances::make_free_balance_be(&user_3, initial_balance);
+		Balances::make_free_balance_be(&user_4, initial_balance);
+
+		assert_ok!(Nfts::force_create(
+			RuntimeOrigin::root(),
+			user_1.clone(),
+			default_collection_config()
+		));
+		assert_ok!(Nfts::mint(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			item_id,
+			user_2.clone(),
+			None,
+		));
+		assert_ok!(Nfts::set_royalty(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			None,
+			bvec![
+				RoyaltyRecipient { account: user_1.clone(), percentage: Permill::from_percent(5) },
+				RoyaltyRecipient { account: user_4.clone(), percentage: Permill::from_percent(10) },
+			],
+		));
+		assert_ok!(Nfts::set_price(
+			RuntimeOrigin::signed(user_2.clone()),
+			collection_id,
+			item_id,
+			Some(price),
+			None,
+		));
+		events();
+
+		assert_ok!(Nfts::buy_item(
+			RuntimeOrigin::signed(user_3.clone()),
+			collection_id,
+			item_id,
+			price,
+		));
+
+		assert_eq!(Balances::total_balance(&user_1), initial_balance + 10);
+		assert_eq!(Balances::total_balance(&user_2), initial_balance + price - 30);
+		assert_eq!(Balances::total_balance(&user_3), initial_balance - price);
+		assert_eq!(Balances::total_balance(&user_4), initial_balance + 20);
+
+		let events = events();
+		assert!(events.contains(&Event::<Test>::RoyaltyPaid {
+			collection: collection_id,
+			item: item_id,
+			payer: user_3.clone(),
+			receiver: user_1.clone(),
+			amount: 10,
+		}));
+		assert!(events.contains(&Event::<Test>::RoyaltyPaid {
+			collection: collection_id,
+			item: item_id,
+			payer: user_3.clone(),
+			receiver: user_4.clone(),
+			amount: 20,
+		}));
+		assert!(events.contains(&Event::<Test>::ItemBought {
+			collection: collection_id,
+			item: item_id,
+			price,
+			seller: user_2,
+			buyer: user_3,
+		}));
+	});
+}
+
+#[test]
+fn royalties_below_existential_deposit_are_left_to_the_seller() {
+	new_test_ext().execute_with(|| {
+		let user_1 = account(1);
+		let user_2 = account(2);
+		let user_3 = account(3);
+		let user_4 = account(4);
+		let collection_id = 0;
+		let item_id = 1;
+		let price = 100;
+		let initial_balance = 1000;
+
+		ExistentialDeposit::set(&10);
+		Balances::make_free_balance_be(&user_1, initial_balance);
+		Balances::make_free_balance_be(&user_2, initial_balance);
+		Balances::make_free_balance_be(&user_3, initial_balance);
+
+		assert_ok!(Nfts::force_create(
+			RuntimeOrigin::root(),
+			user_1.clone(),
+			default_collection_config()
+		));
+		assert_ok!(Nfts::mint(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			item_id,
+			user_2.clone(),
+			None,
+		));
+		// both shares are below the existential deposit, but only `user_4` has no account
+		assert_ok!(Nfts::set_royalty(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			None,
+			bvec![
+				RoyaltyRecipient { account: user_1.clone(), percentage: Permill::from_percent(5) },
+				RoyaltyRecipient { account: user_4.clone(), percentage: Permill::from_percent(5) },
+			],
+		));
+		assert_ok!(Nfts::set_price(
+			RuntimeOrigin::signed(user_2.clone()),
+			collection_id,
+			item_id,
+			Some(price),
+			None,
+		));
+
+		assert_ok!(Nfts::buy_item(
+			RuntimeOrigin::signed(user_3.clone()),
+			collection_id,
+			item_id,
+			price,
+		));
+
+		assert_eq!(Balances::total_balance(&user_1), initial_balance + 5);
+		assert_eq!(Balances::total_balance(&user_2), initial_balance + price - 5);
+		assert_eq!(Balances::total_balance(&user_3), initial_balance - price);
+		assert_eq!(Balances::total_balance(&user_4), 0);
+		assert!(!events().iter().any(|event| matches!(
+			event,
+			Event::<Test>::RoyaltyPaid { receiver, .. } if *receiver == user_4
+		)));
+	});
+}
+
+#[test]
+fn claim_swap_should_pay_royalties() {
+	new_test_ext().execute_with(|| {
+		let user_1 = account(1);
+		let user_2 = account(2);
+		let user_3 = account(3);
+		let collection_id = 0;
+		let item_1 = 1;
+		let item_2 = 2;
+		let price = 100;
+		let initial_balance = 1000;
+
+		Balances::make_free_balance_be(&user_1, initial_balance);
+		Balances::make_free_balance_be(&user_2, initial_balance);
+		Balances::make_free_balance_be(&user_3, initial_balance);
+
+		assert_ok!(Nfts::force_create(
+			RuntimeOrigin::root(),
+			user_3.clone(),
+			default_collection_config()
+		));
+		assert_ok!(Nfts::mint(
+			RuntimeOrigin::signed(user_3.clone()),
+			collection_id,
+			item_1,
+			user_1.clone(),
+			None,
+		));
+		assert_ok!(Nfts::mint(
+			RuntimeOrigin::signed(user_3.clone()),
+			collection_id,
+			item_2,
+			user_2.clone(),
+			None,
+		));
+		// only the item the price pays for is subject to royalties
+		assert_ok!(Nfts::set_royalty(
+			RuntimeOrigin::signed(user_3.clone()),
+			collection_id,
+			Some(item_2),
+			bvec![RoyaltyRecipient {
+				account: user_3.clone(),
+				percentage: Permill::from_percent(10)
+			}],
+		));
+
+		// user 1 offers item 1 and 100 for item 2
+		let price_with_direction =
+			PriceWithDirection { amount: price, direction: PriceDirection::Send };
+		assert_ok!(Nfts::create_swap(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			item_1,
+			collection_id,
+			Some(item_2),
+			Some(price_with_direction.clone()),
+			2,
+		));
+		assert_ok!(Nfts::claim_swap(
+			RuntimeOrigin::signed(user_2.clone()),
+			collection_id,
+			item_2,
+			collection_id,
+			item_1,
+			Some(price_with_direction),
+		));
+
+		assert_eq!(Balances::total_balance(&user_1), initial_balance - price);
+		assert_eq!(Balances::total_balance(&user_2), initial_balance + price - 10);
+		assert_eq!(Balances::total_balance(&user_3), initial_balance + 10);
+		assert!(events().contains(&Event::<Test>::RoyaltyPaid {
+			collection: collection_id,
+			item: item_2,
+			payer: user_1.clone(),
+			receiver: user_3.clone(),
+			amount: 10,
+		}));
+		assert_eq!(Item::<Test>::get(collection_id, item_1).unwrap().owner, user_2);
+		assert_eq!(Item::<Test>::get(collection_id, item_2).unwrap().owner, user_1);
+	});
+}
+
+#[test]
+fn royalties_are_fixed_when_listed() {
+	new_test_ext().execute_with(|| {
+		let user_1 = account(1);
+		let user_2 = account(2);
+		let user_3 = account(3);
+		let collection_id = 0;
+		let item_1 = 1;
+		let item_2 = 2;
+		let price = 100;
+		let initial_balance = 1000;
+
+		Balances::make_free_balance_be(&user_1, initial_balance);
+		Balances::make_free_balance_be(&user_2, initial_balance);
+		Balances::make_free_balance_be(&user_3, initial_balance);
+
+		assert_ok!(Nfts::force_create(
+			RuntimeOrigin::root(),
+			user_1.clone(),
+			default_collection_config()
+		));
+		assert_ok!(Nfts::mint(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			item_1,
+			user_2.clone(),
+			None,
+		));
+		assert_ok!(Nfts::mint(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			item_2,
+			user_3.clone(),
+			None,
+		));
+		let royalty = |percent| -> RoyaltyRecipientsOf<Test> {
+			bvec![RoyaltyRecipient {
+				account: account(1),
+				percentage: Permill::from_percent(percent)
+			}]
+		};
+		assert_ok!(Nfts::set_royalty(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			None,
+			royalty(5),
+		));
+
+		// raising the royalties of a listed item doesn't affect its sale
+		assert_ok!(Nfts::set_price(
+			RuntimeOrigin::signed(user_2.clone()),
+			collection_id,
+			item_1,
+			Some(price),
+			None,
+		));
+		assert_eq!(SaleRoyaltyOf::<Test>::get(collection_id, item_1), Some(royalty(5)));
+		assert_ok!(Nfts::set_royalty(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			Some(item_1),
+			royalty(50),
+		));
+		assert_ok!(Nfts::buy_item(
+			RuntimeOrigin::signed(user_3.clone()),
+			collection_id,
+			item_1,
+			price,
+		));
+		assert_eq!(Balances::total_balance(&user_1), initial_balance + 5);
+		assert_eq!(Balances::total_balance(&user_2), initial_balance + price - 5);
+		assert!(!SaleRoyaltyOf::<Test>::contains_key(collection_id, item_1));
+
+		// neither does raising the royalties of an item offered in a swap for a price
+		assert_ok!(Nfts::clear_royalty(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			Some(item_1),
+		));
+		let price_with_direction =
+			PriceWithDirection { amount: price, direction: PriceDirection::Receive };
+		assert_ok!(Nfts::create_swap(
+			RuntimeOrigin::signed(user_3.clone()),
+			collection_id,
+			item_1,
+			collection_id,
+			None,
+			Some(price_with_direction.clone()),
+			2,
+		));
+		assert_eq!(SwapRoyaltyOf::<Test>::get(collection_id, item_1), Some(royalty(5)));
+		assert_ok!(Nfts::set_royalty(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			Some(item_1),
+			royalty(50),
+		));
+		assert_ok!(Nfts::transfer(
+			RuntimeOrigin::signed(user_3.clone()),
+			collection_id,
+			item_2,
+			user_2.clone(),
+		));
+		assert_ok!(Nfts::claim_swap(
+			RuntimeOrigin::signed(user_2.clone()),
+			collection_id,
+			item_2,
+			collection_id,
+			item_1,
+			Some(price_with_direction),
+		));
+		assert_eq!(Balances::total_balance(&user_1), initial_balance + 10);
+		assert_eq!(Balances::total_balance(&user_2), initial_balance - 5);
+		assert_eq!(Balances::total_balance(&user_3), initial_balance - 5);
+		assert!(!SwapRoyaltyOf::<Test>::contains_key(collection_id, item_1));
+	});
+}
+
+#[test]
+fn rentals_should_work() {
+	new_test_ext().execute_with(|| {
+		let user_1 = account(1);
+		let user_2 = account(2);
+		let user_3 = account(3);
+		let collection_id = 0;
+		let item_id = 1;
+		let fee = 50;
+		let duration = 10;
+		let initial_balance = 1000;
+
+		Balances::make_free_balance_be(&user_1, initial_balance);
+		Balances::make_free_balance_be(&user_2, initial_balance);
+
+		assert_ok!(Nfts::force_create(
+			RuntimeOrigin::root(),
+			user_1.clone(),
+			default_collection_config()
+		));
+		assert_ok!(Nfts::mint(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			item_id,
+			user_1.clone(),
+			None,
+		));
+
+		// validate the offer
+		assert_noop!(
+			Nfts::offer_rental(
+				RuntimeOrigin::signed(user_2.clone()),
+				collection_id,
+				item_id,
+				user_2.clone(),
+				duration,
+				Some(fee),
+			),
+			Error::<Test>::NoPermission
+		);
+		assert_noop!(
+			Nfts::offer_rental(
+				RuntimeOrigin::signed(user_1.clone()),
+				collection_id,
+				item_id,
+				user_2.clone(),
+				0,
+				Some(fee),
+			),
+			Error::<Test>::WrongDuration
+		);
+		assert_noop!(
+			Nfts::offer_rental(
+				RuntimeOrigin::signed(user_1.clone()),
+				collection_id,
+				item_id,
+				user_2.clone(),
+				<Test as Config>::MaxDeadlineDuration::get() + 1,
+				Some(fee),
+			),
+			Error::<Test>::WrongDuration
+		);
+
+		assert_ok!(Nfts::offer_rental(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			item_id,
+			user_2.clone(),
+			duration,
+			Some(fee),
+		));
+		assert!(events().contains(&Event::<Test>::RentalOffered {
+			collection: collection_id,
+			item: item_id,
+			owner: user_1.clone(),
+			renter: user_2.clone(),
+			duration,
+			fee: Some(fee),
+		}));
+
+		// only the renter can accept the offer, with its duration and fee
+		assert_noop!(
+			Nfts::accept_rental(
+				RuntimeOrigin::signed(user_3.clone()),
+				collection_id,
+				item_id,
+				duration,
+				Some(fee),
+			),
+			Error::<Test>::NoPermission
+		);
+		assert_noop!(
+			Nfts::accept_rental(
+				RuntimeOrigin::signed(user_2.clone()),
+				collection_id,
+				item_id,
+				duration,
+				Some(fee - 1),
+			),
+			Error::<Test>::UnknownRental
+		);
+		// nor can it be accepted once the owner shortened it
+		assert_noop!(
+			Nfts::accept_rental(
+				RuntimeOrigin::signed(user_2.clone()),
+				collection_id,
+				item_id,
+				duration - 1,
+				Some(fee),
+			),
+			Error::<Test>::UnknownRental
+		);
+		assert_ok!(Nfts::accept_rental(
+			RuntimeOrigin::signed(user_2.clone()),
+			collection_id,
+			item_id,
+			duration,
+			Some(fee),
+		));
+		assert!(events().contains(&Event::<Test>::RentalStarted {
+			collection: collection_id,
+			item: item_id,
+			owner: user_1.clone(),
+			user: user_2.clone(),
+			fee: Some(fee),
+			expires: 1 + duration,
+		}));
+		assert_eq!(Balances::free_balance(&user_1), initial_balance + fee);
+		assert_eq!(Balances::free_balance(&user_2), initial_balance - fee);
+		assert_eq!(Nfts::item_user(&collection_id, &item_id), Some(user_2.clone()));
+		assert!(!PendingRentalOf::<Test>::contains_key(collection_id, item_id));
+
+		// the item is transfer-locked during the rental
+		assert_noop!(
+			Nfts::transfer(
+				RuntimeOrigin::signed(user_1.clone()),
+				collection_id,
+				item_id,
+				user_3.clone()
+			),
+			Error::<Test>::ItemRented
+		);
+		assert_noop!(
+			Nfts::burn(RuntimeOrigin::signed(user_1.clone()), collection_id, item_id),
+			Error::<Test>::ItemRented
+		);
+		assert_noop!(
+			Nfts::offer_rental(
+				RuntimeOrigin::signed(user_1.clone()),
+				collection_id,
+				item_id,
+				user_3.clone(),
+				duration,
+				None,
+			),
+			Error::<Test>::ItemRented
+		);
+
+		// the renter can set attributes in its own namespace
+		assert_ok!(Nfts::set_attribute(
+			RuntimeOrigin::signed(user_2.clone()),
+			collection_id,
+			Some(item_id),
+			AttributeNamespace::Account(user_2.clone()),
+			bvec![0],
+			bvec![0],
+		));
+		assert_noop!(
+			Nfts::set_attribute(
+				RuntimeOrigin::signed(user_3.clone()),
+				collection_id,
+				Some(item_id),
+				AttributeNamespace::Account(user_3.clone()),
+				bvec![0],
+				bvec![0],
+			),
+			Error::<Test>::NoPermission
+		);
+
+		// only the renter can end the rental before it expires
+		assert_noop!(
+			Nfts::end_rental(RuntimeOrigin::signed(user_3.clone()), collection_id, item_id),
+			Error::<Test>::RentalNotExpired
+		);
+
+		// the rental expires on its own
+		System::set_block_number(2 + duration);
+		assert_eq!(Nfts::item_user(&collection_id, &item_id), None);
+		assert_noop!(
+			Nfts::set_attribute(
+				RuntimeOrigin::signed(user_2.clone()),
+				collection_id,
+				Some(item_id),
+				AttributeNamespace::Account(user_2.clone()),
+				bvec![1],
+				bvec![1],
+			),
+			Error::<Test>::NoPermission
+		);
+		assert_ok!(Nfts::end_rental(RuntimeOrigin::signed(user_3.clone()), collection_id, item_id));
+		assert!(events().contains(&Event::<Test>::RentalEnded {
+			collection: collection_id,
+			item: item_id,
+			user: user_2.clone(),
+		}));
+		assert_noop!(
+			Nfts::end_rental(RuntimeOrigin::signed(user_3.clone()), collection_id, item_id),
+			Error::<Test>::UnknownRental
+		);
+
+		// a free rental can be ended early by its user
+		assert_ok!(Nfts::offer_rental(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			item_id,
+			user_3.clone(),
+			duration,
+			None,
+		));
+		assert_ok!(Nfts::accept_rental(
+			RuntimeOrigin::signed(user_3.clone()),
+			collection_id,
+			item_id,
+			duration,
+			None,
+		));
+		assert_ok!(Nfts::end_rental(RuntimeOrigin::signed(user_3.clone()), collection_id, item_id));
+		assert_ok!(Nfts::transfer(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			item_id,
+			user_3.clone()
+		));
+	});
+}
+
+#[test]
+fn cancel_rental_offer_should_work() {
+	new_test_ext().execute_with(|| {
+		let user_1 = account(1);
+		let user_2 = account(2);
+		let collection_id = 0;
+		let item_id = 1;
+
+		assert_ok!(Nfts::force_create(
+			RuntimeOrigin::root(),
+			user_1.clone(),
+			default_collection_config()
+		));
+		assert_ok!(Nfts::mint(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			item_id,
+			user_1.clone(),
+			None,
+		));
+		assert_ok!(Nfts::offer_rental(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			item_id,
+			user_2.clone(),
+			10,
+			None,
+		));
+
+		assert_noop!(
+			Nfts::cancel_rental_offer(
+				RuntimeOrigin::signed(user_2.clone()),
+				collection_id,
+				item_id
+			),
+			Error::<Test>::NoPermission
+		);
+		assert_ok!(Nfts::cancel_rental_offer(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			item_id
+		));
+		assert!(events().contains(&Event::<Test>::RentalOfferCancelled {
+			collection: collection_id,
+			item: item_id,
+		}));
+		assert_noop!(
+			Nfts::accept_rental(
+				RuntimeOrigin::signed(user_2.clone()),
+				collection_id,
+				item_id,
+				10,
+				None
+			),
+			Error::<Test>::UnknownRental
+		);
+
+		// transferring the item removes its rental offer
+		assert_ok!(Nfts::offer_rental(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			item_id,
+			user_2.clone(),
+			10,
+			None,
+		));
+		assert_ok!(Nfts::transfer(
+			RuntimeOrigin::signed(user_1.clone()),
+			collection_id,
+			item_id,
+			user_2.clone()
+		));
+		assert!(!PendingRentalOf::<Test>::contains_key(collection_id, item_id));
+	});
+}
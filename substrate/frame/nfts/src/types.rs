@@ -70,6 +70,17 @@ pub type ItemTipOf<T, I = ()> = ItemTip<
 	<T as SystemConfig>::AccountId,
 	BalanceOf<T, I>,
 >;
+/// A type alias for the royalty recipients of a collection or an item.
+pub type RoyaltyRecipientsOf<T, I = ()> = BoundedVec<
+	RoyaltyRecipient<<T as SystemConfig>::AccountId>,
+	<T as Config<I>>::MaxRoyaltyRecipients,
+>;
+/// A type alias for a pending rental offer of an item.
+pub type PendingRentalFor<T, I = ()> =
+	PendingRental<<T as SystemConfig>::AccountId, ItemPrice<T, I>, BlockNumberFor<T, I>>;
+/// A type alias for the rental of an item.
+pub type ItemRentalFor<T, I = ()> =
+	ItemRental<<T as SystemConfig>::AccountId, BlockNumberFor<T, I>>;
 /// A type alias for the settings configuration of a collection.
 pub type CollectionConfigFor<T, I = ()> =
 	CollectionConfig<BalanceOf<T, I>, BlockNumberFor<T, I>, <T as Config<I>>::CollectionId>;
@@ -239,6 +250,26 @@ pub struct PendingSwap<CollectionId, ItemId, ItemPriceWithDirection, Deadline> {
 	pub deadline: Deadline,
 }
 
+/// Information about a pending rental offer.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct PendingRental<AccountId, Price, BlockNumber> {
+	/// The account the item is offered to.
+	pub renter: AccountId,
+	/// The number of blocks the rental lasts once accepted.
+	pub duration: BlockNumber,
+	/// An optional fee the renter pays to the owner of the item.
+	pub fee: Option<Price>,
+}
+
+/// Information about the rental of an item.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct ItemRental<AccountId, BlockNumber> {
+	/// The account holding the usage rights of the item.
+	pub user: AccountId,
+	/// The block number after which the rental is over.
+	pub expires: BlockNumber,
+}
+
 /// Information about the reserved attribute deposit.
 #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
 pub struct AttributeDeposit<DepositBalance, AccountId> {
@@ -295,6 +326,25 @@ pub struct PriceWithDirection<Amount> {
 	pub direction: PriceDirection,
 }
 
+/// A recipient of the royalties charged on sales of an item.
+#[derive(
+	Clone,
+	Encode,
+	Decode,
+	DecodeWithMemTracking,
+	Eq,
+	PartialEq,
+	RuntimeDebug,
+	TypeInfo,
+	MaxEncodedLen,
+)]
+pub struct RoyaltyRecipient<AccountId> {
+	/// The account receiving the royalty.
+	pub account: AccountId,
+	/// The share of the sale price paid to the account.
+	pub percentage: Permill,
+}
+
 /// Support for up to 64 user-enabled features on a collection.
 #[bitflags]
 #[repr(u64)]
@@ -310,6 +360,8 @@ pub enum CollectionSetting {
 	UnlockedMaxSupply,
 	/// When this isn't set then the deposit is required to hold the items of this collection.
 	DepositRequired,
+	/// The royalties of this collection and its items can be modified.
+	UnlockedRoyalties,
 }
 
 /// Wrapper type for `BitFlags<CollectionSetting>` that implements `Codec`.
@@ -570,6 +622,8 @@ pub enum PalletFeature {
 	Approvals,
 	/// Allow/disallow atomic items swap.
 	Swaps,
+	/// Allow/disallow item rentals.
+	Rentals,
 }
 
 /// Wrapper type for `BitFlags<PalletFeature>` that implements `Codec`.
@@ -111,6 +111,12 @@ pub trait WeightInfo {
 	fn claim_swap() -> Weight;
 	fn mint_pre_signed(n: u32, ) -> Weight;
 	fn set_attributes_pre_signed(n: u32, ) -> Weight;
+	fn set_royalty() -> Weight;
+	fn clear_royalty() -> Weight;
+	fn offer_rental() -> Weight;
+	fn cancel_rental_offer() -> Weight;
+	fn accept_rental() -> Weight;
+	fn end_rental() -> Weight;
 }
 
 /// Weights for `pallet_nfts` using the Substrate node and recommended hardware.
@@ -803,6 +809,78 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(n.into())))
 			.saturating_add(Weight::from_parts(0, 2954).saturating_mul(n.into()))
 	}
+	/// Storage: `Nfts::CollectionRoleOf` (r:1 w:0)
+	/// Proof: `Nfts::CollectionRoleOf` (`max_values`: None, `max_size`: Some(69), added: 2544, mode: `MaxEncodedLen`)
+	/// Storage: `Nfts::CollectionConfigOf` (r:1 w:0)
+	/// Proof: `Nfts::CollectionConfigOf` (`max_values`: None, `max_size`: Some(73), added: 2548, mode: `MaxEncodedLen`)
+	/// Storage: `Nfts::Item` (r:1 w:0)
+	/// Proof: `Nfts::Item` (`max_values`: None, `max_size`: Some(861), added: 3336, mode: `MaxEncodedLen`)
+	/// Storage: `Nfts::ItemRoyaltyOf` (r:0 w:1)
+	/// Proof: `Nfts::ItemRoyaltyOf` (`max_values`: None, `max_size`: Some(221), added: 2696, mode: `MaxEncodedLen`)
+	fn set_royalty() -> Weight {
+		// Estimated, not measured: to be regenerated with the benchmark CLI.
+		Weight::from_parts(30_524_000, 4326)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Nfts::CollectionRoleOf` (r:1 w:0)
+	/// Proof: `Nfts::CollectionRoleOf` (`max_values`: None, `max_size`: Some(69), added: 2544, mode: `MaxEncodedLen`)
+	/// Storage: `Nfts::CollectionConfigOf` (r:1 w:0)
+	/// Proof: `Nfts::CollectionConfigOf` (`max_values`: None, `max_size`: Some(73), added: 2548, mode: `MaxEncodedLen`)
+	/// Storage: `Nfts::Item` (r:1 w:0)
+	/// Proof: `Nfts::Item` (`max_values`: None, `max_size`: Some(861), added: 3336, mode: `MaxEncodedLen`)
+	/// Storage: `Nfts::ItemRoyaltyOf` (r:1 w:1)
+	/// Proof: `Nfts::ItemRoyaltyOf` (`max_values`: None, `max_size`: Some(221), added: 2696, mode: `MaxEncodedLen`)
+	fn clear_royalty() -> Weight {
+		// Estimated, not measured: to be regenerated with the benchmark CLI.
+		Weight::from_parts(31_948_000, 4326)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Nfts::Item` (r:1 w:0)
+	/// Proof: `Nfts::Item` (`max_values`: None, `max_size`: Some(861), added: 3336, mode: `MaxEncodedLen`)
+	/// Storage: `Nfts::ItemRentalOf` (r:1 w:0)
+	/// Proof: `Nfts::ItemRentalOf` (`max_values`: None, `max_size`: Some(76), added: 2551, mode: `MaxEncodedLen`)
+	/// Storage: `Nfts::PendingRentalOf` (r:0 w:1)
+	/// Proof: `Nfts::PendingRentalOf` (`max_values`: None, `max_size`: Some(93), added: 2568, mode: `MaxEncodedLen`)
+	fn offer_rental() -> Weight {
+		// Estimated, not measured: to be regenerated with the benchmark CLI.
+		Weight::from_parts(24_512_000, 4326)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Nfts::Item` (r:1 w:0)
+	/// Proof: `Nfts::Item` (`max_values`: None, `max_size`: Some(861), added: 3336, mode: `MaxEncodedLen`)
+	/// Storage: `Nfts::PendingRentalOf` (r:1 w:1)
+	/// Proof: `Nfts::PendingRentalOf` (`max_values`: None, `max_size`: Some(93), added: 2568, mode: `MaxEncodedLen`)
+	fn cancel_rental_offer() -> Weight {
+		// Estimated, not measured: to be regenerated with the benchmark CLI.
+		Weight::from_parts(21_664_000, 4326)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Nfts::Item` (r:1 w:0)
+	/// Proof: `Nfts::Item` (`max_values`: None, `max_size`: Some(861), added: 3336, mode: `MaxEncodedLen`)
+	/// Storage: `Nfts::PendingRentalOf` (r:1 w:1)
+	/// Proof: `Nfts::PendingRentalOf` (`max_values`: None, `max_size`: Some(93), added: 2568, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Nfts::ItemRentalOf` (r:0 w:1)
+	/// Proof: `Nfts::ItemRentalOf` (`max_values`: None, `max_size`: Some(76), added: 2551, mode: `MaxEncodedLen`)
+	fn accept_rental() -> Weight {
+		// Estimated, not measured: to be regenerated with the benchmark CLI.
+		Weight::from_parts(56_037_000, 4326)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: `Nfts::ItemRentalOf` (r:1 w:1)
+	/// Proof: `Nfts::ItemRentalOf` (`max_values`: None, `max_size`: Some(76), added: 2551, mode: `MaxEncodedLen`)
+	fn end_rental() -> Weight {
+		// Estimated, not measured: to be regenerated with the benchmark CLI.
+		Weight::from_parts(17_305_000, 3541)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
 }
 
 // For backwards compatibility and tests.
@@ -1494,4 +1572,76 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(n.into())))
 			.saturating_add(Weight::from_parts(0, 2954).saturating_mul(n.into()))
 	}
+	/// Storage: `Nfts::CollectionRoleOf` (r:1 w:0)
+	/// Proof: `Nfts::CollectionRoleOf` (`max_values`: None, `max_size`: Some(69), added: 2544, mode: `MaxEncodedLen`)
+	/// Storage: `Nfts::CollectionConfigOf` (r:1 w:0)
+	/// Proof: `Nfts::CollectionConfigOf` (`max_values`: None, `max_size`: Some(73), added: 2548, mode: `MaxEncodedLen`)
+	/// Storage: `Nfts::Item` (r:1 w:0)
+	/// Proof: `Nfts::Item` (`max_values`: None, `max_size`: Some(861), added: 3336, mode: `MaxEncodedLen`)
+	/// Storage: `Nfts::ItemRoyaltyOf` (r:0 w:1)
+	/// Proof: `Nfts::ItemRoyaltyOf` (`max_values`: None, `max_size`: Some(221), added: 2696, mode: `MaxEncodedLen`)
+	fn set_royalty() -> Weight {
+		// Estimated, not measured: to be regenerated with the benchmark CLI.
+		Weight::from_parts(30_524_000, 4326)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Nfts::CollectionRoleOf` (r:1 w:0)
+	/// Proof: `Nfts::CollectionRoleOf` (`max_values`: None, `max_size`: Some(69), added: 2544, mode: `MaxEncodedLen`)
+	/// Storage: `Nfts::CollectionConfigOf` (r:1 w:0)
+	/// Proof: `Nfts::CollectionConfigOf` (`max_values`: None, `max_size`: Some(73), added: 2548, mode: `MaxEncodedLen`)
+	/// Storage: `Nfts::Item` (r:1 w:0)
+	/// Proof: `Nfts::Item` (`max_values`: None, `max_size`: Some(861), added: 3336, mode: `MaxEncodedLen`)
+	/// Storage: `Nfts::ItemRoyaltyOf` (r:1 w:1)
+	/// Proof: `Nfts::ItemRoyaltyOf` (`max_values`: None, `max_size`: Some(221), added: 2696, mode: `MaxEncodedLen`)
+	fn clear_royalty() -> Weight {
+		// Estimated, not measured: to be regenerated with the benchmark CLI.
+		Weight::from_parts(31_948_000, 4326)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Nfts::Item` (r:1 w:0)
+	/// Proof: `Nfts::Item` (`max_values`: None, `max_size`: Some(861), added: 3336, mode: `MaxEncodedLen`)
+	/// Storage: `Nfts::ItemRentalOf` (r:1 w:0)
+	/// Proof: `Nfts::ItemRentalOf` (`max_values`: None, `max_size`: Some(76), added: 2551, mode: `MaxEncodedLen`)
+	/// Storage: `Nfts::PendingRentalOf` (r:0 w:1)
+	/// Proof: `Nfts::PendingRentalOf` (`max_values`: None, `max_size`: Some(93), added: 2568, mode: `MaxEncodedLen`)
+	fn offer_rental() -> Weight {
+		// Estimated, not measured: to be regenerated with the benchmark CLI.
+		Weight::from_parts(24_512_000, 4326)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Nfts::Item` (r:1 w:0)
+	/// Proof: `Nfts::Item` (`max_values`: None, `max_size`: Some(861), added: 3336, mode: `MaxEncodedLen`)
+	/// Storage: `Nfts::PendingRentalOf` (r:1 w:1)
+	/// Proof: `Nfts::PendingRentalOf` (`max_values`: None, `max_size`: Some(93), added: 2568, mode: `MaxEncodedLen`)
+	fn cancel_rental_offer() -> Weight {
+		// Estimated, not measured: to be regenerated with the benchmark CLI.
+		Weight::from_parts(21_664_000, 4326)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Nfts::Item` (r:1 w:0)
+	/// Proof: `Nfts::Item` (`max_values`: None, `max_size`: Some(861), added: 3336, mode: `MaxEncodedLen`)
+	/// Storage: `Nfts::PendingRentalOf` (r:1 w:1)
+	/// Proof: `Nfts::PendingRentalOf` (`max_values`: None, `max_size`: Some(93), added: 2568, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Nfts::ItemRentalOf` (r:0 w:1)
+	/// Proof: `Nfts::ItemRentalOf` (`max_values`: None, `max_size`: Some(76), added: 2551, mode: `MaxEncodedLen`)
+	fn accept_rental() -> Weight {
+		// Estimated, not measured: to be regenerated with the benchmark CLI.
+		Weight::from_parts(56_037_000, 4326)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: `Nfts::ItemRentalOf` (r:1 w:1)
+	/// Proof: `Nfts::ItemRentalOf` (`max_values`: None, `max_size`: Some(76), added: 2551, mode: `MaxEncodedLen`)
+	fn end_rental() -> Weight {
+		// Estimated, not measured: to be regenerated with the benchmark CLI.
+		Weight::from_parts(17_305_000, 3541)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
 }
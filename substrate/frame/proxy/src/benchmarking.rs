@@ -271,6 +271,79 @@ mod benchmarks {
 		Ok(())
 	}
 
+	#[benchmark]
+	fn add_limited_proxy(p: Linear<1, { T::MaxProxies::get() - 1 }>) -> Result<(), BenchmarkError> {
+		add_proxies::<T>(p, None)?;
+		let caller: T::AccountId = whitelisted_caller();
+		let real = T::Lookup::unlookup(account("target", T::MaxProxies::get(), SEED));
+		let now = T::BlockNumberProvider::current_block_number();
+		let limits = ProxyLimits {
+			expiry: Some(now.saturating_add(One::one())),
+			allowance: T::CallSpending::measures_calls().then(|| SpendingAllowance {
+				amount: BalanceOf::<T>::max_value(),
+				period: One::one(),
+			}),
+		};
+
+		#[extrinsic_call]
+		_(
+			RawOrigin::Signed(caller.clone()),
+			real,
+			T::ProxyType::default(),
+			BlockNumberFor::<T>::zero(),
+			limits,
+		);
+
+		let (proxies, _) = Proxies::<T>::get(&caller);
+		assert_eq!(proxies.len() as u32, p + 1);
+		assert_eq!(Limits::<T>::iter_prefix(&caller).count(), 1);
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn remove_expired_proxy(
+		p: Linear<1, { T::MaxProxies::get() - 1 }>,
+	) -> Result<(), BenchmarkError> {
+		add_proxies::<T>(p - 1, None)?;
+		let caller: T::AccountId = whitelisted_caller();
+		let delegate: T::AccountId = account("target", T::MaxProxies::get(), SEED);
+		let now = T::BlockNumberProvider::current_block_number();
+		let expiry = now.saturating_add(One::one());
+		Proxy::<T>::add_limited_proxy(
+			RawOrigin::Signed(caller.clone()).into(),
+			T::Lookup::unlookup(delegate.clone()),
+			T::ProxyType::default(),
+			BlockNumberFor::<T>::zero(),
+			ProxyLimits { expiry: Some(expiry), allowance: None },
+		)?;
+		T::BlockNumberProvider::set_block_number(expiry);
+		let anyone: T::AccountId = account("anyone", 0, SEED);
+
+		#[extrinsic_call]
+		_(
+			RawOrigin::Signed(anyone),
+			T::Lookup::unlookup(caller.clone()),
+			T::Lookup::unlookup(delegate.clone()),
+			T::ProxyType::default(),
+			BlockNumberFor::<T>::zero(),
+		);
+
+		assert_last_event::<T>(
+			Event::ProxyExpired {
+				delegator: caller.clone(),
+				delegatee: delegate,
+				proxy_type: T::ProxyType::default(),
+				delay: BlockNumberFor::<T>::zero(),
+			}
+			.into(),
+		);
+		let (proxies, _) = Proxies::<T>::get(&caller);
+		assert_eq!(proxies.len() as u32, p - 1);
+
+		Ok(())
+	}
+
 	#[benchmark]
 	fn remove_proxies(p: Linear<1, { T::MaxProxies::get() - 1 }>) -> Result<(), BenchmarkError> {
 		add_proxies::<T>(p, None)?;
@@ -23,6 +23,10 @@
 //! wish to execute some duration prior to execution happens. In this case, the target account may
 //! reject the announcement and in doing so, veto the execution.
 //!
+//! Proxies may further be limited, see [`ProxyLimits`]: they can expire at a given block number
+//! and be given a cumulative allowance for the value they may transfer out of the target account
+//! per period, as measured by [`Config::CallSpending`].
+//!
 //! - [`Config`]
 //! - [`Call`]
 
@@ -52,6 +56,14 @@ pub type BlockNumberFor<T> =
 
 type AccountIdLookupOf<T> = <<T as frame_system::Config>::Lookup as StaticLookup>::Source;
 
+type ProxyDefinitionOf<T> = ProxyDefinition<
+	<T as frame_system::Config>::AccountId,
+	<T as Config>::ProxyType,
+	BlockNumberFor<T>,
+>;
+
+type ProxyLimitsOf<T> = ProxyLimits<BalanceOf<T>, BlockNumberFor<T>>;
+
 /// The parameters under which a particular account has a proxy relationship with some other
 /// account.
 #[derive(
@@ -88,6 +100,85 @@ pub struct Announcement<AccountId, Hash, BlockNumber> {
 	height: BlockNumber,
 }
 
+/// Cumulative value a proxy may transfer out of the proxied account within a period.
+#[derive(
+	Encode,
+	Decode,
+	DecodeWithMemTracking,
+	Clone,
+	Copy,
+	Eq,
+	PartialEq,
+	RuntimeDebug,
+	MaxEncodedLen,
+	TypeInfo,
+)]
+pub struct SpendingAllowance<Balance, BlockNumber> {
+	/// The maximum value which may be transferred within a period.
+	pub amount: Balance,
+	/// The length of a period in blocks. Periods start at multiples of this length.
+	pub period: BlockNumber,
+}
+
+/// Limits of a proxy beyond the calls allowed by its proxy type.
+#[derive(
+	Encode,
+	Decode,
+	DecodeWithMemTracking,
+	Clone,
+	Copy,
+	Eq,
+	PartialEq,
+	RuntimeDebug,
+	MaxEncodedLen,
+	TypeInfo,
+)]
+pub struct ProxyLimits<Balance, BlockNumber> {
+	/// The block number from which the proxy may no longer be used, if any.
+	pub expiry: Option<BlockNumber>,
+	/// The value the proxy may transfer out of the proxied account, if limited.
+	pub allowance: Option<SpendingAllowance<Balance, BlockNumber>>,
+}
+
+/// Value spent by a proxy in its current allowance period.
+#[derive(Encode, Decode, Clone, Copy, Eq, PartialEq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct Spending<Balance, BlockNumber> {
+	/// The block number at which the period started.
+	pub period_start: BlockNumber,
+	/// The value transferred since the start of the period.
+	pub spent: Balance,
+}
+
+/// Measures the value transferred out of an account by a call.
+///
+/// Used to track the [`SpendingAllowance`] of proxies.
+pub trait CallSpending<AccountId, Call, Balance> {
+	/// Whether calls are measured at all.
+	///
+	/// Spending allowances may not be granted to proxies otherwise.
+	fn measures_calls() -> bool {
+		true
+	}
+
+	/// The value `call` transfers out of `who` when dispatched from its signed origin.
+	///
+	/// Implementations must account for the calls nested in `call`, e.g. by `pallet_utility`
+	/// batches, and should overestimate the value of transfers they can't measure exactly. Calls
+	/// which are not known to transfer nothing should be valued at the maximum balance.
+	fn spending(who: &AccountId, call: &Call) -> Balance;
+}
+
+/// Calls are not measured, spending allowances are not supported.
+impl<AccountId, Call, Balance: Zero> CallSpending<AccountId, Call, Balance> for () {
+	fn measures_calls() -> bool {
+		false
+	}
+
+	fn spending(_: &AccountId, _: &Call) -> Balance {
+		Zero::zero()
+	}
+}
+
 /// The type of deposit
 #[derive(
 	Encode,
@@ -211,6 +302,16 @@ pub mod pallet {
 		///     providers can be used. Relay provider can be a bit better in cases where the
 		///     parachain is lagging its block production to avoid clock skew.
 		type BlockNumberProvider: BlockNumberProvider;
+
+		/// Measures the value transferred out of the proxied account by the calls dispatched
+		/// through a proxy, which is counted against the [`SpendingAllowance`] of the proxy.
+		///
+		/// Use `()` if spending allowances are not needed, proxies may not be given one then.
+		type CallSpending: CallSpending<
+			Self::AccountId,
+			<Self as Config>::RuntimeCall,
+			BalanceOf<Self>,
+		>;
 	}
 
 	#[pallet::call]
@@ -230,6 +331,8 @@ pub mod pallet {
 			(T::WeightInfo::proxy(T::MaxProxies::get())
 				 // AccountData for inner call origin accountdata.
 				.saturating_add(T::DbWeight::get().reads_writes(1, 1))
+				 // Limits and spending of the proxy.
+				.saturating_add(T::DbWeight::get().reads_writes(2, 1))
 				.saturating_add(di.call_weight),
 			di.class)
 		})]
@@ -244,9 +347,7 @@ pub mod pallet {
 			let def = Self::find_proxy(&real, &who, force_proxy_type)?;
 			ensure!(def.delay.is_zero(), Error::<T>::Unannounced);
 
-			Self::do_proxy(def, real, *call);
-
-			Ok(())
+			Self::do_proxy(def, real, *call)
 		}
 
 		/// Register a proxy account for the sender that is able to make calls on its behalf.
@@ -389,6 +490,7 @@ pub mod pallet {
 			ensure!(proxy == who, Error::<T>::NoPermission);
 
 			let (_, deposit) = Proxies::<T>::take(&who);
+			Self::clear_limits(&who);
 			T::Currency::unreserve(&spawner, deposit);
 
 			Self::deposit_event(Event::PureKilled {
@@ -529,6 +631,8 @@ pub mod pallet {
 			(T::WeightInfo::proxy_announced(T::MaxPending::get(), T::MaxProxies::get())
 				 // AccountData for inner call origin accountdata.
 				.saturating_add(T::DbWeight::get().reads_writes(1, 1))
+				 // Limits and spending of the proxy.
+				.saturating_add(T::DbWeight::get().reads_writes(2, 1))
 				.saturating_add(di.call_weight),
 			di.class)
 		})]
@@ -553,9 +657,7 @@ pub mod pallet {
 			})
 			.map_err(|_| Error::<T>::Unannounced)?;
 
-			Self::do_proxy(def, real, *call);
-
-			Ok(())
+			Self::do_proxy(def, real, *call)
 		}
 
 		/// Poke / Adjust deposits made for proxies and announcements based on current values.
@@ -656,6 +758,101 @@ pub mod pallet {
 
 			Ok(if deposit_updated { Pays::No.into() } else { Pays::Yes.into() })
 		}
+
+		/// Register a proxy account for the sender that is able to make calls on its behalf within
+		/// the given `limits`.
+		///
+		/// A proxy with limits may not manage the proxies of the sender, see [`ProxyLimits`].
+		///
+		/// The dispatch origin for this call must be _Signed_.
+		///
+		/// Parameters:
+		/// - `proxy`: The account that the `caller` would like to make a proxy.
+		/// - `proxy_type`: The permissions allowed for this proxy account.
+		/// - `delay`: The announcement period required of the initial proxy. Will generally be
+		/// zero.
+		/// - `limits`: The expiry and spending allowance of the proxy.
+		#[pallet::call_index(11)]
+		#[pallet::weight(T::WeightInfo::add_limited_proxy(T::MaxProxies::get()))]
+		pub fn add_limited_proxy(
+			origin: OriginFor<T>,
+			delegate: AccountIdLookupOf<T>,
+			proxy_type: T::ProxyType,
+			delay: BlockNumberFor<T>,
+			limits: ProxyLimitsOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let delegate = T::Lookup::lookup(delegate)?;
+
+			let now = T::BlockNumberProvider::current_block_number();
+			ensure!(limits.expiry.map_or(true, |expiry| expiry > now), Error::<T>::InvalidLimits);
+			ensure!(
+				limits.allowance.map_or(true, |allowance| !allowance.period.is_zero()),
+				Error::<T>::InvalidLimits
+			);
+			ensure!(
+				limits.allowance.is_none() || T::CallSpending::measures_calls(),
+				Error::<T>::AllowanceUnsupported
+			);
+
+			Self::add_proxy_delegate(&who, delegate.clone(), proxy_type.clone(), delay)?;
+			let def = ProxyDefinition {
+				delegate: delegate.clone(),
+				proxy_type: proxy_type.clone(),
+				delay,
+			};
+			Limits::<T>::insert(&who, &def, limits);
+			Self::deposit_event(Event::ProxyLimitsSet {
+				delegator: who,
+				delegatee: delegate,
+				proxy_type,
+				delay,
+				limits,
+			});
+
+			Ok(())
+		}
+
+		/// Unregister an expired proxy account of `delegator`, returning its deposit.
+		///
+		/// The dispatch origin for this call must be _Signed_. Anyone may remove an expired proxy.
+		///
+		/// Parameters:
+		/// - `delegator`: The account which registered the proxy.
+		/// - `delegate`: The expired proxy account.
+		/// - `proxy_type`: The permissions of the expired proxy account.
+		/// - `delay`: The announcement period of the expired proxy account.
+		#[pallet::call_index(12)]
+		#[pallet::weight(T::WeightInfo::remove_expired_proxy(T::MaxProxies::get()))]
+		pub fn remove_expired_proxy(
+			origin: OriginFor<T>,
+			delegator: AccountIdLookupOf<T>,
+			delegate: AccountIdLookupOf<T>,
+			proxy_type: T::ProxyType,
+			delay: BlockNumberFor<T>,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+			let delegator = T::Lookup::lookup(delegator)?;
+			let delegate = T::Lookup::lookup(delegate)?;
+
+			let def = ProxyDefinition {
+				delegate: delegate.clone(),
+				proxy_type: proxy_type.clone(),
+				delay,
+			};
+			let limits = Limits::<T>::get(&delegator, &def).ok_or(Error::<T>::NotExpired)?;
+			ensure!(Self::is_expired(&limits), Error::<T>::NotExpired);
+
+			Self::remove_proxy_delegate(&delegator, delegate.clone(), proxy_type.clone(), delay)?;
+			Self::deposit_event(Event::ProxyExpired {
+				delegator,
+				delegatee: delegate,
+				proxy_type,
+				delay,
+			});
+
+			Ok(())
+		}
 	}
 
 	#[pallet::event]
@@ -705,6 +902,28 @@ pub mod pallet {
 			old_deposit: BalanceOf<T>,
 			new_deposit: BalanceOf<T>,
 		},
+		/// Limits were set for a proxy.
+		ProxyLimitsSet {
+			delegator: T::AccountId,
+			delegatee: T::AccountId,
+			proxy_type: T::ProxyType,
+			delay: BlockNumberFor<T>,
+			limits: ProxyLimitsOf<T>,
+		},
+		/// An expired proxy was removed.
+		ProxyExpired {
+			delegator: T::AccountId,
+			delegatee: T::AccountId,
+			proxy_type: T::ProxyType,
+			delay: BlockNumberFor<T>,
+		},
+		/// A proxy spent its whole allowance for the current period.
+		AllowanceExhausted {
+			delegator: T::AccountId,
+			delegatee: T::AccountId,
+			proxy_type: T::ProxyType,
+			period_start: BlockNumberFor<T>,
+		},
 	}
 
 	#[pallet::error]
@@ -725,6 +944,18 @@ pub mod pallet {
 		Unannounced,
 		/// Cannot add self as proxy.
 		NoSelfProxy,
+		/// The proxy has expired.
+		Expired,
+		/// The proxy has not expired.
+		NotExpired,
+		/// The call transfers more than the remaining allowance of the proxy.
+		AllowanceExceeded,
+		/// The limits are already reached, e.g. the expiry is in the past or the allowance period
+		/// is zero.
+		InvalidLimits,
+		/// Spending allowances are not supported since calls are not measured, see
+		/// [`Config::CallSpending`].
+		AllowanceUnsupported,
 	}
 
 	/// The set of account proxies. Maps the account which has delegated to the accounts
@@ -757,6 +988,31 @@ pub mod pallet {
 		ValueQuery,
 	>;
 
+	/// The limits of the proxies, by proxied account and proxy definition.
+	#[pallet::storage]
+	pub type Limits<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		ProxyDefinitionOf<T>,
+		ProxyLimitsOf<T>,
+		OptionQuery,
+	>;
+
+	/// The value spent by the proxies with a spending allowance in their current period, by
+	/// proxied account and proxy definition.
+	#[pallet::storage]
+	pub type Spent<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		ProxyDefinitionOf<T>,
+		Spending<BalanceOf<T>, BlockNumberFor<T>>,
+		OptionQuery,
+	>;
+
 	#[pallet::view_functions]
 	impl<T: Config> Pallet<T> {
 		/// Check if a `RuntimeCall` is allowed for a given `ProxyType`.
@@ -771,6 +1027,21 @@ pub mod pallet {
 		pub fn is_superset(to_check: T::ProxyType, against: T::ProxyType) -> bool {
 			to_check.is_superset(&against)
 		}
+
+		/// The value a proxy of `real` may still transfer in the current period.
+		///
+		/// Returns `None` if the proxy doesn't exist or has no spending allowance.
+		pub fn remaining_allowance(
+			real: T::AccountId,
+			delegate: T::AccountId,
+			proxy_type: T::ProxyType,
+			delay: BlockNumberFor<T>,
+		) -> Option<BalanceOf<T>> {
+			let def = ProxyDefinition { delegate, proxy_type, delay };
+			let allowance = Limits::<T>::get(&real, &def)?.allowance?;
+			let (_, spent) = Self::spent_in_period(&real, &def, &allowance);
+			Some(allowance.amount.saturating_sub(spent))
+		}
 	}
 }
 
@@ -888,6 +1159,8 @@ impl<T: Config> Pallet<T> {
 			};
 			let i = proxies.binary_search(&proxy_def).ok().ok_or(Error::<T>::NotFound)?;
 			proxies.remove(i);
+			Limits::<T>::remove(delegator, &proxy_def);
+			Spent::<T>::remove(delegator, &proxy_def);
 			let new_deposit = Self::deposit(proxies.len() as u32);
 			if new_deposit > old_deposit {
 				T::Currency::reserve(delegator, new_deposit - old_deposit)?;
@@ -962,6 +1235,9 @@ impl<T: Config> Pallet<T> {
 		})
 	}
 
+	/// Find a proxy of `real` for `delegate`, with the given proxy type if any.
+	///
+	/// Expired proxies are skipped, `Expired` is returned if only expired proxies match.
 	pub fn find_proxy(
 		real: &T::AccountId,
 		delegate: &T::AccountId,
@@ -971,24 +1247,72 @@ impl<T: Config> Pallet<T> {
 			&x.delegate == delegate &&
 				force_proxy_type.as_ref().map_or(true, |y| &x.proxy_type == y)
 		};
-		Ok(Proxies::<T>::get(real).0.into_iter().find(f).ok_or(Error::<T>::NotProxy)?)
+		let mut matching = Proxies::<T>::get(real).0.into_iter().filter(f).peekable();
+		ensure!(matching.peek().is_some(), Error::<T>::NotProxy);
+		Ok(matching
+			.find(|def| !Limits::<T>::get(real, def).map_or(false, |l| Self::is_expired(&l)))
+			.ok_or(Error::<T>::Expired)?)
+	}
+
+	/// Whether a proxy with the given `limits` has expired.
+	fn is_expired(limits: &ProxyLimitsOf<T>) -> bool {
+		limits
+			.expiry
+			.map_or(false, |expiry| expiry <= T::BlockNumberProvider::current_block_number())
+	}
+
+	/// The start of the current `allowance` period and the value spent within it by the proxy
+	/// `def` of `real`.
+	fn spent_in_period(
+		real: &T::AccountId,
+		def: &ProxyDefinitionOf<T>,
+		allowance: &SpendingAllowance<BalanceOf<T>, BlockNumberFor<T>>,
+	) -> (BlockNumberFor<T>, BalanceOf<T>) {
+		let now = T::BlockNumberProvider::current_block_number();
+		let period_start = now.saturating_sub(now % allowance.period.max(One::one()));
+		let spent = Spent::<T>::get(real, def)
+			.filter(|spending| spending.period_start == period_start)
+			.map_or_else(Zero::zero, |spending| spending.spent);
+		(period_start, spent)
+	}
+
+	/// Remove the limits of all the proxies of `real`.
+	fn clear_limits(real: &T::AccountId) {
+		let _ = Limits::<T>::clear_prefix(real, T::MaxProxies::get(), None);
+		let _ = Spent::<T>::clear_prefix(real, T::MaxProxies::get(), None);
 	}
 
 	fn do_proxy(
 		def: ProxyDefinition<T::AccountId, T::ProxyType, BlockNumberFor<T>>,
 		real: T::AccountId,
 		call: <T as Config>::RuntimeCall,
-	) {
+	) -> DispatchResult {
 		use frame::traits::{InstanceFilter as _, OriginTrait as _};
+
+		let limits = Limits::<T>::get(&real, &def);
+		let spending = match limits.as_ref().and_then(|limits| limits.allowance) {
+			Some(allowance) => {
+				let (period_start, spent) = Self::spent_in_period(&real, &def, &allowance);
+				let spent = spent.saturating_add(T::CallSpending::spending(&real, &call));
+				ensure!(spent <= allowance.amount, Error::<T>::AllowanceExceeded);
+				Some((allowance, Spending { period_start, spent }))
+			},
+			None => None,
+		};
+
 		// This is a freshly authenticated new account, the origin restrictions doesn't apply.
-		let mut origin: T::RuntimeOrigin = frame_system::RawOrigin::Signed(real).into();
+		let mut origin: T::RuntimeOrigin = frame_system::RawOrigin::Signed(real.clone()).into();
+		let filter_def = def.clone();
+		let limited = limits.is_some();
 		origin.add_filter(move |c: &<T as frame_system::Config>::RuntimeCall| {
+			let def = &filter_def;
 			let c = <T as Config>::RuntimeCall::from_ref(c);
 			// We make sure the proxy call does access this pallet to change modify proxies.
 			match c.is_sub_type() {
 				// Proxy call cannot add or remove a proxy with more permissions than it already
 				// has.
 				Some(Call::add_proxy { ref proxy_type, .. }) |
+				Some(Call::add_limited_proxy { ref proxy_type, .. }) |
 				Some(Call::remove_proxy { ref proxy_type, .. })
 					if !def.proxy_type.is_superset(proxy_type) =>
 					false,
@@ -997,11 +1321,32 @@ impl<T: Config> Pallet<T> {
 				Some(Call::remove_proxies { .. }) | Some(Call::kill_pure { .. })
 					if def.proxy_type != T::ProxyType::default() =>
 					false,
+				// Limited proxy call cannot escape its limits by managing proxies.
+				Some(Call::add_proxy { .. }) |
+				Some(Call::add_limited_proxy { .. }) |
+				Some(Call::create_pure { .. }) |
+				Some(Call::remove_proxies { .. }) |
+				Some(Call::kill_pure { .. })
+					if limited =>
+					false,
 				_ => def.proxy_type.filter(c),
 			}
 		});
 		let e = call.dispatch(origin);
+
+		if let (Ok(_), Some((allowance, spending))) = (&e, spending) {
+			Spent::<T>::insert(&real, &def, spending);
+			if spending.spent >= allowance.amount {
+				Self::deposit_event(Event::AllowanceExhausted {
+					delegator: real,
+					delegatee: def.delegate,
+					proxy_type: def.proxy_type,
+					period_start: spending.period_start,
+				});
+			}
+		}
 		Self::deposit_event(Event::ProxyExecuted { result: e.map(|_| ()).map_err(|e| e.error) });
+		Ok(())
 	}
 
 	/// Removes all proxy delegates for a given delegator.
@@ -1010,6 +1355,7 @@ impl<T: Config> Pallet<T> {
 	/// - `delegator`: The delegator account.
 	pub fn remove_all_proxy_delegates(delegator: &T::AccountId) {
 		let (_, old_deposit) = Proxies::<T>::take(&delegator);
+		Self::clear_limits(delegator);
 		T::Currency::unreserve(&delegator, old_deposit);
 	}
 }
@@ -113,6 +113,7 @@ parameter_types! {
 	pub static ProxyDepositFactor: u64 = 1;
 	pub static AnnouncementDepositBase: u64 = 1;
 	pub static AnnouncementDepositFactor: u64 = 1;
+	pub static MeasuresCalls: bool = true;
 }
 
 impl Config for Test {
@@ -129,6 +130,28 @@ impl Config for Test {
 	type AnnouncementDepositBase = AnnouncementDepositBase;
 	type AnnouncementDepositFactor = AnnouncementDepositFactor;
 	type BlockNumberProvider = frame_system::Pallet<Test>;
+	type CallSpending = TransferSpending;
+}
+
+pub struct TransferSpending;
+impl CallSpending<u64, RuntimeCall, u64> for TransferSpending {
+	fn measures_calls() -> bool {
+		MeasuresCalls::get()
+	}
+
+	fn spending(who: &u64, call: &RuntimeCall) -> u64 {
+		match call {
+			RuntimeCall::Balances(BalancesCall::transfer_allow_death { value, .. }) |
+			RuntimeCall::Balances(BalancesCall::transfer_keep_alive { value, .. }) => *value,
+			RuntimeCall::Balances(BalancesCall::transfer_all { .. }) => Balances::free_balance(who),
+			RuntimeCall::Utility(UtilityCall::batch { calls }) |
+			RuntimeCall::Utility(UtilityCall::batch_all { calls }) |
+			RuntimeCall::Utility(UtilityCall::force_batch { calls }) =>
+				calls.iter().map(|call| Self::spending(who, call)).fold(0, u64::saturating_add),
+			RuntimeCall::System(..) | RuntimeCall::Proxy(..) => 0,
+			_ => u64::MAX,
+		}
+	}
 }
 
 use super::{Call as ProxyCall, Event as ProxyEvent};
@@ -836,3 +859,234 @@ fn poke_deposit_fails_for_unsigned_origin() {
 		assert_noop!(Proxy::poke_deposit(RuntimeOrigin::none()), DispatchError::BadOrigin,);
 	});
 }
+
+#[test]
+fn expiring_proxy_works() {
+	new_test_ext().execute_with(|| {
+		let limits = ProxyLimits { expiry: Some(5), allowance: None };
+		assert_noop!(
+			Proxy::add_limited_proxy(
+				RuntimeOrigin::signed(1),
+				3,
+				ProxyType::Any,
+				0,
+				ProxyLimits { expiry: Some(1), allowance: None },
+			),
+			Error::<Test>::InvalidLimits
+		);
+		assert_ok!(Proxy::add_limited_proxy(
+			RuntimeOrigin::signed(1),
+			3,
+			ProxyType::Any,
+			0,
+			limits
+		));
+		System::assert_last_event(
+			ProxyEvent::ProxyLimitsSet {
+				delegator: 1,
+				delegatee: 3,
+				proxy_type: ProxyType::Any,
+				delay: 0,
+				limits,
+			}
+			.into(),
+		);
+		assert_eq!(Balances::reserved_balance(1), 2);
+
+		let call = Box::new(call_transfer(6, 1));
+		assert_ok!(Proxy::proxy(RuntimeOrigin::signed(3), 1, None, call.clone()));
+		System::assert_last_event(ProxyEvent::ProxyExecuted { result: Ok(()) }.into());
+		assert_noop!(
+			Proxy::remove_expired_proxy(RuntimeOrigin::signed(4), 1, 3, ProxyType::Any, 0),
+			Error::<Test>::NotExpired
+		);
+
+		System::set_block_number(5);
+		assert_noop!(Proxy::proxy(RuntimeOrigin::signed(3), 1, None, call), Error::<Test>::Expired);
+
+		// Anyone may clean up the expired proxy.
+		assert_ok!(Proxy::remove_expired_proxy(RuntimeOrigin::signed(4), 1, 3, ProxyType::Any, 0));
+		System::assert_last_event(
+			ProxyEvent::ProxyExpired {
+				delegator: 1,
+				delegatee: 3,
+				proxy_type: ProxyType::Any,
+				delay: 0,
+			}
+			.into(),
+		);
+		assert!(Proxies::<Test>::get(1).0.is_empty());
+		assert_eq!(Limits::<Test>::iter_prefix(1).count(), 0);
+		assert_eq!(Balances::reserved_balance(1), 0);
+	});
+}
+
+#[test]
+fn expired_proxy_is_skipped_for_other_proxy_types() {
+	new_test_ext().execute_with(|| {
+		let limits = ProxyLimits { expiry: Some(3), allowance: None };
+		assert_ok!(Proxy::add_limited_proxy(
+			RuntimeOrigin::signed(1),
+			3,
+			ProxyType::Any,
+			0,
+			limits
+		));
+		assert_ok!(Proxy::add_proxy(RuntimeOrigin::signed(1), 3, ProxyType::JustTransfer, 0));
+
+		System::set_block_number(3);
+		let call = Box::new(call_transfer(6, 1));
+		assert_ok!(Proxy::proxy(RuntimeOrigin::signed(3), 1, None, call.clone()));
+		System::assert_last_event(ProxyEvent::ProxyExecuted { result: Ok(()) }.into());
+		assert_noop!(
+			Proxy::proxy(RuntimeOrigin::signed(3), 1, Some(ProxyType::Any), call),
+			Error::<Test>::Expired
+		);
+	});
+}
+
+#[test]
+fn spending_allowance_works() {
+	new_test_ext().execute_with(|| {
+		let limits = ProxyLimits {
+			expiry: None,
+			allowance: Some(SpendingAllowance { amount: 5, period: 10 }),
+		};
+		assert_ok!(Proxy::add_limited_proxy(
+			RuntimeOrigin::signed(1),
+			3,
+			ProxyType::Any,
+			0,
+			limits
+		));
+		assert_eq!(Proxy::remaining_allowance(1, 3, ProxyType::Any, 0), Some(5));
+		assert_eq!(Proxy::remaining_allowance(1, 3, ProxyType::JustTransfer, 0), None);
+
+		assert_ok!(Proxy::proxy(RuntimeOrigin::signed(3), 1, None, Box::new(call_transfer(6, 3))));
+		assert_eq!(Balances::free_balance(6), 3);
+		assert_eq!(Proxy::remaining_allowance(1, 3, ProxyType::Any, 0), Some(2));
+
+		// Transfers nested in batches are counted as well.
+		let batch = RuntimeCall::Utility(UtilityCall::batch {
+			calls: vec![call_transfer(6, 1), call_transfer(6, 2)],
+		});
+		assert_noop!(
+			Proxy::proxy(RuntimeOrigin::signed(3), 1, None, Box::new(batch)),
+			Error::<Test>::AllowanceExceeded
+		);
+
+		// Calls which aren't known to spend nothing exceed the allowance.
+		let derivative = RuntimeCall::Utility(UtilityCall::as_derivative {
+			index: 0,
+			call: Box::new(call_transfer(6, 1)),
+		});
+		assert_noop!(
+			Proxy::proxy(RuntimeOrigin::signed(3), 1, None, Box::new(derivative)),
+			Error::<Test>::AllowanceExceeded
+		);
+
+		// Failed calls are not counted.
+		let batch = RuntimeCall::Utility(UtilityCall::batch_all {
+			calls: vec![
+				call_transfer(6, 1),
+				RuntimeCall::System(SystemCall::kill_storage { keys: vec![] }),
+			],
+		});
+		assert_ok!(Proxy::proxy(RuntimeOrigin::signed(3), 1, None, Box::new(batch)));
+		System::assert_last_event(
+			ProxyEvent::ProxyExecuted { result: Err(SystemError::CallFiltered.into()) }.into(),
+		);
+		assert_eq!(Proxy::remaining_allowance(1, 3, ProxyType::Any, 0), Some(2));
+
+		assert_ok!(Proxy::proxy(RuntimeOrigin::signed(3), 1, None, Box::new(call_transfer(6, 2))));
+		expect_events(vec![
+			BalancesEvent::Transfer { from: 1, to: 6, amount: 2 }.into(),
+			ProxyEvent::AllowanceExhausted {
+				delegator: 1,
+				delegatee: 3,
+				proxy_type: ProxyType::Any,
+				period_start: 0,
+			}
+			.into(),
+			ProxyEvent::ProxyExecuted { result: Ok(()) }.into(),
+		]);
+		assert_eq!(Proxy::remaining_allowance(1, 3, ProxyType::Any, 0), Some(0));
+
+		// The allowance is renewed in the next period.
+		System::set_block_number(10);
+		assert_eq!(Proxy::remaining_allowance(1, 3, ProxyType::Any, 0), Some(5));
+		assert_ok!(Proxy::proxy(RuntimeOrigin::signed(3), 1, None, Box::new(call_transfer(6, 1))));
+		assert_eq!(Proxy::remaining_allowance(1, 3, ProxyType::Any, 0), Some(4));
+
+		// Removing the proxy removes its limits.
+		assert_ok!(Proxy::remove_proxy(RuntimeOrigin::signed(1), 3, ProxyType::Any, 0));
+		assert_eq!(Limits::<Test>::iter_prefix(1).count(), 0);
+		assert_eq!(Spent::<Test>::iter_prefix(1).count(), 0);
+	});
+}
+
+#[test]
+fn limited_proxy_cannot_manage_proxies() {
+	new_test_ext().execute_with(|| {
+		let limits = ProxyLimits { expiry: Some(10), allowance: None };
+		assert_ok!(Proxy::add_limited_proxy(
+			RuntimeOrigin::signed(1),
+			3,
+			ProxyType::Any,
+			0,
+			limits
+		));
+
+		let call = Box::new(RuntimeCall::Proxy(ProxyCall::add_proxy {
+			delegate: 4,
+			proxy_type: ProxyType::Any,
+			delay: 0,
+		}));
+		assert_ok!(Proxy::proxy(RuntimeOrigin::signed(3), 1, None, call));
+		System::assert_last_event(
+			ProxyEvent::ProxyExecuted { result: Err(SystemError::CallFiltered.into()) }.into(),
+		);
+
+		let call = Box::new(RuntimeCall::Proxy(ProxyCall::remove_proxies {}));
+		assert_ok!(Proxy::proxy(RuntimeOrigin::signed(3), 1, None, call));
+		System::assert_last_event(
+			ProxyEvent::ProxyExecuted { result: Err(SystemError::CallFiltered.into()) }.into(),
+		);
+
+		// The proxy may still remove itself.
+		let call = Box::new(RuntimeCall::Proxy(ProxyCall::remove_proxy {
+			delegate: 3,
+			proxy_type: ProxyType::Any,
+			delay: 0,
+		}));
+		assert_ok!(Proxy::proxy(RuntimeOrigin::signed(3), 1, None, call));
+		System::assert_last_event(ProxyEvent::ProxyExecuted { result: Ok(()) }.into());
+		assert!(Proxies::<Test>::get(1).0.is_empty());
+	});
+}
+
+#[test]
+fn allowance_requires_measured_calls() {
+	new_test_ext().execute_with(|| {
+		MeasuresCalls::set(false);
+		let limits = ProxyLimits {
+			expiry: None,
+			allowance: Some(SpendingAllowance { amount: 5, period: 10 }),
+		};
+		assert_noop!(
+			Proxy::add_limited_proxy(RuntimeOrigin::signed(1), 3, ProxyType::Any, 0, limits),
+			Error::<Test>::AllowanceUnsupported
+		);
+
+		// Proxies may still expire.
+		let limits = ProxyLimits { expiry: Some(5), allowance: None };
+		assert_ok!(Proxy::add_limited_proxy(
+			RuntimeOrigin::signed(1),
+			3,
+			ProxyType::Any,
+			0,
+			limits
+		));
+		MeasuresCalls::set(true);
+	});
+}
@@ -124,6 +124,27 @@ fn setup_active_recovery_with_max_friends<T: Config>(
 	<ActiveRecoveries<T>>::insert(lost_account, caller, active_recovery);
 }
 
+fn insert_pending_update<T: Config>(account: &T::AccountId, n: u32) {
+	let friends = generate_friends::<T>(n);
+	let bounded_friends: FriendsOf<T> = friends.try_into().unwrap();
+
+	// Get deposit for the new configuration
+	let total_deposit = get_total_deposit::<T>(&bounded_friends).unwrap();
+
+	let update = PendingRecoveryUpdate {
+		executable_at: DEFAULT_DELAY.into(),
+		deposit: total_deposit,
+		friends: bounded_friends,
+		threshold: n as u16,
+		delay_period: DEFAULT_DELAY.into(),
+	};
+
+	// Reserve deposit for the new configuration
+	T::Currency::reserve(&account, total_deposit).unwrap();
+
+	<PendingRecoveryUpdates<T>>::insert(&account, update);
+}
+
 #[benchmarks]
 mod benchmarks {
 	use super::*;
@@ -465,5 +486,57 @@ mod benchmarks {
 		Ok(())
 	}
 
+	#[benchmark]
+	fn update_recovery(n: Linear<1, { T::MaxFriends::get() }>) {
+		let caller: T::AccountId = whitelisted_caller();
+
+		// Replace a previously scheduled update with the maximum number of friends
+		insert_recovery_config_with_max_friends::<T>(&caller);
+		insert_pending_update::<T>(&caller, T::MaxFriends::get());
+
+		// Create friends
+		let friends = generate_friends::<T>(n);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), friends, n as u16, DEFAULT_DELAY.into());
+
+		let update = <PendingRecoveryUpdates<T>>::get(&caller).unwrap();
+		assert_eq!(update.friends.len(), n as usize);
+		assert_last_event::<T>(
+			Event::RecoveryUpdateScheduled { account: caller, executable_at: update.executable_at }
+				.into(),
+		);
+	}
+
+	#[benchmark]
+	fn cancel_recovery_update(n: Linear<1, { T::MaxFriends::get() }>) {
+		let caller: T::AccountId = whitelisted_caller();
+
+		insert_recovery_config_with_max_friends::<T>(&caller);
+		insert_pending_update::<T>(&caller, n);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()));
+
+		assert!(!<PendingRecoveryUpdates<T>>::contains_key(&caller));
+		assert_last_event::<T>(Event::RecoveryUpdateCancelled { account: caller }.into());
+	}
+
+	#[benchmark]
+	fn apply_recovery_update(n: Linear<1, { T::MaxFriends::get() }>) {
+		let caller: T::AccountId = whitelisted_caller();
+		let account: T::AccountId = account("account", 0, SEED);
+		let account_lookup = T::Lookup::unlookup(account.clone());
+
+		insert_recovery_config_with_max_friends::<T>(&account);
+		insert_pending_update::<T>(&account, n);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), account_lookup);
+
+		assert_eq!(<Recoverable<T>>::get(&account).unwrap().friends.len(), n as usize);
+		assert_last_event::<T>(Event::RecoveryUpdated { account }.into());
+	}
+
 	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
 }
@@ -115,6 +115,23 @@
 //!   your recovery configuration as your life changes and your relationship with new and existing
 //!   friends change as well.
 //!
+//! ### Updating the Friends
+//!
+//! The account owner can change the friends, threshold and delay period of an existing recovery
+//! configuration in place with `update_recovery`. The update only takes effect once the current
+//! `delay_period` has passed and there are no active recovery attempts for the account, so an
+//! attacker who obtained the key cannot immediately replace the friends with accounts they
+//! control. Until it is applied, the owner can withdraw the update with `cancel_recovery_update`,
+//! or recover the account through the current friends, which drops the update once the recovery
+//! is claimed.
+//!
+//! ### Friends Backed by Multiple Keys
+//!
+//! A friend is just an account which calls `vouch_recovery`. This means a friend can be a
+//! multisig account or a (pure) proxy account, which then vouches by dispatching
+//! `vouch_recovery` through the multisig or proxy pallet of the runtime. A recovered account can
+//! also keep vouching for others through `as_recovered`.
+//!
 //! ## Interface
 //!
 //! ### Dispatchable Functions
@@ -141,6 +158,13 @@
 //!   deposit.
 //! * `remove_recovery` - Remove the recovery configuration from the account, making it
 //!   un-recoverable.
+//! * `update_recovery` - Schedule a change of the friends, threshold and delay period of the
+//!   recovery configuration.
+//! * `cancel_recovery_update` - Cancel a scheduled change of the recovery configuration.
+//!
+//! #### For Anyone
+//!
+//! * `apply_recovery_update` - Apply a scheduled change of a recovery configuration once it is due.
 //!
 //! #### For Super Users
 //!
@@ -167,6 +191,7 @@ mod benchmarking;
 
 #[cfg(test)]
 mod mock;
+pub mod runtime_api;
 #[cfg(test)]
 mod tests;
 pub mod weights;
@@ -206,6 +231,23 @@ pub struct RecoveryConfig<BlockNumber, Balance, Friends> {
 	pub threshold: u16,
 }
 
+/// A scheduled update of the recovery configuration of an account.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, Default, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct PendingRecoveryUpdate<BlockNumber, Balance, Friends> {
+	/// The block number from which the update can be applied.
+	pub executable_at: BlockNumber,
+	/// The amount held in reserve of the `depositor` for the new configuration.
+	///
+	/// Once the update is applied this becomes the deposit of the recovery configuration.
+	pub deposit: Balance,
+	/// The new list of friends. Always sorted.
+	pub friends: Friends,
+	/// The new number of approving friends needed to recover the account.
+	pub threshold: u16,
+	/// The new delay period of the recovery configuration.
+	pub delay_period: BlockNumber,
+}
+
 /// The type of deposit
 #[derive(
 	Clone,
@@ -337,6 +379,15 @@ pub mod pallet {
 			old_deposit: BalanceOf<T>,
 			new_deposit: BalanceOf<T>,
 		},
+		/// An update of the recovery configuration has been scheduled for an account.
+		RecoveryUpdateScheduled {
+			account: T::AccountId,
+			executable_at: BlockNumberFromProviderOf<T>,
+		},
+		/// A scheduled update of the recovery configuration has been cancelled.
+		RecoveryUpdateCancelled { account: T::AccountId },
+		/// A scheduled update of the recovery configuration has been applied.
+		RecoveryUpdated { account: T::AccountId },
 	}
 
 	#[pallet::error]
@@ -373,6 +424,10 @@ pub mod pallet {
 		AlreadyProxy,
 		/// Some internal state is broken.
 		BadState,
+		/// No recovery configuration update is scheduled for this account
+		UpdateNotScheduled,
+		/// The scheduled recovery configuration update cannot be applied yet
+		UpdateNotReady,
 	}
 
 	/// The set of recoverable accounts and their recovery configuration.
@@ -400,6 +455,17 @@ pub mod pallet {
 		ActiveRecovery<BlockNumberFromProviderOf<T>, BalanceOf<T>, FriendsOf<T>>,
 	>;
 
+	/// Scheduled updates of recovery configurations.
+	///
+	/// Map from the recoverable account to the configuration it will switch to.
+	#[pallet::storage]
+	pub type PendingRecoveryUpdates<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		PendingRecoveryUpdate<BlockNumberFromProviderOf<T>, BalanceOf<T>, FriendsOf<T>>,
+	>;
+
 	/// The list of allowed proxy accounts.
 	///
 	/// Map from the user who can access it to the recovered account.
@@ -494,12 +560,7 @@ pub mod pallet {
 			// Check account is not already set up for recovery
 			ensure!(!<Recoverable<T>>::contains_key(&who), Error::<T>::AlreadyRecoverable);
 			// Check user input is valid
-			ensure!(threshold >= 1, Error::<T>::ZeroThreshold);
-			ensure!(!friends.is_empty(), Error::<T>::NotEnoughFriends);
-			ensure!(threshold as usize <= friends.len(), Error::<T>::NotEnoughFriends);
-			let bounded_friends: FriendsOf<T> =
-				friends.try_into().map_err(|_| Error::<T>::MaxFriends)?;
-			ensure!(Self::is_sorted_and_unique(&bounded_friends), Error::<T>::NotSorted);
+			let bounded_friends = Self::validate_friends(friends, threshold)?;
 			// Calculate total deposit required
 			let total_deposit = Self::get_recovery_config_deposit(bounded_friends.len())?;
 			// Reserve the deposit
@@ -615,11 +676,16 @@ pub mod pallet {
 		/// who has successfully completed the account recovery process: collected
 		/// `threshold` or more vouches, waited `delay_period` blocks since initiation.
 		///
+		/// Any scheduled update of the recovery configuration of the account is dropped and its
+		/// deposit returned to the account, so that the update can't replace the friends who
+		/// just recovered it.
+		///
 		/// Parameters:
 		/// - `account`: The lost account that you want to claim has been successfully recovered by
 		///   you.
 		#[pallet::call_index(5)]
-		#[pallet::weight(T::WeightInfo::claim_recovery(T::MaxFriends::get()))]
+		#[pallet::weight(T::WeightInfo::claim_recovery(T::MaxFriends::get())
+			.saturating_add(T::DbWeight::get().reads_writes(2, 2)))]
 		pub fn claim_recovery(
 			origin: OriginFor<T>,
 			account: AccountIdLookupOf<T>,
@@ -648,6 +714,13 @@ pub mod pallet {
 			frame_system::Pallet::<T>::inc_consumers(&who).map_err(|_| Error::<T>::BadState)?;
 			// Create the recovery storage item
 			Proxy::<T>::insert(&who, &account);
+			// The update may have been scheduled by whoever took over the account.
+			if let Some(update) = <PendingRecoveryUpdates<T>>::take(&account) {
+				T::Currency::unreserve(&account, update.deposit);
+				Self::deposit_event(Event::<T>::RecoveryUpdateCancelled {
+					account: account.clone(),
+				});
+			}
 			Self::deposit_event(Event::<T>::AccountRecovered {
 				lost_account: account,
 				rescuer_account: who,
@@ -705,7 +778,8 @@ pub mod pallet {
 		/// The dispatch origin for this call must be _Signed_ and must be a
 		/// recoverable account (i.e. has a recovery configuration).
 		#[pallet::call_index(7)]
-		#[pallet::weight(T::WeightInfo::remove_recovery(T::MaxFriends::get()))]
+		#[pallet::weight(T::WeightInfo::remove_recovery(T::MaxFriends::get())
+			.saturating_add(T::DbWeight::get().reads_writes(1, 1)))]
 		pub fn remove_recovery(origin: OriginFor<T>) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 			// Check there are no active recoveries
@@ -716,6 +790,10 @@ pub mod pallet {
 
 			// Unreserve the initial deposit for the recovery configuration.
 			T::Currency::unreserve(&who, recovery_config.deposit);
+			// Drop any scheduled update together with its deposit.
+			if let Some(update) = <PendingRecoveryUpdates<T>>::take(&who) {
+				T::Currency::unreserve(&who, update.deposit);
+			}
 			Self::deposit_event(Event::<T>::RecoveryRemoved { lost_account: who });
 			Ok(())
 		}
@@ -786,6 +864,136 @@ pub mod pallet {
 
 			Ok(if deposit_updated { Pays::No } else { Pays::Yes }.into())
 		}
+
+		/// Schedule an update of the recovery configuration of your account.
+		///
+		/// The update can be applied with `apply_recovery_update` once the `delay_period` of the
+		/// current configuration has passed. Until then the current friends stay in charge of
+		/// the account, and the update can be cancelled with `cancel_recovery_update`. An update
+		/// cannot be applied while there are active recovery attempts for the account, so if
+		/// the update was scheduled by someone who gained access to your key, you can still
+		/// recover your account with the current friends.
+		///
+		/// Payment: `ConfigDepositBase` + `FriendDepositFactor` * #_of_friends balance
+		/// will be reserved for the new configuration. The deposit of the current configuration
+		/// is returned once the update is applied. Scheduling an update replaces any previously
+		/// scheduled one and returns its deposit.
+		///
+		/// The dispatch origin for this call must be _Signed_ and must be a
+		/// recoverable account (i.e. has a recovery configuration).
+		///
+		/// Parameters:
+		/// - `friends`: The new list of friends. Should be ordered and contain no duplicate values.
+		/// - `threshold`: The new number of friends that must vouch for a recovery attempt.
+		/// - `delay_period`: The new number of blocks after a recovery attempt is initialized that
+		///   needs to pass before the account can be recovered.
+		#[pallet::call_index(10)]
+		#[pallet::weight(T::WeightInfo::update_recovery(friends.len() as u32))]
+		pub fn update_recovery(
+			origin: OriginFor<T>,
+			friends: Vec<T::AccountId>,
+			threshold: u16,
+			delay_period: BlockNumberFromProviderOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let recovery_config = Self::recovery_config(&who).ok_or(Error::<T>::NotRecoverable)?;
+			let bounded_friends = Self::validate_friends(friends, threshold)?;
+			let executable_at = T::BlockNumberProvider::current_block_number()
+				.checked_add(&recovery_config.delay_period)
+				.ok_or(ArithmeticError::Overflow)?;
+			// Replace the deposit of a previously scheduled update.
+			if let Some(previous) = <PendingRecoveryUpdates<T>>::get(&who) {
+				T::Currency::unreserve(&who, previous.deposit);
+			}
+			let deposit = Self::get_recovery_config_deposit(bounded_friends.len())?;
+			T::Currency::reserve(&who, deposit)?;
+			let update = PendingRecoveryUpdate {
+				executable_at,
+				deposit,
+				friends: bounded_friends,
+				threshold,
+				delay_period,
+			};
+			<PendingRecoveryUpdates<T>>::insert(&who, update);
+
+			Self::deposit_event(Event::<T>::RecoveryUpdateScheduled {
+				account: who,
+				executable_at,
+			});
+			Ok(())
+		}
+
+		/// Cancel the scheduled update of the recovery configuration of your account.
+		///
+		/// Payment: The deposit reserved for the scheduled configuration is returned.
+		///
+		/// The dispatch origin for this call must be _Signed_ and must be a
+		/// recoverable account with a scheduled update.
+		#[pallet::call_index(11)]
+		#[pallet::weight(T::WeightInfo::cancel_recovery_update(T::MaxFriends::get()))]
+		pub fn cancel_recovery_update(origin: OriginFor<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let update =
+				<PendingRecoveryUpdates<T>>::take(&who).ok_or(Error::<T>::UpdateNotScheduled)?;
+			T::Currency::unreserve(&who, update.deposit);
+			Self::deposit_event(Event::<T>::RecoveryUpdateCancelled { account: who });
+			Ok(())
+		}
+
+		/// Apply the scheduled update of the recovery configuration of an account.
+		///
+		/// The update replaces the friends, threshold and delay period of the configuration in
+		/// place. The deposit of the previous configuration is returned to the account.
+		///
+		/// The dispatch origin for this call must be _Signed_.
+		///
+		/// Parameters:
+		/// - `account`: The recoverable account whose scheduled update should be applied. The
+		///   update must be due and there must be no active recovery attempts for the account.
+		#[pallet::call_index(12)]
+		#[pallet::weight(T::WeightInfo::apply_recovery_update(T::MaxFriends::get()))]
+		pub fn apply_recovery_update(
+			origin: OriginFor<T>,
+			account: AccountIdLookupOf<T>,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+			let account = T::Lookup::lookup(account)?;
+			let update =
+				<PendingRecoveryUpdates<T>>::get(&account).ok_or(Error::<T>::UpdateNotScheduled)?;
+			ensure!(
+				update.executable_at <= T::BlockNumberProvider::current_block_number(),
+				Error::<T>::UpdateNotReady
+			);
+			// Vouches collected under the current friends must not count for the new ones.
+			let mut active_recoveries = <ActiveRecoveries<T>>::iter_prefix_values(&account);
+			ensure!(active_recoveries.next().is_none(), Error::<T>::StillActive);
+			let recovery_config =
+				<Recoverable<T>>::get(&account).ok_or(Error::<T>::NotRecoverable)?;
+
+			let remaining_unreserved = T::Currency::unreserve(&account, recovery_config.deposit);
+			if !remaining_unreserved.is_zero() {
+				defensive!(
+					"Failed to unreserve full amount. (Requested, Actual)",
+					(
+						recovery_config.deposit,
+						recovery_config.deposit.saturating_sub(remaining_unreserved)
+					)
+				);
+			}
+			<PendingRecoveryUpdates<T>>::remove(&account);
+			<Recoverable<T>>::insert(
+				&account,
+				RecoveryConfig {
+					delay_period: update.delay_period,
+					deposit: update.deposit,
+					friends: update.friends,
+					threshold: update.threshold,
+				},
+			);
+
+			Self::deposit_event(Event::<T>::RecoveryUpdated { account });
+			Ok(())
+		}
 	}
 }
 
@@ -795,6 +1003,20 @@ impl<T: Config> Pallet<T> {
 		friends.windows(2).all(|w| w[0] < w[1])
 	}
 
+	/// Check that a friends list and threshold make a valid recovery configuration.
+	fn validate_friends(
+		friends: Vec<T::AccountId>,
+		threshold: u16,
+	) -> Result<FriendsOf<T>, DispatchError> {
+		ensure!(threshold >= 1, Error::<T>::ZeroThreshold);
+		ensure!(!friends.is_empty(), Error::<T>::NotEnoughFriends);
+		ensure!(threshold as usize <= friends.len(), Error::<T>::NotEnoughFriends);
+		let bounded_friends: FriendsOf<T> =
+			friends.try_into().map_err(|_| Error::<T>::MaxFriends)?;
+		ensure!(Self::is_sorted_and_unique(&bounded_friends), Error::<T>::NotSorted);
+		Ok(bounded_friends)
+	}
+
 	/// Check that a user is a friend in the friends list.
 	fn is_friend(friends: &Vec<T::AccountId>, friend: &T::AccountId) -> bool {
 		friends.binary_search(&friend).is_ok()
@@ -811,6 +1033,27 @@ impl<T: Config> Pallet<T> {
 			.ok_or(ArithmeticError::Overflow.into())
 	}
 
+	/// All active recovery attempts for `lost`, keyed by the rescuer account.
+	pub fn active_recoveries(
+		lost: &T::AccountId,
+	) -> Vec<(
+		T::AccountId,
+		ActiveRecovery<BlockNumberFromProviderOf<T>, BalanceOf<T>, Vec<T::AccountId>>,
+	)> {
+		<ActiveRecoveries<T>>::iter_prefix(lost)
+			.map(|(rescuer, recovery)| {
+				(
+					rescuer,
+					ActiveRecovery {
+						created: recovery.created,
+						deposit: recovery.deposit,
+						friends: recovery.friends.into_inner(),
+					},
+				)
+			})
+			.collect()
+	}
+
 	/// Helper function to poke the deposit reserved for creating a recovery config
 	fn poke_recovery_config_deposit(who: &T::AccountId) -> Result<bool, DispatchError> {
 		<Recoverable<T>>::try_mutate(&who, |maybe_config| -> Result<bool, DispatchError> {
@@ -30,6 +30,7 @@ construct_runtime!(
 		System: frame_system,
 		Balances: pallet_balances,
 		Recovery: recovery,
+		Multisig: pallet_multisig,
 	}
 );
 
@@ -70,6 +71,22 @@ impl Config for Test {
 	type RecoveryDeposit = RecoveryDeposit;
 }
 
+parameter_types! {
+	pub const MultisigDepositBase: u64 = 1;
+	pub const MultisigDepositFactor: u64 = 1;
+}
+
+impl pallet_multisig::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeCall = RuntimeCall;
+	type Currency = Balances;
+	type DepositBase = MultisigDepositBase;
+	type DepositFactor = MultisigDepositFactor;
+	type MaxSignatories = ConstU32<3>;
+	type WeightInfo = ();
+	type BlockNumberProvider = System;
+}
+
 pub type BalancesCall = pallet_balances::Call<Test>;
 pub type RecoveryCall = super::Call<Test>;
 
@@ -0,0 +1,38 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Runtime API definition for the recovery pallet.
+
+use crate::ActiveRecovery;
+use alloc::vec::Vec;
+use codec::Codec;
+
+frame::deps::sp_api::decl_runtime_apis! {
+	/// The API to query the recovery state of accounts.
+	pub trait RecoveryApi<AccountId, BlockNumber, Balance>
+	where
+		AccountId: Codec,
+		BlockNumber: Codec,
+		Balance: Codec,
+	{
+		/// All active recovery attempts for the `lost` account, together with the rescuer
+		/// account that initiated each of them.
+		fn active_recoveries(
+			lost: AccountId,
+		) -> Vec<(AccountId, ActiveRecovery<BlockNumber, Balance, Vec<AccountId>>)>;
+	}
+}
@@ -491,6 +491,211 @@ fn remove_recovery_works() {
 	});
 }
 
+#[test]
+fn update_recovery_handles_basic_errors() {
+	new_test_ext().execute_with(|| {
+		// Cannot update an unrecoverable account
+		assert_noop!(
+			Recovery::update_recovery(RuntimeOrigin::signed(5), vec![2, 3], 2, 10),
+			Error::<Test>::NotRecoverable
+		);
+		assert_ok!(Recovery::create_recovery(RuntimeOrigin::signed(5), vec![2, 3, 4], 3, 10));
+		// Same input validation as creating a recovery configuration
+		assert_noop!(
+			Recovery::update_recovery(RuntimeOrigin::signed(5), vec![2, 3], 0, 10),
+			Error::<Test>::ZeroThreshold
+		);
+		assert_noop!(
+			Recovery::update_recovery(RuntimeOrigin::signed(5), vec![2, 3], 3, 10),
+			Error::<Test>::NotEnoughFriends
+		);
+		assert_noop!(
+			Recovery::update_recovery(RuntimeOrigin::signed(5), vec![3, 2], 2, 10),
+			Error::<Test>::NotSorted
+		);
+		// Nothing to cancel or apply yet
+		assert_noop!(
+			Recovery::cancel_recovery_update(RuntimeOrigin::signed(5)),
+			Error::<Test>::UpdateNotScheduled
+		);
+		assert_noop!(
+			Recovery::apply_recovery_update(RuntimeOrigin::signed(1), 5),
+			Error::<Test>::UpdateNotScheduled
+		);
+	});
+}
+
+#[test]
+fn update_recovery_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Recovery::create_recovery(RuntimeOrigin::signed(5), vec![2, 3, 4], 3, 10));
+		assert_eq!(Balances::reserved_balance(5), 13);
+		// The owner schedules new friends, which are only usable after the current delay period
+		assert_ok!(Recovery::update_recovery(RuntimeOrigin::signed(5), vec![1, 2], 2, 5));
+		System::assert_last_event(
+			Event::<Test>::RecoveryUpdateScheduled { account: 5, executable_at: 11 }.into(),
+		);
+		// Deposit for the new configuration is reserved on top
+		assert_eq!(Balances::reserved_balance(5), 25);
+		// Rescheduling replaces the previous update and its deposit
+		assert_ok!(Recovery::update_recovery(RuntimeOrigin::signed(5), vec![1, 2, 3], 2, 5));
+		assert_eq!(Balances::reserved_balance(5), 26);
+		// The owner can change their mind
+		assert_ok!(Recovery::cancel_recovery_update(RuntimeOrigin::signed(5)));
+		assert_eq!(Balances::reserved_balance(5), 13);
+		assert_eq!(PendingRecoveryUpdates::<Test>::get(5), None);
+
+		assert_ok!(Recovery::update_recovery(RuntimeOrigin::signed(5), vec![1, 2], 2, 5));
+		System::run_to_block::<AllPalletsWithSystem>(10);
+		assert_noop!(
+			Recovery::apply_recovery_update(RuntimeOrigin::signed(1), 5),
+			Error::<Test>::UpdateNotReady
+		);
+		System::run_to_block::<AllPalletsWithSystem>(11);
+		// An active recovery attempt blocks the update
+		assert_ok!(Recovery::initiate_recovery(RuntimeOrigin::signed(1), 5));
+		assert_noop!(
+			Recovery::apply_recovery_update(RuntimeOrigin::signed(1), 5),
+			Error::<Test>::StillActive
+		);
+		assert_ok!(Recovery::close_recovery(RuntimeOrigin::signed(5), 1));
+		// Anyone can apply the update once it is due
+		assert_ok!(Recovery::apply_recovery_update(RuntimeOrigin::signed(1), 5));
+		System::assert_last_event(Event::<Test>::RecoveryUpdated { account: 5 }.into());
+		let recovery_config = RecoveryConfig {
+			delay_period: 5,
+			deposit: 12,
+			friends: bounded_vec![1, 2],
+			threshold: 2,
+		};
+		assert_eq!(Recovery::recovery_config(5), Some(recovery_config));
+		assert_eq!(PendingRecoveryUpdates::<Test>::get(5), None);
+		// The deposit of the old configuration is returned
+		assert_eq!(Balances::reserved_balance(5), 12);
+		// Old friends can no longer vouch, new ones can
+		assert_ok!(Recovery::initiate_recovery(RuntimeOrigin::signed(3), 5));
+		assert_noop!(
+			Recovery::vouch_recovery(RuntimeOrigin::signed(4), 5, 3),
+			Error::<Test>::NotFriend
+		);
+		assert_ok!(Recovery::vouch_recovery(RuntimeOrigin::signed(1), 5, 3));
+	});
+}
+
+#[test]
+fn remove_recovery_drops_scheduled_update() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Recovery::create_recovery(RuntimeOrigin::signed(5), vec![2, 3, 4], 3, 10));
+		assert_ok!(Recovery::update_recovery(RuntimeOrigin::signed(5), vec![1, 2], 2, 5));
+		assert_eq!(Balances::reserved_balance(5), 25);
+		assert_ok!(Recovery::remove_recovery(RuntimeOrigin::signed(5)));
+		assert_eq!(PendingRecoveryUpdates::<Test>::get(5), None);
+		assert_eq!(Balances::reserved_balance(5), 0);
+	});
+}
+
+#[test]
+fn claim_recovery_drops_scheduled_update() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Recovery::create_recovery(RuntimeOrigin::signed(5), vec![2, 3, 4], 2, 10));
+		// Whoever took over the key of account 5 schedules friends they control
+		assert_ok!(Recovery::update_recovery(RuntimeOrigin::signed(5), vec![1], 1, 0));
+		assert_eq!(Balances::reserved_balance(5), 24);
+		// Meanwhile the current friends recover the account
+		assert_ok!(Recovery::initiate_recovery(RuntimeOrigin::signed(1), 5));
+		assert_ok!(Recovery::vouch_recovery(RuntimeOrigin::signed(2), 5, 1));
+		assert_ok!(Recovery::vouch_recovery(RuntimeOrigin::signed(3), 5, 1));
+		System::run_to_block::<AllPalletsWithSystem>(11);
+		assert_ok!(Recovery::claim_recovery(RuntimeOrigin::signed(1), 5));
+		System::assert_has_event(Event::<Test>::RecoveryUpdateCancelled { account: 5 }.into());
+		// The update is dropped and its deposit returned
+		assert_eq!(PendingRecoveryUpdates::<Test>::get(5), None);
+		assert_eq!(Balances::reserved_balance(5), 13);
+		// Closing the recovery to get the deposit back doesn't make the update applicable
+		let call = Box::new(RuntimeCall::Recovery(RecoveryCall::close_recovery { rescuer: 1 }));
+		assert_ok!(Recovery::as_recovered(RuntimeOrigin::signed(1), 5, call));
+		assert_noop!(
+			Recovery::apply_recovery_update(RuntimeOrigin::signed(1), 5),
+			Error::<Test>::UpdateNotScheduled
+		);
+		assert_eq!(Recovery::recovery_config(5).unwrap().friends, bounded_vec![2, 3, 4]);
+	});
+}
+
+#[test]
+fn multisig_friend_can_vouch() {
+	new_test_ext().execute_with(|| {
+		// A friend backed by the keys of accounts 1, 2 and 3, any two of which can act for it
+		let multi = Multisig::multi_account_id(&[1, 2, 3][..], 2);
+		let mut friends = vec![4, multi];
+		friends.sort();
+		assert_ok!(Recovery::create_recovery(RuntimeOrigin::signed(5), friends, 2, 10));
+		assert_ok!(Recovery::initiate_recovery(RuntimeOrigin::signed(1), 5));
+		assert_ok!(Recovery::vouch_recovery(RuntimeOrigin::signed(4), 5, 1));
+
+		let call =
+			Box::new(RuntimeCall::Recovery(RecoveryCall::vouch_recovery { lost: 5, rescuer: 1 }));
+		let call_weight = call.get_dispatch_info().call_weight;
+		// A single key of the multisig doesn't vouch
+		assert_ok!(Multisig::as_multi(
+			RuntimeOrigin::signed(2),
+			2,
+			vec![1, 3],
+			None,
+			call.clone(),
+			Weight::zero()
+		));
+		assert_eq!(Recovery::active_recovery(&5, &1).unwrap().friends, vec![4]);
+		System::run_to_block::<AllPalletsWithSystem>(11);
+		assert_noop!(
+			Recovery::claim_recovery(RuntimeOrigin::signed(1), 5),
+			Error::<Test>::Threshold
+		);
+
+		// The second key dispatches the vouch as the multisig
+		assert_ok!(Multisig::as_multi(
+			RuntimeOrigin::signed(3),
+			2,
+			vec![1, 2],
+			Some(pallet_multisig::Timepoint { height: 1, index: 0 }),
+			call,
+			call_weight
+		));
+		System::assert_has_event(
+			Event::<Test>::RecoveryVouched { lost_account: 5, rescuer_account: 1, sender: multi }
+				.into(),
+		);
+		assert_ok!(Recovery::claim_recovery(RuntimeOrigin::signed(1), 5));
+		assert_eq!(Recovery::proxy(&1), Some(5));
+	});
+}
+
+#[test]
+fn recovered_friend_can_vouch_and_active_recoveries_are_listed() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Recovery::create_recovery(RuntimeOrigin::signed(5), vec![2, 3, 4], 2, 10));
+		// Account 1 controls friend 4, e.g. after recovering it
+		assert_ok!(Recovery::set_recovered(RuntimeOrigin::root(), 4, 1));
+		assert_ok!(Recovery::initiate_recovery(RuntimeOrigin::signed(1), 5));
+		assert_ok!(Recovery::initiate_recovery(RuntimeOrigin::signed(2), 5));
+		// Friend 4 vouches through its controlling account
+		let call =
+			Box::new(RuntimeCall::Recovery(RecoveryCall::vouch_recovery { lost: 5, rescuer: 1 }));
+		assert_ok!(Recovery::as_recovered(RuntimeOrigin::signed(1), 4, call));
+
+		let mut active = Recovery::active_recoveries(&5);
+		active.sort_by_key(|(rescuer, _)| *rescuer);
+		assert_eq!(
+			active,
+			vec![
+				(1, ActiveRecovery { created: 1, deposit: 10, friends: vec![4] }),
+				(2, ActiveRecovery { created: 1, deposit: 10, friends: vec![] }),
+			]
+		);
+		assert!(Recovery::active_recoveries(&4).is_empty());
+	});
+}
+
 #[test]
 fn poke_deposit_handles_unsigned_origin() {
 	new_test_ext().execute_with(|| {
@@ -80,6 +80,9 @@ pub trait WeightInfo {
 	fn remove_recovery(n: u32, ) -> Weight;
 	fn cancel_recovered() -> Weight;
 	fn poke_deposit(n: u32, ) -> Weight;
+	fn update_recovery(n: u32, ) -> Weight;
+	fn cancel_recovery_update(n: u32, ) -> Weight;
+	fn apply_recovery_update(n: u32, ) -> Weight;
 }
 
 /// Weights for `pallet_recovery` using the Substrate node and recommended hardware.
@@ -227,6 +230,44 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(2_u64))
 			.saturating_add(T::DbWeight::get().writes(2_u64))
 	}
+	/// Storage: `Recovery::Recoverable` (r:1 w:0)
+	/// Proof: `Recovery::Recoverable` (`max_values`: None, `max_size`: Some(351), added: 2826, mode: `MaxEncodedLen`)
+	/// Storage: `Recovery::PendingRecoveryUpdates` (r:1 w:1)
+	/// Proof: `Recovery::PendingRecoveryUpdates` (`max_values`: None, `max_size`: Some(355), added: 2830, mode: `MaxEncodedLen`)
+	/// The range of component `n` is `[1, 9]`.
+	fn update_recovery(n: u32, ) -> Weight {
+		// Estimated, not measured: to be regenerated with the benchmark CLI.
+		Weight::from_parts(33_512_000, 3820)
+			.saturating_add(Weight::from_parts(172_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Recovery::PendingRecoveryUpdates` (r:1 w:1)
+	/// Proof: `Recovery::PendingRecoveryUpdates` (`max_values`: None, `max_size`: Some(355), added: 2830, mode: `MaxEncodedLen`)
+	/// The range of component `n` is `[1, 9]`.
+	fn cancel_recovery_update(n: u32, ) -> Weight {
+		// Estimated, not measured: to be regenerated with the benchmark CLI.
+		Weight::from_parts(22_184_000, 3820)
+			.saturating_add(Weight::from_parts(151_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Recovery::PendingRecoveryUpdates` (r:1 w:1)
+	/// Proof: `Recovery::PendingRecoveryUpdates` (`max_values`: None, `max_size`: Some(355), added: 2830, mode: `MaxEncodedLen`)
+	/// Storage: `Recovery::ActiveRecoveries` (r:1 w:0)
+	/// Proof: `Recovery::ActiveRecoveries` (`max_values`: None, `max_size`: Some(389), added: 2864, mode: `MaxEncodedLen`)
+	/// Storage: `Recovery::Recoverable` (r:1 w:1)
+	/// Proof: `Recovery::Recoverable` (`max_values`: None, `max_size`: Some(351), added: 2826, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// The range of component `n` is `[1, 9]`.
+	fn apply_recovery_update(n: u32, ) -> Weight {
+		// Estimated, not measured: to be regenerated with the benchmark CLI.
+		Weight::from_parts(45_627_000, 3854)
+			.saturating_add(Weight::from_parts(241_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
 }
 
 // For backwards compatibility and tests.
@@ -373,4 +414,42 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(2_u64))
 			.saturating_add(RocksDbWeight::get().writes(2_u64))
 	}
+	/// Storage: `Recovery::Recoverable` (r:1 w:0)
+	/// Proof: `Recovery::Recoverable` (`max_values`: None, `max_size`: Some(351), added: 2826, mode: `MaxEncodedLen`)
+	/// Storage: `Recovery::PendingRecoveryUpdates` (r:1 w:1)
+	/// Proof: `Recovery::PendingRecoveryUpdates` (`max_values`: None, `max_size`: Some(355), added: 2830, mode: `MaxEncodedLen`)
+	/// The range of component `n` is `[1, 9]`.
+	fn update_recovery(n: u32, ) -> Weight {
+		// Estimated, not measured: to be regenerated with the benchmark CLI.
+		Weight::from_parts(33_512_000, 3820)
+			.saturating_add(Weight::from_parts(172_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Recovery::PendingRecoveryUpdates` (r:1 w:1)
+	/// Proof: `Recovery::PendingRecoveryUpdates` (`max_values`: None, `max_size`: Some(355), added: 2830, mode: `MaxEncodedLen`)
+	/// The range of component `n` is `[1, 9]`.
+	fn cancel_recovery_update(n: u32, ) -> Weight {
+		// Estimated, not measured: to be regenerated with the benchmark CLI.
+		Weight::from_parts(22_184_000, 3820)
+			.saturating_add(Weight::from_parts(151_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Recovery::PendingRecoveryUpdates` (r:1 w:1)
+	/// Proof: `Recovery::PendingRecoveryUpdates` (`max_values`: None, `max_size`: Some(355), added: 2830, mode: `MaxEncodedLen`)
+	/// Storage: `Recovery::ActiveRecoveries` (r:1 w:0)
+	/// Proof: `Recovery::ActiveRecoveries` (`max_values`: None, `max_size`: Some(389), added: 2864, mode: `MaxEncodedLen`)
+	/// Storage: `Recovery::Recoverable` (r:1 w:1)
+	/// Proof: `Recovery::Recoverable` (`max_values`: None, `max_size`: Some(351), added: 2826, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// The range of component `n` is `[1, 9]`.
+	fn apply_recovery_update(n: u32, ) -> Weight {
+		// Estimated, not measured: to be regenerated with the benchmark CLI.
+		Weight::from_parts(45_627_000, 3854)
+			.saturating_add(Weight::from_parts(241_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
 }
@@ -134,6 +134,7 @@ impl pallet_proxy::Config for Test {
 	type AnnouncementDepositBase = ConstU64<1>;
 	type AnnouncementDepositFactor = ConstU64<1>;
 	type BlockNumberProvider = frame_system::Pallet<Test>;
+	type CallSpending = ();
 }
 
 /// The calls that can always bypass safe-mode.
@@ -62,6 +62,25 @@ fn fill_schedule<T: Config>(when: BlockNumberFor<T>, n: u32) -> Result<(), &'sta
 	Ok(())
 }
 
+/// Add `n` recurring tasks, named after their index.
+fn fill_recurring<T: Config>(n: u32) -> Result<(), &'static str> {
+	let origin: <T as Config>::PalletsOrigin = frame_system::RawOrigin::Root.into();
+	for i in 0..n {
+		let call = make_call::<T>(None);
+		let start = T::Time::now().saturating_add(i.into());
+		Pallet::<T>::do_schedule_recurring(
+			u32_to_name(i),
+			start,
+			One::one(),
+			Weight::zero(),
+			origin.clone(),
+			call,
+		)?;
+	}
+	ensure!(RecurringQueue::<T>::get().len() == n as usize, "didn't fill recurring");
+	Ok(())
+}
+
 fn u32_to_name(i: u32) -> TaskName {
 	i.using_encoded(blake2_256)
 }
@@ -506,6 +525,69 @@ mod benchmarks {
 		Ok(())
 	}
 
+	#[benchmark]
+	fn schedule_recurring(
+		s: Linear<0, { T::MaxRecurring::get() - 1 }>,
+	) -> Result<(), BenchmarkError> {
+		let id = u32_to_name(s);
+		// Essentially a no-op call.
+		let call: Box<<T as Config>::RuntimeCall> =
+			Box::new(SystemCall::set_storage { items: vec![] }.into());
+		let max_weight = call.get_dispatch_info().call_weight;
+
+		fill_recurring::<T>(s)?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Root, id, T::Time::now(), One::one(), max_weight, call);
+
+		ensure!(Recurring::<T>::contains_key(id), "didn't add recurring task");
+		ensure!(RecurringQueue::<T>::get().len() == s as usize + 1, "didn't add to queue");
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn cancel_recurring(s: Linear<1, { T::MaxRecurring::get() }>) -> Result<(), BenchmarkError> {
+		fill_recurring::<T>(s)?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Root, u32_to_name(s - 1));
+
+		ensure!(!Recurring::<T>::contains_key(u32_to_name(s - 1)), "didn't remove recurring task");
+		ensure!(RecurringQueue::<T>::get().len() == s as usize - 1, "didn't remove from queue");
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn pause_recurring(s: Linear<1, { T::MaxRecurring::get() }>) -> Result<(), BenchmarkError> {
+		fill_recurring::<T>(s)?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Root, u32_to_name(s - 1));
+
+		ensure!(Recurring::<T>::get(u32_to_name(s - 1)).unwrap().paused, "didn't pause");
+		ensure!(RecurringQueue::<T>::get().len() == s as usize - 1, "didn't remove from queue");
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn resume_recurring(
+		s: Linear<0, { T::MaxRecurring::get() - 1 }>,
+	) -> Result<(), BenchmarkError> {
+		fill_recurring::<T>(s + 1)?;
+		Pallet::<T>::pause_recurring(RawOrigin::Root.into(), u32_to_name(s))?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Root, u32_to_name(s));
+
+		ensure!(!Recurring::<T>::get(u32_to_name(s)).unwrap().paused, "didn't resume");
+		ensure!(RecurringQueue::<T>::get().len() == s as usize + 1, "didn't add to queue");
+
+		Ok(())
+	}
+
 	impl_benchmark_test_suite! {
 		Pallet,
 		mock::new_test_ext(),
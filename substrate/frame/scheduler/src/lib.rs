@@ -41,6 +41,20 @@
 //! If a call is scheduled using proxy or whatever mechanism which adds filter, then those filter
 //! will not be used when dispatching the schedule runtime call.
 //!
+//! ### Recurring Tasks
+//!
+//! Besides periodic tasks, which are expressed in blocks and repeated a given number of times,
+//! named tasks may be scheduled to recur at a fixed interval of time (as given by
+//! [`Config::Time`], e.g. `pallet_timestamp`) until they are cancelled. Their executions are
+//! derived from their start time and thus do not drift when block times change. Recurring tasks
+//! can be paused and resumed, and their upcoming executions are exposed by the
+//! [`runtime_api::SchedulerApi`].
+//!
+//! Recurring tasks are serviced in `on_poll`, i.e. once the inherents of the block, which set its
+//! time, have been applied. They share [`Config::MaximumWeight`] with the agenda of the block and
+//! are not serviced while multi-block migrations are ongoing. A task which could not be executed
+//! even in an otherwise empty block is paused.
+//!
 //! ### Examples
 //!
 //! 1. Scheduling a runtime call at a specific block.
@@ -81,6 +95,7 @@ mod benchmarking;
 pub mod migration;
 #[cfg(test)]
 mod mock;
+pub mod runtime_api;
 #[cfg(test)]
 mod tests;
 pub mod weights;
@@ -91,12 +106,13 @@ use alloc::{boxed::Box, vec::Vec};
 use codec::{Decode, Encode, MaxEncodedLen};
 use core::{borrow::Borrow, cmp::Ordering, marker::PhantomData};
 use frame_support::{
+	defensive,
 	dispatch::{DispatchResult, GetDispatchInfo, Parameter, RawOrigin},
 	ensure,
 	traits::{
 		schedule::{self, DispatchTime, MaybeHashed},
 		Bounded, CallerTrait, EnsureOrigin, Get, IsType, OriginTrait, PalletInfoAccess,
-		PrivilegeCmp, QueryPreimage, StorageVersion, StorePreimage,
+		PrivilegeCmp, QueryPreimage, StorageVersion, StorePreimage, Time,
 	},
 	weights::{Weight, WeightMeter},
 };
@@ -125,6 +141,11 @@ pub type BoundedCallOf<T> =
 pub type BlockNumberFor<T> =
 	<<T as Config>::BlockNumberProvider as BlockNumberProvider>::BlockNumber;
 
+pub type MomentOf<T> = <<T as Config>::Time as Time>::Moment;
+
+/// The maximum number of executions returned by [`Pallet::upcoming_executions`].
+pub const MAX_UPCOMING_EXECUTIONS: u32 = 1_000;
+
 /// The configuration of the retry mechanism for a given task along with its current state.
 #[derive(Clone, Copy, RuntimeDebug, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo)]
 pub struct RetryConfig<Period> {
@@ -182,6 +203,28 @@ where
 	}
 }
 
+/// A named task which is dispatched every `interval` of time until it is cancelled.
+#[derive(Clone, RuntimeDebug, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo)]
+pub struct RecurringTask<Call, Moment, PalletsOrigin> {
+	/// The call to be dispatched.
+	pub call: Call,
+	/// The origin with which to dispatch the call.
+	pub origin: PalletsOrigin,
+	/// The time of the first execution, from which the following ones are derived.
+	pub start: Moment,
+	/// The time between two executions.
+	pub interval: Moment,
+	/// The maximum weight of the call. Executions are skipped while the call weighs more.
+	pub max_weight: Weight,
+	/// The time of the next execution.
+	pub next: Moment,
+	/// Whether the task is paused, in which case it is not executed.
+	pub paused: bool,
+}
+
+pub type RecurringTaskOf<T> =
+	RecurringTask<BoundedCallOf<T>, MomentOf<T>, <T as Config>::PalletsOrigin>;
+
 use crate::{Scheduled as ScheduledV3, Scheduled as ScheduledV2};
 
 pub type ScheduledV2Of<T> = ScheduledV2<
@@ -322,12 +365,25 @@ pub mod pallet {
 		/// swap block number providers on the fly, then please at least ensure that you do not run
 		/// any pallet migration in the same runtime upgrade.
 		type BlockNumberProvider: BlockNumberProvider;
+
+		/// The source of time for recurring tasks, usually `pallet_timestamp`.
+		type Time: Time;
+
+		/// The maximum number of recurring tasks.
+		#[pallet::constant]
+		type MaxRecurring: Get<u32>;
 	}
 
 	/// Block number at which the agenda began incomplete execution.
 	#[pallet::storage]
 	pub type IncompleteSince<T: Config> = StorageValue<_, BlockNumberFor<T>>;
 
+	/// The weight consumed by the agenda of the current block, which the recurring tasks serviced
+	/// later in the block share.
+	#[pallet::storage]
+	#[pallet::whitelist_storage]
+	pub(crate) type AgendaWeight<T: Config> = StorageValue<_, Weight, ValueQuery>;
+
 	/// Items to be executed, indexed by the block number that they should be executed on.
 	#[pallet::storage]
 	pub type Agenda<T: Config> = StorageMap<
@@ -356,6 +412,17 @@ pub mod pallet {
 	pub type Lookup<T: Config> =
 		StorageMap<_, Twox64Concat, TaskName, TaskAddress<BlockNumberFor<T>>>;
 
+	/// Recurring tasks, indexed by their name.
+	#[pallet::storage]
+	pub type Recurring<T: Config> =
+		CountedStorageMap<_, Twox64Concat, TaskName, RecurringTaskOf<T>>;
+
+	/// The names of the recurring tasks which are not paused, along with the time of their next
+	/// execution. Sorted by time.
+	#[pallet::storage]
+	pub type RecurringQueue<T: Config> =
+		StorageValue<_, BoundedVec<(MomentOf<T>, TaskName), T::MaxRecurring>, ValueQuery>;
+
 	/// Events type.
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
@@ -390,6 +457,24 @@ pub mod pallet {
 		PermanentlyOverweight { task: TaskAddress<BlockNumberFor<T>>, id: Option<TaskName> },
 		/// Agenda is incomplete from `when`.
 		AgendaIncomplete { when: BlockNumberFor<T> },
+		/// Scheduled a recurring task.
+		RecurringScheduled { id: TaskName, next: MomentOf<T> },
+		/// Canceled a recurring task.
+		RecurringCanceled { id: TaskName },
+		/// Paused a recurring task.
+		RecurringPaused { id: TaskName },
+		/// Resumed a recurring task.
+		RecurringResumed { id: TaskName, next: MomentOf<T> },
+		/// Dispatched a recurring task.
+		RecurringDispatched { id: TaskName, result: DispatchResult },
+		/// An execution of a recurring task was skipped since its call was not available.
+		RecurringCallUnavailable { id: TaskName },
+		/// An execution of a recurring task was skipped since its call weighs more than the
+		/// maximum weight of the task.
+		RecurringOverweight { id: TaskName },
+		/// A recurring task was paused since its call can never be executed, not even in an
+		/// otherwise empty block.
+		RecurringPermanentlyOverweight { id: TaskName },
 	}
 
 	#[pallet::error]
@@ -404,6 +489,19 @@ pub mod pallet {
 		RescheduleNoChange,
 		/// Attempt to use a non-named function on a named task.
 		Named,
+		/// There are already `MaxRecurring` recurring tasks.
+		TooManyRecurring,
+		/// The interval of a recurring task must not be zero.
+		ZeroInterval,
+		/// The call weighs more than the given maximum weight.
+		MaxWeightTooLow,
+		/// The maximum weight together with the overhead of the task is more than may be scheduled
+		/// per block.
+		MaxWeightTooHigh,
+		/// The recurring task is already paused.
+		AlreadyPaused,
+		/// The recurring task is not paused.
+		NotPaused,
 	}
 
 	#[pallet::hooks]
@@ -413,8 +511,27 @@ pub mod pallet {
 			let now = T::BlockNumberProvider::current_block_number();
 			let mut weight_counter = WeightMeter::with_limit(T::MaximumWeight::get());
 			Self::service_agendas(&mut weight_counter, now, u32::MAX);
+			if !weight_counter.consumed().is_zero() {
+				AgendaWeight::<T>::put(weight_counter.consumed());
+			}
 			weight_counter.consumed()
 		}
+
+		/// Execute the recurring tasks which are due, once the inherents and hence the timestamp
+		/// of the block have been applied.
+		fn on_poll(_now: SystemBlockNumberFor<T>, weight: &mut WeightMeter) {
+			let limit = T::MaximumWeight::get()
+				.saturating_sub(AgendaWeight::<T>::take())
+				.min(weight.remaining());
+			let mut weight_counter = WeightMeter::with_limit(limit);
+			Self::service_recurring(&mut weight_counter);
+			weight.consume(weight_counter.consumed());
+		}
+
+		fn on_finalize(_now: SystemBlockNumberFor<T>) {
+			// Not taken by `on_poll` while multi-block migrations are ongoing.
+			AgendaWeight::<T>::kill();
+		}
 	}
 
 	#[pallet::call]
@@ -635,6 +752,98 @@ pub mod pallet {
 			Self::deposit_event(Event::RetryCancelled { task, id: Some(id) });
 			Ok(())
 		}
+
+		/// Schedule a named task to be dispatched every `interval` of time from `start`, until it
+		/// is cancelled.
+		///
+		/// Times are expressed in the unit of [`Config::Time`], i.e. milliseconds for
+		/// `pallet_timestamp`. An execution happens in the first block whose time is at or after
+		/// its time. If `start` is in the past, the first execution is the next one after the
+		/// current time. The executions are derived from `start` and do not drift; those which
+		/// could not happen before the following one was due are skipped.
+		///
+		/// The call is only dispatched while it weighs at most `max_weight`, which together with
+		/// the overhead of servicing the task must fit in [`Config::MaximumWeight`].
+		#[pallet::call_index(10)]
+		#[pallet::weight(<T as Config>::WeightInfo::schedule_recurring(T::MaxRecurring::get()))]
+		pub fn schedule_recurring(
+			origin: OriginFor<T>,
+			id: TaskName,
+			start: MomentOf<T>,
+			interval: MomentOf<T>,
+			max_weight: Weight,
+			call: Box<<T as Config>::RuntimeCall>,
+		) -> DispatchResult {
+			T::ScheduleOrigin::ensure_origin(origin.clone())?;
+			let origin = <T as Config>::RuntimeOrigin::from(origin);
+			ensure!(
+				call.get_dispatch_info().call_weight.all_lte(max_weight),
+				Error::<T>::MaxWeightTooLow
+			);
+			Self::do_schedule_recurring(
+				id,
+				start,
+				interval,
+				max_weight,
+				origin.caller().clone(),
+				T::Preimages::bound(*call)?,
+			)
+		}
+
+		/// Cancel a recurring task.
+		#[pallet::call_index(11)]
+		#[pallet::weight(<T as Config>::WeightInfo::cancel_recurring(T::MaxRecurring::get()))]
+		pub fn cancel_recurring(origin: OriginFor<T>, id: TaskName) -> DispatchResult {
+			T::ScheduleOrigin::ensure_origin(origin.clone())?;
+			let origin = <T as Config>::RuntimeOrigin::from(origin);
+			let task = Recurring::<T>::get(&id).ok_or(Error::<T>::NotFound)?;
+			Self::ensure_privilege(origin.caller(), &task.origin)?;
+
+			if !task.paused {
+				Self::dequeue_recurring(&id);
+			}
+			Recurring::<T>::remove(&id);
+			T::Preimages::drop(&task.call);
+			Self::deposit_event(Event::RecurringCanceled { id });
+			Ok(())
+		}
+
+		/// Pause a recurring task, so that it is not executed until it is resumed.
+		#[pallet::call_index(12)]
+		#[pallet::weight(<T as Config>::WeightInfo::pause_recurring(T::MaxRecurring::get()))]
+		pub fn pause_recurring(origin: OriginFor<T>, id: TaskName) -> DispatchResult {
+			T::ScheduleOrigin::ensure_origin(origin.clone())?;
+			let origin = <T as Config>::RuntimeOrigin::from(origin);
+			Recurring::<T>::try_mutate(&id, |maybe_task| -> DispatchResult {
+				let task = maybe_task.as_mut().ok_or(Error::<T>::NotFound)?;
+				Self::ensure_privilege(origin.caller(), &task.origin)?;
+				ensure!(!task.paused, Error::<T>::AlreadyPaused);
+				task.paused = true;
+				Ok(())
+			})?;
+			Self::dequeue_recurring(&id);
+			Self::deposit_event(Event::RecurringPaused { id });
+			Ok(())
+		}
+
+		/// Resume a paused recurring task. The executions missed while it was paused are skipped.
+		#[pallet::call_index(13)]
+		#[pallet::weight(<T as Config>::WeightInfo::resume_recurring(T::MaxRecurring::get()))]
+		pub fn resume_recurring(origin: OriginFor<T>, id: TaskName) -> DispatchResult {
+			T::ScheduleOrigin::ensure_origin(origin.clone())?;
+			let origin = <T as Config>::RuntimeOrigin::from(origin);
+			let next = Recurring::<T>::try_mutate(&id, |maybe_task| {
+				let task = maybe_task.as_mut().ok_or(Error::<T>::NotFound)?;
+				Self::ensure_privilege(origin.caller(), &task.origin)?;
+				ensure!(task.paused, Error::<T>::NotPaused);
+				task.paused = false;
+				task.next = Self::next_execution(task.start, task.interval, T::Time::now());
+				Self::enqueue_recurring(task.next, id)?;
+				Ok::<_, DispatchError>(task.next)
+			})?;
+			Self::deposit_event(Event::RecurringResumed { id, next });
+			Ok(())
+		}
 	}
 }
 
@@ -1173,6 +1382,120 @@ impl<T: Config> Pallet<T> {
 		Retries::<T>::remove((when, index));
 		Ok(())
 	}
+
+	fn do_schedule_recurring(
+		id: TaskName,
+		start: MomentOf<T>,
+		interval: MomentOf<T>,
+		max_weight: Weight,
+		origin: T::PalletsOrigin,
+		call: BoundedCallOf<T>,
+	) -> DispatchResult {
+		ensure!(!Recurring::<T>::contains_key(&id), Error::<T>::FailedToSchedule);
+		ensure!(!interval.is_zero(), Error::<T>::ZeroInterval);
+		ensure!(
+			Self::max_recurring_call_weight(&origin, call.lookup_len())
+				.is_some_and(|limit| max_weight.all_lte(limit)),
+			Error::<T>::MaxWeightTooHigh
+		);
+		ensure!(Recurring::<T>::count() < T::MaxRecurring::get(), Error::<T>::TooManyRecurring);
+
+		let next = Self::next_execution(start, interval, T::Time::now());
+		Self::enqueue_recurring(next, id)?;
+
+		if let Some(hash) = call.lookup_hash() {
+			// Request the call to be made available.
+			T::Preimages::request(&hash);
+		}
+		Recurring::<T>::insert(
+			id,
+			RecurringTask { call, origin, start, interval, max_weight, next, paused: false },
+		);
+		Self::deposit_event(Event::RecurringScheduled { id, next });
+		Ok(())
+	}
+
+	/// The maximum weight of the call of a recurring task dispatched from `origin`, i.e.
+	/// `MaximumWeight` less reading the queue and servicing the task, or `None` if the task can't
+	/// be serviced at all.
+	fn max_recurring_call_weight(
+		origin: &T::PalletsOrigin,
+		lookup_len: Option<u32>,
+	) -> Option<Weight> {
+		let overhead = T::DbWeight::get()
+			.reads(1)
+			.saturating_add(Self::recurring_task_base_weight(lookup_len))
+			.saturating_add(Self::dispatch_base_weight(origin));
+		T::MaximumWeight::get().checked_sub(&overhead)
+	}
+
+	/// The weight of servicing a recurring task, without dispatching its call.
+	fn recurring_task_base_weight(lookup_len: Option<u32>) -> Weight {
+		T::WeightInfo::service_task(lookup_len.map(|x| x as usize), true, true)
+			.saturating_add(T::DbWeight::get().reads_writes(1, 2))
+	}
+
+	/// The time of the first execution at or after `now` of a recurring task which starts at
+	/// `start` and recurs every `interval`.
+	fn next_execution(start: MomentOf<T>, interval: MomentOf<T>, now: MomentOf<T>) -> MomentOf<T> {
+		if now <= start {
+			return start
+		}
+		let interval = interval.max(One::one());
+		let elapsed = now - start;
+		let mut periods = elapsed / interval;
+		if !(elapsed % interval).is_zero() {
+			periods.saturating_inc();
+		}
+		start.saturating_add(interval.saturating_mul(periods))
+	}
+
+	/// Insert the recurring task `id` into the queue, to be executed at `next`.
+	fn enqueue_recurring(next: MomentOf<T>, id: TaskName) -> DispatchResult {
+		RecurringQueue::<T>::try_mutate(|queue| {
+			let index = queue.partition_point(|(time, _)| *time <= next);
+			queue
+				.try_insert(index, (next, id))
+				.map_err(|_| Error::<T>::TooManyRecurring.into())
+		})
+	}
+
+	/// Remove the recurring task `id` from the queue.
+	fn dequeue_recurring(id: &TaskName) {
+		RecurringQueue::<T>::mutate(|queue| queue.retain(|(_, name)| name != id));
+	}
+
+	/// The next `max` executions of the recurring tasks which are not paused, as the name of the
+	/// task and the time at which it is due. Sorted by time.
+	///
+	/// At most [`MAX_UPCOMING_EXECUTIONS`] are returned.
+	pub fn upcoming_executions(max: u32) -> Vec<(TaskName, MomentOf<T>)> {
+		let max = max.min(MAX_UPCOMING_EXECUTIONS);
+		let mut upcoming = RecurringQueue::<T>::get()
+			.into_iter()
+			.filter_map(|(next, id)| {
+				Recurring::<T>::get(&id).map(|task| (next, id, task.interval.max(One::one())))
+			})
+			.collect::<Vec<_>>();
+
+		let mut executions = Vec::new();
+		while executions.len() < max as usize {
+			// The queue is sorted by time and the order of the tasks only changes as they recur,
+			// hence the first minimum keeps the order of the queue for executions at equal times.
+			let Some(index) = upcoming
+				.iter()
+				.enumerate()
+				.min_by(|(_, a), (_, b)| a.0.cmp(&b.0))
+				.map(|(index, _)| index)
+			else {
+				break
+			};
+			let (next, id, interval) = &mut upcoming[index];
+			executions.push((*id, *next));
+			*next = next.saturating_add(*interval);
+		}
+		executions
+	}
 }
 
 enum ServiceTaskError {
@@ -1392,10 +1715,7 @@ impl<T: Config> Pallet<T> {
 		origin: T::PalletsOrigin,
 		call: <T as Config>::RuntimeCall,
 	) -> Result<DispatchResult, ()> {
-		let base_weight = match origin.as_system_ref() {
-			Some(&RawOrigin::Signed(_)) => T::WeightInfo::execute_dispatch_signed(),
-			_ => T::WeightInfo::execute_dispatch_unsigned(),
-		};
+		let base_weight = Self::dispatch_base_weight(&origin);
 		let call_weight = call.get_dispatch_info().call_weight;
 		// We only allow a scheduled call if it cannot push the weight past the limit.
 		let max_weight = base_weight.saturating_add(call_weight);
@@ -1416,6 +1736,111 @@ impl<T: Config> Pallet<T> {
 		Ok(result)
 	}
 
+	/// The weight of dispatching a call from `origin`, without the call itself.
+	fn dispatch_base_weight(origin: &T::PalletsOrigin) -> Weight {
+		match origin.as_system_ref() {
+			Some(&RawOrigin::Signed(_)) => T::WeightInfo::execute_dispatch_signed(),
+			_ => T::WeightInfo::execute_dispatch_unsigned(),
+		}
+	}
+
+	/// Service the recurring tasks which are due at the current time, in the order of the queue.
+	///
+	/// Due tasks which do not fit in the remaining `weight` are postponed to a later block. Tasks
+	/// which can never fit are paused, so that they don't hold up the queue.
+	fn service_recurring(weight: &mut WeightMeter) {
+		if weight.try_consume(T::DbWeight::get().reads(1)).is_err() {
+			return
+		}
+
+		let queue = RecurringQueue::<T>::get();
+		let now = T::Time::now();
+		let mut serviced = 0;
+		let mut requeued = Vec::new();
+		for &(due, id) in queue.iter() {
+			if due > now {
+				break
+			}
+			match Self::service_recurring_task(weight, now, id) {
+				Ok(maybe_next) => {
+					serviced += 1;
+					requeued.extend(maybe_next.map(|next| (next, id)));
+				},
+				Err(()) => break,
+			}
+		}
+		if serviced == 0 {
+			return
+		}
+
+		// The serviced tasks are due after all the others, since those were due at `now` at the
+		// latest.
+		let mut queue = queue.into_inner();
+		queue.drain(..serviced);
+		for (next, id) in requeued {
+			let index = queue.partition_point(|(time, _)| *time <= next);
+			queue.insert(index, (next, id));
+		}
+		RecurringQueue::<T>::put(BoundedVec::truncate_from(queue));
+	}
+
+	/// Service (i.e. execute) the recurring task `id`, unless its call is unavailable or too
+	/// heavy, in which case only this execution is skipped.
+	///
+	/// A task whose call can never be executed, e.g. since `MaximumWeight` was lowered after it
+	/// was scheduled, is paused.
+	///
+	/// Returns the time of the next execution of the task, or an error if it does not fit in the
+	/// remaining `weight` and must be postponed.
+	fn service_recurring_task(
+		weight: &mut WeightMeter,
+		now: MomentOf<T>,
+		id: TaskName,
+	) -> Result<Option<MomentOf<T>>, ()> {
+		let Some(mut task) = Recurring::<T>::get(&id) else {
+			defensive!("Recurring task in the queue must exist");
+			return Ok(None)
+		};
+
+		let lookup_len = task.call.lookup_len();
+		let Some(max_call_weight) = Self::max_recurring_call_weight(&task.origin, lookup_len)
+		else {
+			let _ = weight.try_consume(T::DbWeight::get().reads_writes(1, 1));
+			Self::pause_permanently_overweight(id, task);
+			return Ok(None)
+		};
+		if weight.try_consume(Self::recurring_task_base_weight(lookup_len)).is_err() {
+			return Err(())
+		}
+
+		match T::Preimages::peek(&task.call) {
+			Err(_) => Self::deposit_event(Event::RecurringCallUnavailable { id }),
+			Ok((call, _)) if !call.get_dispatch_info().call_weight.all_lte(task.max_weight) =>
+				Self::deposit_event(Event::RecurringOverweight { id }),
+			Ok((call, _)) if !call.get_dispatch_info().call_weight.all_lte(max_call_weight) => {
+				Self::pause_permanently_overweight(id, task);
+				return Ok(None)
+			},
+			Ok((call, _)) => {
+				let result = Self::execute_dispatch(weight, task.origin.clone(), call)?;
+				Self::deposit_event(Event::RecurringDispatched { id, result });
+			},
+		}
+
+		task.next = Self::next_execution(task.start, task.interval, now.saturating_add(One::one()));
+		let next = task.next;
+		Recurring::<T>::insert(id, task);
+		Ok(Some(next))
+	}
+
+	/// Pause the recurring task `id`, which was taken off the queue, since it can never be
+	/// executed.
+	fn pause_permanently_overweight(id: TaskName, mut task: RecurringTaskOf<T>) {
+		task.paused = true;
+		Recurring::<T>::insert(id, task);
+		Self::deposit_event(Event::RecurringPermanentlyOverweight { id });
+	}
+
 	/// Check if a task has a retry configuration in place and, if so, try to reschedule it.
 	///
 	/// Possible causes for failure to schedule a retry for a task:
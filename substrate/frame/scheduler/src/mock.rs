@@ -211,10 +211,31 @@ impl WeightInfo for TestWeightInfo {
 	fn cancel_retry_named() -> Weight {
 		Weight::from_parts(50, 0)
 	}
+	fn schedule_recurring(_s: u32) -> Weight {
+		Weight::from_parts(50, 0)
+	}
+	fn cancel_recurring(_s: u32) -> Weight {
+		Weight::from_parts(50, 0)
+	}
+	fn pause_recurring(_s: u32) -> Weight {
+		Weight::from_parts(50, 0)
+	}
+	fn resume_recurring(_s: u32) -> Weight {
+		Weight::from_parts(50, 0)
+	}
 }
 parameter_types! {
 	pub storage MaximumSchedulerWeight: Weight = Perbill::from_percent(80) *
 		BlockWeights::get().max_block;
+	pub static Now: u64 = 0;
+}
+
+pub struct MockTime;
+impl frame_support::traits::Time for MockTime {
+	type Moment = u64;
+	fn now() -> u64 {
+		Now::get()
+	}
 }
 
 impl Config for Test {
@@ -229,6 +250,8 @@ impl Config for Test {
 	type WeightInfo = TestWeightInfo;
 	type Preimages = Preimage;
 	type BlockNumberProvider = frame_system::Pallet<Self>;
+	type Time = MockTime;
+	type MaxRecurring = ConstU32<4>;
 }
 
 pub type LoggerCall = logger::Call<Test>;
@@ -0,0 +1,35 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Runtime API definition for the FRAME Scheduler pallet.
+
+use crate::TaskName;
+use alloc::vec::Vec;
+use codec::Codec;
+
+sp_api::decl_runtime_apis! {
+	pub trait SchedulerApi<Moment>
+	where
+		Moment: Codec
+	{
+		/// The next `max` executions of the recurring tasks which are not paused, as the name of
+		/// the task and the time at which it is due. Sorted by time.
+		///
+		/// At most [`crate::MAX_UPCOMING_EXECUTIONS`] are returned.
+		fn upcoming_executions(max: u32) -> Vec<(TaskName, Moment)>;
+	}
+}
@@ -24,7 +24,7 @@ use crate::mock::{
 };
 use frame_support::{
 	assert_err, assert_noop, assert_ok,
-	traits::{Contains, GetStorageVersion, OnInitialize, QueryPreimage, StorePreimage},
+	traits::{Contains, GetStorageVersion, Hooks, OnInitialize, QueryPreimage, StorePreimage},
 	Hashable,
 };
 use sp_runtime::traits::Hash;
@@ -3076,3 +3076,290 @@ fn postponed_task_is_still_available() {
 		assert!(Agenda::<Test>::get(4).is_empty());
 	});
 }
+
+fn recurring_log(i: u32) -> Box<RuntimeCall> {
+	Box::new(RuntimeCall::Logger(LoggerCall::log { i, weight: Weight::from_parts(10, 0) }))
+}
+
+/// Run to block `n`, servicing the recurring tasks after the initialization like the executive.
+fn run_to_block_with_poll(n: u64) {
+	System::run_to_block_with::<AllPalletsWithSystem>(
+		n,
+		frame_system::RunToBlockHooks::default().after_initialize(|bn| {
+			Scheduler::on_poll(bn, &mut WeightMeter::new());
+		}),
+	);
+}
+
+#[test]
+fn recurring_task_works() {
+	new_test_ext().execute_with(|| {
+		Now::set(5);
+		assert_ok!(Scheduler::schedule_recurring(
+			RuntimeOrigin::root(),
+			[1u8; 32],
+			10,
+			10,
+			Weight::from_parts(10, 0),
+			recurring_log(42),
+		));
+		System::assert_last_event(Event::RecurringScheduled { id: [1u8; 32], next: 10 }.into());
+
+		// The recurring tasks are not serviced in `on_initialize`, where the time is still the one
+		// of the previous block.
+		Now::set(10);
+		Scheduler::on_initialize(1);
+		assert!(logger::log().is_empty());
+		Now::set(5);
+
+		run_to_block_with_poll(1);
+		assert!(logger::log().is_empty());
+
+		Now::set(10);
+		run_to_block_with_poll(2);
+		assert_eq!(logger::log(), vec![(root(), 42u32)]);
+		assert_eq!(Recurring::<Test>::get([1u8; 32]).unwrap().next, 20);
+
+		Now::set(15);
+		run_to_block_with_poll(3);
+		assert_eq!(logger::log().len(), 1);
+
+		// The executions at 20 and 30 were missed and are skipped, without drifting.
+		Now::set(35);
+		run_to_block_with_poll(4);
+		assert_eq!(logger::log().len(), 2);
+		assert_eq!(Recurring::<Test>::get([1u8; 32]).unwrap().next, 40);
+		assert_eq!(RecurringQueue::<Test>::get().into_inner(), vec![(40, [1u8; 32])]);
+
+		assert_ok!(Scheduler::cancel_recurring(RuntimeOrigin::root(), [1u8; 32]));
+		System::assert_last_event(Event::RecurringCanceled { id: [1u8; 32] }.into());
+		assert_eq!(Recurring::<Test>::count(), 0);
+		assert!(RecurringQueue::<Test>::get().is_empty());
+
+		Now::set(40);
+		run_to_block_with_poll(5);
+		assert_eq!(logger::log().len(), 2);
+	});
+}
+
+#[test]
+fn recurring_task_pause_and_resume_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Scheduler::schedule_recurring(
+			RuntimeOrigin::root(),
+			[1u8; 32],
+			10,
+			10,
+			Weight::from_parts(10, 0),
+			recurring_log(42),
+		));
+		assert_noop!(
+			Scheduler::resume_recurring(RuntimeOrigin::root(), [1u8; 32]),
+			Error::<Test>::NotPaused
+		);
+		assert_ok!(Scheduler::pause_recurring(RuntimeOrigin::root(), [1u8; 32]));
+		System::assert_last_event(Event::RecurringPaused { id: [1u8; 32] }.into());
+		assert_noop!(
+			Scheduler::pause_recurring(RuntimeOrigin::root(), [1u8; 32]),
+			Error::<Test>::AlreadyPaused
+		);
+		assert!(Scheduler::upcoming_executions(10).is_empty());
+
+		Now::set(30);
+		run_to_block_with_poll(1);
+		assert!(logger::log().is_empty());
+
+		// A less privileged origin cannot resume the task.
+		assert_noop!(Scheduler::resume_recurring(RuntimeOrigin::signed(1), [1u8; 32]), BadOrigin);
+		Now::set(35);
+		assert_ok!(Scheduler::resume_recurring(RuntimeOrigin::root(), [1u8; 32]));
+		System::assert_last_event(Event::RecurringResumed { id: [1u8; 32], next: 40 }.into());
+
+		Now::set(40);
+		run_to_block_with_poll(2);
+		assert_eq!(logger::log(), vec![(root(), 42u32)]);
+		System::assert_has_event(
+			Event::RecurringDispatched { id: [1u8; 32], result: Ok(()) }.into(),
+		);
+	});
+}
+
+#[test]
+fn schedule_recurring_checks_work() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Scheduler::schedule_recurring(
+				RuntimeOrigin::root(),
+				[1u8; 32],
+				10,
+				10,
+				Weight::from_parts(9, 0),
+				recurring_log(42),
+			),
+			Error::<Test>::MaxWeightTooLow
+		);
+		assert_noop!(
+			Scheduler::schedule_recurring(
+				RuntimeOrigin::root(),
+				[1u8; 32],
+				10,
+				10,
+				Weight::MAX,
+				recurring_log(42),
+			),
+			Error::<Test>::MaxWeightTooHigh
+		);
+		// The overhead of servicing the task must fit as well.
+		assert_noop!(
+			Scheduler::schedule_recurring(
+				RuntimeOrigin::root(),
+				[1u8; 32],
+				10,
+				10,
+				MaximumSchedulerWeight::get(),
+				recurring_log(42),
+			),
+			Error::<Test>::MaxWeightTooHigh
+		);
+		assert_noop!(
+			Scheduler::schedule_recurring(
+				RuntimeOrigin::root(),
+				[1u8; 32],
+				10,
+				0,
+				Weight::from_parts(10, 0),
+				recurring_log(42),
+			),
+			Error::<Test>::ZeroInterval
+		);
+		for i in 0..4u8 {
+			assert_ok!(Scheduler::schedule_recurring(
+				RuntimeOrigin::root(),
+				[i; 32],
+				10,
+				10,
+				Weight::from_parts(10, 0),
+				recurring_log(i as u32),
+			));
+		}
+		assert_noop!(
+			Scheduler::schedule_recurring(
+				RuntimeOrigin::root(),
+				[0u8; 32],
+				10,
+				10,
+				Weight::from_parts(10, 0),
+				recurring_log(42),
+			),
+			Error::<Test>::FailedToSchedule
+		);
+		assert_noop!(
+			Scheduler::schedule_recurring(
+				RuntimeOrigin::root(),
+				[4u8; 32],
+				10,
+				10,
+				Weight::from_parts(10, 0),
+				recurring_log(42),
+			),
+			Error::<Test>::TooManyRecurring
+		);
+	});
+}
+
+#[test]
+fn permanently_overweight_recurring_task_is_paused() {
+	new_test_ext().execute_with(|| {
+		let max_weight = MaximumSchedulerWeight::get() / 2;
+		assert_ok!(Scheduler::schedule_recurring(
+			RuntimeOrigin::root(),
+			[1u8; 32],
+			10,
+			10,
+			max_weight,
+			Box::new(RuntimeCall::Logger(LoggerCall::log { i: 1, weight: max_weight })),
+		));
+		assert_ok!(Scheduler::schedule_recurring(
+			RuntimeOrigin::root(),
+			[2u8; 32],
+			15,
+			10,
+			Weight::from_parts(10, 0),
+			recurring_log(2),
+		));
+
+		// The first task can't be executed anymore once the maximum weight is lowered.
+		MaximumSchedulerWeight::set(&(max_weight / 2));
+		Now::set(15);
+		run_to_block_with_poll(1);
+		System::assert_has_event(Event::RecurringPermanentlyOverweight { id: [1u8; 32] }.into());
+		assert!(Recurring::<Test>::get([1u8; 32]).unwrap().paused);
+
+		// It is paused instead of holding up the tasks after it.
+		assert_eq!(logger::log(), vec![(root(), 2u32)]);
+		assert_eq!(RecurringQueue::<Test>::get().into_inner(), vec![(25, [2u8; 32])]);
+	});
+}
+
+#[test]
+fn upcoming_executions_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Scheduler::schedule_recurring(
+			RuntimeOrigin::root(),
+			[1u8; 32],
+			10,
+			10,
+			Weight::from_parts(10, 0),
+			recurring_log(1),
+		));
+		assert_ok!(Scheduler::schedule_recurring(
+			RuntimeOrigin::root(),
+			[2u8; 32],
+			15,
+			20,
+			Weight::from_parts(10, 0),
+			recurring_log(2),
+		));
+		assert_eq!(
+			Scheduler::upcoming_executions(5),
+			vec![
+				([1u8; 32], 10),
+				([2u8; 32], 15),
+				([1u8; 32], 20),
+				([1u8; 32], 30),
+				([2u8; 32], 35)
+			]
+		);
+
+		// Both tasks are due and executed in the order of their times.
+		Now::set(15);
+		run_to_block_with_poll(1);
+		assert_eq!(logger::log(), vec![(root(), 1u32), (root(), 2u32)]);
+		assert_eq!(
+			Scheduler::upcoming_executions(3),
+			vec![([1u8; 32], 20), ([1u8; 32], 30), ([2u8; 32], 35)]
+		);
+	});
+}
+
+#[test]
+fn upcoming_executions_is_capped() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Scheduler::schedule_recurring(
+			RuntimeOrigin::root(),
+			[1u8; 32],
+			10,
+			1,
+			Weight::from_parts(10, 0),
+			recurring_log(1),
+		));
+		assert_eq!(
+			Scheduler::upcoming_executions(u32::MAX).len(),
+			MAX_UPCOMING_EXECUTIONS as usize
+		);
+		assert_eq!(
+			Scheduler::upcoming_executions(3),
+			vec![([1u8; 32], 10), ([1u8; 32], 11), ([1u8; 32], 12)]
+		);
+	});
+}
@@ -89,6 +89,10 @@ pub trait WeightInfo {
 	fn set_retry_named() -> Weight;
 	fn cancel_retry() -> Weight;
 	fn cancel_retry_named() -> Weight;
+	fn schedule_recurring(s: u32, ) -> Weight;
+	fn cancel_recurring(s: u32, ) -> Weight;
+	fn pause_recurring(s: u32, ) -> Weight;
+	fn resume_recurring(s: u32, ) -> Weight;
 }
 
 /// Weights for `pallet_scheduler` using the Substrate node and recommended hardware.
@@ -319,6 +323,62 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(2_u64))
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
+	/// Storage: `Scheduler::Recurring` (r:1 w:1)
+	/// Proof: `Scheduler::Recurring` (`max_values`: None, `max_size`: Some(282), added: 2757, mode: `MaxEncodedLen`)
+	/// Storage: `Scheduler::CounterForRecurring` (r:1 w:1)
+	/// Proof: `Scheduler::CounterForRecurring` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `Timestamp::Now` (r:1 w:0)
+	/// Proof: `Timestamp::Now` (`max_values`: Some(1), `max_size`: Some(8), added: 503, mode: `MaxEncodedLen`)
+	/// Storage: `Scheduler::RecurringQueue` (r:1 w:1)
+	/// Proof: `Scheduler::RecurringQueue` (`max_values`: Some(1), `max_size`: Some(2001), added: 2496, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[0, 49]`.
+	fn schedule_recurring(s: u32, ) -> Weight {
+		// Estimated, not measured: to be regenerated with the benchmark CLI.
+		Weight::from_parts(16_214_733, 3747)
+			.saturating_add(Weight::from_parts(93_412, 0).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: `Scheduler::Recurring` (r:1 w:1)
+	/// Proof: `Scheduler::Recurring` (`max_values`: None, `max_size`: Some(282), added: 2757, mode: `MaxEncodedLen`)
+	/// Storage: `Scheduler::CounterForRecurring` (r:1 w:1)
+	/// Proof: `Scheduler::CounterForRecurring` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `Scheduler::RecurringQueue` (r:1 w:1)
+	/// Proof: `Scheduler::RecurringQueue` (`max_values`: Some(1), `max_size`: Some(2001), added: 2496, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[1, 50]`.
+	fn cancel_recurring(s: u32, ) -> Weight {
+		// Estimated, not measured: to be regenerated with the benchmark CLI.
+		Weight::from_parts(14_893_207, 3747)
+			.saturating_add(Weight::from_parts(81_550, 0).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: `Scheduler::Recurring` (r:1 w:1)
+	/// Proof: `Scheduler::Recurring` (`max_values`: None, `max_size`: Some(282), added: 2757, mode: `MaxEncodedLen`)
+	/// Storage: `Scheduler::RecurringQueue` (r:1 w:1)
+	/// Proof: `Scheduler::RecurringQueue` (`max_values`: Some(1), `max_size`: Some(2001), added: 2496, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[1, 50]`.
+	fn pause_recurring(s: u32, ) -> Weight {
+		// Estimated, not measured: to be regenerated with the benchmark CLI.
+		Weight::from_parts(12_408_911, 3747)
+			.saturating_add(Weight::from_parts(79_204, 0).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `Scheduler::Recurring` (r:1 w:1)
+	/// Proof: `Scheduler::Recurring` (`max_values`: None, `max_size`: Some(282), added: 2757, mode: `MaxEncodedLen`)
+	/// Storage: `Timestamp::Now` (r:1 w:0)
+	/// Proof: `Timestamp::Now` (`max_values`: Some(1), `max_size`: Some(8), added: 503, mode: `MaxEncodedLen`)
+	/// Storage: `Scheduler::RecurringQueue` (r:1 w:1)
+	/// Proof: `Scheduler::RecurringQueue` (`max_values`: Some(1), `max_size`: Some(2001), added: 2496, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[0, 49]`.
+	fn resume_recurring(s: u32, ) -> Weight {
+		// Estimated, not measured: to be regenerated with the benchmark CLI.
+		Weight::from_parts(13_712_455, 3747)
+			.saturating_add(Weight::from_parts(92_318, 0).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
 }
 
 // For backwards compatibility and tests.
@@ -548,4 +608,60 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(2_u64))
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
+	/// Storage: `Scheduler::Recurring` (r:1 w:1)
+	/// Proof: `Scheduler::Recurring` (`max_values`: None, `max_size`: Some(282), added: 2757, mode: `MaxEncodedLen`)
+	/// Storage: `Scheduler::CounterForRecurring` (r:1 w:1)
+	/// Proof: `Scheduler::CounterForRecurring` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `Timestamp::Now` (r:1 w:0)
+	/// Proof: `Timestamp::Now` (`max_values`: Some(1), `max_size`: Some(8), added: 503, mode: `MaxEncodedLen`)
+	/// Storage: `Scheduler::RecurringQueue` (r:1 w:1)
+	/// Proof: `Scheduler::RecurringQueue` (`max_values`: Some(1), `max_size`: Some(2001), added: 2496, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[0, 49]`.
+	fn schedule_recurring(s: u32, ) -> Weight {
+		// Estimated, not measured: to be regenerated with the benchmark CLI.
+		Weight::from_parts(16_214_733, 3747)
+			.saturating_add(Weight::from_parts(93_412, 0).saturating_mul(s.into()))
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: `Scheduler::Recurring` (r:1 w:1)
+	/// Proof: `Scheduler::Recurring` (`max_values`: None, `max_size`: Some(282), added: 2757, mode: `MaxEncodedLen`)
+	/// Storage: `Scheduler::CounterForRecurring` (r:1 w:1)
+	/// Proof: `Scheduler::CounterForRecurring` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `Scheduler::RecurringQueue` (r:1 w:1)
+	/// Proof: `Scheduler::RecurringQueue` (`max_values`: Some(1), `max_size`: Some(2001), added: 2496, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[1, 50]`.
+	fn cancel_recurring(s: u32, ) -> Weight {
+		// Estimated, not measured: to be regenerated with the benchmark CLI.
+		Weight::from_parts(14_893_207, 3747)
+			.saturating_add(Weight::from_parts(81_550, 0).saturating_mul(s.into()))
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: `Scheduler::Recurring` (r:1 w:1)
+	/// Proof: `Scheduler::Recurring` (`max_values`: None, `max_size`: Some(282), added: 2757, mode: `MaxEncodedLen`)
+	/// Storage: `Scheduler::RecurringQueue` (r:1 w:1)
+	/// Proof: `Scheduler::RecurringQueue` (`max_values`: Some(1), `max_size`: Some(2001), added: 2496, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[1, 50]`.
+	fn pause_recurring(s: u32, ) -> Weight {
+		// Estimated, not measured: to be regenerated with the benchmark CLI.
+		Weight::from_parts(12_408_911, 3747)
+			.saturating_add(Weight::from_parts(79_204, 0).saturating_mul(s.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `Scheduler::Recurring` (r:1 w:1)
+	/// Proof: `Scheduler::Recurring` (`max_values`: None, `max_size`: Some(282), added: 2757, mode: `MaxEncodedLen`)
+	/// Storage: `Timestamp::Now` (r:1 w:0)
+	/// Proof: `Timestamp::Now` (`max_values`: Some(1), `max_size`: Some(8), added: 503, mode: `MaxEncodedLen`)
+	/// Storage: `Scheduler::RecurringQueue` (r:1 w:1)
+	/// Proof: `Scheduler::RecurringQueue` (`max_values`: Some(1), `max_size`: Some(2001), added: 2496, mode: `MaxEncodedLen`)
+	/// The range of component `s` is `[0, 49]`.
+	fn resume_recurring(s: u32, ) -> Weight {
+		// Estimated, not measured: to be regenerated with the benchmark CLI.
+		Weight::from_parts(13_712_455, 3747)
+			.saturating_add(Weight::from_parts(92_318, 0).saturating_mul(s.into()))
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
 }
@@ -201,6 +201,8 @@ impl pallet_scheduler::Config for Runtime {
 	type OriginPrivilegeCmp = frame_support::traits::EqualPrivilegeOnly;
 	type Preimages = Preimage;
 	type BlockNumberProvider = RelayChainBlockNumberProvider;
+	type Time = Timestamp;
+	type MaxRecurring = ConstU32<50>;
 }
 
 parameter_types! {
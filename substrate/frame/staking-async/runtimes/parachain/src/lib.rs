@@ -55,7 +55,7 @@ use frame_support::{
 		fungibles,
 		tokens::{imbalance::ResolveAssetTo, nonfungibles_v2::Inspect},
 		AsEnsureOriginWithArg, ConstBool, ConstU128, ConstU32, ConstU64, ConstU8,
-		ConstantStoragePrice, Equals, InstanceFilter, TransformOrigin, WithdrawReasons,
+		ConstantStoragePrice, Contains, Equals, InstanceFilter, TransformOrigin, WithdrawReasons,
 	},
 	weights::{ConstantMultiplier, Weight, WeightToFee as _},
 	BoundedVec, PalletId,
@@ -782,6 +782,22 @@ impl InstanceFilter<RuntimeCall> for ProxyType {
 	}
 }
 
+/// The calls which move no funds of the account they are dispatched for, and which a proxy may
+/// dispatch regardless of its spending allowance.
+pub struct SpendsNothing;
+impl Contains<RuntimeCall> for SpendsNothing {
+	fn contains(call: &RuntimeCall) -> bool {
+		matches!(
+			call,
+			RuntimeCall::System(
+				frame_system::Call::remark { .. } | frame_system::Call::remark_with_event { .. }
+			) | RuntimeCall::Session(..) |
+				RuntimeCall::Multisig(..) |
+				RuntimeCall::Proxy(..)
+		)
+	}
+}
+
 impl pallet_proxy::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type RuntimeCall = RuntimeCall;
@@ -797,6 +813,7 @@ impl pallet_proxy::Config for Runtime {
 	type AnnouncementDepositFactor = AnnouncementDepositFactor;
 	// TODO add migration.
 	type BlockNumberProvider = RelayChainBlockNumberProvider;
+	type CallSpending = polkadot_runtime_common::impls::TransferSpending<Runtime, SpendsNothing>;
 }
 
 parameter_types! {
@@ -1024,6 +1041,7 @@ impl pallet_nft_fractionalization::Config for Runtime {
 parameter_types! {
 	pub NftsPalletFeatures: PalletFeatures = PalletFeatures::all_enabled();
 	pub const NftsMaxDeadlineDuration: BlockNumber = 12 * 30 * DAYS;
+	pub const NftsMaxRoyalty: Permill = Permill::from_percent(25);
 	// re-use the Uniques deposits
 	pub const NftsCollectionDeposit: Balance = UniquesCollectionDeposit::get();
 	pub const NftsItemDeposit: Balance = UniquesItemDeposit::get();
@@ -1053,6 +1071,8 @@ impl pallet_nfts::Config for Runtime {
 	type MaxTips = ConstU32<10>;
 	type MaxDeadlineDuration = NftsMaxDeadlineDuration;
 	type MaxAttributesPerCall = ConstU32<10>;
+	type MaxRoyaltyRecipients = ConstU32<5>;
+	type MaxRoyalty = NftsMaxRoyalty;
 	type Features = NftsPalletFeatures;
 	type OffchainSignature = Signature;
 	type OffchainPublic = <Signature as Verify>::Signer;
@@ -1476,6 +1496,7 @@ impl_runtime_apis! {
 		}
 	}
 
+	#[api_version(2)]
 	impl pallet_nfts_runtime_api::NftsApi<Block, AccountId, u32, u32> for Runtime {
 		fn owner(collection: u32, item: u32) -> Option<AccountId> {
 			<Nfts as Inspect<AccountId>>::owner(&collection, &item)
@@ -1518,6 +1539,14 @@ impl_runtime_apis! {
 		fn collection_attribute(collection: u32, key: Vec<u8>) -> Option<Vec<u8>> {
 			<Nfts as Inspect<AccountId>>::collection_attribute(&collection, &key)
 		}
+
+		fn royalty(collection: u32, item: u32) -> Vec<(AccountId, sp_runtime::Permill)> {
+			Nfts::royalty(&collection, &item)
+				.map(|recipients| {
+					recipients.into_iter().map(|r| (r.account, r.percentage)).collect()
+				})
+				.unwrap_or_default()
+		}
 	}
 
 	impl pallet_asset_conversion::AssetConversionApi<
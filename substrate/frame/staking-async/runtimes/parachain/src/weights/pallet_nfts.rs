@@ -773,4 +773,28 @@ impl<T: frame_system::Config> pallet_nfts::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(n.into())))
 			.saturating_add(Weight::from_parts(0, 2954).saturating_mul(n.into()))
 	}
+	/// Not benchmarked for this runtime yet, falls back to the weight of the pallet.
+	fn set_royalty() -> Weight {
+		<pallet_nfts::weights::SubstrateWeight<T> as pallet_nfts::WeightInfo>::set_royalty()
+	}
+	/// Not benchmarked for this runtime yet, falls back to the weight of the pallet.
+	fn clear_royalty() -> Weight {
+		<pallet_nfts::weights::SubstrateWeight<T> as pallet_nfts::WeightInfo>::clear_royalty()
+	}
+	/// Not benchmarked for this runtime yet, falls back to the weight of the pallet.
+	fn offer_rental() -> Weight {
+		<pallet_nfts::weights::SubstrateWeight<T> as pallet_nfts::WeightInfo>::offer_rental()
+	}
+	/// Not benchmarked for this runtime yet, falls back to the weight of the pallet.
+	fn cancel_rental_offer() -> Weight {
+		<pallet_nfts::weights::SubstrateWeight<T> as pallet_nfts::WeightInfo>::cancel_rental_offer()
+	}
+	/// Not benchmarked for this runtime yet, falls back to the weight of the pallet.
+	fn accept_rental() -> Weight {
+		<pallet_nfts::weights::SubstrateWeight<T> as pallet_nfts::WeightInfo>::accept_rental()
+	}
+	/// Not benchmarked for this runtime yet, falls back to the weight of the pallet.
+	fn end_rental() -> Weight {
+		<pallet_nfts::weights::SubstrateWeight<T> as pallet_nfts::WeightInfo>::end_rental()
+	}
 }
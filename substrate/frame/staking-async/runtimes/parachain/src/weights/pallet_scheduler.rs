@@ -283,4 +283,20 @@ impl<T: frame_system::Config> pallet_scheduler::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads(2))
 			.saturating_add(T::DbWeight::get().writes(1))
 	}
+	/// Not benchmarked for this runtime yet, falls back to the weight of the pallet.
+	fn schedule_recurring(s: u32, ) -> Weight {
+		<pallet_scheduler::weights::SubstrateWeight<T> as pallet_scheduler::WeightInfo>::schedule_recurring(s)
+	}
+	/// Not benchmarked for this runtime yet, falls back to the weight of the pallet.
+	fn cancel_recurring(s: u32, ) -> Weight {
+		<pallet_scheduler::weights::SubstrateWeight<T> as pallet_scheduler::WeightInfo>::cancel_recurring(s)
+	}
+	/// Not benchmarked for this runtime yet, falls back to the weight of the pallet.
+	fn pause_recurring(s: u32, ) -> Weight {
+		<pallet_scheduler::weights::SubstrateWeight<T> as pallet_scheduler::WeightInfo>::pause_recurring(s)
+	}
+	/// Not benchmarked for this runtime yet, falls back to the weight of the pallet.
+	fn resume_recurring(s: u32, ) -> Weight {
+		<pallet_scheduler::weights::SubstrateWeight<T> as pallet_scheduler::WeightInfo>::resume_recurring(s)
+	}
 }
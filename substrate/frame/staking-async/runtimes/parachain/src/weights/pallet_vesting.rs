@@ -262,4 +262,12 @@ impl<T: frame_system::Config> pallet_vesting::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads(4_u64))
 			.saturating_add(T::DbWeight::get().writes(3_u64))
 	}
+	/// Not benchmarked for this runtime yet, falls back to the weight of the pallet.
+	fn revocable_vested_transfer(l: u32, s: u32, ) -> Weight {
+		<pallet_vesting::weights::SubstrateWeight<T> as pallet_vesting::WeightInfo>::revocable_vested_transfer(l, s)
+	}
+	/// Not benchmarked for this runtime yet, falls back to the weight of the pallet.
+	fn revoke_grant(l: u32, s: u32, ) -> Weight {
+		<pallet_vesting::weights::SubstrateWeight<T> as pallet_vesting::WeightInfo>::revoke_grant(l, s)
+	}
 }
@@ -69,6 +69,36 @@ fn add_vesting_schedules<T: Config>(
 	Ok(total_locked)
 }
 
+/// Make `n` revocable grants to `target`, each with its own treasury.
+fn add_grants<T: Config>(target: &T::AccountId, n: u32) -> Result<BalanceOf<T>, &'static str> {
+	let min_transfer = T::MinVestedTransfer::get();
+	let locked = min_transfer.checked_mul(&20_u32.into()).unwrap();
+	let schedule = VestingInfo::new(locked, min_transfer, 1_u32.into());
+
+	let source: T::AccountId = account("source", 0, SEED);
+	let revoker: T::AccountId = account("revoker", 0, SEED);
+
+	T::BlockNumberProvider::set_block_number(BlockNumberFor::<T>::zero());
+
+	let mut total_locked: BalanceOf<T> = Zero::zero();
+	for i in 0..n {
+		total_locked += locked;
+
+		T::Currency::make_free_balance_be(&source, BalanceOf::<T>::max_value());
+		let treasury: T::AccountId = account("treasury", i, SEED);
+		Pallet::<T>::revocable_vested_transfer(
+			RawOrigin::Signed(source.clone()).into(),
+			T::Lookup::unlookup(target.clone()),
+			schedule,
+			T::Lookup::unlookup(revoker.clone()),
+			T::Lookup::unlookup(treasury),
+		)
+		.map_err(|_| "grant could not be made")?;
+	}
+
+	Ok(total_locked)
+}
+
 #[benchmarks]
 mod benchmarks {
 	use super::*;
@@ -435,6 +465,78 @@ mod benchmarks {
 		Ok(())
 	}
 
+	#[benchmark]
+	fn revocable_vested_transfer(
+		l: Linear<0, { MaxLocksOf::<T>::get() - 1 }>,
+		s: Linear<0, { T::MAX_VESTING_SCHEDULES - 1 }>,
+	) -> Result<(), BenchmarkError> {
+		let caller = whitelisted_caller();
+		T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value());
+
+		let target = account::<T::AccountId>("target", 0, SEED);
+		let target_lookup = T::Lookup::unlookup(target.clone());
+		// Give target existing locks and grants.
+		T::Currency::make_free_balance_be(&target, T::Currency::minimum_balance());
+		add_locks::<T>(&target, l as u8);
+		let mut expected_balance = add_grants::<T>(&target, s)?;
+
+		let transfer_amount = T::MinVestedTransfer::get();
+		let per_block = transfer_amount.checked_div(&20_u32.into()).unwrap();
+		expected_balance += transfer_amount;
+
+		let vesting_schedule = VestingInfo::new(transfer_amount, per_block, 1_u32.into());
+		let revoker = T::Lookup::unlookup(account("revoker", 0, SEED));
+		let treasury = T::Lookup::unlookup(account("treasury", s, SEED));
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), target_lookup, vesting_schedule, revoker, treasury);
+
+		assert_eq!(Grants::<T>::get(&target).unwrap().len(), s as usize + 1);
+		assert_eq!(
+			Pallet::<T>::vesting_balance(&target),
+			Some(expected_balance),
+			"Lock not correctly updated",
+		);
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn revoke_grant(
+		l: Linear<0, { MaxLocksOf::<T>::get() - 1 }>,
+		s: Linear<1, { T::MAX_VESTING_SCHEDULES }>,
+	) -> Result<(), BenchmarkError> {
+		let target = account::<T::AccountId>("target", 0, SEED);
+		let target_lookup = T::Lookup::unlookup(target.clone());
+		T::Currency::make_free_balance_be(&target, T::Currency::minimum_balance());
+		add_locks::<T>(&target, l as u8);
+		add_grants::<T>(&target, s)?;
+
+		// Revoke all but the last grant while their funds are bonded, so that they are owed.
+		let revoker: T::AccountId = account("revoker", 0, SEED);
+		let bonded = [u8::MAX; 8];
+		T::Currency::set_lock(bonded, &target, BalanceOf::<T>::max_value(), WithdrawReasons::all());
+		let first_grant = NextGrantId::<T>::get() - s;
+		for grant_id in first_grant..first_grant + s - 1 {
+			Pallet::<T>::revoke_grant(
+				RawOrigin::Signed(revoker.clone()).into(),
+				target_lookup.clone(),
+				grant_id,
+			)?;
+		}
+		assert_eq!(GrantDebts::<T>::decode_len(&target), Some(s as usize - 1));
+		// Revoking the last grant pays all the debts, each to its own treasury.
+		T::Currency::remove_lock(bonded, &target);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(revoker), target_lookup, first_grant + s - 1);
+
+		assert!(!Grants::<T>::contains_key(&target));
+		assert!(!GrantDebts::<T>::contains_key(&target));
+
+		Ok(())
+	}
+
 	impl_benchmark_test_suite! {
 		Pallet,
 		mock::ExtBuilder::default().existential_deposit(256).build(),
@@ -42,6 +42,10 @@
 //! - `vest` - Update the lock, reducing it in line with the amount "vested" so far.
 //! - `vest_other` - Update the lock of another account, reducing it in line with the amount
 //!   "vested" so far.
+//! - `revocable_vested_transfer` - Create a vested transfer that a designated revoker may
+//!   terminate.
+//! - `revoke_grant` - Terminate a revocable grant, returning its unvested remainder to the treasury
+//!   account of the grant. Funds which cannot be moved yet stay locked until they are paid.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -143,6 +147,37 @@ impl VestingAction {
 	}
 }
 
+/// A vesting schedule which can be terminated by its `revoker`, in which case the amount not
+/// vested yet goes to `treasury`.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct RevocableGrant<AccountId, Balance, BlockNumber> {
+	/// The identifier of the grant.
+	pub id: u32,
+	/// The vesting schedule of the grant.
+	pub schedule: VestingInfo<Balance, BlockNumber>,
+	/// The account allowed to revoke the grant.
+	pub revoker: AccountId,
+	/// The account receiving the unvested funds of a revoked grant.
+	pub treasury: AccountId,
+}
+
+type RevocableGrantOf<T> =
+	RevocableGrant<<T as frame_system::Config>::AccountId, BalanceOf<T>, BlockNumberFor<T>>;
+
+/// The unvested funds of a revoked grant which could not be taken back yet, e.g. because they
+/// were bonded. They stay locked until they are paid to `treasury`.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct GrantDebt<AccountId, Balance> {
+	/// The identifier of the revoked grant.
+	pub grant_id: u32,
+	/// The account the funds are owed to.
+	pub treasury: AccountId,
+	/// The amount still owed.
+	pub owed: Balance,
+}
+
+type GrantDebtOf<T> = GrantDebt<<T as frame_system::Config>::AccountId, BalanceOf<T>>;
+
 // Wrapper for `T::MAX_VESTING_SCHEDULES` to satisfy `trait Get`.
 pub struct MaxVestingSchedulesGet<T>(PhantomData<T>);
 impl<T: Config> Get<u32> for MaxVestingSchedulesGet<T> {
@@ -232,6 +267,28 @@ pub mod pallet {
 		BoundedVec<VestingInfo<BalanceOf<T>, BlockNumberFor<T>>, MaxVestingSchedulesGet<T>>,
 	>;
 
+	/// The revocable grants of a given account, locked in addition to its `Vesting` schedules.
+	#[pallet::storage]
+	pub type Grants<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		BoundedVec<RevocableGrantOf<T>, MaxVestingSchedulesGet<T>>,
+	>;
+
+	/// The funds of revoked grants still owed by a given account, locked until they are paid.
+	#[pallet::storage]
+	pub type GrantDebts<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		BoundedVec<GrantDebtOf<T>, MaxVestingSchedulesGet<T>>,
+	>;
+
+	/// The identifier of the next revocable grant.
+	#[pallet::storage]
+	pub type NextGrantId<T: Config> = StorageValue<_, u32, ValueQuery>;
+
 	/// Storage version of the pallet.
 	///
 	/// New networks start with latest version, as determined by the genesis build.
@@ -293,6 +350,18 @@ pub mod pallet {
 		VestingUpdated { account: T::AccountId, unvested: BalanceOf<T> },
 		/// An \[account\] has become fully vested.
 		VestingCompleted { account: T::AccountId },
+		/// A revocable grant has been created.
+		GrantCreated { account: T::AccountId, grant_id: u32, revoker: T::AccountId },
+		/// A revocable grant has been revoked. Its unvested funds were moved to the treasury, up
+		/// to a `shortfall` which is kept locked as a debt of the account.
+		GrantRevoked {
+			account: T::AccountId,
+			grant_id: u32,
+			clawed_back: BalanceOf<T>,
+			shortfall: BalanceOf<T>,
+		},
+		/// Funds owed for a revoked grant have been paid to its treasury.
+		GrantDebtPaid { account: T::AccountId, grant_id: u32, amount: BalanceOf<T> },
 	}
 
 	/// Error for the vesting pallet.
@@ -309,6 +378,10 @@ pub mod pallet {
 		ScheduleIndexOutOfBounds,
 		/// Failed to create a new schedule because some parameter was invalid.
 		InvalidScheduleParams,
+		/// The account has no revocable grant with the given identifier.
+		GrantNotFound,
+		/// The sender is not the revoker of the grant.
+		NotRevoker,
 	}
 
 	#[pallet::call]
@@ -318,6 +391,8 @@ pub mod pallet {
 		/// The dispatch origin for this call must be _Signed_ and the sender must have funds still
 		/// locked under this pallet.
 		///
+		/// Funds owed for revoked grants are paid as far as they are free.
+		///
 		/// Emits either `VestingCompleted` or `VestingUpdated`.
 		///
 		/// ## Complexity
@@ -325,6 +400,7 @@ pub mod pallet {
 		#[pallet::call_index(0)]
 		#[pallet::weight(T::WeightInfo::vest_locked(MaxLocksOf::<T>::get(), T::MAX_VESTING_SCHEDULES)
 			.max(T::WeightInfo::vest_unlocked(MaxLocksOf::<T>::get(), T::MAX_VESTING_SCHEDULES))
+			.saturating_add(Pallet::<T>::settle_grant_debts_weight())
 		)]
 		pub fn vest(origin: OriginFor<T>) -> DispatchResult {
 			let who = ensure_signed(origin)?;
@@ -338,6 +414,8 @@ pub mod pallet {
 		/// - `target`: The account whose vested funds should be unlocked. Must have funds still
 		/// locked under this pallet.
 		///
+		/// Funds owed for revoked grants are paid as far as they are free.
+		///
 		/// Emits either `VestingCompleted` or `VestingUpdated`.
 		///
 		/// ## Complexity
@@ -345,6 +423,7 @@ pub mod pallet {
 		#[pallet::call_index(1)]
 		#[pallet::weight(T::WeightInfo::vest_other_locked(MaxLocksOf::<T>::get(), T::MAX_VESTING_SCHEDULES)
 			.max(T::WeightInfo::vest_other_unlocked(MaxLocksOf::<T>::get(), T::MAX_VESTING_SCHEDULES))
+			.saturating_add(Pallet::<T>::settle_grant_debts_weight())
 		)]
 		pub fn vest_other(origin: OriginFor<T>, target: AccountIdLookupOf<T>) -> DispatchResult {
 			ensure_signed(origin)?;
@@ -488,6 +567,131 @@ pub mod pallet {
 			))
 			.into())
 		}
+
+		/// Create a vested transfer which can be revoked by `revoker`.
+		///
+		/// The dispatch origin for this call must be _Signed_.
+		///
+		/// - `target`: The account receiving the vested funds.
+		/// - `schedule`: The vesting schedule attached to the transfer.
+		/// - `revoker`: The account allowed to revoke the grant.
+		/// - `treasury`: The account receiving the unvested funds if the grant is revoked.
+		///
+		/// Emits `GrantCreated`.
+		///
+		/// NOTE: This will unlock all schedules through the current block.
+		#[pallet::call_index(6)]
+		#[pallet::weight(
+			T::WeightInfo::revocable_vested_transfer(MaxLocksOf::<T>::get(), T::MAX_VESTING_SCHEDULES)
+		)]
+		pub fn revocable_vested_transfer(
+			origin: OriginFor<T>,
+			target: AccountIdLookupOf<T>,
+			schedule: VestingInfo<BalanceOf<T>, BlockNumberFor<T>>,
+			revoker: AccountIdLookupOf<T>,
+			treasury: AccountIdLookupOf<T>,
+		) -> DispatchResult {
+			let transactor = ensure_signed(origin)?;
+			let target = T::Lookup::lookup(target)?;
+			let revoker = T::Lookup::lookup(revoker)?;
+			let treasury = T::Lookup::lookup(treasury)?;
+
+			ensure!(schedule.locked() >= T::MinVestedTransfer::get(), Error::<T>::AmountLow);
+			ensure!(schedule.is_valid(), Error::<T>::InvalidScheduleParams);
+
+			let mut grants = Grants::<T>::get(&target).unwrap_or_default();
+			// The debt of a revoked grant keeps its slot until it is paid.
+			let debts = GrantDebts::<T>::decode_len(&target).unwrap_or_default();
+			ensure!(
+				grants.len().saturating_add(debts) < T::MAX_VESTING_SCHEDULES as usize,
+				Error::<T>::AtMaxVestingSchedules
+			);
+			let grant_id = NextGrantId::<T>::get();
+			grants
+				.try_push(RevocableGrant {
+					id: grant_id,
+					schedule,
+					revoker: revoker.clone(),
+					treasury,
+				})
+				.map_err(|_| Error::<T>::AtMaxVestingSchedules)?;
+
+			T::Currency::transfer(
+				&transactor,
+				&target,
+				schedule.locked(),
+				ExistenceRequirement::AllowDeath,
+			)?;
+
+			Grants::<T>::insert(&target, grants);
+			NextGrantId::<T>::put(grant_id.wrapping_add(1));
+			Self::update_lock(&target)?;
+
+			Self::deposit_event(Event::<T>::GrantCreated { account: target, grant_id, revoker });
+			Ok(())
+		}
+
+		/// Revoke a grant, moving the funds not vested yet to the treasury account of the grant.
+		///
+		/// The funds vested so far stay with `target`. Unvested funds which cannot be moved, e.g.
+		/// because they are bonded, stay locked as a debt of `target` and are paid to the treasury
+		/// once they are free, see `vest_other`.
+		///
+		/// The dispatch origin for this call must be _Signed_ by the revoker of the grant.
+		///
+		/// - `target`: The account the grant was made to.
+		/// - `grant_id`: The identifier of the grant.
+		///
+		/// Emits `GrantRevoked`.
+		#[pallet::call_index(7)]
+		#[pallet::weight(T::WeightInfo::revoke_grant(MaxLocksOf::<T>::get(), T::MAX_VESTING_SCHEDULES))]
+		pub fn revoke_grant(
+			origin: OriginFor<T>,
+			target: AccountIdLookupOf<T>,
+			grant_id: u32,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let target = T::Lookup::lookup(target)?;
+
+			let mut grants = Grants::<T>::get(&target).ok_or(Error::<T>::GrantNotFound)?;
+			let index = grants
+				.iter()
+				.position(|grant| grant.id == grant_id)
+				.ok_or(Error::<T>::GrantNotFound)?;
+			ensure!(grants[index].revoker == who, Error::<T>::NotRevoker);
+			let grant = grants.remove(index);
+			if grants.is_empty() {
+				Grants::<T>::remove(&target);
+			} else {
+				Grants::<T>::insert(&target, grants);
+			}
+
+			// The unvested funds are owed to the treasury and paid as far as they are free. Funds
+			// locked otherwise, e.g. for staking, must not be taken.
+			let now = T::BlockNumberProvider::current_block_number();
+			let unvested = grant.schedule.locked_at::<T::BlockNumberToBalance>(now);
+			let mut debts = GrantDebts::<T>::get(&target).unwrap_or_default();
+			debts
+				.try_push(GrantDebt { grant_id, treasury: grant.treasury, owed: unvested })
+				.map_err(|_| Error::<T>::AtMaxVestingSchedules)?;
+			GrantDebts::<T>::insert(&target, debts);
+
+			Self::settle_grant_debts(&target);
+			Self::update_lock(&target)?;
+
+			let shortfall = GrantDebts::<T>::get(&target)
+				.unwrap_or_default()
+				.iter()
+				.find(|debt| debt.grant_id == grant_id)
+				.map_or_else(Zero::zero, |debt| debt.owed);
+			Self::deposit_event(Event::<T>::GrantRevoked {
+				account: target,
+				grant_id,
+				clawed_back: unvested.saturating_sub(shortfall),
+				shortfall,
+			});
+			Ok(())
+		}
 	}
 }
 
@@ -618,7 +822,12 @@ impl<T: Config> Pallet<T> {
 	}
 
 	/// Write an accounts updated vesting lock to storage.
+	///
+	/// The amount still locked by the revocable grants of `who` is added to `total_locked_now`.
 	fn write_lock(who: &T::AccountId, total_locked_now: BalanceOf<T>) {
+		let total_locked_now = total_locked_now
+			.saturating_add(Self::prune_grants(who))
+			.saturating_add(Self::grant_debt(who));
 		if total_locked_now.is_zero() {
 			T::Currency::remove_lock(VESTING_ID, who);
 			Self::deposit_event(Event::<T>::VestingCompleted { account: who.clone() });
@@ -651,15 +860,138 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
-	/// Unlock any vested funds of `who`.
+	/// Remove the revocable grants of `who` which are fully vested. Returns the amount still
+	/// locked by the remaining ones.
+	fn prune_grants(who: &T::AccountId) -> BalanceOf<T> {
+		let Some(mut grants) = Grants::<T>::get(who) else { return Zero::zero() };
+
+		let now = T::BlockNumberProvider::current_block_number();
+		let mut total_locked_now: BalanceOf<T> = Zero::zero();
+		grants.retain(|grant| {
+			let locked_now = grant.schedule.locked_at::<T::BlockNumberToBalance>(now);
+			total_locked_now = total_locked_now.saturating_add(locked_now);
+			!locked_now.is_zero()
+		});
+
+		if grants.is_empty() {
+			Grants::<T>::remove(who);
+		} else {
+			Grants::<T>::insert(who, grants);
+		}
+		total_locked_now
+	}
+
+	/// The total amount owed by `who` for revoked grants.
+	fn grant_debt(who: &T::AccountId) -> BalanceOf<T> {
+		GrantDebts::<T>::get(who)
+			.unwrap_or_default()
+			.iter()
+			.fold(Zero::zero(), |total: BalanceOf<T>, debt| total.saturating_add(debt.owed))
+	}
+
+	/// Pay the funds owed by `who` for revoked grants as far as they are free, oldest debt first.
+	///
+	/// The lock of `who` must be updated afterwards.
+	fn settle_grant_debts(who: &T::AccountId) {
+		let Some(mut debts) = GrantDebts::<T>::get(who) else { return };
+
+		// Release the funds owed, so that only the vesting funds stay locked while paying.
+		let now = T::BlockNumberProvider::current_block_number();
+		let vesting_locked = Vesting::<T>::get(who)
+			.unwrap_or_default()
+			.iter()
+			.chain(Grants::<T>::get(who).unwrap_or_default().iter().map(|grant| &grant.schedule))
+			.fold(Zero::zero(), |total: BalanceOf<T>, schedule| {
+				schedule.locked_at::<T::BlockNumberToBalance>(now).saturating_add(total)
+			});
+		if vesting_locked.is_zero() {
+			T::Currency::remove_lock(VESTING_ID, who);
+		} else {
+			let reasons = WithdrawReasons::except(T::UnvestedFundsAllowedWithdrawReasons::get());
+			T::Currency::set_lock(VESTING_ID, who, vesting_locked, reasons);
+		}
+
+		let mut available = Self::usable_balance(who)
+			.min(T::Currency::free_balance(who).saturating_sub(T::Currency::minimum_balance()));
+		for debt in debts.iter_mut() {
+			let amount = debt.owed.min(available);
+			if amount.is_zero() {
+				break
+			}
+			if T::Currency::transfer(who, &debt.treasury, amount, ExistenceRequirement::KeepAlive)
+				.is_ok()
+			{
+				available = available.saturating_sub(amount);
+				debt.owed = debt.owed.saturating_sub(amount);
+				Self::deposit_event(Event::<T>::GrantDebtPaid {
+					account: who.clone(),
+					grant_id: debt.grant_id,
+					amount,
+				});
+			}
+		}
+
+		debts.retain(|debt| !debt.owed.is_zero());
+		if debts.is_empty() {
+			GrantDebts::<T>::remove(who);
+		} else {
+			GrantDebts::<T>::insert(who, debts);
+		}
+	}
+
+	/// The free balance of `who` which is not frozen by any lock.
+	///
+	/// The locks of other pallets can't be inspected through `LockableCurrency`, so the smallest
+	/// balance which may be kept after a withdrawal is searched with `ensure_can_withdraw`.
+	fn usable_balance(who: &T::AccountId) -> BalanceOf<T> {
+		let free = T::Currency::free_balance(who);
+		let can_keep = |balance| {
+			T::Currency::ensure_can_withdraw(who, One::one(), WithdrawReasons::TRANSFER, balance)
+				.is_ok()
+		};
+		if !can_keep(free) {
+			return Zero::zero()
+		}
+		let (mut low, mut high) = (Zero::zero(), free);
+		while low < high {
+			let mid = low.saturating_add(high.saturating_sub(low) / 2u32.into());
+			if can_keep(mid) {
+				high = mid;
+			} else {
+				low = mid.saturating_add(One::one());
+			}
+		}
+		free.saturating_sub(high)
+	}
+
+	/// The weight of `settle_grant_debts`, on top of updating the lock.
+	pub(crate) fn settle_grant_debts_weight() -> Weight {
+		let debts = T::MAX_VESTING_SCHEDULES as u64;
+		// The debts, schedules and grants of the account, and the treasury of each debt.
+		T::DbWeight::get().reads_writes(3 + debts, 1 + debts)
+	}
+
+	/// Unlock any vested funds of `who` and pay its debts for revoked grants.
 	fn do_vest(who: T::AccountId) -> DispatchResult {
-		let schedules = Vesting::<T>::get(&who).ok_or(Error::<T>::NotVesting)?;
+		ensure!(
+			Vesting::<T>::contains_key(&who) ||
+				Grants::<T>::contains_key(&who) ||
+				GrantDebts::<T>::contains_key(&who),
+			Error::<T>::NotVesting
+		);
+		Self::settle_grant_debts(&who);
+		Self::update_lock(&who)
+	}
+
+	/// Remove the completed schedules of `who` and update its lock accordingly.
+	fn update_lock(who: &T::AccountId) -> DispatchResult {
+		let schedules = Vesting::<T>::get(who).unwrap_or_default();
 
 		let (schedules, locked_now) =
 			Self::exec_action(schedules.to_vec(), VestingAction::Passive)?;
 
-		Self::write_vesting(&who, schedules)?;
-		Self::write_lock(&who, locked_now);
+		Self::write_vesting(who, schedules)?;
+		Self::write_lock(who, locked_now);
 
 		Ok(())
 	}
@@ -718,15 +1050,22 @@ where
 
 	/// Get the amount that is currently being vested and cannot be transferred out of this account.
 	fn vesting_balance(who: &T::AccountId) -> Option<BalanceOf<T>> {
-		if let Some(v) = Vesting::<T>::get(who) {
-			let now = T::BlockNumberProvider::current_block_number();
-			let total_locked_now = v.iter().fold(Zero::zero(), |total, schedule| {
+		let schedules = Vesting::<T>::get(who);
+		let grants = Grants::<T>::get(who);
+		let debt = Self::grant_debt(who);
+		if schedules.is_none() && grants.is_none() && debt.is_zero() {
+			return None
+		}
+
+		let now = T::BlockNumberProvider::current_block_number();
+		let total_locked_now = schedules
+			.unwrap_or_default()
+			.iter()
+			.chain(grants.unwrap_or_default().iter().map(|grant| &grant.schedule))
+			.fold(debt, |total: BalanceOf<T>, schedule| {
 				schedule.locked_at::<T::BlockNumberToBalance>(now).saturating_add(total)
 			});
-			Some(T::Currency::free_balance(who).min(total_locked_now))
-		} else {
-			None
-		}
+		Some(T::Currency::free_balance(who).min(total_locked_now))
 	}
 
 	/// Adds a vesting schedule to a given account.
@@ -1262,3 +1262,168 @@ fn vested_transfer_impl_works() {
 		);
 	});
 }
+
+#[test]
+fn revocable_grant_can_be_revoked() {
+	ExtBuilder::default().existential_deposit(ED).build().execute_with(|| {
+		let schedule = VestingInfo::new(ED * 10, ED, 10);
+		assert_ok!(Vesting::revocable_vested_transfer(Some(3).into(), 12, schedule, 13, 20));
+		System::assert_last_event(
+			Event::<Test>::GrantCreated { account: 12, grant_id: 0, revoker: 13 }.into(),
+		);
+		let user12_vesting_schedule = VestingInfo::new(ED * 5, 64, 10);
+		assert_eq!(VestingStorage::<Test>::get(&12).unwrap(), vec![user12_vesting_schedule]);
+		assert_eq!(Grants::<Test>::get(&12).unwrap()[0].schedule, schedule);
+		assert_eq!(Balances::free_balance(&12), ED * 20);
+		// The grant is locked along with the other schedules.
+		assert_eq!(Vesting::vesting_balance(&12), Some(ED * 15));
+		assert_eq!(Balances::usable_balance(&12), ED * 5);
+
+		System::set_block_number(15);
+		assert_noop!(Vesting::revoke_grant(Some(3).into(), 12, 0), Error::<Test>::NotRevoker);
+		assert_noop!(Vesting::revoke_grant(Some(13).into(), 12, 1), Error::<Test>::GrantNotFound);
+		assert_noop!(Vesting::revoke_grant(Some(13).into(), 4, 0), Error::<Test>::GrantNotFound);
+
+		assert_ok!(Vesting::revoke_grant(Some(13).into(), 12, 0));
+		System::assert_last_event(
+			Event::<Test>::GrantRevoked {
+				account: 12,
+				grant_id: 0,
+				clawed_back: ED * 5,
+				shortfall: 0,
+			}
+			.into(),
+		);
+		assert!(!Grants::<Test>::contains_key(&12));
+		// The vested half of the grant stays with the beneficiary.
+		assert_eq!(Balances::free_balance(&12), ED * 15);
+		assert_eq!(Balances::free_balance(&20), ED * 5);
+		assert_eq!(Vesting::vesting_balance(&12), Some(64 * 15));
+		assert_eq!(Balances::usable_balance(&12), ED * 15 - 64 * 15);
+	});
+}
+
+#[test]
+fn revoking_grant_respects_other_locks() {
+	ExtBuilder::default().existential_deposit(ED).build().execute_with(|| {
+		let schedule = VestingInfo::new(ED * 10, ED, 10);
+		assert_ok!(Vesting::revocable_vested_transfer(Some(3).into(), 4, schedule, 13, 20));
+		Balances::set_lock(*b"staking ", &4, u64::MAX, WithdrawReasons::all());
+
+		// The bonded funds are not taken, but kept locked as a debt.
+		assert_ok!(Vesting::revoke_grant(Some(13).into(), 4, 0));
+		System::assert_last_event(
+			Event::<Test>::GrantRevoked {
+				account: 4,
+				grant_id: 0,
+				clawed_back: 0,
+				shortfall: ED * 10,
+			}
+			.into(),
+		);
+		assert_eq!(Balances::free_balance(&4), ED * 50);
+		assert_eq!(Balances::free_balance(&20), 0);
+		assert_eq!(Vesting::vesting_balance(&4), Some(ED * 10));
+		assert_eq!(GrantDebts::<Test>::get(&4).unwrap()[0].owed, ED * 10);
+
+		// The debt is kept while the funds are bonded.
+		System::set_block_number(15);
+		assert_ok!(Vesting::vest_other(Some(3).into(), 4));
+		assert_eq!(Vesting::vesting_balance(&4), Some(ED * 10));
+
+		// Once unbonded, the debt is paid and the funds stay locked until then.
+		Balances::remove_lock(*b"staking ", &4);
+		assert_eq!(Balances::usable_balance(&4), ED * 40);
+		assert_ok!(Vesting::vest_other(Some(3).into(), 4));
+		System::assert_has_event(
+			Event::<Test>::GrantDebtPaid { account: 4, grant_id: 0, amount: ED * 10 }.into(),
+		);
+		assert!(!GrantDebts::<Test>::contains_key(&4));
+		assert_eq!(Balances::free_balance(&4), ED * 40);
+		assert_eq!(Balances::free_balance(&20), ED * 10);
+		assert_eq!(Vesting::vesting_balance(&4), None);
+		assert_noop!(Vesting::vest(Some(4).into()), Error::<Test>::NotVesting);
+	});
+}
+
+#[test]
+fn revoking_grant_takes_funds_not_locked_otherwise() {
+	ExtBuilder::default().existential_deposit(ED).build().execute_with(|| {
+		let schedule = VestingInfo::new(ED * 10, ED, 10);
+		assert_ok!(Vesting::revocable_vested_transfer(Some(3).into(), 4, schedule, 13, 20));
+		// Only part of the unvested funds are bonded.
+		Balances::set_lock(*b"staking ", &4, ED * 45, WithdrawReasons::all());
+
+		assert_ok!(Vesting::revoke_grant(Some(13).into(), 4, 0));
+		System::assert_has_event(
+			Event::<Test>::GrantDebtPaid { account: 4, grant_id: 0, amount: ED * 5 }.into(),
+		);
+		System::assert_last_event(
+			Event::<Test>::GrantRevoked {
+				account: 4,
+				grant_id: 0,
+				clawed_back: ED * 5,
+				shortfall: ED * 5,
+			}
+			.into(),
+		);
+		assert_eq!(Balances::free_balance(&4), ED * 45);
+		assert_eq!(Balances::free_balance(&20), ED * 5);
+		assert_eq!(GrantDebts::<Test>::get(&4).unwrap()[0].owed, ED * 5);
+
+		// The rest is paid once unbonded.
+		Balances::set_lock(*b"staking ", &4, ED * 30, WithdrawReasons::all());
+		assert_ok!(Vesting::vest_other(Some(3).into(), 4));
+		assert!(!GrantDebts::<Test>::contains_key(&4));
+		assert_eq!(Balances::free_balance(&4), ED * 40);
+		assert_eq!(Balances::free_balance(&20), ED * 10);
+		assert_eq!(Vesting::vesting_balance(&4), None);
+	});
+}
+
+#[test]
+fn revocable_grant_checks_and_completion() {
+	ExtBuilder::default().existential_deposit(ED).build().execute_with(|| {
+		assert_noop!(
+			Vesting::revocable_vested_transfer(
+				Some(3).into(),
+				4,
+				VestingInfo::new(ED, ED, 10),
+				13,
+				20
+			),
+			Error::<Test>::AmountLow
+		);
+		assert_noop!(
+			Vesting::revocable_vested_transfer(
+				Some(3).into(),
+				4,
+				VestingInfo::new(ED * 10, 0, 10),
+				13,
+				20
+			),
+			Error::<Test>::InvalidScheduleParams
+		);
+
+		let schedule = VestingInfo::new(ED * 2, ED, 10);
+		for grant_id in 0..3 {
+			assert_ok!(Vesting::revocable_vested_transfer(Some(3).into(), 4, schedule, 13, 20));
+			System::assert_has_event(
+				Event::<Test>::GrantCreated { account: 4, grant_id, revoker: 13 }.into(),
+			);
+		}
+		assert_noop!(
+			Vesting::revocable_vested_transfer(Some(3).into(), 4, schedule, 13, 20),
+			Error::<Test>::AtMaxVestingSchedules
+		);
+		assert_eq!(Vesting::vesting_balance(&4), Some(ED * 6));
+
+		// Fully vested grants are removed and can no longer be revoked.
+		System::set_block_number(12);
+		assert_ok!(Vesting::vest(Some(4).into()));
+		System::assert_last_event(Event::<Test>::VestingCompleted { account: 4 }.into());
+		assert!(!Grants::<Test>::contains_key(&4));
+		assert_eq!(Vesting::vesting_balance(&4), None);
+		assert_noop!(Vesting::revoke_grant(Some(13).into(), 4, 0), Error::<Test>::GrantNotFound);
+	});
+}
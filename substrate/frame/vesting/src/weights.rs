@@ -81,6 +81,8 @@ pub trait WeightInfo {
 	fn not_unlocking_merge_schedules(l: u32, s: u32, ) -> Weight;
 	fn unlocking_merge_schedules(l: u32, s: u32, ) -> Weight;
 	fn force_remove_vesting_schedule(l: u32, s: u32, ) -> Weight;
+	fn revocable_vested_transfer(l: u32, s: u32, ) -> Weight;
+	fn revoke_grant(l: u32, s: u32, ) -> Weight;
 }
 
 /// Weights for `pallet_vesting` using the Substrate node and recommended hardware.
@@ -285,6 +287,55 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(4_u64))
 			.saturating_add(T::DbWeight::get().writes(3_u64))
 	}
+	/// Storage: `Vesting::Grants` (r:1 w:1)
+	/// Proof: `Vesting::Grants` (`max_values`: None, `max_size`: Some(2961), added: 5436, mode: `MaxEncodedLen`)
+	/// Storage: `Vesting::GrantDebts` (r:1 w:0)
+	/// Proof: `Vesting::GrantDebts` (`max_values`: None, `max_size`: Some(1505), added: 3980, mode: `MaxEncodedLen`)
+	/// Storage: `Vesting::NextGrantId` (r:1 w:1)
+	/// Proof: `Vesting::NextGrantId` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Vesting::Vesting` (r:1 w:0)
+	/// Proof: `Vesting::Vesting` (`max_values`: None, `max_size`: Some(1057), added: 3532, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Locks` (r:1 w:1)
+	/// Proof: `Balances::Locks` (`max_values`: None, `max_size`: Some(1299), added: 3774, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Freezes` (r:1 w:0)
+	/// Proof: `Balances::Freezes` (`max_values`: None, `max_size`: Some(85), added: 2560, mode: `MaxEncodedLen`)
+	/// The range of component `l` is `[0, 49]`.
+	/// The range of component `s` is `[0, 27]`.
+	fn revocable_vested_transfer(l: u32, s: u32, ) -> Weight {
+		// Estimated, not measured: to be regenerated with the benchmark CLI.
+		Weight::from_parts(74_318_402, 6426)
+			.saturating_add(Weight::from_parts(48_357, 0).saturating_mul(l.into()))
+			.saturating_add(Weight::from_parts(143_270, 0).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: `Vesting::Grants` (r:1 w:1)
+	/// Proof: `Vesting::Grants` (`max_values`: None, `max_size`: Some(2961), added: 5436, mode: `MaxEncodedLen`)
+	/// Storage: `Vesting::GrantDebts` (r:1 w:1)
+	/// Proof: `Vesting::GrantDebts` (`max_values`: None, `max_size`: Some(1505), added: 3980, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:29 w:29)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Vesting::Vesting` (r:1 w:0)
+	/// Proof: `Vesting::Vesting` (`max_values`: None, `max_size`: Some(1057), added: 3532, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Locks` (r:1 w:1)
+	/// Proof: `Balances::Locks` (`max_values`: None, `max_size`: Some(1299), added: 3774, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Freezes` (r:1 w:0)
+	/// Proof: `Balances::Freezes` (`max_values`: None, `max_size`: Some(85), added: 2560, mode: `MaxEncodedLen`)
+	/// The range of component `l` is `[0, 49]`.
+	/// The range of component `s` is `[1, 28]`.
+	fn revoke_grant(l: u32, s: u32, ) -> Weight {
+		// Estimated, not measured: to be regenerated with the benchmark CLI.
+		Weight::from_parts(41_276_530, 6426)
+			.saturating_add(Weight::from_parts(40_786, 0).saturating_mul(l.into()))
+			.saturating_add(Weight::from_parts(47_913_644, 0).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(s.into())))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(s.into())))
+			.saturating_add(Weight::from_parts(0, 2603).saturating_mul(s.into()))
+	}
 }
 
 // For backwards compatibility and tests.
@@ -488,4 +539,53 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(4_u64))
 			.saturating_add(RocksDbWeight::get().writes(3_u64))
 	}
+	/// Storage: `Vesting::Grants` (r:1 w:1)
+	/// Proof: `Vesting::Grants` (`max_values`: None, `max_size`: Some(2961), added: 5436, mode: `MaxEncodedLen`)
+	/// Storage: `Vesting::GrantDebts` (r:1 w:0)
+	/// Proof: `Vesting::GrantDebts` (`max_values`: None, `max_size`: Some(1505), added: 3980, mode: `MaxEncodedLen`)
+	/// Storage: `Vesting::NextGrantId` (r:1 w:1)
+	/// Proof: `Vesting::NextGrantId` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:1)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Vesting::Vesting` (r:1 w:0)
+	/// Proof: `Vesting::Vesting` (`max_values`: None, `max_size`: Some(1057), added: 3532, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Locks` (r:1 w:1)
+	/// Proof: `Balances::Locks` (`max_values`: None, `max_size`: Some(1299), added: 3774, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Freezes` (r:1 w:0)
+	/// Proof: `Balances::Freezes` (`max_values`: None, `max_size`: Some(85), added: 2560, mode: `MaxEncodedLen`)
+	/// The range of component `l` is `[0, 49]`.
+	/// The range of component `s` is `[0, 27]`.
+	fn revocable_vested_transfer(l: u32, s: u32, ) -> Weight {
+		// Estimated, not measured: to be regenerated with the benchmark CLI.
+		Weight::from_parts(74_318_402, 6426)
+			.saturating_add(Weight::from_parts(48_357, 0).saturating_mul(l.into()))
+			.saturating_add(Weight::from_parts(143_270, 0).saturating_mul(s.into()))
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: `Vesting::Grants` (r:1 w:1)
+	/// Proof: `Vesting::Grants` (`max_values`: None, `max_size`: Some(2961), added: 5436, mode: `MaxEncodedLen`)
+	/// Storage: `Vesting::GrantDebts` (r:1 w:1)
+	/// Proof: `Vesting::GrantDebts` (`max_values`: None, `max_size`: Some(1505), added: 3980, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:29 w:29)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Vesting::Vesting` (r:1 w:0)
+	/// Proof: `Vesting::Vesting` (`max_values`: None, `max_size`: Some(1057), added: 3532, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Locks` (r:1 w:1)
+	/// Proof: `Balances::Locks` (`max_values`: None, `max_size`: Some(1299), added: 3774, mode: `MaxEncodedLen`)
+	/// Storage: `Balances::Freezes` (r:1 w:0)
+	/// Proof: `Balances::Freezes` (`max_values`: None, `max_size`: Some(85), added: 2560, mode: `MaxEncodedLen`)
+	/// The range of component `l` is `[0, 49]`.
+	/// The range of component `s` is `[1, 28]`.
+	fn revoke_grant(l: u32, s: u32, ) -> Weight {
+		// Estimated, not measured: to be regenerated with the benchmark CLI.
+		Weight::from_parts(41_276_530, 6426)
+			.saturating_add(Weight::from_parts(40_786, 0).saturating_mul(l.into()))
+			.saturating_add(Weight::from_parts(47_913_644, 0).saturating_mul(s.into()))
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(s.into())))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(s.into())))
+			.saturating_add(Weight::from_parts(0, 2603).saturating_mul(s.into()))
+	}
 }
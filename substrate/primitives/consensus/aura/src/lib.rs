@@ -93,5 +93,13 @@ sp_api::decl_runtime_apis! {
 
 		/// Return the current set of authorities.
 		fn authorities() -> Vec<AuthorityId>;
+
+		/// Returns the maximum number of blocks the author of a slot may build within it.
+		///
+		/// The slot is split into as many sub-slots of equal duration, each of them holding at
+		/// most one block. The runtime must accept blocks of the same slot and timestamps which
+		/// are one sub-slot apart.
+		#[api_version(2)]
+		fn blocks_per_slot() -> u32;
 	}
 }
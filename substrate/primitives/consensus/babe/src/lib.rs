@@ -36,7 +36,7 @@ use sp_runtime::{traits::Header, ConsensusEngineId, RuntimeDebug};
 use crate::digests::{NextConfigDescriptor, NextEpochDescriptor};
 
 pub use sp_core::sr25519::vrf::{
-	VrfInput, VrfPreOutput, VrfProof, VrfSignData, VrfSignature, VrfTranscript,
+	VrfInput, VrfPreOutput, VrfProof, VrfSignData, VrfSignature, VrfTranscript, VrfTranscriptData,
 };
 
 /// Key type for BABE module.
@@ -101,7 +101,16 @@ pub type BabeBlockWeight = u32;
 
 /// Make VRF input suitable for BABE's randomness generation.
 pub fn make_vrf_transcript(randomness: &Randomness, slot: Slot, epoch: u64) -> VrfInput {
-	VrfInput::new(
+	make_vrf_transcript_data(randomness, slot, epoch).transcript()
+}
+
+/// Make the data of the VRF transcript used for BABE's protocol.
+pub fn make_vrf_transcript_data(
+	randomness: &Randomness,
+	slot: Slot,
+	epoch: u64,
+) -> VrfTranscriptData {
+	VrfTranscriptData::new(
 		&BABE_ENGINE_ID,
 		&[
 			(b"slot number", &slot.to_le_bytes()),
@@ -112,8 +121,10 @@ pub fn make_vrf_transcript(randomness: &Randomness, slot: Slot, epoch: u64) -> V
 }
 
 /// Make VRF signing data suitable for BABE's protocol.
+///
+/// The data the transcript is built from is kept, so that it can be signed by a remote signer.
 pub fn make_vrf_sign_data(randomness: &Randomness, slot: Slot, epoch: u64) -> VrfSignData {
-	make_vrf_transcript(randomness, slot, epoch).into()
+	make_vrf_transcript_data(randomness, slot, epoch).into()
 }
 
 /// An consensus log item for BABE.
@@ -208,6 +208,25 @@ pub mod vrf {
 		}
 	}
 
+	impl Encode for VrfInput {
+		fn encode(&self) -> Vec<u8> {
+			let mut bytes = [0; PREOUT_SERIALIZED_SIZE];
+			self.0
+				.serialize_compressed(bytes.as_mut_slice())
+				.expect("serialization length is constant and checked by test; qed");
+			bytes.encode()
+		}
+	}
+
+	impl Decode for VrfInput {
+		fn decode<R: codec::Input>(i: &mut R) -> Result<Self, codec::Error> {
+			let buf = <[u8; PREOUT_SERIALIZED_SIZE]>::decode(i)?;
+			let input = bandersnatch::Input::deserialize_compressed_unchecked(buf.as_slice())
+				.map_err(|_| "vrf-input decode error: bad input")?;
+			Ok(VrfInput(input))
+		}
+	}
+
 	/// VRF pre-output derived from [`VrfInput`] using a [`VrfSecret`].
 	///
 	/// This object is hashed to produce the actual VRF output.
@@ -631,6 +650,7 @@ mod tests {
 		assert_eq!(public.compressed_size(), PUBLIC_SERIALIZED_SIZE);
 
 		let input = Input::new(b"foo").unwrap();
+		assert_eq!(input.compressed_size(), PREOUT_SERIALIZED_SIZE);
 		let preout = secret.output(input);
 		assert_eq!(preout.compressed_size(), PREOUT_SERIALIZED_SIZE);
 
@@ -737,6 +757,18 @@ mod tests {
 		assert_eq!(expected, decoded);
 	}
 
+	#[test]
+	fn vrf_input_encode_decode() {
+		let pair = Pair::from_seed(TEST_SEED);
+
+		let input = VrfInput::new(b"data");
+		let bytes = input.encode();
+		assert_eq!(bytes.len(), PREOUT_SERIALIZED_SIZE);
+
+		let decoded = VrfInput::decode(&mut bytes.as_slice()).unwrap();
+		assert_eq!(pair.vrf_pre_output(&input), pair.vrf_pre_output(&decoded));
+	}
+
 	#[test]
 	fn ring_vrf_sign_verify() {
 		let ring_ctx = TestRingContext::new_testing();
@@ -330,6 +330,40 @@ pub mod vrf {
 		}
 	}
 
+	/// The label and the items a [`VrfTranscript`] is built from.
+	///
+	/// A [`VrfTranscript`] can't be inspected once built. Keeping the data it is built from allows
+	/// to rebuild it elsewhere, e.g. by a signer which isn't running in the same process.
+	#[derive(Clone, Debug, PartialEq, Eq)]
+	pub struct VrfTranscriptData {
+		label: &'static [u8],
+		items: Vec<(&'static [u8], Vec<u8>)>,
+	}
+
+	impl VrfTranscriptData {
+		/// Build a new instance, see [`VrfTranscript::new`].
+		pub fn new(label: &'static [u8], data: &[(&'static [u8], &[u8])]) -> Self {
+			let items = data.iter().map(|(l, b)| (*l, b.to_vec())).collect();
+			Self { label, items }
+		}
+
+		/// The label of the transcript.
+		pub fn label(&self) -> &'static [u8] {
+			self.label
+		}
+
+		/// The `(domain, message)` items of the transcript.
+		pub fn items(&self) -> impl Iterator<Item = (&'static [u8], &[u8])> {
+			self.items.iter().map(|(l, b)| (*l, &b[..]))
+		}
+
+		/// Build the transcript.
+		pub fn transcript(&self) -> VrfTranscript {
+			let items = self.items().collect::<Vec<_>>();
+			VrfTranscript::new(self.label, &items)
+		}
+	}
+
 	/// VRF input.
 	///
 	/// Technically a transcript used by the Fiat-Shamir transform.
@@ -342,11 +376,19 @@ pub mod vrf {
 		pub(super) transcript: VrfTranscript,
 		/// Extra transcript data to be signed by the VRF.
 		pub(super) extra: Option<VrfTranscript>,
+		/// The data `transcript` and `extra` have been built from, if known.
+		data: Option<(VrfTranscriptData, Option<VrfTranscriptData>)>,
 	}
 
 	impl From<VrfInput> for VrfSignData {
 		fn from(transcript: VrfInput) -> Self {
-			VrfSignData { transcript, extra: None }
+			VrfSignData { transcript, extra: None, data: None }
+		}
+	}
+
+	impl From<VrfTranscriptData> for VrfSignData {
+		fn from(data: VrfTranscriptData) -> Self {
+			VrfSignData { transcript: data.transcript(), extra: None, data: Some((data, None)) }
 		}
 	}
 
@@ -370,8 +412,26 @@ pub mod vrf {
 		/// `extra` will not contribute to the VRF output bytes.
 		pub fn with_extra(mut self, extra: VrfTranscript) -> Self {
 			self.extra = Some(extra);
+			self.data = None;
 			self
 		}
+
+		/// Add some extra data to be signed, keeping the data the transcript is built from.
+		///
+		/// `extra` will not contribute to the VRF output bytes.
+		pub fn with_extra_data(mut self, extra: VrfTranscriptData) -> Self {
+			self.extra = Some(extra.transcript());
+			self.data = self.data.map(|(data, _)| (data, Some(extra)));
+			self
+		}
+
+		/// The data the input and the extra data have been built from.
+		///
+		/// Only known if the instance has been built from [`VrfTranscriptData`], and the extra
+		/// data, if any, has been added with [`Self::with_extra_data`].
+		pub fn transcript_data(&self) -> Option<(&VrfTranscriptData, Option<&VrfTranscriptData>)> {
+			self.data.as_ref().map(|(data, extra)| (data, extra.as_ref()))
+		}
 	}
 
 	/// VRF signature data
@@ -860,6 +920,25 @@ mod tests {
 		assert!(public.vrf_verify(&data, &signature));
 	}
 
+	#[test]
+	fn vrf_sign_data_from_transcript_data() {
+		let pair = Pair::from_seed(b"12345678901234567890123456789012");
+		let public = pair.public();
+
+		let input = VrfTranscriptData::new(b"label", &[(b"domain1", b"data1")]);
+		let extra = VrfTranscriptData::new(b"extra", &[(b"domain2", b"data2")]);
+		let data = VrfSignData::from(input.clone()).with_extra_data(extra.clone());
+		assert_eq!(data.transcript_data(), Some((&input, Some(&extra))));
+
+		// Signatures are interchangeable with the ones of the transcripts built directly.
+		let direct = VrfTranscript::new(b"label", &[(b"domain1", b"data1")])
+			.into_sign_data()
+			.with_extra(VrfTranscript::new(b"extra", &[(b"domain2", b"data2")]));
+		assert!(direct.transcript_data().is_none());
+		assert!(public.vrf_verify(&direct, &pair.vrf_sign(&data)));
+		assert!(public.vrf_verify(&data, &pair.vrf_sign(&direct)));
+	}
+
 	#[test]
 	fn vrf_make_bytes_matches() {
 		let pair = Pair::from_seed(b"12345678901234567890123456789012");
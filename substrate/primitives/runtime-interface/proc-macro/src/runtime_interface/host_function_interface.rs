@@ -407,7 +407,7 @@ fn generate_host_function_implementation(
 			|mut caller: #crate_::sp_wasm_interface::wasmtime::Caller<T::State>, #(#ffi_args_prototype),*|
 				-> ::core::result::Result<#ffi_return_ty, #crate_::sp_wasm_interface::anyhow::Error>
 			{
-				T::with_function_context(caller, move |__function_context__| {
+				T::with_named_function_context(caller, #name, move |__function_context__| {
 					let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
 						#struct_name::call(
 							__function_context__,
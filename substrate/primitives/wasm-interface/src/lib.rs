@@ -353,6 +353,16 @@ if_wasmtime_is_enabled! {
 			callback: impl FnOnce(&mut dyn FunctionContext) -> R,
 		) -> R;
 
+		/// Same as [`Self::with_function_context`], but also passes the name of the host
+		/// function being called, e.g. for profiling purposes.
+		fn with_named_function_context<R>(
+			caller: wasmtime::Caller<Self::State>,
+			_fn_name: &'static str,
+			callback: impl FnOnce(&mut dyn FunctionContext) -> R,
+		) -> R {
+			Self::with_function_context(caller, callback)
+		}
+
 		/// Registers a given host function with the WASM executor.
 		///
 		/// The function has to be statically callable, and all of its arguments
@@ -449,6 +459,14 @@ where
 					T::with_function_context(caller, callback)
 				}
 
+				fn with_named_function_context<R>(
+					caller: wasmtime::Caller<Self::State>,
+					fn_name: &'static str,
+					callback: impl FnOnce(&mut dyn FunctionContext) -> R,
+				) -> R {
+					T::with_named_function_context(caller, fn_name, callback)
+				}
+
 				fn register_static<Params, Results>(
 					&mut self,
 					fn_name: &str,
@@ -468,6 +468,11 @@ pub const TEST_RUNTIME_BABE_EPOCH_CONFIGURATION: BabeEpochConfiguration = BabeEp
 	allowed_slots: AllowedSlots::PrimaryAndSecondaryPlainSlots,
 };
 
+/// Storage key of the SCALE encoded `u32` returned by `AuraApi::blocks_per_slot`.
+///
+/// One block per slot is allowed if it isn't set, e.g. through the extra genesis storage.
+pub const AURA_BLOCKS_PER_SLOT: &[u8] = b":aura_blocks_per_slot";
+
 impl_runtime_apis! {
 	impl sp_api::Core<Block> for Runtime {
 		fn version() -> RuntimeVersion {
@@ -621,6 +626,7 @@ impl_runtime_apis! {
 		}
 	}
 
+	#[api_version(2)]
 	impl sp_consensus_aura::AuraApi<Block, AuraId> for Runtime {
 		fn slot_duration() -> sp_consensus_aura::SlotDuration {
 			sp_consensus_aura::SlotDuration::from_millis(1000)
@@ -629,6 +635,12 @@ impl_runtime_apis! {
 		fn authorities() -> Vec<AuraId> {
 			SubstrateTest::authorities().into_iter().map(|auth| AuraId::from(auth)).collect()
 		}
+
+		fn blocks_per_slot() -> u32 {
+			sp_io::storage::get(AURA_BLOCKS_PER_SLOT)
+				.and_then(|value| u32::decode(&mut &value[..]).ok())
+				.unwrap_or(1)
+		}
 	}
 
 	impl sp_consensus_babe::BabeApi<Block> for Runtime {
@@ -31,7 +31,7 @@ use sc_service::Configuration;
 use sc_sysinfo::{
 	benchmark_cpu, benchmark_cpu_parallelism, benchmark_disk_random_writes,
 	benchmark_disk_sequential_writes, benchmark_memory, benchmark_sr25519_verify, ExecutionLimit,
-	Metric, Requirement, Requirements, Throughput,
+	HwBench, HwBenchHistory, Metric, Requirement, Requirements, Throughput,
 };
 
 use crate::shared::check_build_profile;
@@ -45,6 +45,8 @@ pub use hardware::SUBSTRATE_REFERENCE_HARDWARE;
 /// which influence the results.
 ///
 /// You can use the `--base-path` flag to set a location for the disk benchmarks.
+/// The results are compared to the latest ones recorded in the hardware benchmark history of the
+/// base path, which also contains the results of the benchmarks run by the node at startup.
 #[derive(Debug, Parser)]
 pub struct MachineCmd {
 	#[allow(missing_docs)]
@@ -79,6 +81,10 @@ pub struct MachineCmd {
 	/// Time limit for each disk benchmark.
 	#[arg(long, default_value_t = 5.0, value_name = "SECONDS")]
 	pub disk_duration: f32,
+
+	/// Record the results in the hardware benchmark history of the base path.
+	#[arg(long)]
+	pub record: bool,
 }
 
 /// Helper for the result of a concrete benchmark.
@@ -117,13 +123,23 @@ impl MachineCmd {
 		let dir = cfg.database.path().ok_or("No DB directory provided")?;
 		fs::create_dir_all(dir)?;
 
+		let mut history = HwBenchHistory::open(cfg.base_path.path())?;
+		let previous = history.latest().map(|record| record.hwbench.clone());
+
 		info!("Running machine benchmarks...");
 		let mut results = Vec::new();
 		for requirement in &requirements.0 {
 			let result = self.run_benchmark(requirement, &dir)?;
 			results.push(result);
 		}
-		self.print_summary(requirements, results)
+
+		if self.record {
+			match to_hwbench(&requirements, &results) {
+				Some(hwbench) => history.push(hwbench)?,
+				None => warn!("Not recording the results, the CPU or memory benchmark is missing"),
+			}
+		}
+		self.print_summary(requirements, results, previous)
 	}
 
 	/// Benchmarks a specific metric of the hardware and judges the resulting score.
@@ -161,10 +177,19 @@ impl MachineCmd {
 	}
 
 	/// Prints a human-readable summary.
-	fn print_summary(&self, requirements: Requirements, results: Vec<BenchResult>) -> Result<()> {
+	fn print_summary(
+		&self,
+		requirements: Requirements,
+		results: Vec<BenchResult>,
+		previous: Option<HwBench>,
+	) -> Result<()> {
 		// Use a table for nicer console output.
 		let mut table = Table::new();
-		table.set_header(["Category", "Function", "Score", "Minimum", "Result"]);
+		let mut header = vec!["Category", "Function", "Score", "Minimum", "Result"];
+		if previous.is_some() {
+			header.push("Previous");
+		}
+		table.set_header(header);
 		// Count how many passed and how many failed.
 		let (mut passed, mut failed) = (0, 0);
 		for (requirement, result) in requirements.0.iter().zip(results.iter()) {
@@ -174,7 +199,11 @@ impl MachineCmd {
 				failed += 1
 			}
 
-			table.add_row(result.to_row(requirement));
+			let mut row = result.to_row(requirement);
+			if let Some(previous) = &previous {
+				row.add_cell(result.previous_cell(previous.score(&requirement.metric)).into());
+			}
+			table.add_row(row);
 		}
 		// Print the table and a summary.
 		info!(
@@ -232,6 +261,49 @@ impl BenchResult {
 		]
 		.into()
 	}
+
+	/// Format the score recorded on the previous run, and how [`Self`] compares to it.
+	fn previous_cell(&self, previous: Option<Throughput>) -> String {
+		match previous {
+			Some(previous) => format!(
+				"{} ({:+.1?} %)",
+				previous,
+				(self.score.as_bytes() / previous.as_bytes() - 1.0) * 100.0
+			),
+			None => "-".into(),
+		}
+	}
+}
+
+/// Builds the [`HwBench`] recorded in the history from the benchmark results.
+///
+/// Returns `None` if the CPU or memory benchmark is missing.
+fn to_hwbench(requirements: &Requirements, results: &[BenchResult]) -> Option<HwBench> {
+	let (mut cpu, mut parallel_cpu, mut memory, mut disk_seq, mut disk_rnd) =
+		(None, None, None, None, None);
+	for (requirement, result) in requirements.0.iter().zip(results) {
+		match requirement.metric {
+			Metric::Blake2256 => cpu = Some(result.score),
+			Metric::Blake2256Parallel { num_cores } =>
+				parallel_cpu = Some((result.score, num_cores)),
+			Metric::MemCopy => memory = Some(result.score),
+			Metric::DiskSeqWrite => disk_seq = Some(result.score),
+			Metric::DiskRndWrite => disk_rnd = Some(result.score),
+			Metric::Sr25519Verify => {},
+		}
+	}
+
+	let cpu_hashrate_score = cpu?;
+	let (parallel_cpu_hashrate_score, parallel_cpu_cores) =
+		parallel_cpu.unwrap_or((cpu_hashrate_score, 1));
+	Some(HwBench {
+		cpu_hashrate_score,
+		parallel_cpu_hashrate_score,
+		parallel_cpu_cores,
+		memory_memcpy_score: memory?,
+		disk_sequential_write_score: disk_seq,
+		disk_random_write_score: disk_rnd,
+	})
 }
 
 // Boilerplate
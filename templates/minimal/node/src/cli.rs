@@ -21,6 +21,12 @@ use polkadot_sdk::*;
 pub enum Consensus {
 	ManualSeal(u64),
 	InstantSeal,
+	/// Seal a block of at most `batch_size` transactions once that many are ready, or after
+	/// `idle_interval` milliseconds.
+	HybridSeal {
+		batch_size: usize,
+		idle_interval: u64,
+	},
 	None,
 }
 
@@ -32,6 +38,14 @@ impl std::str::FromStr for Consensus {
 			Consensus::InstantSeal
 		} else if let Some(block_time) = s.strip_prefix("manual-seal-") {
 			Consensus::ManualSeal(block_time.parse().map_err(|_| "invalid block time")?)
+		} else if let Some(params) = s.strip_prefix("hybrid-seal-") {
+			let (batch_size, idle_interval) = params
+				.split_once('-')
+				.ok_or("expected hybrid-seal-<batch size>-<idle interval>")?;
+			Consensus::HybridSeal {
+				batch_size: batch_size.parse().map_err(|_| "invalid batch size")?,
+				idle_interval: idle_interval.parse().map_err(|_| "invalid idle interval")?,
+			}
 		} else if s.to_lowercase() == "none" {
 			Consensus::None
 		} else {
@@ -160,6 +160,8 @@ pub fn new_full<Network: sc_network::NetworkBackend<Block, <Block as BlockT>::Ha
 				)),
 				network_provider: Arc::new(network.clone()),
 				enable_http_requests: true,
+				http_request_policy: config.offchain_worker.http_request_policy.clone(),
+				prometheus_registry: config.prometheus_registry().cloned(),
 				custom_extensions: |_| vec![],
 			})?;
 		task_manager.spawn_handle().spawn(
@@ -188,7 +190,7 @@ pub fn new_full<Network: sc_network::NetworkBackend<Block, <Block as BlockT>::Ha
 		task_manager: &mut task_manager,
 		transaction_pool: transaction_pool.clone(),
 		rpc_builder: rpc_extensions_builder,
-		backend,
+		backend: backend.clone(),
 		system_rpc_tx,
 		tx_handler_controller,
 		sync_service,
@@ -196,7 +198,7 @@ pub fn new_full<Network: sc_network::NetworkBackend<Block, <Block as BlockT>::Ha
 		telemetry: telemetry.as_mut(),
 	})?;
 
-	let proposer = sc_basic_authorship::ProposerFactory::new(
+	let mut proposer = sc_basic_authorship::ProposerFactory::new(
 		task_manager.spawn_handle(),
 		client.clone(),
 		transaction_pool.clone(),
@@ -226,6 +228,38 @@ pub fn new_full<Network: sc_network::NetworkBackend<Block, <Block as BlockT>::Ha
 				authorship_future,
 			);
 		},
+		Consensus::HybridSeal { batch_size, idle_interval } => {
+			proposer.set_max_transactions(Some(batch_size));
+			let commands_stream = sc_consensus_manual_seal::hybrid_seal_commands_stream(
+				sc_consensus_manual_seal::HybridSealParams {
+					pool: transaction_pool.clone(),
+					batch_size,
+					idle_interval: std::time::Duration::from_millis(idle_interval),
+					finalize: true,
+				},
+			);
+
+			let params = sc_consensus_manual_seal::ManualSealParams {
+				block_import: client.clone(),
+				env: proposer,
+				client,
+				pool: transaction_pool,
+				select_chain,
+				commands_stream,
+				consensus_data_provider: None,
+				create_inherent_data_providers: move |_, ()| async move {
+					Ok(sp_timestamp::InherentDataProvider::from_system_time())
+				},
+				revert_backend: Some(backend),
+			};
+			let authorship_future = sc_consensus_manual_seal::run_manual_seal(params);
+
+			task_manager.spawn_essential_handle().spawn_blocking(
+				"hybrid-seal",
+				None,
+				authorship_future,
+			);
+		},
 		Consensus::ManualSeal(block_time) => {
 			let (mut sink, commands_stream) = futures::channel::mpsc::channel(1024);
 			task_manager.spawn_handle().spawn("block_authoring", None, async move {
@@ -252,6 +286,7 @@ pub fn new_full<Network: sc_network::NetworkBackend<Block, <Block as BlockT>::Ha
 				create_inherent_data_providers: move |_, ()| async move {
 					Ok(sp_timestamp::InherentDataProvider::from_system_time())
 				},
+				revert_backend: Some(backend),
 			};
 			let authorship_future = sc_consensus_manual_seal::run_manual_seal(params);
 
@@ -306,6 +306,8 @@ pub async fn start_parachain_node(
 				network_provider: Arc::new(network.clone()),
 				is_validator: parachain_config.role.is_authority(),
 				enable_http_requests: false,
+				http_request_policy: parachain_config.offchain_worker.http_request_policy.clone(),
+				prometheus_registry: prometheus_registry.clone(),
 				custom_extensions: move |_| vec![],
 			})?;
 		task_manager.spawn_handle().spawn(
@@ -195,6 +195,8 @@ pub fn new_full<
 				)),
 				network_provider: Arc::new(network.clone()),
 				enable_http_requests: true,
+				http_request_policy: config.offchain_worker.http_request_policy.clone(),
+				prometheus_registry: config.prometheus_registry().cloned(),
 				custom_extensions: |_| vec![],
 			})?;
 		task_manager.spawn_handle().spawn(
@@ -268,6 +270,7 @@ pub fn new_full<
 				force_authoring,
 				backoff_authoring_blocks,
 				keystore: keystore_container.keystore(),
+				slashing_protection: Some(keystore_container.slashing_protection()),
 				sync_oracle: sync_service.clone(),
 				justification_sync_link: sync_service.clone(),
 				block_proposal_slot_portion: SlotProportion::new(2f32 / 3f32),
@@ -288,6 +291,8 @@ pub fn new_full<
 		// if the node isn't actively participating in consensus then it doesn't
 		// need a keystore, regardless of which protocol we use below.
 		let keystore = if role.is_authority() { Some(keystore_container.keystore()) } else { None };
+		let slashing_protection =
+			if role.is_authority() { Some(keystore_container.slashing_protection()) } else { None };
 
 		let grandpa_config = sc_consensus_grandpa::Config {
 			// FIXME #1578 make this available through chainspec
@@ -296,6 +301,7 @@ pub fn new_full<
 			name: Some(name),
 			observer_enabled: false,
 			keystore,
+			slashing_protection,
 			local_role: role,
 			telemetry: telemetry.as_ref().map(|x| x.handle()),
 			protocol_name: grandpa_protocol_name,
@@ -308,6 +308,10 @@ pub use pallet_asset_conversion;
 #[cfg(feature = "pallet-asset-conversion-ops")]
 pub use pallet_asset_conversion_ops;
 
+/// FRAME asset conversion pallet's limit and range orders.
+#[cfg(feature = "pallet-asset-conversion-orders")]
+pub use pallet_asset_conversion_orders;
+
 /// Pallet to manage transaction payments in assets by converting them to native assets.
 #[cfg(feature = "pallet-asset-conversion-tx-payment")]
 pub use pallet_asset_conversion_tx_payment;
@@ -340,6 +344,10 @@ pub use pallet_assets_holder;
 #[cfg(feature = "pallet-atomic-swap")]
 pub use pallet_atomic_swap;
 
+/// FRAME pallet for registrars to issue attestations about accounts.
+#[cfg(feature = "pallet-attestation")]
+pub use pallet_attestation;
+
 /// FRAME AURA consensus pallet.
 #[cfg(feature = "pallet-aura")]
 pub use pallet_aura;
@@ -1004,6 +1012,10 @@ pub use sc_allocator;
 #[cfg(feature = "sc-authority-discovery")]
 pub use sc_authority_discovery;
 
+/// RPC extensions for the authority discovery mechanism.
+#[cfg(feature = "sc-authority-discovery-rpc")]
+pub use sc_authority_discovery_rpc;
+
 /// Basic implementation of block-authoring logic.
 #[cfg(feature = "sc-basic-authorship")]
 pub use sc_basic_authorship;